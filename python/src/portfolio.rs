@@ -25,7 +25,7 @@ impl PyPortfolio {
     /// * `interval` - `str` - The interval of the data (2m, 5m, 15m, 30m, 1h, 1d, 1wk, 1mo, 3mo)
     /// * `confidence_level` - `float` - The confidence level for the VaR and ES calculations
     /// * `risk_free_rate` - `float` - The risk free rate to use in the calculations
-    /// * `objective_function` - `str` - The objective function to use in the optimization (max_sharpe, min_vol, max_return, nin_var, min_cvar, min_drawdown)
+    /// * `objective_function` - `str` - The objective function to use in the optimization (max_sharpe, min_vol, max_return, nin_var, min_cvar, min_drawdown, max_diversification, min_correlation, min_tracking_error, max_excess_return)
     /// * `constraints` - `list` - list of tuples with the lower and upper bounds for the weights
     ///
     /// # Returns
@@ -103,6 +103,10 @@ impl PyPortfolio {
                     ObjectiveFunction::MinDrawdown => "Minimize Drawdown",
                     ObjectiveFunction::MinVar => "Minimize Value at Risk",
                     ObjectiveFunction::MinCVaR => "Minimize Expected Shortfall",
+                    ObjectiveFunction::MaxDiversification => "Maximize Diversification Ratio",
+                    ObjectiveFunction::MinCorrelation => "Minimize Correlation",
+                    ObjectiveFunction::MinTrackingError => "Minimize Tracking Error",
+                    ObjectiveFunction::MaxExcessReturn => "Maximize Excess Return",
                 }).unwrap();
                 py_dict.set_item("optimization_method", self.portfolio.performance_stats.optimization_method.clone()).unwrap();
                 py_dict.set_item("constraints", self.portfolio.performance_stats.constraints.clone()).unwrap();
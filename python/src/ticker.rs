@@ -2,6 +2,7 @@ use polars::export::chrono;
 use tokio::task;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+use pyo3::exceptions::PyValueError;
 use finalytics::prelude::*;
 use crate::ffi::{rust_df_to_py_df, rust_plot_to_py_plot, rust_series_to_py_series};
 
@@ -150,6 +151,55 @@ impl PyTicker {
         })
     }
 
+    /// Get the dividends, splits and earnings events for the ticker for a given time period
+    ///
+    /// # Returns
+    ///
+    /// `DataFrame` - A Polars DataFrame containing the ticker events
+    pub fn get_events(&self) -> PyObject {
+        task::block_in_place(move || {
+            let events = tokio::runtime::Runtime::new().unwrap().block_on(
+                self.ticker.get_events()
+            ).unwrap();
+            let df = rust_df_to_py_df(&events).unwrap();
+            df
+        })
+    }
+
+    /// Get a machine learning dataset of the ticker with lagged returns, technical indicators,
+    /// calendar and sentiment features and a forward return target
+    ///
+    /// # Arguments
+    ///
+    /// * `lags` - `optional list[int]` - Lags of the period returns (default [1, 2, 3, 5, 10])
+    /// * `calendar` - `optional bool` - Include the calendar features (default True)
+    /// * `sentiment` - `optional bool` - Include the news sentiment features (default False)
+    /// * `horizon` - `optional int` - Number of periods of the forward return target (default 1)
+    /// * `drop_nulls` - `optional bool` - Drop the warm-up rows and the rows without a target (default True)
+    ///
+    /// # Returns
+    ///
+    /// `DataFrame` - A Polars DataFrame containing the features and targets
+    #[pyo3(signature = (lags=None, calendar=None, sentiment=None, horizon=None, drop_nulls=None))]
+    pub fn features(&self, lags: Option<Vec<usize>>, calendar: Option<bool>, sentiment: Option<bool>,
+                    horizon: Option<usize>, drop_nulls: Option<bool>) -> PyObject {
+        task::block_in_place(move || {
+            let mut spec = FeatureSpec::new();
+            if let Some(lags) = lags {
+                spec = spec.lags(lags);
+            }
+            let spec = spec.calendar(calendar.unwrap_or(true))
+                .sentiment(sentiment.unwrap_or(false))
+                .horizon(horizon.unwrap_or(1))
+                .drop_nulls(drop_nulls.unwrap_or(true));
+            let features = tokio::runtime::Runtime::new().unwrap().block_on(
+                self.ticker.features(spec)
+            ).unwrap();
+            let df = rust_df_to_py_df(&features).unwrap();
+            df
+        })
+    }
+
     /// Get the options chain for the ticker
     ///
     /// # Returns
@@ -413,18 +463,20 @@ impl PyTicker {
     ///
     /// # Arguments
     ///
-    /// * `report_type` - `optional str` - The type of report to display (performance, financials, options, news)
+    /// * `report_type` - `optional str` - The type of report to display (performance, financials, options, news, forecast)
     #[pyo3(signature = (report_type=None))]
-    pub fn report(&self, report_type: Option<String>) {
+    pub fn report(&self, report_type: Option<String>) -> PyResult<()> {
         task::block_in_place(move || {
             let report_type = match report_type {
                 Some(report_type) => ReportType::from_str(&report_type),
                 None => ReportType::Performance
             };
             let report = tokio::runtime::Runtime::new().unwrap().block_on(
-                self.ticker.report(Some(report_type))).unwrap();
+                self.ticker.report(Some(report_type)))
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
             report.show().unwrap();
-        });
+            Ok(())
+        })
     }
 
 }
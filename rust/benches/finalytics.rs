@@ -0,0 +1,97 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use polars::prelude::*;
+use serde_json::json;
+use finalytics::prelude::*;
+use finalytics::analytics::optimization::portfolio_optimization;
+use finalytics::analytics::statistics::{bootstrap_var_es, expected_shortfall, historical_mean_returns,
+                                        maximum_drawdown, value_at_risk, PerformanceStats};
+use finalytics::data::ticker::parse_chart;
+use finalytics::data::validation::validate_ohlcv;
+use finalytics::utils::synthetic::gbm_ohlcv;
+
+const PERIODS: usize = 2520;
+const SEED: u64 = 42;
+
+fn synthetic_chart() -> DataFrame {
+    gbm_ohlcv("2014-01-01", PERIODS, Interval::OneDay, 100.0, 0.08, 0.2, Some(SEED)).unwrap()
+}
+
+fn synthetic_returns(df: &DataFrame) -> Series {
+    let prices = df.column("adjclose").unwrap().f64().unwrap().into_no_null_iter().collect::<Vec<f64>>();
+    Series::new("returns", ReturnType::Simple.returns(&prices))
+}
+
+fn synthetic_portfolio_returns(assets: usize) -> DataFrame {
+    let columns = (0..assets).map(|i| {
+        let df = gbm_ohlcv("2014-01-01", PERIODS, Interval::OneDay, 100.0, 0.05 + 0.01 * i as f64,
+                           0.15 + 0.02 * i as f64, Some(SEED + i as u64)).unwrap();
+        synthetic_returns(&df).with_name(&format!("ASSET{}", i))
+    }).collect::<Vec<Series>>();
+    DataFrame::new(columns).unwrap()
+}
+
+/// Yahoo Finance chart response for the synthetic data
+fn chart_response(df: &DataFrame) -> serde_json::Value {
+    let timestamps = df.column("timestamp").unwrap().datetime().unwrap().into_no_null_iter()
+        .map(|x| x / 1000).collect::<Vec<i64>>();
+    let column = |name: &str| df.column(name).unwrap().f64().unwrap().into_no_null_iter().collect::<Vec<f64>>();
+    json!({
+        "chart": {"result": [{
+            "timestamp": timestamps,
+            "indicators": {
+                "quote": [{
+                    "open": column("open"),
+                    "high": column("high"),
+                    "low": column("low"),
+                    "close": column("close"),
+                    "volume": column("volume"),
+                }],
+                "adjclose": [{"adjclose": column("adjclose")}]
+            }
+        }]}
+    })
+}
+
+fn bench_data(c: &mut Criterion) {
+    let df = synthetic_chart();
+    let response = chart_response(&df);
+    c.bench_function("parse_chart", |b| b.iter(|| parse_chart("SYN", black_box(&response), Interval::OneDay).unwrap()));
+    c.bench_function("validate_ohlcv", |b| b.iter(|| validate_ohlcv("SYN", black_box(&df), Interval::OneDay, MarketCalendar::Equity).unwrap()));
+    c.bench_function("resample_weekly", |b| b.iter(|| black_box(&df).resample(Interval::OneWeek).unwrap()));
+}
+
+fn bench_statistics(c: &mut Criterion) {
+    let df = synthetic_chart();
+    let prices = df.column("adjclose").unwrap().f64().unwrap().into_no_null_iter().collect::<Vec<f64>>();
+    let returns = synthetic_returns(&df);
+    let benchmark = synthetic_returns(&gbm_ohlcv("2014-01-01", PERIODS, Interval::OneDay, 100.0, 0.06, 0.15, Some(SEED + 100)).unwrap());
+    c.bench_function("log_returns", |b| b.iter(|| ReturnType::Log.returns(black_box(&prices))));
+    c.bench_function("value_at_risk", |b| b.iter(|| value_at_risk(black_box(&returns), 0.95)));
+    c.bench_function("expected_shortfall", |b| b.iter(|| expected_shortfall(black_box(&returns), 0.95)));
+    c.bench_function("maximum_drawdown", |b| b.iter(|| maximum_drawdown(black_box(&returns))));
+    c.bench_function("bootstrap_var_es_1000", |b| b.iter(|| bootstrap_var_es(black_box(&returns), 0.95, 1000, Some(SEED))));
+    c.bench_function("performance_stats", |b| b.iter(|| PerformanceStats::compute_stats(
+        black_box(returns.clone()), benchmark.clone(), 0.02, 0.95, Interval::OneDay, MarketCalendar::Equity, ReturnType::Simple).unwrap()));
+}
+
+fn bench_optimization(c: &mut Criterion) {
+    let portfolio_returns = synthetic_portfolio_returns(10);
+    let mean_returns = historical_mean_returns(&portfolio_returns).unwrap();
+    let cov_matrix = CovarianceEstimator::Sample.estimate(&portfolio_returns).unwrap();
+    let settings = OptimizationSettings { seed: Some(SEED), ..OptimizationSettings::new(0.02, 0.95, vec![(0.0, 1.0); 10]) };
+    let mut group = c.benchmark_group("optimization");
+    group.sample_size(10);
+    for objective in [ObjectiveFunction::MaxSharpe, ObjectiveFunction::MinVol, ObjectiveFunction::MinCVaR] {
+        group.bench_function(format!("{:?}", objective), |b| b.iter(|| portfolio_optimization(
+            &mean_returns, &cov_matrix, &portfolio_returns, None, objective, &settings)));
+    }
+    group.finish();
+}
+
+fn bench_reports(c: &mut Criterion) {
+    let df = synthetic_chart();
+    c.bench_function("ohlcv_table_html", |b| b.iter(|| DataTable::new(black_box(df.clone()), TableType::OHLCV).to_html().unwrap()));
+}
+
+criterion_group!(benches, bench_data, bench_statistics, bench_optimization, bench_reports);
+criterion_main!(benches);
@@ -0,0 +1,303 @@
+use std::error::Error;
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+
+
+/// Dated cash flow into (positive) or out of (negative) a portfolio
+#[derive(Debug, Clone, PartialEq)]
+pub struct CashFlow {
+    pub date: NaiveDate,
+    pub amount: f64,
+}
+
+/// Initial investment and scheduled contributions/withdrawals of a portfolio
+#[derive(Debug, Clone, Default)]
+pub struct CashFlowSchedule {
+    pub initial_value: f64,
+    pub flows: Vec<CashFlow>,
+}
+
+impl CashFlowSchedule {
+    /// Creates a cash flow schedule with an initial investment
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_value` - Amount invested at the start of the period (e.g. 10000.0)
+    pub fn new(initial_value: f64) -> CashFlowSchedule {
+        CashFlowSchedule {
+            initial_value,
+            flows: Vec::new(),
+        }
+    }
+
+    /// Adds a single contribution (positive amount) or withdrawal (negative amount)
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date of the cash flow in YYYY-MM-DD format
+    /// * `amount` - Amount of the cash flow
+    pub fn cash_flow(mut self, date: &str, amount: f64) -> Result<CashFlowSchedule, Box<dyn Error>> {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")?;
+        self.flows.push(CashFlow { date, amount });
+        self.flows.sort_by_key(|x| x.date);
+        Ok(self)
+    }
+
+    /// Adds a recurring contribution (positive amount) or withdrawal (negative amount)
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - Date of the first cash flow in YYYY-MM-DD format
+    /// * `end_date` - Last date of the schedule in YYYY-MM-DD format (inclusive)
+    /// * `every_months` - Number of months between cash flows (e.g. 1 for monthly deposits)
+    /// * `amount` - Amount of each cash flow
+    pub fn recurring(mut self, start_date: &str, end_date: &str, every_months: u32, amount: f64) -> Result<CashFlowSchedule, Box<dyn Error>> {
+        if every_months == 0 {
+            return Err("Recurring cash flows must be at least one month apart".into());
+        }
+        let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")?;
+        let end = NaiveDate::parse_from_str(end_date, "%Y-%m-%d")?;
+        let mut n = 0;
+        while let Some(date) = start.checked_add_months(Months::new(every_months * n)) {
+            if date > end {
+                break;
+            }
+            self.flows.push(CashFlow { date, amount });
+            n += 1;
+        }
+        self.flows.sort_by_key(|x| x.date);
+        Ok(self)
+    }
+
+    /// Total amount contributed net of withdrawals, including the initial investment
+    pub fn net_invested(&self) -> f64 {
+        self.initial_value + self.flows.iter().map(|x| x.amount).sum::<f64>()
+    }
+
+    /// Computes the money-weighted return (XIRR) of a position built from the schedule
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - Date of the initial investment in YYYY-MM-DD format
+    /// * `valuation_date` - Date of the final valuation in YYYY-MM-DD format
+    /// * `final_value` - Value of the position on the valuation date
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Annualized money-weighted return in percent
+    pub fn xirr(&self, start_date: &str, valuation_date: &str, final_value: f64) -> Result<f64, Box<dyn Error>> {
+        let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")?;
+        let valuation = NaiveDate::parse_from_str(valuation_date, "%Y-%m-%d")?;
+        // Contributions are outflows and the final value an inflow from the investor's perspective
+        let mut flows = vec![CashFlow { date: start, amount: -self.initial_value }];
+        flows.extend(self.flows.iter()
+            .filter(|x| x.date <= valuation)
+            .map(|x| CashFlow { date: x.date, amount: -x.amount }));
+        flows.push(CashFlow { date: valuation, amount: final_value });
+        Ok(xirr(&flows)? * 100.0)
+    }
+}
+
+
+/// Simulates the value of a portfolio with scheduled cash flows
+///
+/// Cash flows dated on or before the first date are added to the initial investment, later flows are
+/// invested at the end of the first period dated on or after them and flows past the last date are ignored
+///
+/// # Arguments
+///
+/// * `dates` - Dates of the return periods
+/// * `returns` - Portfolio returns of each period in percent
+/// * `schedule` - Cash flow schedule
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns timestamp, return, cash_flow, net_invested and value
+pub fn simulate_cash_flows(dates: &[NaiveDateTime], returns: &[f64], schedule: &CashFlowSchedule) -> Result<DataFrame, Box<dyn Error>> {
+    if dates.is_empty() || dates.len() != returns.len() {
+        return Err("Dates and returns must be non-empty and of equal length".into());
+    }
+    let first_date = dates[0].date();
+    let mut pending = schedule.flows.iter().filter(|x| x.date > first_date).peekable();
+    let initial_flows: f64 = schedule.flows.iter().filter(|x| x.date <= first_date).map(|x| x.amount).sum();
+
+    let mut value = schedule.initial_value + initial_flows;
+    let mut net_invested = value;
+    let mut cash_flows = vec![value];
+    let mut net_invested_history = vec![net_invested];
+    let mut values = vec![value];
+    for t in 1..dates.len() {
+        let mut cash_flow = 0.0;
+        while let Some(flow) = pending.peek() {
+            if flow.date > dates[t].date() {
+                break;
+            }
+            cash_flow += flow.amount;
+            pending.next();
+        }
+        value = value * (1.0 + returns[t] / 100.0) + cash_flow;
+        net_invested += cash_flow;
+        cash_flows.push(cash_flow);
+        net_invested_history.push(net_invested);
+        values.push(value);
+    }
+
+    let df = df!(
+        "timestamp" => dates.to_vec(),
+        "return" => returns.to_vec(),
+        "cash_flow" => cash_flows,
+        "net_invested" => net_invested_history,
+        "value" => values
+    )?;
+    Ok(df)
+}
+
+/// Computes the internal rate of return of evenly spaced cash flows
+///
+/// # Arguments
+///
+/// * `cash_flows` - Cash flows of each period from the investor's perspective (investments negative, proceeds positive)
+///
+/// # Returns
+///
+/// * `f64` - Internal rate of return per period in decimal
+pub fn irr(cash_flows: &[f64]) -> Result<f64, Box<dyn Error>> {
+    let npv = |rate: f64| cash_flows.iter().enumerate()
+        .map(|(t, cf)| cf / (1.0 + rate).powi(t as i32))
+        .sum::<f64>();
+    if !cash_flows.iter().any(|x| *x > 0.0) || !cash_flows.iter().any(|x| *x < 0.0) {
+        return Err("The internal rate of return requires both positive and negative cash flows".into());
+    }
+    solve_rate(npv)
+}
+
+/// Computes the annualized internal rate of return of irregularly dated cash flows (XIRR, actual/365)
+///
+/// # Arguments
+///
+/// * `cash_flows` - Dated cash flows from the investor's perspective (investments negative, proceeds positive)
+///
+/// # Returns
+///
+/// * `f64` - Annualized internal rate of return in decimal
+pub fn xirr(cash_flows: &[CashFlow]) -> Result<f64, Box<dyn Error>> {
+    let first_date = cash_flows.iter().map(|x| x.date).min().ok_or("No cash flows")?;
+    let npv = |rate: f64| cash_flows.iter()
+        .map(|x| x.amount / (1.0 + rate).powf((x.date - first_date).num_days() as f64 / 365.0))
+        .sum::<f64>();
+    if !cash_flows.iter().any(|x| x.amount > 0.0) || !cash_flows.iter().any(|x| x.amount < 0.0) {
+        return Err("The internal rate of return requires both positive and negative cash flows".into());
+    }
+    solve_rate(npv)
+}
+
+/// Finds the rate at which the net present value of the cash flows is zero
+fn solve_rate(npv: impl Fn(f64) -> f64) -> Result<f64, Box<dyn Error>> {
+    // The NPV is monotonic in the rate for conventional flows, so bisection on a bracketing
+    // interval always converges
+    let (mut low, mut high) = (-0.9999, 1.0);
+    while npv(high) > 0.0 && high < 1e6 {
+        high *= 2.0;
+    }
+    if npv(low).signum() == npv(high).signum() {
+        return Err("No internal rate of return found for the cash flows".into());
+    }
+    for _ in 0..200 {
+        let mid = (low + high) / 2.0;
+        if npv(mid).signum() == npv(low).signum() {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        if high - low < 1e-12 {
+            break;
+        }
+    }
+    Ok((low + high) / 2.0)
+}
+
+/// Computes the time-weighted return of a cash flow simulation, which compounds the period returns and
+/// is unaffected by the size and timing of the cash flows
+///
+/// # Arguments
+///
+/// * `simulation` - DataFrame returned by `simulate_cash_flows`
+/// * `periods_per_year` - Number of return periods per year (e.g. 252 for daily equity returns)
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Cumulative and annualized time-weighted return in percent
+pub fn time_weighted_return(simulation: &DataFrame, periods_per_year: f64) -> Result<(f64, f64), Box<dyn Error>> {
+    let returns = simulation.column("return")?.f64()?.into_no_null_iter().skip(1).collect::<Vec<f64>>();
+    if returns.is_empty() {
+        return Err("Not enough periods to compute the time-weighted return".into());
+    }
+    let growth = returns.iter().fold(1.0, |acc, r| acc * (1.0 + r / 100.0));
+    let annualized = growth.powf(periods_per_year / returns.len() as f64) - 1.0;
+    Ok(((growth - 1.0) * 100.0, annualized * 100.0))
+}
+
+/// Computes the annualized money-weighted return (IRR) of a cash flow simulation
+///
+/// # Arguments
+///
+/// * `simulation` - DataFrame returned by `simulate_cash_flows`
+/// * `periods_per_year` - Number of return periods per year (e.g. 252 for daily equity returns)
+///
+/// # Returns
+///
+/// * `f64` - Annualized money-weighted return in percent
+pub fn money_weighted_return(simulation: &DataFrame, periods_per_year: f64) -> Result<f64, Box<dyn Error>> {
+    let mut cash_flows = simulation.column("cash_flow")?.f64()?.into_no_null_iter()
+        .map(|x| -x).collect::<Vec<f64>>();
+    let final_value = simulation.column("value")?.f64()?.into_no_null_iter().last()
+        .ok_or("Empty cash flow simulation")?;
+    if let Some(last) = cash_flows.last_mut() {
+        *last += final_value;
+    }
+    let rate = irr(&cash_flows)?;
+    Ok(((1.0 + rate).powf(periods_per_year) - 1.0) * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow(date: &str, amount: f64) -> CashFlow {
+        CashFlow { date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(), amount }
+    }
+
+    #[test]
+    fn test_xirr_matches_reference() {
+        // Reference example of the XIRR spreadsheet function, 37.34%
+        let cash_flows = vec![
+            flow("2008-01-01", -10000.0),
+            flow("2008-03-01", 2750.0),
+            flow("2008-10-30", 4250.0),
+            flow("2009-02-15", 3250.0),
+            flow("2009-04-01", 2750.0),
+        ];
+        let rate = xirr(&cash_flows).unwrap();
+        assert!((rate - 0.373362535).abs() < 1e-6, "xirr = {}", rate);
+    }
+
+    #[test]
+    fn test_xirr_of_one_year_holding_is_simple_return() {
+        let cash_flows = vec![flow("2023-01-01", -100.0), flow("2024-01-01", 110.0)];
+        assert!((xirr(&cash_flows).unwrap() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_xirr_requires_flows_of_both_signs() {
+        let cash_flows = vec![flow("2023-01-01", 100.0), flow("2024-01-01", 110.0)];
+        assert!(xirr(&cash_flows).is_err());
+        assert!(xirr(&[]).is_err());
+    }
+
+    #[test]
+    fn test_irr_matches_reference() {
+        // -100 now, 60 and 60 over the next two periods: 100 = 60/(1+r) + 60/(1+r)^2, r = 13.07%
+        let rate = irr(&[-100.0, 60.0, 60.0]).unwrap();
+        assert!((rate - 0.130662386).abs() < 1e-6, "irr = {}", rate);
+    }
+}
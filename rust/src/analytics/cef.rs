@@ -0,0 +1,168 @@
+use std::error::Error;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::data::ticker::TickerData;
+use crate::models::ticker::{Ticker, TickerBuilder};
+
+
+/// Closed-end fund discount columns of `TickersData::cef_discounts`, which `rank_universe` joins when ranked on
+pub const CEF_COLUMNS: [&str; 5] = [
+    "premium_discount", "discount_z_score", "average_premium_discount", "discount_percentile", "discount_observations",
+];
+
+/// Trailing window of the discount statistics joined by `rank_universe` (one year of daily bars)
+pub const CEF_DISCOUNT_WINDOW: usize = 252;
+
+/// Yahoo Finance symbol of the net asset value of a closed-end fund (e.g. XPDIX for PDI)
+pub fn cef_nav_symbol(symbol: &str) -> String {
+    format!("X{}X", symbol)
+}
+
+/// Computes the premium/discount of a closed-end fund's price to its NAV and its rolling z-score
+///
+/// # Arguments
+///
+/// * `price` - Market prices of the fund
+/// * `nav` - Net asset values per share aligned with the prices
+/// * `window` - Number of trailing periods of the z-score (e.g. 252)
+///
+/// # Returns
+///
+/// * `(Vec<f64>, Vec<f64>)` - Premium (positive) or discount (negative) in percent and its z-score against the
+///   trailing window, NaN until the window is filled
+pub fn premium_discount(price: &[f64], nav: &[f64], window: usize) -> (Vec<f64>, Vec<f64>) {
+    let premium = price.iter().zip(nav.iter())
+        .map(|(p, n)| if *n > 0.0 && p.is_finite() { (p / n - 1.0) * 100.0 } else { f64::NAN })
+        .collect::<Vec<f64>>();
+    let z_scores = (0..premium.len())
+        .map(|t| {
+            if window < 2 || t + 1 < window || !premium[t].is_finite() {
+                return f64::NAN;
+            }
+            let sample = premium[t + 1 - window..=t].iter().filter(|x| x.is_finite()).cloned().collect::<Vec<f64>>();
+            if sample.len() < 2 {
+                return f64::NAN;
+            }
+            let mean = sample.iter().sum::<f64>() / sample.len() as f64;
+            let std = (sample.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (sample.len() - 1) as f64).sqrt();
+            if std > 0.0 { (premium[t] - mean) / std } else { f64::NAN }
+        })
+        .collect::<Vec<f64>>();
+    (premium, z_scores)
+}
+
+/// Current premium/discount of a closed-end fund relative to its history
+///
+/// * `premium_discount` - Latest premium (positive) or discount (negative) to NAV in percent
+/// * `discount_z_score` - Z-score of the latest premium/discount against the trailing window,
+///   strongly negative values flag unusually wide discounts for discount-capture strategies
+/// * `average_premium_discount` - Average premium/discount over the trailing window in percent
+/// * `discount_percentile` - Percentile (0 - 100) of the latest premium/discount within the trailing window
+/// * `discount_observations` - Number of periods with both a price and a NAV in the trailing window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CefDiscountStats {
+    pub premium_discount: f64,
+    pub discount_z_score: f64,
+    pub average_premium_discount: f64,
+    pub discount_percentile: f64,
+    pub discount_observations: usize,
+}
+
+impl CefDiscountStats {
+    /// Summarizes a premium/discount history (in percent) over its trailing window
+    pub fn compute(premium_discount: &[f64], window: usize) -> Result<CefDiscountStats, Box<dyn Error>> {
+        let start = premium_discount.len().saturating_sub(window);
+        let sample = premium_discount[start..].iter().filter(|x| x.is_finite()).cloned().collect::<Vec<f64>>();
+        if sample.len() < 2 {
+            return Err("At least 2 periods with a price and a NAV are required for the discount statistics".into());
+        }
+        let latest = sample[sample.len() - 1];
+        let mean = sample.iter().sum::<f64>() / sample.len() as f64;
+        let std = (sample.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (sample.len() - 1) as f64).sqrt();
+        Ok(CefDiscountStats {
+            premium_discount: latest,
+            discount_z_score: if std > 0.0 { (latest - mean) / std } else { f64::NAN },
+            average_premium_discount: mean,
+            discount_percentile: sample.iter().filter(|x| **x <= latest).count() as f64 / sample.len() as f64 * 100.0,
+            discount_observations: sample.len(),
+        })
+    }
+
+    /// Statistic values in the order of `CEF_COLUMNS`
+    pub fn values(&self) -> Vec<f64> {
+        vec![
+            self.premium_discount, self.discount_z_score, self.average_premium_discount,
+            self.discount_percentile, self.discount_observations as f64,
+        ]
+    }
+}
+
+pub trait CefAnalytics {
+    fn discount_history(&self, nav_symbol: Option<&str>, window: usize) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn discount_stats(&self, nav_symbol: Option<&str>, window: usize) -> impl std::future::Future<Output = Result<CefDiscountStats, Box<dyn Error>>>;
+}
+
+impl CefAnalytics for Ticker {
+    /// Computes the premium/discount history of a closed-end fund's price to its net asset value
+    ///
+    /// # Arguments
+    ///
+    /// * `nav_symbol` - Symbol of the fund NAV (default - the Yahoo Finance NAV symbol, e.g. XPDIX for PDI)
+    /// * `window` - Number of trailing periods of the z-score (e.g. 252)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, price, nav, premium_discount (%) and z_score,
+    ///   for the dates with both a price and a NAV
+    async fn discount_history(&self, nav_symbol: Option<&str>, window: usize) -> Result<DataFrame, Box<dyn Error>> {
+        let nav_symbol = nav_symbol.map(|x| x.to_string()).unwrap_or(cef_nav_symbol(&self.ticker));
+        let price = self.get_chart().await?.select(["timestamp", "close"])?;
+        let nav = TickerBuilder::new()
+            .ticker(&nav_symbol)
+            .start_date(&self.start_date)
+            .end_date(&self.end_date)
+            .interval(self.interval)
+            .calendar(self.calendar)
+            .build()
+            .get_chart().await
+            .map_err(|e| format!("No NAV found for {} under {}: {}", self.ticker, nav_symbol, e))?
+            .select(["timestamp", "close"])?;
+        let mut nav = nav;
+        nav.rename("close", "nav")?;
+
+        let df = price.join(&nav, ["timestamp"], ["timestamp"], JoinArgs::new(JoinType::Inner))?;
+        let df = df.sort(["timestamp"], SortMultipleOptions::new().with_order_descending(false))?;
+        let prices = df.column("close")?.f64()?.into_iter().map(|x| x.unwrap_or(f64::NAN)).collect::<Vec<f64>>();
+        let navs = df.column("nav")?.f64()?.into_iter().map(|x| x.unwrap_or(f64::NAN)).collect::<Vec<f64>>();
+        if prices.is_empty() {
+            return Err(format!("No overlapping price and NAV data found for {}", self.ticker).into());
+        }
+        let (premium, z_scores) = premium_discount(&prices, &navs, window);
+
+        let df = DataFrame::new(vec![
+            df.column("timestamp")?.clone(),
+            Series::new("price", prices),
+            Series::new("nav", navs),
+            Series::new("premium_discount", premium),
+            Series::new("z_score", z_scores),
+        ])?;
+        Ok(df)
+    }
+
+    /// Summarizes the current premium/discount of a closed-end fund against its trailing history
+    ///
+    /// # Arguments
+    ///
+    /// * `nav_symbol` - Symbol of the fund NAV (default - the Yahoo Finance NAV symbol, e.g. XPDIX for PDI)
+    /// * `window` - Number of trailing periods (e.g. 252)
+    ///
+    /// # Returns
+    ///
+    /// * `CefDiscountStats` struct
+    async fn discount_stats(&self, nav_symbol: Option<&str>, window: usize) -> Result<CefDiscountStats, Box<dyn Error>> {
+        let history = self.discount_history(nav_symbol, window).await?;
+        let premium = history.column("premium_discount")?.f64()?.into_iter().map(|x| x.unwrap_or(f64::NAN)).collect::<Vec<f64>>();
+        CefDiscountStats::compute(&premium, window)
+    }
+}
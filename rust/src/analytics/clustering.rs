@@ -0,0 +1,140 @@
+use std::error::Error;
+use polars::prelude::*;
+
+use crate::analytics::factors::returns_matrix;
+use crate::analytics::statistics::correlation_matrix;
+
+
+/// Correlation distance between two securities, sqrt(0.5 * (1 - rho)), 0 for perfectly correlated
+/// and 1 for perfectly anti-correlated securities
+pub fn correlation_distance(correlation: f64) -> f64 {
+    (0.5 * (1.0 - correlation.clamp(-1.0, 1.0))).sqrt()
+}
+
+/// Clusters of a universe of securities by return correlation
+///
+/// * `symbols` - Symbols of the securities
+/// * `clusters` - Cluster of each security, clusters are numbered from 0 in order of decreasing size
+/// * `medoids` - Representative security of each cluster, the member with the smallest total correlation
+///   distance to the other members
+/// * `correlations` - Correlation of each security with the medoid of its cluster
+#[derive(Debug, Clone)]
+pub struct CorrelationClusters {
+    pub symbols: Vec<String>,
+    pub clusters: Vec<usize>,
+    pub medoids: Vec<String>,
+    pub correlations: Vec<f64>,
+}
+
+impl CorrelationClusters {
+    /// Clusters the securities of a returns DataFrame with average linkage hierarchical clustering
+    /// on the correlation distance and picks the medoid of each cluster
+    ///
+    /// # Arguments
+    ///
+    /// * `returns` - Polars DataFrame of security returns (an optional timestamp column is ignored)
+    /// * `n_clusters` - Number of clusters, i.e. the size of the reduced universe
+    ///
+    /// # Returns
+    ///
+    /// * `CorrelationClusters` - Cluster assignments and representative members
+    pub fn compute(returns: &DataFrame, n_clusters: usize) -> Result<CorrelationClusters, Box<dyn Error>> {
+        let returns = returns_matrix(returns)?;
+        let symbols = returns.get_column_names().iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let n = symbols.len();
+        if n_clusters == 0 {
+            return Err("The number of clusters must be positive".into());
+        }
+        let n_clusters = n_clusters.min(n);
+
+        let correlation = correlation_matrix(&returns)?;
+        let distance = correlation.mapv(correlation_distance);
+
+        // Agglomerative clustering with Lance-Williams updates of the average linkage distances
+        let mut members = (0..n).map(|i| vec![i]).collect::<Vec<Vec<usize>>>();
+        let mut linkage = distance.clone();
+        let mut active = vec![true; n];
+        let mut remaining = n;
+        while remaining > n_clusters {
+            let mut closest = (0, 0, f64::INFINITY);
+            for i in (0..n).filter(|i| active[*i]) {
+                for j in ((i + 1)..n).filter(|j| active[*j]) {
+                    if linkage[(i, j)] < closest.2 {
+                        closest = (i, j, linkage[(i, j)]);
+                    }
+                }
+            }
+            let (i, j, _) = closest;
+            let (size_i, size_j) = (members[i].len() as f64, members[j].len() as f64);
+            for k in (0..n).filter(|k| active[*k] && *k != i && *k != j) {
+                let merged = (size_i * linkage[(k, i)] + size_j * linkage[(k, j)]) / (size_i + size_j);
+                linkage[(k, i)] = merged;
+                linkage[(i, k)] = merged;
+            }
+            let merged_members = std::mem::take(&mut members[j]);
+            members[i].extend(merged_members);
+            active[j] = false;
+            remaining -= 1;
+        }
+
+        let mut groups = members.into_iter()
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<Vec<usize>>>();
+        groups.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].cmp(&b[0])));
+
+        let mut clusters = vec![0; n];
+        let mut correlations = vec![1.0; n];
+        let mut medoids = Vec::new();
+        for (cluster, group) in groups.iter().enumerate() {
+            let medoid = *group.iter()
+                .min_by(|a, b| {
+                    let total_a = group.iter().map(|x| distance[(**a, *x)]).sum::<f64>();
+                    let total_b = group.iter().map(|x| distance[(**b, *x)]).sum::<f64>();
+                    total_a.partial_cmp(&total_b).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap();
+            for member in group {
+                clusters[*member] = cluster;
+                correlations[*member] = correlation[(*member, medoid)];
+            }
+            medoids.push(symbols[medoid].clone());
+        }
+
+        Ok(CorrelationClusters {
+            symbols,
+            clusters,
+            medoids,
+            correlations,
+        })
+    }
+
+    /// Symbols of the members of a cluster
+    pub fn members(&self, cluster: usize) -> Vec<String> {
+        self.symbols.iter().zip(self.clusters.iter())
+            .filter(|(_, c)| **c == cluster)
+            .map(|(symbol, _)| symbol.clone())
+            .collect()
+    }
+
+    /// Cluster assignments of the securities
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - symbol, cluster, cluster_size, medoid, is_medoid and correlation_to_medoid columns
+    pub fn to_dataframe(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let sizes = self.clusters.iter()
+            .map(|c| self.clusters.iter().filter(|x| *x == c).count() as u32)
+            .collect::<Vec<u32>>();
+        let medoids = self.clusters.iter().map(|c| self.medoids[*c].clone()).collect::<Vec<String>>();
+        let is_medoid = self.symbols.iter().zip(medoids.iter()).map(|(s, m)| s == m).collect::<Vec<bool>>();
+        let df = df!(
+            "symbol" => self.symbols.clone(),
+            "cluster" => self.clusters.iter().map(|x| *x as u32).collect::<Vec<u32>>(),
+            "cluster_size" => sizes,
+            "medoid" => medoids,
+            "is_medoid" => is_medoid,
+            "correlation_to_medoid" => self.correlations.clone(),
+        )?;
+        Ok(df)
+    }
+}
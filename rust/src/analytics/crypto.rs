@@ -0,0 +1,201 @@
+use std::error::Error;
+use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+
+use crate::data::ticker::{fetch_quote_fields, TickerData};
+use crate::models::ticker::{Ticker, TickerBuilder};
+use crate::utils::date_utils::to_timestamp;
+use crate::utils::web_utils::get_json_response;
+
+
+/// Default universe of large cryptocurrencies used for the market dominance and volume share metrics
+pub const DEFAULT_CRYPTO_UNIVERSE: [&str; 10] = [
+    "BTC-USD", "ETH-USD", "USDT-USD", "BNB-USD", "SOL-USD",
+    "XRP-USD", "USDC-USD", "DOGE-USD", "ADA-USD", "TRX-USD",
+];
+
+/// Provider of the cryptocurrency market data
+///
+/// Yahoo: prices and volumes from Yahoo Finance, only the current circulating and max supply are
+///        available so the supply history is held constant at the current values
+/// CoinGecko: historical market caps from the CoinGecko public API, from which the circulating supply history is derived
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CryptoDataSource {
+    Yahoo,
+    CoinGecko,
+}
+
+impl CryptoDataSource {
+    pub fn to_string(&self) -> String {
+        match self {
+            CryptoDataSource::Yahoo => "yahoo".to_string(),
+            CryptoDataSource::CoinGecko => "coingecko".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> CryptoDataSource {
+        match s {
+            "yahoo" => CryptoDataSource::Yahoo,
+            "coingecko" => CryptoDataSource::CoinGecko,
+            _ => CryptoDataSource::Yahoo,
+        }
+    }
+}
+
+pub trait CryptoMetrics {
+    fn supply_history(&self, source: CryptoDataSource) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn market_dominance(&self, universe: Option<Vec<&str>>, source: CryptoDataSource) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+}
+
+impl CryptoMetrics for Ticker {
+    /// Returns the market cap and supply history of a cryptocurrency
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Provider of the market data (e.g. CryptoDataSource::CoinGecko)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, price, market_cap, volume, circulating_supply and max_supply
+    ///   (max_supply is null for uncapped cryptocurrencies)
+    async fn supply_history(&self, source: CryptoDataSource) -> Result<DataFrame, Box<dyn Error>> {
+        match source {
+            CryptoDataSource::Yahoo => {
+                let chart = self.get_chart().await?;
+                let fields = vec!["circulatingSupply".to_string(), "maxSupply".to_string()];
+                let supply = fetch_quote_fields(&self.ticker, &fields).await?;
+                let circulating_supply = supply[0].as_f64()
+                    .ok_or(format!("No circulating supply found for {}", self.ticker))?;
+                let max_supply = supply[1].as_f64();
+                let prices = chart.column("close")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+                let df = DataFrame::new(vec![
+                    chart.column("timestamp")?.clone(),
+                    Series::new("price", &prices),
+                    Series::new("market_cap", prices.iter().map(|x| x * circulating_supply).collect::<Vec<f64>>()),
+                    chart.column("volume")?.clone(),
+                    Series::new("circulating_supply", vec![circulating_supply; prices.len()]),
+                    Series::new("max_supply", vec![max_supply; prices.len()]),
+                ])?;
+                Ok(df)
+            }
+            CryptoDataSource::CoinGecko => {
+                let id = coingecko_id(&self.ticker).await?;
+                let url = format!(
+                    "https://api.coingecko.com/api/v3/coins/{}/market_chart/range?vs_currency=usd&from={}&to={}",
+                    id, to_timestamp(&self.start_date)?, to_timestamp(&self.end_date)?
+                );
+                let result = get_json_response(url).await?;
+                let series = |key: &str| -> Result<Vec<(NaiveDateTime, f64)>, Box<dyn Error>> {
+                    Ok(result[key].as_array()
+                        .ok_or(format!("No {} found for {}: {}", key, id, result))?
+                        .iter()
+                        .filter_map(|x| Some((
+                            DateTime::from_timestamp_millis(x[0].as_f64()? as i64)?.naive_utc(),
+                            x[1].as_f64()?,
+                        )))
+                        .collect())
+                };
+                let prices = series("prices")?;
+                let market_caps = series("market_caps")?;
+                let volumes = series("total_volumes")?;
+
+                let url = format!("https://api.coingecko.com/api/v3/coins/{}?localization=false&tickers=false&community_data=false&developer_data=false", id);
+                let coin = get_json_response(url).await?;
+                let max_supply = coin["market_data"]["max_supply"].as_f64();
+
+                let len = prices.len().min(market_caps.len()).min(volumes.len());
+                let df = df!(
+                    "timestamp" => prices[..len].iter().map(|x| x.0).collect::<Vec<NaiveDateTime>>(),
+                    "price" => prices[..len].iter().map(|x| x.1).collect::<Vec<f64>>(),
+                    "market_cap" => market_caps[..len].iter().map(|x| x.1).collect::<Vec<f64>>(),
+                    "volume" => volumes[..len].iter().map(|x| x.1).collect::<Vec<f64>>(),
+                    "circulating_supply" => (0..len).map(|i| if prices[i].1 > 0.0 { market_caps[i].1 / prices[i].1 } else { f64::NAN }).collect::<Vec<f64>>(),
+                    "max_supply" => vec![max_supply; len]
+                )?;
+                Ok(df)
+            }
+        }
+    }
+
+    /// Computes the market dominance and volume share of a cryptocurrency within a universe of cryptocurrencies
+    ///
+    /// # Arguments
+    ///
+    /// * `universe` - Symbols of the reference universe (default - DEFAULT_CRYPTO_UNIVERSE), the ticker is always included
+    /// * `source` - Provider of the market data (e.g. CryptoDataSource::Yahoo)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, market_cap, universe_market_cap, dominance (%),
+    ///   volume, universe_volume and volume_share (%)
+    async fn market_dominance(&self, universe: Option<Vec<&str>>, source: CryptoDataSource) -> Result<DataFrame, Box<dyn Error>> {
+        let mut symbols = universe.unwrap_or(DEFAULT_CRYPTO_UNIVERSE.to_vec())
+            .iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        if !symbols.contains(&self.ticker) {
+            symbols.push(self.ticker.clone());
+        }
+
+        // Daily totals of the universe, keyed by date so the providers' timestamps line up
+        let mut totals: HashMap<NaiveDate, (f64, f64)> = HashMap::new();
+        let mut own: Vec<(NaiveDate, f64, f64)> = Vec::new();
+        for symbol in symbols {
+            let ticker = TickerBuilder::new().ticker(&symbol)
+                .start_date(&self.start_date)
+                .end_date(&self.end_date)
+                .interval(self.interval)
+                .calendar(self.calendar)
+                .build();
+            let history = match ticker.supply_history(source).await {
+                Ok(history) => history,
+                Err(e) => {
+                    eprintln!("Error Fetching Supply History for {}: {}", symbol, e);
+                    continue;
+                }
+            };
+            let dates = history.column("timestamp")?.datetime()?.as_datetime_iter()
+                .map(|x| x.map(|x| x.date())).collect::<Vec<Option<NaiveDate>>>();
+            let market_caps = history.column("market_cap")?.f64()?.to_vec();
+            let volumes = history.column("volume")?.f64()?.to_vec();
+            for i in 0..dates.len() {
+                if let (Some(date), Some(market_cap), Some(volume)) = (dates[i], market_caps[i], volumes[i]) {
+                    let total = totals.entry(date).or_insert((0.0, 0.0));
+                    total.0 += market_cap;
+                    total.1 += volume;
+                    if symbol == self.ticker {
+                        own.push((date, market_cap, volume));
+                    }
+                }
+            }
+        }
+        if own.is_empty() {
+            return Err(format!("No market data found for {}", self.ticker).into());
+        }
+
+        let universe_totals = own.iter().map(|x| totals[&x.0]).collect::<Vec<(f64, f64)>>();
+        let df = df!(
+            "timestamp" => own.iter().map(|x| x.0.and_hms_opt(0, 0, 0).unwrap()).collect::<Vec<NaiveDateTime>>(),
+            "market_cap" => own.iter().map(|x| x.1).collect::<Vec<f64>>(),
+            "universe_market_cap" => universe_totals.iter().map(|x| x.0).collect::<Vec<f64>>(),
+            "dominance" => own.iter().zip(universe_totals.iter()).map(|(x, t)| x.1 / t.0 * 100.0).collect::<Vec<f64>>(),
+            "volume" => own.iter().map(|x| x.2).collect::<Vec<f64>>(),
+            "universe_volume" => universe_totals.iter().map(|x| x.1).collect::<Vec<f64>>(),
+            "volume_share" => own.iter().zip(universe_totals.iter())
+                .map(|(x, t)| if t.1 > 0.0 { x.2 / t.1 * 100.0 } else { f64::NAN }).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+}
+
+/// Looks up the CoinGecko coin id of a Yahoo Finance crypto symbol (e.g. "BTC-USD" -> "bitcoin")
+async fn coingecko_id(symbol: &str) -> Result<String, Box<dyn Error>> {
+    let base = symbol.split('-').next().unwrap_or(symbol).to_lowercase();
+    let url = format!("https://api.coingecko.com/api/v3/search?query={}", base);
+    let result = get_json_response(url).await?;
+    let coins = result["coins"].as_array().ok_or(format!("No CoinGecko coins found for {}", symbol))?;
+    // Search results are ranked by market cap, so the first exact symbol match is the main coin
+    coins.iter()
+        .find(|x| x["symbol"].as_str().map(|s| s.to_lowercase()) == Some(base.clone()))
+        .and_then(|x| x["id"].as_str().map(|s| s.to_string()))
+        .ok_or(format!("No CoinGecko coin found for {}", symbol).into())
+}
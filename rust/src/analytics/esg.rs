@@ -0,0 +1,99 @@
+use std::error::Error;
+
+use crate::data::ticker::TickerData;
+use crate::models::ticker::TickerBuilder;
+
+
+/// Treatment of the assets failing an ESG screen
+///
+/// Exclude: Remove the asset from the portfolio
+/// Cap(max_weight): Limit the weight of the asset in decimal (e.g. Cap(0.05) for 5%)
+#[derive(Debug, Clone, Copy)]
+pub enum EsgAction {
+    Exclude,
+    Cap(f64),
+}
+
+/// ESG screen of the portfolio assets on their Sustainalytics ESG risk rating (total ESG score on Yahoo Finance)
+///
+/// Lower risk ratings are better, so assets with a rating above `max_risk_score` are excluded or capped.
+/// Assets without a rating (e.g. ETFs and cryptocurrencies) pass the screen.
+///
+/// * `max_risk_score` - Highest acceptable ESG risk rating (e.g. 30.0, ratings of 30 and above are high risk)
+/// * `action` - Exclusion or weight cap of the assets failing the screen
+#[derive(Debug, Clone, Copy)]
+pub struct EsgScreen {
+    pub max_risk_score: f64,
+    pub action: EsgAction,
+}
+
+impl EsgScreen {
+    pub fn new(max_risk_score: f64, action: EsgAction) -> EsgScreen {
+        EsgScreen {
+            max_risk_score,
+            action,
+        }
+    }
+
+    /// Whether an asset with the given ESG risk rating fails the screen
+    pub fn fails(&self, score: f64) -> bool {
+        score.is_finite() && score > self.max_risk_score
+    }
+
+    /// Lower and upper weight bounds of an asset failing the screen
+    pub fn bounds(&self, bounds: (f64, f64)) -> (f64, f64) {
+        match self.action {
+            EsgAction::Exclude => (0.0, 0.0),
+            EsgAction::Cap(max_weight) => (bounds.0.min(max_weight), bounds.1.min(max_weight)),
+        }
+    }
+}
+
+/// Fetches the total ESG risk rating of each symbol from Yahoo Finance
+///
+/// # Arguments
+///
+/// * `symbols` - Ticker symbols (e.g. ["AAPL", "XOM"])
+///
+/// # Returns
+///
+/// * `Vec<f64>` - ESG risk rating of each symbol, NaN when it is not rated
+pub async fn fetch_esg_scores(symbols: &[String]) -> Vec<f64> {
+    let mut scores = Vec::with_capacity(symbols.len());
+    for symbol in symbols {
+        let score = match TickerBuilder::new().ticker(symbol).build().get_esg_scores().await {
+            Ok(esg) => esg.total_esg.map(|x| x.raw).unwrap_or(f64::NAN),
+            Err(_) => f64::NAN,
+        };
+        scores.push(score);
+    }
+    scores
+}
+
+/// Computes the weighted ESG risk rating of a portfolio over its rated assets
+///
+/// # Arguments
+///
+/// * `weights` - Vector of portfolio weights
+/// * `scores` - ESG risk rating of each asset, NaN when it is not rated
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Weighted ESG risk rating (NaN without rated holdings) and the share of the portfolio
+///   weight with a rating in percent
+pub fn weighted_esg_score(weights: &[f64], scores: &[f64]) -> Result<(f64, f64), Box<dyn Error>> {
+    if weights.len() != scores.len() {
+        return Err("The number of ESG scores must match the number of weights".into());
+    }
+    let rated = weights.iter().zip(scores.iter())
+        .filter(|(_, s)| s.is_finite())
+        .collect::<Vec<(&f64, &f64)>>();
+    let coverage = rated.iter().map(|(w, _)| **w).sum::<f64>();
+    let total = weights.iter().sum::<f64>();
+    let score = if coverage > 0.0 {
+        rated.iter().map(|(w, s)| **w * **s).sum::<f64>() / coverage
+    } else {
+        f64::NAN
+    };
+    Ok((score, if total > 0.0 { coverage / total * 100.0 } else { f64::NAN }))
+}
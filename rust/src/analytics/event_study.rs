@@ -0,0 +1,297 @@
+use std::error::Error;
+use chrono::{NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+
+use crate::analytics::performance::TickerPerformance;
+use crate::analytics::statistics::z_score;
+use crate::data::ticker::TickerData;
+use crate::models::tickers::Tickers;
+
+
+/// Event of a symbol, e.g. an earnings release or a news spike
+///
+/// * `symbol` - Ticker symbol (e.g. "AAPL")
+/// * `date` - Event date, events on non-trading days are moved to the next trading period
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub symbol: String,
+    pub date: NaiveDate,
+}
+
+impl Event {
+    pub fn new(symbol: &str, date: &str) -> Result<Event, Box<dyn Error>> {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid event date {} for {}: {}", date, symbol, e))?;
+        Ok(Event { symbol: symbol.to_string(), date })
+    }
+
+    /// Label of the event, e.g. "AAPL 2024-02-01"
+    pub fn label(&self) -> String {
+        format!("{} {}", self.symbol, self.date)
+    }
+}
+
+/// Periods around each event used by the event study
+///
+/// * `pre` - Periods before the event in the event window (default 5)
+/// * `post` - Periods after the event in the event window (default 5)
+/// * `estimation` - Periods used to estimate the market model (default 120)
+/// * `gap` - Periods between the estimation window and the event window (default 10)
+#[derive(Debug, Clone, Copy)]
+pub struct EventWindow {
+    pub pre: usize,
+    pub post: usize,
+    pub estimation: usize,
+    pub gap: usize,
+}
+
+impl Default for EventWindow {
+    fn default() -> Self {
+        EventWindow {
+            pre: 5,
+            post: 5,
+            estimation: 120,
+            gap: 10,
+        }
+    }
+}
+
+impl EventWindow {
+    pub fn new(pre: usize, post: usize) -> EventWindow {
+        EventWindow { pre, post, ..EventWindow::default() }
+    }
+
+    pub fn estimation(mut self, estimation: usize) -> EventWindow {
+        self.estimation = estimation;
+        self
+    }
+
+    pub fn gap(mut self, gap: usize) -> EventWindow {
+        self.gap = gap;
+        self
+    }
+
+    /// Number of periods in the event window
+    pub fn len(&self) -> usize {
+        self.pre + self.post + 1
+    }
+
+    /// Always false, the event window includes at least the event day
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Relative day of each period in the event window (e.g. -5 to 5)
+    pub fn days(&self) -> Vec<i32> {
+        (-(self.pre as i32)..=self.post as i32).collect()
+    }
+}
+
+/// Result of an event study
+///
+/// * `events` - Events with enough history for the study
+/// * `abnormal_returns` - DataFrame with the relative day and the abnormal returns (%) of each event
+/// * `results` - DataFrame with columns day, aar (average abnormal return %), aar_t_stat, caar (cumulative
+///   average abnormal return %), caar_lower, caar_upper (confidence interval) and caar_t_stat
+#[derive(Debug, Clone)]
+pub struct EventStudy {
+    pub window: EventWindow,
+    pub confidence_level: f64,
+    pub events: Vec<Event>,
+    pub abnormal_returns: DataFrame,
+    pub results: DataFrame,
+}
+
+/// Computes the market model abnormal returns of a security around an event
+///
+/// The market model (alpha and beta against the benchmark) is estimated over the estimation window,
+/// which ends `gap` periods before the event window starts.
+///
+/// # Arguments
+///
+/// * `security_returns` - Security returns aligned with the benchmark returns
+/// * `benchmark_returns` - Benchmark returns
+/// * `event_index` - Index of the event period
+/// * `window` - Event study windows
+///
+/// # Returns
+///
+/// * `Option<Vec<f64>>` - Abnormal returns over the event window, None without enough history
+pub fn abnormal_returns(security_returns: &[f64], benchmark_returns: &[f64], event_index: usize, window: &EventWindow) -> Option<Vec<f64>> {
+    let history = window.pre + window.gap + window.estimation;
+    if event_index < history || event_index + window.post >= security_returns.len() || window.estimation < 2 {
+        return None;
+    }
+    let start = event_index - history;
+    let end = start + window.estimation;
+    let (alpha, beta) = market_model(&security_returns[start..end], &benchmark_returns[start..end]);
+    let abnormal = (event_index - window.pre..=event_index + window.post)
+        .map(|i| security_returns[i] - (alpha + beta * benchmark_returns[i]))
+        .collect::<Vec<f64>>();
+    Some(abnormal)
+}
+
+/// Aggregates the abnormal returns of the events into average and cumulative average abnormal returns
+///
+/// The t-statistics and confidence intervals use the cross-sectional dispersion of the event abnormal returns.
+///
+/// # Arguments
+///
+/// * `abnormal_returns` - Abnormal returns over the event window of each event
+/// * `window` - Event study windows
+/// * `confidence_level` - Confidence level of the CAAR interval in decimal (e.g. 0.95 for 95%)
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns day, aar, aar_t_stat, caar, caar_lower, caar_upper and caar_t_stat
+pub fn aggregate_abnormal_returns(abnormal_returns: &[Vec<f64>], window: &EventWindow, confidence_level: f64) -> Result<DataFrame, Box<dyn Error>> {
+    let n = abnormal_returns.len();
+    if n == 0 {
+        return Err("The event study requires at least one event with enough history".into());
+    }
+    let cumulative = abnormal_returns.iter()
+        .map(|ar| ar.iter().scan(0.0, |acc, x| { *acc += x; Some(*acc) }).collect::<Vec<f64>>())
+        .collect::<Vec<Vec<f64>>>();
+    let z = z_score(1.0 - (1.0 - confidence_level) / 2.0);
+
+    let mut aar = Vec::with_capacity(window.len());
+    let mut aar_t_stat = Vec::with_capacity(window.len());
+    let mut caar = Vec::with_capacity(window.len());
+    let mut caar_lower = Vec::with_capacity(window.len());
+    let mut caar_upper = Vec::with_capacity(window.len());
+    let mut caar_t_stat = Vec::with_capacity(window.len());
+    for t in 0..window.len() {
+        let (mean, se) = mean_standard_error(&abnormal_returns.iter().map(|x| x[t]).collect::<Vec<f64>>());
+        aar.push(mean);
+        aar_t_stat.push(se.map(|x| mean / x));
+        let (mean, se) = mean_standard_error(&cumulative.iter().map(|x| x[t]).collect::<Vec<f64>>());
+        caar.push(mean);
+        caar_lower.push(se.map(|x| mean - z * x));
+        caar_upper.push(se.map(|x| mean + z * x));
+        caar_t_stat.push(se.map(|x| mean / x));
+    }
+
+    let df = df!(
+        "day" => window.days(),
+        "aar" => aar,
+        "aar_t_stat" => aar_t_stat,
+        "caar" => caar,
+        "caar_lower" => caar_lower,
+        "caar_upper" => caar_upper,
+        "caar_t_stat" => caar_t_stat
+    )?;
+    Ok(df)
+}
+
+/// Runs an event study of the events of the tickers against the benchmark
+///
+/// # Arguments
+///
+/// * `tickers` - Tickers struct, events of symbols outside the tickers are skipped
+/// * `events` - Events to study
+/// * `window` - Event study windows
+///
+/// # Returns
+///
+/// * `EventStudy` struct
+pub async fn event_study(tickers: &Tickers, events: &[Event], window: EventWindow) -> Result<EventStudy, Box<dyn Error>> {
+    let mut used_events = Vec::new();
+    let mut abnormal = Vec::new();
+    for ticker in &tickers.tickers {
+        let ticker_events = events.iter().filter(|e| e.symbol == ticker.ticker).collect::<Vec<&Event>>();
+        if ticker_events.is_empty() {
+            continue;
+        }
+        let stats = match ticker.performance_stats().await {
+            Ok(stats) => stats,
+            Err(e) => {
+                eprintln!("Error Fetching Returns for {}: {}", &ticker.ticker, e);
+                continue;
+            }
+        };
+        let dates = stats.dates_array.iter()
+            .map(|x| NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M:%S").map(|d| d.date()))
+            .collect::<Result<Vec<NaiveDate>, _>>()?;
+        let security_returns = stats.security_returns.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+        let benchmark_returns = stats.benchmark_returns.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+
+        for event in ticker_events {
+            let result = dates.iter().position(|d| *d >= event.date)
+                .and_then(|i| abnormal_returns(&security_returns, &benchmark_returns, i, &window));
+            match result {
+                Some(ar) => {
+                    abnormal.push(ar);
+                    used_events.push(event.clone());
+                }
+                None => eprintln!("Skipping event {}: not enough returns around the event", event.label()),
+            }
+        }
+    }
+
+    let results = aggregate_abnormal_returns(&abnormal, &window, tickers.confidence_level)?;
+    let mut columns = vec![Series::new("day", window.days())];
+    for (event, ar) in used_events.iter().zip(abnormal.iter()) {
+        columns.push(Series::new(&event.label(), ar));
+    }
+    Ok(EventStudy {
+        window,
+        confidence_level: tickers.confidence_level,
+        events: used_events,
+        abnormal_returns: DataFrame::new(columns)?,
+        results,
+    })
+}
+
+/// Retrieves the earnings release dates of the tickers as events
+///
+/// # Arguments
+///
+/// * `tickers` - Tickers struct
+///
+/// # Returns
+///
+/// * `Vec<Event>` - Earnings events within the tickers date range
+pub async fn earnings_events(tickers: &Tickers) -> Result<Vec<Event>, Box<dyn Error>> {
+    let mut events = Vec::new();
+    for ticker in &tickers.tickers {
+        let df = match ticker.get_events().await {
+            Ok(df) => df,
+            Err(e) => {
+                eprintln!("Error Fetching Events for {}: {}", &ticker.ticker, e);
+                continue;
+            }
+        };
+        let types = df.column("type")?.str()?.into_iter().collect::<Vec<Option<&str>>>();
+        let timestamps = df.column("timestamp")?.datetime()?.as_datetime_iter().collect::<Vec<Option<NaiveDateTime>>>();
+        for (event_type, timestamp) in types.iter().zip(timestamps.iter()) {
+            if let (Some("earnings"), Some(timestamp)) = (event_type, timestamp) {
+                events.push(Event { symbol: ticker.ticker.clone(), date: timestamp.date() });
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// Intercept and slope of the OLS regression of the security returns on the benchmark returns
+fn market_model(security_returns: &[f64], benchmark_returns: &[f64]) -> (f64, f64) {
+    let n = security_returns.len() as f64;
+    let mean_s = security_returns.iter().sum::<f64>() / n;
+    let mean_b = benchmark_returns.iter().sum::<f64>() / n;
+    let covariance = security_returns.iter().zip(benchmark_returns.iter())
+        .map(|(s, b)| (s - mean_s) * (b - mean_b)).sum::<f64>();
+    let variance = benchmark_returns.iter().map(|b| (b - mean_b).powi(2)).sum::<f64>();
+    let beta = if variance > 0.0 { covariance / variance } else { 0.0 };
+    (mean_s - beta * mean_b, beta)
+}
+
+/// Mean and standard error of the mean, the standard error requires at least two values
+fn mean_standard_error(values: &[f64]) -> (f64, Option<f64>) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, None);
+    }
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let se = (variance / n).sqrt();
+    (mean, if se > 0.0 { Some(se) } else { None })
+}
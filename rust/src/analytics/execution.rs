@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::collections::HashMap;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+
+use crate::data::config::Interval;
+use crate::data::ticker::TickerData;
+use crate::models::ticker::{Ticker, TickerBuilder};
+use crate::reports::report::ReportType;
+use crate::reports::table::{DataTable, TableType};
+use crate::reports::tabs::TabbedHtml;
+
+
+/// Timestamps, typical prices and volumes of the intraday bars of a trading day
+type Session = (Vec<NaiveDateTime>, Vec<f64>, Vec<f64>);
+
+pub trait ExecutionAnalytics {
+    fn execution_analysis(&self, trades: &DataFrame, interval: Option<Interval>, window: Option<i64>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn execution_summary(&self, trades: &DataFrame, interval: Option<Interval>, window: Option<i64>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn execution_report(&self, trades: &DataFrame, interval: Option<Interval>, window: Option<i64>) -> impl std::future::Future<Output = Result<TabbedHtml, Box<dyn Error>>>;
+}
+
+impl ExecutionAnalytics for Ticker {
+    /// Computes the slippage of each trade versus the interval VWAP and TWAP
+    ///
+    /// Slippage is measured in basis points and signed so that positive values are costs
+    /// (buying above or selling below the benchmark)
+    ///
+    /// # Arguments
+    ///
+    /// * `trades` - DataFrame of trades with columns timestamp (datetime or "%Y-%m-%d %H:%M:%S" string), side ("buy" or "sell"), quantity and price
+    /// * `interval` - Intraday bar interval used for the benchmarks (default - Interval::FiveMinutes), Yahoo Finance only serves recent intraday history
+    /// * `window` - Benchmark window in minutes either side of each trade (default - the whole trading session)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Trades with the VWAP, TWAP, slippage and slippage cost
+    async fn execution_analysis(&self, trades: &DataFrame, interval: Option<Interval>, window: Option<i64>) -> Result<DataFrame, Box<dyn Error>> {
+        let interval = interval.unwrap_or(Interval::FiveMinutes);
+        let timestamps = trade_timestamps(trades)?;
+        let sides = trades.column("side")?.str()?.into_no_null_iter().map(|x| x.to_lowercase()).collect::<Vec<String>>();
+        let quantities = trades.column("quantity")?.cast(&DataType::Float64)?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let prices = trades.column("price")?.cast(&DataType::Float64)?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        // Fetch the intraday bars once for each trading day
+        let mut sessions: HashMap<NaiveDate, Session> = HashMap::new();
+        for ts in &timestamps {
+            let date = ts.date();
+            if sessions.contains_key(&date) {
+                continue;
+            }
+            let ticker = TickerBuilder::new()
+                .ticker(&self.ticker)
+                .start_date(&date.to_string())
+                .end_date(&(date + Duration::days(1)).to_string())
+                .interval(interval)
+                .build();
+            let bars = ticker.get_chart().await?;
+            let dates = bars.column("timestamp")?.datetime()?.as_datetime_iter()
+                .map(|x| x.unwrap()).collect::<Vec<NaiveDateTime>>();
+            let high = bars.column("high")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+            let low = bars.column("low")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+            let close = bars.column("close")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+            let volume = bars.column("volume")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+            let typical = (0..close.len()).map(|i| (high[i] + low[i] + close[i]) / 3.0).collect::<Vec<f64>>();
+            sessions.insert(date, (dates, typical, volume));
+        }
+
+        let mut vwaps = Vec::new();
+        let mut twaps = Vec::new();
+        let mut vwap_slippage = Vec::new();
+        let mut twap_slippage = Vec::new();
+        let mut slippage_cost = Vec::new();
+        for i in 0..timestamps.len() {
+            let (dates, typical, volume) = &sessions[&timestamps[i].date()];
+            let in_window = |d: &NaiveDateTime| match window {
+                Some(w) => (*d - timestamps[i]).num_minutes().abs() <= w,
+                None => true,
+            };
+            let mut price_volume = 0.0;
+            let mut total_volume = 0.0;
+            let mut price_sum = 0.0;
+            let mut count = 0.0;
+            for j in 0..dates.len() {
+                if in_window(&dates[j]) {
+                    price_volume += typical[j] * volume[j];
+                    total_volume += volume[j];
+                    price_sum += typical[j];
+                    count += 1.0;
+                }
+            }
+            if count == 0.0 {
+                return Err(format!("No intraday data found for {} at {}", self.ticker, timestamps[i]).into());
+            }
+            let twap = price_sum / count;
+            let vwap = if total_volume > 0.0 { price_volume / total_volume } else { twap };
+            let sign = if sides[i] == "sell" { -1.0 } else { 1.0 };
+            vwaps.push(vwap);
+            twaps.push(twap);
+            vwap_slippage.push(sign * (prices[i] - vwap) / vwap * 10_000.0);
+            twap_slippage.push(sign * (prices[i] - twap) / twap * 10_000.0);
+            slippage_cost.push(sign * (prices[i] - vwap) * quantities[i]);
+        }
+
+        let df = df!(
+            "timestamp" => &timestamps,
+            "side" => &sides,
+            "quantity" => &quantities,
+            "price" => &prices,
+            "vwap" => &vwaps,
+            "twap" => &twaps,
+            "vwap_slippage_bps" => &vwap_slippage,
+            "twap_slippage_bps" => &twap_slippage,
+            "slippage_cost" => &slippage_cost
+        )?;
+        Ok(df)
+    }
+
+    /// Summarizes the execution quality of a list of trades
+    ///
+    /// # Arguments
+    ///
+    /// * `trades` - DataFrame of trades with columns timestamp, side, quantity and price
+    /// * `interval` - Intraday bar interval used for the benchmarks (default - Interval::FiveMinutes)
+    /// * `window` - Benchmark window in minutes either side of each trade (default - the whole trading session)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Execution quality metrics (notional-weighted slippage in basis points)
+    async fn execution_summary(&self, trades: &DataFrame, interval: Option<Interval>, window: Option<i64>) -> Result<DataFrame, Box<dyn Error>> {
+        let analysis = self.execution_analysis(trades, interval, window).await?;
+        summarize_execution(&analysis)
+    }
+
+    /// Generates an Execution Quality Report for a list of trades
+    ///
+    /// # Arguments
+    ///
+    /// * `trades` - DataFrame of trades with columns timestamp, side, quantity and price
+    /// * `interval` - Intraday bar interval used for the benchmarks (default - Interval::FiveMinutes)
+    /// * `window` - Benchmark window in minutes either side of each trade (default - the whole trading session)
+    ///
+    /// # Returns
+    ///
+    /// * `TabbedHtml` - Report with the execution summary and trade analysis tables
+    async fn execution_report(&self, trades: &DataFrame, interval: Option<Interval>, window: Option<i64>) -> Result<TabbedHtml, Box<dyn Error>> {
+        let analysis = self.execution_analysis(trades, interval, window).await?;
+        let summary = summarize_execution(&analysis)?;
+        let tabs: Vec<(String, String)> = vec![
+            ("Execution Summary".to_string(), DataTable::new(summary, TableType::SummaryStats).to_html()?),
+            ("Trade Analysis".to_string(), DataTable::new(analysis, TableType::ExecutionQuality).to_html()?),
+        ];
+        Ok(TabbedHtml::new(ReportType::Execution, tabs))
+    }
+}
+
+/// Summarizes the execution quality of the trades of an execution analysis
+fn summarize_execution(analysis: &DataFrame) -> Result<DataFrame, Box<dyn Error>> {
+    let quantities = analysis.column("quantity")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+    let prices = analysis.column("price")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+    let vwap_slippage = analysis.column("vwap_slippage_bps")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+    let twap_slippage = analysis.column("twap_slippage_bps")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+    let slippage_cost = analysis.column("slippage_cost")?.f64()?.sum().unwrap_or(0.0);
+
+    let notionals = quantities.iter().zip(prices.iter()).map(|(q, p)| q.abs() * p).collect::<Vec<f64>>();
+    let total_notional: f64 = notionals.iter().sum();
+    let weighted = |slippage: &Vec<f64>| if total_notional > 0.0 {
+        slippage.iter().zip(notionals.iter()).map(|(s, n)| s * n).sum::<f64>() / total_notional
+    } else {
+        0.0
+    };
+
+    let df = df!(
+        "Metric" => vec![
+            "Trades", "Total Quantity", "Total Notional", "VWAP Slippage (bps)",
+            "TWAP Slippage (bps)", "Slippage Cost vs VWAP"
+        ],
+        "Value" => vec![
+            analysis.height().to_string(),
+            format!("{:.2}", quantities.iter().map(|q| q.abs()).sum::<f64>()),
+            format!("{:.2}", total_notional),
+            format!("{:.2}", weighted(&vwap_slippage)),
+            format!("{:.2}", weighted(&twap_slippage)),
+            format!("{:.2}", slippage_cost),
+        ]
+    )?;
+    Ok(df)
+}
+
+/// Parses the trade timestamps from a datetime or string column
+fn trade_timestamps(trades: &DataFrame) -> Result<Vec<NaiveDateTime>, Box<dyn Error>> {
+    let column = trades.column("timestamp")?;
+    let timestamps = match column.dtype() {
+        DataType::Datetime(_, _) => column.datetime()?.as_datetime_iter()
+            .map(|x| x.ok_or("Missing trade timestamp"))
+            .collect::<Result<Vec<NaiveDateTime>, _>>()?,
+        _ => column.str()?.into_no_null_iter()
+            .map(|x| NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M:%S"))
+            .collect::<Result<Vec<NaiveDateTime>, _>>()?,
+    };
+    Ok(timestamps)
+}
@@ -0,0 +1,219 @@
+use std::error::Error;
+use ndarray::Array2;
+use polars::prelude::*;
+
+use crate::analytics::statistics::{correlation_matrix, covariance_matrix};
+
+
+/// Maximum number of Jacobi sweeps of the eigen decomposition
+const MAX_JACOBI_SWEEPS: usize = 100;
+
+/// Eigen decomposition of a symmetric matrix with the cyclic Jacobi method
+///
+/// # Arguments
+///
+/// * `matrix` - Symmetric matrix (e.g. a covariance or correlation matrix)
+///
+/// # Returns
+///
+/// * `(Vec<f64>, Array2<f64>)` - Eigenvalues in descending order and the matching eigenvectors as columns
+pub fn symmetric_eigen(matrix: &Array2<f64>) -> (Vec<f64>, Array2<f64>) {
+    let n = matrix.nrows();
+    let mut a = matrix.clone();
+    let mut v = Array2::<f64>::eye(n);
+
+    for _ in 0..MAX_JACOBI_SWEEPS {
+        let off_diagonal = (0..n)
+            .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[(i, j)].powi(2))
+            .sum::<f64>();
+        if off_diagonal < 1e-22 {
+            break;
+        }
+
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[(p, q)].abs() < 1e-15 {
+                    continue;
+                }
+                // Rotation angle that zeroes the (p, q) element
+                let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for k in 0..n {
+                    let akp = a[(k, p)];
+                    let akq = a[(k, q)];
+                    a[(k, p)] = c * akp - s * akq;
+                    a[(k, q)] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let apk = a[(p, k)];
+                    let aqk = a[(q, k)];
+                    a[(p, k)] = c * apk - s * aqk;
+                    a[(q, k)] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let vkp = v[(k, p)];
+                    let vkq = v[(k, q)];
+                    v[(k, p)] = c * vkp - s * vkq;
+                    v[(k, q)] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order = (0..n).collect::<Vec<usize>>();
+    order.sort_by(|i, j| a[(*j, *j)].partial_cmp(&a[(*i, *i)]).unwrap_or(std::cmp::Ordering::Equal));
+
+    let eigenvalues = order.iter().map(|i| a[(*i, *i)]).collect::<Vec<f64>>();
+    let mut eigenvectors = Array2::<f64>::zeros((n, n));
+    for (col, i) in order.iter().enumerate() {
+        // Orient each eigenvector so that its loadings sum to a positive value
+        let sign = if v.column(*i).sum() < 0.0 { -1.0 } else { 1.0 };
+        for k in 0..n {
+            eigenvectors[(k, col)] = sign * v[(k, *i)];
+        }
+    }
+
+    (eigenvalues, eigenvectors)
+}
+
+/// Removes the timestamp column and the periods with missing returns from a returns DataFrame
+pub(crate) fn returns_matrix(returns: &DataFrame) -> Result<DataFrame, Box<dyn Error>> {
+    let mut returns = returns.clone();
+    let _ = returns.drop_in_place("timestamp");
+    let returns = returns.drop_nulls::<String>(None)?;
+    if returns.width() < 2 || returns.height() < 2 {
+        return Err("At least two securities with overlapping returns are required".into());
+    }
+    Ok(returns)
+}
+
+/// Principal component analysis of the returns of a universe of securities
+///
+/// * `symbols` - Symbols of the securities
+/// * `eigenvalues` - Variance explained by each component in descending order
+/// * `explained_variance_ratio` - Share of the total variance explained by each component in percent
+/// * `loadings` - Eigenvectors of the covariance (or correlation) matrix with one row per security
+///   and one column per component
+/// * `standardized` - Whether the components were extracted from the correlation matrix
+#[derive(Debug, Clone)]
+pub struct PrincipalComponents {
+    pub symbols: Vec<String>,
+    pub eigenvalues: Vec<f64>,
+    pub explained_variance_ratio: Vec<f64>,
+    pub loadings: Array2<f64>,
+    pub standardized: bool,
+}
+
+impl PrincipalComponents {
+    /// Extracts the principal components of a returns DataFrame
+    ///
+    /// # Arguments
+    ///
+    /// * `returns` - Polars DataFrame of security returns (an optional timestamp column is ignored)
+    /// * `standardize` - Extract the components from the correlation matrix instead of the covariance matrix,
+    ///   so that high volatility securities do not dominate the first components
+    ///
+    /// # Returns
+    ///
+    /// * `PrincipalComponents` - Explained variance and factor loadings
+    pub fn compute(returns: &DataFrame, standardize: bool) -> Result<PrincipalComponents, Box<dyn Error>> {
+        let returns = returns_matrix(returns)?;
+        let symbols = returns.get_column_names().iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let matrix = if standardize {
+            correlation_matrix(&returns)?
+        } else {
+            covariance_matrix(&returns)?
+        };
+        let (eigenvalues, loadings) = symmetric_eigen(&matrix);
+        let eigenvalues = eigenvalues.into_iter().map(|x| x.max(0.0)).collect::<Vec<f64>>();
+        let total = eigenvalues.iter().sum::<f64>();
+        let explained_variance_ratio = eigenvalues.iter()
+            .map(|x| if total > 0.0 { x / total * 100.0 } else { 0.0 })
+            .collect::<Vec<f64>>();
+
+        Ok(PrincipalComponents {
+            symbols,
+            eigenvalues,
+            explained_variance_ratio,
+            loadings,
+            standardized: standardize,
+        })
+    }
+
+    /// Labels of the components (PC1, PC2, ...)
+    pub fn component_names(&self) -> Vec<String> {
+        (1..=self.eigenvalues.len()).map(|i| format!("PC{}", i)).collect()
+    }
+
+    /// Share of the total variance explained by the first `n` components in percent,
+    /// high values of the first few components indicate a concentrated universe
+    pub fn absorption_ratio(&self, n: usize) -> f64 {
+        self.explained_variance_ratio.iter().take(n).sum()
+    }
+
+    /// Explained variance of each component
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - component, eigenvalue, explained_variance and cumulative_explained_variance (percent) columns
+    pub fn explained_variance(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let cumulative = self.explained_variance_ratio.iter()
+            .scan(0.0, |acc, x| { *acc += x; Some(*acc) })
+            .collect::<Vec<f64>>();
+        let df = df!(
+            "component" => self.component_names(),
+            "eigenvalue" => self.eigenvalues.clone(),
+            "explained_variance" => self.explained_variance_ratio.clone(),
+            "cumulative_explained_variance" => cumulative,
+        )?;
+        Ok(df)
+    }
+
+    /// Factor loadings of each security
+    ///
+    /// # Arguments
+    ///
+    /// * `n_components` - Number of leading components to return (all components if None)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - symbol column and one loadings column per component
+    pub fn loadings(&self, n_components: Option<usize>) -> Result<DataFrame, Box<dyn Error>> {
+        let n = n_components.unwrap_or(self.eigenvalues.len()).min(self.eigenvalues.len());
+        let mut columns = vec![Series::new("symbol", self.symbols.clone())];
+        for (i, name) in self.component_names().iter().take(n).enumerate() {
+            columns.push(Series::new(name, self.loadings.column(i).to_vec()));
+        }
+        Ok(DataFrame::new(columns)?)
+    }
+}
+
+/// Computes the pairwise beta matrix of a returns DataFrame
+///
+/// # Arguments
+///
+/// * `returns` - Polars DataFrame of security returns (an optional timestamp column is ignored)
+///
+/// # Returns
+///
+/// * `DataFrame` - symbol column and one column per security, where the value in row i and column j
+///   is the beta of security i on security j (cov(i, j) / var(j))
+pub fn beta_matrix(returns: &DataFrame) -> Result<DataFrame, Box<dyn Error>> {
+    let returns = returns_matrix(returns)?;
+    let symbols = returns.get_column_names().iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let cov = covariance_matrix(&returns)?;
+
+    let mut columns = vec![Series::new("symbol", symbols.clone())];
+    for (j, symbol) in symbols.iter().enumerate() {
+        let variance = cov[(j, j)];
+        let betas = (0..symbols.len())
+            .map(|i| if variance > 0.0 { cov[(i, j)] / variance } else { f64::NAN })
+            .collect::<Vec<f64>>();
+        columns.push(Series::new(symbol, betas));
+    }
+    Ok(DataFrame::new(columns)?)
+}
@@ -0,0 +1,280 @@
+use std::error::Error;
+use chrono::{Datelike, NaiveDateTime};
+use polars::prelude::*;
+
+use crate::analytics::performance::TickerPerformance;
+use crate::analytics::technicals::TechnicalIndicators;
+use crate::data::news::NewsOptions;
+use crate::models::ticker::Ticker;
+
+
+/// Technical indicator computed as a model feature, with the same parameters as `TechnicalIndicators`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FeatureIndicator {
+    Sma(usize),
+    Ema(usize),
+    Rsi(usize),
+    Macd(usize, usize, usize),
+    Bb(usize, f64),
+    Atr(usize),
+    Sd(usize),
+    Roc(usize),
+    Mfi(usize),
+    Obv,
+}
+
+impl FeatureIndicator {
+    /// Number of periods before the indicator is fully warmed up
+    pub fn warmup(&self) -> usize {
+        match self {
+            FeatureIndicator::Sma(p) | FeatureIndicator::Ema(p) | FeatureIndicator::Rsi(p) |
+            FeatureIndicator::Bb(p, _) | FeatureIndicator::Atr(p) | FeatureIndicator::Sd(p) |
+            FeatureIndicator::Roc(p) | FeatureIndicator::Mfi(p) => *p,
+            FeatureIndicator::Macd(_, slow, signal) => slow + signal,
+            FeatureIndicator::Obv => 0,
+        }
+    }
+
+    /// Computes the indicator for the ticker
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with the timestamp and indicator columns
+    pub async fn compute(&self, ticker: &Ticker) -> Result<DataFrame, Box<dyn Error>> {
+        let df = match *self {
+            FeatureIndicator::Sma(p) => ticker.sma(p, None).await?,
+            FeatureIndicator::Ema(p) => ticker.ema(p, None).await?,
+            FeatureIndicator::Rsi(p) => ticker.rsi(p, None).await?,
+            FeatureIndicator::Macd(fast, slow, signal) => ticker.macd(fast, slow, signal, None).await?,
+            FeatureIndicator::Bb(p, std_dev) => ticker.bb(p, std_dev, None).await?,
+            FeatureIndicator::Atr(p) => ticker.atr(p).await?,
+            FeatureIndicator::Sd(p) => ticker.sd(p, None).await?,
+            FeatureIndicator::Roc(p) => ticker.roc(p, None).await?,
+            FeatureIndicator::Mfi(p) => ticker.mfi(p).await?,
+            FeatureIndicator::Obv => ticker.obv().await?,
+        };
+        // The indicator methods also return the price columns they are computed from
+        let columns = df.get_column_names().into_iter()
+            .filter(|x| !["open", "high", "low", "close", "adjclose", "volume"].contains(x))
+            .collect::<Vec<&str>>();
+        Ok(df.select(columns)?)
+    }
+}
+
+/// Specification of the features of a model dataset
+///
+/// * `lags` - Lags of the period returns to include (default 1, 2, 3, 5 and 10)
+/// * `indicators` - Technical indicators to include (default RSI 14, MACD 12/26/9, Bollinger Bands 20/2 and ATR 14)
+/// * `calendar` - Whether to include the day of week, day of month, month and quarter (default true)
+/// * `sentiment` - Whether to include the daily news article count and sentiment (default false, slow)
+/// * `horizon` - Number of periods of the forward return target (default 1)
+/// * `drop_nulls` - Whether to drop the warm-up rows and the rows without a target (default true),
+///   disable to keep the latest rows for out-of-sample predictions
+#[derive(Debug, Clone)]
+pub struct FeatureSpec {
+    pub lags: Vec<usize>,
+    pub indicators: Vec<FeatureIndicator>,
+    pub calendar: bool,
+    pub sentiment: bool,
+    pub horizon: usize,
+    pub drop_nulls: bool,
+}
+
+impl Default for FeatureSpec {
+    fn default() -> Self {
+        FeatureSpec {
+            lags: vec![1, 2, 3, 5, 10],
+            indicators: vec![
+                FeatureIndicator::Rsi(14),
+                FeatureIndicator::Macd(12, 26, 9),
+                FeatureIndicator::Bb(20, 2.0),
+                FeatureIndicator::Atr(14),
+            ],
+            calendar: true,
+            sentiment: false,
+            horizon: 1,
+            drop_nulls: true,
+        }
+    }
+}
+
+impl FeatureSpec {
+    pub fn new() -> FeatureSpec {
+        FeatureSpec::default()
+    }
+
+    pub fn lags(mut self, lags: Vec<usize>) -> FeatureSpec {
+        self.lags = lags;
+        self
+    }
+
+    pub fn indicators(mut self, indicators: Vec<FeatureIndicator>) -> FeatureSpec {
+        self.indicators = indicators;
+        self
+    }
+
+    pub fn calendar(mut self, calendar: bool) -> FeatureSpec {
+        self.calendar = calendar;
+        self
+    }
+
+    pub fn sentiment(mut self, sentiment: bool) -> FeatureSpec {
+        self.sentiment = sentiment;
+        self
+    }
+
+    pub fn horizon(mut self, horizon: usize) -> FeatureSpec {
+        self.horizon = horizon.max(1);
+        self
+    }
+
+    pub fn drop_nulls(mut self, drop_nulls: bool) -> FeatureSpec {
+        self.drop_nulls = drop_nulls;
+        self
+    }
+
+    /// Number of leading rows without complete features
+    pub fn warmup(&self) -> usize {
+        let lags = self.lags.iter().max().copied().unwrap_or(0);
+        let indicators = self.indicators.iter().map(|x| x.warmup()).max().unwrap_or(0);
+        lags.max(indicators)
+    }
+}
+
+pub trait FeatureEngineering {
+    fn features(&self, spec: FeatureSpec) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+}
+
+impl FeatureEngineering for Ticker {
+    /// Generates a model dataset of the ticker, with one row per period and no look-ahead in the features
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - Feature specification
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, return (period return %), return_lag_<k>, the indicator columns,
+    ///   the optional calendar and sentiment columns, target_return (forward return % over the horizon)
+    ///   and target_direction (1 if the target return is positive, else 0)
+    async fn features(&self, spec: FeatureSpec) -> Result<DataFrame, Box<dyn Error>> {
+        let returns = self.returns().await?;
+        let period_returns = returns.column("roc-1")?.clone();
+        let mut df = DataFrame::new(vec![
+            returns.column("timestamp")?.clone(),
+            period_returns.clone().with_name("return"),
+        ])?;
+
+        for lag in &spec.lags {
+            df.with_column(period_returns.shift(*lag as i64).with_name(&format!("return_lag_{}", lag)))?;
+        }
+
+        for indicator in &spec.indicators {
+            let values = indicator.compute(self).await?;
+            df = df.join(&values, ["timestamp"], ["timestamp"], JoinArgs::new(JoinType::Left))?;
+        }
+
+        if spec.calendar {
+            let timestamps = df.column("timestamp")?.datetime()?.as_datetime_iter()
+                .collect::<Vec<Option<NaiveDateTime>>>();
+            df.with_column(Series::new("day_of_week", timestamps.iter()
+                .map(|x| x.map(|d| d.weekday().num_days_from_monday())).collect::<Vec<Option<u32>>>()))?;
+            df.with_column(Series::new("day_of_month", timestamps.iter()
+                .map(|x| x.map(|d| d.day())).collect::<Vec<Option<u32>>>()))?;
+            df.with_column(Series::new("month", timestamps.iter()
+                .map(|x| x.map(|d| d.month())).collect::<Vec<Option<u32>>>()))?;
+            df.with_column(Series::new("quarter", timestamps.iter()
+                .map(|x| x.map(|d| (d.month() - 1) / 3 + 1)).collect::<Vec<Option<u32>>>()))?;
+        }
+
+        if spec.sentiment {
+            match self.sentiment_returns(NewsOptions::default()).await {
+                Ok(sentiment) => {
+                    let sentiment = sentiment.select(["timestamp", "articles", "sentiment"])?;
+                    df = df.join(&sentiment, ["timestamp"], ["timestamp"], JoinArgs::new(JoinType::Left))?;
+                    // Periods without news have no articles and a neutral sentiment
+                    df = df.lazy()
+                        .with_columns([
+                            col("articles").fill_null(lit(0u32)),
+                            col("sentiment").fill_null(lit(0.0)),
+                        ])
+                        .collect()?;
+                }
+                Err(e) => eprintln!("Error Fetching News Sentiment for {}: {}", &self.ticker, e),
+            }
+        }
+
+        let values = period_returns.f64()?.to_vec();
+        let target = (0..values.len())
+            .map(|i| {
+                if i + spec.horizon >= values.len() {
+                    return None;
+                }
+                values[i + 1..=i + spec.horizon].iter()
+                    .try_fold(1.0, |acc, x| x.map(|r| acc * (1.0 + r / 100.0)))
+                    .map(|growth| (growth - 1.0) * 100.0)
+            })
+            .collect::<Vec<Option<f64>>>();
+        df.with_column(Series::new("target_direction", target.iter()
+            .map(|x| x.map(|r| if r > 0.0 { 1u32 } else { 0u32 })).collect::<Vec<Option<u32>>>()))?;
+        df.with_column(Series::new("target_return", target))?;
+
+        if spec.drop_nulls {
+            let warmup = spec.warmup().min(df.height());
+            df = df.slice(warmup as i64, df.height() - warmup).drop_nulls::<String>(None)?;
+        }
+        Ok(df)
+    }
+}
+
+/// Splits a model dataset chronologically into a training and a test set
+///
+/// # Arguments
+///
+/// * `df` - Model dataset sorted by timestamp
+/// * `test_size` - Fraction of the rows in the test set in decimal (e.g. 0.2 for 20%)
+/// * `gap` - Rows dropped between the training and test sets, set to the target horizon to avoid overlapping targets
+///
+/// # Returns
+///
+/// * `(DataFrame, DataFrame)` - Training and test sets
+pub fn train_test_split(df: &DataFrame, test_size: f64, gap: usize) -> Result<(DataFrame, DataFrame), Box<dyn Error>> {
+    if !(0.0..1.0).contains(&test_size) {
+        return Err("The test size must be between 0 and 1".into());
+    }
+    let height = df.height();
+    let test_rows = (height as f64 * test_size).round() as usize;
+    let train_rows = height.saturating_sub(test_rows + gap);
+    if train_rows == 0 || test_rows == 0 {
+        return Err(format!("Not enough rows ({}) for a test size of {} and a gap of {}", height, test_size, gap).into());
+    }
+    Ok((df.slice(0, train_rows), df.slice((height - test_rows) as i64, test_rows)))
+}
+
+/// Splits a model dataset into expanding-window walk-forward training and test sets
+///
+/// The rows are divided into `n_splits + 1` blocks, each split trains on all the blocks before its test block.
+///
+/// # Arguments
+///
+/// * `df` - Model dataset sorted by timestamp
+/// * `n_splits` - Number of splits (e.g. 5)
+/// * `gap` - Rows dropped between each training and test set
+///
+/// # Returns
+///
+/// * `Vec<(DataFrame, DataFrame)>` - Training and test sets of each split
+pub fn walk_forward_splits(df: &DataFrame, n_splits: usize, gap: usize) -> Result<Vec<(DataFrame, DataFrame)>, Box<dyn Error>> {
+    let block = df.height() / (n_splits + 1);
+    if n_splits == 0 || block <= gap {
+        return Err(format!("Not enough rows ({}) for {} splits with a gap of {}", df.height(), n_splits, gap).into());
+    }
+    let splits = (1..=n_splits)
+        .map(|k| {
+            let test_start = k * block;
+            let test_rows = if k == n_splits { df.height() - test_start } else { block };
+            (df.slice(0, test_start - gap), df.slice(test_start as i64, test_rows))
+        })
+        .collect::<Vec<(DataFrame, DataFrame)>>();
+    Ok(splits)
+}
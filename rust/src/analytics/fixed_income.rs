@@ -0,0 +1,217 @@
+use std::error::Error;
+use polars::prelude::*;
+
+use crate::data::ticker::TickerData;
+use crate::models::ticker::TickerBuilder;
+
+
+/// Treasury yield indices available on Yahoo Finance with their maturities in years
+pub const TREASURY_YIELD_TICKERS: [(&str, &str, f64); 4] = [
+    ("^IRX", "13W", 0.25),
+    ("^FVX", "5Y", 5.0),
+    ("^TNX", "10Y", 10.0),
+    ("^TYX", "30Y", 30.0),
+];
+
+
+#[derive(Debug, Clone, Copy)]
+pub struct Bond {
+    pub face_value: f64,
+    pub coupon_rate: f64,
+    pub years_to_maturity: f64,
+    pub frequency: u32,
+}
+
+impl Bond {
+    /// Creates a new fixed-coupon Bond
+    ///
+    /// # Arguments
+    ///
+    /// * `face_value` - Face (par) value of the bond (e.g. 1000.0)
+    /// * `coupon_rate` - Annual coupon rate in decimal (e.g 0.05 for 5%)
+    /// * `years_to_maturity` - Time to maturity (in years)
+    /// * `frequency` - Number of coupon payments per year (e.g. 2 for semi-annual)
+    ///
+    /// # Returns
+    ///
+    /// * `Bond` struct
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use finalytics::analytics::fixed_income::Bond;
+    ///
+    /// let bond = Bond::new(1000.0, 0.05, 10.0, 2);
+    /// let price = bond.price(0.04);
+    /// let ytm = bond.yield_to_maturity(price).unwrap();
+    /// println!("Price: {:.2}, YTM: {:.4}, Duration: {:.2}", price, ytm, bond.modified_duration(ytm));
+    /// ```
+    pub fn new(face_value: f64, coupon_rate: f64, years_to_maturity: f64, frequency: u32) -> Self {
+        Self {
+            face_value,
+            coupon_rate,
+            years_to_maturity,
+            frequency: frequency.max(1),
+        }
+    }
+
+    /// Returns the remaining cash flows of the bond as (time in years, amount) pairs
+    pub fn cash_flows(&self) -> Vec<(f64, f64)> {
+        let freq = self.frequency as f64;
+        let coupon = self.face_value * self.coupon_rate / freq;
+        let periods = (self.years_to_maturity * freq).ceil().max(1.0) as usize;
+        // The first coupon may fall in a fractional period when the bond is between payment dates
+        let offset = self.years_to_maturity - (periods - 1) as f64 / freq;
+        (0..periods).map(|i| {
+            let t = offset + i as f64 / freq;
+            let amount = if i == periods - 1 { coupon + self.face_value } else { coupon };
+            (t, amount)
+        }).collect::<Vec<(f64, f64)>>()
+    }
+
+    /// Computes the price of the bond for a given yield to maturity
+    ///
+    /// # Arguments
+    ///
+    /// * `ytm` - Annual yield to maturity in decimal (e.g 0.04 for 4%), compounded at the coupon frequency
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Bond price
+    pub fn price(&self, ytm: f64) -> f64 {
+        let freq = self.frequency as f64;
+        self.cash_flows().iter()
+            .map(|(t, cf)| cf / (1.0 + ytm / freq).powf(t * freq))
+            .sum()
+    }
+
+    /// Computes the yield to maturity of the bond for a given price
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Bond price
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Annual yield to maturity in decimal
+    pub fn yield_to_maturity(&self, price: f64) -> Result<f64, Box<dyn Error>> {
+        if price <= 0.0 {
+            return Err("Bond price must be positive".into());
+        }
+        // Price is monotonically decreasing in yield, so bisection always converges
+        let mut low = -0.99 * self.frequency as f64;
+        let mut high = 10.0;
+        if self.price(high) > price || self.price(low) < price {
+            return Err("Yield to maturity not found within bounds".into());
+        }
+        for _ in 0..200 {
+            let mid = (low + high) / 2.0;
+            if self.price(mid) > price {
+                low = mid;
+            } else {
+                high = mid;
+            }
+            if high - low < 1e-10 {
+                break;
+            }
+        }
+        Ok((low + high) / 2.0)
+    }
+
+    /// Computes the Macaulay duration of the bond (in years)
+    ///
+    /// # Arguments
+    ///
+    /// * `ytm` - Annual yield to maturity in decimal (e.g 0.04 for 4%)
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Macaulay duration
+    pub fn macaulay_duration(&self, ytm: f64) -> f64 {
+        let freq = self.frequency as f64;
+        let price = self.price(ytm);
+        self.cash_flows().iter()
+            .map(|(t, cf)| t * cf / (1.0 + ytm / freq).powf(t * freq))
+            .sum::<f64>() / price
+    }
+
+    /// Computes the modified duration of the bond (in years)
+    ///
+    /// # Arguments
+    ///
+    /// * `ytm` - Annual yield to maturity in decimal (e.g 0.04 for 4%)
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Modified duration
+    pub fn modified_duration(&self, ytm: f64) -> f64 {
+        self.macaulay_duration(ytm) / (1.0 + ytm / self.frequency as f64)
+    }
+
+    /// Computes the convexity of the bond
+    ///
+    /// # Arguments
+    ///
+    /// * `ytm` - Annual yield to maturity in decimal (e.g 0.04 for 4%)
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Convexity
+    pub fn convexity(&self, ytm: f64) -> f64 {
+        let freq = self.frequency as f64;
+        let price = self.price(ytm);
+        self.cash_flows().iter()
+            .map(|(t, cf)| {
+                let n = t * freq;
+                cf * n * (n + 1.0) / (1.0 + ytm / freq).powf(n + 2.0)
+            })
+            .sum::<f64>() / (price * freq * freq)
+    }
+
+    /// Estimates the percentage price change of the bond for a change in yield
+    /// using the duration and convexity approximation
+    ///
+    /// # Arguments
+    ///
+    /// * `ytm` - Annual yield to maturity in decimal (e.g 0.04 for 4%)
+    /// * `yield_change` - Change in yield in decimal (e.g 0.01 for a 100bp increase)
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Estimated price change in percent
+    pub fn price_change(&self, ytm: f64, yield_change: f64) -> f64 {
+        (-self.modified_duration(ytm) * yield_change
+            + 0.5 * self.convexity(ytm) * yield_change.powi(2)) * 100.0
+    }
+}
+
+
+/// Fetches the current US Treasury yield curve from Yahoo Finance
+///
+/// # Returns
+///
+/// * `DataFrame` - Tenor, maturity (in years) and yield (in percent) of each treasury yield index
+pub async fn treasury_yield_curve() -> Result<DataFrame, Box<dyn Error>> {
+    let mut tenors = Vec::new();
+    let mut maturities = Vec::new();
+    let mut yields = Vec::new();
+    for (symbol, tenor, maturity) in TREASURY_YIELD_TICKERS.iter() {
+        match TickerBuilder::new().ticker(symbol).build().get_quote().await {
+            Ok(quote) => {
+                tenors.push(tenor.to_string());
+                maturities.push(*maturity);
+                yields.push(quote.price);
+            }
+            Err(e) => eprintln!("Error fetching treasury yield {}: {}", symbol, e),
+        }
+    }
+    if yields.is_empty() {
+        return Err("No treasury yields found".into());
+    }
+    let df = df!(
+        "tenor" => tenors,
+        "maturity" => maturities,
+        "yield" => yields
+    )?;
+    Ok(df)
+}
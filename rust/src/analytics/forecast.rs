@@ -0,0 +1,326 @@
+use std::error::Error;
+use chrono::{Datelike, Duration, Months, NaiveDateTime, Weekday};
+use polars::prelude::*;
+
+use crate::analytics::statistics::z_score;
+use crate::data::config::{Interval, MarketCalendar};
+use crate::data::ticker::TickerData;
+use crate::models::ticker::Ticker;
+
+
+/// Default number of periods forecast
+pub const DEFAULT_FORECAST_HORIZON: usize = 20;
+
+/// Maximum autoregressive order tried by the automatic ARIMA selection
+const MAX_AR_ORDER: usize = 5;
+
+/// Time-series model used to forecast the ticker prices
+///
+/// Ets: Holt's linear trend exponential smoothing, ETS(A,A,N), with the smoothing parameters fitted by least squares
+/// Arima(p, d): ARIMA(p, d, 0) model, an AR(p) process with intercept fitted by least squares on the d-times differenced series
+/// AutoArima: ARIMA(p, 1, 0) model with the order p (0 - 5) selected by the Akaike information criterion
+///
+/// The models are fitted on the log prices, so the forecasts are positive and the prediction intervals asymmetric
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForecastModel {
+    Ets,
+    Arima(usize, usize),
+    AutoArima,
+}
+
+impl ForecastModel {
+    pub fn from_str(s: &str) -> ForecastModel {
+        match s {
+            "ets" => ForecastModel::Ets,
+            "arima" => ForecastModel::Arima(1, 1),
+            "auto_arima" => ForecastModel::AutoArima,
+            _ => ForecastModel::AutoArima,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            ForecastModel::Ets => "ets".to_string(),
+            ForecastModel::Arima(_, _) => "arima".to_string(),
+            ForecastModel::AutoArima => "auto_arima".to_string(),
+        }
+    }
+}
+
+/// Forecast of a ticker's prices
+///
+/// * `model` - Description of the fitted model, e.g. "ARIMA(2,1,0)"
+/// * `confidence_level` - Confidence level of the prediction intervals
+/// * `residual_std` - Standard deviation of the one-step-ahead residuals (log prices)
+/// * `forecast` - DataFrame with columns step, timestamp, forecast, lower and upper (prediction interval)
+#[derive(Debug, Clone)]
+pub struct Forecast {
+    pub model: String,
+    pub confidence_level: f64,
+    pub residual_std: f64,
+    pub forecast: DataFrame,
+}
+
+/// Point forecasts and forecast standard errors of a series
+///
+/// * `model` - Description of the fitted model
+/// * `mean` - Point forecast of each step
+/// * `std_err` - Standard error of the forecast of each step
+/// * `residual_std` - Standard deviation of the one-step-ahead residuals
+#[derive(Debug, Clone)]
+pub struct SeriesForecast {
+    pub model: String,
+    pub mean: Vec<f64>,
+    pub std_err: Vec<f64>,
+    pub residual_std: f64,
+}
+
+/// Forecasts a series with a time-series model
+///
+/// # Arguments
+///
+/// * `values` - Series values in chronological order
+/// * `model` - Forecast model
+/// * `horizon` - Number of periods to forecast
+///
+/// # Returns
+///
+/// * `SeriesForecast` struct
+pub fn forecast_series(values: &[f64], model: ForecastModel, horizon: usize) -> Result<SeriesForecast, Box<dyn Error>> {
+    match model {
+        ForecastModel::Ets => holt_forecast(values, horizon),
+        ForecastModel::Arima(p, d) => arima_forecast(values, p, d, horizon).map(|(forecast, _)| forecast),
+        ForecastModel::AutoArima => {
+            let mut best: Option<(SeriesForecast, f64)> = None;
+            for p in 0..=MAX_AR_ORDER {
+                match arima_forecast(values, p, 1, horizon) {
+                    Ok((forecast, aic)) => {
+                        if best.as_ref().map(|(_, best_aic)| aic < *best_aic).unwrap_or(true) {
+                            best = Some((forecast, aic));
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+            best.map(|(forecast, _)| forecast).ok_or_else(|| "Not enough observations to fit an ARIMA model".into())
+        }
+    }
+}
+
+pub trait Forecasting {
+    fn forecast(&self, model: ForecastModel, horizon: Option<usize>) -> impl std::future::Future<Output = Result<Forecast, Box<dyn Error>>>;
+}
+
+impl Forecasting for Ticker {
+    /// Forecasts the ticker prices with prediction intervals at the ticker confidence level
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Forecast model (e.g. ForecastModel::AutoArima)
+    /// * `horizon` - Number of periods to forecast (default 20)
+    ///
+    /// # Returns
+    ///
+    /// * `Forecast` struct
+    async fn forecast(&self, model: ForecastModel, horizon: Option<usize>) -> Result<Forecast, Box<dyn Error>> {
+        let horizon = horizon.unwrap_or(DEFAULT_FORECAST_HORIZON);
+        let ohlcv = self.get_chart().await?;
+        let prices = ohlcv.column(&self.price_type.to_string())?.f64()?.into_iter()
+            .flatten().collect::<Vec<f64>>();
+        if prices.iter().any(|x| *x <= 0.0) {
+            return Err("Forecasts require positive prices".into());
+        }
+        let log_prices = prices.iter().map(|x| x.ln()).collect::<Vec<f64>>();
+        let fitted = forecast_series(&log_prices, model, horizon)?;
+
+        let z = z_score(1.0 - (1.0 - self.confidence_level) / 2.0);
+        let last = ohlcv.column("timestamp")?.datetime()?.as_datetime_iter().flatten().last()
+            .ok_or("No price data to forecast")?;
+        let df = df!(
+            "step" => (1..=horizon as u32).collect::<Vec<u32>>(),
+            "timestamp" => future_timestamps(last, self.interval, self.calendar, horizon),
+            "forecast" => fitted.mean.iter().map(|x| x.exp()).collect::<Vec<f64>>(),
+            "lower" => fitted.mean.iter().zip(fitted.std_err.iter()).map(|(m, s)| (m - z * s).exp()).collect::<Vec<f64>>(),
+            "upper" => fitted.mean.iter().zip(fitted.std_err.iter()).map(|(m, s)| (m + z * s).exp()).collect::<Vec<f64>>()
+        )?;
+        Ok(Forecast {
+            model: fitted.model,
+            confidence_level: self.confidence_level,
+            residual_std: fitted.residual_std,
+            forecast: df,
+        })
+    }
+}
+
+/// Timestamps of the periods following the last observation, skipping weekends for daily bars of markets closed on weekends
+fn future_timestamps(last: NaiveDateTime, interval: Interval, calendar: MarketCalendar, horizon: usize) -> Vec<NaiveDateTime> {
+    let mut timestamps = Vec::with_capacity(horizon);
+    let mut current = last;
+    for _ in 0..horizon {
+        current = match interval {
+            Interval::TwoMinutes => current + Duration::minutes(2),
+            Interval::FiveMinutes => current + Duration::minutes(5),
+            Interval::FifteenMinutes => current + Duration::minutes(15),
+            Interval::ThirtyMinutes => current + Duration::minutes(30),
+            Interval::SixtyMinutes | Interval::OneHour => current + Duration::hours(1),
+            Interval::NinetyMinutes => current + Duration::minutes(90),
+            Interval::OneDay => {
+                let mut next = current + Duration::days(1);
+                while !calendar.includes_weekends() && matches!(next.weekday(), Weekday::Sat | Weekday::Sun) {
+                    next += Duration::days(1);
+                }
+                next
+            }
+            Interval::FiveDays | Interval::OneWeek => current + Duration::weeks(1),
+            Interval::OneMonth => current.checked_add_months(Months::new(1)).unwrap_or(current),
+            Interval::ThreeMonths => current.checked_add_months(Months::new(3)).unwrap_or(current),
+        };
+        timestamps.push(current);
+    }
+    timestamps
+}
+
+/// Holt's linear trend model with the smoothing parameters selected by a grid search on the one-step-ahead errors
+fn holt_forecast(values: &[f64], horizon: usize) -> Result<SeriesForecast, Box<dyn Error>> {
+    let n = values.len();
+    if n < 10 {
+        return Err("At least 10 observations are required to fit an ETS model".into());
+    }
+    let mut best = (f64::MAX, 0.0, 0.0);
+    for a in 1..20 {
+        for b in 0..11 {
+            let (alpha, beta) = (a as f64 * 0.05, b as f64 * 0.05);
+            let (sse, _, _) = holt_filter(values, alpha, beta);
+            if sse < best.0 {
+                best = (sse, alpha, beta);
+            }
+        }
+    }
+    let (sse, alpha, beta) = best;
+    let (_, level, trend) = holt_filter(values, alpha, beta);
+    let sigma = (sse / (n - 3) as f64).sqrt();
+
+    let mean = (1..=horizon).map(|h| level + h as f64 * trend).collect::<Vec<f64>>();
+    let std_err = (1..=horizon)
+        .map(|h| {
+            let variance = 1.0 + (1..h).map(|j| (alpha * (1.0 + j as f64 * beta)).powi(2)).sum::<f64>();
+            sigma * variance.sqrt()
+        })
+        .collect::<Vec<f64>>();
+    Ok(SeriesForecast {
+        model: format!("ETS(A,A,N) alpha={:.2} beta={:.2}", alpha, beta),
+        mean,
+        std_err,
+        residual_std: sigma,
+    })
+}
+
+/// Runs Holt's filter, returning the sum of squared one-step-ahead errors and the final level and trend
+fn holt_filter(values: &[f64], alpha: f64, beta: f64) -> (f64, f64, f64) {
+    let mut level = values[0];
+    let mut trend = values[1] - values[0];
+    let mut sse = 0.0;
+    for y in &values[1..] {
+        let error = y - (level + trend);
+        sse += error * error;
+        level += trend + alpha * error;
+        trend += alpha * beta * error;
+    }
+    (sse, level, trend)
+}
+
+/// ARIMA(p, d, 0) model fitted by least squares, returning the forecast and the Akaike information criterion
+fn arima_forecast(values: &[f64], p: usize, d: usize, horizon: usize) -> Result<(SeriesForecast, f64), Box<dyn Error>> {
+    let mut differenced = values.to_vec();
+    for _ in 0..d {
+        differenced = differenced.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    let n = differenced.len().saturating_sub(p);
+    if n < p + 10 {
+        return Err(format!("Not enough observations to fit an ARIMA({},{},0) model", p, d).into());
+    }
+
+    // Regression of each differenced value on an intercept and its p previous values
+    let rows = (p..differenced.len())
+        .map(|t| std::iter::once(1.0).chain((1..=p).map(|i| differenced[t - i])).collect::<Vec<f64>>())
+        .collect::<Vec<Vec<f64>>>();
+    let targets = &differenced[p..];
+    let coefficients = least_squares(&rows, targets)?;
+    let residuals = rows.iter().zip(targets.iter())
+        .map(|(x, y)| y - x.iter().zip(coefficients.iter()).map(|(a, b)| a * b).sum::<f64>())
+        .collect::<Vec<f64>>();
+    let sse = residuals.iter().map(|x| x * x).sum::<f64>();
+    let k = p + 1;
+    let sigma = (sse / (n - k) as f64).sqrt();
+    let aic = n as f64 * (sse / n as f64).ln() + 2.0 * k as f64;
+
+    // AR polynomial of the levels, phi(B)(1 - B)^d, so the forecasts can be made on the undifferenced values
+    let mut polynomial = std::iter::once(1.0).chain(coefficients[1..].iter().map(|x| -x)).collect::<Vec<f64>>();
+    for _ in 0..d {
+        let mut next = vec![0.0; polynomial.len() + 1];
+        for (i, c) in polynomial.iter().enumerate() {
+            next[i] += c;
+            next[i + 1] -= c;
+        }
+        polynomial = next;
+    }
+    let phi = polynomial[1..].iter().map(|x| -x).collect::<Vec<f64>>();
+
+    let mut history = values.to_vec();
+    let mut mean = Vec::with_capacity(horizon);
+    for _ in 0..horizon {
+        let t = history.len();
+        let value = coefficients[0] + phi.iter().enumerate().map(|(i, c)| c * history[t - 1 - i]).sum::<f64>();
+        history.push(value);
+        mean.push(value);
+    }
+
+    // Forecast variance from the psi weights of the MA(infinity) representation
+    let mut psi = vec![1.0];
+    for j in 1..horizon {
+        let weight = (1..=j.min(phi.len())).map(|i| phi[i - 1] * psi[j - i]).sum::<f64>();
+        psi.push(weight);
+    }
+    let std_err = (1..=horizon)
+        .map(|h| sigma * psi[..h].iter().map(|x| x * x).sum::<f64>().sqrt())
+        .collect::<Vec<f64>>();
+
+    Ok((SeriesForecast {
+        model: format!("ARIMA({},{},0)", p, d),
+        mean,
+        std_err,
+        residual_std: sigma,
+    }, aic))
+}
+
+/// Solves the least squares problem by Gaussian elimination of the normal equations
+fn least_squares(rows: &[Vec<f64>], targets: &[f64]) -> Result<Vec<f64>, Box<dyn Error>> {
+    let k = rows[0].len();
+    let mut a = vec![vec![0.0; k + 1]; k];
+    for (x, y) in rows.iter().zip(targets.iter()) {
+        for i in 0..k {
+            for j in 0..k {
+                a[i][j] += x[i] * x[j];
+            }
+            a[i][k] += x[i] * y;
+        }
+    }
+    for col in 0..k {
+        let pivot = (col..k).max_by(|i, j| a[*i][col].abs().partial_cmp(&a[*j][col].abs()).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or(col);
+        if a[pivot][col].abs() < 1e-12 {
+            return Err("Singular regression, the series may be constant".into());
+        }
+        a.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for (row, values) in a.iter_mut().enumerate() {
+            if row != col {
+                let factor = values[col] / pivot_row[col];
+                for (x, p) in values[col..=k].iter_mut().zip(&pivot_row[col..=k]) {
+                    *x -= factor * p;
+                }
+            }
+        }
+    }
+    Ok((0..k).map(|i| a[i][k] / a[i][i]).collect())
+}
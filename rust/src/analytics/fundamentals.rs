@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 use std::error::Error;
 use polars::prelude::*;
-use crate::data::config::{StatementFrequency, StatementType};
-use crate::data::ticker::TickerData;
+use serde::Serialize;
+use crate::data::config::{SegmentType, StatementFrequency, StatementType, StatementView};
+use crate::data::ticker::{fetch_quote_fields, TickerData};
 use crate::models::ticker::Ticker;
 use crate::utils::date_utils::{convert_to_quarter, convert_to_year};
 
@@ -11,6 +12,365 @@ pub trait Financials {
     fn income_statement(&self, frequency: StatementFrequency) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
     fn balance_sheet(&self, frequency: StatementFrequency) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
     fn cashflow_statement(&self, frequency: StatementFrequency) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn per_share_fundamentals(&self, frequency: StatementFrequency) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn fundamental_scores(&self) -> impl std::future::Future<Output = Result<FundamentalScores, Box<dyn Error>>>;
+    fn estimate_revisions(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn statement_view(&self, statement_type: StatementType, frequency: StatementFrequency, view: StatementView) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn revenue_segments(&self, segment_type: SegmentType) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+}
+
+/// Revision momentum of analyst EPS estimates
+///
+/// The momentum is "Positive" when the consensus rose over the last 30 days with more upward than downward
+/// revisions, "Negative" in the opposite case and "Neutral" otherwise.
+///
+/// # Arguments
+///
+/// * `trend` - Earnings estimate trend, as returned by `TickerData::get_earnings_trend`
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per estimate period with columns period, end_date, eps_estimate, eps_change_7d_pct,
+///   eps_change_30d_pct, eps_change_90d_pct, net_revisions_7d, net_revisions_30d, revision_ratio_30d
+///   (net over total revisions, from -1 to 1) and momentum
+pub fn estimate_revisions(trend: &DataFrame) -> Result<DataFrame, Box<dyn Error>> {
+    let values = |name: &str| -> Result<Vec<Option<f64>>, Box<dyn Error>> {
+        Ok(trend.column(name)?.cast(&DataType::Float64)?.f64()?.into_iter().collect())
+    };
+    let current = values("eps_current")?;
+    let change = |name: &str| -> Result<Vec<Option<f64>>, Box<dyn Error>> {
+        Ok(current.iter().zip(values(name)?)
+            .map(|(c, ago)| match (c, ago) {
+                (Some(c), Some(ago)) if ago != 0.0 => Some((c - ago) / ago.abs() * 100.0),
+                _ => None,
+            })
+            .collect())
+    };
+    let change_30d = change("eps_30d_ago")?;
+    let (up_7d, down_7d) = (values("up_7d")?, values("down_7d")?);
+    let (up_30d, down_30d) = (values("up_30d")?, values("down_30d")?);
+    let net = |up: &[Option<f64>], down: &[Option<f64>]| up.iter().zip(down)
+        .map(|(u, d)| u.unwrap_or(0.0) - d.unwrap_or(0.0))
+        .collect::<Vec<f64>>();
+    let net_30d = net(&up_30d, &down_30d);
+    let ratio_30d = up_30d.iter().zip(&down_30d)
+        .map(|(u, d)| {
+            let total = u.unwrap_or(0.0) + d.unwrap_or(0.0);
+            (total > 0.0).then(|| (u.unwrap_or(0.0) - d.unwrap_or(0.0)) / total)
+        })
+        .collect::<Vec<Option<f64>>>();
+    let momentum = change_30d.iter().zip(&net_30d)
+        .map(|(change, net)| match change {
+            Some(c) if *c > 0.0 && *net > 0.0 => "Positive",
+            Some(c) if *c < 0.0 && *net < 0.0 => "Negative",
+            _ => "Neutral",
+        })
+        .collect::<Vec<&str>>();
+
+    let df = df!(
+        "period" => trend.column("period")?.str()?.into_no_null_iter().collect::<Vec<&str>>(),
+        "end_date" => trend.column("end_date")?.str()?.into_no_null_iter().collect::<Vec<&str>>(),
+        "eps_estimate" => values("eps_estimate")?,
+        "eps_change_7d_pct" => change("eps_7d_ago")?,
+        "eps_change_30d_pct" => &change_30d,
+        "eps_change_90d_pct" => change("eps_90d_ago")?,
+        "net_revisions_7d" => net(&up_7d, &down_7d),
+        "net_revisions_30d" => &net_30d,
+        "revision_ratio_30d" => &ratio_30d,
+        "momentum" => &momentum
+    )?;
+    Ok(df)
+}
+
+/// Composite fundamental health scores of the latest fiscal year
+///
+/// * `period` - Date of the latest annual statements (YYYY-MM-DD)
+/// * `piotroski_f_score` - Piotroski F-score from 0 to 9, higher is stronger (8-9 strong, 0-2 weak)
+/// * `altman_z_score` - Altman Z-score, below 1.81 is the distress zone and above 2.99 the safe zone
+/// * `beneish_m_score` - Beneish M-score, above -1.78 flags a likely earnings manipulator
+///
+/// Scores are None when the statements lack the items they need.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FundamentalScores {
+    pub period: String,
+    pub piotroski_f_score: Option<f64>,
+    pub altman_z_score: Option<f64>,
+    pub beneish_m_score: Option<f64>,
+}
+
+/// Computes the Piotroski F-score, Altman Z-score and Beneish M-score from annual statements
+///
+/// The scores compare the latest fiscal year with the previous one. F-score criteria that cannot be evaluated
+/// score 0. The Altman Z-score uses the original public company weights, with the book value of equity standing
+/// in for the market value when no market capitalization is given.
+///
+/// # Arguments
+///
+/// * `income_statement` - Annual income statement, as returned by `TickerData::get_fundamentals`
+/// * `balance_sheet` - Annual balance sheet
+/// * `cash_flow` - Annual cash flow statement
+/// * `market_cap` - Market capitalization of the company
+///
+/// # Returns
+///
+/// * `FundamentalScores` - Scores of the latest fiscal year
+pub fn fundamental_scores(income_statement: &DataFrame, balance_sheet: &DataFrame, cash_flow: &DataFrame,
+                          market_cap: Option<f64>) -> Result<FundamentalScores, Box<dyn Error>> {
+    let revenue = statement_values(income_statement, &["TotalRevenue", "OperatingRevenue"])?;
+    let gross_profit = statement_values(income_statement, &["GrossProfit"])?;
+    let net_income = statement_values(income_statement, &["NetIncome"])?;
+    let continuing_income = statement_values(income_statement, &["NetIncomeContinuousOperations", "NetIncome"])?;
+    let ebit = statement_values(income_statement, &["EBIT"])?;
+    let sga = statement_values(income_statement, &["SellingGeneralAndAdministration"])?;
+    let depreciation = statement_values(income_statement, &["ReconciledDepreciation"])?;
+    let total_assets = statement_values(balance_sheet, &["TotalAssets"])?;
+    let total_liabilities = statement_values(balance_sheet, &["TotalLiabilitiesNetMinorityInterest"])?;
+    let current_assets = statement_values(balance_sheet, &["CurrentAssets"])?;
+    let current_liabilities = statement_values(balance_sheet, &["CurrentLiabilities"])?;
+    let long_term_debt = statement_values(balance_sheet, &["LongTermDebt"])?;
+    let receivables = statement_values(balance_sheet, &["AccountsReceivable"])?;
+    let ppe = statement_values(balance_sheet, &["NetPPE"])?;
+    let retained_earnings = statement_values(balance_sheet, &["RetainedEarnings"])?;
+    let equity = statement_values(balance_sheet, &["CommonStockEquity", "StockholdersEquity"])?;
+    let shares = statement_values(balance_sheet, &["OrdinarySharesNumber", "ShareIssued"])?;
+    let operating_cash_flow = statement_values(cash_flow, &["OperatingCashFlow"])?;
+
+    let mut periods = total_assets.keys().cloned().collect::<Vec<String>>();
+    periods.sort();
+    let Some(t) = periods.last().cloned() else {
+        return Err("No total assets in the balance sheet".into());
+    };
+    let p = periods.len().checked_sub(2).map(|i| periods[i].clone());
+    let get = |values: &HashMap<String, f64>, period: &str| values.get(period).copied();
+    // Items that are legitimately absent (e.g. no long-term debt) count as zero in the ratios that allow it
+    let get_or_zero = |values: &HashMap<String, f64>, period: &str| values.get(period).copied().unwrap_or(0.0);
+
+    // Altman Z-score
+    let altman_z_score = (|| {
+        let ta = get(&total_assets, &t)?;
+        let tl = get(&total_liabilities, &t)?;
+        let working_capital = get(&current_assets, &t)? - get(&current_liabilities, &t)?;
+        let market_value = market_cap.or(get(&equity, &t))?;
+        Some(1.2 * working_capital / ta + 1.4 * get_or_zero(&retained_earnings, &t) / ta + 3.3 * get(&ebit, &t)? / ta
+            + 0.6 * market_value / tl + 1.0 * get(&revenue, &t)? / ta)
+    })();
+
+    let (piotroski_f_score, beneish_m_score) = match &p {
+        None => (None, None),
+        Some(p) => {
+            // Piotroski F-score
+            let roa = |period: &str| Some(get(&net_income, period)? / get(&total_assets, period)?);
+            let leverage = |period: &str| Some(get_or_zero(&long_term_debt, period) / get(&total_assets, period)?);
+            let current_ratio = |period: &str| Some(get(&current_assets, period)? / get(&current_liabilities, period)?);
+            let gross_margin = |period: &str| Some(get(&gross_profit, period)? / get(&revenue, period)?);
+            let asset_turnover = |period: &str| Some(get(&revenue, period)? / get(&total_assets, period)?);
+            let criteria = [
+                roa(&t).map(|x| x > 0.0),
+                get(&operating_cash_flow, &t).map(|x| x > 0.0),
+                roa(&t).zip(roa(p)).map(|(a, b)| a > b),
+                get(&operating_cash_flow, &t).zip(get(&net_income, &t)).map(|(cfo, ni)| cfo > ni),
+                leverage(&t).zip(leverage(p)).map(|(a, b)| a < b),
+                current_ratio(&t).zip(current_ratio(p)).map(|(a, b)| a > b),
+                get(&shares, &t).zip(get(&shares, p)).map(|(a, b)| a <= b),
+                gross_margin(&t).zip(gross_margin(p)).map(|(a, b)| a > b),
+                asset_turnover(&t).zip(asset_turnover(p)).map(|(a, b)| a > b),
+            ];
+            let f_score = criteria.iter().filter(|x| **x == Some(true)).count() as f64;
+
+            // Beneish M-score
+            let m_score = (|| {
+                let (sales_t, sales_p) = (get(&revenue, &t)?, get(&revenue, p)?);
+                let (ta_t, ta_p) = (get(&total_assets, &t)?, get(&total_assets, p)?);
+                let (ppe_t, ppe_p) = (get(&ppe, &t)?, get(&ppe, p)?);
+                let (dep_t, dep_p) = (get(&depreciation, &t)?, get(&depreciation, p)?);
+                let dsri = (get(&receivables, &t)? / sales_t) / (get(&receivables, p)? / sales_p);
+                let gmi = gross_margin(p)? / gross_margin(&t)?;
+                let aqi = (1.0 - (get(&current_assets, &t)? + ppe_t) / ta_t) / (1.0 - (get(&current_assets, p)? + ppe_p) / ta_p);
+                let sgi = sales_t / sales_p;
+                let depi = (dep_p / (dep_p + ppe_p)) / (dep_t / (dep_t + ppe_t));
+                let sgai = (get(&sga, &t)? / sales_t) / (get(&sga, p)? / sales_p);
+                let lvgi = ((get(&current_liabilities, &t)? + get_or_zero(&long_term_debt, &t)) / ta_t)
+                    / ((get(&current_liabilities, p)? + get_or_zero(&long_term_debt, p)) / ta_p);
+                let tata = (get(&continuing_income, &t)? - get(&operating_cash_flow, &t)?) / ta_t;
+                let m = -4.84 + 0.92 * dsri + 0.528 * gmi + 0.404 * aqi + 0.892 * sgi + 0.115 * depi
+                    - 0.172 * sgai + 4.679 * tata - 0.327 * lvgi;
+                m.is_finite().then_some(m)
+            })();
+            (Some(f_score), m_score)
+        }
+    };
+
+    Ok(FundamentalScores {
+        period: t,
+        piotroski_f_score,
+        altman_z_score: altman_z_score.filter(|x| x.is_finite()),
+        beneish_m_score,
+    })
+}
+
+/// Expresses each item of a formatted statement as a percentage of a base item
+///
+/// Per-share items are left empty since they are not comparable to the base item.
+///
+/// # Arguments
+///
+/// * `statement` - Formatted statement with an Items column and a column per period
+/// * `base_statement` - Formatted statement holding the base item, matched to `statement` by period
+/// * `base_item` - Base item, e.g. Revenue or Total Assets
+///
+/// # Returns
+///
+/// * `DataFrame` - Items column and the percentage of the base item of each period
+pub fn common_size(statement: &DataFrame, base_statement: &DataFrame, base_item: &str) -> Result<DataFrame, Box<dyn Error>> {
+    let base_row = base_statement.column("Items")?.str()?.into_iter()
+        .position(|x| x == Some(base_item))
+        .ok_or_else(|| format!("{} not found in the base statement", base_item))?;
+    let per_share = statement.column("Items")?.str()?.into_iter()
+        .map(|x| x.is_some_and(|item| item.contains("per Share")))
+        .collect::<Vec<bool>>();
+    let mut columns = vec![statement.column("Items")?.clone()];
+    for period in statement.get_column_names().into_iter().skip(1) {
+        let base = match base_statement.column(period) {
+            Ok(values) => values.cast(&DataType::Float64)?.f64()?.get(base_row).filter(|x| *x != 0.0),
+            Err(_) => None,
+        };
+        let values = statement.column(period)?.cast(&DataType::Float64)?.f64()?.into_iter()
+            .zip(per_share.iter())
+            .map(|(value, per_share)| match (value, base) {
+                (Some(value), Some(base)) if !per_share => Some(value / base * 100.0),
+                _ => None,
+            })
+            .collect::<Vec<Option<f64>>>();
+        columns.push(Series::new(period, values));
+    }
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Percentage change of each item of a formatted statement from the same period of the previous year
+///
+/// Annual periods (2023) are compared with the previous year (2022) and quarterly periods (2023Q1) with the
+/// same quarter of the previous year (2022Q1), so seasonality does not show up as growth. Changes are
+/// relative to the absolute prior value, so an improving loss reads as a positive change. Periods without
+/// a prior year are dropped.
+///
+/// # Arguments
+///
+/// * `statement` - Formatted statement with an Items column and a column per period
+///
+/// # Returns
+///
+/// * `DataFrame` - Items column and the percentage change of each period with a prior year
+pub fn year_over_year(statement: &DataFrame) -> Result<DataFrame, Box<dyn Error>> {
+    let mut columns = vec![statement.column("Items")?.clone()];
+    for period in statement.get_column_names().into_iter().skip(1) {
+        let Some(year) = period.get(..4).and_then(|x| x.parse::<i32>().ok()) else { continue };
+        let prior_period = format!("{}{}", year - 1, &period[4..]);
+        let Ok(prior) = statement.column(&prior_period) else { continue };
+        let prior = prior.cast(&DataType::Float64)?;
+        let values = statement.column(period)?.cast(&DataType::Float64)?.f64()?.into_iter()
+            .zip(prior.f64()?)
+            .map(|(value, prior)| match (value, prior) {
+                (Some(value), Some(prior)) if prior != 0.0 => Some((value - prior) / prior.abs() * 100.0),
+                _ => None,
+            })
+            .collect::<Vec<Option<f64>>>();
+        columns.push(Series::new(period, values));
+    }
+    if columns.len() == 1 {
+        return Err("No periods with a prior year to compare".into());
+    }
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Pivots revenue segments into a table of segments by fiscal year
+///
+/// # Arguments
+///
+/// * `segments` - Revenue segments, as returned by `TickerData::get_revenue_segments`
+///
+/// # Returns
+///
+/// * `DataFrame` - Items column with the segments, sorted by the revenue of the latest fiscal year, and a
+///   revenue column per fiscal year in chronological order
+pub fn segment_breakdown(segments: &DataFrame) -> Result<DataFrame, Box<dyn Error>> {
+    let years = segments.column("fiscal_year")?.i64()?.into_no_null_iter().collect::<Vec<i64>>();
+    let names = segments.column("segment")?.str()?.into_no_null_iter().collect::<Vec<&str>>();
+    let revenue = segments.column("revenue")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+    let mut periods = years.clone();
+    periods.sort();
+    periods.dedup();
+    let latest = *periods.last().ok_or("No revenue segments to break down")?;
+
+    let mut values: HashMap<(&str, i64), f64> = HashMap::new();
+    for ((name, year), revenue) in names.iter().zip(years.iter()).zip(revenue.iter()) {
+        *values.entry((*name, *year)).or_insert(0.0) += revenue;
+    }
+    let mut items = names.clone();
+    items.sort();
+    items.dedup();
+    let latest_revenue = |name: &str| values.get(&(name, latest)).copied().unwrap_or(0.0);
+    items.sort_by(|a, b| latest_revenue(b).total_cmp(&latest_revenue(a)));
+
+    let mut columns = vec![Series::new("Items", items.clone())];
+    for year in periods {
+        columns.push(Series::new(&year.to_string(), items.iter()
+            .map(|name| values.get(&(*name, year)).copied())
+            .collect::<Vec<Option<f64>>>()));
+    }
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Values of the first available item of a statement by period (asOfDate), zero values are treated as missing
+pub(crate) fn statement_values(statement: &DataFrame, items: &[&str]) -> Result<HashMap<String, f64>, Box<dyn Error>> {
+    let dates = statement.column("asOfDate")?.str()?.into_no_null_iter().collect::<Vec<&str>>();
+    let mut values = HashMap::new();
+    for item in items {
+        let Ok(series) = statement.column(item) else { continue };
+        let series = series.cast(&DataType::Float64)?;
+        for (date, value) in dates.iter().zip(series.f64()?) {
+            if let Some(value) = value.filter(|x| *x != 0.0) {
+                values.entry(date.to_string()).or_insert(value);
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Revenue, free cash flow and book value per share of each period, using the share count of the balance sheet
+///
+/// Dividing by the share count of each period rather than the latest one keeps buybacks and dilution from
+/// distorting the growth of the per-share figures. Periods without a share count are skipped.
+///
+/// # Returns
+///
+/// * `(Vec<String>, Vec<(&str, Vec<Option<f64>>)>)` - Period dates (YYYY-MM-DD) in chronological order and the
+///   shares outstanding, revenue per share, free cash flow per share and book value per share of each period
+pub(crate) async fn per_share_series(ticker: &Ticker, frequency: StatementFrequency) -> Result<(Vec<String>, Vec<(&'static str, Vec<Option<f64>>)>), Box<dyn Error>> {
+    let income_statement = ticker.get_fundamentals(StatementType::IncomeStatement, frequency).await?;
+    let balance_sheet = ticker.get_fundamentals(StatementType::BalanceSheet, frequency).await?;
+    let cash_flow = ticker.get_fundamentals(StatementType::CashFlowStatement, frequency).await?;
+
+    let shares = statement_values(&balance_sheet, &["OrdinarySharesNumber", "ShareIssued"])?;
+    let revenue = statement_values(&income_statement, &["TotalRevenue", "OperatingRevenue"])?;
+    let free_cash_flow = statement_values(&cash_flow, &["FreeCashFlow"])?;
+    let book_value = statement_values(&balance_sheet, &["CommonStockEquity", "StockholdersEquity"])?;
+
+    let mut periods = shares.keys().cloned().collect::<Vec<String>>();
+    periods.sort();
+    if periods.is_empty() {
+        return Err(format!("No share count in the balance sheet of {}", ticker.ticker).into());
+    }
+    let per_share = |values: &HashMap<String, f64>| periods.iter()
+        .map(|p| values.get(p).map(|v| v / shares[p]))
+        .collect::<Vec<Option<f64>>>();
+    let series = vec![
+        ("Shares Outstanding", periods.iter().map(|p| Some(shares[p])).collect()),
+        ("Revenue per Share", per_share(&revenue)),
+        ("Free Cash Flow per Share", per_share(&free_cash_flow)),
+        ("Book Value per Share", per_share(&book_value)),
+    ];
+    Ok((periods, series))
 }
 
 impl Financials for Ticker {
@@ -298,4 +658,175 @@ impl Financials for Ticker {
 
         Ok(transposed_df)
     }
-}
\ No newline at end of file
+
+    /// Computes revenue, free cash flow and book value per share with the share count of each period
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Items column with the shares outstanding and per-share figures, and a column per period
+    async fn per_share_fundamentals(&self, frequency: StatementFrequency) -> Result<DataFrame, Box<dyn Error>> {
+        let (periods, series) = per_share_series(self, frequency).await?;
+        let dates = match frequency {
+            StatementFrequency::Quarterly => convert_to_quarter(periods.iter().map(|x| x.as_str()).collect()),
+            StatementFrequency::Annual => convert_to_year(periods.iter().map(|x| x.as_str()).collect()),
+        };
+        let mut columns = vec![Series::new("Items", series.iter().map(|(name, _)| *name).collect::<Vec<&str>>())];
+        for (i, date) in dates.iter().enumerate() {
+            columns.push(Series::new(date, series.iter().map(|(_, values)| values[i]).collect::<Vec<Option<f64>>>()));
+        }
+        Ok(DataFrame::new(columns)?)
+    }
+
+    /// Computes the Piotroski F-score, Altman Z-score and Beneish M-score of the latest fiscal year
+    ///
+    /// # Returns
+    ///
+    /// * `FundamentalScores` - Scores of the latest fiscal year
+    async fn fundamental_scores(&self) -> Result<FundamentalScores, Box<dyn Error>> {
+        let income_statement = self.get_fundamentals(StatementType::IncomeStatement, StatementFrequency::Annual).await?;
+        let balance_sheet = self.get_fundamentals(StatementType::BalanceSheet, StatementFrequency::Annual).await?;
+        let cash_flow = self.get_fundamentals(StatementType::CashFlowStatement, StatementFrequency::Annual).await?;
+        let market_cap = fetch_quote_fields(&self.ticker, &["marketCap".to_string()]).await.ok()
+            .and_then(|values| values.first().and_then(|v| v.as_f64()));
+        fundamental_scores(&income_statement, &balance_sheet, &cash_flow, market_cap)
+    }
+
+    /// Computes the revision momentum of the analyst EPS estimates
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per estimate period, see `estimate_revisions`
+    async fn estimate_revisions(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let trend = self.get_earnings_trend().await?;
+        estimate_revisions(&trend)
+    }
+
+    /// Formats a financial statement in the given view
+    ///
+    /// Common-size income and cash flow statements are relative to revenue, the balance sheet to total assets.
+    ///
+    /// # Arguments
+    ///
+    /// * `statement_type` - Statement to format
+    /// * `frequency` - Annual or quarterly periods
+    /// * `view` - Reported values, common-size percentages or year-over-year changes
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Items column and a column per period
+    async fn statement_view(&self, statement_type: StatementType, frequency: StatementFrequency, view: StatementView) -> Result<DataFrame, Box<dyn Error>> {
+        let statement = match statement_type {
+            StatementType::IncomeStatement => self.income_statement(frequency).await?,
+            StatementType::BalanceSheet => self.balance_sheet(frequency).await?,
+            StatementType::CashFlowStatement => self.cashflow_statement(frequency).await?,
+            StatementType::FinancialRatios => self.financial_ratios(frequency).await?,
+        };
+        match view {
+            StatementView::Reported => Ok(statement),
+            StatementView::YearOverYear => year_over_year(&statement),
+            StatementView::CommonSize => match statement_type {
+                StatementType::IncomeStatement => common_size(&statement, &statement, "Revenue"),
+                StatementType::BalanceSheet => common_size(&statement, &statement, "Total Assets"),
+                StatementType::CashFlowStatement => common_size(&statement, &self.income_statement(frequency).await?, "Revenue"),
+                StatementType::FinancialRatios => Err("The common-size view is not available for financial ratios".into()),
+            },
+        }
+    }
+
+    /// Breaks the annual revenue down by business or geographic segment
+    ///
+    /// # Arguments
+    ///
+    /// * `segment_type` - Business or geographic segments
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Items column with the segments and a revenue column per fiscal year, see `segment_breakdown`
+    async fn revenue_segments(&self, segment_type: SegmentType) -> Result<DataFrame, Box<dyn Error>> {
+        let segments = self.get_revenue_segments(segment_type).await?;
+        segment_breakdown(&segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERIODS: [&str; 2] = ["2022-12-31", "2023-12-31"];
+
+    fn statement(items: &[(&str, [f64; 2])]) -> DataFrame {
+        let mut columns = vec![Series::new("asOfDate", PERIODS.to_vec())];
+        columns.extend(items.iter().map(|(name, values)| Series::new(name, values.to_vec())));
+        DataFrame::new(columns).unwrap()
+    }
+
+    /// Statements of a company improving on all nine F-score criteria
+    fn statements() -> (DataFrame, DataFrame, DataFrame) {
+        let income_statement = statement(&[
+            ("TotalRevenue", [1000.0, 1200.0]),
+            ("GrossProfit", [400.0, 540.0]),
+            ("NetIncome", [50.0, 80.0]),
+            ("EBIT", [80.0, 120.0]),
+            ("SellingGeneralAndAdministration", [200.0, 220.0]),
+            ("ReconciledDepreciation", [40.0, 45.0]),
+        ]);
+        let balance_sheet = statement(&[
+            ("TotalAssets", [1000.0, 1100.0]),
+            ("TotalLiabilitiesNetMinorityInterest", [600.0, 600.0]),
+            ("CurrentAssets", [400.0, 480.0]),
+            ("CurrentLiabilities", [200.0, 200.0]),
+            ("LongTermDebt", [300.0, 250.0]),
+            ("AccountsReceivable", [100.0, 110.0]),
+            ("NetPPE", [500.0, 520.0]),
+            ("RetainedEarnings", [200.0, 280.0]),
+            ("CommonStockEquity", [400.0, 500.0]),
+            ("OrdinarySharesNumber", [100.0, 100.0]),
+        ]);
+        let cash_flow = statement(&[("OperatingCashFlow", [60.0, 100.0])]);
+        (income_statement, balance_sheet, cash_flow)
+    }
+
+    #[test]
+    fn test_fundamental_scores_match_reference() {
+        let (income_statement, balance_sheet, cash_flow) = statements();
+        let scores = fundamental_scores(&income_statement, &balance_sheet, &cash_flow, Some(1500.0)).unwrap();
+        assert_eq!(scores.period, "2023-12-31");
+        assert_eq!(scores.piotroski_f_score, Some(9.0));
+        // 1.2 * 280 / 1100 + 1.4 * 280 / 1100 + 3.3 * 120 / 1100 + 0.6 * 1500 / 600 + 1200 / 1100
+        assert!((scores.altman_z_score.unwrap() - 3.612727272727273).abs() < 1e-12);
+        // DSRI 0.9167, GMI 0.8889, AQI 0.9091, SGI 1.2, DEPI 0.9300, SGAI 0.9167, LVGI 0.8182, TATA -0.0182
+        assert!((scores.beneish_m_score.unwrap() + 2.492990722035166).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_altman_z_score_uses_book_equity_without_market_cap() {
+        let (income_statement, balance_sheet, cash_flow) = statements();
+        let scores = fundamental_scores(&income_statement, &balance_sheet, &cash_flow, None).unwrap();
+        assert!((scores.altman_z_score.unwrap() - 2.612727272727273).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_piotroski_f_score_of_a_deteriorating_company() {
+        // Swapping the periods fails every comparison with the previous year except the unchanged share count,
+        // leaving it with the positive ROA and operating cash flow and the higher operating cash flow than net income
+        let (income_statement, balance_sheet, cash_flow) = statements();
+        let reverse = |df: &DataFrame| {
+            let mut df = df.clone();
+            df.replace("asOfDate", Series::new("asOfDate", vec![PERIODS[1], PERIODS[0]])).unwrap();
+            df
+        };
+        let scores = fundamental_scores(&reverse(&income_statement), &reverse(&balance_sheet), &reverse(&cash_flow), None).unwrap();
+        assert_eq!(scores.period, "2023-12-31");
+        assert_eq!(scores.piotroski_f_score, Some(4.0));
+    }
+
+    #[test]
+    fn test_scores_need_two_periods_for_comparisons() {
+        let (income_statement, balance_sheet, cash_flow) = statements();
+        let latest = |df: &DataFrame| df.slice(1, 1);
+        let scores = fundamental_scores(&latest(&income_statement), &latest(&balance_sheet), &latest(&cash_flow), Some(1500.0)).unwrap();
+        assert_eq!(scores.piotroski_f_score, None);
+        assert_eq!(scores.beneish_m_score, None);
+        assert!((scores.altman_z_score.unwrap() - 3.612727272727273).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,252 @@
+use std::error::Error;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+
+use crate::data::config::Interval;
+use crate::data::ticker::TickerData;
+use crate::models::ticker::TickerBuilder;
+use crate::utils::date_utils::to_datetime;
+
+
+/// Futures contract month codes (January to December)
+pub const MONTH_CODES: [char; 12] = ['F', 'G', 'H', 'J', 'K', 'M', 'N', 'Q', 'U', 'V', 'X', 'Z'];
+
+
+/// Rule for rolling from the front contract to the next contract
+///
+/// DaysBeforeExpiry: Roll the given number of trading days before the last trading day of the front contract
+/// VolumeCrossover: Roll on the first day the next contract trades more volume than the front contract
+#[derive(Debug, Clone, Copy)]
+pub enum RollRule {
+    DaysBeforeExpiry(usize),
+    VolumeCrossover,
+}
+
+/// Price adjustment applied to the continuous series at each roll
+///
+/// None: Prices are spliced without adjustment (roll gaps are kept)
+/// Difference: Earlier prices are shifted by the price gap at each roll (back-adjustment)
+/// Ratio: Earlier prices are scaled by the price ratio at each roll, preserving percentage returns
+#[derive(Debug, Clone, Copy)]
+pub enum RollAdjustment {
+    None,
+    Difference,
+    Ratio,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct ContinuousFutures {
+    pub root_symbol: String,
+    pub exchange: String,
+    pub contract_months: Vec<char>,
+    pub start_date: String,
+    pub end_date: String,
+    pub roll_rule: RollRule,
+    pub adjustment: RollAdjustment,
+}
+
+struct ContractData {
+    symbol: String,
+    dates: Vec<NaiveDateTime>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    df: DataFrame,
+}
+
+impl ContractData {
+    fn index_of(&self, date: &NaiveDateTime) -> Option<usize> {
+        self.dates.iter().position(|d| d == date)
+    }
+}
+
+impl ContinuousFutures {
+    /// Creates a new ContinuousFutures struct
+    ///
+    /// # Arguments
+    ///
+    /// * `root_symbol` - Futures root symbol (e.g. "CL" for crude oil)
+    /// * `exchange` - Yahoo Finance exchange suffix of the individual contracts (e.g. "NYM")
+    /// * `contract_months` - Month codes of the traded contracts (e.g. "FGHJKMNQUVXZ" for monthly contracts, "HMUZ" for quarterly contracts)
+    /// * `start_date` - Start date in YYYY-MM-DD format (e.g. "2020-01-01")
+    /// * `end_date` - End date in YYYY-MM-DD format (e.g. "2024-01-01")
+    /// * `roll_rule` - Roll rule enum (e.g. RollRule::DaysBeforeExpiry(5))
+    /// * `adjustment` - Roll adjustment enum (e.g. RollAdjustment::Ratio)
+    ///
+    /// # Returns
+    ///
+    /// * `ContinuousFutures` struct
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::error::Error;
+    /// use finalytics::analytics::futures::{ContinuousFutures, RollAdjustment, RollRule};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let futures = ContinuousFutures::new("CL", "NYM", "FGHJKMNQUVXZ", "2023-01-01", "2024-01-01",
+    ///                                          RollRule::DaysBeforeExpiry(5), RollAdjustment::Ratio);
+    ///     let series = futures.continuous_series().await?;
+    ///     println!("{:?}", series);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new(
+        root_symbol: &str,
+        exchange: &str,
+        contract_months: &str,
+        start_date: &str,
+        end_date: &str,
+        roll_rule: RollRule,
+        adjustment: RollAdjustment,
+    ) -> Self {
+        Self {
+            root_symbol: root_symbol.to_string(),
+            exchange: exchange.to_string(),
+            contract_months: contract_months.to_uppercase().chars().filter(|c| MONTH_CODES.contains(c)).collect(),
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            roll_rule,
+            adjustment,
+        }
+    }
+
+    /// Generates the Yahoo Finance symbols of the individual contracts expiring in the date range
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - Contract symbols in expiry order (e.g. ["CLF23.NYM", "CLG23.NYM", ...])
+    pub fn contract_symbols(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let start = to_datetime(&self.start_date)?.date();
+        let end = to_datetime(&self.end_date)?.date();
+        let mut symbols = Vec::new();
+        // Include the following year so the last contracts held in the range are covered
+        for year in start.year()..=end.year() + 1 {
+            for (i, code) in MONTH_CODES.iter().enumerate() {
+                if !self.contract_months.contains(code) {
+                    continue;
+                }
+                let month = NaiveDate::from_ymd_opt(year, i as u32 + 1, 1).ok_or("Invalid contract month")?;
+                if month < start || month > end + chrono::Duration::days(366) {
+                    continue;
+                }
+                let suffix = if self.exchange.is_empty() { String::new() } else { format!(".{}", self.exchange) };
+                symbols.push(format!("{}{}{:02}{}", self.root_symbol, code, year % 100, suffix));
+            }
+        }
+        Ok(symbols)
+    }
+
+    /// Constructs the continuous futures series by rolling through the individual contracts
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Continuous series with the timestamp, held contract, unadjusted close,
+    ///   OHLCV columns and the roll-adjusted close (adjclose)
+    pub async fn continuous_series(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let mut contracts = Vec::new();
+        for symbol in self.contract_symbols()? {
+            let ticker = TickerBuilder::new()
+                .ticker(&symbol)
+                .start_date(&self.start_date)
+                .end_date(&self.end_date)
+                .interval(Interval::OneDay)
+                .build();
+            match ticker.get_chart().await {
+                Ok(df) if df.height() > 0 => {
+                    let dates = df.column("timestamp")?.datetime()?.as_datetime_iter()
+                        .map(|x| x.unwrap()).collect::<Vec<NaiveDateTime>>();
+                    let close = df.column("close")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+                    let volume = df.column("volume")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+                    contracts.push(ContractData { symbol, dates, close, volume, df });
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Error fetching contract {}: {}", symbol, e),
+            }
+        }
+        if contracts.is_empty() {
+            return Err(format!("No contract data found for {}", self.root_symbol).into());
+        }
+
+        // Determine the roll date out of each contract into the next one, contracts still
+        // trading at the end of the date range are not rolled
+        let range_end = contracts.iter().map(|c| *c.dates.last().unwrap()).max().unwrap();
+        let mut segments: Vec<(usize, NaiveDateTime, NaiveDateTime)> = Vec::new();
+        let mut start = contracts[0].dates[0];
+        for i in 0..contracts.len() {
+            let front = &contracts[i];
+            let last = *front.dates.last().unwrap();
+            let roll_date = match contracts.get(i + 1) {
+                Some(next) if last < range_end => self.roll_date(front, next, start).unwrap_or(last),
+                _ => range_end + chrono::Duration::days(1),
+            };
+            if roll_date > start {
+                segments.push((i, start, roll_date));
+                start = roll_date;
+            }
+            if start > range_end {
+                break;
+            }
+        }
+
+        // Splice the segments and accumulate the back-adjustment from the latest contract backwards
+        let mut offsets = vec![0.0; segments.len()];
+        let mut factors = vec![1.0; segments.len()];
+        for s in (0..segments.len().saturating_sub(1)).rev() {
+            let (front, _, roll_date) = segments[s];
+            let (next, _, _) = segments[s + 1];
+            let gap = match (contracts[front].index_of(&roll_date), contracts[next].index_of(&roll_date)) {
+                (Some(f), Some(n)) => Some((contracts[front].close[f], contracts[next].close[n])),
+                _ => None,
+            };
+            offsets[s] = offsets[s + 1];
+            factors[s] = factors[s + 1];
+            if let Some((front_close, next_close)) = gap {
+                offsets[s] += next_close - front_close;
+                if front_close > 0.0 {
+                    factors[s] *= next_close / front_close;
+                }
+            }
+        }
+
+        let mut frames = Vec::new();
+        for (s, (index, start, end)) in segments.iter().enumerate() {
+            let contract = &contracts[*index];
+            let mask = contract.dates.iter().map(|d| d >= start && d < end).collect::<BooleanChunked>();
+            let mut df = contract.df.filter(&mask)?;
+            let close = df.column("close")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+            let adjclose = close.iter().map(|c| match self.adjustment {
+                RollAdjustment::None => *c,
+                RollAdjustment::Difference => c + offsets[s],
+                RollAdjustment::Ratio => c * factors[s],
+            }).collect::<Vec<f64>>();
+            let _ = df.drop_in_place("adjclose")?;
+            df.with_column(Series::new("adjclose", adjclose))?;
+            df.with_column(Series::new("contract", vec![contract.symbol.clone(); df.height()]))?;
+            frames.push(df);
+        }
+
+        let mut df = frames[0].clone();
+        for frame in frames.iter().skip(1) {
+            df.vstack_mut(frame)?;
+        }
+        let df = df.select(["timestamp", "contract", "open", "high", "low", "close", "volume", "adjclose"])?;
+        Ok(df)
+    }
+
+    fn roll_date(&self, front: &ContractData, next: &ContractData, from: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self.roll_rule {
+            RollRule::DaysBeforeExpiry(days) => {
+                let index = front.dates.len().saturating_sub(days + 1);
+                // Roll on the first date from the cutoff on which both contracts have prices
+                front.dates[index..].iter().find(|d| **d > from && next.index_of(d).is_some()).cloned()
+            }
+            RollRule::VolumeCrossover => {
+                front.dates.iter().enumerate().find(|(i, d)| {
+                    **d > from && next.index_of(d).map(|n| next.volume[n] > front.volume[*i]).unwrap_or(false)
+                }).map(|(_, d)| *d)
+            }
+        }
+    }
+}
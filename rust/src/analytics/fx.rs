@@ -0,0 +1,364 @@
+use std::error::Error;
+use chrono::{NaiveDateTime, Timelike};
+use polars::prelude::*;
+
+use crate::data::config::{Interval, MarketCalendar};
+use crate::data::ticker::TickerData;
+use crate::models::ticker::{Ticker, TickerBuilder};
+
+
+/// Currency pair quoted as units of the quote currency per unit of the base currency
+///
+/// Yahoo Finance FX symbols follow two conventions: "EURUSD=X" (EUR/USD) and the
+/// shorthand "JPY=X", which is quoted against the US Dollar (USD/JPY)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyPair {
+    pub base: String,
+    pub quote: String,
+}
+
+impl CurrencyPair {
+    /// Creates a currency pair from ISO currency codes
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - Base currency code (e.g. "EUR")
+    /// * `quote` - Quote currency code (e.g. "USD")
+    pub fn new(base: &str, quote: &str) -> CurrencyPair {
+        CurrencyPair {
+            base: base.to_uppercase(),
+            quote: quote.to_uppercase(),
+        }
+    }
+
+    /// Parses a currency pair from a symbol
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - Yahoo Finance FX symbol (e.g. "EURUSD=X", "JPY=X") or a pair such as "EUR/USD" or "EURUSD"
+    ///
+    /// # Returns
+    ///
+    /// * `CurrencyPair` struct
+    pub fn from_symbol(symbol: &str) -> Result<CurrencyPair, Box<dyn Error>> {
+        let pair = symbol.trim().trim_end_matches("=X").trim_end_matches("=x")
+            .replace(['/', '-', '_'], "")
+            .to_uppercase();
+        if !pair.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("Invalid currency pair symbol: {}", symbol).into());
+        }
+        match pair.len() {
+            3 => Ok(CurrencyPair::new("USD", &pair)),
+            6 => Ok(CurrencyPair::new(&pair[..3], &pair[3..])),
+            _ => Err(format!("Invalid currency pair symbol: {}", symbol).into()),
+        }
+    }
+
+    /// Yahoo Finance symbol of the currency pair (e.g. "EURUSD=X")
+    pub fn symbol(&self) -> String {
+        format!("{}{}=X", self.base, self.quote)
+    }
+
+    /// Size of one pip in quote currency units (0.01 for Yen quoted pairs, 0.0001 otherwise)
+    pub fn pip_size(&self) -> f64 {
+        match self.quote.as_str() {
+            "JPY" | "HUF" | "KRW" | "INR" | "THB" => 0.01,
+            _ => 0.0001,
+        }
+    }
+
+    /// Currency pair with the base and quote currencies swapped (e.g. EUR/USD -> USD/EUR)
+    pub fn inverse(&self) -> CurrencyPair {
+        CurrencyPair::new(&self.quote, &self.base)
+    }
+
+    /// Converts a price difference into pips
+    pub fn to_pips(&self, value: f64) -> f64 {
+        value / self.pip_size()
+    }
+
+    pub fn to_string(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+}
+
+/// Returns true if the symbol is a Yahoo Finance currency pair symbol (e.g. "EURUSD=X")
+pub fn is_fx_symbol(symbol: &str) -> bool {
+    symbol.to_uppercase().ends_with("=X")
+}
+
+
+/// Major FX trading sessions, in UTC hours (standard time, daylight saving shifts are ignored)
+///
+/// Sydney: 21:00 - 06:00
+/// Tokyo: 00:00 - 09:00
+/// London: 07:00 - 16:00
+/// NewYork: 12:00 - 21:00
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FxSession {
+    Sydney,
+    Tokyo,
+    London,
+    NewYork,
+}
+
+impl FxSession {
+    pub fn all() -> Vec<FxSession> {
+        vec![FxSession::Sydney, FxSession::Tokyo, FxSession::London, FxSession::NewYork]
+    }
+
+    /// Opening and closing hours of the session in UTC
+    pub fn utc_hours(&self) -> (u32, u32) {
+        match self {
+            FxSession::Sydney => (21, 6),
+            FxSession::Tokyo => (0, 9),
+            FxSession::London => (7, 16),
+            FxSession::NewYork => (12, 21),
+        }
+    }
+
+    /// Whether the session is open at the given UTC hour
+    pub fn is_open(&self, hour: u32) -> bool {
+        let (open, close) = self.utc_hours();
+        if open < close {
+            hour >= open && hour < close
+        } else {
+            hour >= open || hour < close
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            FxSession::Sydney => "sydney".to_string(),
+            FxSession::Tokyo => "tokyo".to_string(),
+            FxSession::London => "london".to_string(),
+            FxSession::NewYork => "new_york".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> FxSession {
+        match s {
+            "sydney" => FxSession::Sydney,
+            "tokyo" => FxSession::Tokyo,
+            "london" => FxSession::London,
+            "new_york" => FxSession::NewYork,
+            _ => FxSession::London,
+        }
+    }
+}
+
+
+pub trait FxAnalytics {
+    fn currency_pair(&self) -> Result<CurrencyPair, Box<dyn Error>>;
+    fn pip_stats(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn session_stats(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+}
+
+impl FxAnalytics for Ticker {
+    /// Returns the currency pair of an FX ticker
+    ///
+    /// # Returns
+    ///
+    /// * `CurrencyPair` struct
+    fn currency_pair(&self) -> Result<CurrencyPair, Box<dyn Error>> {
+        CurrencyPair::from_symbol(&self.ticker)
+    }
+
+    /// Computes price movement statistics of an FX ticker in pips
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns Metric and Value (pips)
+    async fn pip_stats(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let pair = self.currency_pair()?;
+        let chart = self.get_chart().await?;
+        let open = chart.column("open")?.f64()?.to_vec();
+        let high = chart.column("high")?.f64()?.to_vec();
+        let low = chart.column("low")?.f64()?.to_vec();
+        let close = chart.column("close")?.f64()?.to_vec();
+
+        let mut ranges = Vec::new();
+        let mut true_ranges = Vec::new();
+        let mut changes = Vec::new();
+        let mut gaps = Vec::new();
+        for i in 0..close.len() {
+            if let (Some(h), Some(l)) = (high[i], low[i]) {
+                ranges.push(pair.to_pips(h - l));
+                let prev_close = if i > 0 { close[i - 1] } else { None };
+                let tr = match prev_close {
+                    Some(pc) => (h - l).max((h - pc).abs()).max((l - pc).abs()),
+                    None => h - l,
+                };
+                true_ranges.push(pair.to_pips(tr));
+            }
+            if i > 0 {
+                if let (Some(c), Some(pc)) = (close[i], close[i - 1]) {
+                    changes.push(pair.to_pips(c - pc));
+                }
+                if let (Some(o), Some(pc)) = (open[i], close[i - 1]) {
+                    gaps.push(pair.to_pips(o - pc).abs());
+                }
+            }
+        }
+        if changes.is_empty() {
+            return Err(format!("Not enough price data to compute pip statistics for {}", self.ticker).into());
+        }
+
+        let mean = |x: &[f64]| if x.is_empty() { f64::NAN } else { x.iter().sum::<f64>() / x.len() as f64 };
+        let abs_changes = changes.iter().map(|x| x.abs()).collect::<Vec<f64>>();
+        let change_mean = mean(&changes);
+        let change_std = (changes.iter().map(|x| (x - change_mean).powi(2)).sum::<f64>()
+            / (changes.len() as f64 - 1.0).max(1.0)).sqrt();
+        let mut sorted_ranges = ranges.clone();
+        sorted_ranges.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let median_range = if sorted_ranges.is_empty() { f64::NAN } else { sorted_ranges[sorted_ranges.len() / 2] };
+
+        let df = df!(
+            "Metric" => vec![
+                "Pip Size", "Average Range", "Median Range", "Average True Range", "Average Absolute Change",
+                "Change Volatility", "Largest Gain", "Largest Loss", "Average Opening Gap", "Net Change",
+            ],
+            "Value" => vec![
+                pair.pip_size(), mean(&ranges), median_range, mean(&true_ranges), mean(&abs_changes), change_std,
+                changes.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                changes.iter().cloned().fold(f64::INFINITY, f64::min),
+                mean(&gaps), changes.iter().sum::<f64>(),
+            ]
+        )?;
+        Ok(df)
+    }
+
+    /// Computes intraday price movement statistics of an FX ticker by trading session
+    ///
+    /// Bars are assigned to every session open at the bar's UTC hour, so the overlap hours
+    /// (e.g. London / New York) count towards both sessions
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns session, bars, average_range (pips), average_absolute_change (pips)
+    ///   and range_share (% of the summed bar ranges)
+    async fn session_stats(&self) -> Result<DataFrame, Box<dyn Error>> {
+        if self.interval.to_days() >= 1.0 {
+            return Err("Session statistics require an intraday interval".into());
+        }
+        let pair = self.currency_pair()?;
+        let chart = self.get_chart().await?;
+        let timestamps = chart.column("timestamp")?.datetime()?.as_datetime_iter()
+            .collect::<Vec<Option<NaiveDateTime>>>();
+        let open = chart.column("open")?.f64()?.to_vec();
+        let high = chart.column("high")?.f64()?.to_vec();
+        let low = chart.column("low")?.f64()?.to_vec();
+        let close = chart.column("close")?.f64()?.to_vec();
+
+        let sessions = FxSession::all();
+        let mut bars = vec![0u32; sessions.len()];
+        let mut ranges = vec![0.0; sessions.len()];
+        let mut changes = vec![0.0; sessions.len()];
+        for i in 0..timestamps.len() {
+            if let (Some(ts), Some(o), Some(h), Some(l), Some(c)) = (timestamps[i], open[i], high[i], low[i], close[i]) {
+                for (s, session) in sessions.iter().enumerate() {
+                    if session.is_open(ts.hour()) {
+                        bars[s] += 1;
+                        ranges[s] += pair.to_pips(h - l);
+                        changes[s] += pair.to_pips(c - o).abs();
+                    }
+                }
+            }
+        }
+        let total_range = ranges.iter().sum::<f64>();
+        let avg = |total: f64, n: u32| if n > 0 { total / n as f64 } else { f64::NAN };
+
+        let df = df!(
+            "session" => sessions.iter().map(|x| x.to_string()).collect::<Vec<String>>(),
+            "bars" => bars.clone(),
+            "average_range" => ranges.iter().zip(bars.iter()).map(|(r, n)| avg(*r, *n)).collect::<Vec<f64>>(),
+            "average_absolute_change" => changes.iter().zip(bars.iter()).map(|(c, n)| avg(*c, *n)).collect::<Vec<f64>>(),
+            "range_share" => ranges.iter().map(|r| if total_range > 0.0 { r / total_range * 100.0 } else { f64::NAN }).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+}
+
+
+/// Builds a synthetic cross rate from two currency pairs that share a common currency
+///
+/// The cross of A/C and B/C is A/B = (A/C) / (B/C), while A/C and C/B give A/B = (A/C) * (C/B).
+/// Leg extremes do not occur at the same time, so the high and low of the cross are
+/// taken from its open and close
+///
+/// # Arguments
+///
+/// * `left` - OHLC DataFrame of the first leg (e.g. EUR/USD)
+/// * `right` - OHLC DataFrame of the second leg (e.g. GBP/USD)
+/// * `invert_right` - Whether to divide by the second leg (true) or multiply by it (false)
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns timestamp, open, high, low, close and adjclose, on the timestamps common to both legs
+pub fn synthetic_cross(left: &DataFrame, right: &DataFrame, invert_right: bool) -> Result<DataFrame, Box<dyn Error>> {
+    let select = |df: &DataFrame, suffix: &str| -> Result<DataFrame, Box<dyn Error>> {
+        Ok(df.clone().lazy().select([
+            col("timestamp"),
+            col("open").alias(&format!("open_{}", suffix)),
+            col("close").alias(&format!("close_{}", suffix)),
+        ]).collect()?)
+    };
+    let joined = select(left, "l")?.join(
+        &select(right, "r")?,
+        ["timestamp"],
+        ["timestamp"],
+        JoinArgs::new(JoinType::Inner),
+    )?;
+    let combine = |l: Expr, r: Expr| if invert_right { l / r } else { l * r };
+    let df = joined.lazy()
+        .select([
+            col("timestamp"),
+            combine(col("open_l"), col("open_r")).alias("open"),
+            combine(col("close_l"), col("close_r")).alias("close"),
+        ])
+        .with_columns([
+            when(col("open").gt(col("close"))).then(col("open")).otherwise(col("close")).alias("high"),
+            when(col("open").lt(col("close"))).then(col("open")).otherwise(col("close")).alias("low"),
+        ])
+        .select([col("timestamp"), col("open"), col("high"), col("low"), col("close"), col("close").alias("adjclose")])
+        .sort(["timestamp"], Default::default())
+        .collect()?;
+    Ok(df)
+}
+
+/// Fetches the two US Dollar legs of a currency pair and builds its cross rate
+///
+/// # Arguments
+///
+/// * `pair` - Currency pair to construct (e.g. CurrencyPair::new("EUR", "GBP"))
+/// * `start_date` - Start date in the format YYYY-MM-DD
+/// * `end_date` - End date in the format YYYY-MM-DD
+/// * `interval` - Interval enum
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns timestamp, open, high, low, close and adjclose
+pub async fn cross_rate(
+    pair: &CurrencyPair,
+    start_date: &str,
+    end_date: &str,
+    interval: Interval,
+) -> Result<DataFrame, Box<dyn Error>> {
+    if pair.base == "USD" || pair.quote == "USD" {
+        return Err(format!("{} is quoted directly, fetch {} instead", pair.to_string(), pair.symbol()).into());
+    }
+    let fetch = |ccy: String| async move {
+        TickerBuilder::new()
+            .ticker(&CurrencyPair::new(&ccy, "USD").symbol())
+            .start_date(start_date)
+            .end_date(end_date)
+            .interval(interval)
+            .calendar(MarketCalendar::Forex)
+            .build()
+            .get_chart()
+            .await
+    };
+    let base_leg = fetch(pair.base.clone()).await?;
+    let quote_leg = fetch(pair.quote.clone()).await?;
+    synthetic_cross(&base_leg, &quote_leg, true)
+}
@@ -0,0 +1,104 @@
+use std::error::Error;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use polars::prelude::*;
+use crate::data::ticker::TickerData;
+use crate::models::ticker::Ticker;
+
+
+/// Default trailing windows (in calendar days) used for the insider activity scores
+pub const DEFAULT_INSIDER_WINDOWS: [i64; 4] = [30, 90, 180, 365];
+
+/// Insider activity summary for a single trailing window
+#[derive(Debug, Clone)]
+pub struct InsiderScore {
+    pub window: i64,
+    pub buy_transactions: usize,
+    pub sell_transactions: usize,
+    pub net_shares: f64,
+    pub net_value: f64,
+    pub net_buying_score: f64,
+}
+
+pub trait InsiderActivity {
+    fn insider_scores(&self, windows: Option<Vec<i64>>) -> impl std::future::Future<Output = Result<Vec<InsiderScore>, Box<dyn Error>>>;
+    fn insider_scores_table(&self, windows: Option<Vec<i64>>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+}
+
+impl InsiderActivity for Ticker {
+    /// Computes the net insider buying scores over trailing windows
+    ///
+    /// The net buying score is the net shares bought divided by the gross shares traded by insiders
+    /// in open market transactions, ranging from -1.0 (only sales) to 1.0 (only purchases)
+    ///
+    /// # Arguments
+    ///
+    /// * `windows` - Trailing windows in calendar days (default - [30, 90, 180, 365])
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<InsiderScore>` - Insider activity scores for each window
+    async fn insider_scores(&self, windows: Option<Vec<i64>>) -> Result<Vec<InsiderScore>, Box<dyn Error>> {
+        let transactions = self.get_insider_transactions().await?;
+        let dates = transactions.column("timestamp")?.datetime()?
+            .into_no_null_iter().map(|x| DateTime::from_timestamp_millis(x).unwrap()
+            .naive_local()).collect::<Vec<NaiveDateTime>>();
+        let shares = transactions.column("shares")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let values = transactions.column("value")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let now = Utc::now().naive_utc();
+
+        let scores = windows.unwrap_or(DEFAULT_INSIDER_WINDOWS.to_vec()).iter().map(|&window| {
+            let cutoff = now - Duration::days(window);
+            let mut buy_transactions = 0;
+            let mut sell_transactions = 0;
+            let mut bought = 0.0;
+            let mut sold = 0.0;
+            let mut net_value = 0.0;
+            for i in 0..dates.len() {
+                if dates[i] < cutoff {
+                    continue;
+                }
+                if shares[i] > 0.0 {
+                    buy_transactions += 1;
+                    bought += shares[i];
+                } else if shares[i] < 0.0 {
+                    sell_transactions += 1;
+                    sold += shares[i].abs();
+                }
+                net_value += values[i];
+            }
+            let gross = bought + sold;
+            InsiderScore {
+                window,
+                buy_transactions,
+                sell_transactions,
+                net_shares: bought - sold,
+                net_value,
+                net_buying_score: if gross > 0.0 { (bought - sold) / gross } else { 0.0 },
+            }
+        }).collect::<Vec<InsiderScore>>();
+
+        Ok(scores)
+    }
+
+    /// Generates a DataFrame of the net insider buying scores over trailing windows
+    ///
+    /// # Arguments
+    ///
+    /// * `windows` - Trailing windows in calendar days (default - [30, 90, 180, 365])
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Insider activity scores for each window
+    async fn insider_scores_table(&self, windows: Option<Vec<i64>>) -> Result<DataFrame, Box<dyn Error>> {
+        let scores = self.insider_scores(windows).await?;
+        let df = df!(
+            "Window" => scores.iter().map(|x| format!("{}D", x.window)).collect::<Vec<String>>(),
+            "Buy Transactions" => scores.iter().map(|x| x.buy_transactions as u32).collect::<Vec<u32>>(),
+            "Sell Transactions" => scores.iter().map(|x| x.sell_transactions as u32).collect::<Vec<u32>>(),
+            "Net Shares" => scores.iter().map(|x| x.net_shares).collect::<Vec<f64>>(),
+            "Net Value" => scores.iter().map(|x| x.net_value).collect::<Vec<f64>>(),
+            "Net Buying Score" => scores.iter().map(|x| x.net_buying_score).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+}
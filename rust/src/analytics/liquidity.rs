@@ -0,0 +1,165 @@
+use std::error::Error;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::data::ticker::TickerData;
+use crate::models::ticker::Ticker;
+
+
+/// Liquidity metric columns of `TickersData::liquidity_metrics`, which `rank_universe` joins when ranked on
+pub const LIQUIDITY_COLUMNS: [&str; 7] = [
+    "average_volume", "average_dollar_volume", "median_dollar_volume", "amihud_illiquidity",
+    "roll_spread", "corwin_schultz_spread", "zero_volume_share",
+];
+
+/// Liquidity metrics of a security computed from its OHLCV bars
+///
+/// * `observations` - Number of bars in the window
+/// * `average_volume` - Average volume per bar
+/// * `average_dollar_volume` - Average traded value (close * volume) per bar, the ADV for daily bars
+/// * `median_dollar_volume` - Median traded value per bar, less sensitive to block trades and index rebalances
+/// * `amihud_illiquidity` - Average absolute return (%) per million of traded value, higher is less liquid
+/// * `roll_spread` - Roll (1984) effective spread estimate in percent, from the serial covariance of returns
+///   (zero when the covariance is positive)
+/// * `corwin_schultz_spread` - Corwin-Schultz (2012) bid-ask spread estimate in percent, from the high-low ranges
+///   of consecutive bars
+/// * `zero_volume_share` - Percentage of bars without volume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityMetrics {
+    pub observations: usize,
+    pub average_volume: f64,
+    pub average_dollar_volume: f64,
+    pub median_dollar_volume: f64,
+    pub amihud_illiquidity: f64,
+    pub roll_spread: f64,
+    pub corwin_schultz_spread: f64,
+    pub zero_volume_share: f64,
+}
+
+impl LiquidityMetrics {
+    /// Computes the liquidity metrics from OHLCV bars
+    ///
+    /// # Arguments
+    ///
+    /// * `ohlcv` - DataFrame with high, low, close and volume columns in time order
+    /// * `window` - Number of most recent bars to use (default - all bars)
+    ///
+    /// # Returns
+    ///
+    /// * `LiquidityMetrics` struct
+    pub fn compute(ohlcv: &DataFrame, window: Option<usize>) -> Result<LiquidityMetrics, Box<dyn Error>> {
+        let column = |name: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+            Ok(ohlcv.column(name)?.cast(&DataType::Float64)?.f64()?.into_iter().map(|x| x.unwrap_or(f64::NAN)).collect())
+        };
+        let (high, low, close, volume) = (column("high")?, column("low")?, column("close")?, column("volume")?);
+        let start = window.map(|w| close.len().saturating_sub(w)).unwrap_or(0);
+        let bars = (start..close.len())
+            .filter(|i| high[*i].is_finite() && low[*i].is_finite() && close[*i] > 0.0)
+            .map(|i| (high[i], low[i], close[i], if volume[i].is_finite() { volume[i] } else { 0.0 }))
+            .collect::<Vec<(f64, f64, f64, f64)>>();
+        if bars.len() < 3 {
+            return Err("At least 3 bars are required to compute the liquidity metrics".into());
+        }
+
+        let mean = |x: &[f64]| if x.is_empty() { f64::NAN } else { x.iter().sum::<f64>() / x.len() as f64 };
+        let volumes = bars.iter().map(|x| x.3).collect::<Vec<f64>>();
+        let dollar_volumes = bars.iter().map(|x| x.2 * x.3).collect::<Vec<f64>>();
+        let mut sorted = dollar_volumes.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median_dollar_volume = if sorted.len() % 2 == 0 {
+            (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2.0
+        } else {
+            sorted[sorted.len() / 2]
+        };
+
+        let returns = bars.windows(2).map(|x| (x[1].2 / x[0].2 - 1.0) * 100.0).collect::<Vec<f64>>();
+
+        // Bars without volume carry no price impact information and are left out of the Amihud ratio
+        let amihud = returns.iter().zip(dollar_volumes[1..].iter())
+            .filter(|(_, dv)| **dv > 0.0)
+            .map(|(r, dv)| r.abs() / (dv / 1_000_000.0))
+            .collect::<Vec<f64>>();
+
+        let return_mean = mean(&returns);
+        let serial_covariance = returns.windows(2)
+            .map(|x| (x[1] - return_mean) * (x[0] - return_mean))
+            .sum::<f64>() / (returns.len() - 1) as f64;
+        let roll_spread = if serial_covariance < 0.0 { 2.0 * (-serial_covariance).sqrt() } else { 0.0 };
+
+        // Negative two-bar estimates are set to zero, as recommended by Corwin and Schultz
+        let k = 3.0 - 2.0 * 2f64.sqrt();
+        let cs_spreads = bars.windows(2)
+            .filter(|x| x[0].1 > 0.0 && x[1].1 > 0.0)
+            .map(|x| {
+                let beta = (x[0].0 / x[0].1).ln().powi(2) + (x[1].0 / x[1].1).ln().powi(2);
+                let gamma = (x[0].0.max(x[1].0) / x[0].1.min(x[1].1)).ln().powi(2);
+                let alpha = ((2.0 * beta).sqrt() - beta.sqrt()) / k - (gamma / k).sqrt();
+                (2.0 * (alpha.exp() - 1.0) / (1.0 + alpha.exp())).max(0.0) * 100.0
+            })
+            .collect::<Vec<f64>>();
+
+        Ok(LiquidityMetrics {
+            observations: bars.len(),
+            average_volume: mean(&volumes),
+            average_dollar_volume: mean(&dollar_volumes),
+            median_dollar_volume,
+            amihud_illiquidity: mean(&amihud),
+            roll_spread,
+            corwin_schultz_spread: mean(&cs_spreads),
+            zero_volume_share: volumes.iter().filter(|x| **x <= 0.0).count() as f64 / bars.len() as f64 * 100.0,
+        })
+    }
+
+    /// Metric values in the order of `LIQUIDITY_COLUMNS`
+    pub fn values(&self) -> Vec<f64> {
+        vec![
+            self.average_volume, self.average_dollar_volume, self.median_dollar_volume, self.amihud_illiquidity,
+            self.roll_spread, self.corwin_schultz_spread, self.zero_volume_share,
+        ]
+    }
+}
+
+/// Position size limit relative to the average dollar volume (ADV) of each asset
+///
+/// The weight of each asset is capped at `max_adv_fraction * ADV / portfolio_value`, e.g. a 10% of ADV limit
+/// on a 10 million portfolio caps an asset trading 20 million a day at a 20% weight
+///
+/// * `portfolio_value` - Portfolio value in the quote currency of the assets
+/// * `max_adv_fraction` - Maximum position as a fraction of the average dollar volume (e.g. 0.1)
+/// * `window` - Number of most recent bars used for the average dollar volume (default - all bars)
+#[derive(Debug, Clone)]
+pub struct LiquidityConstraint {
+    pub portfolio_value: f64,
+    pub max_adv_fraction: f64,
+    pub window: Option<usize>,
+}
+
+impl LiquidityConstraint {
+    /// Maximum weight of an asset with the given average dollar volume
+    pub fn max_weight(&self, average_dollar_volume: f64) -> f64 {
+        if self.portfolio_value <= 0.0 {
+            return 1.0;
+        }
+        (self.max_adv_fraction * average_dollar_volume / self.portfolio_value).clamp(0.0, 1.0)
+    }
+}
+
+pub trait LiquidityAnalytics {
+    fn liquidity_metrics(&self, window: Option<usize>) -> impl std::future::Future<Output = Result<LiquidityMetrics, Box<dyn Error>>>;
+}
+
+impl LiquidityAnalytics for Ticker {
+    /// Computes the liquidity metrics of the ticker over its date range
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of most recent bars to use (default - all bars)
+    ///
+    /// # Returns
+    ///
+    /// * `LiquidityMetrics` struct
+    async fn liquidity_metrics(&self, window: Option<usize>) -> Result<LiquidityMetrics, Box<dyn Error>> {
+        let ohlcv = self.get_chart().await?;
+        LiquidityMetrics::compute(&ohlcv, window)
+    }
+}
@@ -3,4 +3,28 @@ pub mod performance;
 pub  mod technicals;
 pub mod statistics;
 pub mod optimization;
-pub mod stochastics;
\ No newline at end of file
+pub mod stochastics;
+pub mod insiders;
+pub mod fixed_income;
+pub mod futures;
+pub mod execution;
+pub mod crypto;
+pub mod fx;
+pub mod cashflows;
+pub mod projection;
+pub mod ranking;
+pub mod event_study;
+pub mod features;
+pub mod forecast;
+#[cfg(feature = "onnx")]
+pub mod onnx;
+pub mod strategy;
+pub mod search;
+pub mod options;
+pub mod sessions;
+pub mod liquidity;
+pub mod sizing;
+pub mod esg;
+pub mod cef;
+pub mod factors;
+pub mod clustering;
@@ -0,0 +1,117 @@
+use std::error::Error;
+use ort::session::Session;
+use ort::value::Tensor;
+use polars::prelude::*;
+
+
+/// Trained model in the ONNX format (e.g. an LSTM exported from PyTorch or a gradient boosting model
+/// converted with onnxmltools) used to generate predictions from a feature DataFrame
+///
+/// * `features` - Feature columns fed to the model, in the order the model was trained on
+/// * `sequence_length` - Number of consecutive rows fed per prediction to sequence models such as LSTMs,
+///   the input is shaped [rows, sequence_length, features] instead of [rows, features]
+/// * `output` - Name of the model output to read (default - the first output)
+pub struct OnnxModel {
+    session: Session,
+    pub features: Vec<String>,
+    pub sequence_length: Option<usize>,
+    pub output: Option<String>,
+}
+
+impl OnnxModel {
+    /// Loads an ONNX model from a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the .onnx file
+    /// * `features` - Feature columns fed to the model (e.g. the columns of `FeatureEngineering::features`)
+    ///
+    /// # Returns
+    ///
+    /// * `OnnxModel` struct
+    pub fn load(path: &str, features: Vec<&str>) -> Result<OnnxModel, Box<dyn Error>> {
+        let session = Session::builder()?.commit_from_file(path)?;
+        Ok(OnnxModel {
+            session,
+            features: features.iter().map(|x| x.to_string()).collect(),
+            sequence_length: None,
+            output: None,
+        })
+    }
+
+    pub fn sequence_length(mut self, sequence_length: usize) -> OnnxModel {
+        self.sequence_length = Some(sequence_length.max(1));
+        self
+    }
+
+    pub fn output(mut self, output: &str) -> OnnxModel {
+        self.output = Some(output.to_string());
+        self
+    }
+
+    /// Runs the model on each row of a feature DataFrame
+    ///
+    /// Rows with missing features, or without enough preceding rows for sequence models, get null predictions.
+    ///
+    /// # Arguments
+    ///
+    /// * `df` - Feature DataFrame in chronological order
+    /// * `name` - Name of the prediction column (e.g. "signal"), models with several outputs per row
+    ///   get one column per output named `<name>_<i>`
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Input DataFrame with the prediction columns
+    pub fn predict(&self, df: &DataFrame, name: &str) -> Result<DataFrame, Box<dyn Error>> {
+        let columns = self.features.iter()
+            .map(|x| Ok(df.column(x)?.cast(&DataType::Float64)?.f64()?.to_vec()))
+            .collect::<Result<Vec<Vec<Option<f64>>>, Box<dyn Error>>>()?;
+        let height = df.height();
+        let n_features = columns.len();
+        let complete = (0..height).map(|i| columns.iter().all(|c| c[i].is_some())).collect::<Vec<bool>>();
+        let sequence_length = self.sequence_length.unwrap_or(1);
+
+        // Rows whose input window only has complete feature rows
+        let rows = (0..height)
+            .filter(|i| *i + 1 >= sequence_length && complete[i + 1 - sequence_length..=*i].iter().all(|x| *x))
+            .collect::<Vec<usize>>();
+        if rows.is_empty() {
+            return Err("No rows with complete features to run the model on".into());
+        }
+        let mut input = Vec::with_capacity(rows.len() * sequence_length * n_features);
+        for i in &rows {
+            for t in i + 1 - sequence_length..=*i {
+                input.extend(columns.iter().map(|c| c[t].unwrap_or_default() as f32));
+            }
+        }
+        let shape = match self.sequence_length {
+            Some(length) => vec![rows.len() as i64, length as i64, n_features as i64],
+            None => vec![rows.len() as i64, n_features as i64],
+        };
+        let tensor = Tensor::from_array((shape, input))?;
+        let outputs = self.session.run(ort::inputs![tensor]?)?;
+        let output = match &self.output {
+            Some(output) => outputs.get(output.as_str()).ok_or_else(|| {
+                format!("The model has no output named {}, its outputs are {:?}", output, outputs.keys().collect::<Vec<&str>>())
+            })?,
+            None if outputs.len() > 0 => &outputs[0],
+            None => return Err("The model has no outputs".into()),
+        };
+        let (output_shape, values) = output.try_extract_raw_tensor::<f32>()?;
+        let width = values.len() / rows.len();
+        if width == 0 || values.len() != width * rows.len() {
+            return Err(format!("Unexpected model output shape {:?} for {} rows", output_shape, rows.len()).into());
+        }
+
+        let mut result = df.clone();
+        for k in 0..width {
+            let mut predictions: Vec<Option<f64>> = vec![None; height];
+            for (r, i) in rows.iter().enumerate() {
+                predictions[*i] = Some(values[r * width + k] as f64);
+            }
+            let column = if width == 1 { name.to_string() } else { format!("{}_{}", name, k) };
+            result.with_column(Series::new(&column, predictions))?;
+        }
+        Ok(result)
+    }
+}
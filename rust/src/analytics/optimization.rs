@@ -1,14 +1,62 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
 use std::sync::{Arc, RwLock};
 use polars::frame::DataFrame;
-use optimization::{Minimizer, GradientDescent, NumericalDifferentiation, Func};
+use polars::prelude::Series;
+use optimization::{Minimizer, GradientDescent, NumericalDifferentiation, Func, Function, Function1};
+use minilp::{ComparisonOp, LinearExpr, OptimizationDirection, Problem};
 use crate::analytics::statistics::{mean_portfolio_return, portfolio_std_dev, rand_weights, maximum_drawdown,
                                    value_at_risk, expected_shortfall, daily_portfolio_returns, efficient_frontier_points};
 
+/// Maximum number of gradient descent iterations
+pub const MAX_ITERATIONS: usize = 1000;
+
+/// Gradient component size below which the optimization is considered converged
+pub const GRADIENT_TOLERANCE: f64 = 1e-3;
+
+/// Number of random feasible portfolios sampled for the efficient frontier of the linear programs
+pub const FRONTIER_SAMPLES: usize = 1000;
+
+/// Maximum number of times the MinCVaR program is solved again with a tighter drawdown limit when the
+/// compounded drawdown of its solution exceeds the limit
+pub const DRAWDOWN_REFINEMENTS: usize = 5;
+
+/// Penalty added to the gradient descent objective per percentage point of drawdown above the limit
+pub const DRAWDOWN_PENALTY: f64 = 10.0;
+
+/// Penalty added to the gradient descent objective per percentage point of per period tracking error above the limit
+pub const TRACKING_ERROR_PENALTY: f64 = 100.0;
+
+/// Number of assets most correlated with the replication residual evaluated at each greedy selection step
+pub const REPLICATION_CANDIDATES: usize = 10;
+
 /// Portfolio Optimization Result Struct
 #[derive(Debug, Clone)]
 pub struct OptResult {
     pub optimal_weights: Vec<f64>,
     pub efficient_frontier: Vec<Vec<f64>>,
+    pub diagnostics: OptimizationDiagnostics,
+}
+
+/// Solver diagnostics of a portfolio optimization
+///
+/// * `converged` - Whether every gradient component fell below the tolerance before reaching max_iterations
+/// * `iterations` - Number of gradient descent iterations performed
+/// * `max_iterations` - Iteration limit of the solver
+/// * `objective_value` - Final value of the minimized objective (negated for maximization objectives e.g. -Sharpe)
+/// * `objective_history` - Objective value at the initial weights and after each iteration
+/// * `active_constraints` - Asset, group and turnover constraints binding at the solution
+/// * `warnings` - Fallbacks taken by the solver (e.g. from the CVaR linear program to gradient descent)
+#[derive(Debug, Clone, Default)]
+pub struct OptimizationDiagnostics {
+    pub converged: bool,
+    pub iterations: usize,
+    pub max_iterations: usize,
+    pub objective_value: f64,
+    pub objective_history: Vec<f64>,
+    pub active_constraints: Vec<String>,
+    pub warnings: Vec<String>,
 }
 
 /// Objective functions for the optimization
@@ -18,7 +66,11 @@ pub struct OptResult {
 /// MaxReturn: Maximize the portfolio return
 /// MinDrawdown: Minimize the maximum drawdown
 /// MinVar: Minimize the portfolio VaR
-/// MinCVaR: Minimize the portfolio CVaR
+/// MinCVaR: Minimize the portfolio CVaR (solved exactly as a linear program over the historical scenarios)
+/// MaxDiversification: Maximize the diversification ratio (weighted average asset volatility / portfolio volatility)
+/// MinCorrelation: Minimize the weighted average correlation between the assets
+/// MinTrackingError: Minimize the tracking error (volatility of the returns in excess of the benchmark)
+/// MaxExcessReturn: Maximize the return in excess of the benchmark, usually combined with a tracking error limit
 #[derive(Debug, Clone, Copy)]
 pub enum ObjectiveFunction {
     MaxSharpe,
@@ -27,6 +79,10 @@ pub enum ObjectiveFunction {
     MinDrawdown,
     MinVar,
     MinCVaR,
+    MaxDiversification,
+    MinCorrelation,
+    MinTrackingError,
+    MaxExcessReturn,
 }
 
 impl ObjectiveFunction {
@@ -38,102 +94,1118 @@ impl ObjectiveFunction {
             "min_drawdown" => ObjectiveFunction::MinDrawdown,
             "min_var" => ObjectiveFunction::MinVar,
             "min_cvar" => ObjectiveFunction::MinCVaR,
+            "max_diversification" => ObjectiveFunction::MaxDiversification,
+            "min_correlation" => ObjectiveFunction::MinCorrelation,
+            "min_tracking_error" => ObjectiveFunction::MinTrackingError,
+            "max_excess_return" => ObjectiveFunction::MaxExcessReturn,
             _ => ObjectiveFunction::MaxSharpe,
         }
     }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            ObjectiveFunction::MaxSharpe => "max_sharpe".to_string(),
+            ObjectiveFunction::MinVol => "min_vol".to_string(),
+            ObjectiveFunction::MaxReturn => "max_return".to_string(),
+            ObjectiveFunction::MinDrawdown => "min_drawdown".to_string(),
+            ObjectiveFunction::MinVar => "min_var".to_string(),
+            ObjectiveFunction::MinCVaR => "min_cvar".to_string(),
+            ObjectiveFunction::MaxDiversification => "max_diversification".to_string(),
+            ObjectiveFunction::MinCorrelation => "min_correlation".to_string(),
+            ObjectiveFunction::MinTrackingError => "min_tracking_error".to_string(),
+            ObjectiveFunction::MaxExcessReturn => "max_excess_return".to_string(),
+        }
+    }
+
+    /// Whether the objective is maximized, its `objective_value` is then the negated metric
+    pub fn is_maximization(&self) -> bool {
+        matches!(self, ObjectiveFunction::MaxSharpe | ObjectiveFunction::MaxReturn | ObjectiveFunction::MaxDiversification
+                       | ObjectiveFunction::MaxExcessReturn)
+    }
+
+    /// Name of the metric the objective optimizes
+    pub fn metric_name(&self) -> String {
+        match self {
+            ObjectiveFunction::MaxSharpe => "Sharpe Ratio".to_string(),
+            ObjectiveFunction::MinVol => "Volatility (%)".to_string(),
+            ObjectiveFunction::MaxReturn => "Return (%)".to_string(),
+            ObjectiveFunction::MinDrawdown => "Max Drawdown (%)".to_string(),
+            ObjectiveFunction::MinVar => "VaR Loss (%)".to_string(),
+            ObjectiveFunction::MinCVaR => "CVaR Loss (%)".to_string(),
+            ObjectiveFunction::MaxDiversification => "Diversification Ratio".to_string(),
+            ObjectiveFunction::MinCorrelation => "Weighted Correlation".to_string(),
+            ObjectiveFunction::MinTrackingError => "Tracking Error (%)".to_string(),
+            ObjectiveFunction::MaxExcessReturn => "Excess Return (%)".to_string(),
+        }
+    }
+}
+
+/// Pareto-efficient portfolios of two competing objectives
+///
+/// * `objectives` - The two objective functions traded off against each other
+/// * `values` - Metric values of each efficient portfolio (e.g. return and CVaR loss), sorted by the first
+///   objective from its worst to its best value
+/// * `weights` - Weights of each efficient portfolio
+#[derive(Debug, Clone)]
+pub struct ParetoFrontier {
+    pub objectives: (ObjectiveFunction, ObjectiveFunction),
+    pub values: Vec<(f64, f64)>,
+    pub weights: Vec<Vec<f64>>,
+}
+
+
+/// Source of the category label used to group assets for category constraints
+///
+/// AssetClass: Quote type reported by Yahoo Finance (e.g. EQUITY, ETF, CRYPTOCURRENCY, FUTURE)
+/// Exchange: Exchange reported by Yahoo Finance (e.g. NasdaqGS, NYSE)
+/// Mapping: User supplied symbol to category mapping (e.g. sectors or custom asset classes)
+#[derive(Debug, Clone)]
+pub enum CategorySource {
+    AssetClass,
+    Exchange,
+    Mapping(HashMap<String, String>),
+}
+
+/// Category level weight constraint (e.g. max 30% in Technology, min 10% in bonds)
+#[derive(Debug, Clone)]
+pub struct CategoryConstraint {
+    pub source: CategorySource,
+    pub category: String,
+    pub min_weight: f64,
+    pub max_weight: f64,
+}
+
+impl CategoryConstraint {
+    pub fn new(source: CategorySource, category: &str, min_weight: f64, max_weight: f64) -> CategoryConstraint {
+        CategoryConstraint {
+            source,
+            category: category.to_string(),
+            min_weight,
+            max_weight,
+        }
+    }
+}
+
+/// Category constraint resolved to the indices of the portfolio assets it applies to
+#[derive(Debug, Clone)]
+pub struct GroupConstraint {
+    pub category: String,
+    pub indices: Vec<usize>,
+    pub min_weight: f64,
+    pub max_weight: f64,
 }
 
+/// Turnover constraint limiting the L1 distance of the optimal weights from the current weights
+#[derive(Debug, Clone)]
+pub struct TurnoverConstraint {
+    pub current_weights: Vec<f64>,
+    pub max_turnover: f64,
+}
+
+/// Cardinality and minimum position size constraints, keeping an allocation practical for small accounts
+///
+/// * `max_positions` - Optional maximum number of assets held (e.g. 10)
+/// * `min_weight` - Optional minimum weight of an asset if held in decimal (e.g. 0.02), a semi-continuous
+///   constraint where each weight is either zero or at least the minimum
+#[derive(Debug, Clone, Copy)]
+pub struct PositionConstraint {
+    pub max_positions: Option<usize>,
+    pub min_weight: Option<f64>,
+}
+
+impl PositionConstraint {
+    pub fn new(max_positions: Option<usize>, min_weight: Option<f64>) -> PositionConstraint {
+        PositionConstraint {
+            max_positions,
+            min_weight,
+        }
+    }
+
+    /// Selects the assets to hold from unconstrained weights and returns the asset bounds of the second stage
+    /// optimization, with unselected assets pinned at zero and the lower bound of the selected assets raised to
+    /// the minimum weight
+    ///
+    /// Assets with a positive lower bound are always held, the others are selected by decreasing weight while
+    /// they reach the minimum weight, within the maximum number of positions
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - Optimal weights without the position constraint
+    /// * `constraints` - Lower and upper weight bounds for each asset
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(f64, f64)>` - Lower and upper weight bounds for each asset
+    pub fn restrict(&self, weights: &[f64], constraints: &[(f64, f64)]) -> Result<Vec<(f64, f64)>, Box<dyn Error>> {
+        let bounds = (0..weights.len()).map(|i| constraints.get(i).cloned().unwrap_or((0.0, 1.0))).collect::<Vec<(f64, f64)>>();
+        let max_positions = self.max_positions.unwrap_or(weights.len());
+        let min_weight = self.min_weight.unwrap_or(0.0);
+        let forced = (0..weights.len()).filter(|&i| bounds[i].0 > 0.0).collect::<Vec<usize>>();
+        if forced.len() > max_positions {
+            return Err(format!("{} assets have a positive lower bound, more than the maximum of {} positions",
+                               forced.len(), max_positions).into());
+        }
+
+        let mut ranked = (0..weights.len()).filter(|i| !forced.contains(i)).collect::<Vec<usize>>();
+        ranked.sort_by(|&a, &b| weights[b].total_cmp(&weights[a]));
+        let mut held = forced.clone();
+        for i in ranked {
+            if held.len() >= max_positions {
+                break;
+            }
+            // The largest position is kept even below the minimum weight so that the portfolio is never empty
+            if weights[i] > 1e-6 && (weights[i] >= min_weight || held.is_empty()) {
+                held.push(i);
+            }
+        }
+
+        // Drop the smallest optional positions until the minimum weights fit in the budget
+        let lower = |held: &Vec<usize>| held.iter().map(|&i| bounds[i].0.max(min_weight)).sum::<f64>();
+        while lower(&held) > 1.0 + 1e-9 && held.len() > forced.len() {
+            let (position, _) = held.iter().enumerate()
+                .filter(|(_, i)| !forced.contains(i))
+                .min_by(|a, b| weights[*a.1].total_cmp(&weights[*b.1]))
+                .unwrap();
+            held.remove(position);
+        }
+        let upper = held.iter().map(|&i| bounds[i].1).sum::<f64>();
+        if lower(&held) > 1.0 + 1e-9 || upper < 1.0 - 1e-9 {
+            return Err("The position constraint is infeasible with the asset bounds".into());
+        }
+
+        Ok((0..weights.len())
+            .map(|i| if held.contains(&i) { (bounds[i].0.max(min_weight).min(bounds[i].1), bounds[i].1) } else { (0.0, 0.0) })
+            .collect())
+    }
+}
+
+/// Uncertainty set of the expected returns for robust mean-variance optimization, the return
+/// objectives then use the worst case portfolio return within the set
+///
+/// Ellipsoidal(kappa): mu'w - kappa * sqrt(w' (Sigma / T) w)
+/// Box(kappa): mu'w - kappa * sum(|w_i| * sigma_i / sqrt(T))
+///
+/// where Sigma / T is the covariance of the sample mean estimates over T return periods and
+/// kappa sets the size of the set (e.g. 1.0 - 3.0)
+#[derive(Debug, Clone, Copy)]
+pub enum UncertaintySet {
+    Ellipsoidal(f64),
+    Box(f64),
+}
+
+impl UncertaintySet {
+    /// Computes the worst case portfolio return within the uncertainty set
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - Vector of portfolio weights
+    /// * `mean_returns` - Vector of mean returns for each asset
+    /// * `cov_matrix` - Covariance matrix of asset returns
+    /// * `periods` - Number of return periods used to estimate the mean returns
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Worst case portfolio return
+    pub fn worst_case_return(&self, weights: &Vec<f64>, mean_returns: &Vec<f64>, cov_matrix: &ndarray::Array2<f64>, periods: usize) -> f64 {
+        let nominal = mean_portfolio_return(weights, mean_returns);
+        let periods = periods.max(1) as f64;
+        match self {
+            UncertaintySet::Ellipsoidal(kappa) => {
+                nominal - kappa * portfolio_std_dev(weights, cov_matrix) / periods.sqrt()
+            }
+            UncertaintySet::Box(kappa) => {
+                let penalty: f64 = weights.iter().enumerate()
+                    .map(|(i, w)| w.abs() * cov_matrix[[i, i]].sqrt())
+                    .sum();
+                nominal - kappa * penalty / periods.sqrt()
+            }
+        }
+    }
+}
+
+/// Settings of a portfolio optimization, shared by all objective functions
+///
+/// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
+/// * `confidence_level` - Confidence level for VaR and CVaR in decimal (e.g 0.95 for 95%)
+/// * `constraints` - Lower and upper weight bounds for each asset, equal bounds pin the asset weight
+/// * `group_constraints` - Lower and upper weight bounds for groups of assets
+/// * `turnover_constraint` - Optional maximum turnover relative to the current weights
+/// * `uncertainty_set` - Optional expected return uncertainty set for robust MaxSharpe and MaxReturn optimization
+/// * `max_drawdown` - Optional limit on the in-sample maximum drawdown in percent (e.g. 20.0), a linear constraint
+///   of the MinCVaR program (see `min_cvar_weights`) and a penalty on the gradient descent objectives
+/// * `tracking_error_limit` - Optional limit on the per period tracking error in percent, a penalty on the
+///   gradient descent objectives (the MinCVaR linear program only reports when it is exceeded)
+/// * `seed` - Optional random seed for the initial weights, making the optimization reproducible
+#[derive(Debug, Clone)]
+pub struct OptimizationSettings {
+    pub risk_free_rate: f64,
+    pub confidence_level: f64,
+    pub constraints: Vec<(f64, f64)>,
+    pub group_constraints: Vec<GroupConstraint>,
+    pub turnover_constraint: Option<TurnoverConstraint>,
+    pub uncertainty_set: Option<UncertaintySet>,
+    pub max_drawdown: Option<f64>,
+    pub tracking_error_limit: Option<f64>,
+    pub seed: Option<u64>,
+}
+
+impl OptimizationSettings {
+    /// Creates the settings of an optimization with only per-asset weight bounds
+    ///
+    /// # Arguments
+    ///
+    /// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
+    /// * `confidence_level` - Confidence level for VaR and CVaR in decimal (e.g 0.95 for 95%)
+    /// * `constraints` - Lower and upper weight bounds for each asset
+    ///
+    /// # Returns
+    ///
+    /// * `OptimizationSettings` struct
+    pub fn new(risk_free_rate: f64, confidence_level: f64, constraints: Vec<(f64, f64)>) -> OptimizationSettings {
+        OptimizationSettings {
+            risk_free_rate,
+            confidence_level,
+            constraints,
+            group_constraints: Vec::new(),
+            turnover_constraint: None,
+            uncertainty_set: None,
+            max_drawdown: None,
+            tracking_error_limit: None,
+            seed: None,
+        }
+    }
+}
 
 /// Computes the optimal portfolio weights for a given set of assets based on a given objective function
-/// and subject to a constraint for weights to sum to one and be non-negative
+/// and subject to a constraint for weights to sum to one and be non-negative, per-asset bounds and
+/// optional group (category) level bounds and turnover limit
+///
+/// Assets with equal lower and upper bounds are pinned to that weight and only the remaining
+/// assets are optimized. MinCVaR is solved with the Rockafellar-Uryasev linear program, falling
+/// back to gradient descent if the program cannot be solved
 ///
 /// # Arguments
 ///
 /// * `mean_returns` - Vector of mean returns for each asset
 /// * `cov_matrix` - Covariance matrix of asset returns
 /// * `portfolio_returns` - DataFrame of portfolio returns for each asset
-/// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
-/// * `confidence_level` - Confidence level for VaR and CVaR in decimal (e.g 0.95 for 95%)
-/// * `max_iterations` - Maximum number of iterations for the optimization (e.g. 1000)
+/// * `benchmark_returns` - Optional benchmark returns aligned with the asset returns, for the tracking error
+///   objectives and limit (a missing benchmark is treated as cash with zero returns)
 /// * `objective` - Objective function to optimize (e.g. ObjectiveFunction::MaxSharpe)
+/// * `settings` - Risk-free rate, confidence level, constraints and seed of the optimization
 ///
 /// # Returns
 ///
 /// * `OptResult` struct
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(objective = ?objective, assets = mean_returns.len())))]
 pub fn portfolio_optimization(
     mean_returns: &Vec<f64>,
     cov_matrix: &ndarray::Array2<f64>,
     portfolio_returns: &DataFrame,
-    risk_free_rate: f64,
-    confidence_level: f64,
+    benchmark_returns: Option<&Series>,
     objective: ObjectiveFunction,
-    constraints: Vec<(f64, f64)>,
+    settings: &OptimizationSettings,
 ) -> OptResult {
-    // objective: max_sharpe, min_vol, max_return, min_drawdown, min_var, min_cvar
+    // objective: max_sharpe, min_vol, max_return, min_drawdown, min_var, min_cvar, max_diversification, min_correlation,
+    // min_tracking_error, max_excess_return
+    let OptimizationSettings { constraints, group_constraints, turnover_constraint, max_drawdown, tracking_error_limit, .. } = settings;
+    let mut warnings = Vec::new();
+    if let ObjectiveFunction::MinCVaR = objective {
+        match min_cvar_weights(portfolio_returns, settings.confidence_level, constraints, group_constraints, turnover_constraint, *max_drawdown) {
+            Ok((weights, cvar)) => {
+                return linear_program_result(weights, cvar, mean_returns, cov_matrix, portfolio_returns, benchmark_returns, settings);
+            }
+            Err(e) => warnings.push(format!("CVaR linear program failed, solved by gradient descent: {}", e)),
+        }
+    }
     let efficient_frontier: Arc<RwLock<Vec<Vec<f64>>>> = Arc::new(RwLock::new(Vec::new()));
     let efficient_frontier_clone = Arc::clone(&efficient_frontier);
 
     // We use the gradient descent method to minimize the objective function
     let function = NumericalDifferentiation::new(Func(|weights: &[f64]| {
-        let weights = enforce_constraints(weights, &constraints);
+        let weights = enforce_constraints(weights, constraints, group_constraints, turnover_constraint);
         let _return = mean_portfolio_return(&weights.to_vec(), mean_returns);
         let std_dev = portfolio_std_dev(&weights.to_vec(), cov_matrix);
         if let Ok(mut guard) = efficient_frontier_clone.write() {
             guard.push(vec![_return, std_dev]);
         }
-        let objective = match objective {
-            ObjectiveFunction::MaxSharpe => {
-                let sharpe = (_return - risk_free_rate) / std_dev;
-                -sharpe
-            },
-            ObjectiveFunction::MinVol => {
-                std_dev
-            },
-            ObjectiveFunction::MaxReturn => {
-                -_return
-            },
-            ObjectiveFunction::MinDrawdown => {
-                let returns = daily_portfolio_returns(&weights, portfolio_returns);
-                let (_, drawdown) = maximum_drawdown(&returns);
-                drawdown
-            },
-            ObjectiveFunction::MinVar => {
-                let returns = daily_portfolio_returns(&weights, portfolio_returns);
-                let var = value_at_risk(&returns, confidence_level);
-                -var
-            },
-            ObjectiveFunction::MinCVaR => {
-                let returns = daily_portfolio_returns(&weights, portfolio_returns);
-                let es = expected_shortfall(&returns, confidence_level);
-                -es
+        let objective = objective_value(objective, &weights, mean_returns, cov_matrix, portfolio_returns,
+                                        benchmark_returns, settings);
+        let drawdown_penalty = match *max_drawdown {
+            Some(limit) => {
+                let (_, drawdown) = maximum_drawdown(&daily_portfolio_returns(&weights, portfolio_returns));
+                DRAWDOWN_PENALTY * (drawdown - limit).max(0.0)
             }
+            None => 0.0,
         };
-        objective
+        let tracking_error_penalty = match *tracking_error_limit {
+            Some(limit) => {
+                let tracking_error = tracking_error(&weights, portfolio_returns, benchmark_returns);
+                TRACKING_ERROR_PENALTY * (tracking_error - limit).max(0.0)
+            }
+            None => 0.0,
+        };
+        objective + drawdown_penalty + tracking_error_penalty
     }));
 
-    // We use a simple gradient descent scheme
-    let minimizer = GradientDescent::new();
-    let minimizer = minimizer.max_iterations(Some(1000));
-    let minimizer = minimizer.gradient_tolerance(1e-3);
-
-    // Initial guess for portfolio weights
-    let num_assets = mean_returns.len();
-    let initial_weights = rand_weights(num_assets);
-
     // Perform the actual minimization
-    let solution = minimizer.minimize(&function, initial_weights);
+    let descent = gradient_descent(&function, mean_returns.len(), settings.seed);
 
     // Enforce the constraints on the solution
-    let constrained_solution = enforce_constraints(&solution.position, &constraints);
+    let constrained_solution = enforce_constraints(&descent.position, constraints, group_constraints, turnover_constraint);
     let efficient_frontier = efficient_frontier_points(efficient_frontier.read().unwrap().clone());
+
+    let symbols = portfolio_returns.get_column_names().iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let diagnostics = OptimizationDiagnostics {
+        converged: descent.converged,
+        iterations: descent.iterations,
+        max_iterations: MAX_ITERATIONS,
+        objective_value: descent.objective_history.last().cloned().unwrap_or(f64::NAN),
+        objective_history: descent.objective_history,
+        active_constraints: active_constraints(&constrained_solution, &symbols, portfolio_returns, benchmark_returns, settings),
+        warnings,
+    };
+    #[cfg(feature = "tracing")]
+    tracing::debug!(converged = diagnostics.converged, iterations = diagnostics.iterations, "optimization finished");
+
     let result = OptResult {
         optimal_weights: constrained_solution,
         efficient_frontier: efficient_frontier.clone(),
+        diagnostics,
     };
     result
 }
 
-fn enforce_constraints(weights: &[f64], constraints: &[(f64, f64)]) -> Vec<f64> {
+/// Replicates the benchmark with at most `max_holdings` assets by greedy forward selection
+///
+/// Starting from an empty portfolio, each step screens the assets most correlated with the residual
+/// (benchmark returns not yet explained by the selected assets), adds the one giving the lowest tracking
+/// error after re-optimizing the weights of the selected assets, and stops early once no candidate lowers
+/// the tracking error. Asset upper bounds are respected, group and turnover constraints are not applied.
+///
+/// # Arguments
+///
+/// * `portfolio_returns` - DataFrame of portfolio returns for each asset
+/// * `benchmark_returns` - Benchmark returns aligned with the asset returns
+/// * `max_holdings` - Maximum number of assets held (e.g. 20)
+/// * `constraints` - Lower and upper weight bounds for each asset, lower bounds only apply to selected assets
+/// * `seed` - Optional random seed for the initial weights of each re-optimization
+///
+/// # Returns
+///
+/// * `OptResult` struct, with the tracking error (%) after each selection step as the objective history
+pub fn index_replication(
+    portfolio_returns: &DataFrame,
+    benchmark_returns: &Series,
+    max_holdings: usize,
+    constraints: &[(f64, f64)],
+    seed: Option<u64>,
+) -> Result<OptResult, Box<dyn Error>> {
+    if max_holdings == 0 {
+        return Err("The maximum number of holdings must be positive".into());
+    }
+    let symbols = portfolio_returns.get_column_names().iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let assets = portfolio_returns.get_columns().iter()
+        .map(|c| Ok(c.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>()))
+        .collect::<Result<Vec<Vec<f64>>, Box<dyn Error>>>()?;
+    let benchmark = benchmark_returns.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+    if assets.is_empty() || benchmark.len() < 2 {
+        return Err("No returns available for the index replication".into());
+    }
+
+    let mut selected: Vec<usize> = Vec::new();
+    let mut weights: Vec<f64> = Vec::new();
+    let mut tracking_error = f64::INFINITY;
+    let mut history = Vec::new();
+    while selected.len() < max_holdings.min(assets.len()) {
+        let replica = (0..benchmark.len())
+            .map(|t| selected.iter().zip(weights.iter()).map(|(&i, w)| w * assets[i][t]).sum::<f64>())
+            .collect::<Vec<f64>>();
+        let residual = benchmark.iter().zip(replica.iter()).map(|(b, r)| b - r).collect::<Vec<f64>>();
+        let mut candidates = (0..assets.len())
+            .filter(|i| !selected.contains(i))
+            .map(|i| (i, correlation(&assets[i], &residual)))
+            .filter(|(_, c)| c.is_finite())
+            .collect::<Vec<(usize, f64)>>();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut best: Option<(usize, Vec<f64>, f64)> = None;
+        for &(candidate, _) in candidates.iter().take(REPLICATION_CANDIDATES) {
+            let subset = selected.iter().cloned().chain(std::iter::once(candidate)).collect::<Vec<usize>>();
+            let (subset_weights, subset_te) = min_tracking_error_weights(&subset, &assets, &benchmark, constraints, seed);
+            if best.as_ref().is_none_or(|b| subset_te < b.2) {
+                best = Some((candidate, subset_weights, subset_te));
+            }
+        }
+        match best {
+            Some((candidate, subset_weights, subset_te)) if subset_te < tracking_error - 1e-9 => {
+                selected.push(candidate);
+                weights = subset_weights;
+                tracking_error = subset_te;
+                history.push(subset_te);
+            }
+            _ => break,
+        }
+    }
+
+    let mut optimal_weights = vec![0.0; assets.len()];
+    for (&i, &w) in selected.iter().zip(weights.iter()) {
+        optimal_weights[i] = w;
+    }
+    let mut active = selected.iter().zip(weights.iter())
+        .filter(|(&i, &w)| constraints.get(i).is_some_and(|&(_, ub)| ub - w < 1e-6))
+        .map(|(&i, _)| format!("{} upper bound", symbols[i]))
+        .collect::<Vec<String>>();
+    if selected.len() == max_holdings {
+        active.push(format!("Max holdings ({})", max_holdings));
+    }
+    let diagnostics = OptimizationDiagnostics {
+        converged: true,
+        iterations: history.len(),
+        max_iterations: max_holdings,
+        objective_value: tracking_error,
+        objective_history: history,
+        active_constraints: active,
+        warnings: Vec::new(),
+    };
+    Ok(OptResult {
+        optimal_weights,
+        efficient_frontier: Vec::new(),
+        diagnostics,
+    })
+}
+
+/// Minimizes the tracking error of a subset of the assets, returning the subset weights and tracking error (%)
+fn min_tracking_error_weights(
+    subset: &[usize],
+    assets: &[Vec<f64>],
+    benchmark: &[f64],
+    constraints: &[(f64, f64)],
+    seed: Option<u64>,
+) -> (Vec<f64>, f64) {
+    let bounds = subset.iter().map(|&i| constraints.get(i).cloned().unwrap_or((0.0, 1.0))).collect::<Vec<(f64, f64)>>();
+    let active_volatility = |weights: &[f64]| {
+        let active = (0..benchmark.len())
+            .map(|t| subset.iter().zip(weights.iter()).map(|(&i, w)| w * assets[i][t]).sum::<f64>() - benchmark[t])
+            .collect::<Vec<f64>>();
+        let mean = active.iter().sum::<f64>() / active.len() as f64;
+        (active.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (active.len() - 1) as f64).sqrt()
+    };
+    if subset.len() == 1 {
+        return (vec![1.0], active_volatility(&[1.0]));
+    }
+    let function = NumericalDifferentiation::new(Func(|weights: &[f64]| {
+        active_volatility(&enforce_constraints(weights, &bounds, &[], &None))
+    }));
+    let descent = gradient_descent(&function, subset.len(), seed);
+    let weights = enforce_constraints(&descent.position, &bounds, &[], &None);
+    let value = active_volatility(&weights);
+    (weights, value)
+}
+
+/// Pearson correlation of two equally long series
+fn correlation(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len().min(y.len()) as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let covariance = x.iter().zip(y.iter()).map(|(a, b)| (a - mean_x) * (b - mean_y)).sum::<f64>();
+    let var_x = x.iter().map(|a| (a - mean_x).powi(2)).sum::<f64>();
+    let var_y = y.iter().map(|b| (b - mean_y).powi(2)).sum::<f64>();
+    covariance / (var_x * var_y).sqrt()
+}
+
+/// Computes the Pareto frontier of two objectives, the portfolios for which neither objective can be
+/// improved without worsening the other
+///
+/// The frontier is traced by minimizing weighted sums of the two objectives, each normalized by its range
+/// between the single objective optima, and completed with random feasible portfolios to cover non-convex
+/// parts of the frontier. Dominated portfolios are dropped.
+///
+/// # Arguments
+///
+/// * `mean_returns` - Vector of mean returns for each asset
+/// * `cov_matrix` - Covariance matrix of asset returns
+/// * `portfolio_returns` - DataFrame of portfolio returns for each asset
+/// * `benchmark_returns` - Optional benchmark returns aligned with the asset returns, for the tracking error objectives
+/// * `objectives` - The two objective functions (e.g. (ObjectiveFunction::MaxReturn, ObjectiveFunction::MinCVaR))
+/// * `points` - Number of weighted sum optimizations including the two single objective optima (e.g. 20)
+/// * `settings` - Settings of the optimizations, the drawdown and tracking error limits are not applied and the
+///   seed also draws the random portfolios
+///
+/// # Returns
+///
+/// * `ParetoFrontier` struct
+pub fn pareto_frontier(
+    mean_returns: &Vec<f64>,
+    cov_matrix: &ndarray::Array2<f64>,
+    portfolio_returns: &DataFrame,
+    benchmark_returns: Option<&Series>,
+    objectives: (ObjectiveFunction, ObjectiveFunction),
+    points: usize,
+    settings: &OptimizationSettings,
+) -> Result<ParetoFrontier, Box<dyn Error>> {
+    if points < 2 {
+        return Err("At least 2 points are required for the Pareto frontier".into());
+    }
+    let settings = OptimizationSettings { max_drawdown: None, tracking_error_limit: None, ..settings.clone() };
+    let OptimizationSettings { constraints, group_constraints, turnover_constraint, seed, .. } = &settings;
+    let evaluate = |weights: &Vec<f64>| (
+        objective_value(objectives.0, weights, mean_returns, cov_matrix, portfolio_returns, benchmark_returns, &settings),
+        objective_value(objectives.1, weights, mean_returns, cov_matrix, portfolio_returns, benchmark_returns, &settings),
+    );
+
+    // The single objective optima anchor the ends of the frontier and set the normalization ranges
+    let anchors = [objectives.0, objectives.1].iter()
+        .map(|&objective| portfolio_optimization(mean_returns, cov_matrix, portfolio_returns, benchmark_returns,
+                                                 objective, &settings).optimal_weights)
+        .collect::<Vec<Vec<f64>>>();
+    let anchor_values = anchors.iter().map(evaluate).collect::<Vec<(f64, f64)>>();
+    let range = |a: f64, b: f64| if (a - b).abs() > 1e-12 { (a - b).abs() } else { 1.0 };
+    let (min_0, range_0) = (anchor_values[0].0, range(anchor_values[0].0, anchor_values[1].0));
+    let (min_1, range_1) = (anchor_values[1].1, range(anchor_values[0].1, anchor_values[1].1));
+
+    let mut candidates = anchors;
+    for j in 1..points - 1 {
+        let lambda = j as f64 / (points - 1) as f64;
+        let function = NumericalDifferentiation::new(Func(|weights: &[f64]| {
+            let weights = enforce_constraints(weights, constraints, group_constraints, turnover_constraint);
+            let (value_0, value_1) = evaluate(&weights);
+            (1.0 - lambda) * (value_0 - min_0) / range_0 + lambda * (value_1 - min_1) / range_1
+        }));
+        let descent = gradient_descent(&function, mean_returns.len(), seed.map(|s| s.wrapping_add(j as u64)));
+        candidates.push(enforce_constraints(&descent.position, constraints, group_constraints, turnover_constraint));
+    }
+    candidates.extend(random_portfolios(mean_returns.len(), FRONTIER_SAMPLES, constraints, group_constraints,
+                                        turnover_constraint, *seed));
+
+    let values = candidates.iter().map(evaluate).collect::<Vec<(f64, f64)>>();
+    let dominated = |i: usize| values.iter().any(|v| {
+        v.0 <= values[i].0 && v.1 <= values[i].1 && (v.0 < values[i].0 || v.1 < values[i].1)
+    });
+    let mut efficient = (0..candidates.len())
+        .filter(|&i| values[i].0.is_finite() && values[i].1.is_finite() && !dominated(i))
+        .collect::<Vec<usize>>();
+    efficient.sort_by(|&a, &b| values[b].0.total_cmp(&values[a].0));
+    efficient.dedup_by(|a, b| (values[*a].0 - values[*b].0).abs() < 1e-12 && (values[*a].1 - values[*b].1).abs() < 1e-12);
+
+    // Report the metrics themselves rather than the minimized (negated) objective values
+    let sign = |objective: ObjectiveFunction| if objective.is_maximization() { -1.0 } else { 1.0 };
+    Ok(ParetoFrontier {
+        objectives,
+        values: efficient.iter().map(|&i| (sign(objectives.0) * values[i].0, sign(objectives.1) * values[i].1)).collect(),
+        weights: efficient.iter().map(|&i| candidates[i].clone()).collect(),
+    })
+}
+
+/// Result of a gradient descent run
+///
+/// * `position` - Final (unconstrained) position
+/// * `objective_history` - Objective value at the initial position and after each iteration
+/// * `iterations` - Number of iterations performed
+/// * `converged` - Whether every gradient component fell below the tolerance before reaching the iteration limit
+struct Descent {
+    position: Vec<f64>,
+    objective_history: Vec<f64>,
+    iterations: usize,
+    converged: bool,
+}
+
+/// Function wrapper tracing a single minimizer run
+///
+/// The minimizer evaluates the gradient once per iterate, right after its value, so the value at each new
+/// gradient is the objective history. The Armijo line search evaluates the value and gradient of its starting
+/// point again, so the last value and gradient are memoized instead of being recomputed.
+struct TracedFunction<'a, F: Function1> {
+    function: &'a F,
+    last_value: RefCell<Option<(Vec<f64>, f64)>>,
+    last_gradient: RefCell<Option<(Vec<f64>, Vec<f64>)>>,
+    history: RefCell<Vec<f64>>,
+}
+
+impl<'a, F: Function1> TracedFunction<'a, F> {
+    fn new(function: &'a F) -> Self {
+        TracedFunction {
+            function,
+            last_value: RefCell::new(None),
+            last_gradient: RefCell::new(None),
+            history: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl<F: Function1> Function for TracedFunction<'_, F> {
+    fn value(&self, position: &[f64]) -> f64 {
+        if let Some((p, value)) = self.last_value.borrow().as_ref() {
+            if p.as_slice() == position {
+                return *value;
+            }
+        }
+        let value = self.function.value(position);
+        *self.last_value.borrow_mut() = Some((position.to_vec(), value));
+        value
+    }
+}
+
+impl<F: Function1> Function1 for TracedFunction<'_, F> {
+    fn gradient(&self, position: &[f64]) -> Vec<f64> {
+        if let Some((p, gradient)) = self.last_gradient.borrow().as_ref() {
+            if p.as_slice() == position {
+                return gradient.clone();
+            }
+        }
+        let value = self.value(position);
+        self.history.borrow_mut().push(value);
+        let gradient = self.function.gradient(position);
+        *self.last_gradient.borrow_mut() = Some((position.to_vec(), gradient.clone()));
+        gradient
+    }
+}
+
+/// Minimizes a function of the portfolio weights with a single gradient descent run, stopping when every
+/// gradient component is below `GRADIENT_TOLERANCE` or after `MAX_ITERATIONS` iterations
+fn gradient_descent<F: Function1>(function: &F, num_assets: usize, seed: Option<u64>) -> Descent {
+    let minimizer = GradientDescent::new();
+    let minimizer = minimizer.max_iterations(Some(MAX_ITERATIONS as u64));
+    let minimizer = minimizer.gradient_tolerance(GRADIENT_TOLERANCE);
+
+    // Initial guess for portfolio weights
+    let traced = TracedFunction::new(function);
+    let solution = minimizer.minimize(&traced, rand_weights(num_assets, seed));
+
+    // The run converged if it stopped on a gradient of the final position below the tolerance,
+    // otherwise it stopped at the iteration limit before evaluating the gradient of the final position
+    let converged = traced.last_gradient.borrow().as_ref()
+        .is_some_and(|(p, g)| *p == solution.position && g.iter().all(|x| x.abs() <= GRADIENT_TOLERANCE));
+    let mut objective_history = traced.history.into_inner();
+    if !converged {
+        objective_history.push(solution.value);
+    }
+    Descent {
+        position: solution.position,
+        iterations: objective_history.len() - 1,
+        objective_history,
+        converged,
+    }
+}
+
+/// Evaluates an objective function at the given portfolio weights, negated for the maximization
+/// objectives so that lower values are always better
+///
+/// # Arguments
+///
+/// * `objective` - Objective function to evaluate (e.g. ObjectiveFunction::MaxSharpe)
+/// * `weights` - Vector of portfolio weights
+/// * `mean_returns` - Vector of mean returns for each asset
+/// * `cov_matrix` - Covariance matrix of asset returns
+/// * `portfolio_returns` - DataFrame of portfolio returns for each asset
+/// * `benchmark_returns` - Optional benchmark returns aligned with the asset returns, for the tracking error objectives
+/// * `settings` - Settings of the optimization, of which the risk-free rate, confidence level and uncertainty set are used
+///
+/// # Returns
+///
+/// * `f64` - Objective value to minimize (e.g. -Sharpe, volatility in percent)
+pub fn objective_value(
+    objective: ObjectiveFunction,
+    weights: &Vec<f64>,
+    mean_returns: &Vec<f64>,
+    cov_matrix: &ndarray::Array2<f64>,
+    portfolio_returns: &DataFrame,
+    benchmark_returns: Option<&Series>,
+    settings: &OptimizationSettings,
+) -> f64 {
+    let OptimizationSettings { risk_free_rate, confidence_level, uncertainty_set, .. } = *settings;
+    let std_dev = portfolio_std_dev(weights, cov_matrix);
+    let robust_return = match uncertainty_set {
+        Some(set) => set.worst_case_return(weights, mean_returns, cov_matrix, portfolio_returns.height()),
+        None => mean_portfolio_return(weights, mean_returns),
+    };
+    match objective {
+        ObjectiveFunction::MaxSharpe => {
+            let sharpe = (robust_return - risk_free_rate) / std_dev;
+            -sharpe
+        },
+        ObjectiveFunction::MinVol => {
+            std_dev
+        },
+        ObjectiveFunction::MaxReturn => {
+            -robust_return
+        },
+        ObjectiveFunction::MinDrawdown => {
+            let returns = daily_portfolio_returns(weights, portfolio_returns);
+            let (_, drawdown) = maximum_drawdown(&returns);
+            drawdown
+        },
+        ObjectiveFunction::MinVar => {
+            let returns = daily_portfolio_returns(weights, portfolio_returns);
+            let var = value_at_risk(&returns, confidence_level);
+            -var
+        },
+        ObjectiveFunction::MinCVaR => {
+            let returns = daily_portfolio_returns(weights, portfolio_returns);
+            let es = expected_shortfall(&returns, confidence_level);
+            -es
+        },
+        ObjectiveFunction::MaxDiversification => {
+            let asset_std_devs = cov_matrix.diag().mapv(f64::sqrt).to_vec();
+            let weighted_std_dev: f64 = weights.iter().zip(asset_std_devs.iter()).map(|(w, s)| w * s).sum();
+            -(weighted_std_dev / std_dev)
+        },
+        ObjectiveFunction::MinCorrelation => {
+            let asset_std_devs = cov_matrix.diag().mapv(f64::sqrt).to_vec();
+            weighted_correlation(weights, cov_matrix, &asset_std_devs)
+        },
+        ObjectiveFunction::MinTrackingError => {
+            tracking_error(weights, portfolio_returns, benchmark_returns)
+        },
+        ObjectiveFunction::MaxExcessReturn => {
+            let active = active_returns(weights, portfolio_returns, benchmark_returns);
+            -(active.iter().sum::<f64>() / active.len().max(1) as f64)
+        }
+    }
+}
+
+/// Returns of the portfolio in excess of the benchmark in each period, a missing benchmark is treated as cash
+fn active_returns(weights: &Vec<f64>, portfolio_returns: &DataFrame, benchmark_returns: Option<&Series>) -> Vec<f64> {
+    let returns = daily_portfolio_returns(weights, portfolio_returns).f64().unwrap().to_vec();
+    let benchmark = match benchmark_returns.and_then(|x| x.f64().ok()) {
+        Some(b) => b.to_vec(),
+        None => vec![Some(0.0); returns.len()],
+    };
+    returns.iter().zip(benchmark.iter())
+        .filter_map(|(r, b)| match (r, b) {
+            (Some(r), Some(b)) if r.is_finite() && b.is_finite() => Some(r - b),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Computes the per period tracking error (standard deviation of the active returns) of the portfolio in percent
+///
+/// # Arguments
+///
+/// * `weights` - Vector of portfolio weights
+/// * `portfolio_returns` - DataFrame of portfolio returns for each asset
+/// * `benchmark_returns` - Optional benchmark returns aligned with the asset returns (a missing benchmark is treated as cash)
+///
+/// # Returns
+///
+/// * `f64` - Tracking error, NaN with less than two periods
+pub fn tracking_error(weights: &Vec<f64>, portfolio_returns: &DataFrame, benchmark_returns: Option<&Series>) -> f64 {
+    let active = active_returns(weights, portfolio_returns, benchmark_returns);
+    if active.len() < 2 {
+        return f64::NAN;
+    }
+    let mean = active.iter().sum::<f64>() / active.len() as f64;
+    (active.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (active.len() - 1) as f64).sqrt()
+}
+
+/// Minimizes the portfolio CVaR with the Rockafellar-Uryasev linear program, using each period of
+/// the asset returns as an equally likely scenario
+///
+/// min  alpha + 1 / ((1 - beta) * T) * sum(u_t)
+/// s.t. u_t >= -r_t . w - alpha, u_t >= 0, sum(w) = 1, lb <= w <= ub, group and turnover bounds
+///
+/// A drawdown limit D adds the running peak p_t of the cumulative return c_t = (r_1 + ... + r_t) . w,
+/// with p_t >= c_t, p_t >= p_(t-1) and p_t - c_t <= D
+///
+/// The constraint is linear because it is built on the additive cumulative returns, the same measure that
+/// `maximum_drawdown` reports. It only approximates the drawdown of the compounded returns, so the compounded
+/// drawdown of the solution is checked after solving and the program is solved again with a proportionally
+/// tighter limit while it exceeds D, up to `DRAWDOWN_REFINEMENTS` times. An error is returned when the
+/// compounded drawdown still exceeds D.
+///
+/// # Arguments
+///
+/// * `portfolio_returns` - DataFrame of portfolio returns for each asset
+/// * `confidence_level` - Confidence level of the CVaR in decimal (e.g 0.95 for 95%)
+/// * `constraints` - Lower and upper weight bounds for each asset
+/// * `group_constraints` - Lower and upper weight bounds for groups of assets
+/// * `turnover_constraint` - Optional maximum turnover relative to the current weights
+/// * `max_drawdown` - Optional limit on the maximum drawdown in percent, of the additive cumulative returns in the
+///   program and of the compounded returns at the solution
+///
+/// # Returns
+///
+/// * `(Vec<f64>, f64)` - Optimal weights and the minimized CVaR (expected loss beyond the VaR)
+pub fn min_cvar_weights(
+    portfolio_returns: &DataFrame,
+    confidence_level: f64,
+    constraints: &[(f64, f64)],
+    group_constraints: &[GroupConstraint],
+    turnover_constraint: &Option<TurnoverConstraint>,
+    max_drawdown: Option<f64>,
+) -> Result<(Vec<f64>, f64), Box<dyn Error>> {
+    let scenarios = portfolio_returns.get_columns().iter()
+        .map(|c| Ok(c.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>()))
+        .collect::<Result<Vec<Vec<f64>>, Box<dyn Error>>>()?;
+    if scenarios.is_empty() || portfolio_returns.height() == 0 {
+        return Err("No returns available for the CVaR optimization".into());
+    }
+
+    let Some(target) = max_drawdown else {
+        return cvar_program(&scenarios, confidence_level, constraints, group_constraints, turnover_constraint, None);
+    };
+    let mut limit = target;
+    let mut solution = cvar_program(&scenarios, confidence_level, constraints, group_constraints, turnover_constraint, Some(limit))?;
+    for _ in 0..DRAWDOWN_REFINEMENTS {
+        let drawdown = compounded_drawdown(&solution.0, &scenarios);
+        if drawdown <= target {
+            return Ok(solution);
+        }
+        limit *= target / drawdown;
+        match cvar_program(&scenarios, confidence_level, constraints, group_constraints, turnover_constraint, Some(limit)) {
+            Ok(tighter) => solution = tighter,
+            Err(_) => break,
+        }
+    }
+    let drawdown = compounded_drawdown(&solution.0, &scenarios);
+    if drawdown > target {
+        return Err(format!("Compounded drawdown of the MinCVaR portfolio ({:.2}%) exceeds the limit of {:.2}%",
+                           drawdown, target).into());
+    }
+    Ok(solution)
+}
+
+/// Maximum drawdown in percent of the compounded returns of a portfolio of percent return scenarios
+fn compounded_drawdown(weights: &[f64], scenarios: &[Vec<f64>]) -> f64 {
+    let num_scenarios = scenarios.first().map_or(0, |x| x.len());
+    let mut wealth = 1.0;
+    let mut peak = f64::NEG_INFINITY;
+    let mut max_drawdown = 0.0;
+    for t in 0..num_scenarios {
+        let portfolio_return = weights.iter().zip(scenarios.iter()).map(|(w, r)| w * r[t]).sum::<f64>();
+        wealth *= 1.0 + portfolio_return / 100.0;
+        peak = f64::max(peak, wealth);
+        max_drawdown = f64::max(max_drawdown, (1.0 - wealth / peak) * 100.0);
+    }
+    max_drawdown
+}
+
+/// Builds and solves the Rockafellar-Uryasev linear program, with an optional limit on the drawdown
+/// of the additive cumulative returns
+fn cvar_program(
+    scenarios: &[Vec<f64>],
+    confidence_level: f64,
+    constraints: &[(f64, f64)],
+    group_constraints: &[GroupConstraint],
+    turnover_constraint: &Option<TurnoverConstraint>,
+    max_drawdown: Option<f64>,
+) -> Result<(Vec<f64>, f64), Box<dyn Error>> {
+    let num_assets = scenarios.len();
+    let num_scenarios = scenarios[0].len();
+
+    let mut problem = Problem::new(OptimizationDirection::Minimize);
+    let weights = (0..num_assets)
+        .map(|i| problem.add_var(0.0, constraints.get(i).cloned().unwrap_or((0.0, 1.0))))
+        .collect::<Vec<_>>();
+    let alpha = problem.add_var(1.0, (f64::NEG_INFINITY, f64::INFINITY));
+    let tail_weight = 1.0 / ((1.0 - confidence_level) * num_scenarios as f64);
+    for t in 0..num_scenarios {
+        // u_t + r_t . w + alpha >= 0
+        let excess_loss = problem.add_var(tail_weight, (0.0, f64::INFINITY));
+        let mut expr = LinearExpr::empty();
+        expr.add(excess_loss, 1.0);
+        expr.add(alpha, 1.0);
+        for (&weight, returns) in weights.iter().zip(scenarios) {
+            expr.add(weight, returns[t]);
+        }
+        problem.add_constraint(expr, ComparisonOp::Ge, 0.0);
+    }
+
+    let mut budget = LinearExpr::empty();
+    weights.iter().for_each(|&w| budget.add(w, 1.0));
+    problem.add_constraint(budget, ComparisonOp::Eq, 1.0);
+
+    for group in group_constraints {
+        if group.indices.is_empty() {
+            continue;
+        }
+        let terms = group.indices.iter().map(|&i| (weights[i], 1.0)).collect::<Vec<_>>();
+        problem.add_constraint(terms.as_slice(), ComparisonOp::Ge, group.min_weight);
+        problem.add_constraint(terms.as_slice(), ComparisonOp::Le, group.max_weight);
+    }
+
+    if let Some(turnover) = turnover_constraint {
+        if turnover.current_weights.len() == num_assets {
+            // d_i >= |w_i - c_i| and sum(d_i) <= max_turnover
+            let mut total = LinearExpr::empty();
+            for (&weight, &current) in weights.iter().zip(&turnover.current_weights) {
+                let change = problem.add_var(0.0, (0.0, f64::INFINITY));
+                problem.add_constraint([(change, 1.0), (weight, -1.0)], ComparisonOp::Ge, -current);
+                problem.add_constraint([(change, 1.0), (weight, 1.0)], ComparisonOp::Ge, current);
+                total.add(change, 1.0);
+            }
+            problem.add_constraint(total, ComparisonOp::Le, turnover.max_turnover);
+        }
+    }
+
+    if let Some(limit) = max_drawdown {
+        let mut cumulative = vec![0.0; num_assets];
+        let mut previous_peak = None;
+        for t in 0..num_scenarios {
+            let peak = problem.add_var(0.0, (f64::NEG_INFINITY, f64::INFINITY));
+            // p_t - c_t >= 0 and p_t - c_t <= D
+            let mut terms = vec![(peak, 1.0)];
+            for ((&weight, returns), total) in weights.iter().zip(scenarios).zip(cumulative.iter_mut()) {
+                *total += returns[t];
+                terms.push((weight, -*total));
+            }
+            problem.add_constraint(terms.as_slice(), ComparisonOp::Ge, 0.0);
+            problem.add_constraint(terms.as_slice(), ComparisonOp::Le, limit);
+            if let Some(previous) = previous_peak {
+                problem.add_constraint([(peak, 1.0), (previous, -1.0)], ComparisonOp::Ge, 0.0);
+            }
+            previous_peak = Some(peak);
+        }
+    }
+
+    let solution = problem.solve()?;
+    let optimal_weights = weights.iter().map(|&w| solution[w].max(0.0)).collect::<Vec<f64>>();
+    Ok((optimal_weights, solution.objective()))
+}
+
+/// Samples random long-only portfolios satisfying the weight constraints
+///
+/// Each sample draws random weights and projects them onto the asset, group and turnover constraints,
+/// the same way the optimizer enforces them on its solution.
+///
+/// # Arguments
+///
+/// * `num_assets` - Number of assets in the portfolio
+/// * `samples` - Number of portfolios to sample (e.g. 1000)
+/// * `constraints` - Lower and upper weight bounds for each asset
+/// * `group_constraints` - Lower and upper weight bounds for groups of assets
+/// * `turnover_constraint` - Optional maximum turnover relative to the current weights
+/// * `seed` - Optional random seed, sample i uses seed + i
+///
+/// # Returns
+///
+/// * `Vec<Vec<f64>>` - Weights of each sampled portfolio
+pub fn random_portfolios(
+    num_assets: usize,
+    samples: usize,
+    constraints: &[(f64, f64)],
+    group_constraints: &[GroupConstraint],
+    turnover_constraint: &Option<TurnoverConstraint>,
+    seed: Option<u64>,
+) -> Vec<Vec<f64>> {
+    (0..samples as u64)
+        .map(|i| {
+            let sample = rand_weights(num_assets, seed.map(|s| s.wrapping_add(i)));
+            enforce_constraints(&sample, constraints, group_constraints, turnover_constraint)
+        })
+        .collect()
+}
+
+/// Builds the optimization result of a linear program solution, sampling random feasible portfolios
+/// for the efficient frontier since the solver does not traverse the weight space
+fn linear_program_result(
+    weights: Vec<f64>,
+    objective_value: f64,
+    mean_returns: &Vec<f64>,
+    cov_matrix: &ndarray::Array2<f64>,
+    portfolio_returns: &DataFrame,
+    benchmark_returns: Option<&Series>,
+    settings: &OptimizationSettings,
+) -> OptResult {
+    let points = random_portfolios(weights.len(), FRONTIER_SAMPLES, &settings.constraints, &settings.group_constraints,
+                                   &settings.turnover_constraint, settings.seed)
+        .iter()
+        .map(|sample| vec![mean_portfolio_return(sample, mean_returns), portfolio_std_dev(sample, cov_matrix)])
+        .chain(std::iter::once(vec![mean_portfolio_return(&weights, mean_returns), portfolio_std_dev(&weights, cov_matrix)]))
+        .collect::<Vec<Vec<f64>>>();
+    let symbols = portfolio_returns.get_column_names().iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let diagnostics = OptimizationDiagnostics {
+        converged: true,
+        iterations: 1,
+        max_iterations: 1,
+        objective_value,
+        objective_history: vec![objective_value],
+        active_constraints: active_constraints(&weights, &symbols, portfolio_returns, benchmark_returns, settings),
+        warnings: Vec::new(),
+    };
+    OptResult {
+        optimal_weights: weights,
+        efficient_frontier: efficient_frontier_points(points),
+        diagnostics,
+    }
+}
+
+/// Computes the portfolio variance under the asset correlation matrix (w' C w), i.e. the
+/// weighted average pairwise correlation of the assets
+fn weighted_correlation(weights: &[f64], cov_matrix: &ndarray::Array2<f64>, std_devs: &[f64]) -> f64 {
+    let mut value = 0.0;
+    for i in 0..weights.len() {
+        for j in 0..weights.len() {
+            let denominator = std_devs[i] * std_devs[j];
+            let correlation = if denominator > 0.0 { cov_matrix[[i, j]] / denominator } else { 0.0 };
+            value += weights[i] * weights[j] * correlation;
+        }
+    }
+    value
+}
+
+/// Lists the constraints binding at the given weights
+fn active_constraints(
+    weights: &[f64],
+    symbols: &[String],
+    portfolio_returns: &DataFrame,
+    benchmark_returns: Option<&Series>,
+    settings: &OptimizationSettings,
+) -> Vec<String> {
+    let OptimizationSettings { constraints, group_constraints, turnover_constraint, max_drawdown, tracking_error_limit, .. } = settings;
+    let tolerance = 1e-6;
+    let mut active = Vec::new();
+    for (i, &(lb, ub)) in constraints.iter().enumerate() {
+        let symbol = symbols.get(i).cloned().unwrap_or(format!("Asset {}", i + 1));
+        if is_pinned(lb, ub) {
+            active.push(format!("{} pinned weight ({:.2})", symbol, lb));
+        } else if (weights[i] - lb).abs() < tolerance {
+            active.push(format!("{} lower bound ({:.2})", symbol, lb));
+        } else if (weights[i] - ub).abs() < tolerance {
+            active.push(format!("{} upper bound ({:.2})", symbol, ub));
+        }
+    }
+    for group in group_constraints {
+        let group_sum: f64 = group.indices.iter().map(|&i| weights[i]).sum();
+        if (group_sum - group.min_weight).abs() < tolerance {
+            active.push(format!("{} lower bound ({:.2})", group.category, group.min_weight));
+        } else if (group_sum - group.max_weight).abs() < tolerance {
+            active.push(format!("{} upper bound ({:.2})", group.category, group.max_weight));
+        }
+    }
+    if let Some(turnover) = turnover_constraint {
+        let value: f64 = weights.iter().zip(turnover.current_weights.iter()).map(|(w, c)| (w - c).abs()).sum();
+        if (value - turnover.max_turnover).abs() < tolerance {
+            active.push(format!("Turnover limit ({:.2})", turnover.max_turnover));
+        }
+    }
+    if let Some(limit) = *max_drawdown {
+        // The gradient descent objectives only penalize the drawdown, so the limit can be exceeded
+        let (_, drawdown) = maximum_drawdown(&daily_portfolio_returns(&weights.to_vec(), portfolio_returns));
+        if drawdown > limit + 1e-6 {
+            active.push(format!("Max drawdown limit ({:.2}%) exceeded ({:.2}%)", limit, drawdown));
+        } else if (drawdown - limit).abs() < 1e-2 {
+            active.push(format!("Max drawdown limit ({:.2}%)", limit));
+        }
+    }
+    if let Some(limit) = *tracking_error_limit {
+        let tracking_error = tracking_error(&weights.to_vec(), portfolio_returns, benchmark_returns);
+        if tracking_error > limit + 1e-6 {
+            active.push(format!("Tracking error limit ({:.4}%) exceeded ({:.4}%)", limit, tracking_error));
+        } else if (tracking_error - limit).abs() < 1e-3 {
+            active.push(format!("Tracking error limit ({:.4}%)", limit));
+        }
+    }
+    active
+}
+
+fn enforce_constraints(
+    weights: &[f64],
+    constraints: &[(f64, f64)],
+    group_constraints: &[GroupConstraint],
+    turnover_constraint: &Option<TurnoverConstraint>,
+) -> Vec<f64> {
     let mut constrained_weights: Vec<f64> = weights.to_vec();
 
     // Apply upper and lower bounds
@@ -146,8 +1218,169 @@ fn enforce_constraints(weights: &[f64], constraints: &[(f64, f64)]) -> Vec<f64>
     }
 
     // Normalize weights to ensure they sum to one
-    let sum: f64 = constrained_weights.iter().sum();
-    constrained_weights.iter_mut().for_each(|w| *w /= sum);
+    enforce_pinned_weights(&mut constrained_weights, constraints);
+
+    // Apply group bounds, group constraints take precedence over conflicting asset bounds
+    enforce_group_constraints(&mut constrained_weights, group_constraints);
+
+    // Limit the turnover from the current weights
+    if let Some(turnover) = turnover_constraint {
+        enforce_turnover_constraint(&mut constrained_weights, turnover);
+    }
+
+    // Pinned weights take precedence over the group and turnover constraints
+    if !group_constraints.is_empty() || turnover_constraint.is_some() {
+        enforce_pinned_weights(&mut constrained_weights, constraints);
+    }
 
     constrained_weights
-}
\ No newline at end of file
+}
+
+/// Whether the asset bounds pin the asset to a fixed weight
+fn is_pinned(lb: f64, ub: f64) -> bool {
+    (ub - lb).abs() < 1e-9
+}
+
+/// Fixes the pinned assets at their weights and scales the remaining assets to the residual weight,
+/// which reduces to a plain normalization when no asset is pinned
+fn enforce_pinned_weights(weights: &mut [f64], constraints: &[(f64, f64)]) {
+    let pinned = constraints.iter().enumerate()
+        .filter(|(i, &(lb, ub))| *i < weights.len() && is_pinned(lb, ub))
+        .map(|(i, &(lb, _))| (i, lb))
+        .collect::<Vec<(usize, f64)>>();
+    for &(i, weight) in &pinned {
+        weights[i] = weight;
+    }
+    let pinned_sum: f64 = pinned.iter().map(|x| x.1).sum();
+    let free = (0..weights.len()).filter(|i| !pinned.iter().any(|x| x.0 == *i)).collect::<Vec<usize>>();
+    if free.is_empty() {
+        return;
+    }
+    let residual = (1.0 - pinned_sum).max(0.0);
+    let free_sum: f64 = free.iter().map(|&i| weights[i]).sum();
+    for &i in &free {
+        weights[i] = if free_sum > 0.0 {
+            weights[i] * residual / free_sum
+        } else {
+            residual / free.len() as f64
+        };
+    }
+}
+
+fn enforce_turnover_constraint(weights: &mut [f64], turnover_constraint: &TurnoverConstraint) {
+    let current_weights = &turnover_constraint.current_weights;
+    if current_weights.len() != weights.len() {
+        return;
+    }
+    let turnover: f64 = weights.iter().zip(current_weights.iter())
+        .map(|(w, c)| (w - c).abs())
+        .sum();
+
+    // Move along the line from the current weights towards the target weights, which keeps the
+    // weights summing to one and within any bounds satisfied by both portfolios
+    if turnover > turnover_constraint.max_turnover && turnover > 0.0 {
+        let step = turnover_constraint.max_turnover / turnover;
+        for (w, c) in weights.iter_mut().zip(current_weights.iter()) {
+            *w = c + step * (*w - c);
+        }
+    }
+}
+
+fn enforce_group_constraints(weights: &mut [f64], group_constraints: &[GroupConstraint]) {
+    if group_constraints.is_empty() {
+        return;
+    }
+
+    // Rescale each violating group to its nearest bound and the remaining assets to the residual weight,
+    // iterating since fixing one group can push an overlapping group out of bounds
+    for _ in 0..100 {
+        let mut adjusted = false;
+        for group in group_constraints {
+            if group.indices.is_empty() {
+                continue;
+            }
+            let group_sum: f64 = group.indices.iter().map(|&i| weights[i]).sum();
+            let target = if group_sum > group.max_weight + 1e-9 {
+                group.max_weight
+            } else if group_sum < group.min_weight - 1e-9 {
+                group.min_weight
+            } else {
+                continue;
+            };
+            let others = (0..weights.len()).filter(|i| !group.indices.contains(i)).collect::<Vec<usize>>();
+            let others_sum: f64 = others.iter().map(|&i| weights[i]).sum();
+
+            for &i in &group.indices {
+                weights[i] = if group_sum > 0.0 {
+                    weights[i] * target / group_sum
+                } else {
+                    target / group.indices.len() as f64
+                };
+            }
+            for &i in &others {
+                weights[i] = if others_sum > 0.0 {
+                    weights[i] * (1.0 - target) / others_sum
+                } else {
+                    (1.0 - target) / others.len() as f64
+                };
+            }
+            adjusted = true;
+        }
+        if !adjusted {
+            break;
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::df;
+
+    fn scenarios(a: &[f64], b: &[f64]) -> DataFrame {
+        df!("a" => a, "b" => b).unwrap()
+    }
+
+    #[test]
+    fn test_min_cvar_weights_matches_reference() {
+        // The worst scenario loss at 75% confidence over 4 scenarios is max(6w - 2, 4 - 6w) for a weight w of
+        // the first asset, minimized at w = 0.5 with a CVaR of 1%
+        let returns = scenarios(&[-4.0, 2.0, 1.0, 3.0], &[2.0, -4.0, 1.0, 3.0]);
+        let (weights, cvar) = min_cvar_weights(&returns, 0.75, &[(0.0, 1.0); 2], &[], &None, None).unwrap();
+        assert!((weights[0] - 0.5).abs() < 1e-6 && (weights[1] - 0.5).abs() < 1e-6, "weights = {:?}", weights);
+        assert!((cvar - 1.0).abs() < 1e-6, "cvar = {}", cvar);
+    }
+
+    #[test]
+    fn test_min_cvar_weights_respect_asset_bounds() {
+        let returns = scenarios(&[-4.0, 2.0, 1.0, 3.0], &[2.0, -4.0, 1.0, 3.0]);
+        let (weights, cvar) = min_cvar_weights(&returns, 0.75, &[(0.0, 0.25), (0.0, 1.0)], &[], &None, None).unwrap();
+        assert!((weights[0] - 0.25).abs() < 1e-6 && (weights[1] - 0.75).abs() < 1e-6, "weights = {:?}", weights);
+        assert!((cvar - 2.5).abs() < 1e-6, "cvar = {}", cvar);
+    }
+
+    #[test]
+    fn test_min_cvar_weights_limit_the_drawdown() {
+        let returns = scenarios(&[-3.0, -3.0, -3.0, 10.0], &[-5.0, 4.0, 4.0, 4.0]);
+        let constraints = [(0.0, 1.0); 2];
+
+        // Unconstrained, the first asset has the lowest CVaR (3%) but a 6% drawdown
+        let (weights, cvar) = min_cvar_weights(&returns, 0.75, &constraints, &[], &None, None).unwrap();
+        assert!((weights[0] - 1.0).abs() < 1e-6, "weights = {:?}", weights);
+        assert!((cvar - 3.0).abs() < 1e-6, "cvar = {}", cvar);
+
+        // A 4% limit caps the first asset at 6/7, where the drawdown of two -2% periods is 2 * 2% additive
+        // and 3.96% compounded
+        let (weights, cvar) = min_cvar_weights(&returns, 0.75, &constraints, &[], &None, Some(4.0)).unwrap();
+        assert!((weights[0] - 6.0 / 7.0).abs() < 1e-6, "weights = {:?}", weights);
+        assert!((cvar - (5.0 - 12.0 / 7.0)).abs() < 1e-6, "cvar = {}", cvar);
+        let scenarios = vec![vec![-3.0, -3.0, -3.0, 10.0], vec![-5.0, 4.0, 4.0, 4.0]];
+        assert!(compounded_drawdown(&weights, &scenarios) <= 4.0);
+    }
+
+    #[test]
+    fn test_compounded_drawdown() {
+        let scenarios = vec![vec![10.0, -10.0, -10.0, 5.0]];
+        // Wealth 1.1, 0.99, 0.891, the drawdown from the peak is 1 - 0.891 / 1.1 = 19%
+        assert!((compounded_drawdown(&[1.0], &scenarios) - 19.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,1018 @@
+use std::error::Error;
+use std::collections::BTreeMap;
+use polars::prelude::*;
+
+use crate::models::ticker::Ticker;
+use crate::data::config::{Interval, Options};
+use crate::data::ticker::TickerData;
+use crate::utils::synthetic::interval_years;
+use crate::analytics::stochastics::{BlackScholesModel, OptionType};
+
+
+/// Average number of days in a month, as used by the time to maturity of the options chain
+const DAYS_PER_MONTH: f64 = 30.44;
+
+/// Trading days per calendar year, used to match realized volatility windows to expirations
+const TRADING_DAYS: f64 = 252.0;
+
+/// Default realized volatility windows in trading days (two weeks to one year)
+pub const REALIZED_VOLATILITY_WINDOWS: [usize; 7] = [10, 20, 30, 60, 90, 180, 252];
+
+/// Window in trading days of the realized volatility range the IV rank is measured against (one month)
+const IV_RANK_WINDOW: usize = 21;
+
+/// Target days to expiration of the at-the-money implied volatility used for the IV rank
+const IV_RANK_DAYS: f64 = 30.0;
+
+/// Contract of an options chain, with the price at the bid/ask midpoint when quoted
+#[derive(Debug, Clone)]
+pub(crate) struct ChainContract {
+    pub contract_symbol: String,
+    pub expiration: String,
+    pub days: f64,
+    pub is_call: bool,
+    pub strike: f64,
+    pub price: f64,
+    pub bid: f64,
+    pub ask: f64,
+    pub open_interest: f64,
+    pub volume: f64,
+    pub implied_volatility: f64,
+}
+
+/// Reads the contracts of an options chain DataFrame
+pub(crate) fn chain_contracts(chain: &DataFrame) -> Result<Vec<ChainContract>, Box<dyn Error>> {
+    let expirations = chain.column("expiration")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string()).collect::<Vec<String>>();
+    let float = |name: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+        Ok(chain.column(name)?.cast(&DataType::Float64)?.f64()?.into_iter()
+            .map(|x| x.unwrap_or(0.0)).collect())
+    };
+    let ttms = float("ttm")?;
+    let strikes = float("strike")?;
+    let last = float("lastPrice")?;
+    let bids = float("bid")?;
+    let asks = float("ask")?;
+    let open_interest = float("openInterest")?;
+    let ivols = float("impliedVolatility")?;
+    // Chains stored before the volume column was added have no traded volume
+    let volumes = match chain.column("volume") {
+        Ok(_) => float("volume")?,
+        Err(_) => vec![0.0; chain.height()],
+    };
+    let types = chain.column("type")?.str()?.into_iter()
+        .map(|x| x == Some("call")).collect::<Vec<bool>>();
+    let symbols = chain.column("contractSymbol")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string()).collect::<Vec<String>>();
+
+    Ok((0..chain.height())
+        .map(|i| ChainContract {
+            contract_symbol: symbols[i].clone(),
+            expiration: expirations[i].clone(),
+            days: ttms[i] * DAYS_PER_MONTH,
+            is_call: types[i],
+            strike: strikes[i],
+            price: if bids[i] > 0.0 && asks[i] >= bids[i] { (bids[i] + asks[i]) / 2.0 } else { last[i] },
+            bid: bids[i],
+            ask: asks[i],
+            open_interest: open_interest[i],
+            volume: volumes[i],
+            implied_volatility: ivols[i],
+        })
+        .collect())
+}
+
+/// Groups contracts by expiration, in chronological order
+pub(crate) fn contracts_by_expiration(contracts: &[ChainContract]) -> BTreeMap<String, Vec<&ChainContract>> {
+    let mut expirations: BTreeMap<String, Vec<&ChainContract>> = BTreeMap::new();
+    for contract in contracts {
+        expirations.entry(contract.expiration.clone()).or_default().push(contract);
+    }
+    expirations
+}
+
+/// Market-implied expected move of the underlying until each expiration
+///
+/// The expected move is the price of the at-the-money straddle (call plus put at the strike closest to spot
+/// with both quoted), which approximates the expected absolute price change by expiration. The one standard
+/// deviation move implied by the average at-the-money volatility is given for comparison.
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the underlying
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per upcoming expiration with columns expiration, days, atm_strike, straddle_price,
+///   expected_move, expected_move_pct, lower_bound, upper_bound, atm_iv and iv_move
+pub fn expected_move(options: &Options) -> Result<DataFrame, Box<dyn Error>> {
+    let spot = options.ticker_price;
+    let contracts = chain_contracts(&options.chain)?;
+
+    let mut expirations = Vec::new();
+    let mut days = Vec::new();
+    let mut atm_strikes = Vec::new();
+    let mut straddles = Vec::new();
+    let mut atm_ivs = Vec::new();
+
+    for (expiration, contracts) in contracts_by_expiration(&contracts) {
+        let expiry_days = contracts[0].days;
+        if expiry_days <= 0.0 {
+            continue;
+        }
+        // Strike closest to spot with both a call and a put priced
+        let atm = contracts.iter()
+            .filter(|c| c.is_call && c.price > 0.0)
+            .filter_map(|call| contracts.iter()
+                .find(|put| !put.is_call && put.strike == call.strike && put.price > 0.0)
+                .map(|put| (call, put)))
+            .min_by(|a, b| (a.0.strike - spot).abs().partial_cmp(&(b.0.strike - spot).abs())
+                .unwrap_or(std::cmp::Ordering::Equal));
+        if let Some((call, put)) = atm {
+            expirations.push(expiration);
+            days.push(expiry_days.round());
+            atm_strikes.push(call.strike);
+            straddles.push(call.price + put.price);
+            atm_ivs.push((call.implied_volatility + put.implied_volatility) / 2.0);
+        }
+    }
+    if expirations.is_empty() {
+        return Err("No expiration with a priced at-the-money straddle".into());
+    }
+
+    let df = df!(
+        "expiration" => &expirations,
+        "days" => &days,
+        "atm_strike" => &atm_strikes,
+        "straddle_price" => &straddles,
+        "expected_move" => &straddles,
+        "expected_move_pct" => straddles.iter().map(|x| x / spot * 100.0).collect::<Vec<f64>>(),
+        "lower_bound" => straddles.iter().map(|x| spot - x).collect::<Vec<f64>>(),
+        "upper_bound" => straddles.iter().map(|x| spot + x).collect::<Vec<f64>>(),
+        "atm_iv" => &atm_ivs,
+        "iv_move" => atm_ivs.iter().zip(&days).map(|(iv, d)| spot * iv * (d / 365.0).sqrt()).collect::<Vec<f64>>()
+    )?;
+    Ok(df)
+}
+
+/// Put/call ratio below which positioning is read as bullish
+pub const BULLISH_PUT_CALL_RATIO: f64 = 0.7;
+
+/// Put/call ratio above which positioning is read as bearish
+pub const BEARISH_PUT_CALL_RATIO: f64 = 1.0;
+
+/// Sentiment read from a put/call ratio, "Bullish", "Neutral" or "Bearish"
+pub fn put_call_sentiment(ratio: f64) -> &'static str {
+    if ratio < BULLISH_PUT_CALL_RATIO {
+        "Bullish"
+    } else if ratio > BEARISH_PUT_CALL_RATIO {
+        "Bearish"
+    } else {
+        "Neutral"
+    }
+}
+
+/// Ratio of put to call activity, None when there is no call activity
+fn ratio(puts: f64, calls: f64) -> Option<f64> {
+    if calls > 0.0 { Some(puts / calls) } else { None }
+}
+
+/// Put/call volume and open interest ratios of each expiration
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the underlying
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per expiration followed by a "Total" row over the whole chain, with columns expiration,
+///   days, call_volume, put_volume, volume_ratio, call_open_interest, put_open_interest and open_interest_ratio
+pub fn put_call_ratios(options: &Options) -> Result<DataFrame, Box<dyn Error>> {
+    let contracts = chain_contracts(&options.chain)?;
+    if contracts.is_empty() {
+        return Err("Options chain is empty".into());
+    }
+
+    let mut expirations = Vec::new();
+    let mut days = Vec::new();
+    let mut totals: Vec<[f64; 4]> = Vec::new();
+    for (expiration, contracts) in contracts_by_expiration(&contracts) {
+        let mut total = [0.0; 4];
+        for contract in contracts.iter() {
+            let offset = if contract.is_call { 0 } else { 1 };
+            total[offset] += contract.volume;
+            total[2 + offset] += contract.open_interest;
+        }
+        expirations.push(expiration);
+        days.push(Some(contracts[0].days.max(0.0).round()));
+        totals.push(total);
+    }
+    let overall = totals.iter().fold([0.0; 4], |acc, x| [acc[0] + x[0], acc[1] + x[1], acc[2] + x[2], acc[3] + x[3]]);
+    expirations.push("Total".to_string());
+    days.push(None);
+    totals.push(overall);
+
+    let df = df!(
+        "expiration" => &expirations,
+        "days" => &days,
+        "call_volume" => totals.iter().map(|x| x[0]).collect::<Vec<f64>>(),
+        "put_volume" => totals.iter().map(|x| x[1]).collect::<Vec<f64>>(),
+        "volume_ratio" => totals.iter().map(|x| ratio(x[1], x[0])).collect::<Vec<Option<f64>>>(),
+        "call_open_interest" => totals.iter().map(|x| x[2]).collect::<Vec<f64>>(),
+        "put_open_interest" => totals.iter().map(|x| x[3]).collect::<Vec<f64>>(),
+        "open_interest_ratio" => totals.iter().map(|x| ratio(x[3], x[2])).collect::<Vec<Option<f64>>>()
+    )?;
+    Ok(df)
+}
+
+/// Call and put open interest and volume at each strike, showing where positioning is concentrated
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the underlying
+/// * `expiration` - Expiration date (YYYY-MM-DD) to include, all expirations are summed when None
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per strike in ascending order with columns strike, call_open_interest,
+///   put_open_interest, total_open_interest, call_volume and put_volume
+pub fn open_interest_by_strike(options: &Options, expiration: Option<&str>) -> Result<DataFrame, Box<dyn Error>> {
+    let contracts = chain_contracts(&options.chain)?;
+
+    // Strikes are keyed by their price in thousandths so they can be ordered and merged across expirations
+    let mut strikes: BTreeMap<i64, [f64; 4]> = BTreeMap::new();
+    for contract in contracts.iter().filter(|c| expiration.is_none_or(|e| c.expiration == e)) {
+        let entry = strikes.entry((contract.strike * 1000.0).round() as i64).or_insert([0.0; 4]);
+        let offset = if contract.is_call { 0 } else { 1 };
+        entry[offset] += contract.open_interest;
+        entry[2 + offset] += contract.volume;
+    }
+    if strikes.is_empty() {
+        return match expiration {
+            Some(e) => Err(format!("No contracts expiring on {}", e).into()),
+            None => Err("Options chain is empty".into()),
+        };
+    }
+
+    let df = df!(
+        "strike" => strikes.keys().map(|x| *x as f64 / 1000.0).collect::<Vec<f64>>(),
+        "call_open_interest" => strikes.values().map(|x| x[0]).collect::<Vec<f64>>(),
+        "put_open_interest" => strikes.values().map(|x| x[1]).collect::<Vec<f64>>(),
+        "total_open_interest" => strikes.values().map(|x| x[0] + x[1]).collect::<Vec<f64>>(),
+        "call_volume" => strikes.values().map(|x| x[2]).collect::<Vec<f64>>(),
+        "put_volume" => strikes.values().map(|x| x[3]).collect::<Vec<f64>>()
+    )?;
+    Ok(df)
+}
+
+/// Max pain strike of each expiration
+///
+/// The max pain strike is the settlement price at which the open contracts of an expiration would pay out the
+/// least in total to option holders, i.e. where option writers as a whole would lose the least.
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the underlying
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per expiration with open interest, with columns expiration, days, max_pain_strike,
+///   total_payout (holder payout at the max pain strike, per unit of the underlying), spot_distance and
+///   spot_distance_pct (max pain strike relative to spot)
+pub fn max_pain(options: &Options) -> Result<DataFrame, Box<dyn Error>> {
+    let spot = options.ticker_price;
+    let contracts = chain_contracts(&options.chain)?;
+
+    let mut expirations = Vec::new();
+    let mut days = Vec::new();
+    let mut strikes = Vec::new();
+    let mut payouts = Vec::new();
+
+    for (expiration, contracts) in contracts_by_expiration(&contracts) {
+        let open = contracts.iter().filter(|c| c.open_interest > 0.0).collect::<Vec<_>>();
+        if open.is_empty() {
+            continue;
+        }
+        // Payout to holders if the underlying settles at a strike of the expiration
+        let payout = |settlement: f64| -> f64 {
+            open.iter()
+                .map(|c| {
+                    let intrinsic = if c.is_call { settlement - c.strike } else { c.strike - settlement };
+                    intrinsic.max(0.0) * c.open_interest
+                })
+                .sum()
+        };
+        let pain = open.iter()
+            .map(|c| (c.strike, payout(c.strike)))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        if let Some((strike, total)) = pain {
+            expirations.push(expiration);
+            days.push(contracts[0].days.max(0.0).round());
+            strikes.push(strike);
+            payouts.push(total);
+        }
+    }
+    if expirations.is_empty() {
+        return Err("No expiration with open interest".into());
+    }
+
+    let df = df!(
+        "expiration" => &expirations,
+        "days" => &days,
+        "max_pain_strike" => &strikes,
+        "total_payout" => &payouts,
+        "spot_distance" => strikes.iter().map(|x| x - spot).collect::<Vec<f64>>(),
+        "spot_distance_pct" => strikes.iter().map(|x| (x - spot) / spot * 100.0).collect::<Vec<f64>>()
+    )?;
+    Ok(df)
+}
+
+/// Annualized volatility of the most recent log returns, None when there are fewer than `periods` returns
+fn trailing_volatility(log_returns: &[f64], periods: usize, periods_per_year: f64) -> Option<f64> {
+    if periods < 2 || periods > log_returns.len() {
+        return None;
+    }
+    let window = &log_returns[log_returns.len() - periods..];
+    let mean = window.iter().sum::<f64>() / periods as f64;
+    let variance = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (periods - 1) as f64;
+    Some((variance * periods_per_year).sqrt())
+}
+
+/// Log returns of the close prices of a price DataFrame
+fn log_returns(prices: &DataFrame) -> Result<Vec<f64>, Box<dyn Error>> {
+    let closes = prices.column("close")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+    Ok(closes.windows(2)
+        .filter(|x| x[0] > 0.0 && x[1] > 0.0)
+        .map(|x| (x[1] / x[0]).ln())
+        .collect())
+}
+
+/// Number of price periods of an interval in a window of trading days
+fn window_periods(window: usize, interval: Interval) -> usize {
+    (window as f64 / TRADING_DAYS / interval_years(interval)).round() as usize
+}
+
+/// Annualized realized (historical) volatility over trailing windows
+///
+/// # Arguments
+///
+/// * `prices` - Price DataFrame with a close column, in chronological order
+/// * `interval` - Interval of the prices
+/// * `windows` - Trailing windows in trading days, windows longer than the price history are skipped
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per window with columns window (trading days), calendar_days and realized_volatility
+///   (annualized, as a decimal like implied volatility)
+pub fn realized_volatility(prices: &DataFrame, interval: Interval, windows: &[usize]) -> Result<DataFrame, Box<dyn Error>> {
+    let returns = log_returns(prices)?;
+    let periods_per_year = 1.0 / interval_years(interval);
+
+    let mut window_days = Vec::new();
+    let mut volatilities = Vec::new();
+    for window in windows {
+        if let Some(volatility) = trailing_volatility(&returns, window_periods(*window, interval), periods_per_year) {
+            window_days.push(*window as u32);
+            volatilities.push(volatility);
+        }
+    }
+    if window_days.is_empty() {
+        return Err("Not enough price history for any realized volatility window".into());
+    }
+
+    let df = df!(
+        "window" => &window_days,
+        "calendar_days" => window_days.iter().map(|x| (*x as f64 * 365.0 / TRADING_DAYS).round()).collect::<Vec<f64>>(),
+        "realized_volatility" => &volatilities
+    )?;
+    Ok(df)
+}
+
+/// At-the-money implied volatility of each expiration against the realized volatility over a matching window
+///
+/// The volatility risk premium is the implied minus the realized volatility: a positive premium means options
+/// are priced for more movement than the underlying has recently delivered. The at-the-money implied volatility
+/// is the average of the call and put implied volatilities at the strike closest to spot. The realized volatility
+/// is measured over the trailing trading days matching the calendar days to expiration.
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the underlying
+/// * `prices` - Price DataFrame of the underlying with a close column, in chronological order
+/// * `interval` - Interval of the prices
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per upcoming expiration with columns expiration, days, atm_iv, realized_volatility,
+///   volatility_risk_premium and iv_rv_ratio (the realized columns are null when the price history is too short)
+pub fn volatility_risk_premium(options: &Options, prices: &DataFrame, interval: Interval) -> Result<DataFrame, Box<dyn Error>> {
+    let spot = options.ticker_price;
+    let contracts = chain_contracts(&options.chain)?;
+    let returns = log_returns(prices)?;
+    let periods_per_year = 1.0 / interval_years(interval);
+
+    let mut expirations = Vec::new();
+    let mut days = Vec::new();
+    let mut atm_ivs = Vec::new();
+    let mut realized = Vec::new();
+
+    for (expiration, contracts) in contracts_by_expiration(&contracts) {
+        let expiry_days = contracts[0].days;
+        if expiry_days <= 0.0 {
+            continue;
+        }
+        let quoted = contracts.iter().filter(|c| c.implied_volatility > 0.0).collect::<Vec<_>>();
+        let Some(atm_strike) = quoted.iter()
+            .map(|c| c.strike)
+            .min_by(|a, b| (a - spot).abs().partial_cmp(&(b - spot).abs()).unwrap_or(std::cmp::Ordering::Equal)) else {
+            continue;
+        };
+        let atm = quoted.iter().filter(|c| c.strike == atm_strike).map(|c| c.implied_volatility).collect::<Vec<f64>>();
+        let window = (expiry_days * TRADING_DAYS / 365.0).round().max(2.0) as usize;
+
+        expirations.push(expiration);
+        days.push(expiry_days.round());
+        atm_ivs.push(atm.iter().sum::<f64>() / atm.len() as f64);
+        realized.push(trailing_volatility(&returns, window_periods(window, interval), periods_per_year));
+    }
+    if expirations.is_empty() {
+        return Err("No upcoming expiration with implied volatility quotes".into());
+    }
+
+    let df = df!(
+        "expiration" => &expirations,
+        "days" => &days,
+        "atm_iv" => &atm_ivs,
+        "realized_volatility" => &realized,
+        "volatility_risk_premium" => atm_ivs.iter().zip(&realized).map(|(iv, rv)| rv.map(|rv| iv - rv)).collect::<Vec<Option<f64>>>(),
+        "iv_rv_ratio" => atm_ivs.iter().zip(&realized)
+            .map(|(iv, rv)| rv.filter(|rv| *rv > 0.0).map(|rv| iv / rv)).collect::<Vec<Option<f64>>>()
+    )?;
+    Ok(df)
+}
+
+/// Criteria of the option chain screener, every criterion is optional
+///
+/// * `option_type` - Only calls or only puts (default both)
+/// * `min_iv_rank` / `max_iv_rank` - IV rank range of the underlying, from 0 to 100
+/// * `min_delta` / `max_delta` - Range of the absolute Black-Scholes delta (e.g. 0.15 to 0.35 for out-of-the-money
+///   covered calls and cash-secured puts)
+/// * `min_open_interest` - Minimum open interest of the contract
+/// * `max_spread_pct` - Maximum bid/ask spread as a percentage of the midpoint
+/// * `min_days` / `max_days` - Range of calendar days to expiration
+///
+/// # Example
+///
+/// ```rust
+/// use finalytics::prelude::*;
+///
+/// let criteria = OptionScreenCriteria::new()
+///     .option_type(OptionType::Put)
+///     .delta_range(0.15, 0.35)
+///     .min_open_interest(100.0)
+///     .max_spread_pct(10.0)
+///     .days_range(20.0, 60.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OptionScreenCriteria {
+    pub option_type: Option<OptionType>,
+    pub min_iv_rank: Option<f64>,
+    pub max_iv_rank: Option<f64>,
+    pub min_delta: Option<f64>,
+    pub max_delta: Option<f64>,
+    pub min_open_interest: Option<f64>,
+    pub max_spread_pct: Option<f64>,
+    pub min_days: Option<f64>,
+    pub max_days: Option<f64>,
+}
+
+impl OptionScreenCriteria {
+    pub fn new() -> OptionScreenCriteria {
+        OptionScreenCriteria::default()
+    }
+
+    pub fn option_type(mut self, option_type: OptionType) -> OptionScreenCriteria {
+        self.option_type = Some(option_type);
+        self
+    }
+
+    pub fn iv_rank_range(mut self, min: f64, max: f64) -> OptionScreenCriteria {
+        self.min_iv_rank = Some(min);
+        self.max_iv_rank = Some(max);
+        self
+    }
+
+    pub fn delta_range(mut self, min: f64, max: f64) -> OptionScreenCriteria {
+        self.min_delta = Some(min);
+        self.max_delta = Some(max);
+        self
+    }
+
+    pub fn min_open_interest(mut self, min_open_interest: f64) -> OptionScreenCriteria {
+        self.min_open_interest = Some(min_open_interest);
+        self
+    }
+
+    pub fn max_spread_pct(mut self, max_spread_pct: f64) -> OptionScreenCriteria {
+        self.max_spread_pct = Some(max_spread_pct);
+        self
+    }
+
+    pub fn days_range(mut self, min: f64, max: f64) -> OptionScreenCriteria {
+        self.min_days = Some(min);
+        self.max_days = Some(max);
+        self
+    }
+}
+
+/// Whether a value is within optional bounds
+fn within(value: f64, min: Option<f64>, max: Option<f64>) -> bool {
+    min.is_none_or(|m| value >= m) && max.is_none_or(|m| value <= m)
+}
+
+/// IV rank of the underlying, from 0 to 100
+///
+/// Historical implied volatility is not available, so the rank places the at-the-money implied volatility of the
+/// expiration closest to 30 days within the range of the one month realized volatility over the price history:
+/// 0 at the lowest and 100 at the highest realized volatility.
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the underlying
+/// * `prices` - Price DataFrame of the underlying with a close column, in chronological order
+/// * `interval` - Interval of the prices
+///
+/// # Returns
+///
+/// * `Option<f64>` - IV rank, None when the chain has no implied volatility or the price history is too short
+pub fn iv_rank(options: &Options, prices: &DataFrame, interval: Interval) -> Result<Option<f64>, Box<dyn Error>> {
+    let premium = match volatility_risk_premium(options, prices, interval) {
+        Ok(premium) => premium,
+        Err(_) => return Ok(None),
+    };
+    let days = premium.column("days")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+    let atm_ivs = premium.column("atm_iv")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+    let Some(atm_iv) = days.iter().zip(&atm_ivs)
+        .min_by(|a, b| (a.0 - IV_RANK_DAYS).abs().partial_cmp(&(b.0 - IV_RANK_DAYS).abs()).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, iv)| *iv) else {
+        return Ok(None);
+    };
+
+    let returns = log_returns(prices)?;
+    let periods = window_periods(IV_RANK_WINDOW, interval);
+    let periods_per_year = 1.0 / interval_years(interval);
+    let volatilities = (periods..=returns.len())
+        .filter_map(|end| trailing_volatility(&returns[..end], periods, periods_per_year))
+        .collect::<Vec<f64>>();
+    if volatilities.len() < 2 {
+        return Ok(None);
+    }
+    let low = volatilities.iter().cloned().fold(f64::INFINITY, f64::min);
+    let high = volatilities.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    if high <= low {
+        return Ok(None);
+    }
+    Ok(Some(((atm_iv - low) / (high - low) * 100.0).clamp(0.0, 100.0)))
+}
+
+/// Screens an options chain for the contracts matching the criteria
+///
+/// The annualized yield is the premium (bid/ask midpoint) over the capital at risk, the strike price, annualized
+/// over the days to expiration: the income of a cash-secured put or, approximately, of a covered call.
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the underlying
+/// * `prices` - Price DataFrame of the underlying with a close column, in chronological order
+/// * `interval` - Interval of the prices
+/// * `risk_free_rate` - Risk-free rate used for the delta
+/// * `criteria` - Screening criteria
+///
+/// # Returns
+///
+/// * `DataFrame` - Matching contracts sorted by annualized yield, with columns contractSymbol, type, expiration,
+///   days, strike, moneyness_pct, bid, ask, price, spread_pct, open_interest, volume, implied_volatility, delta,
+///   iv_rank and annualized_yield
+pub fn screen_contracts(options: &Options, prices: &DataFrame, interval: Interval, risk_free_rate: f64,
+                        criteria: &OptionScreenCriteria) -> Result<DataFrame, Box<dyn Error>> {
+    let spot = options.ticker_price;
+    let rank = iv_rank(options, prices, interval)?;
+    if criteria.min_iv_rank.is_some() || criteria.max_iv_rank.is_some() {
+        match rank {
+            Some(rank) if within(rank, criteria.min_iv_rank, criteria.max_iv_rank) => {}
+            _ => return screen_dataframe(&[], spot, rank),
+        }
+    }
+
+    let contracts = chain_contracts(&options.chain)?;
+    let mut screened = Vec::new();
+    for contract in contracts.iter() {
+        if contract.days <= 0.0 || contract.implied_volatility <= 0.0 || contract.price <= 0.0 {
+            continue;
+        }
+        if let Some(option_type) = criteria.option_type {
+            if contract.is_call != matches!(option_type, OptionType::Call) {
+                continue;
+            }
+        }
+        let option_type = if contract.is_call { OptionType::Call } else { OptionType::Put };
+        let delta = BlackScholesModel::compute(spot, contract.strike, contract.days / 365.0, risk_free_rate,
+                                               contract.implied_volatility, option_type).delta;
+        let spread_pct = if contract.bid > 0.0 && contract.ask >= contract.bid {
+            (contract.ask - contract.bid) / contract.price * 100.0
+        } else {
+            f64::INFINITY
+        };
+        if !within(delta.abs(), criteria.min_delta, criteria.max_delta)
+            || !within(contract.days, criteria.min_days, criteria.max_days)
+            || criteria.min_open_interest.is_some_and(|m| contract.open_interest < m)
+            || criteria.max_spread_pct.is_some_and(|m| spread_pct > m) {
+            continue;
+        }
+        screened.push((contract, delta, spread_pct));
+    }
+    screen_dataframe(&screened, spot, rank)
+}
+
+/// Builds the screener DataFrame of the matching contracts, sorted by annualized yield
+fn screen_dataframe(matches: &[(&ChainContract, f64, f64)], spot: f64, iv_rank: Option<f64>) -> Result<DataFrame, Box<dyn Error>> {
+    let yields = matches.iter()
+        .map(|(c, _, _)| c.price / c.strike * 365.0 / c.days * 100.0)
+        .collect::<Vec<f64>>();
+    let df = df!(
+        "contractSymbol" => matches.iter().map(|(c, _, _)| c.contract_symbol.as_str()).collect::<Vec<&str>>(),
+        "type" => matches.iter().map(|(c, _, _)| if c.is_call { "call" } else { "put" }).collect::<Vec<&str>>(),
+        "expiration" => matches.iter().map(|(c, _, _)| c.expiration.as_str()).collect::<Vec<&str>>(),
+        "days" => matches.iter().map(|(c, _, _)| c.days.round()).collect::<Vec<f64>>(),
+        "strike" => matches.iter().map(|(c, _, _)| c.strike).collect::<Vec<f64>>(),
+        "moneyness_pct" => matches.iter().map(|(c, _, _)| (c.strike / spot - 1.0) * 100.0).collect::<Vec<f64>>(),
+        "bid" => matches.iter().map(|(c, _, _)| c.bid).collect::<Vec<f64>>(),
+        "ask" => matches.iter().map(|(c, _, _)| c.ask).collect::<Vec<f64>>(),
+        "price" => matches.iter().map(|(c, _, _)| c.price).collect::<Vec<f64>>(),
+        "spread_pct" => matches.iter().map(|(_, _, s)| s.is_finite().then_some(*s)).collect::<Vec<Option<f64>>>(),
+        "open_interest" => matches.iter().map(|(c, _, _)| c.open_interest).collect::<Vec<f64>>(),
+        "volume" => matches.iter().map(|(c, _, _)| c.volume).collect::<Vec<f64>>(),
+        "implied_volatility" => matches.iter().map(|(c, _, _)| c.implied_volatility).collect::<Vec<f64>>(),
+        "delta" => matches.iter().map(|(_, d, _)| *d).collect::<Vec<f64>>(),
+        "iv_rank" => vec![iv_rank; matches.len()],
+        "annualized_yield" => &yields
+    )?;
+    Ok(df.sort(["annualized_yield"], SortMultipleOptions::new().with_order_descending(true))?)
+}
+
+/// Black-Scholes greeks of the contracts of an options chain, from the implied volatility of each contract
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the ticker
+/// * `expiration` - Expiration date (YYYY-MM-DD) to include, all expirations when None
+/// * `risk_free_rate` - Risk-free rate in decimal (e.g 0.02 for 2%)
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per contract ordered by expiration, type and strike, with columns contractSymbol, type,
+///   expiration, days, strike, moneyness_pct, bid, ask, price, open_interest, volume, implied_volatility, delta,
+///   gamma, theta (per calendar day), vega and rho (per percentage point), the greeks are null for contracts
+///   without an implied volatility
+pub fn chain_greeks(options: &Options, expiration: Option<&str>, risk_free_rate: f64) -> Result<DataFrame, Box<dyn Error>> {
+    let spot = options.ticker_price;
+    let mut contracts = chain_contracts(&options.chain)?.into_iter()
+        .filter(|c| expiration.is_none_or(|e| c.expiration == e))
+        .collect::<Vec<ChainContract>>();
+    if contracts.is_empty() {
+        return Err(format!("No contracts found for expiration {}", expiration.unwrap_or("(all)")).into());
+    }
+    contracts.sort_by(|a, b| a.expiration.cmp(&b.expiration)
+        .then(b.is_call.cmp(&a.is_call))
+        .then(a.strike.partial_cmp(&b.strike).unwrap_or(std::cmp::Ordering::Equal)));
+
+    let models = contracts.iter()
+        .map(|c| {
+            (c.days > 0.0 && c.implied_volatility > 0.0 && c.strike > 0.0 && spot > 0.0).then(|| {
+                let option_type = if c.is_call { OptionType::Call } else { OptionType::Put };
+                BlackScholesModel::compute(spot, c.strike, c.days / 365.0, risk_free_rate, c.implied_volatility, option_type)
+            })
+        })
+        .collect::<Vec<Option<BlackScholesModel>>>();
+    let greek = |f: fn(&BlackScholesModel) -> f64| -> Vec<Option<f64>> {
+        models.iter().map(|m| m.as_ref().map(f)).collect()
+    };
+
+    let df = df!(
+        "contractSymbol" => contracts.iter().map(|c| c.contract_symbol.as_str()).collect::<Vec<&str>>(),
+        "type" => contracts.iter().map(|c| if c.is_call { "call" } else { "put" }).collect::<Vec<&str>>(),
+        "expiration" => contracts.iter().map(|c| c.expiration.as_str()).collect::<Vec<&str>>(),
+        "days" => contracts.iter().map(|c| c.days.round()).collect::<Vec<f64>>(),
+        "strike" => contracts.iter().map(|c| c.strike).collect::<Vec<f64>>(),
+        "moneyness_pct" => contracts.iter().map(|c| (c.strike / spot - 1.0) * 100.0).collect::<Vec<f64>>(),
+        "bid" => contracts.iter().map(|c| c.bid).collect::<Vec<f64>>(),
+        "ask" => contracts.iter().map(|c| c.ask).collect::<Vec<f64>>(),
+        "price" => contracts.iter().map(|c| c.price).collect::<Vec<f64>>(),
+        "open_interest" => contracts.iter().map(|c| c.open_interest).collect::<Vec<f64>>(),
+        "volume" => contracts.iter().map(|c| c.volume).collect::<Vec<f64>>(),
+        "implied_volatility" => contracts.iter().map(|c| c.implied_volatility).collect::<Vec<f64>>(),
+        "delta" => greek(|m| m.delta),
+        "gamma" => greek(|m| m.gamma),
+        "theta" => greek(|m| m.theta / 365.0),
+        "vega" => greek(|m| m.vega / 100.0),
+        "rho" => greek(|m| m.rho / 100.0)
+    )?;
+    Ok(df)
+}
+
+/// Option income strategy on a holding
+///
+/// * `CoveredCall` - Sell a call against 100 shares held
+/// * `CashSecuredPut` - Sell a put with cash set aside to buy 100 shares at the strike
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IncomeStrategy {
+    CoveredCall,
+    CashSecuredPut,
+}
+
+impl IncomeStrategy {
+    pub fn from_str(strategy: &str) -> Result<IncomeStrategy, String> {
+        match strategy {
+            "covered_call" => Ok(IncomeStrategy::CoveredCall),
+            "cash_secured_put" => Ok(IncomeStrategy::CashSecuredPut),
+            _ => Err(format!("Invalid income strategy: {}", strategy)),
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            IncomeStrategy::CoveredCall => "Covered Call".to_string(),
+            IncomeStrategy::CashSecuredPut => "Cash-Secured Put".to_string(),
+        }
+    }
+
+    /// Profit per share at expiration of the strategy when the underlying settles at a price
+    ///
+    /// # Arguments
+    ///
+    /// * `settlement` - Price of the underlying at expiration
+    /// * `strike` - Strike of the option sold
+    /// * `premium` - Premium received per share
+    /// * `cost_basis` - Cost per share of the holding (covered call only)
+    pub fn payoff(&self, settlement: f64, strike: f64, premium: f64, cost_basis: f64) -> f64 {
+        match self {
+            IncomeStrategy::CoveredCall => settlement - cost_basis + premium - (settlement - strike).max(0.0),
+            IncomeStrategy::CashSecuredPut => premium - (strike - settlement).max(0.0),
+        }
+    }
+}
+
+/// Evaluates the out-of-the-money strikes of a covered call or cash-secured put on a holding
+///
+/// The premium is the bid, the price the option can be sold at, or the last price when there is no bid. The capital
+/// is the market value of the shares for a covered call and the strike for a cash-secured put. The assignment
+/// probability is approximated by the absolute Black-Scholes delta.
+///
+/// # Arguments
+///
+/// * `options` - Options chain of the underlying
+/// * `strategy` - Covered call or cash-secured put
+/// * `cost_basis` - Cost per share of the holding, defaults to the spot price (covered call only)
+/// * `expiration` - Expiration date (YYYY-MM-DD) to evaluate, all upcoming expirations when None
+/// * `risk_free_rate` - Risk-free rate used for the delta
+///
+/// # Returns
+///
+/// * `DataFrame` - One row per candidate ranked by annualized yield, with columns rank, contractSymbol,
+///   expiration, days, strike, premium, premium_yield_pct, annualized_yield, assignment_probability, breakeven,
+///   max_profit and max_profit_pct (per share, relative to the capital)
+pub fn income_candidates(options: &Options, strategy: IncomeStrategy, cost_basis: Option<f64>, expiration: Option<&str>,
+                         risk_free_rate: f64) -> Result<DataFrame, Box<dyn Error>> {
+    let spot = options.ticker_price;
+    let cost_basis = cost_basis.unwrap_or(spot);
+    let contracts = chain_contracts(&options.chain)?;
+
+    let mut candidates = contracts.iter()
+        .filter(|c| expiration.is_none_or(|e| c.expiration == e))
+        .filter(|c| c.days > 0.0 && c.implied_volatility > 0.0)
+        .filter(|c| match strategy {
+            IncomeStrategy::CoveredCall => c.is_call && c.strike >= spot,
+            IncomeStrategy::CashSecuredPut => !c.is_call && c.strike <= spot,
+        })
+        .filter_map(|c| {
+            let option_type = if c.is_call { OptionType::Call } else { OptionType::Put };
+            let premium = if c.bid > 0.0 { c.bid } else { c.price };
+            if premium <= 0.0 {
+                return None;
+            }
+            let delta = BlackScholesModel::compute(spot, c.strike, c.days / 365.0, risk_free_rate,
+                                                   c.implied_volatility, option_type).delta;
+            let (capital, breakeven, max_profit) = match strategy {
+                IncomeStrategy::CoveredCall => (spot, cost_basis - premium, c.strike - cost_basis + premium),
+                IncomeStrategy::CashSecuredPut => (c.strike, c.strike - premium, premium),
+            };
+            let premium_yield = premium / capital * 100.0;
+            Some((c, premium, premium_yield, premium_yield * 365.0 / c.days, delta.abs() * 100.0, breakeven,
+                  max_profit, max_profit / capital * 100.0))
+        })
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return Err(format!("No out-of-the-money {} candidates with a premium", strategy.to_string().to_lowercase()).into());
+    }
+    candidates.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal));
+
+    let df = df!(
+        "rank" => (1..=candidates.len() as u32).collect::<Vec<u32>>(),
+        "contractSymbol" => candidates.iter().map(|x| x.0.contract_symbol.as_str()).collect::<Vec<&str>>(),
+        "expiration" => candidates.iter().map(|x| x.0.expiration.as_str()).collect::<Vec<&str>>(),
+        "days" => candidates.iter().map(|x| x.0.days.round()).collect::<Vec<f64>>(),
+        "strike" => candidates.iter().map(|x| x.0.strike).collect::<Vec<f64>>(),
+        "premium" => candidates.iter().map(|x| x.1).collect::<Vec<f64>>(),
+        "premium_yield_pct" => candidates.iter().map(|x| x.2).collect::<Vec<f64>>(),
+        "annualized_yield" => candidates.iter().map(|x| x.3).collect::<Vec<f64>>(),
+        "assignment_probability" => candidates.iter().map(|x| x.4).collect::<Vec<f64>>(),
+        "breakeven" => candidates.iter().map(|x| x.5).collect::<Vec<f64>>(),
+        "max_profit" => candidates.iter().map(|x| x.6).collect::<Vec<f64>>(),
+        "max_profit_pct" => candidates.iter().map(|x| x.7).collect::<Vec<f64>>()
+    )?;
+    Ok(df)
+}
+
+pub trait OptionsAnalytics {
+    fn expected_move(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn put_call_ratios(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn open_interest_by_strike(&self, expiration: Option<&str>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn max_pain(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn realized_volatility(&self, windows: Option<Vec<usize>>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn volatility_risk_premium(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn screen_options(&self, criteria: &OptionScreenCriteria) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn income_candidates(&self, strategy: IncomeStrategy, cost_basis: Option<f64>, expiration: Option<&str>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn chain_greeks(&self, expiration: Option<&str>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+}
+
+impl OptionsAnalytics for Ticker {
+    /// Market-implied expected move of the ticker until each upcoming expiration, from at-the-money straddle prices
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per expiration with columns expiration, days, atm_strike, straddle_price,
+    ///   expected_move, expected_move_pct, lower_bound, upper_bound, atm_iv and iv_move
+    async fn expected_move(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        expected_move(&options)
+    }
+
+    /// Put/call volume and open interest ratios of each upcoming expiration of the ticker
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per expiration followed by a "Total" row, with columns expiration, days,
+    ///   call_volume, put_volume, volume_ratio, call_open_interest, put_open_interest and open_interest_ratio
+    async fn put_call_ratios(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        put_call_ratios(&options)
+    }
+
+    /// Call and put open interest and volume at each strike of the ticker's options chain
+    ///
+    /// # Arguments
+    ///
+    /// * `expiration` - Expiration date (YYYY-MM-DD) to include, all expirations are summed when None
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per strike with columns strike, call_open_interest, put_open_interest,
+    ///   total_open_interest, call_volume and put_volume
+    async fn open_interest_by_strike(&self, expiration: Option<&str>) -> Result<DataFrame, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        open_interest_by_strike(&options, expiration)
+    }
+
+    /// Max pain strike of each upcoming expiration of the ticker, from the open interest of the chain
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per expiration with columns expiration, days, max_pain_strike, total_payout,
+    ///   spot_distance and spot_distance_pct
+    async fn max_pain(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        max_pain(&options)
+    }
+
+    /// Annualized realized volatility of the ticker over trailing windows of its price history
+    ///
+    /// # Arguments
+    ///
+    /// * `windows` - Trailing windows in trading days (defaults to 10, 20, 30, 60, 90, 180 and 252)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per window with columns window, calendar_days and realized_volatility
+    async fn realized_volatility(&self, windows: Option<Vec<usize>>) -> Result<DataFrame, Box<dyn Error>> {
+        let prices = self.get_chart().await?;
+        let windows = windows.unwrap_or_else(|| REALIZED_VOLATILITY_WINDOWS.to_vec());
+        realized_volatility(&prices, self.interval, &windows)
+    }
+
+    /// At-the-money implied volatility of each upcoming expiration of the ticker against its realized volatility
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per expiration with columns expiration, days, atm_iv, realized_volatility,
+    ///   volatility_risk_premium and iv_rv_ratio
+    async fn volatility_risk_premium(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        let prices = self.get_chart().await?;
+        volatility_risk_premium(&options, &prices, self.interval)
+    }
+
+    /// Screens the ticker's options chain for the contracts matching the criteria
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - Screening criteria (IV rank, delta range, minimum open interest, spread width, days to expiration)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Matching contracts sorted by annualized yield, see `screen_contracts`
+    async fn screen_options(&self, criteria: &OptionScreenCriteria) -> Result<DataFrame, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        let prices = self.get_chart().await?;
+        screen_contracts(&options, &prices, self.interval, self.risk_free_rate, criteria)
+    }
+
+    /// Evaluates the out-of-the-money strikes of a covered call or cash-secured put on the ticker
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - Covered call or cash-secured put
+    /// * `cost_basis` - Cost per share of the holding, defaults to the spot price (covered call only)
+    /// * `expiration` - Expiration date (YYYY-MM-DD) to evaluate, all upcoming expirations when None
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Candidates ranked by annualized yield, see `income_candidates`
+    async fn income_candidates(&self, strategy: IncomeStrategy, cost_basis: Option<f64>, expiration: Option<&str>) -> Result<DataFrame, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        income_candidates(&options, strategy, cost_basis, expiration, self.risk_free_rate)
+    }
+
+    /// Black-Scholes greeks of the contracts of the ticker's options chain
+    ///
+    /// # Arguments
+    ///
+    /// * `expiration` - Expiration date (YYYY-MM-DD) to include, all expirations when None
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per contract with its quotes and greeks, see `chain_greeks`
+    async fn chain_greeks(&self, expiration: Option<&str>) -> Result<DataFrame, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        chain_greeks(&options, expiration, self.risk_free_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(expirations: &[&str], types: &[&str], strikes: &[f64], open_interest: &[f64]) -> Options {
+        let n = strikes.len();
+        let ttms = expirations.iter().map(|x| if *x == "2025-01-17" { 1.0 } else { 2.0 }).collect::<Vec<f64>>();
+        let chain = df!(
+            "expiration" => expirations,
+            "ttm" => &ttms,
+            "strike" => strikes,
+            "lastPrice" => vec![1.0; n],
+            "bid" => vec![0.0; n],
+            "ask" => vec![0.0; n],
+            "openInterest" => open_interest,
+            "impliedVolatility" => vec![0.2; n],
+            "type" => types,
+            "contractSymbol" => (0..n).map(|i| format!("TEST{}", i)).collect::<Vec<String>>(),
+        ).unwrap();
+        Options { ticker_price: 105.0, expiration_dates: Vec::new(), ttms, strikes: strikes.to_vec(), chain }
+    }
+
+    #[test]
+    fn test_max_pain_matches_reference() {
+        // Holder payouts of the first expiration are 400 at 90, 200 at 100 and 400 at 110, so max pain is at 100.
+        // In the second, the 120 put pays 100 at 100 and nothing at 120, and the call without open interest is ignored.
+        let options = options(
+            &["2025-01-17", "2025-01-17", "2025-01-17", "2025-01-17", "2025-01-17", "2025-01-17", "2025-02-21", "2025-02-21", "2025-02-21"],
+            &["call", "call", "call", "put", "put", "put", "put", "put", "call"],
+            &[90.0, 100.0, 110.0, 90.0, 100.0, 110.0, 100.0, 120.0, 80.0],
+            &[10.0, 20.0, 30.0, 30.0, 20.0, 10.0, 5.0, 5.0, 0.0],
+        );
+        let df = max_pain(&options).unwrap();
+        let column = |name: &str| df.column(name).unwrap().f64().unwrap().into_no_null_iter().collect::<Vec<f64>>();
+        assert_eq!(df.column("expiration").unwrap().str().unwrap().into_no_null_iter().collect::<Vec<&str>>(),
+                   vec!["2025-01-17", "2025-02-21"]);
+        assert_eq!(column("max_pain_strike"), vec![100.0, 120.0]);
+        assert_eq!(column("total_payout"), vec![200.0, 0.0]);
+        assert_eq!(column("days"), vec![30.0, 61.0]);
+        assert_eq!(column("spot_distance"), vec![-5.0, 15.0]);
+        assert!((column("spot_distance_pct")[0] + 5.0 / 105.0 * 100.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_max_pain_requires_open_interest() {
+        let options = options(&["2025-01-17", "2025-01-17"], &["call", "put"], &[100.0, 100.0], &[0.0, 0.0]);
+        assert!(max_pain(&options).is_err());
+    }
+}
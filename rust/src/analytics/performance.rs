@@ -1,13 +1,26 @@
 use polars::prelude::*;
 use std::error::Error;
-use chrono::{DateTime, NaiveDateTime};
+use futures::stream::{self, StreamExt};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
 
-use crate::data::config::Interval;
+use crate::data::config::{Interval, MarketCalendar, PriceType, ReturnType, is_stablecoin};
 use crate::models::ticker::{Ticker, TickerBuilder};
-use crate::analytics::technicals::TechnicalIndicators;
-use crate::analytics::optimization::{ObjectiveFunction, portfolio_optimization};
-use crate::analytics::statistics::{PerformanceStats, covariance_matrix, daily_portfolio_returns};
-use crate::prelude::{Column, TickersBuilder, TickersData};
+use crate::data::kline::{kline_for, KLINE};
+use crate::data::ticker::fetch_quote_fields;
+use crate::analytics::fx::CurrencyPair;
+use crate::analytics::sessions::overnight_intraday_returns;
+use crate::analytics::sizing::VolatilityTarget;
+use crate::analytics::esg::{EsgScreen, weighted_esg_score};
+use crate::data::news::{NewsOptions, daily_sentiment};
+use crate::analytics::projection::{GoalPlan, GoalProjection, project_goal};
+use crate::analytics::strategy::{StrategyComparison, StrategyVariant, TrendFilter, TrendFilterTarget, moving_average_filter, position_returns};
+use crate::analytics::cashflows::{CashFlowSchedule, money_weighted_return, simulate_cash_flows, time_weighted_return};
+use crate::analytics::optimization::{CategoryConstraint, CategorySource, GroupConstraint, ObjectiveFunction, OptimizationDiagnostics, OptimizationSettings, OptResult, ParetoFrontier, PositionConstraint, TurnoverConstraint, UncertaintySet, index_replication, pareto_frontier, portfolio_optimization, random_portfolios};
+use crate::analytics::statistics::{CovarianceEstimator, ExpectedReturnModel, BootstrapBands, PerformanceStats, SignificanceStats, analyst_target_expected_returns,
+                                   capm_expected_returns, daily_portfolio_returns, historical_mean_returns, mean_portfolio_return,
+                                   momentum_expected_returns, portfolio_std_dev};
+use crate::prelude::{TickerData, TickersBuilder, TickersData};
 
 
 #[derive(Debug, Clone)]
@@ -18,27 +31,327 @@ pub struct TickerPerformanceStats {
     pub end_date: String,
     pub dates_array: Vec<String>,
     pub interval: Interval,
+    pub calendar: MarketCalendar,
     pub confidence_level: f64,
     pub risk_free_rate: f64,
+    pub return_type: ReturnType,
     pub security_prices: Series,
     pub security_returns: Series,
     pub benchmark_returns: Series,
     pub performance_stats: PerformanceStats,
 }
 
+/// Performance statistics of a ticker with stable field names for JSON APIs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickerPerformanceSummary {
+    pub symbol: String,
+    pub benchmark_symbol: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub interval: String,
+    pub confidence_level: f64,
+    pub risk_free_rate: f64,
+    pub periods: usize,
+    pub stats: PerformanceStats,
+}
+
+impl TickerPerformanceSummary {
+    /// Serializes the summary to a JSON string
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Weight of an asset in a portfolio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetWeight {
+    pub symbol: String,
+    pub weight: f64,
+}
+
+/// Performance statistics and optimal weights of a portfolio with stable field names for JSON APIs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioPerformanceSummary {
+    pub symbols: Vec<String>,
+    pub benchmark_symbol: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub interval: String,
+    pub confidence_level: f64,
+    pub risk_free_rate: f64,
+    pub objective_function: String,
+    pub converged: bool,
+    pub weights: Vec<AssetWeight>,
+    pub stats: PerformanceStats,
+}
+
+impl PortfolioPerformanceSummary {
+    /// Serializes the summary to a JSON string
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+impl TickerPerformanceStats {
+    /// Summary of the performance statistics for JSON APIs
+    pub fn summary(&self) -> TickerPerformanceSummary {
+        TickerPerformanceSummary {
+            symbol: self.ticker_symbol.clone(),
+            benchmark_symbol: self.benchmark_symbol.clone(),
+            start_date: self.start_date.clone(),
+            end_date: self.end_date.clone(),
+            interval: self.interval.to_string(),
+            confidence_level: self.confidence_level,
+            risk_free_rate: self.risk_free_rate,
+            periods: self.security_returns.len(),
+            stats: self.performance_stats.clone(),
+        }
+    }
+
+    /// Standard errors, confidence intervals and significance tests of the Sharpe ratio and alpha
+    ///
+    /// # Returns
+    ///
+    /// * `SignificanceStats` struct
+    pub fn significance(&self) -> Result<SignificanceStats, Box<dyn Error>> {
+        SignificanceStats::compute(&self.security_returns, &self.benchmark_returns, self.risk_free_rate,
+                                   self.confidence_level, self.interval, self.calendar)
+    }
+
+    /// Block bootstrap confidence bands of the performance statistics
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Number of bootstrap resamples (e.g. `DEFAULT_BOOTSTRAP_SAMPLES`)
+    /// * `seed` - Optional seed of the resampling for reproducible bands
+    ///
+    /// # Returns
+    ///
+    /// * `BootstrapBands` struct
+    pub fn bootstrap_bands(&self, samples: usize, seed: Option<u64>) -> Result<BootstrapBands, Box<dyn Error>> {
+        BootstrapBands::compute(&self.security_returns, &self.benchmark_returns, self.risk_free_rate,
+                                self.confidence_level, self.interval, self.calendar, self.return_type,
+                                samples, None, seed)
+    }
+
+    /// Decomposition of the cumulative return into benchmark and active contributions over time
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - timestamp, total_return, benchmark_contribution and active_contribution columns
+    pub fn decomposition(&self) -> Result<DataFrame, Box<dyn Error>> {
+        performance_decomposition(&self.dates_array, &self.security_returns, &self.benchmark_returns, self.return_type)
+    }
+}
+
+/// Performance statistics of a ticker against a benchmark quoted in another currency
+///
+/// The hedged statistics compare the ticker with the benchmark's local currency returns, as if the currency
+/// exposure of the benchmark was hedged. The unhedged statistics compare it with the benchmark returns
+/// converted into the ticker currency, which include the exchange rate moves.
+#[derive(Debug, Clone)]
+pub struct CurrencyHedgedStats {
+    pub ticker_currency: String,
+    pub benchmark_currency: String,
+    pub fx_symbol: String,
+    pub hedged: PerformanceStats,
+    pub unhedged: PerformanceStats,
+}
+
 pub trait TickerPerformance {
+    fn returns(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
     fn performance_stats(&self) -> impl std::future::Future<Output = Result<TickerPerformanceStats, Box<dyn Error>>>;
+    fn currency_hedged_stats(&self) -> impl std::future::Future<Output = Result<Option<CurrencyHedgedStats>, Box<dyn Error>>>;
+    fn sentiment_returns(&self, options: NewsOptions) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn overnight_intraday_split(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn gap_stats(&self, threshold: Option<f64>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
 }
 
 impl TickerPerformance for Ticker {
+    /// Computes the period returns of the ticker using its return type and price type
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, the price column (adjclose or close) and roc-1 (returns in percent)
+    async fn returns(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let ohlcv = self.get_chart().await?;
+        let price_col = self.price_type.to_string();
+        let prices = ohlcv.column(&price_col)?.f64()?.to_vec().iter()
+            .map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+        let returns = self.return_type.returns(&prices);
+        let df = df!(
+            "timestamp" => ohlcv.column("timestamp")?.clone(),
+            &price_col => ohlcv.column(&price_col)?.clone(),
+            "roc-1" => returns
+        )?;
+        Ok(df)
+    }
+
+    /// Aligns the daily news sentiment over the ticker date range with the ticker returns
+    ///
+    /// News published on non-trading days is assigned to the next trading period
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - News options (the ticker date range is used unless the options set one)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, roc-1 (returns in percent), next_return (following period return),
+    ///   articles and sentiment (average compound sentiment score, null without news)
+    async fn sentiment_returns(&self, options: NewsOptions) -> Result<DataFrame, Box<dyn Error>> {
+        let returns = self.returns().await?;
+        let news = self.get_news_with(options).await?;
+        let sentiment = daily_sentiment(&news)?;
+
+        let timestamps = returns.column("timestamp")?.datetime()?.as_datetime_iter()
+            .collect::<Vec<Option<NaiveDateTime>>>();
+        let news_dates = sentiment.column("date")?.date()?.as_date_iter().collect::<Vec<Option<NaiveDate>>>();
+        let news_articles = sentiment.column("articles")?.u32()?.to_vec();
+        let news_scores = sentiment.column("sentiment")?.f64()?.to_vec();
+
+        // Article weighted sentiment of the news published since the previous period up to each period
+        let mut articles = vec![0u32; timestamps.len()];
+        let mut score_sums = vec![0.0; timestamps.len()];
+        let mut period = 0;
+        for i in 0..news_dates.len() {
+            if let (Some(date), Some(n), Some(score)) = (news_dates[i], news_articles[i], news_scores[i]) {
+                while period < timestamps.len() && timestamps[period].map(|x| x.date() < date).unwrap_or(true) {
+                    period += 1;
+                }
+                if period == timestamps.len() {
+                    break;
+                }
+                articles[period] += n;
+                score_sums[period] += score * n as f64;
+            }
+        }
+        let scores = articles.iter().zip(score_sums.iter())
+            .map(|(n, s)| if *n > 0 { Some(s / *n as f64) } else { None })
+            .collect::<Vec<Option<f64>>>();
+
+        let df = df!(
+            "timestamp" => returns.column("timestamp")?.clone(),
+            "roc-1" => returns.column("roc-1")?.clone(),
+            "next_return" => returns.column("roc-1")?.shift(-1),
+            "articles" => articles,
+            "sentiment" => scores
+        )?;
+        Ok(df)
+    }
+
+    /// Decomposes the daily returns of the ticker into overnight (close to open) and intraday (open to close) returns
+    ///
+    /// Returns are computed from unadjusted prices, so the overnight return of an ex-dividend date includes the dividend drop
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, overnight, intraday and close_to_close (returns in percent),
+    ///   the first overnight and close to close returns are NaN
+    async fn overnight_intraday_split(&self) -> Result<DataFrame, Box<dyn Error>> {
+        if self.interval.is_intraday() {
+            return Err("The overnight and intraday split requires a daily or longer interval".into());
+        }
+        let ohlcv = self.get_chart().await?;
+        let open = ohlcv.column("open")?.f64()?.to_vec().iter().map(|x| x.unwrap_or(f64::NAN)).collect::<Vec<f64>>();
+        let close = ohlcv.column("close")?.f64()?.to_vec().iter().map(|x| x.unwrap_or(f64::NAN)).collect::<Vec<f64>>();
+        let (overnight, intraday) = overnight_intraday_returns(&open, &close);
+        let close_to_close = overnight.iter().zip(intraday.iter())
+            .map(|(o, i)| ((1.0 + o) * (1.0 + i) - 1.0) * 100.0)
+            .collect::<Vec<f64>>();
+        let df = df!(
+            "timestamp" => ohlcv.column("timestamp")?.clone(),
+            "overnight" => overnight.iter().map(|x| x * 100.0).collect::<Vec<f64>>(),
+            "intraday" => intraday.iter().map(|x| x * 100.0).collect::<Vec<f64>>(),
+            "close_to_close" => close_to_close
+        )?;
+        Ok(df)
+    }
+
+    /// Computes the opening gap statistics and the overnight versus intraday return split of the ticker
+    ///
+    /// A gap up (down) is filled when the low (high) of the day trades back to the previous close
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Minimum absolute overnight return in percent counted as a gap (default - 1.0)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Metric and Value columns
+    async fn gap_stats(&self, threshold: Option<f64>) -> Result<DataFrame, Box<dyn Error>> {
+        let threshold = threshold.unwrap_or(1.0);
+        let split = self.overnight_intraday_split().await?;
+        let ohlcv = self.get_chart().await?;
+        let high = ohlcv.column("high")?.f64()?.to_vec();
+        let low = ohlcv.column("low")?.f64()?.to_vec();
+        let close = ohlcv.column("close")?.f64()?.to_vec();
+        let overnight = split.column("overnight")?.f64()?.to_vec().iter().map(|x| x.unwrap_or(f64::NAN)).collect::<Vec<f64>>();
+        let intraday = split.column("intraday")?.f64()?.to_vec().iter().map(|x| x.unwrap_or(f64::NAN)).collect::<Vec<f64>>();
+
+        let (mut gaps_up, mut gaps_down, mut filled_up, mut filled_down) = (Vec::new(), Vec::new(), 0, 0);
+        for i in 1..overnight.len() {
+            let (Some(h), Some(l), Some(prev_close)) = (high[i], low[i], close[i - 1]) else { continue };
+            if overnight[i] >= threshold {
+                gaps_up.push(overnight[i]);
+                if l <= prev_close { filled_up += 1; }
+            } else if overnight[i] <= -threshold {
+                gaps_down.push(overnight[i]);
+                if h >= prev_close { filled_down += 1; }
+            }
+        }
+
+        // Periods with a missing price are left out of the split statistics
+        let valid = (0..overnight.len()).filter(|i| overnight[*i].is_finite() && intraday[*i].is_finite()).collect::<Vec<usize>>();
+        let overnight = valid.iter().map(|i| overnight[*i]).collect::<Vec<f64>>();
+        let intraday = valid.iter().map(|i| intraday[*i]).collect::<Vec<f64>>();
+        let mean = |x: &[f64]| if x.is_empty() { f64::NAN } else { x.iter().sum::<f64>() / x.len() as f64 };
+        let variance = |x: &[f64]| if x.len() < 2 { f64::NAN } else {
+            let m = mean(x);
+            x.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (x.len() - 1) as f64
+        };
+        let periods = self.calendar.trading_days() / self.calendar.interval_days(self.interval);
+        let compound = |x: &[f64]| (x.iter().fold(1.0, |acc, r| acc * (1.0 + r / 100.0)) - 1.0) * 100.0;
+        let fill_rate = |filled: usize, gaps: &Vec<f64>| if gaps.is_empty() { f64::NAN } else { filled as f64 / gaps.len() as f64 * 100.0 };
+
+        let df = df!(
+            "Metric" => vec![
+                "Gap Threshold (%)".to_string(), "Gaps Up".to_string(), "Gaps Down".to_string(),
+                "Average Gap Up (%)".to_string(), "Average Gap Down (%)".to_string(),
+                "Gap Up Fill Rate (%)".to_string(), "Gap Down Fill Rate (%)".to_string(),
+                "Annualized Overnight Return (%)".to_string(), "Annualized Intraday Return (%)".to_string(),
+                "Annualized Overnight Volatility (%)".to_string(), "Annualized Intraday Volatility (%)".to_string(),
+                "Overnight Variance Share (%)".to_string(),
+                "Cumulative Overnight Return (%)".to_string(), "Cumulative Intraday Return (%)".to_string(),
+            ],
+            "Value" => vec![
+                format!("{:.2}", threshold),
+                gaps_up.len().to_string(),
+                gaps_down.len().to_string(),
+                format!("{:.2}", mean(&gaps_up)),
+                format!("{:.2}", mean(&gaps_down)),
+                format!("{:.2}", fill_rate(filled_up, &gaps_up)),
+                format!("{:.2}", fill_rate(filled_down, &gaps_down)),
+                format!("{:.2}", mean(&overnight) * periods),
+                format!("{:.2}", mean(&intraday) * periods),
+                format!("{:.2}", (variance(&overnight) * periods).sqrt()),
+                format!("{:.2}", (variance(&intraday) * periods).sqrt()),
+                format!("{:.2}", variance(&overnight) / (variance(&overnight) + variance(&intraday)) * 100.0),
+                format!("{:.2}", compound(&overnight)),
+                format!("{:.2}", compound(&intraday)),
+            ]
+        )?;
+        Ok(df)
+    }
+
     /// Computes the performance statistics for the ticker
     ///
     /// # Returns
     ///
     /// * `TickerPerformanceStats` struct
     async fn performance_stats(&self) -> Result<TickerPerformanceStats, Box<dyn Error>> {
-        let security_df = self.roc(1, Some(Column::AdjClose)).await?;
-        let security_prices = security_df.column(Column::AdjClose.as_str())?.clone();
+        let security_df = self.returns().await?;
+        let security_prices = security_df.column(&self.price_type.to_string())?.clone();
         let security_returns = DataFrame::new(vec![
             security_df.column("timestamp")?.clone(),
             security_df.column("roc-1")?.clone().with_name(&*self.ticker)
@@ -49,8 +362,13 @@ impl TickerPerformance for Ticker {
             .interval(self.interval.clone())
             .confidence_level(self.confidence_level)
             .risk_free_rate(self.risk_free_rate)
+            .calendar(self.calendar)
+            .validate_data(self.validate_data)
+            .return_type(self.return_type)
+            .price_type(self.price_type)
+            .ticker_data(self.benchmark_data.clone())
             .build();
-        let benchmark_returns = benchmark_ticker.roc(1, Some(Column::AdjClose)).await?;
+        let benchmark_returns = benchmark_ticker.returns().await?;
         let benchmark_returns = security_returns.join(
             &benchmark_returns,
             &["timestamp"],
@@ -58,18 +376,23 @@ impl TickerPerformance for Ticker {
             JoinArgs::new(JoinType::Left),
         )?;
         let benchmark_returns = benchmark_returns.sort(&["timestamp"], SortMultipleOptions::new().with_order_descending(false))?;
-        let benchmark_returns = benchmark_returns.fill_null(FillNullStrategy::Forward(None))?;
-        let benchmark_returns = benchmark_returns.fill_null(FillNullStrategy::Backward(None))?;
+        let benchmark_returns = if self.calendar.includes_weekends() {
+            // An equity benchmark does not move on days its market is closed
+            benchmark_returns.fill_null(FillNullStrategy::Zero)?
+        } else {
+            let benchmark_returns = benchmark_returns.fill_null(FillNullStrategy::Forward(None))?;
+            benchmark_returns.fill_null(FillNullStrategy::Backward(None))?
+        };
         let dates_array = benchmark_returns.column("timestamp")?.datetime()?
             .into_no_null_iter().map(|x| DateTime::from_timestamp_millis(x).unwrap()
             .naive_local()).collect::<Vec<NaiveDateTime>>();
         let dates_array = dates_array.iter().map(|x| x.to_string()).collect::<Vec<String>>();
         let security_returns = benchmark_returns.column(&*self.ticker)?.clone();
-        let benchmark_returns = benchmark_returns.column("roc-1")?.clone();
+        let benchmark_returns = pegged_benchmark_returns(&self.benchmark_symbol, benchmark_returns.column("roc-1")?.clone());
 
         let performance_stats = PerformanceStats::compute_stats(
             security_returns.clone(), benchmark_returns.clone(),
-            self.risk_free_rate, self.confidence_level, self.interval)?;
+            self.risk_free_rate, self.confidence_level, self.interval, self.calendar, self.return_type)?;
         Ok(TickerPerformanceStats {
             ticker_symbol: self.ticker.clone(),
             benchmark_symbol: self.benchmark_symbol.clone(),
@@ -77,8 +400,10 @@ impl TickerPerformance for Ticker {
             end_date: self.end_date.clone(),
             dates_array,
             interval: self.interval.clone(),
+            calendar: self.calendar,
             confidence_level: self.confidence_level,
             risk_free_rate: self.risk_free_rate,
+            return_type: self.return_type,
             security_prices: security_prices.clone(),
             security_returns: security_returns.clone(),
             benchmark_returns: benchmark_returns.clone(),
@@ -86,6 +411,120 @@ impl TickerPerformance for Ticker {
         })
     }
 
+    /// Computes the performance statistics against both the hedged and unhedged benchmark
+    /// when the ticker and benchmark trade in different currencies
+    ///
+    /// The exchange rate returns are aligned with the ticker periods, periods without an exchange rate
+    /// are treated as unchanged.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<CurrencyHedgedStats>` - None when the ticker and benchmark share a currency
+    async fn currency_hedged_stats(&self) -> Result<Option<CurrencyHedgedStats>, Box<dyn Error>> {
+        // Prices quoted in minor units (e.g. GBp) share the returns of the major currency
+        let currency = |symbol: String| async move {
+            let values = fetch_quote_fields(&symbol, &["currency".to_string()]).await?;
+            values.first().and_then(|v| v.as_str()).map(|v| v.to_uppercase())
+                .ok_or_else(|| -> Box<dyn Error> { format!("No currency found for {}", symbol).into() })
+        };
+        let ticker_currency = currency(self.ticker.clone()).await?;
+        let benchmark_currency = currency(self.benchmark_symbol.clone()).await?;
+        if ticker_currency == benchmark_currency {
+            return Ok(None);
+        }
+
+        let stats = self.performance_stats().await?;
+        let pair = CurrencyPair::new(&benchmark_currency, &ticker_currency);
+        let fx_ticker = TickerBuilder::new().ticker(&pair.symbol())
+            .start_date(&self.start_date)
+            .end_date(&self.end_date)
+            .interval(self.interval)
+            .calendar(self.calendar)
+            .return_type(self.return_type)
+            .price_type(PriceType::Close)
+            .build();
+        let fx_returns = fx_ticker.returns().await?;
+        let timestamps = self.returns().await?.select(["timestamp"])?;
+        let fx_returns = timestamps.join(&fx_returns, &["timestamp"], &["timestamp"], JoinArgs::new(JoinType::Left))?
+            .sort(["timestamp"], SortMultipleOptions::new().with_order_descending(false))?
+            .fill_null(FillNullStrategy::Zero)?;
+        let fx_returns = fx_returns.column("roc-1")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let benchmark_returns = stats.benchmark_returns.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        if fx_returns.len() != benchmark_returns.len() {
+            return Err(format!("Unable to align the {} exchange rate with the benchmark returns", pair.to_string()).into());
+        }
+        let unhedged_returns = benchmark_returns.iter().zip(fx_returns.iter())
+            .map(|(b, fx)| match self.return_type {
+                ReturnType::Simple => ((1.0 + b / 100.0) * (1.0 + fx / 100.0) - 1.0) * 100.0,
+                ReturnType::Log => b + fx,
+            })
+            .collect::<Vec<f64>>();
+
+        let compute = |benchmark: Series| PerformanceStats::compute_stats(
+            stats.security_returns.clone(), benchmark, self.risk_free_rate, self.confidence_level,
+            self.interval, self.calendar, self.return_type);
+        Ok(Some(CurrencyHedgedStats {
+            ticker_currency,
+            benchmark_currency,
+            fx_symbol: pair.symbol(),
+            hedged: stats.performance_stats.clone(),
+            unhedged: compute(Series::new("roc-1", unhedged_returns))?,
+        }))
+    }
+
+}
+
+/// Inputs of a portfolio, built by `PortfolioBuilder`
+///
+/// * `ticker_symbols` - Vector of ticker symbols (e.g. ["AAPL", "NVDA", "GOOG"])
+/// * `benchmark_symbol` - Benchmark ticker symbol (e.g. "^GSPC")
+/// * `start_date` - Start date in YYYY-MM-DD format (e.g. "2021-01-01")
+/// * `end_date` - End date in YYYY-MM-DD format (e.g. "2021-01-31")
+/// * `interval` - Time interval enum (e.g. Interval::OneDay)
+/// * `confidence_level` - Confidence level for VaR and CVaR in decimal (e.g 0.95 for 95%)
+/// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
+/// * `objective_function` - Objective function for the optimization (e.g. ObjectiveFunction::MaxSharpe)
+/// * `constraints` - Lower and upper weight bounds for each asset
+/// * `category_constraints` - Lower and upper weight bounds for categories of assets
+/// * `turnover_constraint` - Optional current weights and maximum turnover (L1 distance) from them
+/// * `covariance_estimator` - Covariance matrix estimator (e.g. CovarianceEstimator::LedoitWolf)
+/// * `expected_return_model` - Expected return model (e.g. ExpectedReturnModel::Capm)
+/// * `calendar` - Trading calendar used for annualization (e.g. MarketCalendar::Crypto)
+/// * `return_type` - Simple or log returns
+/// * `price_type` - Price column used for the returns (e.g. PriceType::AdjClose)
+/// * `seed` - Optional seed of the optimizer random starting weights
+/// * `ticker_data` - Optional user supplied OHLCV data of the tickers and benchmark (see `KLINE`)
+/// * `uncertainty_set` - Optional expected return uncertainty set for robust optimization
+/// * `max_drawdown` - Optional limit on the in-sample maximum drawdown of the optimal portfolio in decimal (e.g. 0.2)
+/// * `tracking_error_limit` - Optional limit on the annualized tracking error against the benchmark in decimal (e.g. 0.03)
+/// * `replication_holdings` - Optional maximum number of holdings of a benchmark replicating portfolio, replacing the objective function
+/// * `position_constraint` - Optional maximum number of positions and minimum weight of a held asset
+#[derive(Debug, Clone)]
+pub struct PortfolioConfig {
+    pub ticker_symbols: Vec<String>,
+    pub benchmark_symbol: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub interval: Interval,
+    pub confidence_level: f64,
+    pub risk_free_rate: f64,
+    pub objective_function: ObjectiveFunction,
+    pub constraints: Option<Vec<(f64, f64)>>,
+    pub category_constraints: Vec<CategoryConstraint>,
+    pub turnover_constraint: Option<TurnoverConstraint>,
+    pub covariance_estimator: CovarianceEstimator,
+    pub expected_return_model: ExpectedReturnModel,
+    pub calendar: MarketCalendar,
+    pub return_type: ReturnType,
+    pub price_type: PriceType,
+    pub seed: Option<u64>,
+    pub ticker_data: Option<Vec<KLINE>>,
+    pub uncertainty_set: Option<UncertaintySet>,
+    pub max_drawdown: Option<f64>,
+    pub tracking_error_limit: Option<f64>,
+    pub replication_holdings: Option<usize>,
+    pub position_constraint: Option<PositionConstraint>,
 }
 
 /// # Portfolio Performance Struct
@@ -104,11 +543,31 @@ pub struct PortfolioPerformanceStats {
     pub benchmark_returns: Series,
     pub objective_function: ObjectiveFunction,
     pub optimization_method: String,
+    pub covariance_estimator: CovarianceEstimator,
+    pub expected_return_model: ExpectedReturnModel,
+    pub expected_returns: Vec<f64>,
+    pub calendar: MarketCalendar,
+    pub return_type: ReturnType,
+    pub price_type: PriceType,
+    pub seed: Option<u64>,
+    pub ticker_data: Option<Vec<KLINE>>,
     pub constraints: Vec<(f64, f64)>,
+    pub group_constraints: Vec<GroupConstraint>,
+    pub turnover_constraint: Option<TurnoverConstraint>,
+    pub uncertainty_set: Option<UncertaintySet>,
+    pub max_drawdown: Option<f64>,
+    pub tracking_error_limit: Option<f64>,
+    pub replication_holdings: Option<usize>,
+    pub position_constraint: Option<PositionConstraint>,
     pub optimal_weights: Vec<f64>,
+    pub optimization_diagnostics: OptimizationDiagnostics,
     pub optimal_portfolio_returns: Series,
     pub performance_stats: PerformanceStats,
     pub efficient_frontier: Vec<Vec<f64>>,
+    pub volatility_target: Option<VolatilityTarget>,
+    pub trend_filter: Option<TrendFilter>,
+    pub esg_screen: Option<EsgScreen>,
+    pub esg_scores: Option<Vec<f64>>,
 }
 
 
@@ -117,35 +576,28 @@ impl PortfolioPerformanceStats {
     ///
     /// # Arguments
     ///
-    /// * `ticker_symbols` - Vector of ticker symbols (e.g. ["AAPL", "NVDA", "GOOG"])
-    /// * `benchmark_symbol` - Benchmark ticker symbol (e.g. "^GSPC")
-    /// * `start_date` - Start date in YYYY-MM-DD format (e.g. "2021-01-01")
-    /// * `end_date` - End date in YYYY-MM-DD format (e.g. "2021-01-31")
-    /// * `interval` - Time interval enum (e.g. Interval::OneDay)
-    /// * `confidence_level` - Confidence level for VaR and CVaR in decimal (e.g 0.95 for 95%)
-    /// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
-    /// * `max_iterations` - Maximum number of iterations for the optimization (e.g. 1000)
-    /// * `objective_function` - Objective function for the optimization (e.g. ObjectiveFunction::MaxSharpe)
+    /// * `config` - Symbols, period, data and optimization settings of the portfolio (see `PortfolioConfig`)
     ///
     /// # Returns
     ///
     /// * `PortfolioPerformanceStats` struct
-    pub async fn new(
-        ticker_symbols: Vec<String>,
-        benchmark_symbol: &str,
-        start_date: &str,
-        end_date: &str,
-        interval: Interval,
-        confidence_level: f64,
-        risk_free_rate: f64,
-        objective_function: ObjectiveFunction,
-        constraints: Option<Vec<(f64, f64)>>
-    ) -> Result<PortfolioPerformanceStats, Box<dyn Error>> {
+    pub async fn new(config: PortfolioConfig) -> Result<PortfolioPerformanceStats, Box<dyn Error>> {
+        let PortfolioConfig {
+            ticker_symbols, benchmark_symbol, start_date, end_date, interval, confidence_level, risk_free_rate,
+            objective_function, constraints, category_constraints, turnover_constraint, covariance_estimator,
+            expected_return_model, calendar, return_type, price_type, seed, ticker_data, uncertainty_set,
+            max_drawdown, tracking_error_limit, replication_holdings, position_constraint,
+        } = config;
+        let (benchmark_symbol, start_date, end_date) = (benchmark_symbol.as_str(), start_date.as_str(), end_date.as_str());
         let ticker_symbols = ticker_symbols.iter().map(|x| x.as_str()).collect::<Vec<&str>>();
         let tickers = TickersBuilder::new()
             .tickers(ticker_symbols.clone())
             .start_date(start_date)
             .end_date(end_date)
+            .calendar(calendar)
+            .return_type(return_type)
+            .price_type(price_type)
+            .ticker_data(ticker_data.clone())
             .build();
         let mut portfolio_returns = tickers.returns().await?;
         let portfolio_dates = portfolio_returns
@@ -163,8 +615,12 @@ impl PortfolioPerformanceStats {
             .interval(interval)
             .confidence_level(confidence_level)
             .risk_free_rate(risk_free_rate)
+            .calendar(calendar)
+            .return_type(return_type)
+            .price_type(price_type)
+            .ticker_data(kline_for(&ticker_data, benchmark_symbol))
             .build();
-        let benchmark_returns = benchmark_ticker.roc(1, Some(Column::AdjClose)).await?;
+        let benchmark_returns = benchmark_ticker.returns().await?;
         let benchmark_returns =  portfolio_returns.join(
             &benchmark_returns,
             &["timestamp"],
@@ -178,7 +634,7 @@ impl PortfolioPerformanceStats {
             .into_no_null_iter().map(|x| DateTime::from_timestamp_millis(x).unwrap()
             .naive_local()).collect::<Vec<NaiveDateTime>>();
         let dates_array = dates_array.iter().map(|x| x.to_string()).collect::<Vec<String>>();
-        let benchmark_returns = benchmark_returns.column("roc-1")?.clone();
+        let benchmark_returns = pegged_benchmark_returns(benchmark_symbol, benchmark_returns.column("roc-1")?.clone());
 
         let _ = portfolio_returns.drop_in_place("timestamp")?;
 
@@ -197,6 +653,26 @@ impl PortfolioPerformanceStats {
             None => vec![(0.0, 1.0); fetched_symbols.len()]
         };
 
+        let group_constraints = resolve_category_constraints(&fetched_symbols, &category_constraints).await?;
+
+        let expected_returns = estimate_expected_returns(&fetched_symbols, &portfolio_returns, &benchmark_returns,
+                                                         risk_free_rate, interval, calendar, expected_return_model).await?;
+
+        let turnover_constraint = match turnover_constraint {
+            Some(t) => {
+                let current_weights = t.current_weights.iter()
+                    .zip(ticker_symbols.iter())
+                    .filter(|(_, symbol)| fetched_symbols.contains(&symbol.to_string()))
+                    .map(|(x, _)| *x)
+                    .collect::<Vec<f64>>();
+                Some(TurnoverConstraint {
+                    current_weights,
+                    max_turnover: t.max_turnover,
+                })
+            }
+            None => None
+        };
+
         Ok(PortfolioPerformanceStats {
             ticker_symbols: fetched_symbols.clone(),
             benchmark_symbol: benchmark_symbol.to_string(),
@@ -209,12 +685,36 @@ impl PortfolioPerformanceStats {
             portfolio_returns: portfolio_returns.clone(),
             benchmark_returns: benchmark_returns.clone(),
             objective_function,
-            optimization_method: "Simple Gradient Descent".to_string(),
+            optimization_method: match (replication_holdings, objective_function) {
+                (Some(k), _) => format!("Greedy Forward Selection (Index Replication, max {} holdings)", k),
+                (None, ObjectiveFunction::MinCVaR) => "Linear Programming (Rockafellar-Uryasev)".to_string(),
+                _ => "Simple Gradient Descent".to_string(),
+            },
+            covariance_estimator,
+            expected_return_model,
+            expected_returns,
+            calendar,
+            return_type,
+            price_type,
+            seed,
+            ticker_data,
             constraints,
+            group_constraints,
+            turnover_constraint,
+            uncertainty_set,
+            max_drawdown,
+            tracking_error_limit,
+            replication_holdings,
+            position_constraint,
             optimal_weights: Vec::new(),
+            optimization_diagnostics: OptimizationDiagnostics::default(),
             optimal_portfolio_returns: Series::default(),
             performance_stats: PerformanceStats::default(),
             efficient_frontier: Vec::new(),
+            volatility_target: None,
+            trend_filter: None,
+            esg_screen: None,
+            esg_scores: None,
         })
     }
 
@@ -223,27 +723,19 @@ impl PortfolioPerformanceStats {
     /// # Returns
     ///
     /// * `PortfolioPerformanceStats` struct
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err, fields(assets = self.ticker_symbols.len())))]
     pub fn compute_stats(&self) -> Result<PortfolioPerformanceStats, Box<dyn Error>> {
-        let mean_returns = self.portfolio_returns
-            .get_columns()
-            .iter()
-            .map(|col| {
-                col.f64()
-                    .unwrap()
-                    .mean()
-                    .unwrap()
-            })
-            .collect::<Vec<f64>>();
-        let cov_matrix = covariance_matrix(&self.portfolio_returns)?;
+        let mean_returns = self.expected_returns.clone();
+        let cov_matrix = self.covariance_estimator.estimate(&self.portfolio_returns)?;
 
-        let opt_result = portfolio_optimization(&mean_returns, &cov_matrix, &self.portfolio_returns, self.risk_free_rate,
-                                                     self.confidence_level, self.objective_function, self.constraints.clone());
+        let opt_result = self.optimize(&mean_returns, &cov_matrix, &self.portfolio_returns, &self.benchmark_returns,
+                                       self.turnover_constraint.clone())?;
         let optimal_weights = opt_result.optimal_weights;
         let daily_portfolio_returns = daily_portfolio_returns(&optimal_weights, &self.portfolio_returns);
 
         let performance_stats = PerformanceStats::compute_stats(
             daily_portfolio_returns.clone(), self.benchmark_returns.clone(),
-            self.risk_free_rate, self.confidence_level, self.interval)?;
+            self.risk_free_rate, self.confidence_level, self.interval, self.calendar, self.return_type)?;
 
 
         Ok(Self{
@@ -259,21 +751,679 @@ impl PortfolioPerformanceStats {
             benchmark_returns: self.benchmark_returns.clone(),
             objective_function: self.objective_function.clone(),
             optimization_method: self.optimization_method.clone(),
+            covariance_estimator: self.covariance_estimator,
+            expected_return_model: self.expected_return_model,
+            expected_returns: self.expected_returns.clone(),
+            calendar: self.calendar,
+            return_type: self.return_type,
+            price_type: self.price_type,
+            seed: self.seed,
+            ticker_data: self.ticker_data.clone(),
             constraints: self.constraints.clone(),
+            group_constraints: self.group_constraints.clone(),
+            turnover_constraint: self.turnover_constraint.clone(),
+            uncertainty_set: self.uncertainty_set,
+            max_drawdown: self.max_drawdown,
+            tracking_error_limit: self.tracking_error_limit,
+            replication_holdings: self.replication_holdings,
+            position_constraint: self.position_constraint,
             optimal_weights: optimal_weights.clone(),
+            optimization_diagnostics: opt_result.diagnostics,
             optimal_portfolio_returns: daily_portfolio_returns.clone(),
             performance_stats,
             efficient_frontier: opt_result.efficient_frontier,
+            volatility_target: self.volatility_target.clone(),
+            trend_filter: self.trend_filter.clone(),
+            esg_screen: self.esg_screen,
+            esg_scores: self.esg_scores.clone(),
         })
     }
+
+    /// Summary of the optimal portfolio weights and performance statistics for JSON APIs
+    pub fn summary(&self) -> PortfolioPerformanceSummary {
+        PortfolioPerformanceSummary {
+            symbols: self.ticker_symbols.clone(),
+            benchmark_symbol: self.benchmark_symbol.clone(),
+            start_date: self.start_date.clone(),
+            end_date: self.end_date.clone(),
+            interval: self.interval.to_string(),
+            confidence_level: self.confidence_level,
+            risk_free_rate: self.risk_free_rate,
+            objective_function: format!("{:?}", self.objective_function),
+            converged: self.optimization_diagnostics.converged,
+            weights: self.ticker_symbols.iter().zip(self.optimal_weights.iter())
+                .map(|(symbol, weight)| AssetWeight { symbol: symbol.clone(), weight: *weight })
+                .collect(),
+            stats: self.performance_stats.clone(),
+        }
+    }
+
+    /// Standard errors, confidence intervals and significance tests of the optimal portfolio's Sharpe ratio and alpha
+    ///
+    /// # Returns
+    ///
+    /// * `SignificanceStats` struct
+    pub fn significance(&self) -> Result<SignificanceStats, Box<dyn Error>> {
+        SignificanceStats::compute(&self.optimal_portfolio_returns, &self.benchmark_returns, self.risk_free_rate,
+                                   self.confidence_level, self.interval, self.calendar)
+    }
+
+    /// Block bootstrap confidence bands of the optimal portfolio's performance statistics
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Number of bootstrap resamples (e.g. `DEFAULT_BOOTSTRAP_SAMPLES`)
+    ///
+    /// # Returns
+    ///
+    /// * `BootstrapBands` struct, resampled with the portfolio seed
+    pub fn bootstrap_bands(&self, samples: usize) -> Result<BootstrapBands, Box<dyn Error>> {
+        BootstrapBands::compute(&self.optimal_portfolio_returns, &self.benchmark_returns, self.risk_free_rate,
+                                self.confidence_level, self.interval, self.calendar, self.return_type,
+                                samples, None, self.seed)
+    }
+
+    /// Decomposition of the optimal portfolio's cumulative return into benchmark and active contributions over time
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - timestamp, total_return, benchmark_contribution and active_contribution columns
+    pub fn decomposition(&self) -> Result<DataFrame, Box<dyn Error>> {
+        performance_decomposition(&self.dates_array, &self.optimal_portfolio_returns, &self.benchmark_returns, self.return_type)
+    }
+
+    /// Re-optimizes the portfolio on a rolling (walk-forward) window of returns
+    ///
+    /// Each rebalance uses the optimization settings of the portfolio on the trailing window of returns.
+    /// Analyst target expected returns are not re-estimated per window, and a turnover constraint
+    /// is applied relative to the weights of the previous rebalance.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of return periods in each optimization window (e.g. 252)
+    /// * `step` - Number of return periods between rebalances (e.g. 21)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Weights history with a timestamp column (rebalance date) and a weight column per symbol
+    pub fn rolling_weights(&self, window: usize, step: usize) -> Result<DataFrame, Box<dyn Error>> {
+        let height = self.portfolio_returns.height();
+        if window < 2 || step == 0 || window > height {
+            return Err(format!("Invalid rolling window {} and step {} for {} return periods", window, step, height).into());
+        }
+
+        let mut dates = Vec::new();
+        let mut weights_history: Vec<Vec<f64>> = Vec::new();
+        let mut turnover_constraint = self.turnover_constraint.clone();
+        let mut end = window;
+        while end <= height {
+            let returns = self.portfolio_returns.slice((end - window) as i64, window);
+            let benchmark_returns = self.benchmark_returns.slice((end - window) as i64, window);
+            let mean_returns = match self.expected_return_model {
+                ExpectedReturnModel::HistoricalMean => historical_mean_returns(&returns)?,
+                ExpectedReturnModel::Capm => capm_expected_returns(&returns, &benchmark_returns, self.risk_free_rate,
+                                                                   self.interval, self.calendar)?,
+                ExpectedReturnModel::Momentum => momentum_expected_returns(&returns, self.interval, self.calendar)?,
+                ExpectedReturnModel::AnalystTarget => self.expected_returns.clone(),
+            };
+            let cov_matrix = self.covariance_estimator.estimate(&returns)?;
+            let opt_result = self.optimize(&mean_returns, &cov_matrix, &returns, &benchmark_returns,
+                                           turnover_constraint.clone())?;
+            if let Some(t) = turnover_constraint.as_mut() {
+                t.current_weights = opt_result.optimal_weights.clone();
+            }
+            dates.push(self.dates_array[end - 1].clone());
+            weights_history.push(opt_result.optimal_weights);
+            end += step;
+        }
+
+        let mut columns = vec![Series::new("timestamp", dates)];
+        for (i, symbol) in self.ticker_symbols.iter().enumerate() {
+            columns.push(Series::new(symbol, weights_history.iter().map(|x| x[i]).collect::<Vec<f64>>()));
+        }
+        Ok(DataFrame::new(columns)?)
+    }
+
+    /// Optimizes the weights with the settings of the portfolio, replicating the benchmark when a maximum
+    /// number of replication holdings is set
+    ///
+    /// A position constraint is enforced in two stages: the assets to hold are selected from the unconstrained
+    /// solution, then the weights are re-optimized with the other assets pinned at zero
+    fn optimize(
+        &self,
+        mean_returns: &Vec<f64>,
+        cov_matrix: &ndarray::Array2<f64>,
+        returns: &DataFrame,
+        benchmark_returns: &Series,
+        turnover_constraint: Option<TurnoverConstraint>,
+    ) -> Result<OptResult, Box<dyn Error>> {
+        if let Some(max_holdings) = self.replication_holdings {
+            return index_replication(returns, benchmark_returns, max_holdings, &self.constraints, self.seed);
+        }
+        let solve = |constraints: Vec<(f64, f64)>| {
+            let settings = OptimizationSettings {
+                constraints,
+                turnover_constraint: turnover_constraint.clone(),
+                ..self.optimization_settings()
+            };
+            portfolio_optimization(mean_returns, cov_matrix, returns, Some(benchmark_returns), self.objective_function, &settings)
+        };
+        let opt_result = solve(self.constraints.clone());
+        let Some(position_constraint) = self.position_constraint else {
+            return Ok(opt_result);
+        };
+        let constraints = position_constraint.restrict(&opt_result.optimal_weights, &self.constraints)?;
+        let mut opt_result = solve(constraints);
+        let held = opt_result.optimal_weights.iter().filter(|w| **w > 1e-6).count();
+        if let Some(max_positions) = position_constraint.max_positions {
+            if held >= max_positions {
+                opt_result.diagnostics.active_constraints.push(format!("Max positions ({})", max_positions));
+            }
+        }
+        if let Some(min_weight) = position_constraint.min_weight {
+            if opt_result.optimal_weights.iter().any(|w| *w > 1e-6 && *w - min_weight < 1e-6) {
+                opt_result.diagnostics.active_constraints.push(format!("Min position ({:.2}%)", min_weight * 100.0));
+            }
+        }
+        Ok(opt_result)
+    }
+
+    /// Weighted ESG risk rating of the optimal portfolio over its rated holdings
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - Weighted ESG risk rating and the share of the portfolio weight with a rating in percent
+    pub fn esg_score(&self) -> Result<(f64, f64), Box<dyn Error>> {
+        let scores = self.esg_scores.as_ref().ok_or("ESG scores have not been fetched for the portfolio")?;
+        weighted_esg_score(&self.optimal_weights, scores)
+    }
+
+    /// Settings of the portfolio optimization, with the drawdown limit in percent and the tracking error
+    /// limit converted to a per period percentage
+    pub fn optimization_settings(&self) -> OptimizationSettings {
+        OptimizationSettings {
+            risk_free_rate: self.risk_free_rate,
+            confidence_level: self.confidence_level,
+            constraints: self.constraints.clone(),
+            group_constraints: self.group_constraints.clone(),
+            turnover_constraint: self.turnover_constraint.clone(),
+            uncertainty_set: self.uncertainty_set,
+            max_drawdown: self.max_drawdown.map(|x| x * 100.0),
+            tracking_error_limit: self.period_tracking_error_limit(),
+            seed: self.seed,
+        }
+    }
+
+    /// Tracking error limit converted from an annualized decimal to a per period percentage
+    fn period_tracking_error_limit(&self) -> Option<f64> {
+        let periods = self.calendar.trading_days() / self.calendar.interval_days(self.interval);
+        self.tracking_error_limit.map(|x| x * 100.0 / periods.sqrt())
+    }
+
+    /// Computes the Pareto frontier of two objectives under the constraints of the portfolio
+    ///
+    /// # Arguments
+    ///
+    /// * `objectives` - The two objective functions (e.g. (ObjectiveFunction::MaxReturn, ObjectiveFunction::MinCVaR))
+    /// * `points` - Number of weighted sum optimizations along the frontier (e.g. 20)
+    ///
+    /// # Returns
+    ///
+    /// * `ParetoFrontier` struct
+    pub fn pareto_frontier(&self, objectives: (ObjectiveFunction, ObjectiveFunction), points: usize) -> Result<ParetoFrontier, Box<dyn Error>> {
+        let cov_matrix = self.covariance_estimator.estimate(&self.portfolio_returns)?;
+        pareto_frontier(&self.expected_returns, &cov_matrix, &self.portfolio_returns, Some(&self.benchmark_returns),
+                        objectives, points, &self.optimization_settings())
+    }
+
+    /// Samples random portfolios satisfying the asset, category and turnover constraints of the portfolio
+    ///
+    /// The samples show the attainable risk/return region for the optimization chart and give a baseline
+    /// distribution to judge the optimal portfolio against.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Number of portfolios to sample (e.g. 1000)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with a weight column per symbol and columns annualized_return (%), annualized_volatility (%)
+    ///   and sharpe_ratio
+    pub fn random_portfolios(&self, samples: usize) -> Result<DataFrame, Box<dyn Error>> {
+        if samples == 0 {
+            return Err("The number of samples must be positive".into());
+        }
+        let cov_matrix = self.covariance_estimator.estimate(&self.portfolio_returns)?;
+        let weights = random_portfolios(self.ticker_symbols.len(), samples, &self.constraints, &self.group_constraints,
+                                        &self.turnover_constraint, self.seed);
+        let days = self.calendar.interval_days(self.interval);
+        let trading_days = self.calendar.trading_days();
+        let returns = weights.iter()
+            .map(|w| ((1.0 + (mean_portfolio_return(w, &self.expected_returns) / days) / 100.0).powf(trading_days) - 1.0) * 100.0)
+            .collect::<Vec<f64>>();
+        let volatilities = weights.iter()
+            .map(|w| portfolio_std_dev(w, &cov_matrix) * (trading_days / days).sqrt())
+            .collect::<Vec<f64>>();
+        let sharpe_ratios = returns.iter().zip(volatilities.iter())
+            .map(|(r, v)| (r - self.risk_free_rate * 100.0) / v)
+            .collect::<Vec<f64>>();
+
+        let mut columns = self.ticker_symbols.iter().enumerate()
+            .map(|(i, symbol)| Series::new(symbol, weights.iter().map(|x| x[i]).collect::<Vec<f64>>()))
+            .collect::<Vec<Series>>();
+        columns.push(Series::new("annualized_return", returns));
+        columns.push(Series::new("annualized_volatility", volatilities));
+        columns.push(Series::new("sharpe_ratio", sharpe_ratios));
+        Ok(DataFrame::new(columns)?)
+    }
+
+    /// Compares the optimal portfolio with naive allocations of the same universe
+    ///
+    /// The equal-weight and market-cap-weight portfolios are held at fixed weights over the period and
+    /// ignore the optimization constraints, showing how much the optimizer adds over a naive allocation.
+    ///
+    /// # Arguments
+    ///
+    /// * `market_caps` - Optional market capitalization of each asset, in the order of the ticker symbols,
+    ///   the market-cap-weight portfolio is left out when missing or not positive
+    ///
+    /// # Returns
+    ///
+    /// * `StrategyComparison` - with the Optimal Portfolio, Equal Weight and Market Cap Weight variants against the benchmark
+    pub fn naive_comparison(&self, market_caps: Option<&[f64]>) -> Result<StrategyComparison, Box<dyn Error>> {
+        let num_assets = self.ticker_symbols.len();
+        let variant = |name: &str, weights: &Vec<f64>| -> Result<StrategyVariant, Box<dyn Error>> {
+            let df = df!(
+                "timestamp" => self.dates_array.clone(),
+                name => daily_portfolio_returns(weights, &self.portfolio_returns)
+            )?;
+            Ok(StrategyVariant::new(name, df))
+        };
+
+        let mut variants = vec![
+            variant("Optimal Portfolio", &self.optimal_weights)?,
+            variant("Equal Weight", &vec![1.0 / num_assets as f64; num_assets])?,
+        ];
+        match market_caps {
+            Some(caps) if caps.len() == num_assets && caps.iter().all(|x| x.is_finite() && *x > 0.0) => {
+                let total = caps.iter().sum::<f64>();
+                variants.push(variant("Market Cap Weight", &caps.iter().map(|x| x / total).collect::<Vec<f64>>())?);
+            }
+            Some(_) => eprintln!("Market caps are missing for some assets, skipping the market-cap-weight portfolio"),
+            None => {}
+        }
+
+        let benchmark = df!(
+            "timestamp" => self.dates_array.clone(),
+            &self.benchmark_symbol => self.benchmark_returns.clone()
+        )?;
+        Ok(StrategyComparison::new(variants)
+            .benchmark(benchmark)
+            .risk_free_rate(self.risk_free_rate)
+            .confidence_level(self.confidence_level)
+            .interval(self.interval)
+            .calendar(self.calendar)
+            .return_type(self.return_type))
+    }
+
+    /// Compares the optimal portfolio with a volatility targeted version of it
+    ///
+    /// The exposure of each period is set from the realized volatility of the optimal portfolio up to
+    /// the previous period and capped at the maximum leverage, the uninvested share earns no return.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Volatility target settings
+    ///
+    /// # Returns
+    ///
+    /// * `StrategyComparison` - with the Unmanaged and Volatility Targeted variants against the benchmark
+    pub fn volatility_targeting(&self, target: &VolatilityTarget) -> Result<StrategyComparison, Box<dyn Error>> {
+        let portfolio_returns = df!(
+            "timestamp" => self.dates_array.clone(),
+            "roc-1" => self.optimal_portfolio_returns.clone()
+        )?;
+        let returns = self.optimal_portfolio_returns.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+        let positions = target.positions(&returns, self.interval, self.calendar)?;
+        let unmanaged = StrategyVariant::new("Unmanaged", df!(
+            "timestamp" => self.dates_array.clone(),
+            "Unmanaged" => self.optimal_portfolio_returns.clone()
+        )?);
+        let name = format!("Volatility Target {:.0}%", target.target_volatility * 100.0);
+        let managed = StrategyVariant::from_positions(&name, &portfolio_returns, &positions, 0.0)?
+            .param("target_volatility", target.target_volatility)
+            .param("max_leverage", target.max_leverage);
+
+        let benchmark = df!(
+            "timestamp" => self.dates_array.clone(),
+            &self.benchmark_symbol => self.benchmark_returns.clone()
+        )?;
+        Ok(StrategyComparison::new(vec![unmanaged, managed])
+            .benchmark(benchmark)
+            .risk_free_rate(self.risk_free_rate)
+            .confidence_level(self.confidence_level)
+            .interval(self.interval)
+            .calendar(self.calendar)
+            .return_type(self.return_type))
+    }
+
+    /// Compares the optimal portfolio with a moving average trend filter applied to it
+    ///
+    /// The filter holds cash (earning no return) while the portfolio equity, or each constituent's price,
+    /// is below its moving average. The signal of a period is traded in the next period.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Trend filter settings
+    ///
+    /// # Returns
+    ///
+    /// * `StrategyComparison` - with the Unmanaged and trend filtered variants against the benchmark
+    pub fn trend_filtering(&self, filter: &TrendFilter) -> Result<StrategyComparison, Box<dyn Error>> {
+        let to_vec = |series: &Series| -> Result<Vec<f64>, Box<dyn Error>> {
+            Ok(series.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect())
+        };
+        let price_index = |returns: &[f64]| self.return_type.cumulative_returns(returns).iter()
+            .map(|x| 1.0 + x).collect::<Vec<f64>>();
+
+        let portfolio_returns = to_vec(&self.optimal_portfolio_returns)?;
+        let filtered_returns = match filter.target {
+            TrendFilterTarget::Equity => {
+                let signal = moving_average_filter(&price_index(&portfolio_returns), filter.window);
+                position_returns(&portfolio_returns, &signal, 0.0)
+            }
+            TrendFilterTarget::Constituents => {
+                let mut filtered = vec![0.0; portfolio_returns.len()];
+                for (i, weight) in self.optimal_weights.iter().enumerate() {
+                    if *weight == 0.0 {
+                        continue;
+                    }
+                    let asset_returns = to_vec(&self.portfolio_returns.get_columns()[i])?;
+                    let signal = moving_average_filter(&price_index(&asset_returns), filter.window);
+                    for (t, r) in position_returns(&asset_returns, &signal, 0.0).iter().enumerate() {
+                        filtered[t] += weight * r;
+                    }
+                }
+                filtered
+            }
+        };
+
+        let name = format!("{}-Period MA Filter ({})", filter.window, filter.target.to_string());
+        let unmanaged = StrategyVariant::new("Unmanaged", df!(
+            "timestamp" => self.dates_array.clone(),
+            "Unmanaged" => portfolio_returns
+        )?);
+        let filtered = StrategyVariant::new(&name, df!(
+            "timestamp" => self.dates_array.clone(),
+            &name => filtered_returns
+        )?).param("window", filter.window as f64);
+
+        let benchmark = df!(
+            "timestamp" => self.dates_array.clone(),
+            &self.benchmark_symbol => self.benchmark_returns.clone()
+        )?;
+        Ok(StrategyComparison::new(vec![unmanaged, filtered])
+            .benchmark(benchmark)
+            .risk_free_rate(self.risk_free_rate)
+            .confidence_level(self.confidence_level)
+            .interval(self.interval)
+            .calendar(self.calendar)
+            .return_type(self.return_type))
+    }
+
+    /// Simulates the optimal portfolio over the period with scheduled contributions and withdrawals
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Initial investment and cash flow schedule (e.g. monthly deposits)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, return, cash_flow, net_invested and value
+    pub fn cash_flow_simulation(&self, schedule: &CashFlowSchedule) -> Result<DataFrame, Box<dyn Error>> {
+        let dates = self.dates_array.iter()
+            .map(|x| NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M:%S"))
+            .collect::<Result<Vec<NaiveDateTime>, _>>()?;
+        let returns = self.optimal_portfolio_returns.f64()?.into_iter()
+            .map(|x| {
+                let r = x.unwrap_or(0.0);
+                match self.return_type {
+                    ReturnType::Simple => r,
+                    ReturnType::Log => ((r / 100.0).exp() - 1.0) * 100.0,
+                }
+            })
+            .collect::<Vec<f64>>();
+        simulate_cash_flows(&dates, &returns, schedule)
+    }
+
+    /// Computes the annualized money-weighted return (IRR) of the optimal portfolio with scheduled cash flows
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Initial investment and cash flow schedule (e.g. monthly deposits)
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Annualized money-weighted return in percent
+    pub fn money_weighted_return(&self, schedule: &CashFlowSchedule) -> Result<f64, Box<dyn Error>> {
+        let simulation = self.cash_flow_simulation(schedule)?;
+        let periods_per_year = self.calendar.trading_days() / self.calendar.interval_days(self.interval);
+        money_weighted_return(&simulation, periods_per_year)
+    }
+
+    /// Projects a savings plan invested in the optimal portfolio, using its annualized return and volatility
+    ///
+    /// # Arguments
+    ///
+    /// * `plan` - Initial investment, monthly contributions, horizon and target value
+    ///
+    /// # Returns
+    ///
+    /// * `GoalProjection` - Probability of reaching the target and the percentile projections
+    pub fn goal_projection(&self, plan: &GoalPlan) -> Result<GoalProjection, Box<dyn Error>> {
+        let days = self.calendar.interval_days(self.interval);
+        let trading_days = self.calendar.trading_days();
+        let annual_return = (1.0 + (self.performance_stats.daily_return / days) / 100.0).powf(trading_days) - 1.0;
+        let annual_volatility = self.performance_stats.daily_volatility / 100.0 * (trading_days / days).sqrt();
+        project_goal(plan, annual_return, annual_volatility)
+    }
+
+    /// Compares the time-weighted and money-weighted returns of the optimal portfolio with scheduled cash flows
+    ///
+    /// The time-weighted return measures the strategy, while the money-weighted return (XIRR) measures
+    /// the investor's experience including the timing of the contributions and withdrawals
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Initial investment and cash flow schedule (e.g. monthly deposits)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns Metric and Value
+    pub fn return_comparison(&self, schedule: &CashFlowSchedule) -> Result<DataFrame, Box<dyn Error>> {
+        let simulation = self.cash_flow_simulation(schedule)?;
+        let periods_per_year = self.calendar.trading_days() / self.calendar.interval_days(self.interval);
+        let (twr, annualized_twr) = time_weighted_return(&simulation, periods_per_year)?;
+        let dates = simulation.column("timestamp")?.datetime()?.as_datetime_iter()
+            .flatten().map(|x| x.date().to_string()).collect::<Vec<String>>();
+        let (start, end) = (dates.first().ok_or("Empty simulation")?, dates.last().ok_or("Empty simulation")?);
+        let final_value = simulation.column("value")?.f64()?.into_no_null_iter().last().unwrap_or(f64::NAN);
+        let net_invested = simulation.column("net_invested")?.f64()?.into_no_null_iter().last().unwrap_or(f64::NAN);
+        let mwr = schedule.xirr(start, end, final_value)?;
+
+        let df = df!(
+            "Metric" => vec!["Net Invested", "Final Value", "Profit", "Time-Weighted Return (Cumulative)",
+                             "Time-Weighted Return (Annualized)", "Money-Weighted Return (XIRR)"],
+            "Value" => vec![net_invested, final_value, final_value - net_invested, twr, annualized_twr, mwr]
+        )?;
+        Ok(df)
+    }
 }
 
+/// Resolves category constraints to the indices of the portfolio assets in each category
+///
+/// # Arguments
+///
+/// * `symbols` - Vector of portfolio ticker symbols
+/// * `category_constraints` - Vector of category constraints
+///
+/// # Returns
+///
+/// * `Vec<GroupConstraint>` - Group constraints with the matching asset indices
+pub async fn resolve_category_constraints(
+    symbols: &[String],
+    category_constraints: &[CategoryConstraint],
+) -> Result<Vec<GroupConstraint>, Box<dyn Error>> {
+    let needs_quotes = category_constraints.iter()
+        .any(|c| !matches!(c.source, CategorySource::Mapping(_)));
+
+    // Quote metadata is only fetched when a constraint is resolved against it
+    let mut quotes = Vec::new();
+    if needs_quotes {
+        for symbol in symbols {
+            let quote = TickerBuilder::new().ticker(symbol).build().get_quote().await?;
+            quotes.push(quote);
+        }
+    }
 
+    let group_constraints = category_constraints.iter().map(|c| {
+        let indices = symbols.iter().enumerate().filter(|(i, symbol)| {
+            let category = match &c.source {
+                CategorySource::AssetClass => quotes[*i].asset_class.clone(),
+                CategorySource::Exchange => quotes[*i].exchange.clone(),
+                CategorySource::Mapping(mapping) => mapping.get(*symbol).cloned().unwrap_or_default(),
+            };
+            category.eq_ignore_ascii_case(&c.category)
+        }).map(|(i, _)| i).collect::<Vec<usize>>();
+        if indices.is_empty() {
+            eprintln!("No portfolio assets found in category {}", &c.category);
+        }
+        GroupConstraint {
+            category: c.category.clone(),
+            indices,
+            min_weight: c.min_weight,
+            max_weight: c.max_weight,
+        }
+    }).collect::<Vec<GroupConstraint>>();
 
+    Ok(group_constraints)
+}
 
+/// Treats a stablecoin benchmark (e.g. USDT-USD) as cash, pegging its returns to zero so that the price noise
+/// around the peg does not produce a spurious alpha and beta
+fn pegged_benchmark_returns(benchmark_symbol: &str, benchmark_returns: Series) -> Series {
+    if is_stablecoin(benchmark_symbol) {
+        Series::new(benchmark_returns.name(), vec![0.0; benchmark_returns.len()])
+    } else {
+        benchmark_returns
+    }
+}
 
+/// Number of analyst price targets fetched concurrently by the AnalystTarget expected return model
+const PRICE_TARGET_CONCURRENCY: usize = 8;
 
+/// Estimates the expected returns of the portfolio assets used in the optimization
+///
+/// # Arguments
+///
+/// * `symbols` - Vector of portfolio ticker symbols
+/// * `portfolio_returns` - Polars DataFrame of asset returns
+/// * `benchmark_returns` - Polars Series of benchmark returns
+/// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
+/// * `interval` - Time interval enum (e.g. Interval::OneDay)
+/// * `calendar` - Trading calendar enum (e.g. MarketCalendar::Equity)
+/// * `expected_return_model` - Expected return model (e.g. ExpectedReturnModel::Capm)
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Expected return of each asset per interval
+pub async fn estimate_expected_returns(
+    symbols: &[String],
+    portfolio_returns: &DataFrame,
+    benchmark_returns: &Series,
+    risk_free_rate: f64,
+    interval: Interval,
+    calendar: MarketCalendar,
+    expected_return_model: ExpectedReturnModel,
+) -> Result<Vec<f64>, Box<dyn Error>> {
+    match expected_return_model {
+        ExpectedReturnModel::HistoricalMean => historical_mean_returns(portfolio_returns),
+        ExpectedReturnModel::Capm => capm_expected_returns(portfolio_returns, benchmark_returns, risk_free_rate, interval, calendar),
+        ExpectedReturnModel::Momentum => momentum_expected_returns(portfolio_returns, interval, calendar),
+        ExpectedReturnModel::AnalystTarget => {
+            let historical_returns = historical_mean_returns(portfolio_returns)?;
+            let mut upsides = stream::iter(symbols.iter().enumerate())
+                .map(|(i, symbol)| async move {
+                    let target = TickerBuilder::new().ticker(symbol).build().get_price_target().await;
+                    let upside = match target {
+                        Ok(t) => match (t.target_mean_price, t.current_price) {
+                            (Some(target), Some(price)) if price.raw > 0.0 => Some(target.raw / price.raw - 1.0),
+                            _ => None,
+                        },
+                        Err(_) => None,
+                    };
+                    (i, upside)
+                })
+                .buffer_unordered(PRICE_TARGET_CONCURRENCY)
+                .collect::<Vec<(usize, Option<f64>)>>()
+                .await;
+            upsides.sort_by_key(|(i, _)| *i);
 
+            let mut expected_returns = Vec::new();
+            for (i, upside) in upsides {
+                match upside {
+                    Some(u) => expected_returns.push(analyst_target_expected_returns(&[u], interval, calendar)[0]),
+                    None => {
+                        // Assets without analyst coverage fall back to their historical mean return
+                        eprintln!("No analyst price target found for {}, using historical mean return", symbols[i]);
+                        expected_returns.push(historical_returns[i]);
+                    }
+                }
+            }
+            Ok(expected_returns)
+        }
+    }
+}
 
+/// Decomposes the cumulative return of a security into benchmark and active contributions over time
+///
+/// The benchmark contribution is the cumulative return of the benchmark and the active contribution is
+/// the difference between the cumulative returns of the security and the benchmark, so the two add up
+/// to the cumulative return of the security at every date.
+///
+/// # Arguments
+///
+/// * `dates` - Vector of return dates
+/// * `returns` - Polars Series of security returns in percent
+/// * `benchmark_returns` - Polars Series of benchmark returns in percent
+/// * `return_type` - Return type enum (e.g. ReturnType::Simple)
+///
+/// # Returns
+///
+/// * `DataFrame` - timestamp, total_return, benchmark_contribution and active_contribution columns in decimal
+pub fn performance_decomposition(
+    dates: &[String],
+    returns: &Series,
+    benchmark_returns: &Series,
+    return_type: ReturnType,
+) -> Result<DataFrame, Box<dyn Error>> {
+    if returns.len() != benchmark_returns.len() || returns.len() != dates.len() {
+        return Err("Returns, benchmark returns and dates must have the same length".into());
+    }
+    let returns = returns.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+    let benchmark_returns = benchmark_returns.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
 
+    let total_return = return_type.cumulative_returns(&returns);
+    let benchmark_contribution = return_type.cumulative_returns(&benchmark_returns);
+    let active_contribution = total_return.iter().zip(benchmark_contribution.iter())
+        .map(|(r, b)| r - b)
+        .collect::<Vec<f64>>();
 
+    let df = df!(
+        "timestamp" => dates.to_vec(),
+        "total_return" => total_return,
+        "benchmark_contribution" => benchmark_contribution,
+        "active_contribution" => active_contribution,
+    )?;
+    Ok(df)
+}
@@ -0,0 +1,115 @@
+use std::error::Error;
+use polars::prelude::*;
+use rand_distr::{Distribution, Normal};
+use crate::utils::synthetic::rng;
+
+
+/// Percentiles reported by the goal projection
+pub const PROJECTION_PERCENTILES: [f64; 5] = [5.0, 25.0, 50.0, 75.0, 95.0];
+
+/// Savings plan of a goal-based projection
+///
+/// * `initial_value` - Amount invested at the start (e.g. 10000.0)
+/// * `monthly_contribution` - Amount added at the end of each month, negative for withdrawals (e.g. 500.0)
+/// * `years` - Projection horizon in years (e.g. 10)
+/// * `target_value` - Goal amount to reach by the end of the horizon (e.g. 100000.0)
+/// * `simulations` - Number of Monte Carlo paths (default 10000)
+/// * `seed` - Optional random seed for reproducible projections
+#[derive(Debug, Clone)]
+pub struct GoalPlan {
+    pub initial_value: f64,
+    pub monthly_contribution: f64,
+    pub years: usize,
+    pub target_value: f64,
+    pub simulations: usize,
+    pub seed: Option<u64>,
+}
+
+impl GoalPlan {
+    pub fn new(initial_value: f64, monthly_contribution: f64, years: usize, target_value: f64) -> GoalPlan {
+        GoalPlan {
+            initial_value,
+            monthly_contribution,
+            years,
+            target_value,
+            simulations: 10000,
+            seed: None,
+        }
+    }
+
+    pub fn simulations(mut self, simulations: usize) -> GoalPlan {
+        self.simulations = simulations;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> GoalPlan {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+/// Result of a goal-based projection
+///
+/// * `probability` - Probability (%) of the final value reaching the target
+/// * `projections` - DataFrame with columns month, net_invested and a value column per percentile (p5, p25, p50, p75, p95)
+/// * `final_values` - Simulated values at the end of the horizon
+#[derive(Debug, Clone)]
+pub struct GoalProjection {
+    pub target_value: f64,
+    pub probability: f64,
+    pub projections: DataFrame,
+    pub final_values: Vec<f64>,
+}
+
+/// Projects a savings plan with a Monte Carlo simulation of monthly log-normal portfolio returns
+///
+/// # Arguments
+///
+/// * `plan` - Savings plan and goal
+/// * `annual_return` - Expected annualized return of the portfolio in decimal (e.g. 0.07 for 7%)
+/// * `annual_volatility` - Annualized volatility of the portfolio in decimal (e.g. 0.15 for 15%)
+///
+/// # Returns
+///
+/// * `GoalProjection` struct
+pub fn project_goal(plan: &GoalPlan, annual_return: f64, annual_volatility: f64) -> Result<GoalProjection, Box<dyn Error>> {
+    if plan.years == 0 || plan.simulations == 0 {
+        return Err("The projection requires at least one year and one simulation".into());
+    }
+    let months = plan.years * 12;
+    let sigma = annual_volatility / 12f64.sqrt();
+    let mu = (1.0 + annual_return).ln() / 12.0 - 0.5 * sigma * sigma;
+    let normal = Normal::new(mu, sigma.max(0.0))?;
+    let mut rng = rng(plan.seed);
+
+    // values[m][s] is the value of path s at the end of month m
+    let mut values = vec![vec![plan.initial_value; plan.simulations]; months + 1];
+    for s in 0..plan.simulations {
+        let mut value = plan.initial_value;
+        for month in values.iter_mut().skip(1) {
+            value = (value * normal.sample(&mut rng).exp() + plan.monthly_contribution).max(0.0);
+            month[s] = value;
+        }
+    }
+
+    let mut columns = vec![
+        Series::new("month", (0..=months as u32).collect::<Vec<u32>>()),
+        Series::new("net_invested", (0..=months)
+            .map(|m| plan.initial_value + plan.monthly_contribution * m as f64).collect::<Vec<f64>>()),
+    ];
+    let mut sorted_values = values.clone();
+    sorted_values.iter_mut().for_each(|x| x.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)));
+    for p in PROJECTION_PERCENTILES {
+        let index = ((p / 100.0) * (plan.simulations - 1) as f64).round() as usize;
+        columns.push(Series::new(&format!("p{}", p), sorted_values.iter().map(|x| x[index]).collect::<Vec<f64>>()));
+    }
+
+    let final_values = values[months].clone();
+    let hits = final_values.iter().filter(|x| **x >= plan.target_value).count();
+    Ok(GoalProjection {
+        target_value: plan.target_value,
+        probability: hits as f64 / plan.simulations as f64 * 100.0,
+        projections: DataFrame::new(columns)?,
+        final_values,
+    })
+}
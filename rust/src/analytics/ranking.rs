@@ -0,0 +1,123 @@
+use std::error::Error;
+use polars::prelude::*;
+
+
+/// Metric used to rank a universe of symbols
+///
+/// * `column` - Column of the metric in the universe DataFrame (e.g. "forward_pe")
+/// * `higher_is_better` - Whether higher values rank better (e.g. false for P/E ratios)
+/// * `weight` - Weight of the metric in the composite rank (default 1.0)
+/// * `positive_only` - Whether to treat zero and negative values as missing (e.g. for P/E ratios)
+#[derive(Debug, Clone)]
+pub struct RankMetric {
+    pub column: String,
+    pub higher_is_better: bool,
+    pub weight: f64,
+    pub positive_only: bool,
+}
+
+impl RankMetric {
+    pub fn new(column: &str, higher_is_better: bool) -> RankMetric {
+        RankMetric {
+            column: column.to_string(),
+            higher_is_better,
+            weight: 1.0,
+            positive_only: false,
+        }
+    }
+
+    pub fn weight(mut self, weight: f64) -> RankMetric {
+        self.weight = weight;
+        self
+    }
+
+    pub fn positive_only(mut self) -> RankMetric {
+        self.positive_only = true;
+        self
+    }
+
+    /// Default value, growth and momentum metrics of the Tickers summary stats
+    pub fn defaults() -> Vec<RankMetric> {
+        vec![
+            RankMetric::new("forward_pe", false).positive_only(),
+            RankMetric::new("price_to_book", false).positive_only(),
+            RankMetric::new("eps_growth", true),
+            RankMetric::new("fifty_two_week_change_percent", true),
+            RankMetric::new("dividend_yield", true),
+        ]
+    }
+}
+
+/// Computes the percentile rank of each symbol per metric within the universe, and a composite rank
+///
+/// Percentiles run from 0 (worst) to 100 (best) with ties sharing their average rank. Missing values
+/// are left null and excluded from the composite, which is the weighted average of the available percentiles.
+///
+/// # Arguments
+///
+/// * `df` - Universe DataFrame with one row per symbol
+/// * `metrics` - Metrics to rank
+///
+/// # Returns
+///
+/// * `DataFrame` - Input columns plus a `<metric>_pct` column per metric, composite_score and
+///   composite_rank (1 is best), sorted by composite_rank
+pub fn percentile_ranks(df: &DataFrame, metrics: &[RankMetric]) -> Result<DataFrame, Box<dyn Error>> {
+    let height = df.height();
+    let mut result = df.clone();
+    let mut weighted_sum = vec![0.0; height];
+    let mut weight_total = vec![0.0; height];
+
+    for metric in metrics {
+        let values = df.column(&metric.column)?.cast(&DataType::Float64)?.f64()?.into_iter()
+            .map(|x| x.filter(|v| v.is_finite() && (!metric.positive_only || *v > 0.0)))
+            .collect::<Vec<Option<f64>>>();
+        let percentiles = percentiles(&values, metric.higher_is_better);
+        for i in 0..height {
+            if let Some(p) = percentiles[i] {
+                weighted_sum[i] += p * metric.weight;
+                weight_total[i] += metric.weight;
+            }
+        }
+        result.with_column(Series::new(&format!("{}_pct", metric.column), percentiles))?;
+    }
+
+    let composite = (0..height)
+        .map(|i| if weight_total[i] > 0.0 { Some(weighted_sum[i] / weight_total[i]) } else { None })
+        .collect::<Vec<Option<f64>>>();
+    let mut order = (0..height).filter(|i| composite[*i].is_some()).collect::<Vec<usize>>();
+    order.sort_by(|a, b| composite[*b].partial_cmp(&composite[*a]).unwrap_or(std::cmp::Ordering::Equal));
+    let mut composite_rank: Vec<Option<u32>> = vec![None; height];
+    for (rank, &i) in order.iter().enumerate() {
+        composite_rank[i] = Some(rank as u32 + 1);
+    }
+    result.with_column(Series::new("composite_score", composite))?;
+    result.with_column(Series::new("composite_rank", composite_rank))?;
+
+    let result = result.sort(["composite_rank"], SortMultipleOptions::new().with_nulls_last(true))?;
+    Ok(result)
+}
+
+/// Percentile (0 - 100) of each value among the available values, ties share their average rank
+fn percentiles(values: &[Option<f64>], higher_is_better: bool) -> Vec<Option<f64>> {
+    let mut valid = values.iter().enumerate()
+        .filter_map(|(i, x)| x.map(|v| (i, if higher_is_better { v } else { -v })))
+        .collect::<Vec<(usize, f64)>>();
+    valid.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    let n = valid.len();
+    let mut result = vec![None; values.len()];
+    let mut start = 0;
+    while start < n {
+        let mut end = start;
+        while end + 1 < n && valid[end + 1].1 == valid[start].1 {
+            end += 1;
+        }
+        let average_rank = (start + end) as f64 / 2.0;
+        let percentile = if n > 1 { average_rank / (n - 1) as f64 * 100.0 } else { 50.0 };
+        for item in &valid[start..=end] {
+            result[item.0] = Some(percentile);
+        }
+        start = end + 1;
+    }
+    result
+}
@@ -0,0 +1,237 @@
+use std::error::Error;
+use polars::prelude::*;
+use rand::seq::SliceRandom;
+
+use crate::analytics::features::train_test_split;
+use crate::analytics::statistics::{deflated_sharpe_ratio, kurtosis, period_sharpe_ratio, skewness, PerformanceStats};
+use crate::analytics::strategy::{StrategyMetric, StrategyVariant};
+use crate::data::config::{Interval, MarketCalendar, ReturnType};
+use crate::utils::synthetic::rng;
+
+
+/// Method used to pick the parameter combinations of a search
+///
+/// * `Grid` - Every combination of the parameter values
+/// * `Random` - Random sample of `samples` combinations without replacement, seeded when a seed is given
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchMethod {
+    Grid,
+    Random { samples: usize, seed: Option<u64> },
+}
+
+/// Grid or random search over the parameters of an indicator or strategy
+///
+/// Each combination is backtested over the full sample, then split chronologically: the selection metric is
+/// computed on the training part and re-computed on the held-out validation part, and the deflated Sharpe ratio
+/// of the training returns accounts for the number of combinations tried.
+///
+/// * `params` - Parameter names and candidate values (e.g. [("fast", [10, 20]), ("slow", [50, 100])])
+/// * `method` - Search method (default SearchMethod::Grid)
+/// * `metric` - Metric the combinations are ranked by (default StrategyMetric::SharpeRatio)
+/// * `validation_size` - Fraction of the periods held out for validation in decimal (default 0.3)
+/// * `risk_free_rate` - Risk-free rate in decimal (default 0.02)
+/// * `interval` - Interval of the returns (default Interval::OneDay)
+/// * `calendar` - Trading calendar used for annualization (default MarketCalendar::Equity)
+/// * `return_type` - Convention of the returns (default ReturnType::Simple)
+#[derive(Debug, Clone)]
+pub struct ParameterSearch {
+    pub params: Vec<(String, Vec<f64>)>,
+    pub method: SearchMethod,
+    pub metric: StrategyMetric,
+    pub validation_size: f64,
+    pub risk_free_rate: f64,
+    pub interval: Interval,
+    pub calendar: MarketCalendar,
+    pub return_type: ReturnType,
+}
+
+impl Default for ParameterSearch {
+    fn default() -> Self {
+        ParameterSearch {
+            params: Vec::new(),
+            method: SearchMethod::Grid,
+            metric: StrategyMetric::SharpeRatio,
+            validation_size: 0.3,
+            risk_free_rate: 0.02,
+            interval: Interval::OneDay,
+            calendar: MarketCalendar::Equity,
+            return_type: ReturnType::Simple,
+        }
+    }
+}
+
+impl ParameterSearch {
+    pub fn new() -> ParameterSearch {
+        ParameterSearch::default()
+    }
+
+    pub fn param(mut self, name: &str, values: Vec<f64>) -> ParameterSearch {
+        self.params.push((name.to_string(), values));
+        self
+    }
+
+    pub fn method(mut self, method: SearchMethod) -> ParameterSearch {
+        self.method = method;
+        self
+    }
+
+    pub fn metric(mut self, metric: StrategyMetric) -> ParameterSearch {
+        self.metric = metric;
+        self
+    }
+
+    pub fn validation_size(mut self, validation_size: f64) -> ParameterSearch {
+        self.validation_size = validation_size;
+        self
+    }
+
+    pub fn risk_free_rate(mut self, risk_free_rate: f64) -> ParameterSearch {
+        self.risk_free_rate = risk_free_rate;
+        self
+    }
+
+    pub fn interval(mut self, interval: Interval) -> ParameterSearch {
+        self.interval = interval;
+        self
+    }
+
+    pub fn calendar(mut self, calendar: MarketCalendar) -> ParameterSearch {
+        self.calendar = calendar;
+        self
+    }
+
+    pub fn return_type(mut self, return_type: ReturnType) -> ParameterSearch {
+        self.return_type = return_type;
+        self
+    }
+
+    /// Parameter combinations evaluated by the search
+    pub fn candidates(&self) -> Vec<Vec<(String, f64)>> {
+        let mut grid: Vec<Vec<(String, f64)>> = vec![Vec::new()];
+        for (name, values) in &self.params {
+            grid = grid.iter()
+                .flat_map(|combination| values.iter().map(move |v| {
+                    let mut combination = combination.clone();
+                    combination.push((name.clone(), *v));
+                    combination
+                }))
+                .collect();
+        }
+        match self.method {
+            SearchMethod::Grid => grid,
+            SearchMethod::Random { samples, seed } => {
+                grid.shuffle(&mut rng(seed));
+                grid.truncate(samples);
+                grid
+            }
+        }
+    }
+
+    /// Runs the search
+    ///
+    /// Combinations are evaluated in parallel when the `fast_math` feature is enabled. Combinations whose
+    /// strategy fails are reported and skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - Function backtesting a parameter combination, e.g. computing positions from indicators
+    ///   with those parameters and calling `StrategyVariant::from_positions`
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per combination sorted by the training metric, with a column per parameter and columns
+    ///   train_metric, validation_metric, train_sharpe, validation_sharpe, deflated_sharpe and rank
+    pub fn run<F>(&self, strategy: F) -> Result<DataFrame, Box<dyn Error>>
+    where
+        F: Fn(&[(String, f64)]) -> Result<StrategyVariant, Box<dyn Error>> + Sync,
+    {
+        if self.params.is_empty() {
+            return Err("No parameters to search over".into());
+        }
+        let candidates = self.candidates();
+        let evaluate = |params: &Vec<(String, f64)>| -> Result<Evaluation, String> {
+            let variant = strategy(params).map_err(|e| e.to_string())?;
+            self.evaluate(&variant).map_err(|e| e.to_string())
+        };
+
+        #[cfg(feature = "fast_math")]
+        let results = {
+            use rayon::prelude::*;
+            candidates.par_iter().map(evaluate).collect::<Vec<Result<Evaluation, String>>>()
+        };
+        #[cfg(not(feature = "fast_math"))]
+        let results = candidates.iter().map(evaluate).collect::<Vec<Result<Evaluation, String>>>();
+
+        let mut rows = Vec::new();
+        for (params, result) in candidates.iter().zip(results) {
+            match result {
+                Ok(evaluation) => rows.push((params, evaluation)),
+                Err(e) => eprintln!("Error evaluating parameters {:?}: {}", params, e),
+            }
+        }
+        if rows.is_empty() {
+            return Err("No parameter combination could be evaluated".into());
+        }
+
+        // Deflate each training Sharpe ratio by the dispersion of the Sharpe ratios across all the trials
+        let trials = rows.len();
+        let sharpes = rows.iter().map(|(_, e)| e.period_sharpe).filter(|x| x.is_finite()).collect::<Vec<f64>>();
+        let mean = sharpes.iter().sum::<f64>() / sharpes.len().max(1) as f64;
+        let sharpe_variance = sharpes.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (sharpes.len().max(2) - 1) as f64;
+
+        rows.sort_by(|a, b| b.1.train_metric.partial_cmp(&a.1.train_metric).unwrap_or(std::cmp::Ordering::Equal));
+        let mut columns = self.params.iter()
+            .map(|(name, _)| Series::new(name, rows.iter().map(|(p, _)| p.iter().find(|(x, _)| x == name).map(|(_, v)| *v))
+                .collect::<Vec<Option<f64>>>()))
+            .collect::<Vec<Series>>();
+        columns.push(Series::new("train_metric", rows.iter().map(|(_, e)| e.train_metric).collect::<Vec<f64>>()));
+        columns.push(Series::new("validation_metric", rows.iter().map(|(_, e)| e.validation_metric).collect::<Vec<f64>>()));
+        columns.push(Series::new("train_sharpe", rows.iter().map(|(_, e)| e.train_sharpe).collect::<Vec<f64>>()));
+        columns.push(Series::new("validation_sharpe", rows.iter().map(|(_, e)| e.validation_sharpe).collect::<Vec<f64>>()));
+        columns.push(Series::new("deflated_sharpe", rows.iter()
+            .map(|(_, e)| deflated_sharpe_ratio(e.period_sharpe, sharpe_variance, trials, e.observations, e.skewness, e.kurtosis))
+            .collect::<Vec<f64>>()));
+        columns.push(Series::new("rank", (1..=rows.len() as u32).collect::<Vec<u32>>()));
+        Ok(DataFrame::new(columns)?)
+    }
+
+    /// Computes the training and validation statistics of a backtested variant
+    fn evaluate(&self, variant: &StrategyVariant) -> Result<Evaluation, Box<dyn Error>> {
+        let (train, validation) = train_test_split(&variant.returns, self.validation_size, 0)?;
+        let stats = |df: &DataFrame| PerformanceStats::from_returns(df, None, self.risk_free_rate, 0.95,
+                                                                    self.interval, self.calendar, self.return_type);
+        let train_stats = stats(&train)?;
+        let validation_stats = stats(&validation)?;
+        let metric = |s: &PerformanceStats| match self.metric {
+            StrategyMetric::MaximumDrawdown => s.maximum_drawdown,
+            _ => self.metric.value(s),
+        };
+
+        // Sharpe ratio per period of the training returns, as used by the deflated Sharpe ratio
+        let returns = StrategyVariant::new(&variant.name, train).returns_vec()?;
+        let risk_free = self.risk_free_rate * 100.0 * self.calendar.interval_days(self.interval) / self.calendar.trading_days();
+
+        Ok(Evaluation {
+            train_metric: metric(&train_stats),
+            validation_metric: metric(&validation_stats),
+            train_sharpe: train_stats.sharpe_ratio,
+            validation_sharpe: validation_stats.sharpe_ratio,
+            period_sharpe: period_sharpe_ratio(&returns, risk_free),
+            observations: returns.len(),
+            skewness: skewness(&returns),
+            kurtosis: kurtosis(&returns),
+        })
+    }
+}
+
+/// Statistics of a parameter combination
+struct Evaluation {
+    train_metric: f64,
+    validation_metric: f64,
+    train_sharpe: f64,
+    validation_sharpe: f64,
+    period_sharpe: f64,
+    observations: usize,
+    skewness: f64,
+    kurtosis: f64,
+}
@@ -0,0 +1,201 @@
+use std::error::Error;
+use std::collections::BTreeMap;
+use chrono::{NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+
+use crate::data::ticker::TickerData;
+use crate::models::ticker::Ticker;
+
+
+pub trait SessionAnalytics {
+    fn time_of_day_profile(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn session_gaps(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn session_summary(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+}
+
+impl SessionAnalytics for Ticker {
+    /// Computes the volume and volatility profile of an intraday ticker by time of day
+    ///
+    /// Bars are grouped by their UTC time of day, so the first and last rows of a regular session
+    /// hold the opening and closing auction bars
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns time (HH:MM UTC), bars, average_volume, volume_share (% of the total volume),
+    ///   volatility (standard deviation of the bar returns in %) and average_absolute_return (%)
+    async fn time_of_day_profile(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let bars = intraday_bars(self).await?;
+
+        // Returns are measured within a session, the first bar of each session is measured from its open
+        let mut profile: BTreeMap<String, (Vec<f64>, Vec<f64>)> = BTreeMap::new();
+        for i in 0..bars.len() {
+            let (ts, open, close, volume) = bars[i];
+            let base = if i > 0 && bars[i - 1].0.date() == ts.date() { bars[i - 1].2 } else { open };
+            let entry = profile.entry(ts.format("%H:%M").to_string()).or_default();
+            entry.0.push(volume);
+            if base > 0.0 {
+                entry.1.push((close / base - 1.0) * 100.0);
+            }
+        }
+        let total_volume = profile.values().map(|(v, _)| v.iter().sum::<f64>()).sum::<f64>();
+
+        let df = df!(
+            "time" => profile.keys().cloned().collect::<Vec<String>>(),
+            "bars" => profile.values().map(|(v, _)| v.len() as u32).collect::<Vec<u32>>(),
+            "average_volume" => profile.values().map(|(v, _)| mean(v)).collect::<Vec<f64>>(),
+            "volume_share" => profile.values().map(|(v, _)| if total_volume > 0.0 {
+                v.iter().sum::<f64>() / total_volume * 100.0
+            } else {
+                f64::NAN
+            }).collect::<Vec<f64>>(),
+            "volatility" => profile.values().map(|(_, r)| std_dev(r)).collect::<Vec<f64>>(),
+            "average_absolute_return" => profile.values()
+                .map(|(_, r)| mean(&r.iter().map(|x| x.abs()).collect::<Vec<f64>>())).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+
+    /// Computes the opening gap, intraday return and auction volume of each trading session
+    ///
+    /// Sessions are the UTC calendar days of the intraday bars. The overnight return runs from the close
+    /// of the previous session to the open of the session, and the intraday return from the open to the close.
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns date, open, close, overnight_return (%), intraday_return (%),
+    ///   open_volume_share and close_volume_share (% of the session volume traded in the first and last bars)
+    async fn session_gaps(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let bars = intraday_bars(self).await?;
+
+        let mut sessions: BTreeMap<NaiveDate, Vec<(f64, f64, f64)>> = BTreeMap::new();
+        for (ts, open, close, volume) in bars {
+            sessions.entry(ts.date()).or_default().push((open, close, volume));
+        }
+
+        let dates = sessions.keys().map(|x| x.to_string()).collect::<Vec<String>>();
+        let opens = sessions.values().map(|x| x[0].0).collect::<Vec<f64>>();
+        let closes = sessions.values().map(|x| x[x.len() - 1].1).collect::<Vec<f64>>();
+        let (overnight, intraday) = overnight_intraday_returns(&opens, &closes);
+        let volume_share = |session: &Vec<(f64, f64, f64)>, volume: f64| {
+            let total = session.iter().map(|x| x.2).sum::<f64>();
+            if total > 0.0 { volume / total * 100.0 } else { f64::NAN }
+        };
+
+        let df = df!(
+            "date" => dates,
+            "open" => opens,
+            "close" => closes,
+            "overnight_return" => overnight.iter().map(|x| x * 100.0).collect::<Vec<f64>>(),
+            "intraday_return" => intraday.iter().map(|x| x * 100.0).collect::<Vec<f64>>(),
+            "open_volume_share" => sessions.values().map(|x| volume_share(x, x[0].2)).collect::<Vec<f64>>(),
+            "close_volume_share" => sessions.values().map(|x| volume_share(x, x[x.len() - 1].2)).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+
+    /// Summarizes the session statistics of an intraday ticker
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Metric and Value columns with the overnight and intraday return split,
+    ///   the share of the return variance earned overnight and the average auction volume shares
+    async fn session_summary(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let gaps = self.session_gaps().await?;
+        let column = |name: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+            Ok(gaps.column(name)?.f64()?.into_iter().flatten().filter(|x| x.is_finite()).collect())
+        };
+        // The first session has no previous close, so its overnight return is NaN and dropped
+        let overnight = column("overnight_return")?;
+        let intraday = column("intraday_return")?;
+        let compound = |returns: &[f64]| (returns.iter().fold(1.0, |acc, r| acc * (1.0 + r / 100.0)) - 1.0) * 100.0;
+        let overnight_var = std_dev(&overnight).powi(2);
+        let intraday_var = std_dev(&intraday).powi(2);
+        let positive = |returns: &[f64]| if returns.is_empty() {
+            f64::NAN
+        } else {
+            returns.iter().filter(|x| **x > 0.0).count() as f64 / returns.len() as f64 * 100.0
+        };
+
+        let df = df!(
+            "Metric" => vec![
+                "Sessions", "Average Overnight Return (%)", "Average Intraday Return (%)",
+                "Overnight Volatility (%)", "Intraday Volatility (%)", "Overnight Variance Share (%)",
+                "Positive Overnight Sessions (%)", "Positive Intraday Sessions (%)",
+                "Cumulative Overnight Return (%)", "Cumulative Intraday Return (%)",
+                "Average Opening Bar Volume Share (%)", "Average Closing Bar Volume Share (%)",
+            ],
+            "Value" => vec![
+                gaps.height().to_string(),
+                format!("{:.4}", mean(&overnight)),
+                format!("{:.4}", mean(&intraday)),
+                format!("{:.4}", std_dev(&overnight)),
+                format!("{:.4}", std_dev(&intraday)),
+                format!("{:.2}", overnight_var / (overnight_var + intraday_var) * 100.0),
+                format!("{:.2}", positive(&overnight)),
+                format!("{:.2}", positive(&intraday)),
+                format!("{:.2}", compound(&overnight)),
+                format!("{:.2}", compound(&intraday)),
+                format!("{:.2}", mean(&column("open_volume_share")?)),
+                format!("{:.2}", mean(&column("close_volume_share")?)),
+            ]
+        )?;
+        Ok(df)
+    }
+}
+
+/// Splits close-to-close returns into overnight (previous close to open) and intraday (open to close) returns
+///
+/// The overnight and intraday returns compound to the close-to-close return of each period
+///
+/// # Arguments
+///
+/// * `open` - Opening prices
+/// * `close` - Closing prices
+///
+/// # Returns
+///
+/// * `(Vec<f64>, Vec<f64>)` - Overnight and intraday returns in decimal, the first overnight return is NaN
+pub fn overnight_intraday_returns(open: &[f64], close: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let ratio = |a: f64, b: f64| if b > 0.0 { a / b - 1.0 } else { f64::NAN };
+    let overnight = (0..open.len())
+        .map(|i| if i == 0 { f64::NAN } else { ratio(open[i], close[i - 1]) })
+        .collect::<Vec<f64>>();
+    let intraday = open.iter().zip(close.iter())
+        .map(|(o, c)| ratio(*c, *o))
+        .collect::<Vec<f64>>();
+    (overnight, intraday)
+}
+
+/// Fetches the intraday bars of a ticker as (timestamp, open, close, volume) tuples
+async fn intraday_bars(ticker: &Ticker) -> Result<Vec<(NaiveDateTime, f64, f64, f64)>, Box<dyn Error>> {
+    if !ticker.interval.is_intraday() {
+        return Err(format!("Session statistics require an intraday interval, got {}", ticker.interval.to_string()).into());
+    }
+    let chart = ticker.get_chart().await?;
+    let timestamps = chart.column("timestamp")?.datetime()?.as_datetime_iter().collect::<Vec<Option<NaiveDateTime>>>();
+    let open = chart.column("open")?.f64()?.to_vec();
+    let close = chart.column("close")?.f64()?.to_vec();
+    let volume = chart.column("volume")?.f64()?.to_vec();
+    let bars = (0..timestamps.len())
+        .filter_map(|i| match (timestamps[i], open[i], close[i]) {
+            (Some(ts), Some(o), Some(c)) => Some((ts, o, c, volume[i].unwrap_or(0.0))),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if bars.is_empty() {
+        return Err(format!("No intraday data found for {}", ticker.ticker).into());
+    }
+    Ok(bars)
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() { f64::NAN } else { values.iter().sum::<f64>() / values.len() as f64 }
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return f64::NAN;
+    }
+    let m = mean(values);
+    (values.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt()
+}
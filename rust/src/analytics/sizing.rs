@@ -0,0 +1,172 @@
+use std::error::Error;
+
+use crate::data::config::{Interval, MarketCalendar};
+
+
+/// Kelly fraction of a bet with a binary outcome
+///
+/// # Arguments
+///
+/// * `win_probability` - Probability of a winning trade in decimal (e.g. 0.55)
+/// * `win_loss_ratio` - Average win divided by the average loss (e.g. 1.5)
+///
+/// # Returns
+///
+/// * `f64` - Fraction of equity to risk, negative when the bet has no edge
+pub fn kelly_fraction(win_probability: f64, win_loss_ratio: f64) -> Result<f64, Box<dyn Error>> {
+    if !(0.0..=1.0).contains(&win_probability) {
+        return Err("Win probability must be between 0 and 1".into());
+    }
+    if win_loss_ratio <= 0.0 {
+        return Err("Win/loss ratio must be positive".into());
+    }
+    Ok(win_probability - (1.0 - win_probability) / win_loss_ratio)
+}
+
+/// Kelly fraction of a bet estimated from the win rate and payoff of historical trade returns
+///
+/// # Arguments
+///
+/// * `trade_returns` - Returns of closed trades in percent
+///
+/// # Returns
+///
+/// * `f64` - Fraction of equity to risk, negative when the trades have no edge
+pub fn kelly_fraction_from_trades(trade_returns: &[f64]) -> Result<f64, Box<dyn Error>> {
+    let wins = trade_returns.iter().filter(|x| **x > 0.0).cloned().collect::<Vec<f64>>();
+    let losses = trade_returns.iter().filter(|x| **x < 0.0).map(|x| x.abs()).collect::<Vec<f64>>();
+    if wins.is_empty() || losses.is_empty() {
+        return Err("At least one winning and one losing trade are required to estimate the Kelly fraction".into());
+    }
+    let win_probability = wins.len() as f64 / (wins.len() + losses.len()) as f64;
+    let average_win = wins.iter().sum::<f64>() / wins.len() as f64;
+    let average_loss = losses.iter().sum::<f64>() / losses.len() as f64;
+    kelly_fraction(win_probability, average_win / average_loss)
+}
+
+/// Continuous-time Kelly leverage of an asset with normally distributed returns
+///
+/// # Arguments
+///
+/// * `expected_return` - Annualized expected return in decimal (e.g. 0.08)
+/// * `volatility` - Annualized volatility in decimal (e.g. 0.2)
+/// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
+///
+/// # Returns
+///
+/// * `f64` - Optimal leverage, the excess return divided by the variance
+pub fn continuous_kelly(expected_return: f64, volatility: f64, risk_free_rate: f64) -> Result<f64, Box<dyn Error>> {
+    if volatility <= 0.0 {
+        return Err("Volatility must be positive".into());
+    }
+    Ok((expected_return - risk_free_rate) / volatility.powi(2))
+}
+
+/// Position size scaling an asset to a target volatility
+///
+/// # Arguments
+///
+/// * `target_volatility` - Annualized target volatility in decimal (e.g. 0.1)
+/// * `volatility` - Annualized volatility estimate of the asset in decimal
+/// * `max_leverage` - Maximum position size (e.g. 1.0 for no leverage)
+///
+/// # Returns
+///
+/// * `f64` - Position size as a fraction of equity
+pub fn volatility_target_size(target_volatility: f64, volatility: f64, max_leverage: f64) -> f64 {
+    if volatility <= 0.0 || !volatility.is_finite() {
+        return max_leverage;
+    }
+    (target_volatility / volatility).min(max_leverage)
+}
+
+/// Volatility targeted position sizes from the trailing realized volatility of a return series
+///
+/// The size of each period only uses the returns up to that period, so it can be passed to
+/// `StrategyVariant::from_positions` (alone or multiplied with a signal) without look-ahead
+///
+/// # Arguments
+///
+/// * `returns` - Asset returns in percent
+/// * `target_volatility` - Annualized target volatility in decimal (e.g. 0.1)
+/// * `window` - Number of trailing periods of the volatility estimate (e.g. 20)
+/// * `max_leverage` - Maximum position size (e.g. 1.0 for no leverage)
+/// * `interval` - Time interval enum (e.g. Interval::OneDay)
+/// * `calendar` - Trading calendar enum (e.g. MarketCalendar::Equity)
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Position size of each period, 0.0 until the window is filled
+pub fn volatility_target_positions(
+    returns: &[f64],
+    target_volatility: f64,
+    window: usize,
+    max_leverage: f64,
+    interval: Interval,
+    calendar: MarketCalendar,
+) -> Result<Vec<f64>, Box<dyn Error>> {
+    if window < 2 {
+        return Err("The volatility window must be at least 2 periods".into());
+    }
+    let periods = calendar.trading_days() / calendar.interval_days(interval);
+    let positions = (0..returns.len())
+        .map(|t| {
+            if t + 1 < window {
+                return 0.0;
+            }
+            let sample = &returns[t + 1 - window..=t];
+            let mean = sample.iter().sum::<f64>() / window as f64;
+            let variance = sample.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (window - 1) as f64;
+            let volatility = (variance * periods).sqrt() / 100.0;
+            volatility_target_size(target_volatility, volatility, max_leverage)
+        })
+        .collect();
+    Ok(positions)
+}
+
+/// Fixed fractional position size risking a fraction of equity between the entry and the stop price
+///
+/// # Arguments
+///
+/// * `equity` - Account equity
+/// * `risk_fraction` - Fraction of equity lost if the stop is hit in decimal (e.g. 0.01)
+/// * `entry_price` - Entry price of the position
+/// * `stop_price` - Stop loss price of the position
+///
+/// # Returns
+///
+/// * `f64` - Number of units to trade
+pub fn fixed_fractional_size(equity: f64, risk_fraction: f64, entry_price: f64, stop_price: f64) -> Result<f64, Box<dyn Error>> {
+    let risk_per_unit = (entry_price - stop_price).abs();
+    if risk_per_unit == 0.0 {
+        return Err("The stop price must differ from the entry price".into());
+    }
+    Ok(equity * risk_fraction / risk_per_unit)
+}
+
+/// Volatility targeting overlay scaling the exposure of a portfolio to a target volatility
+///
+/// * `target_volatility` - Annualized target volatility in decimal (e.g. 0.1)
+/// * `window` - Number of trailing periods of the realized volatility estimate (e.g. 20)
+/// * `max_leverage` - Maximum exposure (e.g. 1.5 allows 50% leverage, 1.0 only scales down)
+#[derive(Debug, Clone)]
+pub struct VolatilityTarget {
+    pub target_volatility: f64,
+    pub window: usize,
+    pub max_leverage: f64,
+}
+
+impl VolatilityTarget {
+    pub fn new(target_volatility: f64, window: usize, max_leverage: f64) -> VolatilityTarget {
+        VolatilityTarget {
+            target_volatility,
+            window,
+            max_leverage,
+        }
+    }
+
+    /// Exposure of each period to the returns (in percent) being managed
+    pub fn positions(&self, returns: &[f64], interval: Interval, calendar: MarketCalendar) -> Result<Vec<f64>, Box<dyn Error>> {
+        volatility_target_positions(returns, self.target_volatility, self.window, self.max_leverage, interval, calendar)
+    }
+}
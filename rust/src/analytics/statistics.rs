@@ -1,16 +1,18 @@
 use std::cmp::Ordering;
 use polars::prelude::*;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
 use std::error::Error;
 use std::ops::Mul;
+use serde::{Deserialize, Serialize};
 use smartcore::linalg::basic::arrays::Array2;
 use smartcore::linalg::basic::matrix::DenseMatrix;
 use smartcore::linear::linear_regression::LinearRegression;
 use statrs::statistics::Statistics;
 use statrs::distribution::{ContinuousCDF, Normal};
-use crate::data::config::Interval;
+use crate::data::config::{Interval, MarketCalendar, ReturnType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceStats {
     pub daily_return: f64,
     pub daily_volatility: f64,
@@ -31,6 +33,11 @@ pub struct PerformanceStats {
 }
 
 impl PerformanceStats {
+    /// Serializes the statistics to a JSON string
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string(self)?)
+    }
+
     /// Creates a default PerformanceStats struct
     pub fn default() -> Self {
         Self {
@@ -53,6 +60,38 @@ impl PerformanceStats {
         }
     }
 
+    /// Statistics in field order
+    fn values(&self) -> Vec<f64> {
+        vec![
+            self.daily_return, self.daily_volatility, self.cumulative_return, self.annualized_return,
+            self.annualized_volatility, self.alpha, self.beta, self.sharpe_ratio, self.sortino_ratio,
+            self.active_return, self.active_risk, self.information_ratio, self.calmar_ratio,
+            self.maximum_drawdown, self.value_at_risk, self.expected_shortfall,
+        ]
+    }
+
+    /// Creates a PerformanceStats struct from statistics in field order
+    fn from_values(values: &[f64]) -> Self {
+        Self {
+            daily_return: values[0],
+            daily_volatility: values[1],
+            cumulative_return: values[2],
+            annualized_return: values[3],
+            annualized_volatility: values[4],
+            alpha: values[5],
+            beta: values[6],
+            sharpe_ratio: values[7],
+            sortino_ratio: values[8],
+            active_return: values[9],
+            active_risk: values[10],
+            information_ratio: values[11],
+            calmar_ratio: values[12],
+            maximum_drawdown: values[13],
+            value_at_risk: values[14],
+            expected_shortfall: values[15],
+        }
+    }
+
     /// Computes the performance statistics of a series of security returns
     ///
     /// # Arguments
@@ -61,6 +100,9 @@ impl PerformanceStats {
     /// * `benchmark_returns` - Polars Series of benchmark returns
     /// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
     /// * `confidence_level` - Confidence level for the VaR and CVaR calculations in decimal (e.g. 0.95 for 95%)
+    /// * `interval` - Time interval of the returns (e.g. Interval::OneDay)
+    /// * `calendar` - Trading calendar used for annualization (e.g. MarketCalendar::Crypto for 365 days)
+    /// * `return_type` - Convention of the returns (simple or log), used when compounding returns
     ///
     /// # Returns
     ///
@@ -71,24 +113,43 @@ impl PerformanceStats {
         risk_free_rate: f64,
         confidence_level: f64,
         interval: Interval,
+        calendar: MarketCalendar,
+        return_type: ReturnType,
     ) -> Result<PerformanceStats, Box<dyn Error>> {
         let _len = returns.len();
-        let days = interval.to_days();
+        let days = calendar.interval_days(interval);
+        let trading_days = calendar.trading_days();
         let risk_free_rate = risk_free_rate * 100.0;
-        let cumulative_return = cumulative_return(&returns);
         let daily_return = returns.mean().ok_or("Error calculating mean return")?/days;
         let daily_volatility = std_dev(&returns);
-        let annualized_return = ((1.0 + daily_return/100.0).powf(252.0) - 1.0) * 100.0;
-        let annualized_volatility = daily_volatility * 252.0_f64.sqrt();
-        let (alpha, beta) = ols_regression(&returns.clone(), &benchmark_returns.clone());
+        let (cumulative_return, annualized_return) = match return_type {
+            ReturnType::Simple => (
+                cumulative_return(&returns),
+                ((1.0 + daily_return/100.0).powf(trading_days) - 1.0) * 100.0,
+            ),
+            ReturnType::Log => (
+                return_type.to_simple(returns.f64()?.sum().unwrap_or(0.0)),
+                return_type.to_simple(daily_return * trading_days),
+            ),
+        };
+        let annualized_volatility = daily_volatility * trading_days.sqrt();
+        // A constant benchmark (e.g. a stablecoin) has no systematic risk to regress against
+        let (alpha, beta) = if std_dev(&benchmark_returns) > f64::EPSILON {
+            ols_regression(&returns.clone(), &benchmark_returns.clone())
+        } else {
+            (returns.mean().unwrap_or(0.0), 0.0)
+        };
         let sharpe_ratio = (annualized_return - risk_free_rate) / annualized_volatility;
         let downside_mask = &returns.lt_eq(0.0).unwrap();
         let downside_returns = returns.filter(downside_mask).unwrap();
-        let sortino_ratio = (annualized_return - risk_free_rate) / (std_dev( &downside_returns) * 252.0_f64.sqrt());
+        let sortino_ratio = (annualized_return - risk_free_rate) / (std_dev( &downside_returns) * trading_days.sqrt());
         let excess_returns = (returns.clone() - benchmark_returns.clone())?;
         let active_return = excess_returns.mean().ok_or("Error calculating active return")?;
-        let active_return = ((1.0 + active_return/100.0).powf(252.0) - 1.0) * 100.0;
-        let active_risk = std_dev(&excess_returns) * 252.0_f64.sqrt();
+        let active_return = match return_type {
+            ReturnType::Simple => ((1.0 + active_return/100.0).powf(trading_days) - 1.0) * 100.0,
+            ReturnType::Log => return_type.to_simple(active_return * trading_days),
+        };
+        let active_risk = std_dev(&excess_returns) * trading_days.sqrt();
         let information_ratio = active_return / active_risk;
         let (_, maximum_drawdown) = maximum_drawdown(&returns);
         let calmar_ratio = annualized_return / maximum_drawdown;
@@ -113,6 +174,292 @@ impl PerformanceStats {
             expected_shortfall,
         })
     }
+
+    /// Computes the performance statistics of a user supplied returns series (e.g. of an external strategy)
+    ///
+    /// Returns are expected in percent (e.g. 1.5 for 1.5%), like the `roc-1` column of `TickerPerformance::returns`.
+    /// When both DataFrames have a `timestamp` column the benchmark is aligned on it (missing benchmark
+    /// returns are treated as 0), otherwise the series are aligned by position.
+    ///
+    /// # Arguments
+    ///
+    /// * `returns` - DataFrame with an optional `timestamp` column and one returns column
+    /// * `benchmark_returns` - Optional DataFrame with an optional `timestamp` column and one returns column,
+    ///   alpha and beta are 0 when not provided
+    /// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
+    /// * `confidence_level` - Confidence level for the VaR and CVaR calculations in decimal (e.g. 0.95 for 95%)
+    /// * `interval` - Time interval of the returns (e.g. Interval::OneDay)
+    /// * `calendar` - Trading calendar used for annualization (e.g. MarketCalendar::Crypto for 365 days)
+    /// * `return_type` - Convention of the returns (simple or log)
+    ///
+    /// # Returns
+    ///
+    /// * `PerformanceStats` struct
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let strategy = df!("timestamp" => &dates, "strategy" => &returns)?;
+    /// let stats = PerformanceStats::from_returns(&strategy, None, 0.02, 0.95,
+    ///     Interval::OneDay, MarketCalendar::Equity, ReturnType::Simple)?;
+    /// println!("Sharpe: {:.2}, Max Drawdown: {:.2}%", stats.sharpe_ratio, stats.maximum_drawdown);
+    /// ```
+    pub fn from_returns(
+        returns: &DataFrame,
+        benchmark_returns: Option<&DataFrame>,
+        risk_free_rate: f64,
+        confidence_level: f64,
+        interval: Interval,
+        calendar: MarketCalendar,
+        return_type: ReturnType,
+    ) -> Result<PerformanceStats, Box<dyn Error>> {
+        let returns_column = |df: &DataFrame| -> Result<Series, Box<dyn Error>> {
+            let series = df.get_columns().iter()
+                .find(|x| x.name() != "timestamp")
+                .ok_or("Returns DataFrame has no returns column")?;
+            Ok(series.cast(&DataType::Float64)?)
+        };
+        let security = returns_column(returns)?;
+        let benchmark = match benchmark_returns {
+            Some(benchmark) if returns.column("timestamp").is_ok() && benchmark.column("timestamp").is_ok() => {
+                let left = DataFrame::new(vec![returns.column("timestamp")?.clone(), security.clone().with_name("returns")])?;
+                let right = DataFrame::new(vec![benchmark.column("timestamp")?.clone(), returns_column(benchmark)?.with_name("benchmark")])?;
+                let joined = left.join(&right, &["timestamp"], &["timestamp"], JoinArgs::new(JoinType::Left))?;
+                joined.column("benchmark")?.fill_null(FillNullStrategy::Zero)?
+            }
+            Some(benchmark) => {
+                let benchmark = returns_column(benchmark)?;
+                if benchmark.len() != security.len() {
+                    return Err("Returns and benchmark returns have different lengths and no timestamp column to align on".into());
+                }
+                benchmark
+            }
+            None => Series::new("benchmark", vec![0.0; security.len()]),
+        };
+        let security = security.fill_null(FillNullStrategy::Zero)?;
+        Self::compute_stats(security, benchmark, risk_free_rate, confidence_level, interval, calendar, return_type)
+    }
+}
+
+/// Default number of bootstrap resamples of the performance statistics confidence bands
+pub const DEFAULT_BOOTSTRAP_SAMPLES: usize = 1000;
+
+/// Block bootstrap confidence bands of the performance statistics
+///
+/// The returns and benchmark returns are resampled together in circular blocks of consecutive periods,
+/// which preserves their correlation and short-term autocorrelation (volatility clustering), and every
+/// statistic is recomputed on each resample. The bands are the percentiles of the resampled statistics
+/// at the confidence level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapBands {
+    pub samples: usize,
+    pub block_size: usize,
+    pub confidence_level: f64,
+    pub lower: PerformanceStats,
+    pub upper: PerformanceStats,
+}
+
+impl BootstrapBands {
+    /// Computes the block bootstrap confidence bands of the performance statistics of a series of security returns
+    ///
+    /// # Arguments
+    ///
+    /// * `returns` - Polars Series of security returns
+    /// * `benchmark_returns` - Polars Series of benchmark returns
+    /// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
+    /// * `confidence_level` - Confidence level of the bands, and of the VaR and CVaR, in decimal (e.g. 0.95 for 95%)
+    /// * `interval` - Time interval of the returns (e.g. Interval::OneDay)
+    /// * `calendar` - Trading calendar used for annualization (e.g. MarketCalendar::Crypto for 365 days)
+    /// * `return_type` - Convention of the returns (simple or log), used when compounding returns
+    /// * `samples` - Number of bootstrap resamples (e.g. `DEFAULT_BOOTSTRAP_SAMPLES`)
+    /// * `block_size` - Number of consecutive periods in each block, defaults to the cube root of the number of returns
+    /// * `seed` - Optional seed of the resampling for reproducible bands
+    ///
+    /// # Returns
+    ///
+    /// * `BootstrapBands` struct
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute(
+        returns: &Series,
+        benchmark_returns: &Series,
+        risk_free_rate: f64,
+        confidence_level: f64,
+        interval: Interval,
+        calendar: MarketCalendar,
+        return_type: ReturnType,
+        samples: usize,
+        block_size: Option<usize>,
+        seed: Option<u64>,
+    ) -> Result<BootstrapBands, Box<dyn Error>> {
+        let returns = returns.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let benchmark = benchmark_returns.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let n = returns.len();
+        if n != benchmark.len() {
+            return Err("Returns and benchmark returns have different lengths".into());
+        }
+        if n < 2 || samples == 0 {
+            return Err("Not enough returns or samples to bootstrap the performance statistics".into());
+        }
+        let block_size = block_size.unwrap_or_else(|| (n as f64).cbrt().round() as usize).clamp(1, n);
+        let blocks = n.div_ceil(block_size);
+
+        let base_seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let sample = |i: usize| -> Option<Vec<f64>> {
+            let mut rng = StdRng::seed_from_u64(base_seed.wrapping_add(i as u64));
+            let indices = (0..blocks)
+                .flat_map(|_| {
+                    let start = rng.gen_range(0..n);
+                    (0..block_size).map(move |k| (start + k) % n)
+                })
+                .take(n)
+                .collect::<Vec<usize>>();
+            let stats = PerformanceStats::compute_stats(
+                Series::new("returns", indices.iter().map(|i| returns[*i]).collect::<Vec<f64>>()),
+                Series::new("benchmark", indices.iter().map(|i| benchmark[*i]).collect::<Vec<f64>>()),
+                risk_free_rate, confidence_level, interval, calendar, return_type).ok()?;
+            Some(stats.values())
+        };
+
+        #[cfg(feature = "fast_math")]
+        let results = {
+            use rayon::prelude::*;
+            (0..samples).into_par_iter().filter_map(sample).collect::<Vec<Vec<f64>>>()
+        };
+        #[cfg(not(feature = "fast_math"))]
+        let results = (0..samples).filter_map(sample).collect::<Vec<Vec<f64>>>();
+
+        // Percentiles of each statistic across the resamples, ignoring undefined values (e.g. a zero drawdown)
+        let tail = (1.0 - confidence_level) / 2.0;
+        let fields = PerformanceStats::default().values().len();
+        let (lower, upper): (Vec<f64>, Vec<f64>) = (0..fields)
+            .map(|field| {
+                let mut values = results.iter()
+                    .map(|x| x[field])
+                    .filter(|x| x.is_finite())
+                    .collect::<Vec<f64>>();
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                let percentile = |q: f64| match values.len() {
+                    0 => f64::NAN,
+                    len => values[((len - 1) as f64 * q).round() as usize],
+                };
+                (percentile(tail), percentile(1.0 - tail))
+            })
+            .unzip();
+
+        Ok(BootstrapBands {
+            samples,
+            block_size,
+            confidence_level,
+            lower: PerformanceStats::from_values(&lower),
+            upper: PerformanceStats::from_values(&upper),
+        })
+    }
+}
+
+/// Minimum number of return observations for the significance tests to be reliable
+pub const MIN_SIGNIFICANCE_OBSERVATIONS: usize = 60;
+
+/// Standard errors, confidence intervals and significance tests of the Sharpe ratio and alpha
+///
+/// The Sharpe ratio is the mean excess return over its standard deviation, annualized with Lo's (2002)
+/// autocorrelation adjustment, so it can differ from the compounded Sharpe ratio of `PerformanceStats`.
+/// Its standard error accounts for the skewness and kurtosis of the returns (Mertens, 2002). The alpha is
+/// the per period intercept of the regression on the benchmark returns with Newey-West (HAC) standard errors.
+/// P-values test a Sharpe ratio above zero (one-sided) and a non-zero alpha (two-sided).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignificanceStats {
+    pub observations: usize,
+    pub lags: usize,
+    pub confidence_level: f64,
+    pub sharpe_ratio: f64,
+    pub sharpe_standard_error: f64,
+    pub sharpe_lower: f64,
+    pub sharpe_upper: f64,
+    pub sharpe_p_value: f64,
+    pub alpha: f64,
+    pub alpha_standard_error: f64,
+    pub alpha_lower: f64,
+    pub alpha_upper: f64,
+    pub alpha_t_stat: f64,
+    pub alpha_p_value: f64,
+    pub minimum_track_record: f64,
+    pub sufficient_sample: bool,
+}
+
+impl SignificanceStats {
+    /// Computes the significance statistics of a series of security returns
+    ///
+    /// # Arguments
+    ///
+    /// * `returns` - Polars Series of security returns (in percent)
+    /// * `benchmark_returns` - Polars Series of benchmark returns (in percent)
+    /// * `risk_free_rate` - Risk-free rate of return in decimal (e.g 0.02 for 2%)
+    /// * `confidence_level` - Confidence level of the intervals in decimal (e.g. 0.95 for 95%)
+    /// * `interval` - Time interval of the returns (e.g. Interval::OneDay)
+    /// * `calendar` - Trading calendar used for annualization (e.g. MarketCalendar::Crypto for 365 days)
+    ///
+    /// # Returns
+    ///
+    /// * `SignificanceStats` struct, `sufficient_sample` is false with fewer than `MIN_SIGNIFICANCE_OBSERVATIONS`
+    ///   observations or fewer than the minimum track record length
+    pub fn compute(
+        returns: &Series,
+        benchmark_returns: &Series,
+        risk_free_rate: f64,
+        confidence_level: f64,
+        interval: Interval,
+        calendar: MarketCalendar,
+    ) -> Result<SignificanceStats, Box<dyn Error>> {
+        let returns = returns.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let benchmark = benchmark_returns.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        if returns.len() != benchmark.len() {
+            return Err("Returns and benchmark returns have different lengths".into());
+        }
+        let n = returns.len();
+        if n < 3 {
+            return Err(format!("Not enough observations ({}) to test the significance", n).into());
+        }
+        let periods_per_year = calendar.trading_days() / calendar.interval_days(interval);
+        let period_risk_free_rate = risk_free_rate * 100.0 / periods_per_year;
+        let lags = newey_west_lags(n);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let z = normal.inverse_cdf(0.5 + confidence_level / 2.0);
+
+        let sharpe = period_sharpe_ratio(&returns, period_risk_free_rate);
+        let (skew, kurt) = (skewness(&returns), kurtosis(&returns));
+        let sharpe_variance = (1.0 - skew * sharpe + (kurt - 1.0) / 4.0 * sharpe.powi(2)).max(0.0);
+        let sharpe_standard_error = (sharpe_variance / (n - 1) as f64).sqrt();
+        let eta = lo_annualization_factor(&returns, periods_per_year, lags);
+        let sharpe_p_value = 1.0 - probabilistic_sharpe_ratio(sharpe, 0.0, n, skew, kurt);
+        let minimum_track_record = if sharpe > 0.0 {
+            1.0 + sharpe_variance * (normal.inverse_cdf(confidence_level) / sharpe).powi(2)
+        } else {
+            f64::INFINITY
+        };
+
+        let (alpha, _, alpha_standard_error, _) = newey_west_regression(&returns, &benchmark, lags);
+        let alpha_t_stat = alpha / alpha_standard_error;
+        let alpha_p_value = 2.0 * (1.0 - normal.cdf(alpha_t_stat.abs()));
+
+        Ok(SignificanceStats {
+            observations: n,
+            lags,
+            confidence_level,
+            sharpe_ratio: eta * sharpe,
+            sharpe_standard_error: eta * sharpe_standard_error,
+            sharpe_lower: eta * (sharpe - z * sharpe_standard_error),
+            sharpe_upper: eta * (sharpe + z * sharpe_standard_error),
+            sharpe_p_value,
+            alpha,
+            alpha_standard_error,
+            alpha_lower: alpha - z * alpha_standard_error,
+            alpha_upper: alpha + z * alpha_standard_error,
+            alpha_t_stat,
+            alpha_p_value,
+            minimum_track_record,
+            sufficient_sample: n >= MIN_SIGNIFICANCE_OBSERVATIONS && n as f64 >= minimum_track_record,
+        })
+    }
 }
 
 /// computes the standard deviation of a series of security returns
@@ -203,6 +550,306 @@ pub fn covariance_matrix(df: &DataFrame) -> Result<ndarray::Array2<f64>, Box<dyn
 }
 
 
+/// Covariance matrix estimators used in portfolio optimization
+///
+/// Sample: Sample covariance of the asset returns
+/// LedoitWolf: Ledoit-Wolf shrinkage of the sample covariance towards a scaled identity matrix
+/// Ewma: Exponentially weighted covariance with the given decay factor (e.g. 0.94)
+/// SemiCovariance: Covariance of the downside (negative) returns only
+#[derive(Debug, Clone, Copy)]
+pub enum CovarianceEstimator {
+    Sample,
+    LedoitWolf,
+    Ewma(f64),
+    SemiCovariance,
+}
+
+impl CovarianceEstimator {
+    pub fn from_str(s: &str) -> CovarianceEstimator {
+        match s {
+            "sample" => CovarianceEstimator::Sample,
+            "ledoit_wolf" => CovarianceEstimator::LedoitWolf,
+            "ewma" => CovarianceEstimator::Ewma(0.94),
+            "semi_covariance" => CovarianceEstimator::SemiCovariance,
+            _ => CovarianceEstimator::Sample,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            CovarianceEstimator::Sample => "Sample Covariance".to_string(),
+            CovarianceEstimator::LedoitWolf => "Ledoit-Wolf Shrinkage".to_string(),
+            CovarianceEstimator::Ewma(lambda) => format!("EWMA Covariance (λ = {})", lambda),
+            CovarianceEstimator::SemiCovariance => "Semi-Covariance".to_string(),
+        }
+    }
+
+    /// Estimates the covariance matrix of a polars dataframe of security returns
+    ///
+    /// # Arguments
+    ///
+    /// * `df` - Polars DataFrame of security returns
+    ///
+    /// # Returns
+    ///
+    /// * `ndarray::Array2<f64>` - Covariance matrix
+    pub fn estimate(&self, df: &DataFrame) -> Result<ndarray::Array2<f64>, Box<dyn Error>> {
+        match self {
+            CovarianceEstimator::Sample => covariance_matrix(df),
+            CovarianceEstimator::LedoitWolf => ledoit_wolf_covariance(df),
+            CovarianceEstimator::Ewma(lambda) => ewma_covariance(df, *lambda),
+            CovarianceEstimator::SemiCovariance => semi_covariance(df),
+        }
+    }
+}
+
+/// Converts a polars dataframe of security returns into an observations x assets matrix
+fn returns_array(df: &DataFrame) -> Result<ndarray::Array2<f64>, Box<dyn Error>> {
+    let mut array = ndarray::Array2::zeros((df.height(), df.width()));
+    for j in 0..df.width() {
+        let series = df.select_at_idx(j).unwrap().f64()?.to_vec();
+        for (i, x) in series.iter().enumerate() {
+            array[(i, j)] = x.unwrap_or(0.0);
+        }
+    }
+    Ok(array)
+}
+
+/// Computes the Ledoit-Wolf shrinkage covariance matrix of a polars dataframe of security returns
+///
+/// # Arguments
+///
+/// * `df` - Polars DataFrame of security returns
+///
+/// # Returns
+///
+/// * `ndarray::Array2<f64>` - Covariance matrix shrunk towards a scaled identity matrix
+pub fn ledoit_wolf_covariance(df: &DataFrame) -> Result<ndarray::Array2<f64>, Box<dyn Error>> {
+    let returns = returns_array(df)?;
+    let (t, n) = returns.dim();
+    if t == 0 || n == 0 {
+        return Err("Not enough observations to estimate the covariance matrix".into());
+    }
+    let means = returns.mean_axis(ndarray::Axis(0)).unwrap();
+    let centered = &returns - &means;
+    let sample = centered.t().dot(&centered) / t as f64;
+
+    // Shrinkage target: identity scaled by the average variance
+    let mu = sample.diag().sum() / n as f64;
+    let target = ndarray::Array2::<f64>::eye(n) * mu;
+
+    // Optimal shrinkage intensity
+    let d2 = (&sample - &target).mapv(|x| x * x).sum();
+    let mut b2 = 0.0;
+    for row in centered.outer_iter() {
+        let x = row.to_owned().insert_axis(ndarray::Axis(1));
+        let outer = x.dot(&x.t());
+        b2 += (&outer - &sample).mapv(|x| x * x).sum();
+    }
+    let b2 = (b2 / (t as f64 * t as f64)).min(d2);
+    let shrinkage = if d2 > 0.0 { b2 / d2 } else { 0.0 };
+
+    Ok(target * shrinkage + sample * (1.0 - shrinkage))
+}
+
+/// Computes the exponentially weighted covariance matrix of a polars dataframe of security returns
+///
+/// # Arguments
+///
+/// * `df` - Polars DataFrame of security returns
+/// * `lambda` - Decay factor in decimal (e.g. 0.94), more recent observations receive higher weights
+///
+/// # Returns
+///
+/// * `ndarray::Array2<f64>` - Covariance matrix
+pub fn ewma_covariance(df: &DataFrame, lambda: f64) -> Result<ndarray::Array2<f64>, Box<dyn Error>> {
+    let returns = returns_array(df)?;
+    let (t, n) = returns.dim();
+    if t == 0 || n == 0 {
+        return Err("Not enough observations to estimate the covariance matrix".into());
+    }
+    let weights = (0..t).map(|i| lambda.powi((t - 1 - i) as i32)).collect::<Vec<f64>>();
+    let weight_sum: f64 = weights.iter().sum();
+    let weights = ndarray::Array1::from(weights.iter().map(|w| w / weight_sum).collect::<Vec<f64>>());
+
+    let means = weights.dot(&returns);
+    let centered = &returns - &means;
+    let weighted = &centered * &weights.clone().insert_axis(ndarray::Axis(1));
+    Ok(weighted.t().dot(&centered))
+}
+
+/// Computes the semi-covariance matrix (downside returns only) of a polars dataframe of security returns
+///
+/// # Arguments
+///
+/// * `df` - Polars DataFrame of security returns
+///
+/// # Returns
+///
+/// * `ndarray::Array2<f64>` - Semi-covariance matrix
+pub fn semi_covariance(df: &DataFrame) -> Result<ndarray::Array2<f64>, Box<dyn Error>> {
+    let returns = returns_array(df)?;
+    let (t, n) = returns.dim();
+    if t == 0 || n == 0 {
+        return Err("Not enough observations to estimate the covariance matrix".into());
+    }
+    let downside = returns.mapv(|x| x.min(0.0));
+    Ok(downside.t().dot(&downside) / t as f64)
+}
+
+/// Expected return models used in portfolio optimization
+///
+/// HistoricalMean: Mean of the historical asset returns
+/// Capm: Risk-free rate plus the asset beta times the benchmark excess return
+/// Momentum: 12-1 momentum, the return over the trailing 12 months excluding the most recent month
+/// AnalystTarget: Upside implied by the mean analyst price target, assumed to be realized over one year
+#[derive(Debug, Clone, Copy)]
+pub enum ExpectedReturnModel {
+    HistoricalMean,
+    Capm,
+    Momentum,
+    AnalystTarget,
+}
+
+impl ExpectedReturnModel {
+    pub fn from_str(s: &str) -> ExpectedReturnModel {
+        match s {
+            "historical_mean" => ExpectedReturnModel::HistoricalMean,
+            "capm" => ExpectedReturnModel::Capm,
+            "momentum" => ExpectedReturnModel::Momentum,
+            "analyst_target" => ExpectedReturnModel::AnalystTarget,
+            _ => ExpectedReturnModel::HistoricalMean,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            ExpectedReturnModel::HistoricalMean => "Historical Mean".to_string(),
+            ExpectedReturnModel::Capm => "CAPM".to_string(),
+            ExpectedReturnModel::Momentum => "12-1 Momentum".to_string(),
+            ExpectedReturnModel::AnalystTarget => "Analyst Price Target".to_string(),
+        }
+    }
+}
+
+/// Computes the mean returns of a polars dataframe of security returns
+///
+/// # Arguments
+///
+/// * `df` - Polars DataFrame of security returns
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Mean return of each security
+pub fn historical_mean_returns(df: &DataFrame) -> Result<Vec<f64>, Box<dyn Error>> {
+    let mut mean_returns = Vec::new();
+    for col in df.get_columns() {
+        mean_returns.push(col.f64()?.mean().ok_or("Error calculating mean return")?);
+    }
+    Ok(mean_returns)
+}
+
+/// Computes the CAPM expected returns of a polars dataframe of security returns
+///
+/// The beta of each security is estimated on the periods where both the security and the benchmark have
+/// a return, a constant benchmark (e.g. a stablecoin) gives a beta of 0
+///
+/// # Arguments
+///
+/// * `df` - Polars DataFrame of security returns
+/// * `benchmark_returns` - Polars Series of benchmark returns
+/// * `risk_free_rate` - Annual risk-free rate of return in decimal (e.g 0.02 for 2%)
+/// * `interval` - Time interval of the returns (e.g. Interval::OneDay)
+/// * `calendar` - Trading calendar of the returns (e.g. MarketCalendar::Equity)
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Expected return of each security per interval
+pub fn capm_expected_returns(
+    df: &DataFrame,
+    benchmark_returns: &Series,
+    risk_free_rate: f64,
+    interval: Interval,
+    calendar: MarketCalendar,
+) -> Result<Vec<f64>, Box<dyn Error>> {
+    let periods = calendar.trading_days() / calendar.interval_days(interval);
+    let rf = ((1.0 + risk_free_rate).powf(1.0 / periods) - 1.0) * 100.0;
+    let benchmark_mean = benchmark_returns.mean().ok_or("Error calculating benchmark mean return")?;
+    let mut expected_returns = Vec::new();
+    for col in df.get_columns() {
+        let pairs = benchmark_returns.f64()?.into_iter().zip(col.f64()?)
+            .filter_map(|(b, r)| match (b, r) {
+                (Some(b), Some(r)) if b.is_finite() && r.is_finite() => Some((b, r)),
+                _ => None,
+            })
+            .collect::<Vec<(f64, f64)>>();
+        if pairs.len() < 2 {
+            return Err(format!("Not enough returns overlapping with the benchmark to estimate the beta of {}", col.name()).into());
+        }
+        // OLS slope of the security returns on the benchmark returns
+        let n = pairs.len() as f64;
+        let mean_b = pairs.iter().map(|x| x.0).sum::<f64>() / n;
+        let mean_r = pairs.iter().map(|x| x.1).sum::<f64>() / n;
+        let covariance = pairs.iter().map(|(b, r)| (b - mean_b) * (r - mean_r)).sum::<f64>();
+        let variance = pairs.iter().map(|(b, _)| (b - mean_b).powi(2)).sum::<f64>();
+        let beta = if variance > f64::EPSILON { covariance / variance } else { 0.0 };
+        expected_returns.push(rf + beta * (benchmark_mean - rf));
+    }
+    Ok(expected_returns)
+}
+
+/// Computes the 12-1 momentum expected returns of a polars dataframe of security returns
+///
+/// The compounded return over the trailing 12 months, excluding the most recent month,
+/// is converted back to an average return per interval
+///
+/// # Arguments
+///
+/// * `df` - Polars DataFrame of security returns
+/// * `interval` - Time interval of the returns (e.g. Interval::OneDay)
+/// * `calendar` - Trading calendar of the returns (e.g. MarketCalendar::Equity)
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Expected return of each security per interval
+pub fn momentum_expected_returns(df: &DataFrame, interval: Interval, calendar: MarketCalendar) -> Result<Vec<f64>, Box<dyn Error>> {
+    let days = calendar.interval_days(interval);
+    let lookback = (calendar.trading_days() / days).round().max(1.0) as usize;
+    let skip = (calendar.trading_days() / 12.0 / days).round() as usize;
+    let height = df.height();
+    if height <= skip {
+        return historical_mean_returns(df);
+    }
+    let end = height - skip;
+    let start = end.saturating_sub(lookback);
+    let mut expected_returns = Vec::new();
+    for col in df.get_columns() {
+        let returns = col.f64()?.to_vec()[start..end].iter()
+            .map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+        let growth = returns.iter().fold(1.0, |acc, r| acc * (1.0 + r / 100.0));
+        expected_returns.push((growth.powf(1.0 / returns.len() as f64) - 1.0) * 100.0);
+    }
+    Ok(expected_returns)
+}
+
+/// Converts analyst price target upsides into expected returns
+///
+/// # Arguments
+///
+/// * `upsides` - Implied upside of each security in decimal (e.g. 0.15 for 15%), assumed to be realized over one year
+/// * `interval` - Time interval of the returns (e.g. Interval::OneDay)
+/// * `calendar` - Trading calendar of the returns (e.g. MarketCalendar::Equity)
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Expected return of each security per interval
+pub fn analyst_target_expected_returns(upsides: &[f64], interval: Interval, calendar: MarketCalendar) -> Vec<f64> {
+    let periods = calendar.trading_days() / calendar.interval_days(interval);
+    upsides.iter()
+        .map(|u| ((1.0 + u).max(f64::EPSILON).powf(1.0 / periods) - 1.0) * 100.0)
+        .collect::<Vec<f64>>()
+}
+
 /// Computes the correlation matrix of a polars dataframe of security returns
 ///
 /// # Arguments
@@ -261,29 +908,17 @@ pub fn maximum_drawdown(returns: &Series) -> (Vec<f64>, f64) {
         .map(|x| x.unwrap())
         .collect::<Vec<f64>>();
 
-    // Step 1: Calculate cumulative returns
-    let mut cumulative_returns = Vec::with_capacity(returns.len());
+    // Track the cumulative return, its running peak and the drawdown from the peak in a single pass
+    let mut rolling_drawdowns = Vec::with_capacity(returns.len());
+    let mut max_drawdown = 0.0;
     let mut cumulative_sum = 0.0;
+    let mut current_max = f64::NEG_INFINITY;
     for &return_value in &returns {
         cumulative_sum += return_value;
-        cumulative_returns.push(cumulative_sum);
-    }
-
-    // Step 2: Calculate cumulative maximum of cumulative returns
-    let mut cumulative_max = Vec::with_capacity(cumulative_returns.len());
-    let mut current_max = cumulative_returns[0];
-    for &cum_return in &cumulative_returns {
-        if cum_return > current_max {
-            current_max = cum_return;
+        if cumulative_sum > current_max {
+            current_max = cumulative_sum;
         }
-        cumulative_max.push(current_max);
-    }
-
-    // Step 3: Calculate drawdowns
-    let mut rolling_drawdowns = Vec::new();
-    let mut max_drawdown = 0.0;
-    for (cum_max, cum_return) in cumulative_max.iter().zip(cumulative_returns.iter()) {
-        let drawdown = cum_max - cum_return; // Difference between the peak and current value
+        let drawdown = current_max - cumulative_sum; // Difference between the peak and current value
         rolling_drawdowns.push(-drawdown); // Negative drawdown for underwater plot
         if drawdown > max_drawdown {
             max_drawdown = drawdown; // Update maximum drawdown
@@ -306,12 +941,8 @@ pub fn maximum_drawdown(returns: &Series) -> (Vec<f64>, f64) {
 ///
 /// * `f64` - Value at risk
 pub fn value_at_risk(returns: &Series, confidence_level: f64) -> f64 {
-    let returns = returns.f64().unwrap().to_vec().iter().map(|x| x.unwrap()).collect::<Vec<f64>>();
-    let mut sorted_returns = returns.clone();
-    sorted_returns.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-    let index = ((1.0 - confidence_level) * (returns.len() as f64 - 1.0)) as usize;
-    let var = sorted_returns[index];
-    var
+    let mut returns = returns.f64().unwrap().to_vec().iter().map(|x| x.unwrap()).collect::<Vec<f64>>();
+    var_es(&mut returns, confidence_level).0
 }
 
 /// computes the expected shortfall of a series of security returns
@@ -325,11 +956,78 @@ pub fn value_at_risk(returns: &Series, confidence_level: f64) -> f64 {
 ///
 /// * `f64` - Expected shortfall
 pub fn expected_shortfall(returns: &Series, confidence_level: f64) -> f64 {
-    let var = value_at_risk(returns, confidence_level);
-    let returns = returns.f64().unwrap().to_vec().iter().map(|x| x.unwrap()).collect::<Vec<f64>>();
-    let loss_returns = returns.iter().filter(|&x| x < &var).map(|x| x.clone()).collect::<Vec<f64>>();
-    let es = loss_returns.iter().sum::<f64>() / (loss_returns.len() as f64);
-    es
+    let mut returns = returns.f64().unwrap().to_vec().iter().map(|x| x.unwrap()).collect::<Vec<f64>>();
+    var_es(&mut returns, confidence_level).1
+}
+
+/// Computes the historical value at risk and expected shortfall of a slice of returns
+///
+/// Uses a linear-time selection of the VaR quantile instead of a full sort, the slice is reordered in place.
+///
+/// # Arguments
+///
+/// * `returns` - Slice of security returns
+/// * `confidence_level` - Confidence level in decimal (e.g. 0.95 for 95%)
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Value at risk and expected shortfall
+pub fn var_es(returns: &mut [f64], confidence_level: f64) -> (f64, f64) {
+    if returns.is_empty() {
+        return (f64::NAN, f64::NAN);
+    }
+    let index = ((1.0 - confidence_level) * (returns.len() as f64 - 1.0)) as usize;
+    let (losses, var, _) = returns.select_nth_unstable_by(index, |a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let var = *var;
+    // Only returns strictly below the VaR count as tail losses (ties with the VaR are excluded)
+    let (sum, count) = losses.iter().filter(|&&x| x < var).fold((0.0, 0usize), |(s, c), x| (s + x, c + 1));
+    let es = sum / count as f64;
+    (var, es)
+}
+
+/// Computes the bootstrap value at risk and expected shortfall of a series of security returns
+///
+/// Each bootstrap sample resamples the returns with replacement and the VaR and expected shortfall
+/// are averaged over the samples. Samples are computed in parallel when the `fast_math` feature is enabled,
+/// with per-sample seeds so results are identical with and without the feature.
+///
+/// # Arguments
+///
+/// * `returns` - Polars Series of security returns
+/// * `confidence_level` - Confidence level in decimal (e.g. 0.95 for 95%)
+/// * `samples` - Number of bootstrap samples (e.g. 10000)
+/// * `seed` - Optional random seed for reproducible results
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Bootstrap value at risk and expected shortfall
+pub fn bootstrap_var_es(returns: &Series, confidence_level: f64, samples: usize, seed: Option<u64>) -> (f64, f64) {
+    let returns = returns.f64().unwrap().into_no_null_iter().collect::<Vec<f64>>();
+    if returns.is_empty() || samples == 0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let base_seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let sample = |i: usize| -> (f64, f64) {
+        let mut rng = StdRng::seed_from_u64(base_seed.wrapping_add(i as u64));
+        let mut resampled = (0..returns.len())
+            .map(|_| returns[rng.gen_range(0..returns.len())])
+            .collect::<Vec<f64>>();
+        var_es(&mut resampled, confidence_level)
+    };
+
+    #[cfg(feature = "fast_math")]
+    let results = {
+        use rayon::prelude::*;
+        (0..samples).into_par_iter().map(sample).collect::<Vec<(f64, f64)>>()
+    };
+    #[cfg(not(feature = "fast_math"))]
+    let results = (0..samples).map(sample).collect::<Vec<(f64, f64)>>();
+
+    let valid = results.iter().filter(|x| x.0.is_finite() && x.1.is_finite()).collect::<Vec<_>>();
+    let n = valid.len() as f64;
+    let var = valid.iter().map(|x| x.0).sum::<f64>() / n;
+    let es = valid.iter().map(|x| x.1).sum::<f64>() / n;
+    (var, es)
 }
 
 /// Generates random weights for a portfolio
@@ -337,12 +1035,16 @@ pub fn expected_shortfall(returns: &Series, confidence_level: f64) -> f64 {
 /// # Arguments
 ///
 /// * `num_assets` - Number of assets in the portfolio
+/// * `seed` - Optional random seed for reproducible weights
 ///
 /// # Returns
 ///
 /// * `Vec<f64>` - Vector of random weights
-pub fn rand_weights(num_assets: usize) -> Vec<f64> {
-    let mut rng = rand::thread_rng();
+pub fn rand_weights(num_assets: usize, seed: Option<u64>) -> Vec<f64> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
     let weights = (0..num_assets)
         .map(|_| rng.gen_range(0.0..1.0))
         .collect::<Vec<f64>>();
@@ -536,3 +1238,398 @@ pub fn linear_interpolation(vec: Vec<f64>) -> Vec<f64> {
 
 
 
+
+/// Computes the sample skewness of a series of returns
+///
+/// # Arguments
+///
+/// * `returns` - Slice of returns
+///
+/// # Returns
+///
+/// * `f64` - Skewness (0.0 for a normal distribution)
+pub fn skewness(returns: &[f64]) -> f64 {
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let m2 = returns.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    let m3 = returns.iter().map(|x| (x - mean).powi(3)).sum::<f64>() / n;
+    if m2 <= f64::EPSILON { 0.0 } else { m3 / m2.powf(1.5) }
+}
+
+/// Computes the sample kurtosis (not excess kurtosis) of a series of returns
+///
+/// # Arguments
+///
+/// * `returns` - Slice of returns
+///
+/// # Returns
+///
+/// * `f64` - Kurtosis (3.0 for a normal distribution)
+pub fn kurtosis(returns: &[f64]) -> f64 {
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let m2 = returns.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    let m4 = returns.iter().map(|x| (x - mean).powi(4)).sum::<f64>() / n;
+    if m2 <= f64::EPSILON { 3.0 } else { m4 / m2.powi(2) }
+}
+
+/// Computes the probabilistic Sharpe ratio, the probability that the true Sharpe ratio exceeds a benchmark
+/// Sharpe ratio given the sample length, skewness and kurtosis of the returns (Bailey & López de Prado, 2012)
+///
+/// # Arguments
+///
+/// * `sharpe_ratio` - Observed Sharpe ratio per period (not annualized)
+/// * `benchmark_sharpe` - Benchmark Sharpe ratio per period
+/// * `observations` - Number of return observations
+/// * `skewness` - Skewness of the returns
+/// * `kurtosis` - Kurtosis of the returns (3.0 for normal returns)
+///
+/// # Returns
+///
+/// * `f64` - Probability in decimal
+pub fn probabilistic_sharpe_ratio(sharpe_ratio: f64, benchmark_sharpe: f64, observations: usize, skewness: f64, kurtosis: f64) -> f64 {
+    if observations < 2 {
+        return f64::NAN;
+    }
+    let variance = 1.0 - skewness * sharpe_ratio + (kurtosis - 1.0) / 4.0 * sharpe_ratio.powi(2);
+    if variance <= 0.0 {
+        return f64::NAN;
+    }
+    let z = (sharpe_ratio - benchmark_sharpe) * ((observations - 1) as f64).sqrt() / variance.sqrt();
+    Normal::new(0.0, 1.0).unwrap().cdf(z)
+}
+
+/// Computes the expected maximum Sharpe ratio of a number of independent trials with zero true Sharpe ratio
+///
+/// # Arguments
+///
+/// * `trials` - Number of strategy variants tried
+/// * `sharpe_variance` - Variance of the Sharpe ratios (per period) across the trials
+///
+/// # Returns
+///
+/// * `f64` - Expected maximum Sharpe ratio per period
+pub fn expected_maximum_sharpe(trials: usize, sharpe_variance: f64) -> f64 {
+    if trials < 2 {
+        return 0.0;
+    }
+    let euler_mascheroni = 0.5772156649;
+    let n = trials as f64;
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    sharpe_variance.max(0.0).sqrt() * ((1.0 - euler_mascheroni) * normal.inverse_cdf(1.0 - 1.0 / n)
+        + euler_mascheroni * normal.inverse_cdf(1.0 - 1.0 / (n * std::f64::consts::E)))
+}
+
+/// Computes the deflated Sharpe ratio, the probabilistic Sharpe ratio against the Sharpe ratio expected
+/// from the best of the trials by chance alone, which corrects for selection bias in parameter searches
+/// (Bailey & López de Prado, 2014)
+///
+/// # Arguments
+///
+/// * `sharpe_ratio` - Observed Sharpe ratio per period of the selected variant
+/// * `sharpe_variance` - Variance of the Sharpe ratios (per period) across the trials
+/// * `trials` - Number of strategy variants tried
+/// * `observations` - Number of return observations
+/// * `skewness` - Skewness of the returns of the selected variant
+/// * `kurtosis` - Kurtosis of the returns of the selected variant
+///
+/// # Returns
+///
+/// * `f64` - Probability in decimal that the true Sharpe ratio is positive after the selection
+pub fn deflated_sharpe_ratio(sharpe_ratio: f64, sharpe_variance: f64, trials: usize, observations: usize, skewness: f64, kurtosis: f64) -> f64 {
+    let benchmark_sharpe = expected_maximum_sharpe(trials, sharpe_variance);
+    probabilistic_sharpe_ratio(sharpe_ratio, benchmark_sharpe, observations, skewness, kurtosis)
+}
+
+/// Computes the Sharpe ratio per period (not annualized) of a series of returns
+///
+/// # Arguments
+///
+/// * `returns` - Slice of returns
+/// * `risk_free_rate` - Risk-free return per period, in the same units as the returns
+///
+/// # Returns
+///
+/// * `f64` - Sharpe ratio per period (NaN when the returns have no dispersion)
+pub fn period_sharpe_ratio(returns: &[f64], risk_free_rate: f64) -> f64 {
+    let n = returns.len() as f64;
+    if n < 2.0 {
+        return f64::NAN;
+    }
+    let mean = returns.iter().map(|x| x - risk_free_rate).sum::<f64>() / n;
+    let std = (returns.iter().map(|x| (x - risk_free_rate - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    if std > f64::EPSILON { mean / std } else { f64::NAN }
+}
+
+/// Computes the probability of backtest overfitting with combinatorially symmetric cross-validation
+/// (Bailey, Borwein, López de Prado & Zhu, 2015)
+///
+/// The periods are divided into `blocks` blocks. For every way of choosing half of the blocks as the in-sample set,
+/// the trial with the best in-sample Sharpe ratio is ranked against all the trials out-of-sample on the other half.
+/// The probability of backtest overfitting is the fraction of the splits where the in-sample winner ranks
+/// at or below the out-of-sample median.
+///
+/// # Arguments
+///
+/// * `returns` - Returns of each trial (strategy variant), all over the same periods
+/// * `blocks` - Even number of blocks the periods are divided into (e.g. 16)
+///
+/// # Returns
+///
+/// * `(f64, Vec<f64>)` - Probability of backtest overfitting in decimal and the logit of the out-of-sample
+///   relative rank of the in-sample winner for each split
+pub fn probability_of_backtest_overfitting(returns: &[Vec<f64>], blocks: usize) -> Result<(f64, Vec<f64>), Box<dyn Error>> {
+    if returns.len() < 2 {
+        return Err("At least two trials are needed to estimate the probability of backtest overfitting".into());
+    }
+    if blocks < 2 || !blocks.is_multiple_of(2) || blocks > 24 {
+        return Err("The number of blocks must be even and between 2 and 24".into());
+    }
+    let periods = returns[0].len();
+    if returns.iter().any(|x| x.len() != periods) {
+        return Err("All the trials must have returns over the same periods".into());
+    }
+    let block_size = periods / blocks;
+    if block_size < 2 {
+        return Err(format!("Not enough periods ({}) for {} blocks", periods, blocks).into());
+    }
+    let block_range = |b: usize| {
+        let start = b * block_size;
+        let end = if b == blocks - 1 { periods } else { start + block_size };
+        start..end
+    };
+    let sharpe = |trial: &[f64], mask: u32, in_sample: bool| {
+        let values = (0..blocks)
+            .filter(|b| (mask >> b & 1 == 1) == in_sample)
+            .flat_map(|b| trial[block_range(b)].iter().copied())
+            .collect::<Vec<f64>>();
+        period_sharpe_ratio(&values, 0.0)
+    };
+
+    let n = returns.len() as f64;
+    let logits = (0u32..1 << blocks)
+        .filter(|mask| mask.count_ones() as usize == blocks / 2)
+        .filter_map(|mask| {
+            let in_sample = returns.iter().map(|x| sharpe(x, mask, true)).collect::<Vec<f64>>();
+            let out_of_sample = returns.iter().map(|x| sharpe(x, mask, false)).collect::<Vec<f64>>();
+            let best = in_sample.iter().enumerate()
+                .filter(|(_, x)| x.is_finite())
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal))?.0;
+            let rank = out_of_sample.iter().filter(|x| **x <= out_of_sample[best]).count() as f64;
+            let relative_rank = rank / (n + 1.0);
+            Some((relative_rank / (1.0 - relative_rank)).ln())
+        })
+        .collect::<Vec<f64>>();
+    if logits.is_empty() {
+        return Err("No split had a valid in-sample Sharpe ratio".into());
+    }
+    let pbo = logits.iter().filter(|x| **x <= 0.0).count() as f64 / logits.len() as f64;
+    Ok((pbo, logits))
+}
+
+/// Computes the autocorrelation of a series of returns at a lag
+///
+/// # Arguments
+///
+/// * `returns` - Slice of returns
+/// * `lag` - Number of periods between the correlated returns
+///
+/// # Returns
+///
+/// * `f64` - Autocorrelation (0.0 when the returns have no dispersion)
+pub fn autocorrelation(returns: &[f64], lag: usize) -> f64 {
+    let n = returns.len();
+    if lag >= n {
+        return 0.0;
+    }
+    let mean = returns.iter().sum::<f64>() / n as f64;
+    let variance = returns.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+    if variance <= f64::EPSILON {
+        return 0.0;
+    }
+    (lag..n).map(|t| (returns[t] - mean) * (returns[t - lag] - mean)).sum::<f64>() / variance
+}
+
+/// Computes the number of lags of the Newey-West estimator with the rule of thumb floor(4 (n / 100)^(2/9))
+pub fn newey_west_lags(observations: usize) -> usize {
+    (4.0 * (observations as f64 / 100.0).powf(2.0 / 9.0)).floor() as usize
+}
+
+/// Computes the factor annualizing a per period Sharpe ratio when the returns are autocorrelated (Lo, 2002)
+///
+/// Autocorrelations beyond `lags` are assumed to be zero, so the factor is the square root of the periods
+/// per year for uncorrelated returns.
+///
+/// # Arguments
+///
+/// * `returns` - Slice of returns
+/// * `periods_per_year` - Number of return periods in a year (e.g. 252 for daily equity returns)
+/// * `lags` - Number of autocorrelation lags
+///
+/// # Returns
+///
+/// * `f64` - Annualization factor
+pub fn lo_annualization_factor(returns: &[f64], periods_per_year: f64, lags: usize) -> f64 {
+    let q = periods_per_year;
+    let correlation_sum = (1..=lags)
+        .filter(|k| (*k as f64) < q)
+        .map(|k| (q - k as f64) * autocorrelation(returns, k))
+        .sum::<f64>();
+    let variance = q + 2.0 * correlation_sum;
+    if variance > 0.0 { q / variance.sqrt() } else { q.sqrt() }
+}
+
+/// Regresses a series of returns on a benchmark with Newey-West heteroskedasticity and autocorrelation
+/// consistent (HAC) standard errors
+///
+/// # Arguments
+///
+/// * `returns` - Slice of security returns (dependent variable)
+/// * `benchmark` - Slice of benchmark returns (regressor)
+/// * `lags` - Number of lags of the Bartlett kernel (e.g. `newey_west_lags(n)`)
+///
+/// # Returns
+///
+/// * `(f64, f64, f64, f64)` - Alpha, beta and their standard errors (NaN for a constant benchmark)
+pub fn newey_west_regression(returns: &[f64], benchmark: &[f64], lags: usize) -> (f64, f64, f64, f64) {
+    let n = returns.len().min(benchmark.len());
+    let nf = n as f64;
+    let x_mean = benchmark[..n].iter().sum::<f64>() / nf;
+    let y_mean = returns[..n].iter().sum::<f64>() / nf;
+    let sxx = benchmark[..n].iter().map(|x| (x - x_mean).powi(2)).sum::<f64>();
+    if n < 3 || sxx <= f64::EPSILON {
+        return (y_mean, 0.0, f64::NAN, f64::NAN);
+    }
+    let beta = (0..n).map(|t| (benchmark[t] - x_mean) * (returns[t] - y_mean)).sum::<f64>() / sxx;
+    let alpha = y_mean - beta * x_mean;
+    let residuals = (0..n).map(|t| returns[t] - alpha - beta * benchmark[t]).collect::<Vec<f64>>();
+
+    // Long-run covariance of the moment conditions [e, e * x] with Bartlett weights
+    let mut s = [[0.0; 2]; 2];
+    for lag in 0..=lags.min(n - 1) {
+        let weight = if lag == 0 { 1.0 } else { 1.0 - lag as f64 / (lags + 1) as f64 };
+        for t in lag..n {
+            let u = [residuals[t], residuals[t] * benchmark[t]];
+            let v = [residuals[t - lag], residuals[t - lag] * benchmark[t - lag]];
+            for i in 0..2 {
+                for j in 0..2 {
+                    let term = if lag == 0 { u[i] * v[j] } else { u[i] * v[j] + v[i] * u[j] };
+                    s[i][j] += weight * term;
+                }
+            }
+        }
+    }
+
+    // Sandwich estimator (X'X)^-1 S (X'X)^-1
+    let sum_x = benchmark[..n].iter().sum::<f64>();
+    let sum_xx = benchmark[..n].iter().map(|x| x * x).sum::<f64>();
+    let det = nf * sum_xx - sum_x * sum_x;
+    let inv = [[sum_xx / det, -sum_x / det], [-sum_x / det, nf / det]];
+    let mut covariance = [[0.0; 2]; 2];
+    for i in 0..2 {
+        for j in 0..2 {
+            covariance[i][j] = (0..2).flat_map(|k| (0..2).map(move |l| (k, l)))
+                .map(|(k, l)| inv[i][k] * s[k][l] * inv[l][j])
+                .sum::<f64>();
+        }
+    }
+    (alpha, beta, covariance[0][0].max(0.0).sqrt(), covariance[1][1].max(0.0).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_matrix_eq(actual: &ndarray::Array2<f64>, expected: [[f64; 2]; 2]) {
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((actual[(i, j)] - expected[i][j]).abs() < 1e-9,
+                        "({}, {}) = {}, expected {}", i, j, actual[(i, j)], expected[i][j]);
+            }
+        }
+    }
+
+    fn two_asset_returns() -> DataFrame {
+        df!(
+            "a" => [1.0, 2.0, 3.0, 4.0],
+            "b" => [2.0, 1.0, 5.0, 3.0],
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_ledoit_wolf_covariance_matches_reference() {
+        // Sample covariance [[1.25, 0.875], [0.875, 2.1875]] shrunk towards 1.71875 * I with intensity 0.664024,
+        // the intensity of the reference (scikit-learn) estimator
+        let cov = ledoit_wolf_covariance(&two_asset_returns()).unwrap();
+        assert_matrix_eq(&cov, [[1.561261149653122, 0.2939791873141725], [0.2939791873141725, 1.876238850346878]]);
+        // Shrinking towards the scaled identity preserves the total variance
+        assert!((cov.diag().sum() - 3.4375).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ewma_covariance_matches_reference() {
+        let df = df!(
+            "a" => [1.0, 2.0, 3.0],
+            "b" => [2.0, 1.0, 5.0],
+        ).unwrap();
+        // Weights 1/7, 2/7 and 4/7 of the observations for a decay factor of 0.5, weighted means 17/7 and 24/7
+        let cov = ewma_covariance(&df, 0.5).unwrap();
+        assert_matrix_eq(&cov, [[26.0 / 49.0, 54.0 / 49.0], [54.0 / 49.0, 166.0 / 49.0]]);
+    }
+
+    #[test]
+    fn test_ewma_covariance_without_decay_is_sample_covariance() {
+        let df = two_asset_returns();
+        let ewma = ewma_covariance(&df, 1.0).unwrap();
+        let sample = covariance_matrix(&df).unwrap();
+        assert_matrix_eq(&ewma, [[sample[(0, 0)], sample[(0, 1)]], [sample[(1, 0)], sample[(1, 1)]]]);
+        assert_matrix_eq(&sample, [[1.25, 0.875], [0.875, 2.1875]]);
+    }
+
+    #[test]
+    fn test_probabilistic_sharpe_ratio_matches_reference() {
+        // Normal returns: z = 0.1 * sqrt(100) / sqrt(1 + 0.1^2 / 2)
+        assert!((probabilistic_sharpe_ratio(0.1, 0.0, 101, 0.0, 3.0) - 0.8407413278).abs() < 1e-6);
+        assert!((probabilistic_sharpe_ratio(0.1, 0.1, 101, 0.0, 3.0) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_deflated_sharpe_ratio_matches_reference() {
+        // Numerical example of Bailey & López de Prado (2014): an annualized Sharpe ratio of 2.5 over 1250 daily
+        // returns with skewness -3 and kurtosis 10, selected from 100 trials with an annualized Sharpe ratio
+        // variance of 0.5, has an expected maximum Sharpe ratio of 0.1132 per period and a DSR of 0.9004
+        let sharpe_ratio = 2.5 / 250f64.sqrt();
+        let sharpe_variance = 0.5 / 250.0;
+        assert!((expected_maximum_sharpe(100, sharpe_variance) - 0.1131720019).abs() < 1e-6);
+        let dsr = deflated_sharpe_ratio(sharpe_ratio, sharpe_variance, 100, 1250, -3.0, 10.0);
+        assert!((dsr - 0.9003968344).abs() < 1e-6, "dsr = {}", dsr);
+    }
+
+    #[test]
+    fn test_probability_of_backtest_overfitting_of_a_dominant_trial() {
+        // The in-sample winner is also the out-of-sample winner of every split, ranked 2 of 2 (logit ln 2)
+        let good = vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0];
+        let bad = good.iter().map(|x| -x).collect::<Vec<f64>>();
+        let (pbo, logits) = probability_of_backtest_overfitting(&[good, bad], 2).unwrap();
+        assert_eq!(pbo, 0.0);
+        assert_eq!(logits.len(), 2);
+        assert!(logits.iter().all(|x| (x - 2f64.ln()).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_probability_of_backtest_overfitting_of_alternating_trials() {
+        // Each trial only performs in one half, so the in-sample winner is the out-of-sample loser (logit -ln 2)
+        let first = vec![1.0, 2.0, 1.0, 2.0, -1.0, -2.0, -1.0, -2.0];
+        let second = first.iter().map(|x| -x).collect::<Vec<f64>>();
+        let (pbo, logits) = probability_of_backtest_overfitting(&[first, second], 2).unwrap();
+        assert_eq!(pbo, 1.0);
+        assert!(logits.iter().all(|x| (x + 2f64.ln()).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_probability_of_backtest_overfitting_validates_blocks() {
+        let returns = vec![vec![1.0; 8], vec![2.0; 8]];
+        assert!(probability_of_backtest_overfitting(&returns, 3).is_err());
+        assert!(probability_of_backtest_overfitting(&returns[..1], 2).is_err());
+    }
+}
@@ -0,0 +1,411 @@
+use std::error::Error;
+use polars::prelude::*;
+
+use crate::analytics::statistics::{deflated_sharpe_ratio, kurtosis, period_sharpe_ratio, probabilistic_sharpe_ratio,
+                                   probability_of_backtest_overfitting, skewness, PerformanceStats};
+use crate::data::config::{Interval, MarketCalendar, ReturnType};
+
+
+/// Performance statistic used to compare and select strategy variants
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StrategyMetric {
+    SharpeRatio,
+    SortinoRatio,
+    CalmarRatio,
+    CumulativeReturn,
+    AnnualizedReturn,
+    MaximumDrawdown,
+}
+
+impl StrategyMetric {
+    pub fn from_str(s: &str) -> StrategyMetric {
+        match s {
+            "sharpe_ratio" => StrategyMetric::SharpeRatio,
+            "sortino_ratio" => StrategyMetric::SortinoRatio,
+            "calmar_ratio" => StrategyMetric::CalmarRatio,
+            "cumulative_return" => StrategyMetric::CumulativeReturn,
+            "annualized_return" => StrategyMetric::AnnualizedReturn,
+            "maximum_drawdown" => StrategyMetric::MaximumDrawdown,
+            _ => StrategyMetric::SharpeRatio,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            StrategyMetric::SharpeRatio => "Sharpe Ratio".to_string(),
+            StrategyMetric::SortinoRatio => "Sortino Ratio".to_string(),
+            StrategyMetric::CalmarRatio => "Calmar Ratio".to_string(),
+            StrategyMetric::CumulativeReturn => "Cumulative Return".to_string(),
+            StrategyMetric::AnnualizedReturn => "Annualized Return".to_string(),
+            StrategyMetric::MaximumDrawdown => "Maximum Drawdown".to_string(),
+        }
+    }
+
+    /// Value of the metric, signed so that higher is always better (drawdowns are negated)
+    pub fn value(&self, stats: &PerformanceStats) -> f64 {
+        match self {
+            StrategyMetric::SharpeRatio => stats.sharpe_ratio,
+            StrategyMetric::SortinoRatio => stats.sortino_ratio,
+            StrategyMetric::CalmarRatio => stats.calmar_ratio,
+            StrategyMetric::CumulativeReturn => stats.cumulative_return,
+            StrategyMetric::AnnualizedReturn => stats.annualized_return,
+            StrategyMetric::MaximumDrawdown => -stats.maximum_drawdown,
+        }
+    }
+}
+
+/// Variant of a trading strategy and its backtested returns
+///
+/// * `name` - Name of the variant (e.g. "SMA 20/50")
+/// * `params` - Parameter values of the variant (e.g. [("fast", 20.0), ("slow", 50.0)])
+/// * `returns` - DataFrame with a timestamp column and one returns column in percent
+#[derive(Debug, Clone)]
+pub struct StrategyVariant {
+    pub name: String,
+    pub params: Vec<(String, f64)>,
+    pub returns: DataFrame,
+}
+
+impl StrategyVariant {
+    pub fn new(name: &str, returns: DataFrame) -> StrategyVariant {
+        StrategyVariant {
+            name: name.to_string(),
+            params: Vec::new(),
+            returns,
+        }
+    }
+
+    pub fn param(mut self, name: &str, value: f64) -> StrategyVariant {
+        self.params.push((name.to_string(), value));
+        self
+    }
+
+    /// Backtests target positions on an asset
+    ///
+    /// The position decided at the close of each period is held over the next period, so the signal
+    /// never uses the return it trades on. Trading costs are charged on each change of position.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variant
+    /// * `asset_returns` - DataFrame with columns timestamp and roc-1 (e.g. from `TickerPerformance::returns`)
+    /// * `positions` - Target position of each period (e.g. 1.0 long, 0.0 flat, -1.0 short)
+    /// * `cost_bps` - Trading cost in basis points of the traded position
+    ///
+    /// # Returns
+    ///
+    /// * `StrategyVariant` struct
+    pub fn from_positions(name: &str, asset_returns: &DataFrame, positions: &[f64], cost_bps: f64) -> Result<StrategyVariant, Box<dyn Error>> {
+        let returns = asset_returns.column("roc-1")?.f64()?.into_iter()
+            .map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+        if returns.len() != positions.len() {
+            return Err(format!("{} positions for {} returns", positions.len(), returns.len()).into());
+        }
+        let df = df!(
+            "timestamp" => asset_returns.column("timestamp")?.clone(),
+            name => position_returns(&returns, positions, cost_bps)
+        )?;
+        Ok(StrategyVariant::new(name, df))
+    }
+
+    /// Value of a parameter of the variant
+    pub fn param_value(&self, name: &str) -> Option<f64> {
+        self.params.iter().find(|(x, _)| x == name).map(|(_, v)| *v)
+    }
+
+    /// Returns of the variant in percent
+    pub fn returns_vec(&self) -> Result<Vec<f64>, Box<dyn Error>> {
+        let series = self.returns.get_columns().iter()
+            .find(|x| x.name() != "timestamp")
+            .ok_or("Strategy returns DataFrame has no returns column")?;
+        Ok(series.cast(&DataType::Float64)?.f64()?.into_iter().map(|x| x.unwrap_or(0.0)).collect())
+    }
+}
+
+/// Computes the returns (%) of holding the previous period's position, net of trading costs
+///
+/// # Arguments
+///
+/// * `asset_returns` - Asset returns in percent
+/// * `positions` - Target position of each period
+/// * `cost_bps` - Trading cost in basis points of the traded position
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Strategy returns in percent, the first period is 0.0
+pub fn position_returns(asset_returns: &[f64], positions: &[f64], cost_bps: f64) -> Vec<f64> {
+    (0..asset_returns.len())
+        .map(|t| {
+            if t == 0 {
+                return 0.0;
+            }
+            let held = positions[t - 1];
+            let previous = if t >= 2 { positions[t - 2] } else { 0.0 };
+            held * asset_returns[t] - (held - previous).abs() * cost_bps / 100.0
+        })
+        .collect()
+}
+
+/// Side-by-side comparison of strategy variants
+///
+/// * `variants` - Strategy variants to compare
+/// * `benchmark` - Optional benchmark DataFrame with a timestamp column and one returns column in percent
+/// * `risk_free_rate` - Risk-free rate in decimal (default 0.02)
+/// * `confidence_level` - Confidence level of the VaR and ES in decimal (default 0.95)
+/// * `interval` - Interval of the returns (default Interval::OneDay)
+/// * `calendar` - Trading calendar used for annualization (default MarketCalendar::Equity)
+/// * `return_type` - Convention of the returns (default ReturnType::Simple)
+#[derive(Debug, Clone)]
+pub struct StrategyComparison {
+    pub variants: Vec<StrategyVariant>,
+    pub benchmark: Option<DataFrame>,
+    pub risk_free_rate: f64,
+    pub confidence_level: f64,
+    pub interval: Interval,
+    pub calendar: MarketCalendar,
+    pub return_type: ReturnType,
+}
+
+impl StrategyComparison {
+    pub fn new(variants: Vec<StrategyVariant>) -> StrategyComparison {
+        StrategyComparison {
+            variants,
+            benchmark: None,
+            risk_free_rate: 0.02,
+            confidence_level: 0.95,
+            interval: Interval::OneDay,
+            calendar: MarketCalendar::Equity,
+            return_type: ReturnType::Simple,
+        }
+    }
+
+    pub fn benchmark(mut self, benchmark: DataFrame) -> StrategyComparison {
+        self.benchmark = Some(benchmark);
+        self
+    }
+
+    pub fn risk_free_rate(mut self, risk_free_rate: f64) -> StrategyComparison {
+        self.risk_free_rate = risk_free_rate;
+        self
+    }
+
+    pub fn confidence_level(mut self, confidence_level: f64) -> StrategyComparison {
+        self.confidence_level = confidence_level;
+        self
+    }
+
+    pub fn interval(mut self, interval: Interval) -> StrategyComparison {
+        self.interval = interval;
+        self
+    }
+
+    pub fn calendar(mut self, calendar: MarketCalendar) -> StrategyComparison {
+        self.calendar = calendar;
+        self
+    }
+
+    pub fn return_type(mut self, return_type: ReturnType) -> StrategyComparison {
+        self.return_type = return_type;
+        self
+    }
+
+    /// Computes the performance statistics of each variant
+    pub fn stats(&self) -> Result<Vec<PerformanceStats>, Box<dyn Error>> {
+        self.variants.iter()
+            .map(|v| PerformanceStats::from_returns(&v.returns, self.benchmark.as_ref(), self.risk_free_rate,
+                                                    self.confidence_level, self.interval, self.calendar, self.return_type))
+            .collect()
+    }
+
+    /// Performance statistics of the variants side by side
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with an Items column and one column per variant
+    pub fn stats_table(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let stats = self.stats()?;
+        let items = ["Daily Return", "Daily Volatility", "Cumulative Return", "Annualized Return", "Annualized Volatility",
+            "Alpha", "Beta", "Sharpe Ratio", "Sortino Ratio", "Active Return", "Active Risk", "Information Ratio",
+            "Calmar Ratio", "Maximum Drawdown", "Value at Risk", "Expected Shortfall"];
+        let mut columns = vec![Series::new("Items", items.to_vec())];
+        for (variant, s) in self.variants.iter().zip(stats.iter()) {
+            let values = vec![
+                format!("{:.2}%", s.daily_return),
+                format!("{:.2}%", s.daily_volatility),
+                format!("{:.2}%", s.cumulative_return),
+                format!("{:.2}%", s.annualized_return),
+                format!("{:.2}%", s.annualized_volatility),
+                format!("{:.2}", s.alpha),
+                format!("{:.2}", s.beta),
+                format!("{:.2}", s.sharpe_ratio),
+                format!("{:.2}", s.sortino_ratio),
+                format!("{:.2}%", s.active_return),
+                format!("{:.2}%", s.active_risk),
+                format!("{:.2}", s.information_ratio),
+                format!("{:.2}", s.calmar_ratio),
+                format!("{:.2}%", s.maximum_drawdown),
+                format!("{:.2}%", s.value_at_risk),
+                format!("{:.2}%", s.expected_shortfall),
+            ];
+            columns.push(Series::new(&variant.name, values));
+        }
+        Ok(DataFrame::new(columns)?)
+    }
+
+    /// Metric of each variant over a two-parameter grid
+    ///
+    /// # Arguments
+    ///
+    /// * `x_param` - Parameter on the x axis (e.g. "fast")
+    /// * `y_param` - Parameter on the y axis (e.g. "slow")
+    /// * `metric` - Performance metric (e.g. StrategyMetric::SharpeRatio)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns x_param, y_param and the metric, for the variants having both parameters
+    pub fn parameter_grid(&self, x_param: &str, y_param: &str, metric: StrategyMetric) -> Result<DataFrame, Box<dyn Error>> {
+        let stats = self.stats()?;
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+        let mut values = Vec::new();
+        for (variant, s) in self.variants.iter().zip(stats.iter()) {
+            if let (Some(x), Some(y)) = (variant.param_value(x_param), variant.param_value(y_param)) {
+                xs.push(x);
+                ys.push(y);
+                values.push(match metric {
+                    StrategyMetric::MaximumDrawdown => s.maximum_drawdown,
+                    _ => metric.value(s),
+                });
+            }
+        }
+        if values.is_empty() {
+            return Err(format!("No variants have both the {} and {} parameters", x_param, y_param).into());
+        }
+        let df = df!(
+            x_param => xs,
+            y_param => ys,
+            &metric.to_string() => values
+        )?;
+        Ok(df)
+    }
+
+    /// Statistical significance of the Sharpe ratio of each variant
+    ///
+    /// The deflated Sharpe ratio treats every variant as a trial of the same search, so it measures
+    /// whether a variant's Sharpe ratio exceeds what the best of that many variants would reach by chance.
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns variant, observations, skewness, kurtosis, sharpe_ratio (per period),
+    ///   probabilistic_sharpe and deflated_sharpe (probabilities in decimal)
+    pub fn sharpe_significance(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let risk_free = self.risk_free_rate * 100.0 * self.calendar.interval_days(self.interval) / self.calendar.trading_days();
+        let returns = self.variants.iter().map(|v| v.returns_vec()).collect::<Result<Vec<Vec<f64>>, Box<dyn Error>>>()?;
+        let sharpes = returns.iter().map(|x| period_sharpe_ratio(x, risk_free)).collect::<Vec<f64>>();
+        let finite = sharpes.iter().filter(|x| x.is_finite()).copied().collect::<Vec<f64>>();
+        let mean = finite.iter().sum::<f64>() / finite.len().max(1) as f64;
+        let sharpe_variance = finite.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (finite.len().max(2) - 1) as f64;
+        let skews = returns.iter().map(|x| skewness(x)).collect::<Vec<f64>>();
+        let kurts = returns.iter().map(|x| kurtosis(x)).collect::<Vec<f64>>();
+
+        let df = df!(
+            "variant" => self.variants.iter().map(|v| v.name.clone()).collect::<Vec<String>>(),
+            "observations" => returns.iter().map(|x| x.len() as u32).collect::<Vec<u32>>(),
+            "skewness" => skews.clone(),
+            "kurtosis" => kurts.clone(),
+            "sharpe_ratio" => sharpes.clone(),
+            "probabilistic_sharpe" => (0..returns.len())
+                .map(|i| probabilistic_sharpe_ratio(sharpes[i], 0.0, returns[i].len(), skews[i], kurts[i]))
+                .collect::<Vec<f64>>(),
+            "deflated_sharpe" => (0..returns.len())
+                .map(|i| deflated_sharpe_ratio(sharpes[i], sharpe_variance, returns.len(), returns[i].len(), skews[i], kurts[i]))
+                .collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+
+    /// Probability of backtest overfitting of selecting the best variant, by combinatorially symmetric cross-validation
+    ///
+    /// # Arguments
+    ///
+    /// * `blocks` - Even number of blocks the periods are divided into (e.g. 16)
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Probability in decimal that the in-sample best variant underperforms the median variant out-of-sample
+    pub fn probability_of_backtest_overfitting(&self, blocks: usize) -> Result<f64, Box<dyn Error>> {
+        let returns = self.variants.iter().map(|v| v.returns_vec()).collect::<Result<Vec<Vec<f64>>, Box<dyn Error>>>()?;
+        let (pbo, _) = probability_of_backtest_overfitting(&returns, blocks)?;
+        Ok(pbo)
+    }
+}
+
+/// Series a trend filter overlay is applied to
+///
+/// * `Equity` - Switches the whole portfolio between risk-on and cash on the trend of its equity curve
+/// * `Constituents` - Switches each asset between risk-on and cash on the trend of its own price
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrendFilterTarget {
+    Equity,
+    Constituents,
+}
+
+impl TrendFilterTarget {
+    pub fn from_str(s: &str) -> Result<TrendFilterTarget, String> {
+        match s {
+            "equity" => Ok(TrendFilterTarget::Equity),
+            "constituents" => Ok(TrendFilterTarget::Constituents),
+            _ => Err(format!("Invalid TrendFilterTarget: {}", s)),
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            TrendFilterTarget::Equity => "equity".to_string(),
+            TrendFilterTarget::Constituents => "constituents".to_string(),
+        }
+    }
+}
+
+/// Moving average trend filter overlay, risk-on while the price is above its moving average and in cash otherwise
+///
+/// * `window` - Number of periods of the simple moving average (e.g. 200)
+/// * `target` - Whether the filter is applied to the portfolio equity or to each constituent
+#[derive(Debug, Clone)]
+pub struct TrendFilter {
+    pub window: usize,
+    pub target: TrendFilterTarget,
+}
+
+impl TrendFilter {
+    pub fn new(window: usize, target: TrendFilterTarget) -> TrendFilter {
+        TrendFilter { window, target }
+    }
+}
+
+/// Risk-on (1.0) / risk-off (0.0) signal of a moving average filter on a price or equity series
+///
+/// The signal of each period only uses prices up to that period, so it can be passed to
+/// `StrategyVariant::from_positions` without look-ahead. Periods before the moving average
+/// is available are risk-on.
+///
+/// # Arguments
+///
+/// * `prices` - Price or equity series
+/// * `window` - Number of periods of the simple moving average (e.g. 200)
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Signal of each period
+pub fn moving_average_filter(prices: &[f64], window: usize) -> Vec<f64> {
+    let mut sum = 0.0;
+    (0..prices.len())
+        .map(|t| {
+            sum += prices[t];
+            if t >= window {
+                sum -= prices[t - window];
+            }
+            if window == 0 || t + 1 < window || prices[t] >= sum / window as f64 { 1.0 } else { 0.0 }
+        })
+        .collect()
+}
@@ -0,0 +1,47 @@
+use std::error::Error;
+use plotly::common::{Mode, Title};
+use plotly::{Layout, Plot, Scatter};
+use plotly::layout::{Axis, AxisType};
+
+use crate::analytics::fixed_income::treasury_yield_curve;
+use crate::charts::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
+
+
+/// Generates a Chart of the current US Treasury Yield Curve
+///
+/// # Arguments
+///
+/// * `height` - `Option<usize>` - Height of the chart
+/// * `width` - `Option<usize>` - Width of the chart
+///
+/// # Returns
+///
+/// * `Plot` - Plotly Chart struct
+pub async fn yield_curve_chart(height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+    let curve = treasury_yield_curve().await?;
+    let tenors = curve.column("tenor")?.str()?.into_no_null_iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let maturities = curve.column("maturity")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+    let yields = curve.column("yield")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+    let trace = Scatter::new(maturities, yields)
+        .mode(Mode::LinesMarkersText)
+        .text_array(tenors)
+        .name("Treasury Yield");
+
+    let mut plot = Plot::new();
+    plot.add_trace(trace);
+
+    let layout = Layout::new()
+        .title(Title::from("<span style=\"font-weight:bold; color:darkgreen;\">US Treasury Yield Curve Chart</span>"))
+        .height(height.unwrap_or(DEFAULT_HEIGHT))
+        .width(width.unwrap_or(DEFAULT_WIDTH))
+        .x_axis(Axis::new()
+            .title("Maturity (Years)")
+            .type_(AxisType::Log))
+        .y_axis(Axis::new()
+            .title("Yield (%)"));
+
+    plot.set_layout(layout);
+
+    Ok(plot)
+}
@@ -1,5 +1,284 @@
+use std::error::Error;
+use chrono::NaiveDateTime;
+use polars::prelude::*;
+use plotly::{Bar, Layout, Plot, Scatter};
+use plotly::common::{DashType, Line, Mode, Title};
+use plotly::layout::{Annotation, Axis, BarMode, RangeSelector, SelectorButton, SelectorStep, Shape, ShapeLine, ShapeType, StepMode};
+use crate::analytics::statistics::{MIN_SIGNIFICANCE_OBSERVATIONS, SignificanceStats};
+use crate::reports::table::{DataTable, TableType};
+
 pub mod portfolio;
 pub mod ticker;
 pub mod tickers;
+pub mod fixed_income;
+pub mod strategy;
 pub const DEFAULT_HEIGHT: usize = 800;
-pub const DEFAULT_WIDTH: usize = 1200;
\ No newline at end of file
+pub const DEFAULT_WIDTH: usize = 1200;
+
+
+/// Display options for the ticker price and performance charts
+///
+/// * `range_selector` - Show the 1M/6M/YTD/1Y/All range-selector buttons
+/// * `log_scale` - Plot prices (or growth of 1 unit for performance charts) on a log y-axis
+/// * `events` - Optional DataFrame of events to annotate, with a `timestamp` column (datetime or YYYY-MM-DD string)
+///   and an `event` column holding the label (e.g. earnings dates, dividends or custom events)
+/// * `ticker_events` - Mark the ticker's dividends, splits and earnings (from `TickerData::get_events`) on the candlestick chart
+#[derive(Debug, Clone)]
+pub struct ChartOptions {
+    pub range_selector: bool,
+    pub log_scale: bool,
+    pub events: Option<DataFrame>,
+    pub ticker_events: bool,
+}
+
+impl Default for ChartOptions {
+    fn default() -> Self {
+        Self {
+            range_selector: true,
+            log_scale: false,
+            events: None,
+            ticker_events: false,
+        }
+    }
+}
+
+impl ChartOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn range_selector(mut self, range_selector: bool) -> Self {
+        self.range_selector = range_selector;
+        self
+    }
+
+    pub fn log_scale(mut self, log_scale: bool) -> Self {
+        self.log_scale = log_scale;
+        self
+    }
+
+    pub fn events(mut self, events: DataFrame) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    pub fn ticker_events(mut self, ticker_events: bool) -> Self {
+        self.ticker_events = ticker_events;
+        self
+    }
+}
+
+/// Standard range-selector buttons for date axes (1M/6M/YTD/1Y/All)
+pub fn range_selector() -> RangeSelector {
+    RangeSelector::new().buttons(vec![
+        SelectorButton::new()
+            .count(1)
+            .label("1M")
+            .step(SelectorStep::Month)
+            .step_mode(StepMode::Backward),
+        SelectorButton::new()
+            .count(6)
+            .label("6M")
+            .step(SelectorStep::Month)
+            .step_mode(StepMode::Backward),
+        SelectorButton::new()
+            .count(1)
+            .label("YTD")
+            .step(SelectorStep::Year)
+            .step_mode(StepMode::ToDate),
+        SelectorButton::new()
+            .count(1)
+            .label("1Y")
+            .step(SelectorStep::Year)
+            .step_mode(StepMode::Backward),
+        SelectorButton::new()
+            .label("All")
+            .step(SelectorStep::All),
+    ])
+}
+
+/// Builds vertical marker lines and labels for the events in a DataFrame
+///
+/// # Arguments
+///
+/// * `events` - DataFrame with a `timestamp` column (datetime or YYYY-MM-DD string) and an `event` label column
+/// * `x_ref` - Reference of the date axis to annotate (e.g. "x")
+///
+/// # Returns
+///
+/// * `(Vec<Shape>, Vec<Annotation>)` - Marker lines and labels to add to the chart layout
+pub fn event_annotations(events: &DataFrame, x_ref: &str) -> Result<(Vec<Shape>, Vec<Annotation>), Box<dyn Error>> {
+    let timestamps = events.column("timestamp")?;
+    let dates = match timestamps.dtype() {
+        DataType::Datetime(_, _) => timestamps.datetime()?.as_datetime_iter()
+            .map(|x| x.map(|d| d.to_string()).unwrap_or_default())
+            .collect::<Vec<String>>(),
+        _ => timestamps.str()?.into_no_null_iter()
+            .map(|x| match NaiveDateTime::parse_from_str(&format!("{} 00:00:00", x), "%Y-%m-%d %H:%M:%S") {
+                Ok(d) => d.to_string(),
+                Err(_) => x.to_string(),
+            })
+            .collect::<Vec<String>>(),
+    };
+    let labels = events.column("event")?.str()?.into_no_null_iter()
+        .map(|x| x.to_string()).collect::<Vec<String>>();
+
+    let mut shapes = Vec::new();
+    let mut annotations = Vec::new();
+    for (date, label) in dates.iter().zip(labels.iter()) {
+        shapes.push(Shape::new()
+            .shape_type(ShapeType::Line)
+            .x_ref(x_ref)
+            .y_ref("paper")
+            .x0(date.clone())
+            .x1(date.clone())
+            .y0(0)
+            .y1(1)
+            .line(ShapeLine::new().width(1.0).dash(DashType::Dot)));
+        annotations.push(Annotation::new()
+            .x_ref(x_ref)
+            .y_ref("paper")
+            .x(date.clone())
+            .y(1)
+            .text(label)
+            .show_arrow(false)
+            .text_angle(-90.0));
+    }
+    Ok((shapes, annotations))
+}
+
+/// Generates a Table of the standard errors, confidence intervals and significance tests of the Sharpe ratio and alpha
+///
+/// # Arguments
+///
+/// * `stats` - Significance statistics of the returns
+///
+/// # Returns
+///
+/// * `DataTable` - Items and Values table, the sample size row flags samples too short to be conclusive
+pub fn significance_stats_table(stats: &SignificanceStats) -> Result<DataTable, Box<dyn Error>> {
+    let confidence = stats.confidence_level * 100.0;
+    let sample = if stats.sufficient_sample {
+        format!("{} (sufficient)", stats.observations)
+    } else if stats.observations < MIN_SIGNIFICANCE_OBSERVATIONS {
+        format!("{} (insufficient, fewer than {} observations)", stats.observations, MIN_SIGNIFICANCE_OBSERVATIONS)
+    } else {
+        format!("{} (insufficient, below the minimum track record)", stats.observations)
+    };
+    let fields = vec![
+        "Observations".to_string(),
+        "HAC Lags".to_string(),
+        "Sharpe Ratio (Lo Adjusted)".to_string(),
+        "Sharpe Ratio Standard Error".to_string(),
+        format!("Sharpe Ratio {:.0}% Interval", confidence),
+        "Sharpe Ratio p-value".to_string(),
+        "Minimum Track Record".to_string(),
+        "Alpha".to_string(),
+        "Alpha Standard Error (HAC)".to_string(),
+        format!("Alpha {:.0}% Interval", confidence),
+        "Alpha t-stat".to_string(),
+        "Alpha p-value".to_string(),
+    ];
+    let values = vec![
+        sample,
+        stats.lags.to_string(),
+        format!("{:.2}", stats.sharpe_ratio),
+        format!("{:.2}", stats.sharpe_standard_error),
+        format!("[{:.2}, {:.2}]", stats.sharpe_lower, stats.sharpe_upper),
+        format!("{:.4}", stats.sharpe_p_value),
+        if stats.minimum_track_record.is_finite() { format!("{:.0}", stats.minimum_track_record.ceil()) } else { "-".to_string() },
+        format!("{:.4}", stats.alpha),
+        format!("{:.4}", stats.alpha_standard_error),
+        format!("[{:.4}, {:.4}]", stats.alpha_lower, stats.alpha_upper),
+        format!("{:.2}", stats.alpha_t_stat),
+        format!("{:.4}", stats.alpha_p_value),
+    ];
+    let df = DataFrame::new(vec![
+        Series::new("Items", fields),
+        Series::new("Values", values),
+    ])?;
+    Ok(DataTable::new(df, TableType::PerformanceStats))
+}
+
+/// Generates a Chart decomposing a cumulative return into benchmark and active contributions
+///
+/// # Arguments
+///
+/// * `decomposition` - DataFrame returned by `performance_decomposition`
+/// * `name` - Name of the security or portfolio
+/// * `benchmark` - Name of the benchmark
+/// * `height` - `usize` - Height of the chart
+/// * `width` - `usize` - Width of the chart
+///
+/// # Returns
+///
+/// * `Plot` - Stacked benchmark and active contributions with the total cumulative return line
+pub fn performance_decomposition_chart(
+    decomposition: &DataFrame,
+    name: &str,
+    benchmark: &str,
+    height: Option<usize>,
+    width: Option<usize>,
+) -> Result<Plot, Box<dyn Error>> {
+    let column = |col: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+        Ok(decomposition.column(col)?.f64()?.into_iter().map(|x| x.unwrap_or(f64::NAN)).collect())
+    };
+    let dates = decomposition.column("timestamp")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string())
+        .collect::<Vec<String>>();
+
+    let benchmark_trace = Bar::new(dates.clone(), column("benchmark_contribution")?)
+        .name(format!("{} Contribution", benchmark));
+    let active_trace = Bar::new(dates.clone(), column("active_contribution")?)
+        .name("Active Contribution");
+    let total_trace = Scatter::new(dates, column("total_return")?)
+        .name(format!("{} Cumulative Return", name))
+        .mode(Mode::Lines)
+        .line(Line::new().color("black"));
+
+    let mut plot = Plot::new();
+    plot.add_trace(benchmark_trace);
+    plot.add_trace(active_trace);
+    plot.add_trace(total_trace);
+
+    // Relative mode stacks positive and negative contributions on either side of zero
+    let layout = Layout::new()
+        .height(height.unwrap_or(DEFAULT_HEIGHT))
+        .width(width.unwrap_or(DEFAULT_WIDTH))
+        .bar_mode(BarMode::Relative)
+        .bar_gap(0.0)
+        .title(Title::from(format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Performance Decomposition</span>", name).as_str()))
+        .x_axis(Axis::new().range_selector(range_selector()))
+        .y_axis(
+            Axis::new()
+                .title(Title::from("Cumulative Return"))
+                .tick_format(".0%")
+        );
+    plot.set_layout(layout);
+    Ok(plot)
+}
+
+/// Generates a Table of the benchmark and active contributions to a cumulative return
+///
+/// # Arguments
+///
+/// * `decomposition` - DataFrame returned by `performance_decomposition`
+///
+/// # Returns
+///
+/// * `DataTable` - Timestamp, total return, benchmark and active contribution columns in percent
+pub fn performance_decomposition_table(decomposition: &DataFrame) -> Result<DataTable, Box<dyn Error>> {
+    let percent = |col: &str, title: &str| -> Result<Series, Box<dyn Error>> {
+        let values = decomposition.column(col)?.f64()?.into_iter()
+            .map(|x| x.map(|v| v * 100.0))
+            .collect::<Vec<Option<f64>>>();
+        Ok(Series::new(title, values))
+    };
+    let df = DataFrame::new(vec![
+        decomposition.column("timestamp")?.clone().with_name("Timestamp"),
+        percent("total_return", "Total Return (%)")?,
+        percent("benchmark_contribution", "Benchmark Contribution (%)")?,
+        percent("active_contribution", "Active Contribution (%)")?,
+    ])?;
+    Ok(DataTable::new(df, TableType::Returns))
+}
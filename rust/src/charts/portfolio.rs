@@ -3,14 +3,20 @@ use polars::prelude::{DataFrame, NamedFrom, Series};
 use plotly::color::NamedColor;
 use plotly::{Bar, HeatMap, Histogram, Layout, Plot, Scatter};
 use plotly::layout::{Axis, GridPattern, LayoutGrid, RowOrder};
-use plotly::common::{ColorScalePalette, Fill, Marker, MarkerSymbol, Mode, Title};
+use plotly::common::{ColorScalePalette, DashType, Fill, Line, Marker, MarkerSymbol, Mode, Title};
 
 use crate::prelude::TickersData;
 use crate::prelude::TickersBuilder;
 use crate::models::portfolio::Portfolio;
 use crate::reports::table::{DataTable, TableType};
-use crate::charts::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
-use crate::analytics::statistics::{correlation_matrix, cumulative_returns_list, maximum_drawdown};
+use crate::charts::{DEFAULT_HEIGHT, DEFAULT_WIDTH, performance_decomposition_chart, performance_decomposition_table, significance_stats_table};
+use crate::analytics::statistics::{correlation_matrix, maximum_drawdown};
+use crate::analytics::cashflows::CashFlowSchedule;
+use crate::analytics::projection::GoalPlan;
+use crate::analytics::strategy::StrategyComparison;
+use crate::analytics::optimization::{ObjectiveFunction, objective_value};
+use crate::analytics::esg::EsgAction;
+use crate::data::kline::kline_for;
 
 
 pub trait PortfolioCharts {
@@ -18,9 +24,19 @@ pub trait PortfolioCharts {
     fn optimization_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
     fn performance_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
     fn performance_stats_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
+    fn optimization_details_table(&self) -> Result<DataTable, Box<dyn Error>>;
+    fn pareto_frontier_chart(&self, objectives: (ObjectiveFunction, ObjectiveFunction), points: usize, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn significance_table(&self) -> Result<DataTable, Box<dyn Error>>;
+    fn performance_decomposition_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn performance_decomposition_table(&self) -> Result<DataTable, Box<dyn Error>>;
     fn returns_table(&self) -> Result<DataTable, Box<dyn Error>>;
     fn returns_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
     fn returns_matrix(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn weights_history(&self, window: usize, step: usize) -> Result<DataFrame, Box<dyn Error>>;
+    fn weights_history_chart(&self, window: usize, step: usize, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn cash_flow_chart(&self, schedule: &CashFlowSchedule, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn goal_projection_chart(&self, plan: &GoalPlan, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn naive_benchmarks(&self) -> impl std::future::Future<Output = Result<StrategyComparison, Box<dyn Error>>>;
 }
 
 impl PortfolioCharts for Portfolio {
@@ -51,21 +67,22 @@ impl PortfolioCharts for Portfolio {
     ///
     /// * `Plot` Plotly Chart struct
     fn optimization_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
-        let days = self.performance_stats.interval.to_days();
+        let days = self.performance_stats.calendar.interval_days(self.performance_stats.interval);
+        let trading_days = self.performance_stats.calendar.trading_days();
 
         let ef_returns = self.performance_stats.efficient_frontier.clone().iter()
-            .map(|x| (1.0 + (x[0]/days)/100.0).powf(252.0) - 1.0).collect::<Vec<f64>>();
+            .map(|x| (1.0 + (x[0]/days)/100.0).powf(trading_days) - 1.0).collect::<Vec<f64>>();
 
         let ef_risk = self.performance_stats.efficient_frontier.clone().iter()
-            .map(|x| x[1]/100.0 * 252.0_f64.sqrt()).collect::<Vec<f64>>();
+            .map(|x| x[1]/100.0 * trading_days.sqrt()).collect::<Vec<f64>>();
 
         let ef_trace = Scatter::new(ef_risk, ef_returns)
             .name("Efficient Frontier")
             .mode(Mode::Markers)
             .marker(Marker::new().size(10));
 
-        let opt_return = (1.0 + (self.performance_stats.performance_stats.daily_return/days)/100.0).powf(252.0) - 1.0;
-        let opt_risk = self.performance_stats.performance_stats.daily_volatility/100.0 * 252.0_f64.sqrt();
+        let opt_return = (1.0 + (self.performance_stats.performance_stats.daily_return/days)/100.0).powf(trading_days) - 1.0;
+        let opt_risk = self.performance_stats.performance_stats.daily_volatility/100.0 * trading_days.sqrt();
 
         let optimal_point = Scatter::new(vec![opt_risk],
                                          vec![opt_return])
@@ -154,9 +171,9 @@ impl PortfolioCharts for Portfolio {
         let benchmark_returns = self.performance_stats.benchmark_returns.f64().unwrap().to_vec()
             .iter().map(|x| x.unwrap()).collect::<Vec<f64>>();
 
-        let cum_returns= cumulative_returns_list(returns.clone());
+        let cum_returns= self.performance_stats.return_type.cumulative_returns(&returns);
 
-        let benchmark_cum_returns= cumulative_returns_list(benchmark_returns.clone());
+        let benchmark_cum_returns= self.performance_stats.return_type.cumulative_returns(&benchmark_returns);
 
         let (drawdowns, _) = maximum_drawdown(&self.performance_stats.optimal_portfolio_returns);
         let drawdowns = drawdowns.iter().map(|x| x/100.0).collect::<Vec<f64>>();
@@ -256,6 +273,7 @@ impl PortfolioCharts for Portfolio {
             .benchmark_symbol(&self.performance_stats.benchmark_symbol)
             .confidence_level(self.performance_stats.confidence_level)
             .risk_free_rate(self.performance_stats.risk_free_rate)
+            .ticker_data(self.performance_stats.ticker_data.clone())
             .build();
 
         let symbols_stats = tickers.performance_stats().await?;
@@ -297,6 +315,153 @@ impl PortfolioCharts for Portfolio {
         Ok(data_table)
     }
 
+    /// Generates Table of the significance of the Portfolio's Sharpe ratio and alpha
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` Display Table struct
+    fn significance_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        significance_stats_table(&self.performance_stats.significance()?)
+    }
+
+    /// Generates Chart decomposing the Portfolio's cumulative return into benchmark and active contributions
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - usize - Height of the chart
+    /// * `width` - usize - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    fn performance_decomposition_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        performance_decomposition_chart(&self.performance_stats.decomposition()?, "Portfolio",
+                                        &self.performance_stats.benchmark_symbol, height, width)
+    }
+
+    /// Generates Table of the benchmark and active contributions to the Portfolio's cumulative return
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` Display Table struct
+    fn performance_decomposition_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        performance_decomposition_table(&self.performance_stats.decomposition()?)
+    }
+
+    /// Generates Table of the Portfolio Optimization Settings and Solver Diagnostics
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` Display Table struct
+    fn optimization_details_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        let stats = &self.performance_stats;
+        let diagnostics = &stats.optimization_diagnostics;
+        let active_constraints = if diagnostics.active_constraints.is_empty() {
+            "None".to_string()
+        } else {
+            diagnostics.active_constraints.join(", ")
+        };
+        let mut items = vec![
+            ("Objective Function", format!("{:?}", stats.objective_function)),
+            ("Optimization Method", stats.optimization_method.clone()),
+            ("Covariance Estimator", stats.covariance_estimator.to_string()),
+            ("Expected Return Model", stats.expected_return_model.to_string()),
+            ("Return Type", stats.return_type.to_string()),
+            ("Price Type", stats.price_type.to_string()),
+            ("Random Seed", stats.seed.map(|x| x.to_string()).unwrap_or("None (not reproducible)".to_string())),
+            ("Converged", diagnostics.converged.to_string()),
+            ("Iterations", format!("{} / {}", diagnostics.iterations, diagnostics.max_iterations)),
+            ("Objective Value", format!("{:.6}", diagnostics.objective_value)),
+            ("Active Constraints", active_constraints),
+        ];
+        if !diagnostics.warnings.is_empty() {
+            items.push(("Warnings", diagnostics.warnings.join(", ")));
+        }
+        if let Some(screen) = &stats.esg_screen {
+            let action = match screen.action {
+                EsgAction::Exclude => "Exclude".to_string(),
+                EsgAction::Cap(max_weight) => format!("Cap at {:.2}%", max_weight * 100.0),
+            };
+            items.push(("ESG Screen", format!("Risk rating above {:.2}: {}", screen.max_risk_score, action)));
+        }
+        if let Ok((score, coverage)) = stats.esg_score() {
+            items.push(("Weighted ESG Risk Rating", format!("{:.2}", score)));
+            items.push(("ESG Rating Coverage", format!("{:.2}%", coverage)));
+        }
+        let df = DataFrame::new(vec![
+            Series::new("Metric", items.iter().map(|x| x.0).collect::<Vec<&str>>()),
+            Series::new("Value", items.iter().map(|x| x.1.clone()).collect::<Vec<String>>()),
+        ])?;
+        Ok(DataTable::new(df, TableType::SummaryStats))
+    }
+
+    /// Generates Scatter Chart of the Pareto frontier of two objectives, with the optimal portfolio for reference
+    ///
+    /// # Arguments
+    ///
+    /// * `objectives` - The two objective functions (e.g. (ObjectiveFunction::MaxReturn, ObjectiveFunction::MinCVaR))
+    /// * `points` - Number of weighted sum optimizations along the frontier (e.g. 20)
+    /// * `height` - usize - Height of the chart
+    /// * `width` - usize - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    fn pareto_frontier_chart(&self, objectives: (ObjectiveFunction, ObjectiveFunction), points: usize, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let stats = &self.performance_stats;
+        let frontier = stats.pareto_frontier(objectives, points)?;
+
+        // Hover text lists the holdings of each efficient portfolio
+        let holdings = frontier.weights.iter()
+            .map(|weights| {
+                let mut held = stats.ticker_symbols.iter().zip(weights.iter())
+                    .filter(|(_, &w)| w > 1e-4)
+                    .collect::<Vec<_>>();
+                held.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
+                held.iter().map(|(s, w)| format!("{}: {:.2}%", s, *w * 100.0)).collect::<Vec<String>>().join("<br>")
+            })
+            .collect::<Vec<String>>();
+        let frontier_trace = Scatter::new(frontier.values.iter().map(|x| x.1).collect::<Vec<f64>>(),
+                                          frontier.values.iter().map(|x| x.0).collect::<Vec<f64>>())
+            .name("Pareto Frontier")
+            .mode(Mode::LinesMarkers)
+            .marker(Marker::new().size(8))
+            .hover_text_array(holdings);
+
+        let cov_matrix = stats.covariance_estimator.estimate(&stats.portfolio_returns)?;
+        let settings = stats.optimization_settings();
+        let metric = |objective: ObjectiveFunction| {
+            let value = objective_value(objective, &stats.optimal_weights, &stats.expected_returns, &cov_matrix,
+                                        &stats.portfolio_returns, Some(&stats.benchmark_returns), &settings);
+            if objective.is_maximization() { -value } else { value }
+        };
+        let optimal_point = Scatter::new(vec![metric(objectives.1)], vec![metric(objectives.0)])
+            .name(format!("Optimal Portfolio ({})", stats.objective_function.to_string()))
+            .mode(Mode::Markers)
+            .marker(Marker::new().size(12).color(NamedColor::Red).symbol(MarkerSymbol::Star));
+
+        let mut plot = Plot::new();
+        plot.add_trace(frontier_trace);
+        plot.add_trace(optimal_point);
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(format!("<span style=\"font-weight:bold; color:darkgreen;\">Pareto Frontier: {} vs {}</span>",
+                                       objectives.0.to_string(), objectives.1.to_string()).as_str()))
+            .x_axis(
+                Axis::new()
+                    .title(Title::from(format!("Per Period {}", objectives.1.metric_name()).as_str()))
+            )
+            .y_axis(
+                Axis::new()
+                    .title(Title::from(format!("Per Period {}", objectives.0.metric_name()).as_str()))
+            );
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
     fn returns_table(&self) -> Result<DataTable, Box<dyn Error>> {
         let returns = self.performance_stats.portfolio_returns.clone();
         let optimal_returns = self.performance_stats.optimal_portfolio_returns.clone();
@@ -325,7 +490,7 @@ impl PortfolioCharts for Portfolio {
                 Ok(returns_series) => {
                     let returns = returns_series.f64().unwrap().to_vec()
                         .iter().map(|x| x.unwrap()).collect::<Vec<f64>>();
-                    let cum_returns = cumulative_returns_list(returns.clone());
+                    let cum_returns = self.performance_stats.return_type.cumulative_returns(&returns);
                     let cum_returns_trace = Scatter::new(dates.clone(), cum_returns.clone())
                         .name(format!("{}", symbol))
                         .mode(Mode::Lines);
@@ -386,4 +551,203 @@ impl PortfolioCharts for Portfolio {
 
         Ok(plot)
     }
-}
\ No newline at end of file
+
+    /// Returns the weights history of a rolling (walk-forward) re-optimization of the portfolio
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of return periods in each optimization window (e.g. 252)
+    /// * `step` - Number of return periods between rebalances (e.g. 21)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` Weights history with a timestamp column and a weight column per symbol
+    fn weights_history(&self, window: usize, step: usize) -> Result<DataFrame, Box<dyn Error>> {
+        self.performance_stats.rolling_weights(window, step)
+    }
+
+    /// Generates a Stacked Area Chart of the Portfolio Weights over a rolling (walk-forward) re-optimization
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of return periods in each optimization window (e.g. 252)
+    /// * `step` - Number of return periods between rebalances (e.g. 21)
+    /// * `height` - usize - Height of the chart
+    /// * `width` - usize - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    fn weights_history_chart(&self, window: usize, step: usize, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let history = self.weights_history(window, step)?;
+        let dates = history.column("timestamp")?.str()?.into_no_null_iter()
+            .map(|x| x.to_string()).collect::<Vec<String>>();
+        let mut plot = Plot::new();
+
+        for symbol in self.performance_stats.ticker_symbols.iter() {
+            let weights = history.column(symbol)?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+            // Assets never held over the history are left out of the chart
+            if weights.iter().all(|x| x.abs() < 1e-6) {
+                continue;
+            }
+            let trace = Scatter::new(dates.clone(), weights)
+                .name(symbol)
+                .mode(Mode::Lines)
+                .stack_group("weights");
+            plot.add_trace(trace);
+        }
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from("<span style=\"font-weight:bold; color:darkgreen;\">Portfolio Weights History</span>"))
+            .y_axis(
+                Axis::new()
+                    .title(Title::from("Asset Allocation"))
+                    .tick_format(".0%")
+                    .range(vec![0.0, 1.0])
+            );
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a Chart of the Portfolio Value against the Net Amount Invested with scheduled Cash Flows
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - CashFlowSchedule - Initial investment and scheduled contributions/withdrawals
+    /// * `height` - usize - Height of the chart
+    /// * `width` - usize - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    fn cash_flow_chart(&self, schedule: &CashFlowSchedule, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let simulation = self.performance_stats.cash_flow_simulation(schedule)?;
+        let mwr = self.performance_stats.money_weighted_return(schedule)?;
+        let dates = self.performance_stats.dates_array.clone();
+        let values = simulation.column("value")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let net_invested = simulation.column("net_invested")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let value_trace = Scatter::new(dates.clone(), values)
+            .name("Portfolio Value")
+            .mode(Mode::Lines)
+            .fill(Fill::ToZeroY);
+        let invested_trace = Scatter::new(dates, net_invested)
+            .name("Net Invested")
+            .mode(Mode::Lines)
+            .line(Line::new().color(NamedColor::Black).dash(DashType::Dash));
+
+        let mut plot = Plot::new();
+        plot.add_trace(value_trace);
+        plot.add_trace(invested_trace);
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!(
+                "<span style=\"font-weight:bold; color:darkgreen;\">Portfolio Value with Cash Flows (Money-Weighted Return: {:.2}%)</span>", mwr
+            )))
+            .y_axis(Axis::new().title(Title::from("Value")));
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a Fan Chart of the Monte Carlo Projection of a Savings Plan invested in the Portfolio
+    ///
+    /// # Arguments
+    ///
+    /// * `plan` - GoalPlan - Initial investment, monthly contributions, horizon and target value
+    /// * `height` - usize - Height of the chart
+    /// * `width` - usize - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    fn goal_projection_chart(&self, plan: &GoalPlan, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let projection = self.performance_stats.goal_projection(plan)?;
+        let df = &projection.projections;
+        let years = df.column("month")?.u32()?.into_no_null_iter()
+            .map(|x| x as f64 / 12.0).collect::<Vec<f64>>();
+        let column = |name: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+            Ok(df.column(name)?.f64()?.into_no_null_iter().collect::<Vec<f64>>())
+        };
+
+        let mut plot = Plot::new();
+        // Each band is drawn as its lower bound followed by the upper bound filled down to it
+        for (lower, upper, name, color) in [("p5", "p95", "5th - 95th Percentile", "rgba(0, 100, 0, 0.15)"),
+                                            ("p25", "p75", "25th - 75th Percentile", "rgba(0, 100, 0, 0.3)")] {
+            plot.add_trace(Scatter::new(years.clone(), column(lower)?)
+                .mode(Mode::Lines)
+                .line(Line::new().width(0.0))
+                .show_legend(false)
+                .name(lower));
+            plot.add_trace(Scatter::new(years.clone(), column(upper)?)
+                .mode(Mode::Lines)
+                .line(Line::new().width(0.0))
+                .fill(Fill::ToNextY)
+                .fill_color(color)
+                .name(name));
+        }
+        plot.add_trace(Scatter::new(years.clone(), column("p50")?)
+            .name("Median")
+            .mode(Mode::Lines)
+            .line(Line::new().color(NamedColor::DarkGreen)));
+        plot.add_trace(Scatter::new(years.clone(), column("net_invested")?)
+            .name("Net Invested")
+            .mode(Mode::Lines)
+            .line(Line::new().color(NamedColor::Black).dash(DashType::Dash)));
+        plot.add_trace(Scatter::new(vec![0.0, plan.years as f64], vec![plan.target_value; 2])
+            .name("Target")
+            .mode(Mode::Lines)
+            .line(Line::new().color(NamedColor::Red).dash(DashType::Dot)));
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!(
+                "<span style=\"font-weight:bold; color:darkgreen;\">Goal Projection (Probability of reaching {:.0} in {} years: {:.1}%)</span>",
+                plan.target_value, plan.years, projection.probability
+            )))
+            .x_axis(Axis::new().title(Title::from("Years")))
+            .y_axis(Axis::new().title(Title::from("Value")));
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Compares the optimal portfolio with equal-weight and market-cap-weight portfolios of the same universe
+    ///
+    /// Market caps are fetched from the ticker stats unless every ticker has custom ticker data, the
+    /// market-cap-weight portfolio is left out when they are unavailable for any asset (e.g. for currencies).
+    ///
+    /// # Returns
+    ///
+    /// * `StrategyComparison` - Comparison of the optimal and naive portfolios (see `StrategyCharts` for the tables and charts)
+    async fn naive_benchmarks(&self) -> Result<StrategyComparison, Box<dyn Error>> {
+        let stats = &self.performance_stats;
+        let market_caps = if stats.ticker_symbols.iter().all(|x| kline_for(&stats.ticker_data, x).is_some()) {
+            None
+        } else {
+            let tickers = TickersBuilder::new()
+                .tickers(stats.ticker_symbols.iter().map(|x| x.as_str()).collect::<Vec<&str>>())
+                .build();
+            match tickers.get_ticker_stats().await {
+                Ok(df) => {
+                    let symbols = df.column("symbol")?.str()?.into_no_null_iter().map(|x| x.to_string()).collect::<Vec<String>>();
+                    let caps = df.column("market_cap")?.f64()?.to_vec();
+                    Some(stats.ticker_symbols.iter()
+                        .map(|s| symbols.iter().position(|x| x == s).and_then(|i| caps[i]).unwrap_or(f64::NAN))
+                        .collect::<Vec<f64>>())
+                }
+                Err(e) => {
+                    eprintln!("Error fetching market caps: {}", e);
+                    None
+                }
+            }
+        };
+        stats.naive_comparison(market_caps.as_deref())
+    }
+}
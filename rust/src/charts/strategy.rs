@@ -0,0 +1,211 @@
+use std::error::Error;
+use chrono::NaiveDateTime;
+use plotly::common::{ColorScalePalette, Fill, Mode, Title};
+use plotly::layout::Axis;
+use plotly::{HeatMap, Layout, Plot, Scatter};
+use polars::prelude::*;
+
+use crate::analytics::statistics::maximum_drawdown;
+use crate::analytics::strategy::{StrategyComparison, StrategyMetric};
+use crate::charts::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
+use crate::reports::report::ReportType;
+use crate::reports::table::{DataTable, TableType};
+use crate::reports::tabs::TabbedHtml;
+
+pub trait StrategyCharts {
+    fn performance_stats_table(&self) -> Result<DataTable, Box<dyn Error>>;
+    fn sharpe_significance_table(&self) -> Result<DataTable, Box<dyn Error>>;
+    fn equity_curves_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn drawdown_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn parameter_heatmap(&self, x_param: &str, y_param: &str, metric: StrategyMetric, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>>;
+    fn strategy_report(&self, sweep: Option<(&str, &str, StrategyMetric)>) -> Result<TabbedHtml, Box<dyn Error>>;
+}
+
+impl StrategyCharts for StrategyComparison {
+    /// Displays the Performance Stats of the strategy variants side by side
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` - Performance Stats Table
+    fn performance_stats_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        Ok(DataTable::new(self.stats_table()?, TableType::PerformanceStats))
+    }
+
+    /// Displays the probabilistic and deflated Sharpe ratios of the strategy variants
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` - Sharpe Significance Table
+    fn sharpe_significance_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        Ok(DataTable::new(self.sharpe_significance()?, TableType::StrategyValidation))
+    }
+
+    /// Generates a Chart of the Cumulative Returns of the strategy variants and the benchmark
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    fn equity_curves_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let mut plot = Plot::new();
+        for variant in &self.variants {
+            let cumulative = self.return_type.cumulative_returns(&variant.returns_vec()?);
+            plot.add_trace(Scatter::new(dates(&variant.returns)?, cumulative)
+                .name(&variant.name)
+                .mode(Mode::Lines));
+        }
+        if let Some(benchmark) = &self.benchmark {
+            let series = benchmark.get_columns().iter()
+                .find(|x| x.name() != "timestamp")
+                .ok_or("Benchmark DataFrame has no returns column")?;
+            let returns = series.cast(&DataType::Float64)?.f64()?.into_iter()
+                .map(|x| x.unwrap_or(0.0)).collect::<Vec<f64>>();
+            plot.add_trace(Scatter::new(dates(benchmark)?, self.return_type.cumulative_returns(&returns))
+                .name(format!("{} (Benchmark)", series.name()))
+                .mode(Mode::Lines));
+        }
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from("<span style=\"font-weight:bold; color:darkgreen;\">Strategy Equity Curves</span>"))
+            .y_axis(Axis::new()
+                .title(Title::from("Cumulative Returns"))
+                .tick_format(".0%"));
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a Chart of the Drawdowns of the strategy variants
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    fn drawdown_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let mut plot = Plot::new();
+        for variant in &self.variants {
+            let (drawdowns, _) = maximum_drawdown(&Series::new("returns", variant.returns_vec()?));
+            plot.add_trace(Scatter::new(dates(&variant.returns)?, drawdowns.iter().map(|x| x / 100.0).collect::<Vec<f64>>())
+                .name(&variant.name)
+                .mode(Mode::Lines)
+                .fill(Fill::ToZeroY));
+        }
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from("<span style=\"font-weight:bold; color:darkgreen;\">Strategy Drawdowns</span>"))
+            .y_axis(Axis::new()
+                .title(Title::from("Drawdown"))
+                .tick_format(".0%"));
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a Heatmap of a performance metric over a two-parameter sweep of the strategy variants
+    ///
+    /// # Arguments
+    ///
+    /// * `x_param` - Parameter on the x axis (e.g. "fast")
+    /// * `y_param` - Parameter on the y axis (e.g. "slow")
+    /// * `metric` - Performance metric (e.g. StrategyMetric::SharpeRatio)
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    fn parameter_heatmap(&self, x_param: &str, y_param: &str, metric: StrategyMetric, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let grid = self.parameter_grid(x_param, y_param, metric)?;
+        let xs = grid.column(x_param)?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let ys = grid.column(y_param)?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let values = grid.column(&metric.to_string())?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let mut x_values = xs.clone();
+        x_values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        x_values.dedup();
+        let mut y_values = ys.clone();
+        y_values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        y_values.dedup();
+
+        // Grid cells without a variant are left empty
+        let mut z: Vec<Vec<Option<f64>>> = vec![vec![None; x_values.len()]; y_values.len()];
+        for i in 0..values.len() {
+            let col = x_values.iter().position(|x| *x == xs[i]).unwrap_or_default();
+            let row = y_values.iter().position(|y| *y == ys[i]).unwrap_or_default();
+            z[row][col] = Some(values[i]);
+        }
+
+        let heatmap = HeatMap::new(x_values, y_values, z)
+            .color_scale(ColorScalePalette::Viridis.into());
+
+        let mut plot = Plot::new();
+        plot.add_trace(heatmap);
+        plot.set_layout(
+            Layout::new()
+                .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} by {} and {}</span>",
+                                             metric.to_string(), x_param, y_param)))
+                .height(height.unwrap_or(DEFAULT_HEIGHT))
+                .width(width.unwrap_or(DEFAULT_WIDTH))
+                .x_axis(Axis::new().title(Title::from(x_param)))
+                .y_axis(Axis::new().title(Title::from(y_param)))
+        );
+        Ok(plot)
+    }
+
+    /// Generates a Strategy Comparison Report
+    ///
+    /// # Arguments
+    ///
+    /// * `sweep` - Optional two parameters and metric of a parameter sweep (e.g. Some(("fast", "slow", StrategyMetric::SharpeRatio)))
+    ///   to add a parameter heatmap
+    ///
+    /// # Returns
+    ///
+    /// * `TabbedHtml` - Report with the stats table, equity curves, drawdowns, overfitting diagnostics and parameter heatmap
+    fn strategy_report(&self, sweep: Option<(&str, &str, StrategyMetric)>) -> Result<TabbedHtml, Box<dyn Error>> {
+        let mut tabs: Vec<(String, String)> = Vec::new();
+        tabs.push(("Performance Stats".to_string(), self.performance_stats_table()?.to_html()?));
+        tabs.push(("Equity Curves".to_string(), self.equity_curves_chart(None, None)?
+            .to_html().replace("plotly-html-element", "equity_curves_chart")));
+        tabs.push(("Drawdowns".to_string(), self.drawdown_chart(None, None)?
+            .to_html().replace("plotly-html-element", "drawdown_chart")));
+        if self.variants.len() > 1 {
+            // Too few periods for the cross-validation blocks only drops the probability, not the tab
+            let pbo = self.probability_of_backtest_overfitting(16)
+                .map(|x| format!("<p><b>Probability of Backtest Overfitting:</b> {:.2}%</p>", x * 100.0))
+                .unwrap_or_default();
+            tabs.push(("Overfitting".to_string(), format!("{}{}", pbo, self.sharpe_significance_table()?.to_html()?)));
+        }
+        if let Some((x_param, y_param, metric)) = sweep {
+            tabs.push(("Parameter Heatmap".to_string(), self.parameter_heatmap(x_param, y_param, metric, None, None)?
+                .to_html().replace("plotly-html-element", "parameter_heatmap")));
+        }
+        Ok(TabbedHtml::new(ReportType::Strategy, tabs))
+    }
+}
+
+/// Formats the timestamp column of a returns DataFrame for the chart axis
+fn dates(df: &DataFrame) -> Result<Vec<String>, Box<dyn Error>> {
+    let column = df.column("timestamp")?;
+    let dates = match column.dtype() {
+        DataType::Datetime(_, _) => column.datetime()?.as_datetime_iter()
+            .map(|x| x.map(|d: NaiveDateTime| d.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default())
+            .collect::<Vec<String>>(),
+        _ => column.cast(&DataType::String)?.str()?.into_iter()
+            .map(|x| x.unwrap_or_default().to_string())
+            .collect::<Vec<String>>(),
+    };
+    Ok(dates)
+}
@@ -1,22 +1,32 @@
 use std::error::Error;
+use std::collections::BTreeMap;
 use polars::prelude::*;
+use serde_json::Value;
 use chrono::{DateTime, NaiveDateTime};
 use num_format::{Locale, ToFormattedString};
-use plotly::common::{AxisSide, Fill, Line, LineShape, Mode, Title};
+use plotly::color::{NamedColor, Rgba};
+use plotly::common::{AxisSide, DashType, Fill, HoverInfo, Line, LineShape, Marker, Mode, Orientation, Position, Title};
 use plotly::{Bar, Candlestick, Histogram, Layout, Plot, Scatter, Surface};
-use plotly::layout::{Axis, GridPattern, LayoutGrid, LayoutScene, RangeSelector, RangeSlider, RowOrder, SelectorButton, SelectorStep, StepMode};
+use plotly::layout::{Annotation, Axis, AxisType, BarMode, GridPattern, LayoutGrid, LayoutScene, RangeSelector, RangeSlider, RowOrder, SelectorButton, SelectorStep, Shape, ShapeLine, ShapeType, StepMode};
 
 use crate::models::ticker::Ticker;
-use crate::data::ticker::TickerData;
-use crate::prelude::StatementFrequency;
+use crate::data::ticker::{fetch_quote_fields, TickerData};
+use crate::data::config::{QuoteType, ReturnType, SegmentType};
+use crate::data::news::{news_keywords, DEFAULT_TOP_KEYWORDS};
+use crate::prelude::{StatementFrequency, StatementType};
 use crate::prelude::TechnicalIndicators;
-use crate::analytics::fundamentals::Financials;
+use crate::analytics::fundamentals::{Financials, per_share_series};
 use crate::analytics::performance::TickerPerformance;
+use crate::analytics::sessions::SessionAnalytics;
+use crate::analytics::cef::CefAnalytics;
 use crate::analytics::stochastics::VolatilitySurface;
-use crate::analytics::statistics::{cumulative_returns_list, maximum_drawdown};
+use crate::analytics::options::{BEARISH_PUT_CALL_RATIO, BULLISH_PUT_CALL_RATIO, IncomeStrategy, OptionsAnalytics, chain_greeks, put_call_sentiment};
+use crate::analytics::statistics::{PerformanceStats, maximum_drawdown};
+use crate::analytics::crypto::{CryptoDataSource, CryptoMetrics};
+use crate::analytics::forecast::{ForecastModel, Forecasting};
 use crate::utils::date_utils::to_date;
 use crate::reports::table::{DataTable, TableType};
-use crate::charts::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
+use crate::charts::{ChartOptions, DEFAULT_HEIGHT, DEFAULT_WIDTH, event_annotations, performance_decomposition_chart, performance_decomposition_table, range_selector, significance_stats_table};
 
 
 pub struct FinancialsTables {
@@ -40,13 +50,142 @@ pub struct OptionsTables {
 pub trait TickerCharts {
     fn ohlcv_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
     fn candlestick_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn candlestick_chart_with_options(&self, options: ChartOptions, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
     fn performance_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn performance_chart_with_options(&self, options: ChartOptions, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
     fn summary_stats_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
     fn performance_stats_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
+    fn currency_hedged_stats_table(&self) -> impl std::future::Future<Output = Result<Option<DataTable>, Box<dyn Error>>>;
+    fn significance_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
+    fn performance_decomposition_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn performance_decomposition_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
+    fn time_of_day_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn session_gaps_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn overnight_intraday_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn cef_discount_chart(&self, nav_symbol: Option<&str>, window: usize, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
     fn financials_tables(&self, frequency: StatementFrequency) -> impl std::future::Future<Output = Result<FinancialsTables, Box<dyn Error>>>;
+    fn income_waterfall_chart(&self, frequency: StatementFrequency, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn income_trend_chart(&self, frequency: StatementFrequency, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn per_share_chart(&self, frequency: StatementFrequency, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn estimate_trend_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn revenue_segments_chart(&self, segment_type: SegmentType, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
     fn options_charts(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<OptionsCharts, Box<dyn Error>>>;
     fn options_tables(&self) -> impl std::future::Future<Output = Result<OptionsTables, Box<dyn Error>>>;
+    fn expected_move_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn open_interest_chart(&self, expiration: Option<&str>, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn expiration_smile_chart(&self, expiration: Option<&str>, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn put_call_sentiment_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn max_pain_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn volatility_comparison_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn income_payoff_chart(&self, strategy: IncomeStrategy, cost_basis: Option<f64>, expiration: Option<&str>, top_n: Option<usize>, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
     fn news_sentiment_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn news_keywords_chart(&self, top_n: Option<usize>, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn forecast_chart(&self, model: ForecastModel, horizon: Option<usize>, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn insider_activity_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn asset_profile_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
+    fn fund_holdings_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
+    fn fund_sectors_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn crypto_supply_chart(&self, source: CryptoDataSource, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn crypto_dominance_chart(&self, universe: Option<Vec<&str>>, source: CryptoDataSource, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+}
+
+/// Labels and formatted values of the performance statistics
+fn performance_stats_rows(stats: &PerformanceStats) -> (Vec<String>, Vec<String>) {
+    let fields = vec![
+        "Daily Return".to_string(),
+        "Daily Volatility".to_string(),
+        "Cumulative Return".to_string(),
+        "Annualized Return".to_string(),
+        "Annualized Volatility".to_string(),
+        "Alpha".to_string(),
+        "Beta".to_string(),
+        "Sharpe Ratio".to_string(),
+        "Sortino Ratio".to_string(),
+        "Active Return".to_string(),
+        "Active Risk".to_string(),
+        "Information Ratio".to_string(),
+        "Calmar Ratio".to_string(),
+        "Maximum Drawdown".to_string(),
+        "Value At Risk".to_string(),
+        "Expected Shortfall".to_string(),
+    ];
+
+    let values = vec![
+        format!("{:.2}%",stats.daily_return),
+        format!("{:.2}%",stats.daily_volatility),
+        format!("{:.2}%",stats.cumulative_return),
+        format!("{:.2}%",stats.annualized_return),
+        format!("{:.2}%",stats.annualized_volatility),
+        format!("{:.2}",stats.alpha),
+        format!("{:.2}",stats.beta),
+        format!("{:.2}",stats.sharpe_ratio),
+        format!("{:.2}",stats.sortino_ratio),
+        format!("{:.2}%",stats.active_return),
+        format!("{:.2}%",stats.active_risk),
+        format!("{:.2}",stats.information_ratio),
+        format!("{:.2}",stats.calmar_ratio),
+        format!("{:.2}%",stats.maximum_drawdown),
+        format!("{:.2}%",stats.value_at_risk),
+        format!("{:.2}%",stats.expected_shortfall),
+    ];
+
+    (fields, values)
+}
+
+/// Display format of an asset profile field
+#[derive(Clone, Copy)]
+enum FieldFormat {
+    Number,
+    Percent,
+    Date,
+    Text,
+}
+
+/// Asset profile fields (Yahoo Finance field, label and format) shown for each quote type
+fn asset_profile_fields(quote_type: QuoteType) -> Vec<(&'static str, &'static str, FieldFormat)> {
+    let mut fields = vec![
+        ("longName", "Name", FieldFormat::Text),
+        ("quoteType", "Quote Type", FieldFormat::Text),
+        ("fullExchangeName", "Exchange", FieldFormat::Text),
+        ("currency", "Currency", FieldFormat::Text),
+        ("regularMarketPrice", "Price", FieldFormat::Number),
+    ];
+    let specific = match quote_type {
+        QuoteType::Etf | QuoteType::MutualFund => vec![
+            ("fundFamily", "Fund Family", FieldFormat::Text),
+            ("category", "Category", FieldFormat::Text),
+            ("fundInceptionDate", "Inception Date", FieldFormat::Date),
+            ("netAssets", "Net Assets", FieldFormat::Number),
+            ("netExpenseRatio", "Expense Ratio (%)", FieldFormat::Number),
+            ("yield", "Yield", FieldFormat::Percent),
+            ("beta3Year", "Beta (3 Year)", FieldFormat::Number),
+            ("morningStarOverallRating", "Morningstar Rating", FieldFormat::Number),
+        ],
+        QuoteType::Cryptocurrency => vec![
+            ("marketCap", "Market Cap", FieldFormat::Number),
+            ("circulatingSupply", "Circulating Supply", FieldFormat::Number),
+            ("maxSupply", "Max Supply", FieldFormat::Number),
+            ("volume24Hr", "Volume (24h)", FieldFormat::Number),
+            ("volumeAllCurrencies", "Volume All Currencies (24h)", FieldFormat::Number),
+            ("startDate", "Start Date", FieldFormat::Date),
+        ],
+        QuoteType::Future => vec![
+            ("underlyingSymbol", "Underlying Symbol", FieldFormat::Text),
+            ("expireDate", "Expiry Date", FieldFormat::Date),
+            ("openInterest", "Open Interest", FieldFormat::Number),
+            ("regularMarketVolume", "Volume", FieldFormat::Number),
+            ("regularMarketPreviousClose", "Previous Settlement", FieldFormat::Number),
+            ("exchangeTimezoneName", "Exchange Timezone", FieldFormat::Text),
+        ],
+        _ => vec![
+            ("fiftyTwoWeekHigh", "52 Week High", FieldFormat::Number),
+            ("fiftyTwoWeekLow", "52 Week Low", FieldFormat::Number),
+            ("fiftyDayAverage", "50 Day Average", FieldFormat::Number),
+            ("twoHundredDayAverage", "200 Day Average", FieldFormat::Number),
+        ],
+    };
+    fields.extend(specific);
+    fields
 }
 
 impl TickerCharts for Ticker {
@@ -74,6 +213,21 @@ impl TickerCharts for Ticker {
     ///
     /// * `Plot` Plotly Chart struct
     async fn candlestick_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        self.candlestick_chart_with_options(ChartOptions::default(), height, width).await
+    }
+
+    /// Generates an OHLCV candlestick chart for the ticker with display options
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - `ChartOptions` - Range selector, log scale and event annotation options
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn candlestick_chart_with_options(&self, options: ChartOptions, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
         let data = self.get_chart().await?;
         let x = data.column("timestamp")?.datetime()?.to_vec().iter().map(|x|
             DateTime::from_timestamp_millis( x.unwrap()).unwrap().naive_local()).collect::<Vec<NaiveDateTime>>();
@@ -119,7 +273,52 @@ impl TickerCharts for Ticker {
             .mode(Mode::Lines)
             .line(Line::new().shape(LineShape::Spline));
 
-        let layout = Layout::new()
+        let mut x_axis = Axis::new()
+            .range_slider(RangeSlider::new().visible(true));
+        if options.range_selector {
+            x_axis = x_axis.range_selector(RangeSelector::new().buttons(vec![
+                SelectorButton::new()
+                    .count(1)
+                    .label("1H")
+                    .step(SelectorStep::Hour)
+                    .step_mode(StepMode::Backward),
+                SelectorButton::new()
+                    .count(1)
+                    .label("1D")
+                    .step(SelectorStep::Day)
+                    .step_mode(StepMode::Backward),
+                SelectorButton::new()
+                    .count(1)
+                    .label("1M")
+                    .step(SelectorStep::Month)
+                    .step_mode(StepMode::Backward),
+                SelectorButton::new()
+                    .count(6)
+                    .label("6M")
+                    .step(SelectorStep::Month)
+                    .step_mode(StepMode::Backward),
+                SelectorButton::new()
+                    .count(1)
+                    .label("YTD")
+                    .step(SelectorStep::Year)
+                    .step_mode(StepMode::ToDate),
+                SelectorButton::new()
+                    .count(1)
+                    .label("1Y")
+                    .step(SelectorStep::Year)
+                    .step_mode(StepMode::Backward),
+                SelectorButton::new()
+                    .label("MAX")
+                    .step(SelectorStep::All),
+            ]));
+        }
+        let mut y_axis = Axis::new()
+            .domain(&[0.4, 1.0]);
+        if options.log_scale {
+            y_axis = y_axis.type_(AxisType::Log);
+        }
+
+        let mut layout = Layout::new()
             .height(height.unwrap_or(DEFAULT_HEIGHT))
             .width(width.unwrap_or(DEFAULT_WIDTH))
             .title(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Candlestick Chart</span>", self.ticker))
@@ -130,49 +329,8 @@ impl TickerCharts for Ticker {
                     .pattern(GridPattern::Coupled)
                     .row_order(RowOrder::TopToBottom)
             )
-            .x_axis(
-                Axis::new()
-                    .range_slider(RangeSlider::new().visible(true))
-                    .range_selector(RangeSelector::new().buttons(vec![
-                        SelectorButton::new()
-                            .count(1)
-                            .label("1H")
-                            .step(SelectorStep::Hour)
-                            .step_mode(StepMode::Backward),
-                        SelectorButton::new()
-                            .count(1)
-                            .label("1D")
-                            .step(SelectorStep::Day)
-                            .step_mode(StepMode::Backward),
-                        SelectorButton::new()
-                            .count(1)
-                            .label("1M")
-                            .step(SelectorStep::Month)
-                            .step_mode(StepMode::Backward),
-                        SelectorButton::new()
-                            .count(6)
-                            .label("6M")
-                            .step(SelectorStep::Month)
-                            .step_mode(StepMode::Backward),
-                        SelectorButton::new()
-                            .count(1)
-                            .label("YTD")
-                            .step(SelectorStep::Year)
-                            .step_mode(StepMode::ToDate),
-                        SelectorButton::new()
-                            .count(1)
-                            .label("1Y")
-                            .step(SelectorStep::Year)
-                            .step_mode(StepMode::Backward),
-                        SelectorButton::new()
-                            .label("MAX")
-                            .step(SelectorStep::All),
-                    ])),
-            )
-            .y_axis(
-                Axis::new()
-                    .domain(&[0.4, 1.0])
-            )
+            .x_axis(x_axis)
+            .y_axis(y_axis)
             .y_axis2(
                 Axis::new()
                     .domain(&[0.2, 0.4])
@@ -182,6 +340,23 @@ impl TickerCharts for Ticker {
                     .domain(&[0.0, 0.2])
             );
 
+        let mut shapes = Vec::new();
+        let mut annotations = Vec::new();
+        if let Some(events) = &options.events {
+            let (event_shapes, event_labels) = event_annotations(events, "x")?;
+            shapes.extend(event_shapes);
+            annotations.extend(event_labels);
+        }
+        if options.ticker_events {
+            let events = self.get_events().await?;
+            let (event_shapes, event_labels) = event_annotations(&events, "x")?;
+            shapes.extend(event_shapes);
+            annotations.extend(event_labels);
+        }
+        if !shapes.is_empty() {
+            layout = layout.shapes(shapes).annotations(annotations);
+        }
+
         let mut plot = Plot::new();
         plot.add_trace(Box::new(candlestick_trace));
         plot.add_trace(volume_trace);
@@ -205,6 +380,23 @@ impl TickerCharts for Ticker {
     ///
     /// * `Plot` Plotly Chart struct
     async fn performance_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        self.performance_chart_with_options(ChartOptions::default(), height, width).await
+    }
+
+    /// Generates a performance chart for the ticker with display options
+    ///
+    /// With a log scale, the cumulative returns are plotted as the growth of one unit invested
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - `ChartOptions` - Range selector, log scale and event annotation options
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn performance_chart_with_options(&self, options: ChartOptions, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
         let performance_stats = self.performance_stats().await?;
         let dates = performance_stats.dates_array;
         let returns = performance_stats.security_returns.clone().f64().unwrap().to_vec()
@@ -213,9 +405,14 @@ impl TickerCharts for Ticker {
         let benchmark_returns = performance_stats.benchmark_returns.f64().unwrap().to_vec()
             .iter().map(|x| x.unwrap()).collect::<Vec<f64>>();
 
-        let cum_returns= cumulative_returns_list(returns.clone());
+        let mut cum_returns= performance_stats.return_type.cumulative_returns(&returns);
 
-        let benchmark_cum_returns= cumulative_returns_list(benchmark_returns.clone());
+        let mut benchmark_cum_returns= performance_stats.return_type.cumulative_returns(&benchmark_returns);
+
+        if options.log_scale {
+            cum_returns = cum_returns.iter().map(|x| 1.0 + x).collect::<Vec<f64>>();
+            benchmark_cum_returns = benchmark_cum_returns.iter().map(|x| 1.0 + x).collect::<Vec<f64>>();
+        }
 
         let (drawdowns, _) = maximum_drawdown(&performance_stats.security_returns);
         let drawdowns = drawdowns.iter().map(|x| x/100.0).collect::<Vec<f64>>();
@@ -258,8 +455,22 @@ impl TickerCharts for Ticker {
         plot.add_trace(benchmark_cum_returns_trace);
         plot.add_trace(drawdown_trace);
 
+        let mut x_axis = Axis::new();
+        if options.range_selector {
+            x_axis = x_axis.range_selector(range_selector());
+        }
+        let y_axis3 = if options.log_scale {
+            Axis::new()
+                .title(Title::from("Growth of 1"))
+                .type_(AxisType::Log)
+        } else {
+            Axis::new()
+                .title(Title::from("Cumulative Returns"))
+                .tick_format(".0%")
+        };
+
         // Set layout for the plot
-        let layout = Layout::new()
+        let mut layout = Layout::new()
             .height(height.unwrap_or(DEFAULT_HEIGHT))
             .width(width.unwrap_or(DEFAULT_WIDTH))
             .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Performance Chart</span>",
@@ -271,6 +482,7 @@ impl TickerCharts for Ticker {
                     .pattern(GridPattern::Independent)
                     .row_order(RowOrder::TopToBottom)
             )
+            .x_axis(x_axis)
             .y_axis(
                 Axis::new()
                     .title(Title::from("Returns"))
@@ -284,10 +496,14 @@ impl TickerCharts for Ticker {
                 Axis::new()
                     .tick_format(".0%")
             )
-            .y_axis3(
+            .x_axis3(
                 Axis::new()
-                    .title(Title::from("Cumulative Returns"))
-                    .tick_format(".0%")
+                    .matches("x")
+            )
+            .y_axis3(y_axis3)
+            .x_axis4(
+                Axis::new()
+                    .matches("x")
             )
             .y_axis4(
                 Axis::new()
@@ -295,6 +511,11 @@ impl TickerCharts for Ticker {
                     .tick_format(".0%")
             );
 
+        if let Some(events) = &options.events {
+            let (shapes, annotations) = event_annotations(events, "x3")?;
+            layout = layout.shapes(shapes).annotations(annotations);
+        }
+
         plot.set_layout(layout);
 
         Ok(plot)
@@ -386,58 +607,263 @@ impl TickerCharts for Ticker {
 
     /// Displays the Performance Statistics table for the ticker
     ///
+    /// With `bootstrap_samples` set on the ticker, the table adds the block bootstrap confidence bands of each statistic
+    ///
     /// # Returns
     ///
     /// * `DataTable` - Table Chart struct
     async fn performance_stats_table(&self) -> Result<DataTable, Box<dyn Error>> {
         let stats = self.performance_stats().await?;
 
-        let fields = vec![
-            "Daily Return".to_string(),
-            "Daily Volatility".to_string(),
-            "Cumulative Return".to_string(),
-            "Annualized Return".to_string(),
-            "Annualized Volatility".to_string(),
-            "Alpha".to_string(),
-            "Beta".to_string(),
-            "Sharpe Ratio".to_string(),
-            "Sortino Ratio".to_string(),
-            "Active Return".to_string(),
-            "Active Risk".to_string(),
-            "Information Ratio".to_string(),
-            "Calmar Ratio".to_string(),
-            "Maximum Drawdown".to_string(),
-            "Value At Risk".to_string(),
-            "Expected Shortfall".to_string(),
-        ];
+        let (fields, values) = performance_stats_rows(&stats.performance_stats);
 
-        let values = vec![
-            format!("{:.2}%",stats.performance_stats.daily_return),
-            format!("{:.2}%",stats.performance_stats.daily_volatility),
-            format!("{:.2}%",stats.performance_stats.cumulative_return),
-            format!("{:.2}%",stats.performance_stats.annualized_return),
-            format!("{:.2}%",stats.performance_stats.annualized_volatility),
-            format!("{:.2}",stats.performance_stats.alpha),
-            format!("{:.2}",stats.performance_stats.beta),
-            format!("{:.2}",stats.performance_stats.sharpe_ratio),
-            format!("{:.2}",stats.performance_stats.sortino_ratio),
-            format!("{:.2}%",stats.performance_stats.active_return),
-            format!("{:.2}%",stats.performance_stats.active_risk),
-            format!("{:.2}",stats.performance_stats.information_ratio),
-            format!("{:.2}",stats.performance_stats.calmar_ratio),
-            format!("{:.2}%",stats.performance_stats.maximum_drawdown),
-            format!("{:.2}%",stats.performance_stats.value_at_risk),
-            format!("{:.2}%",stats.performance_stats.expected_shortfall),
+        let mut columns = vec![
+            Series::new("Items", fields),
+            Series::new("Values", values),
         ];
+        if let Some(samples) = self.bootstrap_samples {
+            let bands = stats.bootstrap_bands(samples, None)?;
+            let confidence = bands.confidence_level * 100.0;
+            columns.push(Series::new(&format!("{:.0}% Lower", confidence), performance_stats_rows(&bands.lower).1));
+            columns.push(Series::new(&format!("{:.0}% Upper", confidence), performance_stats_rows(&bands.upper).1));
+        }
+        let df = DataFrame::new(columns)?;
+
+        let data_table = DataTable::new(df, TableType::PerformanceStats);
+
+        Ok(data_table)
+    }
+
+    /// Generates a Table of the Ticker's performance statistics against the hedged and unhedged benchmark
+    ///
+    /// # Returns
+    ///
+    /// * `Option<DataTable>` - None when the ticker and benchmark share a currency
+    async fn currency_hedged_stats_table(&self) -> Result<Option<DataTable>, Box<dyn Error>> {
+        let Some(stats) = self.currency_hedged_stats().await? else { return Ok(None) };
+        let (fields, hedged) = performance_stats_rows(&stats.hedged);
+        let (_, unhedged) = performance_stats_rows(&stats.unhedged);
 
         let df = DataFrame::new(vec![
             Series::new("Items", fields),
-            Series::new("Values", values),
+            Series::new(&format!("Hedged ({})", stats.benchmark_currency), hedged),
+            Series::new(&format!("Unhedged ({} via {})", stats.ticker_currency, stats.fx_symbol), unhedged),
         ])?;
 
-        let data_table = DataTable::new(df, TableType::PerformanceStats);
+        Ok(Some(DataTable::new(df, TableType::PerformanceStats)))
+    }
 
-        Ok(data_table)
+    /// Generates a Table of the significance of the Ticker's Sharpe ratio and alpha
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` Table Chart struct
+    async fn significance_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        let stats = self.performance_stats().await?;
+        significance_stats_table(&stats.significance()?)
+    }
+
+    /// Generates a Chart decomposing the Ticker's cumulative return into benchmark and active contributions
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn performance_decomposition_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let stats = self.performance_stats().await?;
+        performance_decomposition_chart(&stats.decomposition()?, &self.ticker, &stats.benchmark_symbol, height, width)
+    }
+
+    /// Generates a Table of the benchmark and active contributions to the Ticker's cumulative return
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` Table Chart struct
+    async fn performance_decomposition_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        let stats = self.performance_stats().await?;
+        performance_decomposition_table(&stats.decomposition()?)
+    }
+
+    /// Generates a Time of Day Profile Chart of the Ticker's intraday volume and volatility
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn time_of_day_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let profile = self.time_of_day_profile().await?;
+        let times = profile.column("time")?.str()?.into_no_null_iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let volume_share = profile.column("volume_share")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let volatility = profile.column("volatility")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let volume_trace = Bar::new(times.clone(), volume_share)
+            .name("Volume Share (%)");
+        let volatility_trace = Scatter::new(times, volatility)
+            .name("Volatility (%)")
+            .mode(Mode::LinesMarkers)
+            .y_axis("y2");
+
+        let mut plot = Plot::new();
+        plot.add_trace(volume_trace);
+        plot.add_trace(volatility_trace);
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Time of Day Profile</span>", &self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .x_axis(Axis::new()
+                .title("Time of Day (UTC)")
+                .type_(AxisType::Category))
+            .y_axis(Axis::new()
+                .title("Volume Share (%)")
+                .show_grid(false))
+            .y_axis2(Axis::new()
+                .title("Volatility (%)")
+                .show_grid(false)
+                .overlaying("y")
+                .side(AxisSide::Right)
+            );
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a Chart of the Ticker's overnight gap and intraday return in each session
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn session_gaps_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let gaps = self.session_gaps().await?;
+        let dates = gaps.column("date")?.str()?.into_no_null_iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let overnight = gaps.column("overnight_return")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let intraday = gaps.column("intraday_return")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let mut plot = Plot::new();
+        plot.add_trace(Bar::new(dates.clone(), overnight).name("Overnight Gap (%)"));
+        plot.add_trace(Bar::new(dates, intraday).name("Intraday Return (%)"));
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Session Gaps</span>", &self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .bar_mode(BarMode::Relative)
+            .x_axis(Axis::new().title("Session"))
+            .y_axis(Axis::new().title("Return (%)"));
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a Chart of the Ticker's cumulative overnight, intraday and close to close returns
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn overnight_intraday_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let split = self.overnight_intraday_split().await?;
+        let dates = split.column("timestamp")?.datetime()?.as_datetime_iter()
+            .map(|x| x.map(|d| d.to_string()).unwrap_or_default()).collect::<Vec<String>>();
+
+        let mut plot = Plot::new();
+        for (column, name) in [("close_to_close", "Close to Close"), ("overnight", "Overnight"), ("intraday", "Intraday")] {
+            let returns = split.column(column)?.f64()?.to_vec().iter()
+                .map(|x| x.filter(|r| r.is_finite()).unwrap_or(0.0)).collect::<Vec<f64>>();
+            let cum_returns = ReturnType::Simple.cumulative_returns(&returns);
+            plot.add_trace(Scatter::new(dates.clone(), cum_returns)
+                .name(name)
+                .mode(Mode::Lines));
+        }
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Overnight vs Intraday Returns</span>", &self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .x_axis(Axis::new().range_selector(range_selector()))
+            .y_axis(Axis::new()
+                .title(Title::from("Cumulative Returns"))
+                .tick_format(".0%"));
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates Chart of a Closed-End Fund's Price, NAV and Premium/Discount with its z-score
+    ///
+    /// # Arguments
+    ///
+    /// * `nav_symbol` - Symbol of the fund NAV (default - the Yahoo Finance NAV symbol, e.g. XPDIX for PDI)
+    /// * `window` - Number of trailing periods of the z-score (e.g. 252)
+    /// * `height` - usize - Height of the chart
+    /// * `width` - usize - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn cef_discount_chart(&self, nav_symbol: Option<&str>, window: usize, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let history = self.discount_history(nav_symbol, window).await?;
+        let dates = history.column("timestamp")?.datetime()?.as_datetime_iter()
+            .map(|x| x.map(|d| d.to_string()).unwrap_or_default()).collect::<Vec<String>>();
+        let column = |name: &str| -> Result<Vec<Option<f64>>, Box<dyn Error>> {
+            Ok(history.column(name)?.f64()?.into_iter().map(|x| x.filter(|v| v.is_finite())).collect())
+        };
+
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(dates.clone(), column("price")?)
+            .name("Price")
+            .mode(Mode::Lines));
+        plot.add_trace(Scatter::new(dates.clone(), column("nav")?)
+            .name("NAV")
+            .mode(Mode::Lines));
+        plot.add_trace(Scatter::new(dates.clone(), column("premium_discount")?)
+            .name("Premium/Discount")
+            .mode(Mode::Lines)
+            .fill(Fill::ToZeroY)
+            .x_axis("x2")
+            .y_axis("y2"));
+        plot.add_trace(Scatter::new(dates, column("z_score")?)
+            .name(format!("Z-Score ({} periods)", window))
+            .mode(Mode::Lines)
+            .line(Line::new().dash(DashType::Dot))
+            .x_axis("x2")
+            .y_axis("y3"));
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Premium/Discount to NAV</span>", &self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .grid(
+                LayoutGrid::new()
+                    .rows(2)
+                    .columns(1)
+                    .pattern(GridPattern::Independent)
+                    .row_order(RowOrder::TopToBottom)
+            )
+            .y_axis(Axis::new().title(Title::from("Price")))
+            .x_axis2(Axis::new().matches("x"))
+            .y_axis2(Axis::new()
+                .title(Title::from("Premium/Discount (%)")))
+            .y_axis3(Axis::new()
+                .title(Title::from("Z-Score"))
+                .overlaying("y2")
+                .side(AxisSide::Right));
+        plot.set_layout(layout);
+        Ok(plot)
     }
 
     /// Generates Table Plots for the Ticker's Financial Statements
@@ -485,73 +911,363 @@ impl TickerCharts for Ticker {
         })
     }
 
-    /// Generates Charts of the Ticker's Option Volatility Surface, Smile, and Term Structure
+    /// Generates a Waterfall Chart decomposing the latest period's revenue into operating and net income
     ///
     /// # Arguments
     ///
-    /// * `height` - `usize` - Height of the chart
-    /// * `width` - `usize` - Width of the chart
+    /// * `frequency` - `StatementFrequency` - Annual or Quarterly statements
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
     ///
     /// # Returns
     ///
-    /// * `OptionsCharts` - Options Charts struct
-    async fn options_charts(&self, height: Option<usize>, width: Option<usize>) -> Result<OptionsCharts, Box<dyn Error>> {
-        let vol_surface = self.volatility_surface().await?;
-        let symbol = vol_surface.symbol;
-        let ivols = vol_surface.ivols;
-        let strikes = vol_surface.strikes;
-        let ttms = vol_surface.ttms;
+    /// * `Plot` - Plotly Chart struct
+    async fn income_waterfall_chart(&self, frequency: StatementFrequency, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let (periods, breakdown) = income_breakdown(self, frequency).await?;
+        let latest = breakdown.last().ok_or(format!("No income statement data found for {}", self.ticker))?;
+        let period = periods.last().unwrap();
+        let [revenue, cost_of_revenue, operating_expenses, _, net_income] = *latest;
+        let gross_profit = revenue - cost_of_revenue;
+        let operating_income = gross_profit - operating_expenses;
+
+        // Each step spans from its start to its end value, totals start from zero
+        let steps = vec![
+            ("Revenue", 0.0, revenue, true),
+            ("Cost of Revenue", revenue, gross_profit, false),
+            ("Gross Profit", 0.0, gross_profit, true),
+            ("Operating Expenses", gross_profit, operating_income, false),
+            ("Operating Income", 0.0, operating_income, true),
+            ("Interest, Taxes & Other", operating_income, net_income, false),
+            ("Net Income", 0.0, net_income, true),
+        ];
 
-        // Volatility Surface
-        let trace = Surface::new(ivols.clone()).x(strikes.clone()).y(ttms.clone());
-        let mut surface_plot = Plot::new();
-        surface_plot.add_trace(trace);
+        let labels = steps.iter().map(|x| x.0.to_string()).collect::<Vec<String>>();
+        let mut base = Vec::new();
+        let mut positive = Vec::new();
+        let mut negative = Vec::new();
+        let mut colors = Vec::new();
+        for (_, start, end, total) in &steps {
+            let (low, high) = (start.min(*end), start.max(*end));
+            // Stack an invisible base under the visible segment so it floats between its start and end
+            if low >= 0.0 {
+                base.push(low);
+                positive.push(high - low);
+                negative.push(0.0);
+            } else if high <= 0.0 {
+                base.push(high);
+                positive.push(0.0);
+                negative.push(low - high);
+            } else {
+                base.push(0.0);
+                positive.push(high);
+                negative.push(low);
+            }
+            colors.push(if *total {
+                NamedColor::SteelBlue
+            } else if end >= start {
+                NamedColor::Green
+            } else {
+                NamedColor::Red
+            });
+        }
 
-        let layout = Layout::new()
-            .height(height.unwrap_or(DEFAULT_HEIGHT))
-            .width(width.unwrap_or(DEFAULT_WIDTH))
-            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{symbol} Volatility Surface</span>")))
-            .scene(
-                LayoutScene::new()
-                    .x_axis(
-                        Axis::new()
-                            .title(Title::from("Strike"))
-                    )
-                    .y_axis(
-                        Axis::new()
-                            .title(Title::from("Time to Maturity"))
-                    )
-                    .z_axis(Axis::new()
-                        .title(Title::from("Implied Volatility")))
+        let base_trace = Bar::new(labels.clone(), base)
+            .name("Base")
+            .show_legend(false)
+            .hover_info(HoverInfo::Skip)
+            .marker(Marker::new().color(Rgba::new(0, 0, 0, 0.0)));
 
-            );
-        surface_plot.set_layout(layout);
+        let values = steps.iter().map(|(_, start, end, total)| {
+            if *total { *end } else { end - start }
+        }).collect::<Vec<f64>>();
+        let text = values.iter().map(|x| format!("{:.2}B", x / 1e9)).collect::<Vec<String>>();
 
-        // Volatility Smile
-        let mut traces = Vec::new();
+        let positive_trace = Bar::new(labels.clone(), positive)
+            .name(period.as_str())
+            .text_array(text.clone())
+            .marker(Marker::new().color_array(colors.clone()));
 
-        for (index, ttm) in ttms.iter().enumerate() {
-            let ivols = ivols[index].clone();
-            let trace = Scatter::new(strikes.clone(), ivols)
-                .mode(Mode::LinesMarkers)
-                .line(Line::new().shape(LineShape::Spline))
-                .name(&*format!("Volatility Smile - {:.1} Months Expiration", ttm));
+        let negative_trace = Bar::new(labels, negative)
+            .name(period.as_str())
+            .show_legend(false)
+            .marker(Marker::new().color_array(colors));
 
-            traces.push(trace);
-        }
+        let mut plot = Plot::new();
+        plot.add_trace(base_trace);
+        plot.add_trace(positive_trace);
+        plot.add_trace(negative_trace);
 
         let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} {} Income Waterfall Chart</span>", self.ticker, period)))
             .height(height.unwrap_or(DEFAULT_HEIGHT))
             .width(width.unwrap_or(DEFAULT_WIDTH))
-            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{symbol} Volatility Smile</span>")))
-            .x_axis(Axis::new().title(Title::from("Strike")))
-            .y_axis(Axis::new().title(Title::from("Implied Volatility")));
-
-        let mut smile_plot = Plot::new();
-        for trace in traces {
-            smile_plot.add_trace(trace);
-        }
-        smile_plot.set_layout(layout);
+            .bar_mode(BarMode::Relative)
+            .show_legend(false)
+            .y_axis(Axis::new()
+                .title("Amount"));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates a Stacked Chart of the revenue breakdown into expenses and net income across periods
+    ///
+    /// # Arguments
+    ///
+    /// * `frequency` - `StatementFrequency` - Annual or Quarterly statements
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn income_trend_chart(&self, frequency: StatementFrequency, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let (periods, breakdown) = income_breakdown(self, frequency).await?;
+        let components = ["Cost of Revenue", "Operating Expenses", "Interest, Taxes & Other", "Net Income"];
+
+        let mut plot = Plot::new();
+        for (i, component) in components.iter().enumerate() {
+            let values = breakdown.iter().map(|x| x[i + 1]).collect::<Vec<f64>>();
+            let trace = Bar::new(periods.clone(), values)
+                .name(*component);
+            plot.add_trace(trace);
+        }
+        let revenue = breakdown.iter().map(|x| x[0]).collect::<Vec<f64>>();
+        let revenue_trace = Scatter::new(periods, revenue)
+            .mode(Mode::LinesMarkers)
+            .name("Revenue");
+        plot.add_trace(revenue_trace);
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Income Trend Chart</span>", self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .bar_mode(BarMode::Relative)
+            .x_axis(Axis::new()
+                .title("Period"))
+            .y_axis(Axis::new()
+                .title("Amount"));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates a Chart of the Ticker's revenue, free cash flow and book value per share, with the share count
+    ///
+    /// # Arguments
+    ///
+    /// * `frequency` - `StatementFrequency` - Frequency of the financial statements
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn per_share_chart(&self, frequency: StatementFrequency, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let (periods, series) = per_share_series(self, frequency).await?;
+
+        let mut plot = Plot::new();
+        for (name, values) in series.iter() {
+            if *name == "Shares Outstanding" {
+                plot.add_trace(Bar::new(periods.clone(), values.clone())
+                    .name(*name)
+                    .opacity(0.3)
+                    .y_axis("y2"));
+            } else {
+                plot.add_trace(Scatter::new(periods.clone(), values.clone())
+                    .mode(Mode::LinesMarkers)
+                    .name(*name));
+            }
+        }
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Per Share Fundamentals</span>", self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .x_axis(Axis::new()
+                .title("Period"))
+            .y_axis(Axis::new()
+                .title("Per Share"))
+            .y_axis2(Axis::new()
+                .title("Shares Outstanding")
+                .overlaying("y")
+                .side(AxisSide::Right)
+                .show_grid(false));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates a Chart of how the consensus EPS estimate of each period moved over the last 90 days
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn estimate_trend_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let trend = self.get_earnings_trend().await?;
+        let periods = trend.column("period")?.str()?.into_no_null_iter().collect::<Vec<&str>>();
+        let end_dates = trend.column("end_date")?.str()?.into_no_null_iter().collect::<Vec<&str>>();
+        let columns = ["eps_90d_ago", "eps_60d_ago", "eps_30d_ago", "eps_7d_ago", "eps_current"];
+        let labels = vec!["90 Days Ago", "60 Days Ago", "30 Days Ago", "7 Days Ago", "Current"];
+        let trends = columns.iter()
+            .map(|c| Ok(trend.column(c)?.cast(&DataType::Float64)?.f64()?.into_iter().collect::<Vec<Option<f64>>>()))
+            .collect::<Result<Vec<Vec<Option<f64>>>, Box<dyn Error>>>()?;
+
+        let mut plot = Plot::new();
+        for (i, (period, end_date)) in periods.iter().zip(&end_dates).enumerate() {
+            let label = match *period {
+                "0q" => "Current Quarter",
+                "+1q" => "Next Quarter",
+                "0y" => "Current Year",
+                "+1y" => "Next Year",
+                other => other,
+            };
+            plot.add_trace(Scatter::new(labels.clone(), trends.iter().map(|x| x[i]).collect::<Vec<Option<f64>>>())
+                .mode(Mode::LinesMarkers)
+                .name(format!("{} ({})", label, end_date)));
+        }
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} EPS Estimate Trend</span>", self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .x_axis(Axis::new()
+                .title("Estimate Date"))
+            .y_axis(Axis::new()
+                .title("Consensus EPS"));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates a stacked Chart of the annual revenue by business or geographic segment
+    ///
+    /// # Arguments
+    ///
+    /// * `segment_type` - Business or geographic segments
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn revenue_segments_chart(&self, segment_type: SegmentType, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let breakdown = self.revenue_segments(segment_type).await?;
+        let segments = breakdown.column("Items")?.str()?.into_no_null_iter().collect::<Vec<&str>>();
+        let years = breakdown.get_column_names().into_iter().skip(1).map(|x| x.to_string()).collect::<Vec<String>>();
+        let totals = years.iter()
+            .map(|year| Ok(breakdown.column(year)?.f64()?.sum().unwrap_or(0.0)))
+            .collect::<Result<Vec<f64>, Box<dyn Error>>>()?;
+
+        let mut plot = Plot::new();
+        for (i, segment) in segments.iter().enumerate() {
+            let revenue = years.iter()
+                .map(|year| Ok(breakdown.column(year)?.f64()?.get(i)))
+                .collect::<Result<Vec<Option<f64>>, Box<dyn Error>>>()?;
+            let shares = revenue.iter().zip(totals.iter())
+                .map(|(r, t)| r.filter(|_| *t != 0.0).map_or(String::new(), |r| format!("{:.1}%", r / t * 100.0)))
+                .collect::<Vec<String>>();
+            plot.add_trace(Bar::new(years.clone(), revenue)
+                .name(*segment)
+                .text_array(shares));
+        }
+
+        let title = match segment_type {
+            SegmentType::Business => "Revenue by Business Segment",
+            SegmentType::Geographic => "Revenue by Geography",
+        };
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} {}</span>", self.ticker, title)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .bar_mode(BarMode::Stack)
+            .x_axis(Axis::new()
+                .title("Fiscal Year")
+                .type_(AxisType::Category))
+            .y_axis(Axis::new()
+                .title("Revenue"));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates Charts of the Ticker's Option Volatility Surface, Smile, and Term Structure
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `OptionsCharts` - Options Charts struct
+    async fn options_charts(&self, height: Option<usize>, width: Option<usize>) -> Result<OptionsCharts, Box<dyn Error>> {
+        let vol_surface = self.volatility_surface().await?;
+        let symbol = vol_surface.symbol;
+        let ivols = vol_surface.ivols;
+        let strikes = vol_surface.strikes;
+        let ttms = vol_surface.ttms;
+
+        // Volatility Surface
+        let trace = Surface::new(ivols.clone()).x(strikes.clone()).y(ttms.clone());
+        let mut surface_plot = Plot::new();
+        surface_plot.add_trace(trace);
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{symbol} Volatility Surface</span>")))
+            .scene(
+                LayoutScene::new()
+                    .x_axis(
+                        Axis::new()
+                            .title(Title::from("Strike"))
+                    )
+                    .y_axis(
+                        Axis::new()
+                            .title(Title::from("Time to Maturity"))
+                    )
+                    .z_axis(Axis::new()
+                        .title(Title::from("Implied Volatility")))
+
+            );
+        surface_plot.set_layout(layout);
+
+        // Volatility Smile
+        let mut traces = Vec::new();
+
+        for (index, ttm) in ttms.iter().enumerate() {
+            let ivols = ivols[index].clone();
+            let trace = Scatter::new(strikes.clone(), ivols)
+                .mode(Mode::LinesMarkers)
+                .line(Line::new().shape(LineShape::Spline))
+                .name(&*format!("Volatility Smile - {:.1} Months Expiration", ttm));
+
+            traces.push(trace);
+        }
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{symbol} Volatility Smile</span>")))
+            .x_axis(Axis::new().title(Title::from("Strike")))
+            .y_axis(Axis::new().title(Title::from("Implied Volatility")));
+
+        let mut smile_plot = Plot::new();
+        for trace in traces {
+            smile_plot.add_trace(trace);
+        }
+        smile_plot.set_layout(layout);
 
 
         // Volatility Term Structure
@@ -618,6 +1334,395 @@ impl TickerCharts for Ticker {
         })
     }
 
+    /// Generates a candlestick chart with the price range implied by the options market until each expiration
+    ///
+    /// The cone spans the spot price plus and minus the at-the-money straddle price of the expirations
+    /// within a year.
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn expected_move_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let moves = self.expected_move().await?;
+        let mut plot = self.candlestick_chart(height, width).await?;
+
+        let data = self.get_chart().await?;
+        let last_date = data.column("timestamp")?.datetime()?.as_datetime_iter().last().flatten()
+            .ok_or("No price data")?;
+        let spot = data.column("close")?.f64()?.into_iter().last().flatten().ok_or("No price data")?;
+
+        let days = moves.column("days")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let within_year = days.iter().take_while(|x| **x <= 365.0).count().max(1);
+        let expirations = moves.column("expiration")?.str()?.into_no_null_iter()
+            .take(within_year).map(|x| format!("{} 00:00:00", x)).collect::<Vec<String>>();
+        let expected = moves.column("expected_move")?.f64()?.into_no_null_iter()
+            .take(within_year).collect::<Vec<f64>>();
+
+        let mut x = vec![last_date.to_string()];
+        x.extend(expirations);
+        let mut upper = vec![spot];
+        upper.extend(expected.iter().map(|m| spot + m));
+        let mut lower = vec![spot];
+        lower.extend(expected.iter().map(|m| spot - m));
+
+        plot.add_trace(Scatter::new(x.clone(), upper)
+            .name("Expected Move (Upper)")
+            .mode(Mode::LinesMarkers)
+            .line(Line::new().color(NamedColor::DarkOrange).dash(DashType::Dash)));
+        plot.add_trace(Scatter::new(x, lower)
+            .name("Expected Move (Lower)")
+            .mode(Mode::LinesMarkers)
+            .fill(Fill::ToNextY)
+            .fill_color(Rgba::new(255, 140, 0, 0.15))
+            .line(Line::new().color(NamedColor::DarkOrange).dash(DashType::Dash)));
+
+        let symbol = &self.ticker;
+        let layout = plot.layout().clone()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{symbol} Expected Move</span>")));
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a bar chart of call and put open interest by strike, with the spot price marked
+    ///
+    /// Strikes with the largest open interest (open interest walls) tend to act as support and resistance
+    /// into expiration. Only strikes within 50% of spot are shown.
+    ///
+    /// # Arguments
+    ///
+    /// * `expiration` - `Option<&str>` - Expiration date (YYYY-MM-DD) to chart, all expirations are summed when None
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn open_interest_chart(&self, expiration: Option<&str>, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let spot = self.get_options().await?.ticker_price;
+        let oi = self.open_interest_by_strike(expiration).await?;
+        let oi = oi.lazy()
+            .filter(col("strike").gt_eq(lit(spot * 0.5)).and(col("strike").lt_eq(lit(spot * 1.5))))
+            .collect()?;
+
+        let strikes = oi.column("strike")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let calls = oi.column("call_open_interest")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let puts = oi.column("put_open_interest")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let max_oi = calls.iter().chain(puts.iter()).cloned().fold(0.0, f64::max);
+
+        let mut plot = Plot::new();
+        plot.add_trace(Bar::new(strikes.clone(), calls)
+            .name("Call Open Interest")
+            .marker(Marker::new().color(NamedColor::SeaGreen)));
+        plot.add_trace(Bar::new(strikes, puts)
+            .name("Put Open Interest")
+            .marker(Marker::new().color(NamedColor::IndianRed)));
+
+        let symbol = &self.ticker;
+        let title = match expiration {
+            Some(e) => format!("{symbol} Open Interest by Strike ({e})"),
+            None => format!("{symbol} Open Interest by Strike"),
+        };
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{title}</span>")))
+            .bar_mode(BarMode::Group)
+            .x_axis(Axis::new().title(Title::from("Strike")))
+            .y_axis(Axis::new().title(Title::from("Open Interest")))
+            .shapes(vec![Shape::new()
+                .shape_type(ShapeType::Line)
+                .x0(spot)
+                .x1(spot)
+                .y0(0)
+                .y1(max_oi)
+                .line(ShapeLine::new().color(NamedColor::Black).width(1.5).dash(DashType::Dash))])
+            .annotations(vec![Annotation::new()
+                .x(spot)
+                .y(max_oi)
+                .text(format!("Spot {:.2}", spot))
+                .show_arrow(false)
+                .y_shift(10.0)]);
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates the implied volatility smile of the calls and puts of a single expiration
+    ///
+    /// # Arguments
+    ///
+    /// * `expiration` - Expiration date (YYYY-MM-DD), the nearest expiration when None
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn expiration_smile_chart(&self, expiration: Option<&str>, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let options = self.get_options().await?;
+        let spot = options.ticker_price;
+        let expiration = match expiration {
+            Some(e) => e.to_string(),
+            None => options.expiration_dates.first().cloned().ok_or("No expirations found")?,
+        };
+        let greeks = chain_greeks(&options, Some(&expiration), self.risk_free_rate)?;
+        let greeks = greeks.lazy()
+            .filter(col("strike").gt_eq(lit(spot * 0.5))
+                .and(col("strike").lt_eq(lit(spot * 1.5)))
+                .and(col("implied_volatility").gt(lit(0.0))))
+            .collect()?;
+
+        let mut plot = Plot::new();
+        for (option_type, name, color) in [("call", "Calls", NamedColor::SeaGreen), ("put", "Puts", NamedColor::IndianRed)] {
+            let contracts = greeks.clone().lazy().filter(col("type").eq(lit(option_type))).collect()?;
+            let strikes = contracts.column("strike")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+            let ivols = contracts.column("implied_volatility")?.f64()?.into_no_null_iter()
+                .map(|x| x * 100.0).collect::<Vec<f64>>();
+            plot.add_trace(Scatter::new(strikes, ivols)
+                .name(name)
+                .mode(Mode::LinesMarkers)
+                .line(Line::new().color(color)));
+        }
+
+        let symbol = &self.ticker;
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{symbol} Volatility Smile ({expiration})</span>")))
+            .x_axis(Axis::new().title(Title::from("Strike")))
+            .y_axis(Axis::new().title(Title::from("Implied Volatility (%)")))
+            .shapes(vec![Shape::new()
+                .shape_type(ShapeType::Line)
+                .x_ref("x")
+                .y_ref("paper")
+                .x0(spot)
+                .x1(spot)
+                .y0(0)
+                .y1(1)
+                .line(ShapeLine::new().color(NamedColor::Black).width(1.5).dash(DashType::Dash))]);
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a gauge of the put/call open interest and volume ratios over the whole options chain
+    ///
+    /// Ratios below 0.7 are read as bullish positioning, ratios above 1.0 as bearish.
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn put_call_sentiment_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let ratios = self.put_call_ratios().await?;
+        let total = ratios.height() - 1;
+        let oi_ratio = ratios.column("open_interest_ratio")?.f64()?.get(total);
+        let volume_ratio = ratios.column("volume_ratio")?.f64()?.get(total);
+        let oi_ratio = oi_ratio.ok_or("No call open interest to compute the put/call ratio")?;
+
+        let scale_max = oi_ratio.max(volume_ratio.unwrap_or(0.0)).max(2.0) * 1.1;
+        let zones = [
+            (0.0, BULLISH_PUT_CALL_RATIO, Rgba::new(46, 139, 87, 0.35)),
+            (BULLISH_PUT_CALL_RATIO, BEARISH_PUT_CALL_RATIO, Rgba::new(128, 128, 128, 0.25)),
+            (BEARISH_PUT_CALL_RATIO, scale_max, Rgba::new(205, 92, 92, 0.35)),
+        ];
+        let shapes = zones.iter()
+            .map(|(x0, x1, color)| Shape::new()
+                .shape_type(ShapeType::Rect)
+                .y_ref("paper")
+                .x0(*x0)
+                .x1(*x1)
+                .y0(0)
+                .y1(1)
+                .fill_color(*color)
+                .line(ShapeLine::new().width(0.0)))
+            .collect::<Vec<Shape>>();
+        let annotations = zones.iter().map(|(x0, x1, _)| (x0 + x1) / 2.0)
+            .map(|x| Annotation::new()
+                .x(x)
+                .y_ref("paper")
+                .y(1)
+                .text(put_call_sentiment(x))
+                .show_arrow(false)
+                .y_shift(10.0))
+            .collect::<Vec<Annotation>>();
+
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(vec![oi_ratio], vec!["Open Interest"])
+            .name("Open Interest Ratio")
+            .mode(Mode::MarkersText)
+            .text(format!("{:.2} ({})", oi_ratio, put_call_sentiment(oi_ratio)))
+            .text_position(Position::TopCenter)
+            .marker(Marker::new().size(18).color(NamedColor::Black)));
+        if let Some(volume_ratio) = volume_ratio {
+            plot.add_trace(Scatter::new(vec![volume_ratio], vec!["Volume"])
+                .name("Volume Ratio")
+                .mode(Mode::MarkersText)
+                .text(format!("{:.2} ({})", volume_ratio, put_call_sentiment(volume_ratio)))
+                .text_position(Position::TopCenter)
+                .marker(Marker::new().size(18).color(NamedColor::DarkBlue)));
+        }
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Put/Call Sentiment</span>", &self.ticker)))
+            .x_axis(Axis::new().title(Title::from("Put/Call Ratio")).range(vec![0.0, scale_max]))
+            .show_legend(false)
+            .shapes(shapes)
+            .annotations(annotations);
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a chart of the max pain strike of each expiration against the current spot price
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn max_pain_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let spot = self.get_options().await?.ticker_price;
+        let pain = self.max_pain().await?;
+        let expirations = pain.column("expiration")?.str()?.into_no_null_iter()
+            .map(|x| x.to_string()).collect::<Vec<String>>();
+        let strikes = pain.column("max_pain_strike")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(expirations.clone(), strikes)
+            .name("Max Pain Strike")
+            .mode(Mode::LinesMarkers)
+            .line(Line::new().color(NamedColor::DarkOrange)));
+        plot.add_trace(Scatter::new(expirations.clone(), vec![spot; expirations.len()])
+            .name(format!("Spot ({:.2})", spot))
+            .mode(Mode::Lines)
+            .line(Line::new().color(NamedColor::Black).dash(DashType::Dash)));
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Max Pain by Expiration</span>", &self.ticker)))
+            .x_axis(Axis::new().title(Title::from("Expiration")))
+            .y_axis(Axis::new().title(Title::from("Strike")));
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates a chart of the at-the-money implied volatility by days to expiration against the realized
+    /// volatility over trailing windows, with the volatility risk premium of each expiration
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn volatility_comparison_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let premium = self.volatility_risk_premium().await?;
+        let realized = self.realized_volatility(None).await?;
+        let percent = |df: &DataFrame, name: &str| -> Result<Vec<Option<f64>>, Box<dyn Error>> {
+            Ok(df.column(name)?.f64()?.into_iter().map(|x| x.map(|v| v * 100.0)).collect())
+        };
+
+        let days = premium.column("days")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let window_days = realized.column("calendar_days")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(days.clone(), percent(&premium, "atm_iv")?)
+            .name("ATM Implied Volatility")
+            .mode(Mode::LinesMarkers)
+            .line(Line::new().color(NamedColor::DarkOrange)));
+        plot.add_trace(Scatter::new(window_days, percent(&realized, "realized_volatility")?)
+            .name("Realized Volatility")
+            .mode(Mode::LinesMarkers)
+            .line(Line::new().color(NamedColor::SteelBlue)));
+        plot.add_trace(Bar::new(days, percent(&premium, "volatility_risk_premium")?)
+            .name("Volatility Risk Premium")
+            .opacity(0.4)
+            .marker(Marker::new().color(NamedColor::Gray)));
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Implied vs Realized Volatility</span>", &self.ticker)))
+            .x_axis(Axis::new().title(Title::from("Days")))
+            .y_axis(Axis::new().title(Title::from("Volatility (%)")));
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Generates the payoff at expiration of the top ranked covered call or cash-secured put candidates
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - `IncomeStrategy` - Covered call or cash-secured put
+    /// * `cost_basis` - `Option<f64>` - Cost per share of the holding, defaults to the spot price (covered call only)
+    /// * `expiration` - `Option<&str>` - Expiration date (YYYY-MM-DD) to evaluate, all upcoming expirations when None
+    /// * `top_n` - `Option<usize>` - Number of candidates to chart (default 3)
+    /// * `height` - `usize` - Height of the chart
+    /// * `width` - `usize` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` Plotly Chart struct
+    async fn income_payoff_chart(&self, strategy: IncomeStrategy, cost_basis: Option<f64>, expiration: Option<&str>,
+                                 top_n: Option<usize>, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let spot = self.get_options().await?.ticker_price;
+        let cost = cost_basis.unwrap_or(spot);
+        let candidates = self.income_candidates(strategy, cost_basis, expiration).await?;
+        let top_n = top_n.unwrap_or(3).min(candidates.height());
+        let symbols = candidates.column("contractSymbol")?.str()?.into_no_null_iter().take(top_n)
+            .map(|x| x.to_string()).collect::<Vec<String>>();
+        let strikes = candidates.column("strike")?.f64()?.into_no_null_iter().take(top_n).collect::<Vec<f64>>();
+        let premiums = candidates.column("premium")?.f64()?.into_no_null_iter().take(top_n).collect::<Vec<f64>>();
+
+        let prices = (0..=100).map(|i| spot * (0.7 + 0.6 * i as f64 / 100.0)).collect::<Vec<f64>>();
+        let mut plot = Plot::new();
+        if strategy == IncomeStrategy::CoveredCall {
+            plot.add_trace(Scatter::new(prices.clone(), prices.iter().map(|p| p - cost).collect::<Vec<f64>>())
+                .name("Shares Only")
+                .mode(Mode::Lines)
+                .line(Line::new().color(NamedColor::Gray).dash(DashType::Dot)));
+        }
+        for ((symbol, strike), premium) in symbols.iter().zip(&strikes).zip(&premiums) {
+            let payoff = prices.iter().map(|p| strategy.payoff(*p, *strike, *premium, cost)).collect::<Vec<f64>>();
+            plot.add_trace(Scatter::new(prices.clone(), payoff)
+                .name(format!("{} (K={:.2}, premium {:.2})", symbol, strike, premium))
+                .mode(Mode::Lines));
+        }
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} {} Payoff at Expiration</span>",
+                                          &self.ticker, strategy.to_string())))
+            .x_axis(Axis::new().title(Title::from("Underlying Price at Expiration")))
+            .y_axis(Axis::new().title(Title::from("Profit per Share")).zero_line(true))
+            .shapes(vec![Shape::new()
+                .shape_type(ShapeType::Line)
+                .y_ref("paper")
+                .x0(spot)
+                .x1(spot)
+                .y0(0)
+                .y1(1)
+                .line(ShapeLine::new().color(NamedColor::Black).width(1.0).dash(DashType::Dash))]);
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
     /// Generates a News Sentiment Chart for the Ticker
     ///
     /// # Arguments
@@ -691,4 +1796,378 @@ impl TickerCharts for Ticker {
 
         Ok(plot)
     }
+
+    /// Generates a Keyword Frequency Chart of the Ticker News topics
+    ///
+    /// # Arguments
+    ///
+    /// * `top_n` - `Option<usize>` - Number of keywords to show (default 20)
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn news_keywords_chart(&self, top_n: Option<usize>, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let news = self.get_news().await?;
+        let keywords = news_keywords(&news, &[&self.ticker.to_lowercase()], top_n.unwrap_or(DEFAULT_TOP_KEYWORDS))?;
+
+        // Reverse so the top keyword is drawn at the top of the horizontal bars
+        let labels = keywords.column("keyword")?.str()?.into_no_null_iter()
+            .map(|x| x.to_string()).rev().collect::<Vec<String>>();
+        let scores = keywords.column("score")?.f64()?.into_no_null_iter().rev().collect::<Vec<f64>>();
+        let articles = keywords.column("articles")?.u32()?.into_no_null_iter().rev()
+            .map(|x| format!("{} articles", x)).collect::<Vec<String>>();
+
+        let trace = Bar::new(scores, labels)
+            .orientation(Orientation::Horizontal)
+            .name("TF-IDF Score")
+            .hover_text_array(articles)
+            .marker(Marker::new().color(NamedColor::DarkGreen));
+
+        let mut plot = Plot::new();
+        plot.add_trace(trace);
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} News Topics</span>", &self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .x_axis(Axis::new()
+                .title("TF-IDF Score")
+                .color("purple")
+                .show_grid(false))
+            .y_axis(Axis::new()
+                .color("purple")
+                .show_grid(false));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates a Price Forecast Chart with the prediction interval for the Ticker
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Forecast model (e.g. ForecastModel::AutoArima)
+    /// * `horizon` - `Option<usize>` - Number of periods to forecast (default 20)
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn forecast_chart(&self, model: ForecastModel, horizon: Option<usize>, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let forecast = self.forecast(model, horizon).await?;
+        let ohlcv = self.get_chart().await?;
+        let price_col = self.price_type.to_string();
+        let format_dates = |df: &DataFrame| -> Result<Vec<String>, Box<dyn Error>> {
+            Ok(df.column("timestamp")?.datetime()?.as_datetime_iter()
+                .map(|x| x.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default())
+                .collect::<Vec<String>>())
+        };
+        let dates = format_dates(&ohlcv)?;
+        let prices = ohlcv.column(&price_col)?.f64()?.to_vec();
+        let forecast_dates = format_dates(&forecast.forecast)?;
+        let column = |name: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+            Ok(forecast.forecast.column(name)?.f64()?.into_no_null_iter().collect::<Vec<f64>>())
+        };
+
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(dates, prices)
+            .name(&price_col)
+            .mode(Mode::Lines)
+            .line(Line::new().color(NamedColor::Blue)));
+        plot.add_trace(Scatter::new(forecast_dates.clone(), column("lower")?)
+            .mode(Mode::Lines)
+            .line(Line::new().width(0.0))
+            .show_legend(false)
+            .name("Lower Bound"));
+        plot.add_trace(Scatter::new(forecast_dates.clone(), column("upper")?)
+            .mode(Mode::Lines)
+            .line(Line::new().width(0.0))
+            .fill(Fill::ToNextY)
+            .fill_color("rgba(0, 100, 0, 0.2)")
+            .name(format!("{:.0}% Prediction Interval", forecast.confidence_level * 100.0)));
+        plot.add_trace(Scatter::new(forecast_dates, column("forecast")?)
+            .name("Forecast")
+            .mode(Mode::Lines)
+            .line(Line::new().color(NamedColor::DarkGreen).dash(DashType::Dash)));
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Price Forecast - {}</span>", &self.ticker, forecast.model)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .x_axis(Axis::new()
+                .title("Date")
+                .color("purple")
+                .show_grid(false)
+                .range_selector(range_selector()))
+            .y_axis(Axis::new()
+                .title("Price")
+                .color("purple")
+                .show_grid(false));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates an Insider Activity Chart for the Ticker
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn insider_activity_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let data = self.get_insider_transactions().await?;
+        let dates = data.column("timestamp")?.datetime()?
+            .into_no_null_iter().map(|x| DateTime::from_timestamp_millis(x).unwrap()
+            .naive_local().format("%Y-%m").to_string()).collect::<Vec<String>>();
+        let shares = data.column("shares")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        // Aggregate purchases and sales by month
+        let mut monthly: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+        for (date, share) in dates.iter().zip(shares.iter()) {
+            let entry = monthly.entry(date.clone()).or_insert((0.0, 0.0));
+            if *share > 0.0 {
+                entry.0 += share;
+            } else {
+                entry.1 += share;
+            }
+        }
+        let months = monthly.keys().cloned().collect::<Vec<String>>();
+        let bought = monthly.values().map(|x| x.0).collect::<Vec<f64>>();
+        let sold = monthly.values().map(|x| x.1).collect::<Vec<f64>>();
+        let cumulative_net = monthly.values().scan(0.0, |acc, x| {
+            *acc += x.0 + x.1;
+            Some(*acc)
+        }).collect::<Vec<f64>>();
+
+        let bought_trace = Bar::new(months.clone(), bought)
+            .name("Shares Bought");
+
+        let sold_trace = Bar::new(months.clone(), sold)
+            .name("Shares Sold");
+
+        let net_trace = Scatter::new(months, cumulative_net)
+            .mode(Mode::LinesMarkers)
+            .name("Cumulative Net Shares")
+            .y_axis("y2");
+
+        let mut plot = Plot::new();
+        plot.add_trace(bought_trace);
+        plot.add_trace(sold_trace);
+        plot.add_trace(net_trace);
+
+        let layout = Layout::new()
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Insider Activity Chart</span>", &self.ticker)))
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .bar_mode(BarMode::Relative)
+            .x_axis(Axis::new()
+                .title("Month"))
+            .y_axis(Axis::new()
+                .title("Shares Traded"))
+            .y_axis2(Axis::new()
+                .title("Cumulative Net Shares")
+                .overlaying("y")
+                .side(AxisSide::Right)
+            );
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Displays the key profile fields of the ticker for its asset class
+    /// (fund profile for ETFs and mutual funds, supply metrics for crypto, contract specs for futures)
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` - Table Chart struct
+    async fn asset_profile_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        let quote_type = self.get_quote_type().await?;
+        let fields = asset_profile_fields(quote_type);
+        let names = fields.iter().map(|x| x.0.to_string()).collect::<Vec<String>>();
+        let values = fetch_quote_fields(&self.ticker, &names).await?;
+
+        let values = values.iter().zip(fields.iter()).map(|(value, (_, _, format))| {
+            match (value, format) {
+                (Value::Null, _) => "-".to_string(),
+                (Value::String(s), _) => s.clone(),
+                (v, FieldFormat::Percent) => format!("{:.2}%", v.as_f64().unwrap_or_default() * 100.0),
+                (v, FieldFormat::Date) => to_date(v.as_i64().unwrap_or_default()),
+                (v, FieldFormat::Number) => {
+                    let x = v.as_f64().unwrap_or_default();
+                    if x.abs() >= 1e6 { (x as i64).to_formatted_string(&Locale::en) } else { format!("{:.2}", x) }
+                }
+                (v, FieldFormat::Text) => v.to_string(),
+            }
+        }).collect::<Vec<String>>();
+
+        let df = DataFrame::new(vec![
+            Series::new("Items", fields.iter().map(|x| x.1).collect::<Vec<&str>>()),
+            Series::new("Values", values),
+        ])?;
+
+        Ok(DataTable::new(df, TableType::SummaryStats))
+    }
+
+    /// Displays the top holdings of an ETF or mutual fund
+    ///
+    /// # Returns
+    ///
+    /// * `DataTable` - Interactive Table Chart struct
+    async fn fund_holdings_table(&self) -> Result<DataTable, Box<dyn Error>> {
+        let mut data = self.get_fund_holdings().await?;
+        data.rename("symbol", "Symbol")?;
+        data.rename("name", "Name")?;
+        data.rename("weight", "Weight (%)")?;
+        Ok(DataTable::new(data, TableType::FundHoldings))
+    }
+
+    /// Generates a Bar Chart of the sector weightings of an ETF or mutual fund
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn fund_sectors_chart(&self, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let data = self.get_fund_sectors().await?;
+        let sectors = data.column("sector")?.str()?.into_no_null_iter()
+            .map(|x| x.replace('_', " ")).collect::<Vec<String>>();
+        let weights = data.column("weight")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let trace = Bar::new(sectors, weights.clone())
+            .name("Sector Weight")
+            .text_array(weights.iter().map(|w| format!("{:.2}%", w)).collect::<Vec<String>>());
+
+        let mut plot = Plot::new();
+        plot.add_trace(trace);
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Sector Weightings</span>", self.ticker)))
+            .y_axis(Axis::new().title(Title::from("Weight (%)")));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates a Chart of the circulating supply (with the max supply) and market cap of a cryptocurrency
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Provider of the market data (e.g. CryptoDataSource::CoinGecko)
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn crypto_supply_chart(&self, source: CryptoDataSource, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let data = self.supply_history(source).await?;
+        let dates = data.column("timestamp")?.datetime()?.as_datetime_iter()
+            .map(|x| x.unwrap().to_string()).collect::<Vec<String>>();
+        let supply = data.column("circulating_supply")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let market_cap = data.column("market_cap")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let max_supply = data.column("max_supply")?.f64()?.get(0);
+
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(dates.clone(), supply).name("Circulating Supply").mode(Mode::Lines));
+        if let Some(max_supply) = max_supply {
+            plot.add_trace(Scatter::new(dates.clone(), vec![max_supply; dates.len()])
+                .name("Max Supply")
+                .mode(Mode::Lines)
+                .line(Line::new().dash(DashType::Dash)));
+        }
+        plot.add_trace(Scatter::new(dates, market_cap).name("Market Cap").mode(Mode::Lines).y_axis("y2"));
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Supply and Market Cap</span>", self.ticker)))
+            .y_axis(Axis::new().title(Title::from("Supply")))
+            .y_axis2(Axis::new().title(Title::from("Market Cap")).overlaying("y").side(AxisSide::Right));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+
+    /// Generates a Chart of the market dominance and volume share of a cryptocurrency within a universe of cryptocurrencies
+    ///
+    /// # Arguments
+    ///
+    /// * `universe` - Symbols of the reference universe (default - DEFAULT_CRYPTO_UNIVERSE)
+    /// * `source` - Provider of the market data (e.g. CryptoDataSource::Yahoo)
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn crypto_dominance_chart(&self, universe: Option<Vec<&str>>, source: CryptoDataSource, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let data = self.market_dominance(universe, source).await?;
+        let dates = data.column("timestamp")?.datetime()?.as_datetime_iter()
+            .map(|x| x.unwrap().to_string()).collect::<Vec<String>>();
+        let dominance = data.column("dominance")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+        let volume_share = data.column("volume_share")?.f64()?.into_no_null_iter().collect::<Vec<f64>>();
+
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(dates.clone(), dominance).name("Market Dominance").mode(Mode::Lines));
+        plot.add_trace(Scatter::new(dates, volume_share).name("Volume Share").mode(Mode::Lines));
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">{} Market Dominance and Volume Share</span>", self.ticker)))
+            .y_axis(Axis::new().title(Title::from("Share of Universe (%)")));
+
+        plot.set_layout(layout);
+
+        Ok(plot)
+    }
+}
+
+/// Breaks down each period's revenue into cost of revenue, operating expenses,
+/// interest, taxes & other items and net income, in chronological order
+async fn income_breakdown(ticker: &Ticker, frequency: StatementFrequency) -> Result<(Vec<String>, Vec<[f64; 5]>), Box<dyn Error>> {
+    let data = ticker.get_fundamentals(StatementType::IncomeStatement, frequency).await?;
+    let data = data.sort(["asOfDate"], SortMultipleOptions::new().with_order_descending(false))?;
+    let periods = data.column("asOfDate")?.str()?.into_no_null_iter()
+        .map(|x| x.to_string()).collect::<Vec<String>>();
+    let values = |name: &str| -> Vec<f64> {
+        match data.column(name).and_then(|x| x.cast(&DataType::Float64)) {
+            Ok(series) => series.f64().unwrap().into_iter().map(|x| x.unwrap_or(0.0)).collect(),
+            Err(_) => vec![0.0; data.height()],
+        }
+    };
+    let revenue = values("TotalRevenue");
+    let gross_profit = values("GrossProfit");
+    let operating_income = values("EBIT");
+    let net_income = values("NetIncome");
+
+    let breakdown = (0..data.height()).map(|i| {
+        [
+            revenue[i],
+            revenue[i] - gross_profit[i],
+            gross_profit[i] - operating_income[i],
+            operating_income[i] - net_income[i],
+            net_income[i],
+        ]
+    }).collect::<Vec<[f64; 5]>>();
+
+    Ok((periods, breakdown))
 }
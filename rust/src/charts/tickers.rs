@@ -1,10 +1,14 @@
 use std::error::Error;
+use chrono::NaiveDateTime;
 use plotly::layout::Axis;
-use plotly::{HeatMap, Layout, Plot, Scatter};
-use plotly::common::{ColorScalePalette, Mode, Title};
-use polars::prelude::{NamedFrom, Series};
-use crate::prelude::{Tickers, TickersData};
-use crate::analytics::statistics::{correlation_matrix, cumulative_returns_list};
+use plotly::{Bar, HeatMap, Layout, Plot, Scatter};
+use plotly::color::NamedColor;
+use plotly::common::{AxisSide, ColorScalePalette, Fill, Line, Mode, Title};
+use polars::prelude::*;
+use crate::prelude::{TickerBuilder, TickerPerformance, Tickers, TickersData};
+use crate::data::kline::kline_for;
+use crate::analytics::event_study::{event_study, Event, EventWindow};
+use crate::analytics::statistics::{correlation_matrix, ols_regression, std_dev};
 use crate::charts::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
 use crate::reports::table::{DataTable, TableType};
 
@@ -14,6 +18,37 @@ pub trait TickersCharts {
     fn returns_table(&self) -> impl std::future::Future<Output = Result<DataTable, Box<dyn Error>>>;
     fn returns_chart(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
     fn returns_matrix(&self, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn risk_adjusted_returns_chart(&self, normalization: RiskNormalization, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn event_study_chart(&self, events: &[Event], window: EventWindow, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn pca_scree_chart(&self, standardize: bool, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+    fn pca_loadings_chart(&self, standardize: bool, n_components: Option<usize>, height: Option<usize>, width: Option<usize>) -> impl std::future::Future<Output = Result<Plot, Box<dyn Error>>>;
+}
+
+/// Risk normalization applied to the returns in the risk-adjusted comparison chart
+///
+/// Volatility: Returns are scaled so every ticker has the volatility of the benchmark
+/// Beta: Returns are divided by each ticker's beta to the benchmark, giving every ticker a beta of one
+#[derive(Debug, Clone, Copy)]
+pub enum RiskNormalization {
+    Volatility,
+    Beta,
+}
+
+impl RiskNormalization {
+    pub fn from_str(s: &str) -> RiskNormalization {
+        match s {
+            "volatility" => RiskNormalization::Volatility,
+            "beta" => RiskNormalization::Beta,
+            _ => RiskNormalization::Volatility,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            RiskNormalization::Volatility => "Volatility-Normalized".to_string(),
+            RiskNormalization::Beta => "Beta-Adjusted".to_string(),
+        }
+    }
 }
 
 
@@ -62,7 +97,7 @@ impl TickersCharts for Tickers {
                 Ok(returns_series) => {
                     let returns = returns_series.f64().unwrap().to_vec()
                         .iter().map(|x| x.unwrap_or_default()).collect::<Vec<f64>>();
-                    let cum_returns = cumulative_returns_list(returns.clone());
+                    let cum_returns = self.return_type.cumulative_returns(&returns);
                     let cum_returns_trace = Scatter::new(dates.clone(), cum_returns.clone())
                         .name(format!("{}", symbol))
                         .mode(Mode::Lines);
@@ -113,4 +148,234 @@ impl TickersCharts for Tickers {
 
         Ok(plot)
     }
-}
\ No newline at end of file
+
+    /// Display a Risk-Adjusted Cumulative Returns Chart for all tickers in the Tickers Struct
+    ///
+    /// # Arguments
+    ///
+    /// * `normalization` - Risk normalization enum (e.g. RiskNormalization::Volatility)
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn risk_adjusted_returns_chart(&self, normalization: RiskNormalization, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let symbols = self.tickers.iter().map(|x| x.ticker.clone()).collect::<Vec<String>>();
+        let mut asset_returns = self.returns().await?;
+        let dates = asset_returns.column("timestamp")?.str()?.into_no_null_iter()
+            .map(|x| NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M:%S").unwrap())
+            .collect::<Vec<NaiveDateTime>>();
+        let _ = asset_returns.replace("timestamp", Series::new("timestamp", dates.clone()))?;
+
+        // Align the benchmark returns with the ticker returns
+        let benchmark_ticker = TickerBuilder::new().ticker(&self.benchmark_symbol)
+            .start_date(&self.start_date)
+            .end_date(&self.end_date)
+            .interval(self.interval)
+            .calendar(self.calendar)
+            .return_type(self.return_type)
+            .price_type(self.price_type)
+            .ticker_data(kline_for(&self.ticker_data, &self.benchmark_symbol))
+            .build();
+        let benchmark_returns = benchmark_ticker.returns().await?;
+        let joined = asset_returns.join(
+            &benchmark_returns,
+            &["timestamp"],
+            &["timestamp"],
+            JoinArgs::new(JoinType::Left),
+        )?;
+        let joined = joined.sort(["timestamp"], SortMultipleOptions::new().with_order_descending(false))?;
+        let joined = joined.fill_null(FillNullStrategy::Zero)?;
+        let dates = joined.column("timestamp")?.datetime()?.as_datetime_iter()
+            .map(|x| x.unwrap().to_string()).collect::<Vec<String>>();
+        let benchmark_returns = joined.column("roc-1")?.clone();
+        let benchmark_volatility = std_dev(&benchmark_returns);
+
+        let mut plot = Plot::new();
+
+        for symbol in symbols {
+            match joined.column(&symbol) {
+                Ok(returns_series) => {
+                    let scale = match normalization {
+                        RiskNormalization::Volatility => {
+                            let volatility = std_dev(returns_series);
+                            if volatility > 0.0 { benchmark_volatility / volatility } else { 1.0 }
+                        }
+                        RiskNormalization::Beta => {
+                            let (_, beta) = ols_regression(&benchmark_returns, returns_series);
+                            if beta.abs() > f64::EPSILON { 1.0 / beta } else { 1.0 }
+                        }
+                    };
+                    let returns = returns_series.f64().unwrap().to_vec()
+                        .iter().map(|x| x.unwrap_or_default() * scale).collect::<Vec<f64>>();
+                    let cum_returns = self.return_type.cumulative_returns(&returns);
+                    let cum_returns_trace = Scatter::new(dates.clone(), cum_returns)
+                        .name(symbol.clone())
+                        .mode(Mode::Lines);
+                    plot.add_trace(cum_returns_trace);
+                }
+                Err(e) => {
+                    eprintln!("Unable to fetch returns for {}: {}", symbol, e);
+                }
+            }
+        }
+
+        let benchmark_cum_returns = self.return_type.cumulative_returns(&benchmark_returns.f64()?.to_vec()
+            .iter().map(|x| x.unwrap_or_default()).collect::<Vec<f64>>());
+        let benchmark_trace = Scatter::new(dates, benchmark_cum_returns)
+            .name(format!("{} (Benchmark)", self.benchmark_symbol))
+            .mode(Mode::Lines);
+        plot.add_trace(benchmark_trace);
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!("<span style=\"font-weight:bold; color:darkgreen;\">Tickers {} Cumulative Returns</span>", normalization.to_string())))
+            .y_axis(
+                Axis::new()
+                    .title(Title::from("Cumulative Returns"))
+                    .tick_format(".0%")
+            );
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Display the Cumulative Average Abnormal Returns around the events of the tickers
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - Events to study (e.g. from `earnings_events`)
+    /// * `window` - Event study windows
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn event_study_chart(&self, events: &[Event], window: EventWindow, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let study = event_study(self, events, window).await?;
+        let df = &study.results;
+        let days = df.column("day")?.i32()?.into_no_null_iter().collect::<Vec<i32>>();
+        let column = |name: &str| -> Result<Vec<Option<f64>>, Box<dyn Error>> {
+            Ok(df.column(name)?.f64()?.to_vec())
+        };
+
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(days.clone(), column("caar_lower")?)
+            .mode(Mode::Lines)
+            .line(Line::new().width(0.0))
+            .show_legend(false)
+            .name("Lower Bound"));
+        plot.add_trace(Scatter::new(days.clone(), column("caar_upper")?)
+            .mode(Mode::Lines)
+            .line(Line::new().width(0.0))
+            .fill(Fill::ToNextY)
+            .fill_color("rgba(0, 100, 0, 0.2)")
+            .name(format!("{:.0}% Confidence Interval", study.confidence_level * 100.0)));
+        plot.add_trace(Scatter::new(days.clone(), column("caar")?)
+            .name("CAAR")
+            .mode(Mode::LinesMarkers)
+            .line(Line::new().color(NamedColor::DarkGreen)));
+        plot.add_trace(Bar::new(days.clone(), column("aar")?)
+            .name("AAR")
+            .opacity(0.5));
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!(
+                "<span style=\"font-weight:bold; color:darkgreen;\">Cumulative Abnormal Returns around {} Events vs {}</span>",
+                study.events.len(), self.benchmark_symbol
+            )))
+            .x_axis(Axis::new().title(Title::from("Days Relative to Event")).zero_line(true))
+            .y_axis(Axis::new().title(Title::from("Abnormal Return (%)")));
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Display a Scree Chart of the principal components of the returns of all tickers in the Tickers Struct
+    ///
+    /// # Arguments
+    ///
+    /// * `standardize` - Extract the components from the correlation matrix instead of the covariance matrix
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn pca_scree_chart(&self, standardize: bool, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let pca = self.principal_components(standardize).await?;
+        let components = pca.component_names();
+        let cumulative = pca.explained_variance_ratio.iter()
+            .scan(0.0, |acc, x| { *acc += x; Some(*acc) })
+            .collect::<Vec<f64>>();
+
+        let mut plot = Plot::new();
+        plot.add_trace(Bar::new(components.clone(), pca.explained_variance_ratio.clone())
+            .name("Explained Variance"));
+        plot.add_trace(Scatter::new(components.clone(), cumulative)
+            .name("Cumulative Explained Variance")
+            .mode(Mode::LinesMarkers)
+            .line(Line::new().color(NamedColor::DarkGreen))
+            .y_axis("y2"));
+
+        let layout = Layout::new()
+            .height(height.unwrap_or(DEFAULT_HEIGHT))
+            .width(width.unwrap_or(DEFAULT_WIDTH))
+            .title(Title::from(&*format!(
+                "<span style=\"font-weight:bold; color:darkgreen;\">Principal Components Scree Chart (PC1 explains {:.2}%)</span>",
+                pca.absorption_ratio(1)
+            )))
+            .x_axis(Axis::new().title(Title::from("Component")))
+            .y_axis(Axis::new().title(Title::from("Explained Variance (%)")))
+            .y_axis2(Axis::new()
+                .title(Title::from("Cumulative Explained Variance (%)"))
+                .range(vec![0.0, 100.0])
+                .show_grid(false)
+                .overlaying("y")
+                .side(AxisSide::Right));
+
+        plot.set_layout(layout);
+        Ok(plot)
+    }
+
+    /// Display a Heatmap of the principal component loadings of all tickers in the Tickers Struct
+    ///
+    /// # Arguments
+    ///
+    /// * `standardize` - Extract the components from the correlation matrix instead of the covariance matrix
+    /// * `n_components` - Number of leading components to display (default - 5)
+    /// * `height` - `Option<usize>` - Height of the chart
+    /// * `width` - `Option<usize>` - Width of the chart
+    ///
+    /// # Returns
+    ///
+    /// * `Plot` - Plotly Chart struct
+    async fn pca_loadings_chart(&self, standardize: bool, n_components: Option<usize>, height: Option<usize>, width: Option<usize>) -> Result<Plot, Box<dyn Error>> {
+        let pca = self.principal_components(standardize).await?;
+        let n = n_components.unwrap_or(5).min(pca.eigenvalues.len());
+        let components = pca.component_names().into_iter().take(n).collect::<Vec<String>>();
+        let loadings = pca.loadings.outer_iter()
+            .map(|row| row.iter().take(n).cloned().collect::<Vec<f64>>())
+            .collect::<Vec<Vec<f64>>>();
+        let heatmap = HeatMap::new(components, pca.symbols.clone(), loadings)
+            .zmin(-1.0)
+            .zmax(1.0)
+            .color_scale(ColorScalePalette::RdBu.into());
+
+        let mut plot = Plot::new();
+        plot.add_trace(heatmap);
+        plot.set_layout(
+            Layout::new()
+                .title(Title::from("<span style=\"font-weight:bold; color:darkgreen;\">Principal Component Loadings</span>"))
+                .height(height.unwrap_or(DEFAULT_HEIGHT))
+                .width(width.unwrap_or(DEFAULT_WIDTH))
+        );
+
+        Ok(plot)
+    }
+}
@@ -103,6 +103,8 @@ pub struct TickerSummaryStats {
     pub shares_outstanding: f64,
     #[serde(default)]
     pub average_analyst_rating: String,
+    #[serde(default)]
+    pub quote_type: String,
 }
 
 #[allow(dead_code)]
@@ -128,6 +130,8 @@ pub struct OptionContract {
     pub lastTradeDate: i64,
     pub impliedVolatility: f64,
     pub inTheMoney: bool,
+    #[serde(default)]
+    pub volume: f64,
 }
 
 #[derive(Debug)]
@@ -161,6 +165,61 @@ pub struct Figure {
     pub raw: f64,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsiderTransaction {
+    #[serde(default)]
+    pub filer_name: String,
+    #[serde(default)]
+    pub filer_relation: String,
+    #[serde(default)]
+    pub transaction_text: String,
+    #[serde(default)]
+    pub ownership: String,
+    pub start_date: Figure,
+    #[serde(default)]
+    pub shares: Option<Figure>,
+    #[serde(default)]
+    pub value: Option<Figure>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceTarget {
+    #[serde(default)]
+    pub current_price: Option<Figure>,
+    #[serde(default)]
+    pub target_mean_price: Option<Figure>,
+    #[serde(default)]
+    pub target_median_price: Option<Figure>,
+    #[serde(default)]
+    pub target_high_price: Option<Figure>,
+    #[serde(default)]
+    pub target_low_price: Option<Figure>,
+    #[serde(default)]
+    pub number_of_analyst_opinions: Option<Figure>,
+}
+
+/// Sustainalytics ESG risk ratings reported by Yahoo Finance, lower scores carry less unmanaged ESG risk
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EsgScores {
+    #[serde(default)]
+    pub total_esg: Option<Figure>,
+    #[serde(default)]
+    pub environment_score: Option<Figure>,
+    #[serde(default)]
+    pub social_score: Option<Figure>,
+    #[serde(default)]
+    pub governance_score: Option<Figure>,
+    #[serde(default)]
+    pub percentile: Option<Figure>,
+    #[serde(default)]
+    pub highest_controversy: Option<f64>,
+    #[serde(default)]
+    pub peer_group: Option<String>,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum Interval {
     TwoMinutes,
@@ -229,6 +288,225 @@ impl Interval {
             Interval::ThreeMonths => 60.0,
         }
     }
+
+    /// Whether the interval is shorter than a trading day
+    pub fn is_intraday(&self) -> bool {
+        matches!(self, Interval::TwoMinutes | Interval::FiveMinutes | Interval::FifteenMinutes
+            | Interval::ThirtyMinutes | Interval::SixtyMinutes | Interval::NinetyMinutes | Interval::OneHour)
+    }
+}
+
+/// Stablecoin symbols (Yahoo Finance) supported as benchmarks for crypto portfolios
+pub const STABLECOINS: [&str; 8] = ["USDT-USD", "USDC-USD", "DAI-USD", "BUSD-USD", "TUSD-USD", "USDP-USD", "FDUSD-USD", "PYUSD-USD"];
+
+/// Checks whether a ticker symbol is a stablecoin, stablecoin benchmarks are treated as cash in the performance stats
+pub fn is_stablecoin(symbol: &str) -> bool {
+    STABLECOINS.contains(&symbol.to_uppercase().as_str())
+}
+
+/// Trading calendar conventions used to annualize performance statistics
+///
+/// Equity: 252 trading days per year, weekends excluded
+/// Crypto: 365 trading days per year, weekends included
+/// Forex: 260 trading days per year, trading around the clock on weekdays
+#[derive(Clone, Copy, Debug)]
+pub enum MarketCalendar {
+    Equity,
+    Crypto,
+    Forex,
+}
+
+impl MarketCalendar {
+    pub fn to_string(&self) -> String {
+        match self {
+            MarketCalendar::Equity => "equity".to_string(),
+            MarketCalendar::Crypto => "crypto".to_string(),
+            MarketCalendar::Forex => "forex".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> MarketCalendar {
+        match s {
+            "equity" => MarketCalendar::Equity,
+            "crypto" => MarketCalendar::Crypto,
+            "forex" => MarketCalendar::Forex,
+            _ => MarketCalendar::Equity,
+        }
+    }
+
+    /// Number of trading days in a year
+    pub fn trading_days(&self) -> f64 {
+        match self {
+            MarketCalendar::Equity => 252.0,
+            MarketCalendar::Crypto => 365.0,
+            MarketCalendar::Forex => 260.0,
+        }
+    }
+
+    /// Whether weekend observations are part of the calendar
+    pub fn includes_weekends(&self) -> bool {
+        matches!(self, MarketCalendar::Crypto)
+    }
+
+    /// Whether the market trades overnight (no daily session close)
+    pub fn trades_overnight(&self) -> bool {
+        matches!(self, MarketCalendar::Crypto | MarketCalendar::Forex)
+    }
+
+    /// Number of trading days spanned by each interval
+    pub fn interval_days(&self, interval: Interval) -> f64 {
+        match self {
+            MarketCalendar::Equity | MarketCalendar::Forex => interval.to_days(),
+            MarketCalendar::Crypto => match interval {
+                Interval::OneWeek => 7.0,
+                Interval::OneMonth => 30.0,
+                Interval::ThreeMonths => 91.0,
+                _ => interval.to_days(),
+            },
+        }
+    }
+}
+
+/// Convention used to compute period returns (in percent)
+///
+/// Simple: (P_t / P_t-1 - 1) * 100
+/// Log: ln(P_t / P_t-1) * 100
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReturnType {
+    Simple,
+    Log,
+}
+
+impl ReturnType {
+    pub fn to_string(&self) -> String {
+        match self {
+            ReturnType::Simple => "simple".to_string(),
+            ReturnType::Log => "log".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> ReturnType {
+        match s {
+            "simple" => ReturnType::Simple,
+            "log" => ReturnType::Log,
+            _ => ReturnType::Simple,
+        }
+    }
+
+    /// Computes the period returns of a price series, the first return is 0.0
+    pub fn returns(&self, prices: &[f64]) -> Vec<f64> {
+        let mut returns = Vec::with_capacity(prices.len());
+        for i in 0..prices.len() {
+            if i == 0 || prices[i - 1] == 0.0 {
+                returns.push(0.0);
+                continue;
+            }
+            let ratio = prices[i] / prices[i - 1];
+            returns.push(match self {
+                ReturnType::Simple => (ratio - 1.0) * 100.0,
+                ReturnType::Log => ratio.ln() * 100.0,
+            });
+        }
+        returns
+    }
+
+    /// Converts a return in this convention to a simple return (both in percent)
+    pub fn to_simple(&self, value: f64) -> f64 {
+        match self {
+            ReturnType::Simple => value,
+            ReturnType::Log => ((value / 100.0).exp() - 1.0) * 100.0,
+        }
+    }
+
+    /// Compounds a series of returns (in percent) into cumulative returns (in decimal)
+    pub fn cumulative_returns(&self, returns: &[f64]) -> Vec<f64> {
+        let mut cumulative_returns = Vec::with_capacity(returns.len());
+        let mut total = 0.0;
+        for value in returns {
+            total = match self {
+                ReturnType::Simple => (1.0 + total) * (1.0 + value / 100.0) - 1.0,
+                ReturnType::Log => total + value / 100.0,
+            };
+            cumulative_returns.push(match self {
+                ReturnType::Simple => total,
+                ReturnType::Log => total.exp() - 1.0,
+            });
+        }
+        cumulative_returns
+    }
+}
+
+/// Price column used to compute returns
+///
+/// AdjClose: close adjusted for splits and dividends (total return)
+/// Close: close adjusted for splits only (price return)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PriceType {
+    AdjClose,
+    Close,
+}
+
+impl PriceType {
+    pub fn to_string(&self) -> String {
+        match self {
+            PriceType::AdjClose => "adjclose".to_string(),
+            PriceType::Close => "close".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> PriceType {
+        match s {
+            "adjclose" => PriceType::AdjClose,
+            "close" => PriceType::Close,
+            _ => PriceType::AdjClose,
+        }
+    }
+}
+
+/// Asset class of a security, from the Yahoo Finance quote type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuoteType {
+    Equity,
+    Etf,
+    MutualFund,
+    Cryptocurrency,
+    Future,
+    Index,
+    Currency,
+    Other,
+}
+
+impl QuoteType {
+    pub fn to_string(&self) -> String {
+        match self {
+            QuoteType::Equity => "EQUITY".to_string(),
+            QuoteType::Etf => "ETF".to_string(),
+            QuoteType::MutualFund => "MUTUALFUND".to_string(),
+            QuoteType::Cryptocurrency => "CRYPTOCURRENCY".to_string(),
+            QuoteType::Future => "FUTURE".to_string(),
+            QuoteType::Index => "INDEX".to_string(),
+            QuoteType::Currency => "CURRENCY".to_string(),
+            QuoteType::Other => "OTHER".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> QuoteType {
+        match s.to_uppercase().as_str() {
+            "EQUITY" => QuoteType::Equity,
+            "ETF" => QuoteType::Etf,
+            "MUTUALFUND" => QuoteType::MutualFund,
+            "CRYPTOCURRENCY" => QuoteType::Cryptocurrency,
+            "FUTURE" => QuoteType::Future,
+            "INDEX" => QuoteType::Index,
+            "CURRENCY" => QuoteType::Currency,
+            _ => QuoteType::Other,
+        }
+    }
+
+    /// Returns true for ETFs and mutual funds
+    pub fn is_fund(&self) -> bool {
+        matches!(self, QuoteType::Etf | QuoteType::MutualFund)
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -283,6 +561,64 @@ impl StatementFrequency {
     }
 }
 
+/// Presentation of a financial statement
+///
+/// * `Reported` - Values as reported
+/// * `CommonSize` - Items as a percentage of revenue (income and cash flow statements) or total assets (balance sheet)
+/// * `YearOverYear` - Percentage change of each item from the same period of the previous year
+#[derive(Clone, Copy, Debug)]
+pub enum StatementView {
+    Reported,
+    CommonSize,
+    YearOverYear,
+}
+
+impl StatementView {
+    pub fn to_string(&self) -> String {
+        match self {
+            StatementView::Reported => "reported".to_string(),
+            StatementView::CommonSize => "common-size".to_string(),
+            StatementView::YearOverYear => "year-over-year".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<StatementView, String> {
+        match s {
+            "reported" => Ok(StatementView::Reported),
+            "common-size" => Ok(StatementView::CommonSize),
+            "year-over-year" => Ok(StatementView::YearOverYear),
+            _ => Err(format!("Invalid statement view: {}", s)),
+        }
+    }
+}
+
+/// Revenue segmentation reported in company filings
+///
+/// * `Business` - Revenue by product line or business segment
+/// * `Geographic` - Revenue by geographic region
+#[derive(Clone, Copy, Debug)]
+pub enum SegmentType {
+    Business,
+    Geographic,
+}
+
+impl SegmentType {
+    pub fn to_string(&self) -> String {
+        match self {
+            SegmentType::Business => "business".to_string(),
+            SegmentType::Geographic => "geographic".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<SegmentType, String> {
+        match s {
+            "business" => Ok(SegmentType::Business),
+            "geographic" => Ok(SegmentType::Geographic),
+            _ => Err(format!("Invalid segment type: {}", s)),
+        }
+    }
+}
+
 pub struct Fundamentals;
 
 impl Fundamentals {
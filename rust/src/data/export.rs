@@ -0,0 +1,257 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use polars::prelude::*;
+use serde_json::{json, Value};
+
+use crate::data::config::StatementFrequency;
+use crate::prelude::{Portfolio, PortfolioCharts, Ticker, TickerCharts, TickerData, TickerPerformance, Tickers, TickersData};
+
+
+/// File format of the exported DataFrames
+///
+/// * `Csv` - CSV files with a header row
+/// * `Parquet` - Parquet files, requires the `parquet` feature
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn from_str(s: &str) -> Result<ExportFormat, String> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(ExportFormat::Parquet),
+            #[cfg(not(feature = "parquet"))]
+            "parquet" => Err("Parquet export requires the parquet feature".to_string()),
+            _ => Err(format!("Invalid export format: {}", s)),
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            ExportFormat::Csv => "csv".to_string(),
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => "parquet".to_string(),
+        }
+    }
+}
+
+/// Writes named DataFrames to a directory and records them in a manifest
+///
+/// DataFrames that could not be computed (e.g. options of a symbol without listed options) are recorded
+/// in the errors of the manifest instead of aborting the export.
+pub(crate) struct ExportWriter {
+    dir: PathBuf,
+    format: ExportFormat,
+    files: Vec<Value>,
+    errors: Vec<Value>,
+}
+
+impl ExportWriter {
+    pub(crate) fn new(path: &Path, format: ExportFormat) -> Result<ExportWriter, Box<dyn Error>> {
+        fs::create_dir_all(path)?;
+        Ok(ExportWriter { dir: path.to_path_buf(), format, files: Vec::new(), errors: Vec::new() })
+    }
+
+    /// Writes a DataFrame, or records the error that prevented computing it
+    pub(crate) fn write(&mut self, name: &str, df: Result<DataFrame, Box<dyn Error>>) {
+        let result = df.and_then(|mut df| {
+            let file_name = format!("{}.{}", name, self.format.to_string());
+            let file = File::create(self.dir.join(&file_name))?;
+            match self.format {
+                ExportFormat::Csv => {
+                    CsvWriter::new(file).include_header(true).finish(&mut df)?;
+                }
+                #[cfg(feature = "parquet")]
+                ExportFormat::Parquet => {
+                    ParquetWriter::new(file).finish(&mut df)?;
+                }
+            }
+            Ok(json!({
+                "name": name,
+                "file": file_name,
+                "rows": df.height(),
+                "columns": df.get_column_names(),
+            }))
+        });
+        match result {
+            Ok(entry) => self.files.push(entry),
+            Err(e) => {
+                eprintln!("Unable to export {}: {}", name, e);
+                self.errors.push(json!({"name": name, "error": e.to_string()}));
+            }
+        }
+    }
+
+    /// Writes the manifest.json of the export
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Model that was exported (e.g. "ticker")
+    /// * `parameters` - Parameters the model was built with
+    ///
+    /// # Returns
+    ///
+    /// * `PathBuf` - Path of the manifest
+    pub(crate) fn finish(self, model: &str, parameters: Value) -> Result<PathBuf, Box<dyn Error>> {
+        let manifest = json!({
+            "model": model,
+            "created_at": chrono::Utc::now().to_rfc3339(),
+            "format": self.format.to_string(),
+            "parameters": parameters,
+            "files": self.files,
+            "errors": self.errors,
+        });
+        let path = self.dir.join("manifest.json");
+        fs::write(&path, serde_json::to_string_pretty(&manifest)?)?;
+        Ok(path)
+    }
+}
+
+pub trait DataExport {
+    fn dump<P: AsRef<Path>>(&self, path: P, format: ExportFormat) -> impl std::future::Future<Output = Result<PathBuf, Box<dyn Error>>>;
+}
+
+impl DataExport for Ticker {
+    /// Exports the underlying DataFrames of the Ticker to a directory
+    ///
+    /// Writes the ohlcv, events, returns, performance and summary stats, options chain and volatility surface
+    /// and the annual financial statements, along with a manifest.json of the files and the Ticker parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Directory to write to, created if missing
+    /// * `format` - File format of the DataFrames
+    ///
+    /// # Returns
+    ///
+    /// * `PathBuf` - Path of the manifest
+    async fn dump<P: AsRef<Path>>(&self, path: P, format: ExportFormat) -> Result<PathBuf, Box<dyn Error>> {
+        let mut writer = ExportWriter::new(path.as_ref(), format)?;
+        writer.write("ohlcv", self.get_chart().await);
+        writer.write("events", self.get_events().await);
+        writer.write("returns", self.returns().await);
+        writer.write("performance_stats", self.performance_stats_table().await.map(|x| x.data().clone()));
+        writer.write("summary_stats", self.summary_stats_table().await.map(|x| x.data().clone()));
+        match self.options_tables().await {
+            Ok(tables) => {
+                writer.write("options_chain", Ok(tables.options_chain.data().clone()));
+                writer.write("volatility_surface", Ok(tables.volatility_surface.data().clone()));
+            }
+            Err(e) => writer.write("options_chain", Err(e)),
+        }
+        match self.financials_tables(StatementFrequency::Annual).await {
+            Ok(tables) => {
+                writer.write("income_statement", Ok(tables.income_statement.data().clone()));
+                writer.write("balance_sheet", Ok(tables.balance_sheet.data().clone()));
+                writer.write("cashflow_statement", Ok(tables.cashflow_statement.data().clone()));
+                writer.write("financial_ratios", Ok(tables.financial_ratios.data().clone()));
+            }
+            Err(e) => writer.write("financial_statements", Err(e)),
+        }
+        writer.finish("ticker", json!({
+            "symbol": self.ticker,
+            "start_date": self.start_date,
+            "end_date": self.end_date,
+            "interval": self.interval.to_string(),
+            "benchmark_symbol": self.benchmark_symbol,
+            "confidence_level": self.confidence_level,
+            "risk_free_rate": self.risk_free_rate,
+        }))
+    }
+}
+
+impl DataExport for Tickers {
+    /// Exports the underlying DataFrames of the Tickers to a directory
+    ///
+    /// Writes the ohlcv, returns, performance and summary stats and options chains of all tickers,
+    /// along with a manifest.json of the files and the Tickers parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Directory to write to, created if missing
+    /// * `format` - File format of the DataFrames
+    ///
+    /// # Returns
+    ///
+    /// * `PathBuf` - Path of the manifest
+    async fn dump<P: AsRef<Path>>(&self, path: P, format: ExportFormat) -> Result<PathBuf, Box<dyn Error>> {
+        let mut writer = ExportWriter::new(path.as_ref(), format)?;
+        writer.write("ohlcv", self.get_chart().await);
+        writer.write("returns", self.returns().await);
+        writer.write("performance_stats", TickersData::performance_stats(self).await);
+        writer.write("summary_stats", self.get_ticker_stats().await);
+        writer.write("options_chain", self.get_options().await);
+        writer.finish("tickers", json!({
+            "symbols": self.tickers.iter().map(|x| x.ticker.clone()).collect::<Vec<String>>(),
+            "start_date": self.start_date,
+            "end_date": self.end_date,
+            "interval": self.interval.to_string(),
+            "benchmark_symbol": self.benchmark_symbol,
+            "confidence_level": self.confidence_level,
+            "risk_free_rate": self.risk_free_rate,
+        }))
+    }
+}
+
+impl DataExport for Portfolio {
+    /// Exports the underlying DataFrames of the Portfolio to a directory
+    ///
+    /// Writes the optimal weights, asset, portfolio and benchmark returns, performance stats,
+    /// optimization details and efficient frontier, along with a manifest.json of the files and the Portfolio parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Directory to write to, created if missing
+    /// * `format` - File format of the DataFrames
+    ///
+    /// # Returns
+    ///
+    /// * `PathBuf` - Path of the manifest
+    async fn dump<P: AsRef<Path>>(&self, path: P, format: ExportFormat) -> Result<PathBuf, Box<dyn Error>> {
+        let stats = &self.performance_stats;
+        let mut writer = ExportWriter::new(path.as_ref(), format)?;
+
+        writer.write("weights", df!(
+            "symbol" => stats.ticker_symbols.clone(),
+            "weight" => stats.optimal_weights.clone(),
+            "expected_return" => stats.expected_returns.clone(),
+        ).map_err(|e| e.into()));
+
+        let mut asset_returns = stats.portfolio_returns.clone();
+        writer.write("asset_returns", asset_returns.insert_column(0, Series::new("timestamp", stats.dates_array.clone()))
+            .map(|x| x.clone())
+            .map_err(|e| e.into()));
+
+        writer.write("portfolio_returns", DataFrame::new(vec![
+            Series::new("timestamp", stats.dates_array.clone()),
+            stats.optimal_portfolio_returns.clone().with_name("portfolio"),
+            stats.benchmark_returns.clone().with_name("benchmark"),
+        ]).map_err(|e| e.into()));
+
+        writer.write("performance_stats", self.performance_stats_table().await.map(|x| x.data().clone()));
+        writer.write("optimization_details", self.optimization_details_table().map(|x| x.data().clone()));
+        writer.write("efficient_frontier", df!(
+            "return" => stats.efficient_frontier.iter().map(|x| x[0]).collect::<Vec<f64>>(),
+            "risk" => stats.efficient_frontier.iter().map(|x| x[1]).collect::<Vec<f64>>(),
+        ).map_err(|e| e.into()));
+
+        writer.finish("portfolio", json!({
+            "symbols": stats.ticker_symbols,
+            "benchmark_symbol": stats.benchmark_symbol,
+            "start_date": stats.start_date,
+            "end_date": stats.end_date,
+            "interval": stats.interval.to_string(),
+            "objective_function": stats.objective_function.to_string(),
+            "optimization_method": stats.optimization_method,
+            "constraints": stats.constraints,
+            "confidence_level": stats.confidence_level,
+            "risk_free_rate": stats.risk_free_rate,
+            "seed": stats.seed,
+        }))
+    }
+}
@@ -0,0 +1,511 @@
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use polars::prelude::*;
+
+
+/// Timestamp formats tried, in order, when no format is configured
+const TIMESTAMP_FORMATS: [&str; 8] = [
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%m/%d/%Y %H:%M:%S",
+    "%d.%m.%Y %H:%M:%S",
+    "%Y%m%d %H:%M:%S",
+    "%m/%d/%Y %H:%M",
+];
+
+/// Date formats tried, in order, when no format is configured
+const DATE_FORMATS: [&str; 5] = ["%Y-%m-%d", "%Y/%m/%d", "%m/%d/%Y", "%d.%m.%Y", "%Y%m%d"];
+
+
+/// Loader configuration for OHLCV data in arbitrary layouts (e.g. broker exports)
+///
+/// * Column names of the timestamp, open, high, low, close, volume and adjclose fields (matched case-insensitively);
+///   volume defaults to 0 and open, high, low and adjclose default to the close when missing,
+///   so a single price series (e.g. the levels of a proprietary benchmark) can be loaded
+/// * `timestamp_format` - chrono format of the timestamps (e.g. "%d/%m/%Y %H:%M"), common formats,
+///   RFC 3339 and unix seconds/milliseconds are detected when not set
+/// * `timezone` - IANA timezone of timestamps without an offset (e.g. "America/New_York"), converted to UTC
+/// * `delimiter` - Field delimiter of CSV files
+#[derive(Debug, Clone)]
+pub struct KlineConfig {
+    pub timestamp: String,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: String,
+    pub adjclose: String,
+    pub timestamp_format: Option<String>,
+    pub timezone: Option<String>,
+    pub delimiter: u8,
+}
+
+impl Default for KlineConfig {
+    fn default() -> Self {
+        Self {
+            timestamp: "timestamp".to_string(),
+            open: "open".to_string(),
+            high: "high".to_string(),
+            low: "low".to_string(),
+            close: "close".to_string(),
+            volume: "volume".to_string(),
+            adjclose: "adjclose".to_string(),
+            timestamp_format: None,
+            timezone: None,
+            delimiter: b',',
+        }
+    }
+}
+
+impl KlineConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn timestamp(mut self, column: &str) -> Self {
+        self.timestamp = column.to_string();
+        self
+    }
+
+    pub fn open(mut self, column: &str) -> Self {
+        self.open = column.to_string();
+        self
+    }
+
+    pub fn high(mut self, column: &str) -> Self {
+        self.high = column.to_string();
+        self
+    }
+
+    pub fn low(mut self, column: &str) -> Self {
+        self.low = column.to_string();
+        self
+    }
+
+    pub fn close(mut self, column: &str) -> Self {
+        self.close = column.to_string();
+        self
+    }
+
+    pub fn volume(mut self, column: &str) -> Self {
+        self.volume = column.to_string();
+        self
+    }
+
+    pub fn adjclose(mut self, column: &str) -> Self {
+        self.adjclose = column.to_string();
+        self
+    }
+
+    pub fn timestamp_format(mut self, format: &str) -> Self {
+        self.timestamp_format = Some(format.to_string());
+        self
+    }
+
+    pub fn timezone(mut self, timezone: &str) -> Self {
+        self.timezone = Some(timezone.to_string());
+        self
+    }
+
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+}
+
+
+/// # KLINE Struct
+///
+/// ### Description
+///    - OHLCV data of a symbol loaded from user data (CSV files or DataFrames) instead of Yahoo Finance.
+///    - The data is normalized to the `get_chart` schema: timestamp (UTC), open, high, low, close, volume and adjclose.
+///    - Pass it to `TickerBuilder::ticker_data` to run the Ticker analytics on the loaded data.
+///
+/// ### Example
+///
+/// ```rust,no_run
+/// use std::error::Error;
+/// use finalytics::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let config = KlineConfig::new()
+///         .timestamp("Date")
+///         .open("Open").high("High").low("Low").close("Last").volume("Vol")
+///         .timestamp_format("%d/%m/%Y")
+///         .delimiter(b';');
+///     let kline = KLINE::from_csv("AAPL", "aapl.csv", &config)?;
+///     let ticker = TickerBuilder::new().ticker("AAPL")
+///         .start_date("2023-01-01")
+///         .end_date("2023-12-31")
+///         .ticker_data(Some(kline))
+///         .build();
+///     ticker.candlestick_chart(None, None).await?.show();
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct KLINE {
+    pub ticker: String,
+    pub data: DataFrame,
+}
+
+impl KLINE {
+    /// Loads OHLCV data from a DataFrame
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - Symbol of the data
+    /// * `df` - DataFrame with the OHLCV columns named as in the config
+    /// * `config` - Column mapping and timestamp parsing configuration
+    ///
+    /// # Returns
+    ///
+    /// * `KLINE` struct
+    pub fn from_dataframe(ticker: &str, df: &DataFrame, config: &KlineConfig) -> Result<KLINE, Box<dyn Error>> {
+        let timestamps = parse_timestamps(find_column(df, &config.timestamp)?, config)?;
+        let close = float_column(find_column(df, &config.close)?)?;
+        // Close-only series (e.g. benchmark index levels) use the close as the open, high and low
+        let optional = |name: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+            match find_column(df, name) {
+                Ok(series) => float_column(series),
+                Err(_) => Ok(close.clone()),
+            }
+        };
+        let volume = match find_column(df, &config.volume) {
+            Ok(series) => float_column(series)?,
+            Err(_) => vec![0.0; close.len()],
+        };
+        let adjclose = match find_column(df, &config.adjclose) {
+            Ok(series) => float_column(series)?,
+            Err(_) => close.clone(),
+        };
+        let data = df!(
+            "timestamp" => &timestamps,
+            "open" => optional(&config.open)?,
+            "high" => optional(&config.high)?,
+            "low" => optional(&config.low)?,
+            "close" => &close,
+            "volume" => &volume,
+            "adjclose" => &adjclose
+        )?;
+        let data = data.sort(["timestamp"], SortMultipleOptions::new().with_order_descending(false))?;
+        Ok(KLINE { ticker: ticker.to_string(), data })
+    }
+
+    /// Loads OHLCV data from a CSV file
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - Symbol of the data
+    /// * `path` - Path of the CSV file (with a header row)
+    /// * `config` - Column mapping, delimiter and timestamp parsing configuration
+    ///
+    /// # Returns
+    ///
+    /// * `KLINE` struct
+    pub fn from_csv<P: AsRef<Path>>(ticker: &str, path: P, config: &KlineConfig) -> Result<KLINE, Box<dyn Error>> {
+        let df = read_csv(path.as_ref(), config.delimiter)?;
+        Self::from_dataframe(ticker, &df, config)
+    }
+
+    /// Loads OHLCV data from a Parquet file
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - Symbol of the data
+    /// * `path` - Path of the Parquet file
+    /// * `config` - Column mapping and timestamp parsing configuration
+    ///
+    /// # Returns
+    ///
+    /// * `KLINE` struct
+    #[cfg(feature = "parquet")]
+    pub fn from_parquet<P: AsRef<Path>>(ticker: &str, path: P, config: &KlineConfig) -> Result<KLINE, Box<dyn Error>> {
+        let df = ParquetReader::new(std::fs::File::open(path.as_ref())?).finish()?;
+        Self::from_dataframe(ticker, &df, config)
+    }
+
+    /// Loads a returns series (e.g. of a proprietary benchmark) from a DataFrame, compounded into a price index from a base of 100
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - Symbol of the data
+    /// * `df` - DataFrame with the timestamp column named as in the config and a returns column
+    /// * `returns_column` - Name of the returns column, in decimals (e.g. 0.01 for 1%)
+    /// * `config` - Timestamp column and parsing configuration
+    ///
+    /// # Returns
+    ///
+    /// * `KLINE` struct
+    pub fn from_returns(ticker: &str, df: &DataFrame, returns_column: &str, config: &KlineConfig) -> Result<KLINE, Box<dyn Error>> {
+        let timestamps = parse_timestamps(find_column(df, &config.timestamp)?, config)?;
+        let returns = float_column(find_column(df, returns_column)?)?;
+        let mut returns = timestamps.into_iter().zip(returns).collect::<Vec<(NaiveDateTime, f64)>>();
+        returns.sort_by_key(|(timestamp, _)| *timestamp);
+        let mut level = 100.0;
+        let levels = returns.iter()
+            .map(|(_, r)| {
+                if r.is_finite() {
+                    level *= 1.0 + r;
+                }
+                level
+            })
+            .collect::<Vec<f64>>();
+        let df = df!(
+            "timestamp" => returns.iter().map(|(timestamp, _)| *timestamp).collect::<Vec<NaiveDateTime>>(),
+            "close" => levels,
+        )?;
+        Self::from_dataframe(ticker, &df, &KlineConfig::new())
+    }
+
+    /// Loads a returns series (e.g. of a proprietary benchmark) from a CSV file, compounded into a price index from a base of 100
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - Symbol of the data
+    /// * `path` - Path of the CSV file (with a header row)
+    /// * `returns_column` - Name of the returns column, in decimals (e.g. 0.01 for 1%)
+    /// * `config` - Timestamp column, delimiter and parsing configuration
+    ///
+    /// # Returns
+    ///
+    /// * `KLINE` struct
+    pub fn from_returns_csv<P: AsRef<Path>>(ticker: &str, path: P, returns_column: &str, config: &KlineConfig) -> Result<KLINE, Box<dyn Error>> {
+        let df = read_csv(path.as_ref(), config.delimiter)?;
+        Self::from_returns(ticker, &df, returns_column, config)
+    }
+
+    /// Loads OHLCV data of many symbols from a long-format DataFrame (one row per symbol and timestamp)
+    ///
+    /// # Arguments
+    ///
+    /// * `df` - DataFrame with a symbol column and the OHLCV columns named as in the config
+    /// * `symbol_column` - Name of the symbol column (e.g. "symbol")
+    /// * `config` - Column mapping and timestamp parsing configuration
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<KLINE>` - One KLINE per symbol, in order of first appearance
+    pub fn from_long_dataframe(df: &DataFrame, symbol_column: &str, config: &KlineConfig) -> Result<Vec<KLINE>, Box<dyn Error>> {
+        let symbols = find_column(df, symbol_column)?.cast(&DataType::String)?;
+        let symbols = symbols.str()?;
+        let mut unique: Vec<String> = Vec::new();
+        for symbol in symbols.into_iter().flatten() {
+            if !unique.iter().any(|x| x == symbol) {
+                unique.push(symbol.to_string());
+            }
+        }
+        let mut klines = Vec::new();
+        for symbol in unique {
+            let mask = symbols.into_iter().map(|x| x == Some(symbol.as_str())).collect::<BooleanChunked>();
+            klines.push(Self::from_dataframe(&symbol, &df.filter(&mask)?, config)?);
+        }
+        Ok(klines)
+    }
+
+    /// Loads OHLCV data of many symbols from a long-format CSV file (one row per symbol and timestamp)
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the CSV file (with a header row)
+    /// * `symbol_column` - Name of the symbol column (e.g. "symbol")
+    /// * `config` - Column mapping, delimiter and timestamp parsing configuration
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<KLINE>` - One KLINE per symbol, in order of first appearance
+    pub fn from_long_csv<P: AsRef<Path>>(path: P, symbol_column: &str, config: &KlineConfig) -> Result<Vec<KLINE>, Box<dyn Error>> {
+        let df = read_csv(path.as_ref(), config.delimiter)?;
+        Self::from_long_dataframe(&df, symbol_column, config)
+    }
+
+    /// Loads OHLCV data of many symbols from a directory with one CSV file per symbol
+    ///
+    /// The symbol of each file is its name without the extension (e.g. "AAPL.csv" for AAPL).
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory containing the CSV files
+    /// * `config` - Column mapping, delimiter and timestamp parsing configuration
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<KLINE>` - One KLINE per file, sorted by symbol
+    pub fn from_directory<P: AsRef<Path>>(dir: P, config: &KlineConfig) -> Result<Vec<KLINE>, Box<dyn Error>> {
+        let mut paths = std::fs::read_dir(dir.as_ref())?
+            .filter_map(|x| x.ok().map(|x| x.path()))
+            .filter(|x| x.is_file() && x.extension().is_some_and(|e| e.eq_ignore_ascii_case("csv")))
+            .collect::<Vec<_>>();
+        paths.sort();
+        if paths.is_empty() {
+            return Err(format!("No CSV files found in {}", dir.as_ref().display()).into());
+        }
+        paths.iter()
+            .map(|path| {
+                let symbol = path.file_stem().and_then(|x| x.to_str()).ok_or("Invalid file name")?;
+                Self::from_csv(symbol, path, config)
+            })
+            .collect()
+    }
+
+    /// Returns the OHLCV data, optionally restricted to a date range
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - Optional start date in the format YYYY-MM-DD
+    /// * `end_date` - Optional end date in the format YYYY-MM-DD (exclusive)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, open, high, low, close, volume and adjclose
+    pub fn to_dataframe(&self, start_date: Option<&str>, end_date: Option<&str>) -> Result<DataFrame, Box<dyn Error>> {
+        let parse = |x: Option<&str>| -> Result<Option<NaiveDateTime>, Box<dyn Error>> {
+            match x {
+                Some(date) if !date.is_empty() => Ok(Some(NaiveDate::parse_from_str(date, "%Y-%m-%d")?
+                    .and_hms_opt(0, 0, 0).unwrap())),
+                _ => Ok(None),
+            }
+        };
+        let (start, end) = (parse(start_date)?, parse(end_date)?);
+        let mask = self.data.column("timestamp")?.datetime()?.as_datetime_iter()
+            .map(|x| match x {
+                Some(x) => start.is_none_or(|s| x >= s) && end.is_none_or(|e| x < e),
+                None => false,
+            })
+            .collect::<BooleanChunked>();
+        Ok(self.data.filter(&mask)?)
+    }
+}
+
+/// Returns the KLINE of a symbol from a panel of user data
+pub(crate) fn kline_for(ticker_data: &Option<Vec<KLINE>>, symbol: &str) -> Option<KLINE> {
+    ticker_data.as_ref().and_then(|x| x.iter().find(|k| k.ticker == symbol).cloned())
+}
+
+/// Adds user supplied benchmark data to a panel of user data, replacing any data of the same symbol
+pub(crate) fn with_benchmark(ticker_data: &Option<Vec<KLINE>>, benchmark_data: &Option<KLINE>) -> Option<Vec<KLINE>> {
+    match benchmark_data {
+        Some(benchmark) => {
+            let mut data = ticker_data.clone().unwrap_or_default();
+            data.retain(|x| x.ticker != benchmark.ticker);
+            data.push(benchmark.clone());
+            Some(data)
+        }
+        None => ticker_data.clone(),
+    }
+}
+
+/// Reads a CSV file with a header row
+pub(crate) fn read_csv(path: &Path, delimiter: u8) -> Result<DataFrame, Box<dyn Error>> {
+    let df = CsvReadOptions::default()
+        .with_has_header(true)
+        .with_infer_schema_length(Some(1000))
+        .with_parse_options(CsvParseOptions::default().with_separator(delimiter))
+        .try_into_reader_with_file_path(Some(path.to_path_buf()))?
+        .finish()?;
+    Ok(df)
+}
+
+/// Finds a column by name, ignoring case and surrounding whitespace
+pub(crate) fn find_column<'a>(df: &'a DataFrame, name: &str) -> Result<&'a Series, Box<dyn Error>> {
+    let name = name.trim().to_lowercase();
+    df.get_columns().iter()
+        .find(|s| s.name().trim().to_lowercase() == name)
+        .ok_or(format!("Column {} not found, available columns: {:?}", name, df.get_column_names()).into())
+}
+
+/// Converts a numeric or string column to f64 values (thousands separators are removed)
+fn float_column(series: &Series) -> Result<Vec<f64>, Box<dyn Error>> {
+    let values = match series.dtype() {
+        DataType::String => series.str()?.into_iter()
+            .map(|x| x.and_then(|v| v.trim().replace(',', "").parse::<f64>().ok()).unwrap_or(f64::NAN))
+            .collect::<Vec<f64>>(),
+        _ => series.cast(&DataType::Float64)?.f64()?.into_iter()
+            .map(|x| x.unwrap_or(f64::NAN))
+            .collect::<Vec<f64>>(),
+    };
+    Ok(values)
+}
+
+/// Parses a timestamp column to UTC timestamps
+fn parse_timestamps(series: &Series, config: &KlineConfig) -> Result<Vec<NaiveDateTime>, Box<dyn Error>> {
+    let timezone = match &config.timezone {
+        Some(tz) => Some(Tz::from_str(tz).map_err(|e| format!("Invalid timezone {}: {}", tz, e))?),
+        None => None,
+    };
+    let to_utc = |datetime: NaiveDateTime, has_time: bool| -> NaiveDateTime {
+        match (timezone, has_time) {
+            // Dates without a time are kept as is so daily bars align with other daily data
+            (Some(tz), true) => tz.from_local_datetime(&datetime).earliest()
+                .map(|x| x.naive_utc()).unwrap_or(datetime),
+            _ => datetime,
+        }
+    };
+
+    let timestamps = match series.dtype() {
+        DataType::Datetime(_, _) => series.datetime()?.as_datetime_iter()
+            .map(|x| x.map(|x| to_utc(x, true)).ok_or("Null timestamp".into()))
+            .collect::<Result<Vec<NaiveDateTime>, Box<dyn Error>>>()?,
+        DataType::Date => series.cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?.datetime()?.as_datetime_iter()
+            .map(|x| x.ok_or("Null timestamp".into()))
+            .collect::<Result<Vec<NaiveDateTime>, Box<dyn Error>>>()?,
+        DataType::String => series.str()?.into_iter()
+            .map(|x| {
+                let x = x.ok_or("Null timestamp")?.trim();
+                parse_timestamp(x, config.timestamp_format.as_deref())
+                    .map(|(datetime, has_time)| to_utc(datetime, has_time))
+            })
+            .collect::<Result<Vec<NaiveDateTime>, Box<dyn Error>>>()?,
+        _ => series.cast(&DataType::Int64)?.i64()?.into_iter()
+            .map(|x| {
+                let x = x.ok_or("Null timestamp")?;
+                // Unix timestamps in milliseconds have more than 11 digits
+                let datetime = if x.abs() > 99_999_999_999 { DateTime::from_timestamp_millis(x) } else { DateTime::from_timestamp(x, 0) };
+                datetime.map(|x| x.naive_utc()).ok_or(format!("Invalid unix timestamp {}", x).into())
+            })
+            .collect::<Result<Vec<NaiveDateTime>, Box<dyn Error>>>()?,
+    };
+    Ok(timestamps)
+}
+
+/// Parses a timestamp string, returning the timestamp and whether it has a time component
+fn parse_timestamp(value: &str, format: Option<&str>) -> Result<(NaiveDateTime, bool), Box<dyn Error>> {
+    if let Some(format) = format {
+        if let Ok(datetime) = DateTime::parse_from_str(value, format) {
+            return Ok((datetime.naive_utc(), true));
+        }
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok((datetime, true));
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+            return Ok((date.and_hms_opt(0, 0, 0).unwrap(), false));
+        }
+        return Err(format!("Timestamp {} does not match the format {}", value, format).into());
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Ok((datetime.naive_utc(), true));
+    }
+    for format in TIMESTAMP_FORMATS {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok((datetime, true));
+        }
+    }
+    for format in DATE_FORMATS {
+        if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+            return Ok((date.and_hms_opt(0, 0, 0).unwrap(), false));
+        }
+    }
+    if let Ok(unix) = value.parse::<i64>() {
+        let datetime = if unix.abs() > 99_999_999_999 { DateTime::from_timestamp_millis(unix) } else { DateTime::from_timestamp(unix, 0) };
+        if let Some(datetime) = datetime {
+            return Ok((datetime.naive_utc(), true));
+        }
+    }
+    Err(format!("Unable to parse timestamp {}, set KlineConfig::timestamp_format", value).into())
+}
@@ -0,0 +1,143 @@
+use std::error::Error;
+use polars::prelude::*;
+use serde_json::Value;
+
+use crate::data::ticker::{fetch_quote_fields, quote_fields_dataframe};
+use crate::utils::web_utils::get_json_response;
+
+
+/// Quote fields returned for each market mover
+const MOVER_FIELDS: [(&str, &str); 7] = [
+    ("shortName", "name"),
+    ("regularMarketPrice", "price"),
+    ("regularMarketChange", "change"),
+    ("regularMarketChangePercent", "change_percent"),
+    ("regularMarketVolume", "volume"),
+    ("averageDailyVolume3Month", "average_volume"),
+    ("marketCap", "market_cap"),
+];
+
+/// Predefined Yahoo Finance market mover lists
+///
+/// DayGainers: Stocks with the largest percentage gain in the trading day
+/// DayLosers: Stocks with the largest percentage loss in the trading day
+/// MostActives: Stocks with the highest trading volume in the trading day
+#[derive(Debug, Clone, Copy)]
+pub enum MarketMovers {
+    DayGainers,
+    DayLosers,
+    MostActives,
+}
+
+impl MarketMovers {
+    pub fn to_string(&self) -> String {
+        match self {
+            MarketMovers::DayGainers => "day_gainers".to_string(),
+            MarketMovers::DayLosers => "day_losers".to_string(),
+            MarketMovers::MostActives => "most_actives".to_string(),
+        }
+    }
+
+    pub fn from_str(s: &str) -> MarketMovers {
+        match s {
+            "day_gainers" => MarketMovers::DayGainers,
+            "day_losers" => MarketMovers::DayLosers,
+            "most_actives" => MarketMovers::MostActives,
+            _ => MarketMovers::MostActives,
+        }
+    }
+}
+
+/// Fetches a predefined list of market movers from Yahoo Finance
+///
+/// # Arguments
+///
+/// * `movers` - Market movers enum (e.g. MarketMovers::DayGainers)
+/// * `count` - Number of tickers to return (default - 25)
+///
+/// # Returns
+///
+/// * `DataFrame` - Symbol, name, price, change, change percent, volume, average volume and market cap of each ticker
+///
+/// # Example
+///
+/// ```
+/// use std::error::Error;
+/// use finalytics::data::market::{get_market_movers, MarketMovers};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let gainers = get_market_movers(MarketMovers::DayGainers, Some(10)).await?;
+///     println!("{:?}", gainers);
+///     Ok(())
+/// }
+/// ```
+pub async fn get_market_movers(movers: MarketMovers, count: Option<usize>) -> Result<DataFrame, Box<dyn Error>> {
+    let url = format!(
+        "https://query1.finance.yahoo.com/v1/finance/screener/predefined/saved?scrIds={}&count={}",
+        movers.to_string(), count.unwrap_or(25)
+    );
+    let result = get_json_response(url).await?;
+    let quotes = result["finance"]["result"][0]["quotes"]
+        .as_array()
+        .ok_or(format!("No {} found: {}", movers.to_string(), result))?;
+
+    let symbols = quotes.iter()
+        .map(|q| q["symbol"].as_str().unwrap_or_default().to_string())
+        .collect::<Vec<String>>();
+    let rows = quotes.iter()
+        .map(|q| MOVER_FIELDS.iter().map(|(f, _)| match q[*f].get("raw") {
+            Some(raw) => raw.clone(),
+            None => q[*f].clone(),
+        }).collect::<Vec<Value>>())
+        .collect::<Vec<Vec<Value>>>();
+    movers_dataframe(&symbols, &rows)
+}
+
+/// Fetches the trending tickers for a region from Yahoo Finance
+///
+/// # Arguments
+///
+/// * `region` - Region code (e.g. "US", "GB", "DE")
+/// * `count` - Number of tickers to return (default - 20)
+///
+/// # Returns
+///
+/// * `DataFrame` - Symbol, name, price, change, change percent, volume, average volume and market cap of each ticker
+pub async fn get_trending_tickers(region: &str, count: Option<usize>) -> Result<DataFrame, Box<dyn Error>> {
+    let url = format!(
+        "https://query1.finance.yahoo.com/v1/finance/trending/{}?count={}",
+        region.to_uppercase(), count.unwrap_or(20)
+    );
+    let result = get_json_response(url).await?;
+    let symbols = result["finance"]["result"][0]["quotes"]
+        .as_array()
+        .ok_or(format!("No trending tickers found for {}: {}", region, result))?
+        .iter()
+        .filter_map(|q| q["symbol"].as_str().map(|s| s.to_string()))
+        .collect::<Vec<String>>();
+
+    // The trending endpoint only returns symbols, so the quote fields are fetched separately
+    let fields = MOVER_FIELDS.iter().map(|(f, _)| f.to_string()).collect::<Vec<String>>();
+    let mut found = Vec::new();
+    let mut rows = Vec::new();
+    for symbol in symbols {
+        match fetch_quote_fields(&symbol, &fields).await {
+            Ok(values) => {
+                found.push(symbol);
+                rows.push(values);
+            }
+            Err(e) => eprintln!("Error Fetching Quote for {}: {}", symbol, e),
+        }
+    }
+    movers_dataframe(&found, &rows)
+}
+
+fn movers_dataframe(symbols: &[String], rows: &[Vec<Value>]) -> Result<DataFrame, Box<dyn Error>> {
+    let fields = MOVER_FIELDS.iter().map(|(f, _)| f.to_string()).collect::<Vec<String>>();
+    let mut df = quote_fields_dataframe(symbols, rows, &fields)?;
+    for (field, name) in MOVER_FIELDS.iter() {
+        df.rename(field, name)?;
+    }
+    Ok(df)
+}
@@ -1,3 +1,10 @@
 pub mod ticker;
 pub mod config;
-pub mod tickers;
\ No newline at end of file
+pub mod tickers;
+pub mod market;
+pub mod validation;
+pub mod resample;
+pub mod kline;
+pub mod news;
+pub mod schema;
+pub mod export;
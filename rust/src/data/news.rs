@@ -0,0 +1,338 @@
+use std::error::Error;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use polars::prelude::*;
+use select::document::Document;
+use select::predicate::Name;
+use crate::utils::web_utils::fetch_html;
+
+
+/// Maximum Hamming distance between the SimHashes of two headlines considered duplicates
+pub const DEFAULT_SIMILARITY_DISTANCE: u32 = 3;
+
+/// Number of days covered by each news feed request, the feed returns at most ~100 items per request
+pub const DEFAULT_NEWS_WINDOW_DAYS: i64 = 3;
+
+/// Number of keywords returned by default by the keyword extraction
+pub const DEFAULT_TOP_KEYWORDS: usize = 20;
+
+/// Common English words and feed boilerplate ignored by the keyword extraction
+const STOPWORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are", "as", "at",
+    "be", "because", "been", "before", "being", "below", "between", "both", "but", "by", "can", "could",
+    "did", "do", "does", "doing", "down", "during", "each", "few", "for", "from", "further", "had", "has",
+    "have", "having", "he", "her", "here", "hers", "him", "his", "how", "i", "if", "in", "into", "is", "it",
+    "its", "itself", "just", "me", "more", "most", "my", "no", "nor", "not", "now", "of", "off", "on",
+    "once", "only", "or", "other", "our", "ours", "out", "over", "own", "same", "she", "should", "so",
+    "some", "such", "than", "that", "the", "their", "them", "then", "there", "these", "they", "this",
+    "those", "through", "to", "too", "under", "until", "up", "very", "was", "we", "were", "what", "when",
+    "where", "which", "while", "who", "why", "will", "with", "would", "you", "your", "new", "says", "inc",
+    "stock", "stocks", "shares", "here's", "today",
+];
+
+/// Options for fetching and cleaning the news headlines
+///
+/// * `deduplicate` - Whether to drop near-duplicate headlines syndicated across outlets (default true)
+/// * `similarity_distance` - Maximum Hamming distance of the headline SimHashes for duplicates (default 3 of 64 bits)
+/// * `include_sources` - Only keep articles from these publishers (case-insensitive, empty keeps all)
+/// * `exclude_sources` - Drop articles from these publishers (case-insensitive)
+/// * `full_text` - Whether to retrieve the article text from the article url (best effort, slow)
+/// * `start_date` - Start of the news archive range in YYYY-MM-DD format (default - the ticker start date)
+/// * `end_date` - End of the news archive range in YYYY-MM-DD format (default - the ticker end date)
+/// * `window_days` - Days covered by each feed request when paging through the range (default 3),
+///   smaller windows retrieve more articles for heavily covered tickers
+#[derive(Debug, Clone)]
+pub struct NewsOptions {
+    pub deduplicate: bool,
+    pub similarity_distance: u32,
+    pub include_sources: Vec<String>,
+    pub exclude_sources: Vec<String>,
+    pub full_text: bool,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub window_days: i64,
+}
+
+impl Default for NewsOptions {
+    fn default() -> Self {
+        NewsOptions {
+            deduplicate: true,
+            similarity_distance: DEFAULT_SIMILARITY_DISTANCE,
+            include_sources: Vec::new(),
+            exclude_sources: Vec::new(),
+            full_text: false,
+            start_date: None,
+            end_date: None,
+            window_days: DEFAULT_NEWS_WINDOW_DAYS,
+        }
+    }
+}
+
+impl NewsOptions {
+    pub fn new() -> NewsOptions {
+        NewsOptions::default()
+    }
+
+    pub fn deduplicate(mut self, deduplicate: bool) -> NewsOptions {
+        self.deduplicate = deduplicate;
+        self
+    }
+
+    pub fn similarity_distance(mut self, similarity_distance: u32) -> NewsOptions {
+        self.similarity_distance = similarity_distance;
+        self
+    }
+
+    pub fn include_sources(mut self, sources: Vec<&str>) -> NewsOptions {
+        self.include_sources = sources.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    pub fn exclude_sources(mut self, sources: Vec<&str>) -> NewsOptions {
+        self.exclude_sources = sources.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    pub fn full_text(mut self, full_text: bool) -> NewsOptions {
+        self.full_text = full_text;
+        self
+    }
+
+    pub fn date_range(mut self, start_date: &str, end_date: &str) -> NewsOptions {
+        self.start_date = Some(start_date.to_string());
+        self.end_date = Some(end_date.to_string());
+        self
+    }
+
+    pub fn window_days(mut self, window_days: i64) -> NewsOptions {
+        self.window_days = window_days.max(1);
+        self
+    }
+}
+
+/// Applies the source filters and deduplication of the news options to a news DataFrame
+///
+/// # Arguments
+///
+/// * `news` - DataFrame returned by `TickerData::get_news`, sorted by Published Date
+/// * `options` - News options
+///
+/// # Returns
+///
+/// * `DataFrame` - Filtered news, with a Duplicates column counting the dropped copies of each headline
+pub fn clean_news(news: &DataFrame, options: &NewsOptions) -> Result<DataFrame, Box<dyn Error>> {
+    if news.height() == 0 {
+        return Ok(news.clone());
+    }
+    let sources = news.column("Source")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_lowercase()).collect::<Vec<String>>();
+    let include = options.include_sources.iter().map(|x| x.to_lowercase()).collect::<Vec<String>>();
+    let exclude = options.exclude_sources.iter().map(|x| x.to_lowercase()).collect::<Vec<String>>();
+    let mask = sources.iter()
+        .map(|s| (include.is_empty() || include.contains(s)) && !exclude.contains(s))
+        .collect::<BooleanChunked>();
+    let news = news.filter(&mask)?;
+
+    let titles = news.column("Title")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string()).collect::<Vec<String>>();
+    let sources = news.column("Source")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string()).collect::<Vec<String>>();
+
+    // Keep the earliest copy of each headline and count the later near-duplicates against it
+    let mut kept: Vec<(usize, u64)> = Vec::new();
+    let mut duplicates = vec![0u32; titles.len()];
+    let mut keep = vec![true; titles.len()];
+    if options.deduplicate {
+        for (i, title) in titles.iter().enumerate() {
+            let hash = simhash(&headline(title, &sources[i]));
+            match kept.iter().find(|(_, h)| (h ^ hash).count_ones() <= options.similarity_distance) {
+                Some(&(original, _)) => {
+                    keep[i] = false;
+                    duplicates[original] += 1;
+                }
+                None => kept.push((i, hash)),
+            }
+        }
+    }
+    let mut news = news.clone();
+    news.with_column(Series::new("Duplicates", duplicates))?;
+    let news = news.filter(&keep.into_iter().collect::<BooleanChunked>())?;
+    Ok(news)
+}
+
+/// Aggregates the news sentiment by day
+///
+/// # Arguments
+///
+/// * `news` - DataFrame returned by `TickerData::get_news`
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns date, articles and sentiment (average compound sentiment score)
+pub fn daily_sentiment(news: &DataFrame) -> Result<DataFrame, Box<dyn Error>> {
+    let df = news.clone().lazy()
+        .group_by([col("Published Date").dt().date().alias("date")])
+        .agg([
+            col("Sentiment Score").count().cast(DataType::UInt32).alias("articles"),
+            col("Sentiment Score").mean().alias("sentiment"),
+        ])
+        .sort(["date"], Default::default())
+        .collect()?;
+    Ok(df)
+}
+
+/// Extracts the top keywords and two-word phrases of the news with TF-IDF
+///
+/// Each article (headline plus the Full Text when retrieved) is a document. Terms are scored by the sum
+/// of their TF-IDF weights across articles, so topics covered by many but not all articles rank highest.
+///
+/// # Arguments
+///
+/// * `news` - DataFrame returned by `TickerData::get_news`
+/// * `exclude` - Additional words to ignore, e.g. the company name and symbol
+/// * `top_n` - Number of keywords to return
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns keyword, articles (number of articles mentioning it), count (total mentions)
+///   and score (TF-IDF), sorted by score
+pub fn news_keywords(news: &DataFrame, exclude: &[&str], top_n: usize) -> Result<DataFrame, Box<dyn Error>> {
+    let titles = news.column("Title")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string()).collect::<Vec<String>>();
+    let sources = news.column("Source")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string()).collect::<Vec<String>>();
+    let texts = match news.column("Full Text") {
+        Ok(series) => series.str()?.into_iter().map(|x| x.unwrap_or_default().to_string()).collect::<Vec<String>>(),
+        Err(_) => vec![String::new(); titles.len()],
+    };
+    let exclude = exclude.iter().map(|x| x.to_lowercase()).collect::<Vec<String>>();
+
+    let documents = titles.iter().enumerate()
+        .map(|(i, title)| terms(&format!("{} {}", headline(title, &sources[i]), texts[i].to_lowercase()), &exclude))
+        .collect::<Vec<Vec<String>>>();
+    let n = documents.iter().filter(|d| !d.is_empty()).count();
+    if n == 0 {
+        return Err("No keywords found in the news".into());
+    }
+
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut term_frequencies = Vec::with_capacity(documents.len());
+    for document in &documents {
+        let mut tf: HashMap<&str, usize> = HashMap::new();
+        for term in document {
+            *tf.entry(term.as_str()).or_insert(0) += 1;
+            *counts.entry(term.as_str()).or_insert(0) += 1;
+        }
+        for term in tf.keys() {
+            *document_frequency.entry(*term).or_insert(0) += 1;
+        }
+        term_frequencies.push((tf, document.len()));
+    }
+
+    let mut scores: HashMap<&str, f64> = HashMap::new();
+    for (tf, length) in &term_frequencies {
+        for (term, count) in tf {
+            let idf = ((1.0 + n as f64) / (1.0 + document_frequency[term] as f64)).ln() + 1.0;
+            *scores.entry(*term).or_insert(0.0) += *count as f64 / *length as f64 * idf;
+        }
+    }
+
+    let mut ranked = scores.into_iter().collect::<Vec<(&str, f64)>>();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(b.0)));
+    ranked.truncate(top_n);
+
+    let df = df!(
+        "keyword" => ranked.iter().map(|x| x.0.to_string()).collect::<Vec<String>>(),
+        "articles" => ranked.iter().map(|x| document_frequency[x.0] as u32).collect::<Vec<u32>>(),
+        "count" => ranked.iter().map(|x| counts[x.0] as u32).collect::<Vec<u32>>(),
+        "score" => ranked.iter().map(|x| x.1).collect::<Vec<f64>>(),
+    )?;
+    Ok(df)
+}
+
+/// Words and two-word phrases of a normalized text, without stopwords, numbers and excluded words
+fn terms(text: &str, exclude: &[String]) -> Vec<String> {
+    let words = text.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .map(|w| w.trim_matches('\''))
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<&str>>();
+    let keep = |w: &str| w.chars().count() > 2 && !w.chars().all(|c| c.is_numeric())
+        && !STOPWORDS.contains(&w) && !exclude.iter().any(|x| x == w);
+    let mut terms = words.iter().filter(|w| keep(w)).map(|w| w.to_string()).collect::<Vec<String>>();
+    terms.extend(words.windows(2)
+        .filter(|w| keep(w[0]) && keep(w[1]))
+        .map(|w| format!("{} {}", w[0], w[1])));
+    terms
+}
+
+/// Retrieves the text of the paragraphs of each article, leaving it empty when the page cannot be fetched
+///
+/// # Arguments
+///
+/// * `news` - News DataFrame with a URL column
+///
+/// # Returns
+///
+/// * `DataFrame` - News with a Full Text column
+pub async fn add_full_text(news: &DataFrame) -> Result<DataFrame, Box<dyn Error>> {
+    let urls = news.column("URL")?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string()).collect::<Vec<String>>();
+    let mut texts = Vec::with_capacity(urls.len());
+    for url in urls {
+        let text = match fetch_html(url.clone()).await {
+            Ok(body) => article_text(&body),
+            Err(e) => {
+                eprintln!("Error Fetching Article Text for {}: {}", url, e);
+                String::new()
+            }
+        };
+        texts.push(text);
+    }
+    let mut news = news.clone();
+    news.with_column(Series::new("Full Text", texts))?;
+    Ok(news)
+}
+
+/// Extracts the paragraph text of an article page
+fn article_text(body: &str) -> String {
+    match Document::from_read(body.as_bytes()) {
+        Ok(document) => document.find(Name("p"))
+            .map(|p| p.text().trim().to_string())
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Err(_) => String::new(),
+    }
+}
+
+/// Normalizes a headline for comparison, removing the trailing " - Publisher" added by the feed
+fn headline(title: &str, source: &str) -> String {
+    let title = title.strip_suffix(&format!(" - {}", source)).unwrap_or(title);
+    title.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+}
+
+/// 64 bit SimHash of the word bigrams of a text, similar texts have hashes with a small Hamming distance
+fn simhash(text: &str) -> u64 {
+    let words = text.split_whitespace().collect::<Vec<&str>>();
+    let features = if words.len() > 1 {
+        words.windows(2).map(|w| w.join(" ")).collect::<Vec<String>>()
+    } else {
+        words.iter().map(|w| w.to_string()).collect::<Vec<String>>()
+    };
+    let mut weights = [0i32; 64];
+    for feature in features {
+        let mut hasher = DefaultHasher::new();
+        feature.hash(&mut hasher);
+        let hash = hasher.finish();
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash >> bit & 1 == 1 { *weight += 1 } else { *weight -= 1 }
+        }
+    }
+    weights.iter().enumerate()
+        .fold(0u64, |acc, (bit, weight)| if *weight > 0 { acc | (1 << bit) } else { acc })
+}
@@ -0,0 +1,107 @@
+use std::error::Error;
+use polars::prelude::*;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use crate::data::config::Interval;
+
+
+pub trait Resample {
+    fn resample(&self, interval: Interval) -> Result<DataFrame, Box<dyn Error>>;
+}
+
+impl Resample for DataFrame {
+    /// Resamples an OHLCV DataFrame (e.g. from `get_chart`) to a lower frequency
+    ///
+    /// Bars are aggregated with the first open, highest high, lowest low, last close,
+    /// total volume and last adjclose of each period, and labelled with the period start.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Target interval (e.g. Interval::OneWeek), must be coarser than the data interval
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Resampled OHLCV DataFrame
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let daily = ticker.get_chart().await?;
+    /// let weekly = daily.resample(Interval::OneWeek)?;
+    /// let monthly = daily.resample(Interval::OneMonth)?;
+    /// ```
+    fn resample(&self, interval: Interval) -> Result<DataFrame, Box<dyn Error>> {
+        let df = self.sort(["timestamp"], SortMultipleOptions::new().with_order_descending(false))?;
+        let timestamps = df.column("timestamp")?.datetime()?.to_vec().iter()
+            .map(|x| DateTime::from_timestamp_millis(x.unwrap()).unwrap().naive_utc())
+            .collect::<Vec<NaiveDateTime>>();
+        let column = |name: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+            Ok(df.column(name)?.f64()?.to_vec().iter().map(|x| x.unwrap_or(f64::NAN)).collect())
+        };
+        let (open, high, low, close, volume) =
+            (column("open")?, column("high")?, column("low")?, column("close")?, column("volume")?);
+        let adjclose = match df.column("adjclose") {
+            Ok(_) => column("adjclose")?,
+            Err(_) => close.clone(),
+        };
+
+        let mut period_start: Vec<NaiveDateTime> = Vec::new();
+        let mut bars: Vec<[f64; 6]> = Vec::new();
+        for i in 0..timestamps.len() {
+            let period = period_start_of(timestamps[i], interval);
+            match (period_start.last(), bars.last_mut()) {
+                (Some(last), Some(bar)) if *last == period => {
+                    if bar[0].is_nan() { bar[0] = open[i]; }
+                    bar[1] = bar[1].max(high[i]);
+                    bar[2] = bar[2].min(low[i]);
+                    if !close[i].is_nan() { bar[3] = close[i]; }
+                    if !volume[i].is_nan() { bar[4] += volume[i]; }
+                    if !adjclose[i].is_nan() { bar[5] = adjclose[i]; }
+                }
+                _ => {
+                    period_start.push(period);
+                    bars.push([open[i], high[i], low[i], close[i], if volume[i].is_nan() { 0.0 } else { volume[i] }, adjclose[i]]);
+                }
+            }
+        }
+
+        let df = df!(
+            "timestamp" => &period_start,
+            "open" => bars.iter().map(|x| x[0]).collect::<Vec<f64>>(),
+            "high" => bars.iter().map(|x| x[1]).collect::<Vec<f64>>(),
+            "low" => bars.iter().map(|x| x[2]).collect::<Vec<f64>>(),
+            "close" => bars.iter().map(|x| x[3]).collect::<Vec<f64>>(),
+            "volume" => bars.iter().map(|x| x[4]).collect::<Vec<f64>>(),
+            "adjclose" => bars.iter().map(|x| x[5]).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+}
+
+/// Returns the start of the resampling period containing a timestamp
+fn period_start_of(timestamp: NaiveDateTime, interval: Interval) -> NaiveDateTime {
+    let floor_minutes = |minutes: u32| {
+        let minute_of_day = timestamp.hour() * 60 + timestamp.minute();
+        let floored = minute_of_day - minute_of_day % minutes;
+        timestamp.date().and_hms_opt(floored / 60, floored % 60, 0).unwrap()
+    };
+    let date = timestamp.date();
+    match interval {
+        Interval::TwoMinutes => floor_minutes(2),
+        Interval::FiveMinutes => floor_minutes(5),
+        Interval::FifteenMinutes => floor_minutes(15),
+        Interval::ThirtyMinutes => floor_minutes(30),
+        Interval::SixtyMinutes | Interval::OneHour => floor_minutes(60),
+        Interval::NinetyMinutes => floor_minutes(90),
+        Interval::OneDay => date.and_hms_opt(0, 0, 0).unwrap(),
+        Interval::FiveDays | Interval::OneWeek => {
+            let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            monday.and_hms_opt(0, 0, 0).unwrap()
+        }
+        Interval::OneMonth => NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+            .unwrap().and_hms_opt(0, 0, 0).unwrap(),
+        Interval::ThreeMonths => {
+            let month = (date.month0() / 3) * 3 + 1;
+            NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()
+        }
+    }
+}
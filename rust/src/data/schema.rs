@@ -0,0 +1,169 @@
+use std::error::Error;
+use chrono::NaiveDateTime;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+
+/// Typed rows of a finalytics DataFrame
+///
+/// The column names of each DataFrame are kept in one place, so code reading the rows keeps working
+/// when the DataFrame columns change, and the rows serialize to JSON with serde.
+pub trait DataFrameRows: Sized {
+    /// Columns the rows are read from and written to
+    fn columns() -> Vec<&'static str>;
+
+    /// Reads the rows of a DataFrame
+    fn from_dataframe(df: &DataFrame) -> Result<Vec<Self>, Box<dyn Error>>;
+
+    /// Writes rows to a DataFrame with the columns of `columns()`
+    fn to_dataframe(rows: &[Self]) -> Result<DataFrame, Box<dyn Error>>;
+}
+
+/// OHLCV bar, as returned by `TickerData::get_chart`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhlcvRow {
+    pub timestamp: NaiveDateTime,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub adjclose: f64,
+}
+
+impl DataFrameRows for OhlcvRow {
+    fn columns() -> Vec<&'static str> {
+        vec!["timestamp", "open", "high", "low", "close", "volume", "adjclose"]
+    }
+
+    fn from_dataframe(df: &DataFrame) -> Result<Vec<OhlcvRow>, Box<dyn Error>> {
+        let timestamps = datetime_values(df, "timestamp")?;
+        let open = float_values(df, "open")?;
+        let high = float_values(df, "high")?;
+        let low = float_values(df, "low")?;
+        let close = float_values(df, "close")?;
+        let volume = float_values(df, "volume")?;
+        let adjclose = float_values(df, "adjclose")?;
+        Ok((0..df.height())
+            .map(|i| OhlcvRow {
+                timestamp: timestamps[i],
+                open: open[i],
+                high: high[i],
+                low: low[i],
+                close: close[i],
+                volume: volume[i],
+                adjclose: adjclose[i],
+            })
+            .collect())
+    }
+
+    fn to_dataframe(rows: &[OhlcvRow]) -> Result<DataFrame, Box<dyn Error>> {
+        let df = df!(
+            "timestamp" => rows.iter().map(|x| x.timestamp).collect::<Vec<NaiveDateTime>>(),
+            "open" => rows.iter().map(|x| x.open).collect::<Vec<f64>>(),
+            "high" => rows.iter().map(|x| x.high).collect::<Vec<f64>>(),
+            "low" => rows.iter().map(|x| x.low).collect::<Vec<f64>>(),
+            "close" => rows.iter().map(|x| x.close).collect::<Vec<f64>>(),
+            "volume" => rows.iter().map(|x| x.volume).collect::<Vec<f64>>(),
+            "adjclose" => rows.iter().map(|x| x.adjclose).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+}
+
+/// Period return in percent, as returned by `TickerPerformance::returns`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReturnRow {
+    pub timestamp: NaiveDateTime,
+    pub returns: f64,
+}
+
+impl DataFrameRows for ReturnRow {
+    fn columns() -> Vec<&'static str> {
+        vec!["timestamp", "roc-1"]
+    }
+
+    fn from_dataframe(df: &DataFrame) -> Result<Vec<ReturnRow>, Box<dyn Error>> {
+        let timestamps = datetime_values(df, "timestamp")?;
+        let returns = float_values(df, "roc-1")?;
+        Ok((0..df.height())
+            .map(|i| ReturnRow { timestamp: timestamps[i], returns: returns[i] })
+            .collect())
+    }
+
+    fn to_dataframe(rows: &[ReturnRow]) -> Result<DataFrame, Box<dyn Error>> {
+        let df = df!(
+            "timestamp" => rows.iter().map(|x| x.timestamp).collect::<Vec<NaiveDateTime>>(),
+            "roc-1" => rows.iter().map(|x| x.returns).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+}
+
+/// News headline, as returned by `TickerData::get_news`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewsRow {
+    pub published_date: NaiveDateTime,
+    pub source: String,
+    pub title: String,
+    pub url: String,
+    pub sentiment_score: Option<f64>,
+}
+
+impl DataFrameRows for NewsRow {
+    fn columns() -> Vec<&'static str> {
+        vec!["Published Date", "Source", "Title", "URL", "Sentiment Score"]
+    }
+
+    fn from_dataframe(df: &DataFrame) -> Result<Vec<NewsRow>, Box<dyn Error>> {
+        let dates = datetime_values(df, "Published Date")?;
+        let sources = string_values(df, "Source")?;
+        let titles = string_values(df, "Title")?;
+        let urls = string_values(df, "URL")?;
+        let scores = match df.column("Sentiment Score") {
+            Ok(series) => series.cast(&DataType::Float64)?.f64()?.to_vec(),
+            Err(_) => vec![None; df.height()],
+        };
+        Ok((0..df.height())
+            .map(|i| NewsRow {
+                published_date: dates[i],
+                source: sources[i].clone(),
+                title: titles[i].clone(),
+                url: urls[i].clone(),
+                sentiment_score: scores[i],
+            })
+            .collect())
+    }
+
+    fn to_dataframe(rows: &[NewsRow]) -> Result<DataFrame, Box<dyn Error>> {
+        let df = df!(
+            "Published Date" => rows.iter().map(|x| x.published_date).collect::<Vec<NaiveDateTime>>(),
+            "Source" => rows.iter().map(|x| x.source.clone()).collect::<Vec<String>>(),
+            "Title" => rows.iter().map(|x| x.title.clone()).collect::<Vec<String>>(),
+            "URL" => rows.iter().map(|x| x.url.clone()).collect::<Vec<String>>(),
+            "Sentiment Score" => rows.iter().map(|x| x.sentiment_score).collect::<Vec<Option<f64>>>()
+        )?;
+        Ok(df)
+    }
+}
+
+/// Values of a numeric column, nulls are read as NaN
+fn float_values(df: &DataFrame, name: &str) -> Result<Vec<f64>, Box<dyn Error>> {
+    Ok(df.column(name)?.cast(&DataType::Float64)?.f64()?.into_iter()
+        .map(|x| x.unwrap_or(f64::NAN))
+        .collect())
+}
+
+/// Values of a text column, nulls are read as empty strings
+fn string_values(df: &DataFrame, name: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    Ok(df.column(name)?.cast(&DataType::String)?.str()?.into_iter()
+        .map(|x| x.unwrap_or_default().to_string())
+        .collect())
+}
+
+/// Values of a datetime column
+fn datetime_values(df: &DataFrame, name: &str) -> Result<Vec<NaiveDateTime>, Box<dyn Error>> {
+    df.column(name)?.datetime()?.as_datetime_iter()
+        .map(|x| x.ok_or_else(|| format!("Null value in the {} column", name).into()))
+        .collect()
+}
@@ -8,17 +8,30 @@ use indicatif::{ProgressBar, ProgressStyle};
 use tokio::sync::Semaphore;
 use crate::models::ticker::Ticker;
 use crate::utils::web_utils::{fetch_news, get_json_response};
+use crate::data::validation::validate_ohlcv;
+use crate::data::news::{NewsOptions, add_full_text, clean_news};
 use crate::utils::date_utils::{round_datetime_to_day, round_datetime_to_hour, round_datetime_to_minute, time_to_maturity, to_date, to_datetime, to_timestamp};
-use crate::data::config::{Fundamentals, FundamentalsResponse, Interval, Object, OptionContract, Options, Quote, StatementFrequency, StatementType, TickerSummaryStats};
+use crate::data::config::{EsgScores, Fundamentals, FundamentalsResponse, InsiderTransaction, Interval, Object, OptionContract, Options, PriceTarget, Quote, QuoteType, SegmentType, StatementFrequency, StatementType, TickerSummaryStats};
 
 
 pub trait TickerData {
     fn get_quote(&self) -> impl std::future::Future<Output = Result<Quote, Box<dyn Error>>>;
     fn get_ticker_stats(&self) -> impl std::future::Future<Output = Result<TickerSummaryStats, Box<dyn Error>>>;
     fn get_chart(&self) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn get_events(&self) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
     fn get_options(&self) -> impl std::future::Future<Output = Result<Options, Box<dyn Error>>>;
     fn get_fundamentals(&self, statement_type: StatementType, frequency: StatementFrequency) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
     fn get_news(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn get_news_with(&self, options: NewsOptions) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn get_insider_transactions(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn get_price_target(&self) -> impl std::future::Future<Output = Result<PriceTarget, Box<dyn Error>>>;
+    fn get_esg_scores(&self) -> impl std::future::Future<Output = Result<EsgScores, Box<dyn Error>>>;
+    fn get_quote_fields(&self, fields: Vec<&str>) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn get_quote_type(&self) -> impl std::future::Future<Output = Result<QuoteType, Box<dyn Error>>>;
+    fn get_fund_holdings(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn get_fund_sectors(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn get_earnings_trend(&self) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
+    fn get_revenue_segments(&self, segment_type: SegmentType) -> impl std::future::Future<Output = Result<DataFrame, Box<dyn Error>>>;
 }
 
 impl TickerData for Ticker {
@@ -43,8 +56,21 @@ impl TickerData for Ticker {
     }
 
 
-    /// Returns the Ticker OHLCV Data from Yahoo Finance for a given time range
+    /// Returns the Ticker OHLCV Data from Yahoo Finance (or the user supplied KLINE data) for a given time range
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err, fields(symbol = %self.ticker)))]
     async fn get_chart(&self) -> Result<DataFrame, Box<dyn Error>> {
+        if let Some(kline) = &self.ticker_data {
+            let df = kline.to_dataframe(Some(&self.start_date), Some(&self.end_date))?;
+            if self.validate_data {
+                let (df, report) = validate_ohlcv(&self.ticker, &df, self.interval, self.calendar)?;
+                if !report.is_clean() {
+                    eprintln!("{}", report);
+                }
+                return Ok(df);
+            }
+            return Ok(df);
+        }
+
         let period1 = to_timestamp(&self.start_date)?;
         let period2 = to_timestamp(&self.end_date)?;
         let url = format!(
@@ -53,90 +79,7 @@ impl TickerData for Ticker {
         );
         let result= get_json_response(url).await?;
 
-        let value = &result["chart"]["result"][0];
-        let timestamp = &value["timestamp"]
-            .as_array()
-            .ok_or(format!("timestamp array not found for {}: {}", self.ticker, result))?
-            .iter()
-            .map(|ts| {
-                let timestamp = ts.as_i64().unwrap();
-                let datetime = match self.interval {
-                    Interval::OneDay | Interval::FiveDays | Interval::OneWeek | Interval::OneMonth | Interval::ThreeMonths => {
-                        round_datetime_to_day(DateTime::from_timestamp(timestamp, 0).unwrap())
-                    }
-                    Interval::SixtyMinutes | Interval::OneHour => {
-                        round_datetime_to_hour(DateTime::from_timestamp(timestamp, 0).unwrap())
-                    },
-                    Interval::NinetyMinutes | Interval::ThirtyMinutes | Interval::FifteenMinutes | Interval::FiveMinutes | Interval::TwoMinutes => {
-                        round_datetime_to_minute(DateTime::from_timestamp(timestamp, 0).unwrap())
-                    },
-                };
-                datetime
-            })
-            .collect::<Vec<NaiveDateTime>>();
-
-        let indicators = &value["indicators"]["quote"][0];
-
-        let open = indicators["open"]
-            .as_array()
-            .ok_or(format!("open array not found for {}: {}", self.ticker, result))?
-            .iter()
-            .map(|o| o.as_f64().unwrap_or(0.0))
-            .collect::<Vec<f64>>();
-
-        let high = indicators["high"]
-            .as_array()
-            .ok_or(format!("high array not found for {}: {}", self.ticker, result))?
-            .iter()
-            .map(|h| h.as_f64().unwrap_or(0.0))
-            .collect::<Vec<f64>>();
-
-        let low = indicators["low"]
-            .as_array()
-            .ok_or(format!("low array not found for {}: {}", self.ticker, result))?
-            .iter()
-            .map(|l| l.as_f64().unwrap_or(0.0))
-            .collect::<Vec<f64>>();
-
-        let close = indicators["close"]
-            .as_array()
-            .ok_or(format!("close array not found for {}: {}", self.ticker, result))?
-            .iter()
-            .map(|c| c.as_f64().unwrap_or(0.0))
-            .collect::<Vec<f64>>();
-
-        let volume = indicators["volume"]
-            .as_array()
-            .ok_or(format!("volume array not found for {}: {}", self.ticker, result))?
-            .iter()
-            .map(|v| v.as_f64().unwrap_or(0.0))
-            .collect::<Vec<f64>>();
-
-        let adjclose = &value["indicators"]["adjclose"][0]["adjclose"]
-            .as_array()
-            .unwrap_or_else(|| {
-                indicators["close"]
-                    .as_array()
-                    .ok_or(format!("close array not found for {}: {}", self.ticker, result))
-                    .unwrap_or_else(|_| {
-                        indicators["close"]
-                            .as_array()
-                            .expect("close array not found")
-                    })
-            })
-            .iter()
-            .map(|c| c.as_f64().unwrap_or(0.0))
-            .collect::<Vec<f64>>();
-
-        let df = df!(
-        "timestamp" => &timestamp,
-        "open" => &open,
-        "high" => &high,
-        "low" => &low,
-        "close" => &close,
-        "volume" => &volume,
-        "adjclose" => &adjclose
-    )?;
+        let df = parse_chart(&self.ticker, &result, self.interval)?;
 
         // check if any adjclose values are 0.0
         let mask = df.column("adjclose")?.gt(0.0)?;
@@ -150,6 +93,84 @@ impl TickerData for Ticker {
             .map(|x| x.unwrap() < dt)
             .collect();
         let df = df.filter(&mask)?;
+
+        if self.validate_data {
+            let (df, report) = validate_ohlcv(&self.ticker, &df, self.interval, self.calendar)?;
+            if !report.is_clean() {
+                eprintln!("{}", report);
+            }
+            return Ok(df);
+        }
+        Ok(df)
+    }
+
+
+    /// Returns the Ticker Corporate Events (dividends, splits and earnings) from Yahoo Finance for a given time range
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns timestamp, type (dividend/split/earnings), value and event (chart label)
+    async fn get_events(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let period1 = to_timestamp(&self.start_date)?;
+        let period2 = to_timestamp(&self.end_date)?;
+        let url = format!(
+            "https://query1.finance.yahoo.com/v8/finance/chart/{}?period1={}&period2={}&interval={}&events=div%7Csplit%7Cearn",
+            self.ticker, period1, period2, self.interval.to_string()
+        );
+        let result = get_json_response(url).await?;
+        let events = &result["chart"]["result"][0]["events"];
+
+        let mut timestamps: Vec<NaiveDateTime> = Vec::new();
+        let mut types: Vec<String> = Vec::new();
+        let mut values: Vec<Option<f64>> = Vec::new();
+        let mut labels: Vec<String> = Vec::new();
+
+        let mut push_event = |date: &Value, event_type: &str, value: Option<f64>, label: String| {
+            if let Some(ts) = date.as_i64().and_then(|x| DateTime::from_timestamp(x, 0)) {
+                timestamps.push(round_datetime_to_day(ts));
+                types.push(event_type.to_string());
+                values.push(value);
+                labels.push(label);
+            }
+        };
+
+        if let Some(dividends) = events["dividends"].as_object() {
+            for dividend in dividends.values() {
+                let amount = dividend["amount"].as_f64();
+                push_event(&dividend["date"], "dividend", amount,
+                           format!("Dividend {:.2}", amount.unwrap_or(0.0)));
+            }
+        }
+
+        if let Some(splits) = events["splits"].as_object() {
+            for split in splits.values() {
+                let numerator = split["numerator"].as_f64().unwrap_or(1.0);
+                let denominator = split["denominator"].as_f64().unwrap_or(1.0);
+                let ratio = split["splitRatio"].as_str().map(|x| x.replace('/', ":"))
+                    .unwrap_or(format!("{}:{}", numerator, denominator));
+                push_event(&split["date"], "split", Some(numerator / denominator),
+                           format!("Split {}", ratio));
+            }
+        }
+
+        if let Some(earnings) = events["earnings"].as_object() {
+            for earning in earnings.values() {
+                let eps = earning["epsActual"].as_f64();
+                let label = match eps {
+                    Some(eps) => format!("Earnings (EPS {:.2})", eps),
+                    None => "Earnings".to_string(),
+                };
+                push_event(&earning["date"], "earnings", eps, label);
+            }
+        }
+
+        let df = df!(
+            "timestamp" => &timestamps,
+            "type" => &types,
+            "value" => &values,
+            "event" => &labels
+        )?;
+        let df = df.sort(["timestamp"], SortMultipleOptions::new().with_order_descending(false))?;
         Ok(df)
     }
 
@@ -223,6 +244,7 @@ impl TickerData for Ticker {
             "lastTradeDate" => calls_vec.iter().map(|x| DateTime::from_timestamp(x.lastTradeDate, 0).unwrap().naive_local()).collect::<Vec<NaiveDateTime>>(),
             "impliedVolatility" => calls_vec.iter().map(|x| x.impliedVolatility).collect::<Vec<f64>>(),
             "inTheMoney" => calls_vec.iter().map(|x| x.inTheMoney).collect::<Vec<bool>>(),
+            "volume" => calls_vec.iter().map(|x| x.volume).collect::<Vec<f64>>(),
         )?;
 
             let puts = &result["optionChain"]["result"][0]["options"][0]["puts"];
@@ -246,6 +268,7 @@ impl TickerData for Ticker {
             "lastTradeDate" => puts_vec.iter().map(|x| DateTime::from_timestamp(x.lastTradeDate, 0).unwrap().naive_local()).collect::<Vec<NaiveDateTime>>(),
             "impliedVolatility" => puts_vec.iter().map(|x| x.impliedVolatility).collect::<Vec<f64>>(),
             "inTheMoney" => puts_vec.iter().map(|x| x.inTheMoney).collect::<Vec<bool>>(),
+            "volume" => puts_vec.iter().map(|x| x.volume).collect::<Vec<f64>>(),
         )?;
 
             let df = calls_df.vstack(&puts_df)?;
@@ -367,6 +390,21 @@ impl TickerData for Ticker {
     }
 
     async fn get_news(&self) -> Result<DataFrame, Box<dyn Error>> {
+        self.get_news_with(NewsOptions::default()).await
+    }
+
+    /// Returns the Ticker News Headlines from Google News for the ticker (or options) date range, filtered by source,
+    /// deduplicated across outlets and optionally with the article text
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - News options (e.g. NewsOptions::new().exclude_sources(vec!["Yahoo Finance"]))
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns Published Date, Source (publisher), Title, Link (html), URL,
+    ///   Sentiment Score, Duplicates and, if requested, Full Text
+    async fn get_news_with(&self, options: NewsOptions) -> Result<DataFrame, Box<dyn Error>> {
         let quote = self.get_quote().await?;
         let symbol = if quote.asset_class == "CRYPTOCURRENCY" {
             self.ticker.replace("-USD", "")
@@ -375,8 +413,8 @@ impl TickerData for Ticker {
         };
         let token = format!("({} OR {})", &symbol, &quote.name);
 
-        let start_date = NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d")?;
-        let end_date = NaiveDate::parse_from_str(&self.end_date, "%Y-%m-%d")?;
+        let start_date = NaiveDate::parse_from_str(options.start_date.as_ref().unwrap_or(&self.start_date), "%Y-%m-%d")?;
+        let end_date = NaiveDate::parse_from_str(options.end_date.as_ref().unwrap_or(&self.end_date), "%Y-%m-%d")?;
 
         let mut current_date = start_date;
         let mut futures = Vec::new();
@@ -386,7 +424,7 @@ impl TickerData for Ticker {
         let semaphore = Arc::new(Semaphore::new(max_concurrent_tasks));
 
         // Create and configure the progress bar
-        let total_steps = (end_date - start_date).num_days()/3;
+        let total_steps = (end_date - start_date).num_days()/options.window_days;
         let pb = ProgressBar::new(total_steps as u64);
         pb.set_style(
             ProgressStyle::default_bar()
@@ -395,7 +433,7 @@ impl TickerData for Ticker {
         );
 
         while current_date < end_date {
-            let next_date = (current_date + Duration::days(3)).min(end_date);
+            let next_date = (current_date + Duration::days(options.window_days)).min(end_date);
             let token_clone = token.clone();
             let current_date_clone = current_date.clone();
             let next_date_clone = next_date.clone();
@@ -431,11 +469,437 @@ impl TickerData for Ticker {
             }
         }
 
-        combined_df.sort(&["Published Date"], SortMultipleOptions::new().with_order_descending(false))?;
+        let combined_df = combined_df.sort(["Published Date"], SortMultipleOptions::new().with_order_descending(false))?;
+        let combined_df = clean_news(&combined_df, &options)?;
+        let combined_df = if options.full_text {
+            add_full_text(&combined_df).await?
+        } else {
+            combined_df
+        };
 
         pb.finish_with_message(format!("News Data Fetched for {}", &symbol));
 
         Ok(combined_df)
     }
 
-}
\ No newline at end of file
+    /// Returns the Ticker Insider Transactions from Yahoo Finance
+    ///
+    /// Share counts and values are signed: purchases are positive, sales are negative
+    /// and all other transactions (awards, gifts, option exercises) are zero.
+    async fn get_insider_transactions(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=insiderTransactions",
+            self.ticker
+        );
+        let result = get_json_response(url).await?;
+        let transactions = &result["quoteSummary"]["result"][0]["insiderTransactions"]["transactions"];
+        let transactions: Vec<InsiderTransaction> = serde_json::from_value(transactions.clone())
+            .map_err(|e| format!("Failed to deserialize into InsiderTransaction: {}", e))?;
+
+        let mut dates = Vec::new();
+        let mut names = Vec::new();
+        let mut relations = Vec::new();
+        let mut directions = Vec::new();
+        let mut descriptions = Vec::new();
+        let mut shares = Vec::new();
+        let mut values = Vec::new();
+
+        for t in &transactions {
+            let sign = if t.transaction_text.starts_with("Purchase") {
+                1.0
+            } else if t.transaction_text.starts_with("Sale") {
+                -1.0
+            } else {
+                0.0
+            };
+            let direction = match sign {
+                s if s > 0.0 => "Buy",
+                s if s < 0.0 => "Sell",
+                _ => "Other",
+            };
+            dates.push(DateTime::from_timestamp(t.start_date.raw as i64, 0).unwrap().naive_local());
+            names.push(t.filer_name.clone());
+            relations.push(t.filer_relation.clone());
+            directions.push(direction.to_string());
+            descriptions.push(t.transaction_text.clone());
+            shares.push(sign * t.shares.as_ref().map(|x| x.raw).unwrap_or(0.0));
+            values.push(sign * t.value.as_ref().map(|x| x.raw).unwrap_or(0.0));
+        }
+
+        let df = df!(
+            "timestamp" => dates,
+            "filer_name" => names,
+            "filer_relation" => relations,
+            "direction" => directions,
+            "transaction" => descriptions,
+            "shares" => shares,
+            "value" => values
+        )?;
+        let df = df.sort(["timestamp"], SortMultipleOptions::new().with_order_descending(false))?;
+        Ok(df)
+    }
+
+    /// Returns the Ticker Analyst Price Targets from Yahoo Finance
+    async fn get_price_target(&self) -> Result<PriceTarget, Box<dyn Error>> {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=financialData",
+            self.ticker
+        );
+        let result = get_json_response(url).await?;
+        let value = &result["quoteSummary"]["result"][0]["financialData"];
+        let target: PriceTarget = serde_json::from_value(value.clone())
+            .map_err(|e| format!("Failed to deserialize into PriceTarget: {}", e))?;
+        Ok(target)
+    }
+
+    /// Returns the Ticker ESG Risk Ratings from Yahoo Finance
+    async fn get_esg_scores(&self) -> Result<EsgScores, Box<dyn Error>> {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=esgScores",
+            self.ticker
+        );
+        let result = get_json_response(url).await?;
+        let value = &result["quoteSummary"]["result"][0]["esgScores"];
+        if value.is_null() {
+            return Err(format!("No ESG scores found for {}", self.ticker).into());
+        }
+        let scores: EsgScores = serde_json::from_value(value.clone())
+            .map_err(|e| format!("Failed to deserialize into EsgScores: {}", e))?;
+        Ok(scores)
+    }
+
+    /// Returns a custom list of quote fields for the Ticker from Yahoo Finance
+    ///
+    /// Fields are looked up in the quote first, then in the key statistics, financial data
+    /// and summary detail modules (e.g. "trailingPE", "pegRatio", "beta", "shortRatio", "sharesShort")
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Yahoo Finance field names in camelCase
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Single row with a symbol column and one column per field (null if unavailable)
+    async fn get_quote_fields(&self, fields: Vec<&str>) -> Result<DataFrame, Box<dyn Error>> {
+        let fields = fields.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let values = fetch_quote_fields(&self.ticker, &fields).await?;
+        quote_fields_dataframe(std::slice::from_ref(&self.ticker), &[values], &fields)
+    }
+
+    /// Returns the asset class of the Ticker (e.g. QuoteType::Etf) from the Yahoo Finance quote
+    async fn get_quote_type(&self) -> Result<QuoteType, Box<dyn Error>> {
+        let values = fetch_quote_fields(&self.ticker, &["quoteType".to_string()]).await?;
+        Ok(QuoteType::from_str(values[0].as_str().unwrap_or_default()))
+    }
+
+    /// Returns the top holdings of an ETF or mutual fund from Yahoo Finance
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns symbol, name and weight (in percent)
+    async fn get_fund_holdings(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=topHoldings",
+            self.ticker
+        );
+        let result = get_json_response(url).await?;
+        let holdings = result["quoteSummary"]["result"][0]["topHoldings"]["holdings"]
+            .as_array()
+            .ok_or(format!("No fund holdings found for {}", self.ticker))?;
+        let df = df!(
+            "symbol" => holdings.iter().map(|x| x["symbol"].as_str().unwrap_or_default().to_string()).collect::<Vec<String>>(),
+            "name" => holdings.iter().map(|x| x["holdingName"].as_str().unwrap_or_default().to_string()).collect::<Vec<String>>(),
+            "weight" => holdings.iter().map(|x| x["holdingPercent"]["raw"].as_f64().unwrap_or(0.0) * 100.0).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+
+    /// Returns the sector weightings of an ETF or mutual fund from Yahoo Finance
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - with columns sector and weight (in percent), sorted by weight
+    async fn get_fund_sectors(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=topHoldings",
+            self.ticker
+        );
+        let result = get_json_response(url).await?;
+        let weightings = result["quoteSummary"]["result"][0]["topHoldings"]["sectorWeightings"]
+            .as_array()
+            .ok_or(format!("No fund sector weightings found for {}", self.ticker))?;
+        // Each weighting is an object with a single sector key, e.g. {"technology": {"raw": 0.3}}
+        let (sectors, weights): (Vec<String>, Vec<f64>) = weightings.iter()
+            .filter_map(|x| x.as_object())
+            .flat_map(|x| x.iter().map(|(k, v)| (k.clone(), v["raw"].as_f64().unwrap_or(0.0) * 100.0)))
+            .unzip();
+        let df = df!("sector" => sectors, "weight" => weights)?;
+        let df = df.sort(["weight"], SortMultipleOptions::new().with_order_descending(true))?;
+        Ok(df)
+    }
+
+    /// Returns the analyst earnings and revenue estimates of the Ticker from Yahoo Finance
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per estimate period (current quarter "0q", next quarter "+1q", current year "0y"
+    ///   and next year "+1y") with columns period, end_date, eps_estimate, eps_low, eps_high, eps_year_ago,
+    ///   eps_growth, eps_analysts, revenue_estimate, revenue_low, revenue_high, revenue_year_ago, revenue_growth,
+    ///   revenue_analysts, the EPS estimate trend eps_current, eps_7d_ago, eps_30d_ago, eps_60d_ago and
+    ///   eps_90d_ago, and the number of EPS revisions up_7d, up_30d, down_7d and down_30d
+    async fn get_earnings_trend(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=earningsTrend",
+            self.ticker
+        );
+        let result = get_json_response(url).await?;
+        let trend = result["quoteSummary"]["result"][0]["earningsTrend"]["trend"]
+            .as_array()
+            .ok_or(format!("No earnings estimates found for {}", self.ticker))?;
+        // Estimate periods beyond next year (e.g. "+5y" growth) carry no estimates
+        let trend = trend.iter()
+            .filter(|x| matches!(x["period"].as_str(), Some("0q" | "+1q" | "0y" | "+1y")))
+            .collect::<Vec<&Value>>();
+        if trend.is_empty() {
+            return Err(format!("No earnings estimates found for {}", self.ticker).into());
+        }
+        let raw = |path: &[&str]| -> Vec<Option<f64>> {
+            trend.iter()
+                .map(|x| path.iter().fold(*x, |v, key| &v[*key])["raw"].as_f64())
+                .collect()
+        };
+
+        let df = df!(
+            "period" => trend.iter().map(|x| x["period"].as_str().unwrap_or_default()).collect::<Vec<&str>>(),
+            "end_date" => trend.iter().map(|x| x["endDate"].as_str().unwrap_or_default()).collect::<Vec<&str>>(),
+            "eps_estimate" => raw(&["earningsEstimate", "avg"]),
+            "eps_low" => raw(&["earningsEstimate", "low"]),
+            "eps_high" => raw(&["earningsEstimate", "high"]),
+            "eps_year_ago" => raw(&["earningsEstimate", "yearAgoEps"]),
+            "eps_growth" => raw(&["earningsEstimate", "growth"]),
+            "eps_analysts" => raw(&["earningsEstimate", "numberOfAnalysts"]),
+            "revenue_estimate" => raw(&["revenueEstimate", "avg"]),
+            "revenue_low" => raw(&["revenueEstimate", "low"]),
+            "revenue_high" => raw(&["revenueEstimate", "high"]),
+            "revenue_year_ago" => raw(&["revenueEstimate", "yearAgoRevenue"]),
+            "revenue_growth" => raw(&["revenueEstimate", "growth"]),
+            "revenue_analysts" => raw(&["revenueEstimate", "numberOfAnalysts"]),
+            "eps_current" => raw(&["epsTrend", "current"]),
+            "eps_7d_ago" => raw(&["epsTrend", "7daysAgo"]),
+            "eps_30d_ago" => raw(&["epsTrend", "30daysAgo"]),
+            "eps_60d_ago" => raw(&["epsTrend", "60daysAgo"]),
+            "eps_90d_ago" => raw(&["epsTrend", "90daysAgo"]),
+            "up_7d" => raw(&["epsRevisions", "upLast7days"]),
+            "up_30d" => raw(&["epsRevisions", "upLast30days"]),
+            "down_7d" => raw(&["epsRevisions", "downLast7Days"]),
+            "down_30d" => raw(&["epsRevisions", "downLast30days"])
+        )?;
+        Ok(df)
+    }
+
+    /// Returns the annual revenue by business or geographic segment of the Ticker
+    ///
+    /// Yahoo Finance does not publish segment data, so the segments are fetched from Financial Modeling Prep,
+    /// which requires an API key in the `FMP_API_KEY` environment variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment_type` - Business or geographic segments
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per fiscal year and segment with columns date, fiscal_year, segment and revenue,
+    ///   sorted by date
+    async fn get_revenue_segments(&self, segment_type: SegmentType) -> Result<DataFrame, Box<dyn Error>> {
+        let api_key = std::env::var("FMP_API_KEY")
+            .map_err(|_| "Segment revenue requires a Financial Modeling Prep API key in FMP_API_KEY")?;
+        let endpoint = match segment_type {
+            SegmentType::Business => "revenue-product-segmentation",
+            SegmentType::Geographic => "revenue-geographic-segmentation",
+        };
+        let url = format!(
+            "https://financialmodelingprep.com/stable/{}?symbol={}&period=annual&apikey={}",
+            endpoint, self.ticker, api_key
+        );
+        let result = get_json_response(url).await?;
+        let periods = result.as_array()
+            .ok_or(format!("No {} segments found for {}", segment_type.to_string(), self.ticker))?;
+
+        let mut rows = periods.iter()
+            .flat_map(|period| {
+                let date = period["date"].as_str().unwrap_or_default().to_string();
+                let fiscal_year = period["fiscalYear"].as_i64().unwrap_or_default();
+                period["data"].as_object().into_iter().flatten()
+                    .filter_map(move |(segment, revenue)| Some((date.clone(), fiscal_year, segment.clone(), revenue.as_f64()?)))
+            })
+            .collect::<Vec<(String, i64, String, f64)>>();
+        if rows.is_empty() {
+            return Err(format!("No {} segments found for {}", segment_type.to_string(), self.ticker).into());
+        }
+        rows.sort_by(|a, b| a.0.cmp(&b.0).then(b.3.total_cmp(&a.3)));
+
+        let df = df!(
+            "date" => rows.iter().map(|x| x.0.as_str()).collect::<Vec<&str>>(),
+            "fiscal_year" => rows.iter().map(|x| x.1).collect::<Vec<i64>>(),
+            "segment" => rows.iter().map(|x| x.2.as_str()).collect::<Vec<&str>>(),
+            "revenue" => rows.iter().map(|x| x.3).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+}
+
+/// Yahoo Finance quoteSummary modules searched for fields missing from the quote
+const QUOTE_FIELD_MODULES: [&str; 4] = ["defaultKeyStatistics", "financialData", "summaryDetail", "price"];
+
+/// Parses a Yahoo Finance chart response into an OHLCV DataFrame
+///
+/// # Arguments
+///
+/// * `symbol` - Ticker symbol, used in error messages
+/// * `result` - JSON response of the Yahoo Finance v8 chart endpoint
+/// * `interval` - Interval of the requested bars, used to round the timestamps
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns timestamp, open, high, low, close, volume and adjclose
+pub fn parse_chart(symbol: &str, result: &Value, interval: Interval) -> Result<DataFrame, Box<dyn Error>> {
+    let value = &result["chart"]["result"][0];
+    let timestamp = &value["timestamp"]
+        .as_array()
+        .ok_or(format!("timestamp array not found for {}: {}", symbol, result))?
+        .iter()
+        .map(|ts| {
+            let timestamp = ts.as_i64().unwrap();
+            match interval {
+                Interval::OneDay | Interval::FiveDays | Interval::OneWeek | Interval::OneMonth | Interval::ThreeMonths => {
+                    round_datetime_to_day(DateTime::from_timestamp(timestamp, 0).unwrap())
+                }
+                Interval::SixtyMinutes | Interval::OneHour => {
+                    round_datetime_to_hour(DateTime::from_timestamp(timestamp, 0).unwrap())
+                },
+                Interval::NinetyMinutes | Interval::ThirtyMinutes | Interval::FifteenMinutes | Interval::FiveMinutes | Interval::TwoMinutes => {
+                    round_datetime_to_minute(DateTime::from_timestamp(timestamp, 0).unwrap())
+                },
+            }
+        })
+        .collect::<Vec<NaiveDateTime>>();
+
+    let indicators = &value["indicators"]["quote"][0];
+
+    let open = indicators["open"]
+        .as_array()
+        .ok_or(format!("open array not found for {}: {}", symbol, result))?
+        .iter()
+        .map(|o| o.as_f64().unwrap_or(0.0))
+        .collect::<Vec<f64>>();
+
+    let high = indicators["high"]
+        .as_array()
+        .ok_or(format!("high array not found for {}: {}", symbol, result))?
+        .iter()
+        .map(|h| h.as_f64().unwrap_or(0.0))
+        .collect::<Vec<f64>>();
+
+    let low = indicators["low"]
+        .as_array()
+        .ok_or(format!("low array not found for {}: {}", symbol, result))?
+        .iter()
+        .map(|l| l.as_f64().unwrap_or(0.0))
+        .collect::<Vec<f64>>();
+
+    let close = indicators["close"]
+        .as_array()
+        .ok_or(format!("close array not found for {}: {}", symbol, result))?
+        .iter()
+        .map(|c| c.as_f64().unwrap_or(0.0))
+        .collect::<Vec<f64>>();
+
+    let volume = indicators["volume"]
+        .as_array()
+        .ok_or(format!("volume array not found for {}: {}", symbol, result))?
+        .iter()
+        .map(|v| v.as_f64().unwrap_or(0.0))
+        .collect::<Vec<f64>>();
+
+    let adjclose = &value["indicators"]["adjclose"][0]["adjclose"]
+        .as_array()
+        .unwrap_or_else(|| {
+            indicators["close"]
+                .as_array()
+                .ok_or(format!("close array not found for {}: {}", symbol, result))
+                .unwrap_or_else(|_| {
+                    indicators["close"]
+                        .as_array()
+                        .expect("close array not found")
+                })
+        })
+        .iter()
+        .map(|c| c.as_f64().unwrap_or(0.0))
+        .collect::<Vec<f64>>();
+
+    let df = df!(
+        "timestamp" => &timestamp,
+        "open" => &open,
+        "high" => &high,
+        "low" => &low,
+        "close" => &close,
+        "volume" => &volume,
+        "adjclose" => &adjclose
+    )?;
+    Ok(df)
+}
+
+
+/// Fetches the values of a list of quote fields for a symbol
+pub(crate) async fn fetch_quote_fields(symbol: &str, fields: &[String]) -> Result<Vec<Value>, Box<dyn Error>> {
+    let url = format!("https://query2.finance.yahoo.com/v6/finance/options/{}", symbol);
+    let result = get_json_response(url).await?;
+    let quote = &result["optionChain"]["result"][0]["quote"];
+    let mut values = fields.iter().map(|f| quote[f].clone()).collect::<Vec<Value>>();
+
+    // Only request the summary modules when a field is not part of the quote
+    if values.iter().any(|v| v.is_null()) {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules={}",
+            symbol, QUOTE_FIELD_MODULES.join(",")
+        );
+        let result = get_json_response(url).await?;
+        let summary = &result["quoteSummary"]["result"][0];
+        for (value, field) in values.iter_mut().zip(fields.iter()) {
+            if value.is_null() {
+                if let Some(v) = QUOTE_FIELD_MODULES.iter().map(|m| &summary[*m][field]).find(|v| !v.is_null()) {
+                    *value = v.clone();
+                }
+            }
+        }
+    }
+
+    // Summary module values are objects holding the raw and formatted value
+    let values = values.into_iter().map(|v| match v.get("raw") {
+        Some(raw) => raw.clone(),
+        None => v,
+    }).collect::<Vec<Value>>();
+    Ok(values)
+}
+
+/// Builds a DataFrame of quote field values, one row per symbol
+///
+/// Fields with numeric values for every symbol become f64 columns, all other fields become string columns
+pub(crate) fn quote_fields_dataframe(symbols: &[String], rows: &[Vec<Value>], fields: &[String]) -> Result<DataFrame, Box<dyn Error>> {
+    let mut columns = vec![Series::new("symbol", symbols.to_vec())];
+    for (i, field) in fields.iter().enumerate() {
+        let values = rows.iter().map(|r| &r[i]).collect::<Vec<&Value>>();
+        let numeric = values.iter().all(|v| v.is_number() || v.is_null());
+        let series = if numeric {
+            Series::new(field, values.iter().map(|v| v.as_f64()).collect::<Vec<Option<f64>>>())
+        } else {
+            Series::new(field, values.iter().map(|v| match v {
+                Value::Null => None,
+                Value::String(s) => Some(s.clone()),
+                other => Some(other.to_string()),
+            }).collect::<Vec<Option<String>>>())
+        };
+        columns.push(series);
+    }
+    Ok(DataFrame::new(columns)?)
+}
@@ -2,14 +2,33 @@ use std::error::Error;
 use polars::prelude::*;
 use futures::future::join_all;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::Value;
 use crate::data::config::TickerSummaryStats;
+use crate::data::ticker::{fetch_quote_fields, quote_fields_dataframe};
 use crate::analytics::performance::TickerPerformanceStats;
+use crate::analytics::ranking::{RankMetric, percentile_ranks};
+use crate::analytics::insiders::{InsiderActivity, InsiderScore, DEFAULT_INSIDER_WINDOWS};
+use crate::analytics::options::{OptionScreenCriteria, OptionsAnalytics};
+use crate::analytics::fundamentals::FundamentalScores;
+use crate::analytics::liquidity::{LiquidityAnalytics, LiquidityMetrics, LIQUIDITY_COLUMNS};
+use crate::analytics::cef::{CefAnalytics, CefDiscountStats, CEF_COLUMNS, CEF_DISCOUNT_WINDOW};
+use crate::analytics::factors::{beta_matrix, PrincipalComponents};
+use crate::analytics::clustering::CorrelationClusters;
 use crate::prelude::{Financials, StatementFrequency, TickerData, TickerPerformance, Tickers};
 
+/// Fund specific summary stats and the Yahoo Finance fields they are read from, in order of preference
+const FUND_FIELDS: [(&str, &[&str]); 5] = [
+    ("expense_ratio", &["netExpenseRatio", "annualReportExpenseRatio"]),
+    ("net_assets", &["netAssets", "totalAssets"]),
+    ("yield", &["yield", "trailingAnnualDividendYield"]),
+    ("category", &["category"]),
+    ("morningstar_rating", &["morningStarOverallRating"]),
+];
+
 macro_rules! fetch_all {
-    ($tickers:expr, $method:ident, $idx:expr $(, $param:expr)?) => {{
+    ($self:expr, $method:ident, $idx:expr $(, $param:expr)?) => {{
         let mut futures = Vec::new();
-        let tickers = $tickers.clone();
+        let tickers = $self.tickers.clone();
         let total_tickers = tickers.len();
         let pb = ProgressBar::new(total_tickers as u64);
         pb.set_style(
@@ -30,12 +49,12 @@ macro_rules! fetch_all {
                             Ok(df)
                         } else {
                             eprintln!("No Data for {}", &ticker.ticker);
-                            Err(format!("No Data for {}", &ticker.ticker))
+                            Err((ticker.ticker.clone(), format!("No Data for {}", &ticker.ticker)))
                         }
                     }
                     Err(e) => {
                         eprintln!("Error Fetching Data for {}: {}", &ticker.ticker, e);
-                        Err(format!("Error Fetching Data for {}: {}", &ticker.ticker, e))
+                        Err((ticker.ticker.clone(), format!("Error Fetching Data for {}: {}", &ticker.ticker, e)))
                     }
                 }
             });
@@ -46,6 +65,7 @@ macro_rules! fetch_all {
         let results = join_all(futures).await;
         let mut joint_df = DataFrame::default();
 
+        let mut failures = Vec::new();
         for result in results {
             match result {
                 Ok(Ok(df)) => {
@@ -54,10 +74,11 @@ macro_rules! fetch_all {
                         Err(e) => eprintln!("Unable to Vstack {:?}: {}", &df, e),
                     }
                 }
-                Ok(Err(_)) => continue,
+                Ok(Err(failure)) => failures.push(failure),
                 Err(e) => eprintln!("Error in task: {}", e),
             }
         }
+        $self.handle_failures(stringify!($method), failures)?;
 
         pb.finish_with_message(format!("Done"));
         Ok(joint_df)
@@ -75,38 +96,48 @@ pub trait TickersData {
     fn get_options(&self) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
     fn returns(&self) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
     fn performance_stats(&self) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn insider_scores(&self, windows: Option<Vec<i64>>) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn get_quote_fields(&self, fields: Vec<&str>) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn rank_universe(&self, metrics: Option<Vec<RankMetric>>) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn screen_options(&self, criteria: OptionScreenCriteria) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn fundamental_scores(&self) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn liquidity_metrics(&self, window: Option<usize>) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn cef_discounts(&self, window: usize) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn principal_components(&self, standardize: bool) -> impl std::future::Future<Output =  Result<PrincipalComponents, Box<dyn Error>>>;
+    fn beta_matrix(&self) -> impl std::future::Future<Output =  Result<DataFrame, Box<dyn Error>>>;
+    fn correlation_clusters(&self, n_clusters: usize) -> impl std::future::Future<Output =  Result<CorrelationClusters, Box<dyn Error>>>;
 }
 
 
 impl TickersData for Tickers {
     /// Fetch the OHLCV Data for all tickers in the Tickers Struct
     async fn get_chart(&self) -> Result<DataFrame, Box<dyn Error>> {
-        fetch_all!(self.tickers.clone(), get_chart, 1)
+        fetch_all!(self, get_chart, 1)
     }
 
     /// Fetch the Historical News Headlines for all tickers in the Tickers Struct
     async fn get_news(&self) -> Result<DataFrame, Box<dyn Error>> {
-        fetch_all!(self.tickers.clone(), get_news, 1)
+        fetch_all!(self, get_news, 1)
     }
 
     /// Fetch the Income Statement Data for all tickers in the Tickers Struct
     async fn income_statement(&self, frequency: StatementFrequency) -> Result<DataFrame, Box<dyn Error>> {
-        fetch_all!(self.tickers.clone(), income_statement, 1, frequency)
+        fetch_all!(self, income_statement, 1, frequency)
     }
 
     /// Fetch the Balance Sheet Data for all tickers in the Tickers Struct
     async fn balance_sheet(&self, frequency: StatementFrequency) -> Result<DataFrame, Box<dyn Error>> {
-        fetch_all!(self.tickers.clone(), balance_sheet, 1, frequency)
+        fetch_all!(self, balance_sheet, 1, frequency)
     }
 
     /// Fetch the Cashflow Statement Data for all tickers in the Tickers Struct
     async fn cashflow_statement(&self, frequency: StatementFrequency) -> Result<DataFrame, Box<dyn Error>> {
-        fetch_all!(self.tickers.clone(), cashflow_statement, 1, frequency)
+        fetch_all!(self, cashflow_statement, 1, frequency)
     }
 
     /// Fetch the Financial Ratios Data for all tickers in the Tickers Struct
     async fn financial_ratios(&self, frequency: StatementFrequency) -> Result<DataFrame, Box<dyn Error>> {
-        fetch_all!(self.tickers.clone(), financial_ratios, 1, frequency)
+        fetch_all!(self, financial_ratios, 1, frequency)
     }
 
     /// Fetch the Ticker Summary Stats Data for all tickers in the Tickers Struct
@@ -128,7 +159,7 @@ impl TickersData for Tickers {
                     }
                     Err(e) => {
                         eprintln!("Error Fetching Ticker Stats for {}: {}", &ticker.ticker, e);
-                        Err(format!("Error Fetching Ticker Stats for {}: {}", &ticker.ticker, e))
+                        Err((ticker.ticker.clone(), format!("Error Fetching Ticker Stats for {}: {}", &ticker.ticker, e)))
                     }
                 }
             });
@@ -139,15 +170,17 @@ impl TickersData for Tickers {
         let results = join_all(futures).await;
         let mut all_stats: Vec<TickerSummaryStats> = Vec::new();
 
+        let mut failures = Vec::new();
         for result in results {
             match result {
                 Ok(Ok(stats)) => {
                     all_stats.push(stats);
                 }
-                Ok(Err(_)) => continue,
+                Ok(Err(failure)) => failures.push(failure),
                 Err(e) => eprintln!("Error in task: {}", e),
             }
         }
+        self.handle_failures("get_ticker_stats", failures)?;
 
         let mut fields: Vec<Vec<AnyValue>> = vec![vec![]; 29]; // We have 28 fields excluding 'symbol'
 
@@ -213,8 +246,44 @@ impl TickersData for Tickers {
             Series::new("price_to_book", fields[26].clone()),
             Series::new("market_cap", fields[27].clone()),
             Series::new("shares_outstanding", fields[28].clone()),
+            Series::new("quote_type", all_stats.iter().map(|x| x.quote_type.clone()).collect::<Vec<String>>()),
         ])?;
 
+        // Funds have no earnings, so their fees, size, yield and rating are added instead
+        let is_fund = |x: &TickerSummaryStats| matches!(x.quote_type.as_str(), "ETF" | "MUTUALFUND");
+        let df = if all_stats.iter().any(is_fund) {
+            let fields = FUND_FIELDS.iter()
+                .flat_map(|(_, fields)| fields.iter().map(|x| x.to_string()))
+                .collect::<Vec<String>>();
+            let mut rows = Vec::new();
+            for stat in &all_stats {
+                let values = if is_fund(stat) {
+                    match fetch_quote_fields(&stat.symbol, &fields).await {
+                        Ok(values) => values,
+                        Err(e) => {
+                            eprintln!("Error Fetching Fund Stats for {}: {}", &stat.symbol, e);
+                            vec![Value::Null; fields.len()]
+                        }
+                    }
+                } else {
+                    vec![Value::Null; fields.len()]
+                };
+                let mut values = values.into_iter();
+                rows.push(FUND_FIELDS.iter()
+                    .map(|(_, fields)| {
+                        let candidates = values.by_ref().take(fields.len()).collect::<Vec<Value>>();
+                        candidates.into_iter().find(|x| !x.is_null()).unwrap_or(Value::Null)
+                    })
+                    .collect::<Vec<Value>>());
+            }
+            let symbols = all_stats.iter().map(|x| x.symbol.clone()).collect::<Vec<String>>();
+            let names = FUND_FIELDS.iter().map(|(name, _)| name.to_string()).collect::<Vec<String>>();
+            let fund_df = quote_fields_dataframe(&symbols, &rows, &names)?.drop("symbol")?;
+            df.hstack(fund_df.get_columns())?
+        } else {
+            df
+        };
+
         pb.finish_with_message("Done");
         Ok(df)
     }
@@ -241,12 +310,12 @@ impl TickersData for Tickers {
                             Ok(df)
                         } else {
                             eprintln!("No Options Data for {}", &ticker.ticker);
-                            Err(format!("No Options Data for {}", &ticker.ticker))
+                            Err((ticker.ticker.clone(), format!("No Options Data for {}", &ticker.ticker)))
                         }
                     }
                     Err(e) => {
                         eprintln!("Error Fetching Options Data for {}: {}", &ticker.ticker, e);
-                        Err(format!("Error Fetching Options Data for {}: {}", &ticker.ticker, e))
+                        Err((ticker.ticker.clone(), format!("Error Fetching Options Data for {}: {}", &ticker.ticker, e)))
                     }
                 }
             });
@@ -257,15 +326,17 @@ impl TickersData for Tickers {
         let results = join_all(futures).await;
         let mut joint_df = DataFrame::default();
 
+        let mut failures = Vec::new();
         for result in results {
             match result {
                 Ok(Ok(df)) => {
                     joint_df = joint_df.vstack(&df)?;
                 }
-                Ok(Err(_)) => continue,
+                Ok(Err(failure)) => failures.push(failure),
                 Err(e) => eprintln!("Error in task: {}", e),
             }
         }
+        self.handle_failures("get_options", failures)?;
 
         pb.finish_with_message("Done");
 
@@ -293,12 +364,12 @@ impl TickersData for Tickers {
                             Ok(df)
                         } else {
                             eprintln!("No Returns Data for {}", &ticker.ticker);
-                            Err(format!("No Returns Data for {}", &ticker.ticker))
+                            Err((ticker.ticker.clone(), format!("No Returns Data for {}", &ticker.ticker)))
                         }
                     }
                     Err(e) => {
                         eprintln!("No Returns Data for {}: {}", &ticker.ticker, e);
-                        Err(format!("No Returns Data for {}: {}", &ticker.ticker, e))
+                        Err((ticker.ticker.clone(), format!("No Returns Data for {}: {}", &ticker.ticker, e)))
                     }
                 }
             });
@@ -309,6 +380,7 @@ impl TickersData for Tickers {
         let results = join_all(futures).await;
         let mut joint_df = DataFrame::default();
 
+        let mut failures = Vec::new();
         for result in results {
             match result {
                 Ok(Ok(df)) => {
@@ -324,10 +396,11 @@ impl TickersData for Tickers {
                             )?;
                     }
                 }
-                Ok(Err(_)) => continue,
+                Ok(Err(failure)) => failures.push(failure),
                 Err(e) => eprintln!("Error in task: {}", e),
             }
         }
+        self.handle_failures("returns", failures)?;
 
         pb.finish_with_message("Done");
 
@@ -355,7 +428,7 @@ impl TickersData for Tickers {
                     }
                     Err(e) => {
                         eprintln!("No Returns Data for {}: {}", &ticker.ticker, e);
-                        Err(format!("No Returns Data for {}: {}", &ticker.ticker, e))
+                        Err((ticker.ticker.clone(), format!("No Returns Data for {}: {}", &ticker.ticker, e)))
                     }
                 }
             });
@@ -366,15 +439,17 @@ impl TickersData for Tickers {
         let results = join_all(futures).await;
         let mut all_stats: Vec<TickerPerformanceStats> = Vec::new();
 
+        let mut failures = Vec::new();
         for result in results {
             match result {
                 Ok(Ok(stats)) => {
                     all_stats.push(stats);
                 }
-                Ok(Err(_)) => continue,
+                Ok(Err(failure)) => failures.push(failure),
                 Err(e) => eprintln!("Error in task: {}", e),
             }
         }
+        self.handle_failures("performance_stats", failures)?;
 
         let mut fields: Vec<Vec<String>> = vec![vec![]; 17];
 
@@ -422,4 +497,463 @@ impl TickersData for Tickers {
 
         Ok(df)
     }
-}
\ No newline at end of file
+
+    /// Compute the net insider buying scores for all tickers in the Tickers Struct
+    ///
+    /// ### Arguments
+    /// - `windows` - Trailing windows in calendar days (default - [30, 90, 180, 365])
+    ///
+    /// ### Returns
+    /// - `DataFrame` with one row per symbol and one net buying score column per window
+    async fn insider_scores(&self, windows: Option<Vec<i64>>) -> Result<DataFrame, Box<dyn Error>> {
+        let windows = windows.unwrap_or(DEFAULT_INSIDER_WINDOWS.to_vec());
+        let mut futures = Vec::new();
+        let total_tickers = self.tickers.len();
+        let pb = ProgressBar::new(total_tickers as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")?
+                .progress_chars("#>-"),
+        );
+
+        for ticker in self.tickers.clone().into_iter() {
+            let windows = windows.clone();
+            let fut = tokio::task::spawn(async move {
+                match ticker.insider_scores(Some(windows)).await {
+                    Ok(scores) => {
+                        Ok((ticker.ticker.clone(), scores))
+                    }
+                    Err(e) => {
+                        eprintln!("No Insider Data for {}: {}", &ticker.ticker, e);
+                        Err((ticker.ticker.clone(), format!("No Insider Data for {}: {}", &ticker.ticker, e)))
+                    }
+                }
+            });
+
+            futures.push(fut);
+        }
+
+        let results = join_all(futures).await;
+        let mut all_scores: Vec<(String, Vec<InsiderScore>)> = Vec::new();
+
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(Ok(scores)) => {
+                    all_scores.push(scores);
+                }
+                Ok(Err(failure)) => failures.push(failure),
+                Err(e) => eprintln!("Error in task: {}", e),
+            }
+        }
+        self.handle_failures("insider_scores", failures)?;
+
+        let mut columns = vec![
+            Series::new("Symbol", all_scores.iter().map(|(symbol, _)| symbol.clone()).collect::<Vec<String>>())
+        ];
+        for (i, window) in windows.iter().enumerate() {
+            let scores = all_scores.iter().map(|(_, scores)| scores[i].net_buying_score).collect::<Vec<f64>>();
+            columns.push(Series::new(&format!("Net Buying Score {}D", window), scores));
+        }
+        let df = DataFrame::new(columns)?;
+
+        pb.finish_with_message("Done");
+
+        Ok(df)
+    }
+
+    /// Fetch a custom list of quote fields for all tickers in the Tickers Struct
+    ///
+    /// ### Arguments
+    /// - `fields` - Yahoo Finance field names in camelCase (e.g. ["trailingPE", "pegRatio", "beta", "shortRatio"])
+    ///
+    /// ### Returns
+    ///
+    /// - A `DataFrame` with a symbol column and one column per field
+    async fn get_quote_fields(&self, fields: Vec<&str>) -> Result<DataFrame, Box<dyn Error>> {
+        let fields = fields.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let mut futures = Vec::new();
+        let total_tickers = self.tickers.len();
+        let pb = ProgressBar::new(total_tickers as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")?
+                .progress_chars("#>-"),
+        );
+
+        for ticker in self.tickers.clone().into_iter() {
+            let fields = fields.clone();
+            let fut = tokio::task::spawn(async move {
+                match fetch_quote_fields(&ticker.ticker, &fields).await {
+                    Ok(values) => Ok((ticker.ticker.clone(), values)),
+                    Err(e) => {
+                        eprintln!("Error Fetching Quote Fields for {}: {}", &ticker.ticker, e);
+                        Err((ticker.ticker.clone(), format!("Error Fetching Quote Fields for {}: {}", &ticker.ticker, e)))
+                    }
+                }
+            });
+
+            futures.push(fut);
+        }
+
+        let results = join_all(futures).await;
+        let mut symbols = Vec::new();
+        let mut rows = Vec::new();
+
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(Ok((symbol, values))) => {
+                    symbols.push(symbol);
+                    rows.push(values);
+                }
+                Ok(Err(failure)) => failures.push(failure),
+                Err(e) => eprintln!("Error in task: {}", e),
+            }
+        }
+        self.handle_failures("get_quote_fields", failures)?;
+
+        pb.finish_with_message("Done");
+
+        quote_fields_dataframe(&symbols, &rows, &fields)
+    }
+
+    /// Ranks the tickers against each other on their summary stats
+    ///
+    /// ### Arguments
+    /// - `metrics` - Metrics to rank (default - RankMetric::defaults(): forward P/E, price to book,
+    ///   EPS growth, 52 week momentum and dividend yield). Any `get_ticker_stats` column can be used,
+    ///   plus eps_growth, the forward EPS growth over the trailing EPS in percent, the `LIQUIDITY_COLUMNS`
+    ///   and the closed-end fund `CEF_COLUMNS`
+    ///
+    /// ### Returns
+    ///
+    /// - A `DataFrame` with the symbol, the metric values, a `<metric>_pct` percentile rank (0 - 100) per metric,
+    ///   composite_score and composite_rank, sorted from the best to the worst composite rank
+    async fn rank_universe(&self, metrics: Option<Vec<RankMetric>>) -> Result<DataFrame, Box<dyn Error>> {
+        let metrics = metrics.unwrap_or(RankMetric::defaults());
+        let stats = self.get_ticker_stats().await?;
+        let stats = stats.lazy()
+            .with_column(
+                when(col("trailing_eps").abs().gt(lit(0.0)))
+                    .then((col("eps_forward") - col("trailing_eps")) / col("trailing_eps").abs() * lit(100.0))
+                    .otherwise(lit(NULL).cast(DataType::Float64))
+                    .alias("eps_growth")
+            )
+            .collect()?;
+        // Liquidity metrics are computed from the price history, so they are only fetched when ranked on
+        let stats = if metrics.iter().any(|x| LIQUIDITY_COLUMNS.contains(&x.column.as_str())) {
+            let liquidity = self.liquidity_metrics(None).await?;
+            stats.join(&liquidity, ["symbol"], ["symbol"], JoinArgs::new(JoinType::Left))?
+        } else {
+            stats
+        };
+        // Closed-end fund discounts need the NAV history, so they are only fetched when ranked on
+        let stats = if metrics.iter().any(|x| CEF_COLUMNS.contains(&x.column.as_str())) {
+            let discounts = self.cef_discounts(CEF_DISCOUNT_WINDOW).await?;
+            stats.join(&discounts, ["symbol"], ["symbol"], JoinArgs::new(JoinType::Left))?
+        } else {
+            stats
+        };
+        let mut columns = vec!["symbol".to_string()];
+        columns.extend(metrics.iter().map(|x| x.column.clone()).filter(|x| x != "symbol"));
+        columns.dedup();
+        let universe = stats.select(columns)?;
+        percentile_ranks(&universe, &metrics)
+    }
+
+    /// Screen the options chains of all tickers in the Tickers Struct for contracts matching the criteria
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - Screening criteria (IV rank, delta range, minimum open interest, spread width, days to expiration)
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - Matching contracts of all tickers with a symbol column, ranked by annualized yield
+    ///   (rank 1 is the highest yield)
+    async fn screen_options(&self, criteria: OptionScreenCriteria) -> Result<DataFrame, Box<dyn Error>> {
+        let mut futures = Vec::new();
+        let total_tickers = self.tickers.len();
+        let pb = ProgressBar::new(total_tickers as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")?
+                .progress_chars("#>-"),
+        );
+
+        for ticker in self.tickers.clone().into_iter() {
+            let criteria = criteria.clone();
+            let fut = tokio::task::spawn(async move {
+                match ticker.screen_options(&criteria).await {
+                    Ok(mut df) => {
+                        let symbol_series = Series::new("symbol", vec![ticker.ticker.clone(); df.height()]);
+                        let _ = df.insert_column(0, symbol_series);
+                        Ok(df)
+                    }
+                    Err(e) => {
+                        eprintln!("Error Screening Options for {}: {}", &ticker.ticker, e);
+                        Err((ticker.ticker.clone(), format!("Error Screening Options for {}: {}", &ticker.ticker, e)))
+                    }
+                }
+            });
+
+            futures.push(fut);
+        }
+
+        let results = join_all(futures).await;
+        let mut joint_df = DataFrame::default();
+
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(Ok(df)) => {
+                    joint_df = joint_df.vstack(&df)?;
+                    pb.inc(1);
+                }
+                Ok(Err(failure)) => failures.push(failure),
+                Err(e) => eprintln!("Error in task: {}", e),
+            }
+        }
+        self.handle_failures("screen_options", failures)?;
+
+        pb.finish_with_message("Done");
+
+        if joint_df.height() == 0 {
+            return Ok(joint_df);
+        }
+        let mut ranked = joint_df.sort(["annualized_yield"], SortMultipleOptions::new().with_order_descending(true))?;
+        let ranks = Series::new("rank", (1..=ranked.height() as u32).collect::<Vec<u32>>());
+        ranked.insert_column(0, ranks)?;
+        Ok(ranked)
+    }
+
+    /// Compute the Piotroski F-score, Altman Z-score and Beneish M-score for all tickers in the Tickers Struct
+    ///
+    /// ### Returns
+    ///
+    /// - A `DataFrame` with columns symbol, period, piotroski_f_score, altman_z_score and beneish_m_score,
+    ///   ready to screen or rank with `RankMetric` (e.g. `RankMetric::new("piotroski_f_score", true)`)
+    async fn fundamental_scores(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let mut futures = Vec::new();
+        let total_tickers = self.tickers.len();
+        let pb = ProgressBar::new(total_tickers as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")?
+                .progress_chars("#>-"),
+        );
+
+        for ticker in self.tickers.clone().into_iter() {
+            let fut = tokio::task::spawn(async move {
+                match ticker.fundamental_scores().await {
+                    Ok(scores) => Ok((ticker.ticker.clone(), scores)),
+                    Err(e) => {
+                        eprintln!("Error Computing Fundamental Scores for {}: {}", &ticker.ticker, e);
+                        Err((ticker.ticker.clone(), format!("Error Computing Fundamental Scores for {}: {}", &ticker.ticker, e)))
+                    }
+                }
+            });
+
+            futures.push(fut);
+        }
+
+        let results = join_all(futures).await;
+        let mut all_scores: Vec<(String, FundamentalScores)> = Vec::new();
+
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(Ok(scores)) => {
+                    all_scores.push(scores);
+                    pb.inc(1);
+                }
+                Ok(Err(failure)) => failures.push(failure),
+                Err(e) => eprintln!("Error in task: {}", e),
+            }
+        }
+        self.handle_failures("fundamental_scores", failures)?;
+
+        let df = df!(
+            "symbol" => all_scores.iter().map(|(symbol, _)| symbol.as_str()).collect::<Vec<&str>>(),
+            "period" => all_scores.iter().map(|(_, x)| x.period.as_str()).collect::<Vec<&str>>(),
+            "piotroski_f_score" => all_scores.iter().map(|(_, x)| x.piotroski_f_score).collect::<Vec<Option<f64>>>(),
+            "altman_z_score" => all_scores.iter().map(|(_, x)| x.altman_z_score).collect::<Vec<Option<f64>>>(),
+            "beneish_m_score" => all_scores.iter().map(|(_, x)| x.beneish_m_score).collect::<Vec<Option<f64>>>()
+        )?;
+
+        pb.finish_with_message("Done");
+
+        Ok(df)
+    }
+
+    /// Compute the liquidity metrics for all tickers in the Tickers Struct
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of most recent bars to use (default - all bars)
+    ///
+    /// ### Returns
+    ///
+    /// - A `DataFrame` with a symbol column and the `LIQUIDITY_COLUMNS`, ready to screen or rank with `RankMetric`
+    ///   (e.g. `RankMetric::new("amihud_illiquidity", false)`)
+    async fn liquidity_metrics(&self, window: Option<usize>) -> Result<DataFrame, Box<dyn Error>> {
+        let mut futures = Vec::new();
+        let total_tickers = self.tickers.len();
+        let pb = ProgressBar::new(total_tickers as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")?
+                .progress_chars("#>-"),
+        );
+
+        for ticker in self.tickers.clone().into_iter() {
+            let fut = tokio::task::spawn(async move {
+                match ticker.liquidity_metrics(window).await {
+                    Ok(metrics) => Ok((ticker.ticker.clone(), metrics)),
+                    Err(e) => {
+                        eprintln!("Error Computing Liquidity Metrics for {}: {}", &ticker.ticker, e);
+                        Err((ticker.ticker.clone(), format!("Error Computing Liquidity Metrics for {}: {}", &ticker.ticker, e)))
+                    }
+                }
+            });
+
+            futures.push(fut);
+        }
+
+        let results = join_all(futures).await;
+        let mut all_metrics: Vec<(String, LiquidityMetrics)> = Vec::new();
+
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(Ok(metrics)) => {
+                    all_metrics.push(metrics);
+                    pb.inc(1);
+                }
+                Ok(Err(failure)) => failures.push(failure),
+                Err(e) => eprintln!("Error in task: {}", e),
+            }
+        }
+        self.handle_failures("liquidity_metrics", failures)?;
+
+        let mut columns = vec![
+            Series::new("symbol", all_metrics.iter().map(|(symbol, _)| symbol.as_str()).collect::<Vec<&str>>()),
+        ];
+        for (i, name) in LIQUIDITY_COLUMNS.iter().enumerate() {
+            columns.push(Series::new(name, all_metrics.iter().map(|(_, x)| x.values()[i]).collect::<Vec<f64>>()));
+        }
+        let df = DataFrame::new(columns)?;
+
+        pb.finish_with_message("Done");
+
+        Ok(df)
+    }
+
+    /// Compute the premium/discount to NAV of all closed-end funds in the Tickers Struct
+    ///
+    /// The NAV of each fund is fetched under its Yahoo Finance NAV symbol (e.g. XPDIX for PDI),
+    /// tickers without a NAV are reported as failures
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of trailing periods of the discount statistics (e.g. 252)
+    ///
+    /// ### Returns
+    ///
+    /// - A `DataFrame` with a symbol column and the `CEF_COLUMNS`, ready to screen or rank with `RankMetric`
+    ///   (e.g. `RankMetric::new("discount_z_score", false)` ranks the most unusually wide discounts first)
+    async fn cef_discounts(&self, window: usize) -> Result<DataFrame, Box<dyn Error>> {
+        let mut futures = Vec::new();
+        let total_tickers = self.tickers.len();
+        let pb = ProgressBar::new(total_tickers as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")?
+                .progress_chars("#>-"),
+        );
+
+        for ticker in self.tickers.clone().into_iter() {
+            let fut = tokio::task::spawn(async move {
+                match ticker.discount_stats(None, window).await {
+                    Ok(stats) => Ok((ticker.ticker.clone(), stats)),
+                    Err(e) => {
+                        eprintln!("Error Computing CEF Discount for {}: {}", &ticker.ticker, e);
+                        Err((ticker.ticker.clone(), format!("Error Computing CEF Discount for {}: {}", &ticker.ticker, e)))
+                    }
+                }
+            });
+
+            futures.push(fut);
+        }
+
+        let results = join_all(futures).await;
+        let mut all_stats: Vec<(String, CefDiscountStats)> = Vec::new();
+
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(Ok(stats)) => {
+                    all_stats.push(stats);
+                    pb.inc(1);
+                }
+                Ok(Err(failure)) => failures.push(failure),
+                Err(e) => eprintln!("Error in task: {}", e),
+            }
+        }
+        self.handle_failures("cef_discounts", failures)?;
+
+        let mut columns = vec![
+            Series::new("symbol", all_stats.iter().map(|(symbol, _)| symbol.as_str()).collect::<Vec<&str>>()),
+        ];
+        for (i, name) in CEF_COLUMNS.iter().enumerate() {
+            columns.push(Series::new(name, all_stats.iter().map(|(_, x)| x.values()[i]).collect::<Vec<f64>>()));
+        }
+        let df = DataFrame::new(columns)?;
+
+        pb.finish_with_message("Done");
+
+        Ok(df)
+    }
+
+    /// Extract the principal components of the returns of all tickers in the Tickers Struct
+    ///
+    /// # Arguments
+    ///
+    /// * `standardize` - Use the correlation matrix instead of the covariance matrix
+    ///
+    /// ### Returns
+    ///
+    /// - A `PrincipalComponents` struct, use `explained_variance()` and `loadings()` for the DataFrames
+    ///   and `absorption_ratio(n)` to gauge the concentration of the universe
+    async fn principal_components(&self, standardize: bool) -> Result<PrincipalComponents, Box<dyn Error>> {
+        let returns = self.returns().await?;
+        PrincipalComponents::compute(&returns, standardize)
+    }
+
+    /// Compute the pairwise beta matrix of all tickers in the Tickers Struct
+    ///
+    /// ### Returns
+    ///
+    /// - A `DataFrame` with a symbol column and one column per ticker,
+    ///   the value in row i and column j is the beta of ticker i on ticker j
+    async fn beta_matrix(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let returns = self.returns().await?;
+        beta_matrix(&returns)
+    }
+
+    /// Cluster all tickers in the Tickers Struct by return correlation
+    ///
+    /// # Arguments
+    ///
+    /// * `n_clusters` - Number of clusters
+    ///
+    /// ### Returns
+    ///
+    /// - A `CorrelationClusters` struct with the cluster of each ticker and the medoid of each cluster,
+    ///   use `to_dataframe()` for the assignments table
+    async fn correlation_clusters(&self, n_clusters: usize) -> Result<CorrelationClusters, Box<dyn Error>> {
+        let returns = self.returns().await?;
+        CorrelationClusters::compute(&returns, n_clusters)
+    }
+}
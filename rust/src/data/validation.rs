@@ -0,0 +1,214 @@
+use std::error::Error;
+use std::fmt;
+use polars::prelude::*;
+use chrono::{DateTime, Datelike, NaiveDateTime};
+use crate::data::config::{Interval, MarketCalendar};
+
+/// Common stock split factors used to recognise unadjusted split jumps
+const SPLIT_FACTORS: [f64; 10] = [2.0, 3.0, 4.0, 5.0, 8.0, 10.0, 15.0, 20.0, 25.0, 50.0];
+
+/// Tolerance (relative) when matching a price jump to a split factor
+const SPLIT_TOLERANCE: f64 = 0.05;
+
+
+/// Summary of the issues found (and repaired) by the OHLCV validation pass
+#[derive(Debug, Clone, Default)]
+pub struct DataQualityReport {
+    pub symbol: String,
+    pub rows_checked: usize,
+    pub duplicate_timestamps: usize,
+    pub invalid_prices: usize,
+    pub inconsistent_ranges: usize,
+    pub split_adjustments: Vec<(NaiveDateTime, f64)>,
+    pub gaps: Vec<(NaiveDateTime, NaiveDateTime)>,
+}
+
+impl DataQualityReport {
+    /// Returns true if no issues were found
+    pub fn is_clean(&self) -> bool {
+        self.duplicate_timestamps == 0
+            && self.invalid_prices == 0
+            && self.inconsistent_ranges == 0
+            && self.split_adjustments.is_empty()
+            && self.gaps.is_empty()
+    }
+}
+
+impl fmt::Display for DataQualityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Data quality report for {} ({} rows checked)", self.symbol, self.rows_checked)?;
+        if self.is_clean() {
+            return writeln!(f, "  No issues found");
+        }
+        if self.duplicate_timestamps > 0 {
+            writeln!(f, "  Removed {} duplicated timestamps", self.duplicate_timestamps)?;
+        }
+        if self.invalid_prices > 0 {
+            writeln!(f, "  Repaired {} zero/negative prices with the previous close", self.invalid_prices)?;
+        }
+        if self.inconsistent_ranges > 0 {
+            writeln!(f, "  Corrected {} bars with high/low outside the open/close range", self.inconsistent_ranges)?;
+        }
+        for (date, factor) in &self.split_adjustments {
+            writeln!(f, "  Adjusted prices before {} for an unadjusted split of {:.2}", date, factor)?;
+        }
+        for (start, end) in &self.gaps {
+            writeln!(f, "  Gap in data between {} and {} (not filled)", start, end)?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Validates and repairs an OHLCV DataFrame
+///
+/// The following issues are detected and repaired:
+/// - duplicated timestamps (the last observation is kept)
+/// - zero/negative prices (replaced with the previous close, leading invalid rows are dropped)
+/// - bars whose high/low do not contain the open and close
+/// - unadjusted split jumps (prices before the split are divided by the split factor and volumes multiplied)
+///
+/// Gaps larger than expected for the interval and market calendar are reported but not filled.
+///
+/// # Arguments
+///
+/// * `symbol` - Ticker symbol, used in the report
+/// * `df` - OHLCV DataFrame with timestamp, open, high, low, close, volume and adjclose columns
+/// * `interval` - Interval of the data
+/// * `calendar` - Market calendar of the security
+///
+/// # Returns
+///
+/// * `(DataFrame, DataQualityReport)` - Repaired DataFrame and the report of what was fixed
+pub fn validate_ohlcv(
+    symbol: &str,
+    df: &DataFrame,
+    interval: Interval,
+    calendar: MarketCalendar,
+) -> Result<(DataFrame, DataQualityReport), Box<dyn Error>> {
+    let df = df.sort(["timestamp"], SortMultipleOptions::new().with_order_descending(false))?;
+    let timestamps = df.column("timestamp")?.datetime()?.to_vec().iter()
+        .map(|x| DateTime::from_timestamp_millis(x.unwrap()).unwrap().naive_utc())
+        .collect::<Vec<NaiveDateTime>>();
+    let column = |name: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+        Ok(df.column(name)?.f64()?.to_vec().iter().map(|x| x.unwrap_or(0.0)).collect())
+    };
+    let (open, high, low, close, volume, adjclose) =
+        (column("open")?, column("high")?, column("low")?, column("close")?, column("volume")?, column("adjclose")?);
+
+    let mut report = DataQualityReport {
+        symbol: symbol.to_string(),
+        rows_checked: timestamps.len(),
+        ..Default::default()
+    };
+
+    // Duplicated timestamps: keep the last observation
+    let mut rows: Vec<(NaiveDateTime, [f64; 6])> = Vec::new();
+    for i in 0..timestamps.len() {
+        let bar = [open[i], high[i], low[i], close[i], volume[i], adjclose[i]];
+        match rows.last_mut() {
+            Some(last) if last.0 == timestamps[i] => {
+                *last = (timestamps[i], bar);
+                report.duplicate_timestamps += 1;
+            }
+            _ => rows.push((timestamps[i], bar)),
+        }
+    }
+
+    // Zero/negative prices: replace with the previous close
+    let mut repaired: Vec<(NaiveDateTime, [f64; 6])> = Vec::new();
+    for (date, mut bar) in rows {
+        let invalid = bar[..4].iter().chain(std::iter::once(&bar[5])).filter(|x| **x <= 0.0).count();
+        if invalid > 0 {
+            report.invalid_prices += invalid;
+            match repaired.last() {
+                Some((_, prev)) => {
+                    for i in [0, 1, 2, 3] {
+                        if bar[i] <= 0.0 { bar[i] = prev[3]; }
+                    }
+                    if bar[5] <= 0.0 { bar[5] = prev[5] * bar[3] / prev[3]; }
+                }
+                None => continue,
+            }
+        }
+        repaired.push((date, bar));
+    }
+
+    // High/low must contain the open and close
+    for (_, bar) in repaired.iter_mut() {
+        let max = bar[0].max(bar[3]);
+        let min = bar[0].min(bar[3]);
+        if bar[1] < max || bar[2] > min {
+            bar[1] = bar[1].max(max);
+            bar[2] = bar[2].min(min);
+            report.inconsistent_ranges += 1;
+        }
+    }
+
+    // Unadjusted split jumps: a close-to-close move matching a split factor in both close and adjclose
+    for i in 1..repaired.len() {
+        let (prev, curr) = (repaired[i - 1].1, repaired[i].1);
+        let close_ratio = prev[3] / curr[3];
+        let adj_ratio = prev[5] / curr[5];
+        let factor = SPLIT_FACTORS.iter()
+            .flat_map(|f| [*f, 1.0 / *f])
+            .find(|f| (close_ratio / f - 1.0).abs() < SPLIT_TOLERANCE && (adj_ratio / f - 1.0).abs() < SPLIT_TOLERANCE);
+        if let Some(factor) = factor {
+            for (_, bar) in repaired[..i].iter_mut() {
+                for j in [0, 1, 2, 3, 5] {
+                    bar[j] /= factor;
+                }
+                bar[4] *= factor;
+            }
+            report.split_adjustments.push((repaired[i].0, factor));
+        }
+    }
+
+    // Gaps: report intervals missing more bars than the calendar allows
+    let threshold = gap_threshold_minutes(interval, calendar);
+    for pair in repaired.windows(2) {
+        let (start, end) = (pair[0].0, pair[1].0);
+        let intraday = interval.is_intraday();
+        // Overnight sessions are expected gaps for equity intraday data
+        if intraday && !calendar.trades_overnight() && start.date() != end.date() {
+            continue;
+        }
+        // Forex closes over the weekend only
+        if intraday && matches!(calendar, MarketCalendar::Forex) && (end - start).num_days() <= 3
+            && start.weekday().num_days_from_monday() >= 4 && end.weekday().num_days_from_monday() == 6 {
+            continue;
+        }
+        if (end - start).num_minutes() as f64 > threshold {
+            report.gaps.push((start, end));
+        }
+    }
+
+    let df = df!(
+        "timestamp" => repaired.iter().map(|x| x.0).collect::<Vec<NaiveDateTime>>(),
+        "open" => repaired.iter().map(|x| x.1[0]).collect::<Vec<f64>>(),
+        "high" => repaired.iter().map(|x| x.1[1]).collect::<Vec<f64>>(),
+        "low" => repaired.iter().map(|x| x.1[2]).collect::<Vec<f64>>(),
+        "close" => repaired.iter().map(|x| x.1[3]).collect::<Vec<f64>>(),
+        "volume" => repaired.iter().map(|x| x.1[4]).collect::<Vec<f64>>(),
+        "adjclose" => repaired.iter().map(|x| x.1[5]).collect::<Vec<f64>>()
+    )?;
+    Ok((df, report))
+}
+
+/// Largest expected distance (in minutes) between consecutive bars
+fn gap_threshold_minutes(interval: Interval, calendar: MarketCalendar) -> f64 {
+    let day = 24.0 * 60.0;
+    match interval {
+        Interval::TwoMinutes => 2.0 * 1.5,
+        Interval::FiveMinutes => 5.0 * 1.5,
+        Interval::FifteenMinutes => 15.0 * 1.5,
+        Interval::ThirtyMinutes => 30.0 * 1.5,
+        Interval::SixtyMinutes | Interval::OneHour => 60.0 * 1.5,
+        Interval::NinetyMinutes => 90.0 * 1.5,
+        // Allow for long weekends on equity markets
+        Interval::OneDay => if calendar.includes_weekends() { day } else { 4.0 * day },
+        Interval::FiveDays | Interval::OneWeek => 7.0 * 1.5 * day,
+        Interval::OneMonth => 31.0 * 1.5 * day,
+        Interval::ThreeMonths => 92.0 * 1.5 * day,
+    }
+}
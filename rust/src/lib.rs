@@ -19,6 +19,14 @@
 //! cargo install finalytics
 //! ```
 //!
+//! ## Features
+//! - `fast_math` - Parallel bootstrap and parameter search computations with rayon
+//! - `kaleido` - Static image export of the charts
+//! - `onnx` - Inference with ONNX models on feature DataFrames
+//! - `tracing` - `tracing` spans for data requests (url, cache hits, retries), optimization and reports,
+//!   install a subscriber (e.g. `tracing_subscriber::fmt().with_span_events(FmtSpan::CLOSE)`) to log their durations
+//! - `parquet` - Parquet export of the model DataFrames with `DataExport::dump` and loading of KLINE data with `KLINE::from_parquet`
+//!
 //! ## Models
 //! These are the main Interfaces for accessing the `finalytics` library methods
 //! ##### [Ticker](https://docs.rs/finalytics/latest/finalytics/models/ticker/struct.Ticker.html) - Retrieve and analyze ticker data
@@ -80,16 +88,72 @@ pub mod prelude {
     // Structs
     pub use crate::models::ticker::Ticker;
     pub use crate::models::tickers::Tickers;
+    pub use crate::models::tickers::{ReportProgress, SymbolFailure, SymbolReport};
     pub use crate::models::portfolio::Portfolio;
-    pub use crate::reports::table::DataTable;
+    pub use crate::models::watchlist::{Watchlist, WatchlistStore};
+    pub use crate::reports::table::{DataTable, DataTableFormat};
+    pub use crate::reports::i18n::ReportTranslations;
+    pub use crate::charts::ChartOptions;
+    pub use crate::analytics::optimization::{CategoryConstraint, OptimizationDiagnostics, OptimizationSettings, ParetoFrontier, PositionConstraint};
+    pub use crate::analytics::fixed_income::Bond;
+    pub use crate::analytics::futures::ContinuousFutures;
+    pub use crate::analytics::statistics::{BootstrapBands, PerformanceStats, SignificanceStats};
+    pub use crate::analytics::performance::{AssetWeight, CurrencyHedgedStats, PortfolioConfig, PortfolioPerformanceSummary, TickerPerformanceSummary};
+    pub use crate::data::validation::DataQualityReport;
+    pub use crate::data::kline::{KLINE, KlineConfig};
+    pub use crate::data::news::NewsOptions;
+    pub use crate::data::schema::{NewsRow, OhlcvRow, ReturnRow};
+    pub use crate::analytics::fx::CurrencyPair;
+    pub use crate::analytics::cashflows::{CashFlow, CashFlowSchedule};
+    pub use crate::analytics::projection::{GoalPlan, GoalProjection};
+    pub use crate::analytics::ranking::RankMetric;
+    pub use crate::analytics::options::OptionScreenCriteria;
+    pub use crate::models::positions::{Position, Positions};
+    pub use crate::analytics::fundamentals::FundamentalScores;
+    pub use crate::analytics::liquidity::{LiquidityConstraint, LiquidityMetrics, LIQUIDITY_COLUMNS};
+    pub use crate::analytics::cef::{CefDiscountStats, CEF_COLUMNS};
+    pub use crate::analytics::factors::PrincipalComponents;
+    pub use crate::analytics::clustering::CorrelationClusters;
+    pub use crate::analytics::sizing::VolatilityTarget;
+    pub use crate::analytics::esg::EsgScreen;
+    pub use crate::analytics::event_study::{Event, EventStudy, EventWindow};
+    pub use crate::analytics::features::FeatureSpec;
+    pub use crate::analytics::forecast::Forecast;
+    pub use crate::analytics::strategy::{StrategyComparison, StrategyVariant, TrendFilter};
+    pub use crate::analytics::search::ParameterSearch;
+    #[cfg(feature = "onnx")]
+    pub use crate::analytics::onnx::OnnxModel;
 
 
     // Enums
     pub use crate::data::config::Interval;
+    pub use crate::data::config::MarketCalendar;
+    pub use crate::data::config::{PriceType, ReturnType};
+    pub use crate::data::config::QuoteType;
+    pub use crate::utils::fixtures::DataMode;
+    pub use crate::data::export::ExportFormat;
     pub use crate::data::config::StatementType;
     pub use crate::data::config::StatementFrequency;
+    pub use crate::data::config::StatementView;
+    pub use crate::data::config::SegmentType;
     pub use crate::analytics::technicals::Column;
+    pub use crate::analytics::stochastics::OptionType;
+    pub use crate::analytics::options::IncomeStrategy;
+    pub use crate::models::positions::PositionKind;
     pub use crate::analytics::optimization::ObjectiveFunction;
+    pub use crate::analytics::optimization::{CategorySource, UncertaintySet};
+    pub use crate::analytics::statistics::{CovarianceEstimator, ExpectedReturnModel};
+    pub use crate::analytics::futures::{RollAdjustment, RollRule};
+    pub use crate::analytics::crypto::CryptoDataSource;
+    pub use crate::analytics::fx::FxSession;
+    pub use crate::analytics::features::FeatureIndicator;
+    pub use crate::analytics::forecast::ForecastModel;
+    pub use crate::analytics::strategy::{StrategyMetric, TrendFilterTarget};
+    pub use crate::analytics::esg::EsgAction;
+    pub use crate::analytics::search::SearchMethod;
+    pub use crate::models::tickers::FailurePolicy;
+    pub use crate::charts::tickers::RiskNormalization;
+    pub use crate::data::market::MarketMovers;
     pub use crate::reports::table::TableType;
     pub use crate::reports::report::ReportType;
 
@@ -102,17 +166,32 @@ pub mod prelude {
 
     // Traits
     pub use crate::data::ticker::TickerData;
+    pub use crate::data::resample::Resample;
+    pub use crate::data::schema::DataFrameRows;
     pub use crate::data::tickers::TickersData;
+    pub use crate::data::export::DataExport;
     pub use crate::charts::ticker::TickerCharts;
     pub use crate::charts::tickers::TickersCharts;
     pub use crate::charts::portfolio::PortfolioCharts;
+    pub use crate::charts::strategy::StrategyCharts;
     pub use crate::analytics::fundamentals::Financials;
     pub use crate::analytics::performance::TickerPerformance;
     pub use crate::analytics::stochastics::VolatilitySurface;
+    pub use crate::analytics::options::OptionsAnalytics;
     pub use crate::analytics::technicals::TechnicalIndicators;
+    pub use crate::analytics::insiders::InsiderActivity;
+    pub use crate::analytics::execution::ExecutionAnalytics;
+    pub use crate::analytics::crypto::CryptoMetrics;
+    pub use crate::analytics::features::FeatureEngineering;
+    pub use crate::analytics::forecast::Forecasting;
+    pub use crate::analytics::fx::FxAnalytics;
+    pub use crate::analytics::sessions::SessionAnalytics;
+    pub use crate::analytics::liquidity::LiquidityAnalytics;
+    pub use crate::analytics::cef::CefAnalytics;
     pub use crate::reports::report::Report;
 
     // Utils
+    pub use crate::utils::web_utils::{check_connectivity, ConnectivityReport};
     #[cfg(feature = "kaleido")]
     pub use crate::utils::chart_utils::PlotImage;
 
@@ -1,3 +1,5 @@
 pub mod ticker;
 pub mod portfolio;
 pub mod tickers;
+pub mod watchlist;
+pub mod positions;
@@ -1,9 +1,20 @@
 use std::error::Error;
-use crate::data::config::Interval;
-use crate::analytics::optimization::ObjectiveFunction;
-use crate::analytics::performance::PortfolioPerformanceStats;
+use crate::data::config::{Interval, MarketCalendar, PriceType, ReturnType};
+use crate::analytics::esg::{EsgAction, EsgScreen, fetch_esg_scores};
+use crate::analytics::optimization::{CategoryConstraint, ObjectiveFunction, PositionConstraint, TurnoverConstraint, UncertaintySet};
+use crate::analytics::performance::{PortfolioConfig, PortfolioPerformanceStats};
+use crate::analytics::statistics::{CovarianceEstimator, ExpectedReturnModel};
+use crate::data::kline::{kline_for, with_benchmark, KLINE};
+use crate::data::ticker::TickerData;
+use crate::analytics::liquidity::{LiquidityConstraint, LiquidityMetrics};
+use crate::analytics::sizing::VolatilityTarget;
+use crate::analytics::strategy::{TrendFilter, TrendFilterTarget};
+use crate::models::ticker::TickerBuilder;
 
 
+/// Lower and upper weight bounds of each asset
+type AssetBounds = Vec<(f64, f64)>;
+
 pub struct PortfolioBuilder {
     pub ticker_symbols: Vec<String>,
     pub benchmark_symbol: String,
@@ -14,6 +25,26 @@ pub struct PortfolioBuilder {
     pub risk_free_rate: f64,
     pub objective_function: ObjectiveFunction,
     pub constraints: Option<Vec<(f64, f64)>>,
+    pub pinned_weights: Vec<(String, f64)>,
+    pub category_constraints: Vec<CategoryConstraint>,
+    pub turnover_constraint: Option<TurnoverConstraint>,
+    pub liquidity_constraint: Option<LiquidityConstraint>,
+    pub covariance_estimator: CovarianceEstimator,
+    pub expected_return_model: ExpectedReturnModel,
+    pub calendar: MarketCalendar,
+    pub return_type: ReturnType,
+    pub price_type: PriceType,
+    pub seed: Option<u64>,
+    pub ticker_data: Option<Vec<KLINE>>,
+    pub benchmark_data: Option<KLINE>,
+    pub uncertainty_set: Option<UncertaintySet>,
+    pub max_drawdown: Option<f64>,
+    pub tracking_error_limit: Option<f64>,
+    pub replication_holdings: Option<usize>,
+    pub position_constraint: Option<PositionConstraint>,
+    pub volatility_target: Option<VolatilityTarget>,
+    pub trend_filter: Option<TrendFilter>,
+    pub esg_screen: Option<EsgScreen>,
 }
 
 
@@ -29,6 +60,26 @@ impl PortfolioBuilder {
             risk_free_rate: 0.02,
             objective_function: ObjectiveFunction::MaxSharpe,
             constraints: None,
+            pinned_weights: Vec::new(),
+            category_constraints: Vec::new(),
+            turnover_constraint: None,
+            liquidity_constraint: None,
+            covariance_estimator: CovarianceEstimator::Sample,
+            expected_return_model: ExpectedReturnModel::HistoricalMean,
+            calendar: MarketCalendar::Equity,
+            return_type: ReturnType::Simple,
+            price_type: PriceType::AdjClose,
+            seed: None,
+            ticker_data: None,
+            benchmark_data: None,
+            uncertainty_set: None,
+            max_drawdown: None,
+            tracking_error_limit: None,
+            replication_holdings: None,
+            position_constraint: None,
+            volatility_target: None,
+            trend_filter: None,
+            esg_screen: None,
         }
     }
 
@@ -77,14 +128,272 @@ impl PortfolioBuilder {
         self
     }
 
+    /// Pins an asset to a fixed weight (e.g. keep 10% in BTC-USD) while the remaining assets are optimized,
+    /// overriding any bounds given for the asset in `constraints`
+    pub fn pin_weight(&mut self, symbol: &str, weight: f64) -> &mut PortfolioBuilder {
+        self.pinned_weights.retain(|(s, _)| s != symbol);
+        self.pinned_weights.push((symbol.to_string(), weight));
+        self
+    }
+
+    pub fn category_constraints(&mut self, category_constraints: Vec<CategoryConstraint>) -> &mut PortfolioBuilder {
+        self.category_constraints = category_constraints;
+        self
+    }
+
+    /// Limits the sum of absolute weight changes from the current weights (given in ticker symbol order),
+    /// e.g. a max_turnover of 0.2 allows 10% of the portfolio to be reallocated
+    pub fn turnover_constraint(&mut self, current_weights: Vec<f64>, max_turnover: f64) -> &mut PortfolioBuilder {
+        self.turnover_constraint = Some(TurnoverConstraint {
+            current_weights,
+            max_turnover,
+        });
+        self
+    }
+
+    /// Caps the weight of each asset at a fraction of its average dollar volume (ADV) relative to the
+    /// portfolio value, e.g. `liquidity_constraint(10_000_000.0, 0.1, Some(20))` keeps every position
+    /// within 10% of its 20 bar ADV for a 10 million portfolio
+    pub fn liquidity_constraint(&mut self, portfolio_value: f64, max_adv_fraction: f64, window: Option<usize>) -> &mut PortfolioBuilder {
+        self.liquidity_constraint = Some(LiquidityConstraint {
+            portfolio_value,
+            max_adv_fraction,
+            window,
+        });
+        self
+    }
+
+    pub fn covariance_estimator(&mut self, covariance_estimator: CovarianceEstimator) -> &mut PortfolioBuilder {
+        self.covariance_estimator = covariance_estimator;
+        self
+    }
+
+    pub fn expected_return_model(&mut self, expected_return_model: ExpectedReturnModel) -> &mut PortfolioBuilder {
+        self.expected_return_model = expected_return_model;
+        self
+    }
+
+    pub fn calendar(&mut self, calendar: MarketCalendar) -> &mut PortfolioBuilder {
+        self.calendar = calendar;
+        self
+    }
+
+    pub fn return_type(&mut self, return_type: ReturnType) -> &mut PortfolioBuilder {
+        self.return_type = return_type;
+        self
+    }
+
+    pub fn price_type(&mut self, price_type: PriceType) -> &mut PortfolioBuilder {
+        self.price_type = price_type;
+        self
+    }
+
+    pub fn seed(&mut self, seed: u64) -> &mut PortfolioBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Uses user supplied OHLCV data (e.g. from `KLINE::from_directory`) for the tickers and,
+    /// if included, the benchmark instead of Yahoo Finance
+    pub fn ticker_data(&mut self, ticker_data: Option<Vec<KLINE>>) -> &mut PortfolioBuilder {
+        self.ticker_data = ticker_data;
+        self
+    }
+
+    /// Uses a user supplied benchmark series (e.g. a proprietary index from `KLINE::from_csv` or `KLINE::from_returns_csv`)
+    /// instead of a Yahoo Finance symbol, the benchmark symbol becomes the symbol of the KLINE
+    pub fn benchmark_data(&mut self, benchmark_data: Option<KLINE>) -> &mut PortfolioBuilder {
+        self.benchmark_data = benchmark_data;
+        self
+    }
+
+    /// Optimizes against the worst case expected returns within an uncertainty set (robust mean-variance),
+    /// making MaxSharpe and MaxReturn allocations less sensitive to estimation error
+    pub fn uncertainty_set(&mut self, uncertainty_set: UncertaintySet) -> &mut PortfolioBuilder {
+        self.uncertainty_set = Some(uncertainty_set);
+        self
+    }
+
+    /// Limits the in-sample maximum drawdown of the optimal portfolio (e.g. 0.2 for 20%), enforced as a linear
+    /// constraint by the MinCVaR program (checked against the compounded drawdown after solving) and as a penalty
+    /// by the other objectives
+    pub fn max_drawdown(&mut self, max_drawdown: f64) -> &mut PortfolioBuilder {
+        self.max_drawdown = Some(max_drawdown);
+        self
+    }
+
+    /// Limits the annualized tracking error of the optimal portfolio against the benchmark (e.g. 0.03 for 3%),
+    /// typically combined with `ObjectiveFunction::MaxExcessReturn` for a benchmark-aware portfolio
+    pub fn tracking_error_limit(&mut self, tracking_error_limit: f64) -> &mut PortfolioBuilder {
+        self.tracking_error_limit = Some(tracking_error_limit);
+        self
+    }
+
+    /// Replaces the objective function with a replication of the benchmark by at most `max_holdings` of the assets,
+    /// selected greedily to minimize the tracking error (e.g. a 20 stock tracker of an index or screener universe)
+    pub fn replicate_benchmark(&mut self, max_holdings: usize) -> &mut PortfolioBuilder {
+        self.replication_holdings = Some(max_holdings);
+        self
+    }
+
+    /// Limits the number of positions (e.g. Some(10)) and sets a minimum weight of any asset held
+    /// (e.g. Some(0.02) for 2%), assets are otherwise left out of the portfolio
+    pub fn position_constraint(&mut self, max_positions: Option<usize>, min_weight: Option<f64>) -> &mut PortfolioBuilder {
+        self.position_constraint = Some(PositionConstraint::new(max_positions, min_weight));
+        self
+    }
+
+    /// Adds a volatility targeting overlay scaling the exposure of the optimal portfolio to a target
+    /// annualized volatility (e.g. 0.1) estimated over a trailing window, capped at a maximum leverage,
+    /// compared with the unmanaged portfolio in the performance report
+    pub fn volatility_target(&mut self, target_volatility: f64, window: usize, max_leverage: f64) -> &mut PortfolioBuilder {
+        self.volatility_target = Some(VolatilityTarget::new(target_volatility, window, max_leverage));
+        self
+    }
+
+    /// Adds a moving average trend filter overlay (e.g. a 200 day filter) on the portfolio equity or its
+    /// constituents, compared with the unmanaged portfolio in the performance report
+    pub fn trend_filter(&mut self, window: usize, target: TrendFilterTarget) -> &mut PortfolioBuilder {
+        self.trend_filter = Some(TrendFilter::new(window, target));
+        self
+    }
+
+    /// Screens the assets on their ESG risk rating from Yahoo Finance (e.g. 30.0), excluding or capping the assets
+    /// rated above it, and reports the weighted ESG risk rating of the portfolio
+    pub fn esg_screen(&mut self, max_risk_score: f64, action: EsgAction) -> &mut PortfolioBuilder {
+        self.esg_screen = Some(EsgScreen::new(max_risk_score, action));
+        self
+    }
+
     pub async fn build(&mut self) -> Result<Portfolio, Box<dyn Error>> {
-        let performance_stats = PortfolioPerformanceStats::new(
-            self.ticker_symbols.clone(), &self.benchmark_symbol, &self.start_date, &self.end_date, self.interval,
-            self.confidence_level, self.risk_free_rate, self.objective_function, self.constraints.clone()).await?.compute_stats()?;
+        let constraints = self.liquidity_constraints(self.pinned_constraints()?).await?;
+        let esg_scores = match self.esg_screen {
+            Some(_) => Some(fetch_esg_scores(&self.ticker_symbols).await),
+            None => None,
+        };
+        let constraints = self.esg_constraints(constraints, esg_scores.as_deref())?;
+        let mut performance_stats = PortfolioPerformanceStats::new(self.config(constraints)).await?.compute_stats()?;
+        performance_stats.volatility_target = self.volatility_target.clone();
+        performance_stats.trend_filter = self.trend_filter.clone();
+        performance_stats.esg_screen = self.esg_screen;
+        performance_stats.esg_scores = esg_scores.map(|scores| {
+            performance_stats.ticker_symbols.iter()
+                .map(|s| self.ticker_symbols.iter().position(|x| x == s).map(|i| scores[i]).unwrap_or(f64::NAN))
+                .collect()
+        });
         Ok(Portfolio {
             performance_stats,
         })
     }
+
+    /// Inputs of the portfolio with the resolved weight bounds of each asset
+    fn config(&self, constraints: Option<Vec<(f64, f64)>>) -> PortfolioConfig {
+        PortfolioConfig {
+            ticker_symbols: self.ticker_symbols.clone(),
+            benchmark_symbol: self.benchmark_data.as_ref().map_or(self.benchmark_symbol.clone(), |x| x.ticker.clone()),
+            start_date: self.start_date.clone(),
+            end_date: self.end_date.clone(),
+            interval: self.interval,
+            confidence_level: self.confidence_level,
+            risk_free_rate: self.risk_free_rate,
+            objective_function: self.objective_function,
+            constraints,
+            category_constraints: self.category_constraints.clone(),
+            turnover_constraint: self.turnover_constraint.clone(),
+            covariance_estimator: self.covariance_estimator,
+            expected_return_model: self.expected_return_model,
+            calendar: self.calendar,
+            return_type: self.return_type,
+            price_type: self.price_type,
+            seed: self.seed,
+            ticker_data: with_benchmark(&self.ticker_data, &self.benchmark_data),
+            uncertainty_set: self.uncertainty_set,
+            max_drawdown: self.max_drawdown,
+            tracking_error_limit: self.tracking_error_limit,
+            replication_holdings: self.replication_holdings,
+            position_constraint: self.position_constraint,
+        }
+    }
+
+    /// Lowers the upper bound of each asset to its liquidity limit, pinned weights are kept but flagged when they exceed it
+    async fn liquidity_constraints(&self, constraints: Option<Vec<(f64, f64)>>) -> Result<Option<Vec<(f64, f64)>>, Box<dyn Error>> {
+        let Some(liquidity) = &self.liquidity_constraint else {
+            return Ok(constraints);
+        };
+        let mut constraints = constraints.unwrap_or(vec![(0.0, 1.0); self.ticker_symbols.len()]);
+        for (i, symbol) in self.ticker_symbols.iter().enumerate() {
+            let ohlcv = TickerBuilder::new()
+                .ticker(symbol)
+                .start_date(&self.start_date)
+                .end_date(&self.end_date)
+                .interval(self.interval)
+                .calendar(self.calendar)
+                .ticker_data(kline_for(&self.ticker_data, symbol))
+                .build()
+                .get_chart().await?;
+            let metrics = LiquidityMetrics::compute(&ohlcv, liquidity.window)?;
+            let max_weight = liquidity.max_weight(metrics.average_dollar_volume);
+            if self.pinned_weights.iter().any(|(s, _)| s == symbol) {
+                if constraints[i].0 > max_weight {
+                    eprintln!("Pinned weight of {} exceeds its liquidity limit of {:.4}", symbol, max_weight);
+                }
+                continue;
+            }
+            constraints[i] = (constraints[i].0.min(max_weight), constraints[i].1.min(max_weight));
+        }
+        let capacity: f64 = constraints.iter().map(|x| x.1).sum();
+        if capacity < 1.0 - 1e-9 {
+            return Err(format!("Liquidity limits only allow {:.2}% of the portfolio to be invested, \
+                                reduce the portfolio value or add more liquid assets", capacity * 100.0).into());
+        }
+        Ok(Some(constraints))
+    }
+
+    /// Excludes or caps the assets failing the ESG screen, pinned weights are kept but flagged when they fail it
+    fn esg_constraints(&self, constraints: Option<Vec<(f64, f64)>>, esg_scores: Option<&[f64]>) -> Result<Option<AssetBounds>, Box<dyn Error>> {
+        let (Some(screen), Some(scores)) = (&self.esg_screen, esg_scores) else {
+            return Ok(constraints);
+        };
+        let mut constraints = constraints.unwrap_or(vec![(0.0, 1.0); self.ticker_symbols.len()]);
+        for (i, symbol) in self.ticker_symbols.iter().enumerate() {
+            if !screen.fails(scores[i]) {
+                continue;
+            }
+            if self.pinned_weights.iter().any(|(s, _)| s == symbol) {
+                eprintln!("Pinned asset {} fails the ESG screen with a risk rating of {:.2}", symbol, scores[i]);
+                continue;
+            }
+            constraints[i] = screen.bounds(constraints[i]);
+        }
+        let capacity: f64 = constraints.iter().map(|x| x.1).sum();
+        if capacity < 1.0 - 1e-9 {
+            return Err(format!("The ESG screen only allows {:.2}% of the portfolio to be invested, \
+                                raise the maximum risk rating or add more assets", capacity * 100.0).into());
+        }
+        Ok(Some(constraints))
+    }
+
+    /// Merges the pinned weights into the per-asset constraints as equal lower and upper bounds
+    fn pinned_constraints(&self) -> Result<Option<AssetBounds>, Box<dyn Error>> {
+        if self.pinned_weights.is_empty() {
+            return Ok(self.constraints.clone());
+        }
+        let mut constraints = self.constraints.clone()
+            .unwrap_or(vec![(0.0, 1.0); self.ticker_symbols.len()]);
+        for (symbol, weight) in &self.pinned_weights {
+            let index = self.ticker_symbols.iter().position(|s| s == symbol)
+                .ok_or(format!("Pinned symbol {} is not in the portfolio", symbol))?;
+            if !(0.0..=1.0).contains(weight) {
+                return Err(format!("Pinned weight of {} must be between 0 and 1", symbol).into());
+            }
+            constraints[index] = (*weight, *weight);
+        }
+        let pinned_sum: f64 = self.pinned_weights.iter().map(|x| x.1).sum();
+        if pinned_sum > 1.0 + 1e-9 {
+            return Err(format!("Pinned weights sum to {:.2}, which exceeds 1", pinned_sum).into());
+        }
+        Ok(Some(constraints))
+    }
 }
 
 /// # Portfolio Struct
@@ -0,0 +1,324 @@
+use std::error::Error;
+use std::collections::BTreeMap;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::models::ticker::TickerBuilder;
+use crate::data::ticker::TickerData;
+use crate::analytics::options::{chain_contracts, ChainContract};
+use crate::analytics::stochastics::{BlackScholesModel, OptionType};
+
+
+/// Shares represented by one listed equity option contract
+pub const CONTRACT_MULTIPLIER: f64 = 100.0;
+
+/// Kind of instrument held in a position
+///
+/// * `Stock` - Shares of the symbol
+/// * `Option` - Option contracts on the symbol, `option_type` is "call" or "put", `expiration` is YYYY-MM-DD
+///   and `multiplier` the shares per contract
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PositionKind {
+    Stock,
+    Option {
+        option_type: String,
+        strike: f64,
+        expiration: String,
+        multiplier: f64,
+    },
+}
+
+/// A stock or option position, long when the quantity is positive and short when it is negative
+///
+/// * `symbol` - Symbol of the stock, or of the underlying for an option
+/// * `quantity` - Number of shares or contracts
+/// * `kind` - Stock or option
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub symbol: String,
+    pub quantity: f64,
+    pub kind: PositionKind,
+}
+
+impl Position {
+    /// Stock position of a number of shares, negative for a short position
+    pub fn stock(symbol: &str, shares: f64) -> Position {
+        Position { symbol: symbol.to_uppercase(), quantity: shares, kind: PositionKind::Stock }
+    }
+
+    /// Option position of a number of contracts with the standard multiplier of 100 shares, negative for contracts sold
+    pub fn option(underlying: &str, option_type: OptionType, strike: f64, expiration: &str, contracts: f64) -> Position {
+        Position {
+            symbol: underlying.to_uppercase(),
+            quantity: contracts,
+            kind: PositionKind::Option {
+                option_type: option_type.to_string().to_lowercase(),
+                strike,
+                expiration: expiration.to_string(),
+                multiplier: CONTRACT_MULTIPLIER,
+            },
+        }
+    }
+
+    /// Overrides the shares per contract of an option position (e.g. for adjusted or mini contracts)
+    pub fn multiplier(mut self, shares: f64) -> Position {
+        if let PositionKind::Option { multiplier, .. } = &mut self.kind {
+            *multiplier = shares;
+        }
+        self
+    }
+
+    /// Description of the instrument, e.g. "AAPL", "AAPL 2025-01-17 200 call"
+    pub fn instrument(&self) -> String {
+        match &self.kind {
+            PositionKind::Stock => self.symbol.clone(),
+            PositionKind::Option { option_type, strike, expiration, .. } =>
+                format!("{} {} {} {}", self.symbol, expiration, strike, option_type),
+        }
+    }
+}
+
+/// Market value, share-equivalent greeks and margin estimate of a position
+#[derive(Debug, Clone)]
+struct PositionRisk {
+    price: f64,
+    market_value: f64,
+    delta: f64,
+    gamma: f64,
+    vega: f64,
+    theta: f64,
+    margin: f64,
+}
+
+/// Values a position at the spot price of its underlying and its option contract in the chain
+///
+/// Greeks are per position: delta in shares, gamma in shares per unit move of the underlying, vega per
+/// volatility point and theta per calendar day.
+fn position_risk(position: &Position, spot: f64, contract: Option<&ChainContract>, risk_free_rate: f64) -> Result<PositionRisk, Box<dyn Error>> {
+    match &position.kind {
+        PositionKind::Stock => Ok(PositionRisk {
+            price: spot,
+            market_value: position.quantity * spot,
+            delta: position.quantity,
+            gamma: 0.0,
+            vega: 0.0,
+            theta: 0.0,
+            // Regulation T initial margin of 50% for long and short stock
+            margin: 0.5 * (position.quantity * spot).abs(),
+        }),
+        PositionKind::Option { option_type, strike, multiplier, .. } => {
+            let contract = contract.ok_or(format!("{} is not listed in the options chain", position.instrument()))?;
+            let option_type = if option_type == "call" { OptionType::Call } else { OptionType::Put };
+            let shares = position.quantity * multiplier;
+            let (delta, gamma, vega, theta) = if contract.days > 0.0 && contract.implied_volatility > 0.0 {
+                let bs = BlackScholesModel::compute(spot, *strike, contract.days / 365.0, risk_free_rate,
+                                                    contract.implied_volatility, option_type);
+                (bs.delta, bs.gamma, bs.vega / 100.0, bs.theta / 365.0)
+            } else {
+                // Expiring contracts only carry their intrinsic delta
+                let itm = match option_type {
+                    OptionType::Call => spot > *strike,
+                    OptionType::Put => spot < *strike,
+                };
+                let delta = match (itm, option_type) {
+                    (false, _) => 0.0,
+                    (true, OptionType::Call) => 1.0,
+                    (true, OptionType::Put) => -1.0,
+                };
+                (delta, 0.0, 0.0, 0.0)
+            };
+            let margin = if position.quantity >= 0.0 {
+                // Long options are paid in full
+                contract.price * shares
+            } else {
+                // Naked short options: 20% of the underlying less the out-of-the-money amount, at least 10%
+                // of the underlying (calls) or strike (puts), plus the premium
+                let (otm, floor) = match option_type {
+                    OptionType::Call => ((strike - spot).max(0.0), 0.1 * spot),
+                    OptionType::Put => ((spot - strike).max(0.0), 0.1 * strike),
+                };
+                ((0.2 * spot - otm).max(floor) + contract.price) * shares.abs()
+            };
+            Ok(PositionRisk {
+                price: contract.price,
+                market_value: contract.price * shares,
+                delta: delta * shares,
+                gamma: gamma * shares,
+                vega: vega * shares,
+                theta: theta * shares,
+                margin,
+            })
+        }
+    }
+}
+
+
+/// # Positions Struct
+///
+/// ### Description
+///    - A book of stock and option positions, valued with the latest prices and options chains to summarize
+///      the exposure and greeks of mixed stock and option portfolios.
+///    - Option greeks use the Black-Scholes model with the implied volatility of the contract in the chain.
+///      Margin requirements are Regulation T style estimates for each position on its own, covered and spread
+///      positions are not netted.
+///
+/// ### Example
+///
+/// ```rust
+/// use std::error::Error;
+/// use finalytics::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let positions = Positions::new(vec![
+///         Position::stock("AAPL", 200.0),
+///         Position::option("AAPL", OptionType::Call, 250.0, "2025-12-19", -2.0),
+///         Position::option("MSFT", OptionType::Put, 400.0, "2025-12-19", 1.0),
+///     ]).risk_free_rate(0.04);
+///
+///     let summary = positions.exposure_summary().await?;
+///     println!("{:?}", summary);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Positions {
+    pub positions: Vec<Position>,
+    pub risk_free_rate: f64,
+}
+
+impl Positions {
+    pub fn new(positions: Vec<Position>) -> Positions {
+        Positions { positions, risk_free_rate: 0.02 }
+    }
+
+    /// Sets the risk-free rate used for the option greeks (default 0.02)
+    pub fn risk_free_rate(mut self, risk_free_rate: f64) -> Positions {
+        self.risk_free_rate = risk_free_rate;
+        self
+    }
+
+    /// Adds a position to the book
+    pub fn add(&mut self, position: Position) {
+        self.positions.push(position);
+    }
+
+    /// Values every position with the latest spot price and options chain of its underlying
+    async fn position_risks(&self) -> Result<Vec<(f64, PositionRisk)>, Box<dyn Error>> {
+        let mut underlyings: BTreeMap<String, (f64, Vec<ChainContract>)> = BTreeMap::new();
+        for position in &self.positions {
+            if underlyings.contains_key(&position.symbol) {
+                continue;
+            }
+            let ticker = TickerBuilder::new().ticker(&position.symbol).build();
+            let has_options = self.positions.iter()
+                .any(|x| x.symbol == position.symbol && matches!(x.kind, PositionKind::Option { .. }));
+            let market = if has_options {
+                let options = ticker.get_options().await
+                    .map_err(|e| format!("Unable to fetch the options chain of {}: {}", position.symbol, e))?;
+                (options.ticker_price, chain_contracts(&options.chain)?)
+            } else {
+                let quote = ticker.get_quote().await
+                    .map_err(|e| format!("Unable to fetch the quote of {}: {}", position.symbol, e))?;
+                (quote.price, Vec::new())
+            };
+            underlyings.insert(position.symbol.clone(), market);
+        }
+
+        self.positions.iter()
+            .map(|position| {
+                let (spot, contracts) = &underlyings[&position.symbol];
+                let contract = match &position.kind {
+                    PositionKind::Stock => None,
+                    PositionKind::Option { option_type, strike, expiration, .. } => contracts.iter()
+                        .find(|c| c.expiration == *expiration && c.strike == *strike && c.is_call == (option_type == "call")),
+                };
+                Ok((*spot, position_risk(position, *spot, contract, self.risk_free_rate)?))
+            })
+            .collect()
+    }
+
+    /// Market value, greeks and margin estimate of each position
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per position with columns instrument, underlying, type (stock, call or put),
+    ///   quantity, spot, price, market_value, delta (shares), dollar_delta, gamma, vega (per volatility point),
+    ///   theta (per day) and margin_requirement
+    pub async fn position_greeks(&self) -> Result<DataFrame, Box<dyn Error>> {
+        let risks = self.position_risks().await?;
+        let df = df!(
+            "instrument" => self.positions.iter().map(|x| x.instrument()).collect::<Vec<String>>(),
+            "underlying" => self.positions.iter().map(|x| x.symbol.as_str()).collect::<Vec<&str>>(),
+            "type" => self.positions.iter().map(|x| match &x.kind {
+                PositionKind::Stock => "stock",
+                PositionKind::Option { option_type, .. } => option_type.as_str(),
+            }).collect::<Vec<&str>>(),
+            "quantity" => self.positions.iter().map(|x| x.quantity).collect::<Vec<f64>>(),
+            "spot" => risks.iter().map(|(spot, _)| *spot).collect::<Vec<f64>>(),
+            "price" => risks.iter().map(|(_, r)| r.price).collect::<Vec<f64>>(),
+            "market_value" => risks.iter().map(|(_, r)| r.market_value).collect::<Vec<f64>>(),
+            "delta" => risks.iter().map(|(_, r)| r.delta).collect::<Vec<f64>>(),
+            "dollar_delta" => risks.iter().map(|(spot, r)| r.delta * spot).collect::<Vec<f64>>(),
+            "gamma" => risks.iter().map(|(_, r)| r.gamma).collect::<Vec<f64>>(),
+            "vega" => risks.iter().map(|(_, r)| r.vega).collect::<Vec<f64>>(),
+            "theta" => risks.iter().map(|(_, r)| r.theta).collect::<Vec<f64>>(),
+            "margin_requirement" => risks.iter().map(|(_, r)| r.margin).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+
+    /// Net exposure and greeks of each underlying and of the whole book
+    ///
+    /// Net delta and gamma are in shares of the underlying and left empty on the total row, where only the
+    /// dollar delta (share-equivalent exposure at spot), vega, theta and margin are additive.
+    ///
+    /// # Returns
+    ///
+    /// * `DataFrame` - One row per underlying followed by a "Total" row, with columns underlying, market_value,
+    ///   gross_exposure, net_delta, dollar_delta, net_gamma, net_vega, net_theta and margin_requirement
+    pub async fn exposure_summary(&self) -> Result<DataFrame, Box<dyn Error>> {
+        if self.positions.is_empty() {
+            return Err("No positions in the book".into());
+        }
+        let risks = self.position_risks().await?;
+
+        // market value, gross exposure, delta, dollar delta, gamma, vega, theta, margin
+        let mut underlyings: BTreeMap<String, [f64; 8]> = BTreeMap::new();
+        for (position, (spot, risk)) in self.positions.iter().zip(&risks) {
+            let entry = underlyings.entry(position.symbol.clone()).or_insert([0.0; 8]);
+            entry[0] += risk.market_value;
+            entry[1] += (risk.delta * spot).abs();
+            entry[2] += risk.delta;
+            entry[3] += risk.delta * spot;
+            entry[4] += risk.gamma;
+            entry[5] += risk.vega;
+            entry[6] += risk.theta;
+            entry[7] += risk.margin;
+        }
+        let mut names = underlyings.keys().cloned().collect::<Vec<String>>();
+        let mut rows = underlyings.values().cloned().collect::<Vec<[f64; 8]>>();
+        let total = rows.iter().fold([0.0; 8], |mut acc, row| {
+            acc.iter_mut().zip(row).for_each(|(a, x)| *a += x);
+            acc
+        });
+        names.push("Total".to_string());
+        rows.push(total);
+        let per_underlying = |i: usize| rows.iter().enumerate()
+            .map(|(r, x)| (r < rows.len() - 1).then_some(x[i]))
+            .collect::<Vec<Option<f64>>>();
+
+        let df = df!(
+            "underlying" => &names,
+            "market_value" => rows.iter().map(|x| x[0]).collect::<Vec<f64>>(),
+            "gross_exposure" => rows.iter().map(|x| x[1]).collect::<Vec<f64>>(),
+            "net_delta" => per_underlying(2),
+            "dollar_delta" => rows.iter().map(|x| x[3]).collect::<Vec<f64>>(),
+            "net_gamma" => per_underlying(4),
+            "net_vega" => rows.iter().map(|x| x[5]).collect::<Vec<f64>>(),
+            "net_theta" => rows.iter().map(|x| x[6]).collect::<Vec<f64>>(),
+            "margin_requirement" => rows.iter().map(|x| x[7]).collect::<Vec<f64>>()
+        )?;
+        Ok(df)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::data::config::Interval;
+use crate::data::config::{Interval, MarketCalendar, PriceType, ReturnType};
+use crate::data::kline::KLINE;
 
 
 pub struct TickerBuilder {
@@ -9,6 +10,13 @@ pub struct TickerBuilder {
     benchmark_symbol: String,
     confidence_level: f64,
     risk_free_rate: f64,
+    calendar: MarketCalendar,
+    validate_data: bool,
+    return_type: ReturnType,
+    price_type: PriceType,
+    ticker_data: Option<KLINE>,
+    benchmark_data: Option<KLINE>,
+    bootstrap_samples: Option<usize>,
 }
 
 impl TickerBuilder {
@@ -21,6 +29,13 @@ impl TickerBuilder {
             benchmark_symbol: String::from("^GSPC"),
             confidence_level: 0.95,
             risk_free_rate: 0.02,
+            calendar: MarketCalendar::Equity,
+            validate_data: false,
+            return_type: ReturnType::Simple,
+            price_type: PriceType::AdjClose,
+            ticker_data: None,
+            benchmark_data: None,
+            bootstrap_samples: None,
         }
     }
 
@@ -59,6 +74,42 @@ impl TickerBuilder {
         self
     }
 
+    pub fn calendar(mut self, calendar: MarketCalendar) -> TickerBuilder {
+        self.calendar = calendar;
+        self
+    }
+
+    pub fn validate_data(mut self, validate_data: bool) -> TickerBuilder {
+        self.validate_data = validate_data;
+        self
+    }
+
+    pub fn return_type(mut self, return_type: ReturnType) -> TickerBuilder {
+        self.return_type = return_type;
+        self
+    }
+
+    pub fn price_type(mut self, price_type: PriceType) -> TickerBuilder {
+        self.price_type = price_type;
+        self
+    }
+
+    pub fn ticker_data(mut self, ticker_data: Option<KLINE>) -> TickerBuilder {
+        self.ticker_data = ticker_data;
+        self
+    }
+
+    pub fn benchmark_data(mut self, benchmark_data: Option<KLINE>) -> TickerBuilder {
+        self.benchmark_data = benchmark_data;
+        self
+    }
+
+    /// Adds block bootstrap confidence bands with the given number of resamples to the performance stats table
+    pub fn bootstrap_samples(mut self, bootstrap_samples: Option<usize>) -> TickerBuilder {
+        self.bootstrap_samples = bootstrap_samples;
+        self
+    }
+
     pub fn build(self) -> Ticker {
         Ticker {
             ticker: self.ticker,
@@ -68,6 +119,13 @@ impl TickerBuilder {
             benchmark_symbol: self.benchmark_symbol,
             confidence_level: self.confidence_level,
             risk_free_rate: self.risk_free_rate,
+            calendar: self.calendar,
+            validate_data: self.validate_data,
+            return_type: self.return_type,
+            price_type: self.price_type,
+            ticker_data: self.ticker_data,
+            benchmark_data: self.benchmark_data,
+            bootstrap_samples: self.bootstrap_samples,
         }
     }
 }
@@ -109,6 +167,7 @@ impl TickerBuilder {
 ///  ticker.report(Some(ReportType::Financials)).await?.show()?;
 ///  ticker.report(Some(ReportType::Options)).await?.show()?;
 ///  ticker.report(Some(ReportType::News)).await?.show()?;
+///  ticker.report(Some(ReportType::Forecast)).await?.show()?;
 ///
 ///
 ///  Ok(())
@@ -124,6 +183,13 @@ pub struct Ticker {
     pub benchmark_symbol: String,
     pub confidence_level: f64,
     pub risk_free_rate: f64,
+    pub calendar: MarketCalendar,
+    pub validate_data: bool,
+    pub return_type: ReturnType,
+    pub price_type: PriceType,
+    pub ticker_data: Option<KLINE>,
+    pub benchmark_data: Option<KLINE>,
+    pub bootstrap_samples: Option<usize>,
 }
 
 
@@ -1,5 +1,55 @@
 use std::error::Error;
-use crate::prelude::{Interval, ObjectiveFunction, Portfolio, PortfolioBuilder, Ticker, TickerBuilder};
+use std::sync::{Arc, Mutex};
+use futures::stream::{self, StreamExt};
+use crate::data::kline::{kline_for, with_benchmark, KLINE};
+use crate::reports::report::Report;
+use crate::reports::tabs::TabbedHtml;
+use crate::prelude::{Interval, MarketCalendar, PriceType, ReturnType, ReportType, ObjectiveFunction, Portfolio, PortfolioBuilder, Ticker, TickerBuilder, TickersData};
+
+/// How Tickers operations handle symbols that fail (e.g. delisted or mistyped symbols)
+///
+/// * `Skip` - Leave the failed symbols out of the result and record them, see `Tickers::failures`
+/// * `Abort` - Return an error listing the failed symbols
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FailurePolicy {
+    Skip,
+    Abort,
+}
+
+/// Symbol that failed in a Tickers operation
+///
+/// * `symbol` - Ticker symbol
+/// * `operation` - Operation that failed (e.g. "get_chart", "returns")
+/// * `error` - Error message
+#[derive(Debug, Clone)]
+pub struct SymbolFailure {
+    pub symbol: String,
+    pub operation: String,
+    pub error: String,
+}
+
+/// Default number of symbol reports generated concurrently
+pub const DEFAULT_REPORT_CONCURRENCY: usize = 4;
+
+/// Progress of a batch of symbol reports, passed to the progress callback as each symbol finishes
+///
+/// * `symbol` - Symbol whose report just finished
+/// * `completed` - Number of reports finished so far
+/// * `total` - Number of reports in the batch
+/// * `error` - Error message when the report of the symbol failed
+#[derive(Debug, Clone)]
+pub struct ReportProgress {
+    pub symbol: String,
+    pub completed: usize,
+    pub total: usize,
+    pub error: Option<String>,
+}
+
+/// Report of one symbol of a batch, failed symbols keep the error message instead of the report
+pub struct SymbolReport {
+    pub symbol: String,
+    pub report: Result<TabbedHtml, String>,
+}
 
 
 pub struct TickersBuilder {
@@ -10,6 +60,13 @@ pub struct TickersBuilder {
     benchmark_symbol: String,
     confidence_level: f64,
     risk_free_rate: f64,
+    calendar: MarketCalendar,
+    validate_data: bool,
+    return_type: ReturnType,
+    price_type: PriceType,
+    ticker_data: Option<Vec<KLINE>>,
+    benchmark_data: Option<KLINE>,
+    failure_policy: FailurePolicy,
 }
 
 impl TickersBuilder {
@@ -22,6 +79,13 @@ impl TickersBuilder {
             benchmark_symbol: String::from("^GSPC"),
             confidence_level: 0.95,
             risk_free_rate: 0.02,
+            calendar: MarketCalendar::Equity,
+            validate_data: false,
+            return_type: ReturnType::Simple,
+            price_type: PriceType::AdjClose,
+            ticker_data: None,
+            benchmark_data: None,
+            failure_policy: FailurePolicy::Skip,
         }
     }
 
@@ -60,24 +124,86 @@ impl TickersBuilder {
         self
     }
 
+    pub fn calendar(&mut self, calendar: MarketCalendar) -> &mut TickersBuilder {
+        self.calendar = calendar;
+        self
+    }
+
+    pub fn validate_data(&mut self, validate_data: bool) -> &mut TickersBuilder {
+        self.validate_data = validate_data;
+        self
+    }
+
+    pub fn return_type(&mut self, return_type: ReturnType) -> &mut TickersBuilder {
+        self.return_type = return_type;
+        self
+    }
+
+    pub fn price_type(&mut self, price_type: PriceType) -> &mut TickersBuilder {
+        self.price_type = price_type;
+        self
+    }
+
+    /// Uses user supplied OHLCV data (e.g. from `KLINE::from_directory`) instead of Yahoo Finance;
+    /// all the symbols in the data (except the benchmark) are used when no tickers are set
+    pub fn ticker_data(&mut self, ticker_data: Option<Vec<KLINE>>) -> &mut TickersBuilder {
+        self.ticker_data = ticker_data;
+        self
+    }
+
+    /// Uses a user supplied benchmark series (e.g. a proprietary index from `KLINE::from_csv` or `KLINE::from_returns_csv`)
+    /// instead of a Yahoo Finance symbol, the benchmark symbol becomes the symbol of the KLINE
+    pub fn benchmark_data(&mut self, benchmark_data: Option<KLINE>) -> &mut TickersBuilder {
+        self.benchmark_data = benchmark_data;
+        self
+    }
+
+    /// Sets how symbols that fail are handled (default - FailurePolicy::Skip)
+    pub fn failure_policy(&mut self, failure_policy: FailurePolicy) -> &mut TickersBuilder {
+        self.failure_policy = failure_policy;
+        self
+    }
+
     pub fn build(&self) -> Tickers {
+        let ticker_data = with_benchmark(&self.ticker_data, &self.benchmark_data);
+        let benchmark_symbol = self.benchmark_data.as_ref().map_or(self.benchmark_symbol.clone(), |x| x.ticker.clone());
+        let symbols = match (&ticker_data, self.tickers.is_empty()) {
+            (Some(data), true) => data.iter()
+                .filter(|x| x.ticker != benchmark_symbol)
+                .map(|x| x.ticker.clone())
+                .collect::<Vec<String>>(),
+            _ => self.tickers.clone(),
+        };
         Tickers {
-            tickers: self.tickers.clone().into_iter().map(|x|
+            tickers: symbols.into_iter().map(|x|
                 TickerBuilder::new().ticker(&x)
                     .start_date(&self.start_date)
                     .end_date(&self.end_date)
                     .interval(self.interval)
-                    .benchmark_symbol(&self.benchmark_symbol)
+                    .benchmark_symbol(&benchmark_symbol)
                     .confidence_level(self.confidence_level)
                     .risk_free_rate(self.risk_free_rate)
+                    .calendar(self.calendar)
+                    .validate_data(self.validate_data)
+                    .return_type(self.return_type)
+                    .price_type(self.price_type)
+                    .ticker_data(kline_for(&ticker_data, &x))
+                    .benchmark_data(kline_for(&ticker_data, &benchmark_symbol))
                     .build()
             ).collect(),
             start_date: self.start_date.clone(),
             end_date: self.end_date.clone(),
             interval: self.interval,
-            benchmark_symbol: self.benchmark_symbol.clone(),
+            benchmark_symbol: benchmark_symbol.clone(),
             confidence_level: self.confidence_level,
             risk_free_rate: self.risk_free_rate,
+            calendar: self.calendar,
+            validate_data: self.validate_data,
+            return_type: self.return_type,
+            price_type: self.price_type,
+            ticker_data,
+            failure_policy: self.failure_policy,
+            failures: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -100,6 +226,120 @@ impl Tickers {
         }
     }
 
+    /// Generate a Report for each ticker within the Tickers Struct
+    ///
+    /// Reports are generated concurrently up to the concurrency limit. A failing symbol does not abort the batch,
+    /// its error is kept in the returned `SymbolReport` and passed to the progress callback.
+    ///
+    /// ### Arguments
+    /// - `report_type` - The Report Type (Performance, Financials, Options, News or Forecast)
+    /// - `concurrency` - Maximum number of reports generated at once (e.g. `DEFAULT_REPORT_CONCURRENCY`)
+    /// - `progress` - Optional callback invoked as each symbol finishes
+    ///
+    /// ### Returns
+    ///
+    /// - A `SymbolReport` for each ticker, in the order of the tickers
+    pub async fn symbol_reports(&self, report_type: ReportType, concurrency: usize,
+                                progress: Option<&dyn Fn(&ReportProgress)>) -> Result<Vec<SymbolReport>, Box<dyn Error>> {
+        if let ReportType::Execution | ReportType::Strategy = report_type {
+            return Err(format!("{} reports are not generated per symbol", report_type.to_str()).into());
+        }
+        let total = self.tickers.len();
+        let mut completed = 0;
+        let mut reports = stream::iter(self.tickers.iter().enumerate())
+            .map(|(i, ticker)| async move {
+                let report = ticker.report(Some(report_type)).await.map_err(|e| e.to_string());
+                (i, SymbolReport { symbol: ticker.ticker.clone(), report })
+            })
+            .buffer_unordered(concurrency.max(1))
+            .inspect(|(_, symbol_report)| {
+                completed += 1;
+                if let Err(e) = &symbol_report.report {
+                    eprintln!("Error generating {} report for {}: {}", report_type.to_str(), symbol_report.symbol, e);
+                }
+                if let Some(callback) = progress {
+                    callback(&ReportProgress {
+                        symbol: symbol_report.symbol.clone(),
+                        completed,
+                        total,
+                        error: symbol_report.report.as_ref().err().cloned(),
+                    });
+                }
+            })
+            .collect::<Vec<(usize, SymbolReport)>>()
+            .await;
+        reports.sort_by_key(|(i, _)| *i);
+        self.record_failures("report", &reports.iter()
+            .filter_map(|(_, x)| x.report.as_ref().err().map(|e| (x.symbol.clone(), e.clone())))
+            .collect::<Vec<(String, String)>>());
+        Ok(reports.into_iter().map(|(_, x)| x).collect())
+    }
+
+    /// Symbols that failed in the operations run so far, the latest failure of each symbol and operation
+    ///
+    /// ### Returns
+    ///
+    /// - A `SymbolFailure` for each failed symbol and operation
+    pub fn failures(&self) -> Vec<SymbolFailure> {
+        self.failures.lock().map(|x| x.clone()).unwrap_or_default()
+    }
+
+    /// Clears the recorded symbol failures
+    pub fn clear_failures(&self) {
+        if let Ok(mut failures) = self.failures.lock() {
+            failures.clear();
+        }
+    }
+
+    /// Records the symbols that failed in an operation
+    pub(crate) fn record_failures(&self, operation: &str, failures: &[(String, String)]) {
+        if let Ok(mut recorded) = self.failures.lock() {
+            for (symbol, error) in failures {
+                recorded.retain(|x| !(x.symbol == *symbol && x.operation == operation));
+                recorded.push(SymbolFailure { symbol: symbol.clone(), operation: operation.to_string(), error: error.clone() });
+            }
+        }
+    }
+
+    /// Records the symbols that failed in an operation and applies the failure policy
+    pub(crate) fn handle_failures(&self, operation: &str, failures: Vec<(String, String)>) -> Result<(), Box<dyn Error>> {
+        self.record_failures(operation, &failures);
+        #[cfg(feature = "tracing")]
+        for (symbol, error) in &failures {
+            tracing::warn!(operation, symbol = %symbol, error = %error, "symbol failed");
+        }
+        if self.failure_policy == FailurePolicy::Abort && !failures.is_empty() {
+            let symbols = failures.iter().map(|(symbol, _)| symbol.as_str()).collect::<Vec<&str>>();
+            return Err(format!("{} failed for {} of {} symbols: {}", operation, failures.len(), self.tickers.len(),
+                               symbols.join(", ")).into());
+        }
+        Ok(())
+    }
+
+    /// Reduce the Tickers Struct to a diversified subset of representative tickers
+    ///
+    /// The tickers are clustered by return correlation and the medoid of each cluster is kept,
+    /// e.g. to reduce hundreds of screener results to a tractable universe before optimization.
+    ///
+    /// ### Arguments
+    /// - `n_clusters` - Number of tickers to keep
+    ///
+    /// ### Returns
+    ///
+    /// - A `Tickers` Struct with the medoid tickers
+    pub async fn reduce_universe(&self, n_clusters: usize) -> Result<Tickers, Box<dyn Error>> {
+        let clusters = self.correlation_clusters(n_clusters).await?;
+        let tickers = self.tickers.iter()
+            .filter(|x| clusters.medoids.contains(&x.ticker))
+            .cloned()
+            .collect::<Vec<Ticker>>();
+        Ok(Tickers {
+            tickers,
+            failures: Arc::new(Mutex::new(Vec::new())),
+            ..self.clone()
+        })
+    }
+
     /// Optimize a Portfolio of multiple tickers within the Tickers Struct
     ///
     /// ### Arguments
@@ -119,6 +359,10 @@ impl Tickers {
             .interval(self.interval)
             .confidence_level(self.confidence_level)
             .risk_free_rate(self.risk_free_rate)
+            .calendar(self.calendar)
+            .return_type(self.return_type)
+            .price_type(self.price_type)
+            .ticker_data(self.ticker_data.clone())
             .objective_function(objective_function.unwrap_or(ObjectiveFunction::MaxSharpe))
             .constraints(constraints)
             .build().await
@@ -166,6 +410,15 @@ impl Tickers {
 ///    // Generate a Multiple Ticker Report
 ///    tickers.report(Some(ReportType::Performance)).await?.show()?;
 ///
+///    // Generate a Report for each Ticker, 4 at a time, without aborting on failed symbols
+///    let progress = |p: &ReportProgress| println!("{} ({}/{})", p.symbol, p.completed, p.total);
+///    for symbol_report in tickers.symbol_reports(ReportType::Performance, 4, Some(&progress)).await? {
+///        match symbol_report.report {
+///            Ok(report) => report.show()?,
+///            Err(e) => println!("{} failed: {}", symbol_report.symbol, e),
+///        }
+///    }
+///
 ///    // Perform a Portfolio Optimization
 ///    let portfolio = tickers.optimize(Some(ObjectiveFunction::MaxSharpe), None).await?;
 ///
@@ -185,4 +438,11 @@ pub struct Tickers {
     pub benchmark_symbol: String,
     pub confidence_level: f64,
     pub risk_free_rate: f64,
+    pub calendar: MarketCalendar,
+    pub validate_data: bool,
+    pub return_type: ReturnType,
+    pub price_type: PriceType,
+    pub ticker_data: Option<Vec<KLINE>>,
+    pub failure_policy: FailurePolicy,
+    failures: Arc<Mutex<Vec<SymbolFailure>>>,
 }
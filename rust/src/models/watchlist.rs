@@ -0,0 +1,176 @@
+use std::error::Error;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::models::portfolio::PortfolioBuilder;
+use crate::models::tickers::TickersBuilder;
+
+
+/// A named list of ticker symbols
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watchlist {
+    pub name: String,
+    pub symbols: Vec<String>,
+}
+
+impl Watchlist {
+    pub fn new(name: &str, symbols: Vec<&str>) -> Self {
+        let mut watchlist = Self {
+            name: name.to_string(),
+            symbols: Vec::new(),
+        };
+        watchlist.add_symbols(symbols);
+        watchlist
+    }
+
+    /// Adds symbols to the watchlist, ignoring symbols already in the list
+    pub fn add_symbols(&mut self, symbols: Vec<&str>) {
+        for symbol in symbols {
+            let symbol = symbol.trim().to_uppercase();
+            if !symbol.is_empty() && !self.symbols.contains(&symbol) {
+                self.symbols.push(symbol);
+            }
+        }
+    }
+
+    /// Removes symbols from the watchlist
+    pub fn remove_symbols(&mut self, symbols: Vec<&str>) {
+        let symbols = symbols.iter().map(|x| x.trim().to_uppercase()).collect::<Vec<String>>();
+        self.symbols.retain(|x| !symbols.contains(x));
+    }
+
+    /// Returns a TickersBuilder initialized with the watchlist symbols
+    pub fn tickers_builder(&self) -> TickersBuilder {
+        let mut builder = TickersBuilder::new();
+        builder.tickers(self.symbols.iter().map(|x| x.as_str()).collect());
+        builder
+    }
+
+    /// Returns a PortfolioBuilder initialized with the watchlist symbols
+    pub fn portfolio_builder(&self) -> PortfolioBuilder {
+        let mut builder = PortfolioBuilder::new();
+        builder.ticker_symbols(self.symbols.iter().map(|x| x.as_str()).collect());
+        builder
+    }
+}
+
+
+/// # WatchlistStore Struct
+///
+/// ### Description
+///    - Saves named watchlists to a JSON file so they can be shared across sessions and applications.
+///
+/// ### Example
+///
+/// ```rust
+/// use std::error::Error;
+/// use finalytics::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let mut store = WatchlistStore::open(std::env::temp_dir().join("watchlists.json"))?;
+///     let _ = store.delete("tech");
+///     store.create("tech", vec!["AAPL", "MSFT", "NVDA"])?;
+///     store.add_symbols("tech", vec!["GOOG"])?;
+///
+///     let tickers = store.get("tech")?.tickers_builder()
+///         .start_date("2023-01-01")
+///         .end_date("2023-12-31")
+///         .build();
+///     tickers.report(Some(ReportType::Performance)).await?.show()?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WatchlistStore {
+    pub path: PathBuf,
+    pub watchlists: BTreeMap<String, Watchlist>,
+}
+
+impl WatchlistStore {
+    /// Opens the watchlist store at the given path, starting empty if the file does not exist
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the JSON file holding the watchlists
+    ///
+    /// # Returns
+    ///
+    /// * `WatchlistStore` struct
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref().to_path_buf();
+        let watchlists = if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            let lists: Vec<Watchlist> = serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse watchlists from {}: {}", path.display(), e))?;
+            lists.into_iter().map(|w| (w.name.clone(), w)).collect()
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, watchlists })
+    }
+
+    /// Writes the watchlists to disk
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let lists = self.watchlists.values().collect::<Vec<&Watchlist>>();
+        fs::write(&self.path, serde_json::to_string_pretty(&lists)?)?;
+        Ok(())
+    }
+
+    /// Returns the names of all saved watchlists
+    pub fn names(&self) -> Vec<String> {
+        self.watchlists.keys().cloned().collect()
+    }
+
+    /// Returns a saved watchlist
+    pub fn get(&self, name: &str) -> Result<&Watchlist, Box<dyn Error>> {
+        self.watchlists.get(name).ok_or(format!("Watchlist {} not found", name).into())
+    }
+
+    /// Creates a new watchlist and saves it
+    pub fn create(&mut self, name: &str, symbols: Vec<&str>) -> Result<(), Box<dyn Error>> {
+        if self.watchlists.contains_key(name) {
+            return Err(format!("Watchlist {} already exists", name).into());
+        }
+        self.watchlists.insert(name.to_string(), Watchlist::new(name, symbols));
+        self.save()
+    }
+
+    /// Adds symbols to a saved watchlist
+    pub fn add_symbols(&mut self, name: &str, symbols: Vec<&str>) -> Result<(), Box<dyn Error>> {
+        let watchlist = self.watchlists.get_mut(name).ok_or(format!("Watchlist {} not found", name))?;
+        watchlist.add_symbols(symbols);
+        self.save()
+    }
+
+    /// Removes symbols from a saved watchlist
+    pub fn remove_symbols(&mut self, name: &str, symbols: Vec<&str>) -> Result<(), Box<dyn Error>> {
+        let watchlist = self.watchlists.get_mut(name).ok_or(format!("Watchlist {} not found", name))?;
+        watchlist.remove_symbols(symbols);
+        self.save()
+    }
+
+    /// Renames a saved watchlist
+    pub fn rename(&mut self, name: &str, new_name: &str) -> Result<(), Box<dyn Error>> {
+        if self.watchlists.contains_key(new_name) {
+            return Err(format!("Watchlist {} already exists", new_name).into());
+        }
+        let mut watchlist = self.watchlists.remove(name).ok_or(format!("Watchlist {} not found", name))?;
+        watchlist.name = new_name.to_string();
+        self.watchlists.insert(new_name.to_string(), watchlist);
+        self.save()
+    }
+
+    /// Deletes a saved watchlist
+    pub fn delete(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        self.watchlists.remove(name).ok_or(format!("Watchlist {} not found", name))?;
+        self.save()
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+
+/// Translations of the report section titles and statistic labels
+///
+/// Labels without a translation are rendered unchanged, so a partial map only overrides the labels it contains.
+///
+/// * `locale` - BCP 47 language of the translations, set as the language of the report (default "en")
+/// * `labels` - English label to translated label
+///
+/// # Example
+///
+/// ```rust
+/// use finalytics::prelude::*;
+///
+/// let translations = ReportTranslations::from_locale("de")
+///     .label("Naive Benchmarks", "Einfache Vergleichsportfolios");
+/// assert_eq!(translations.translate("Sharpe Ratio"), "Sharpe-Ratio");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportTranslations {
+    pub locale: String,
+    pub labels: HashMap<String, String>,
+}
+
+impl Default for ReportTranslations {
+    fn default() -> Self {
+        ReportTranslations::new("en")
+    }
+}
+
+impl ReportTranslations {
+    pub fn new(locale: &str) -> ReportTranslations {
+        ReportTranslations { locale: locale.to_string(), labels: HashMap::new() }
+    }
+
+    /// Built-in translations of a locale
+    ///
+    /// # Arguments
+    ///
+    /// * `locale` - BCP 47 locale, only the language is used (de, es and fr have built-in translations,
+    ///   other languages start from an empty map)
+    ///
+    /// # Returns
+    ///
+    /// * `ReportTranslations` - Translations of the locale
+    pub fn from_locale(locale: &str) -> ReportTranslations {
+        let language = locale.split(['-', '_']).next().unwrap_or_default().to_lowercase();
+        let index = match language.as_str() {
+            "de" => Some(0),
+            "es" => Some(1),
+            "fr" => Some(2),
+            _ => None,
+        };
+        let labels = match index {
+            Some(i) => BUILTIN_LABELS.iter().map(|(en, t)| (en.to_string(), t[i].to_string())).collect(),
+            None => HashMap::new(),
+        };
+        ReportTranslations { locale: locale.to_string(), labels }
+    }
+
+    /// Adds or overrides the translation of a label
+    pub fn label(mut self, label: &str, translation: &str) -> ReportTranslations {
+        self.labels.insert(label.to_string(), translation.to_string());
+        self
+    }
+
+    /// Adds or overrides the translations of several labels
+    pub fn labels(mut self, labels: HashMap<String, String>) -> ReportTranslations {
+        self.labels.extend(labels);
+        self
+    }
+
+    /// Translates a label, labels without a translation are returned unchanged
+    pub fn translate(&self, label: &str) -> String {
+        self.labels.get(label).cloned().unwrap_or_else(|| label.to_string())
+    }
+
+    /// Serializes the labels to the JavaScript object read by the table renderers
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string(&self.labels)?)
+    }
+}
+
+/// Built-in translations in German, Spanish and French
+static BUILTIN_LABELS: &[(&str, [&str; 3])] = &[
+    // Report sections
+    ("Price Data", ["Kursdaten", "Datos de Precios", "Données de Prix"]),
+    ("Candlestick Chart", ["Kerzendiagramm", "Gráfico de Velas", "Graphique en Chandeliers"]),
+    ("Performance Chart", ["Performance-Diagramm", "Gráfico de Rendimiento", "Graphique de Performance"]),
+    ("Performance Stats", ["Performance-Kennzahlen", "Estadísticas de Rendimiento", "Statistiques de Performance"]),
+    ("Optimization Chart", ["Optimierungsdiagramm", "Gráfico de Optimización", "Graphique d'Optimisation"]),
+    ("Optimization Details", ["Optimierungsdetails", "Detalles de Optimización", "Détails de l'Optimisation"]),
+    ("Returns Data", ["Renditedaten", "Datos de Rentabilidad", "Données de Rendement"]),
+    ("Returns Chart", ["Renditediagramm", "Gráfico de Rentabilidad", "Graphique des Rendements"]),
+    ("Returns Matrix", ["Renditematrix", "Matriz de Rentabilidad", "Matrice des Rendements"]),
+    ("Naive Benchmarks", ["Naive Vergleichsportfolios", "Carteras de Referencia Simples", "Portefeuilles de Référence Naïfs"]),
+    ("Quarterly Income Statement", ["Quartals-Gewinn- und Verlustrechnung", "Cuenta de Resultados Trimestral", "Compte de Résultat Trimestriel"]),
+    ("Annual Income Statement", ["Jährliche Gewinn- und Verlustrechnung", "Cuenta de Resultados Anual", "Compte de Résultat Annuel"]),
+    ("Quarterly Balance Sheet", ["Quartalsbilanz", "Balance Trimestral", "Bilan Trimestriel"]),
+    ("Annual Balance Sheet", ["Jahresbilanz", "Balance Anual", "Bilan Annuel"]),
+    ("Quarterly Cash Flow Statement", ["Quartals-Kapitalflussrechnung", "Flujo de Caja Trimestral", "Tableau des Flux de Trésorerie Trimestriel"]),
+    ("Annual Cash Flow Statement", ["Jährliche Kapitalflussrechnung", "Flujo de Caja Anual", "Tableau des Flux de Trésorerie Annuel"]),
+    ("Quarterly Financial Ratios", ["Quartalskennzahlen", "Ratios Financieros Trimestrales", "Ratios Financiers Trimestriels"]),
+    ("Annual Financial Ratios", ["Jahreskennzahlen", "Ratios Financieros Anuales", "Ratios Financiers Annuels"]),
+    ("Options Chain", ["Optionskette", "Cadena de Opciones", "Chaîne d'Options"]),
+    ("Volatility Smile", ["Volatilitätslächeln", "Sonrisa de Volatilidad", "Smile de Volatilité"]),
+    ("Volatility Term Structure", ["Laufzeitstruktur der Volatilität", "Estructura Temporal de Volatilidad", "Structure par Terme de la Volatilité"]),
+    ("Volatility Surface Data", ["Volatilitätsflächen-Daten", "Datos de Superficie de Volatilidad", "Données de Surface de Volatilité"]),
+    ("Volatility Surface Chart", ["Volatilitätsfläche", "Superficie de Volatilidad", "Surface de Volatilité"]),
+    ("News Sentiment Data", ["Nachrichtenstimmung", "Datos de Sentimiento de Noticias", "Données de Sentiment des Actualités"]),
+    ("News Sentiment Chart", ["Stimmungsdiagramm", "Gráfico de Sentimiento de Noticias", "Graphique de Sentiment des Actualités"]),
+    // Table headers
+    ("Items", ["Kennzahl", "Indicador", "Indicateur"]),
+    ("Values", ["Wert", "Valor", "Valeur"]),
+    // Statistic labels
+    ("Daily Return", ["Tägliche Rendite", "Rentabilidad Diaria", "Rendement Quotidien"]),
+    ("Daily Volatility", ["Tägliche Volatilität", "Volatilidad Diaria", "Volatilité Quotidienne"]),
+    ("Cumulative Return", ["Kumulierte Rendite", "Rentabilidad Acumulada", "Rendement Cumulé"]),
+    ("Annualized Return", ["Annualisierte Rendite", "Rentabilidad Anualizada", "Rendement Annualisé"]),
+    ("Annualized Volatility", ["Annualisierte Volatilität", "Volatilidad Anualizada", "Volatilité Annualisée"]),
+    ("Alpha", ["Alpha", "Alfa", "Alpha"]),
+    ("Beta", ["Beta", "Beta", "Bêta"]),
+    ("Sharpe Ratio", ["Sharpe-Ratio", "Ratio de Sharpe", "Ratio de Sharpe"]),
+    ("Sortino Ratio", ["Sortino-Ratio", "Ratio de Sortino", "Ratio de Sortino"]),
+    ("Active Return", ["Aktive Rendite", "Rentabilidad Activa", "Rendement Actif"]),
+    ("Active Risk", ["Aktives Risiko", "Riesgo Activo", "Risque Actif"]),
+    ("Information Ratio", ["Information-Ratio", "Ratio de Información", "Ratio d'Information"]),
+    ("Calmar Ratio", ["Calmar-Ratio", "Ratio de Calmar", "Ratio de Calmar"]),
+    ("Maximum Drawdown", ["Maximaler Drawdown", "Máxima Caída", "Perte Maximale"]),
+    ("Max Drawdown", ["Maximaler Drawdown", "Máxima Caída", "Perte Maximale"]),
+    ("Value At Risk", ["Value at Risk", "Valor en Riesgo", "Valeur à Risque"]),
+    ("Expected Shortfall", ["Expected Shortfall", "Déficit Esperado", "Perte Attendue"]),
+];
@@ -1,4 +1,5 @@
 pub mod tabs;
 pub mod report;
 
-pub mod table;
\ No newline at end of file
+pub mod table;
+pub mod i18n;
\ No newline at end of file
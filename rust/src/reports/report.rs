@@ -1,14 +1,25 @@
 use std::error::Error;
 use crate::reports::table::DataTable;
-use crate::prelude::{TableType, Portfolio, PortfolioCharts, StatementFrequency, Ticker, TickerCharts, TickerData, Tickers, TickersCharts};
+use crate::prelude::{QuoteType, SegmentType, TableType, Portfolio, PortfolioCharts, StatementFrequency, StatementType, StatementView, Ticker, TickerCharts, TickerData, Tickers, TickersCharts};
 use crate::reports::tabs::TabbedHtml;
+use crate::analytics::crypto::CryptoDataSource;
+use crate::data::news::{news_keywords, DEFAULT_TOP_KEYWORDS};
+use crate::analytics::forecast::{ForecastModel, Forecasting};
+use crate::charts::strategy::StrategyCharts;
+use crate::analytics::options::OptionsAnalytics;
+use crate::analytics::fundamentals::Financials;
+use crate::analytics::performance::TickerPerformance;
+use crate::analytics::sessions::SessionAnalytics;
 
 #[derive(Debug, Clone, Copy)]
 pub enum ReportType {
     Performance,
     Financials,
     Options,
-    News
+    News,
+    Forecast,
+    Execution,
+    Strategy
 }
 
 impl ReportType {
@@ -18,6 +29,9 @@ impl ReportType {
             "financials" => ReportType::Financials,
             "options" => ReportType::Options,
             "news" => ReportType::News,
+            "forecast" => ReportType::Forecast,
+            "execution" => ReportType::Execution,
+            "strategy" => ReportType::Strategy,
             _ => panic!("Invalid Report Type")
         }
     }
@@ -28,6 +42,9 @@ impl ReportType {
             ReportType::Financials => "financials",
             ReportType::Options => "options",
             ReportType::News => "news",
+            ReportType::Forecast => "forecast",
+            ReportType::Execution => "execution",
+            ReportType::Strategy => "strategy",
         }
     }
 }
@@ -37,6 +54,7 @@ pub trait Report {
 }
 
 impl Report for Ticker {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err, fields(symbol = %self.ticker, report_type = ?report_type)))]
     async fn report(&self, report_type: Option<ReportType>) -> Result<TabbedHtml, Box<dyn Error>> {
         let report_type = report_type.unwrap_or(ReportType::Performance);
         let report = match report_type {
@@ -50,12 +68,98 @@ impl Report for Ticker {
                 let performance_chart = self.performance_chart(None, None).await?
                     .to_html().replace("plotly-html-element", "performance_chart");
                 tabs.push(("Performance Chart".to_string(), performance_chart));
+                match (self.performance_decomposition_chart(None, None).await, self.performance_decomposition_table().await) {
+                    (Ok(chart), Ok(table)) => {
+                        let decomposition = format!("{}{}",
+                                                    chart.to_html().replace("plotly-html-element", "performance_decomposition_chart"),
+                                                    table.to_html()?);
+                        tabs.push(("Performance Decomposition".to_string(), decomposition));
+                    }
+                    (Err(e), _) | (_, Err(e)) => eprintln!("Unable to compute the performance decomposition of {}: {}", self.ticker, e),
+                }
                 let performance_stats = self.performance_stats_table().await?.to_html()?;
                 tabs.push(("Performance Stats".to_string(), performance_stats));
+                match self.significance_table().await {
+                    Ok(table) => tabs.push(("Statistical Significance".to_string(), table.to_html()?)),
+                    Err(e) => eprintln!("Unable to compute the statistical significance of {}: {}", self.ticker, e),
+                }
+                match self.currency_hedged_stats_table().await {
+                    Ok(Some(table)) => tabs.push(("Currency Hedged Stats".to_string(), table.to_html()?)),
+                    Ok(None) => {}
+                    Err(e) => eprintln!("Unable to compute the currency hedged stats of {}: {}", self.ticker, e),
+                }
+                if !self.interval.is_intraday() {
+                    match self.gap_stats(None).await {
+                        Ok(stats) => {
+                            let split = format!("{}{}",
+                                                DataTable::new(stats, TableType::SummaryStats).to_html()?,
+                                                self.overnight_intraday_chart(None, None).await?
+                                                    .to_html().replace("plotly-html-element", "overnight_intraday_chart"));
+                            tabs.push(("Overnight vs Intraday".to_string(), split));
+                        }
+                        Err(e) => eprintln!("Unable to compute the gap stats of {}: {}", self.ticker, e),
+                    }
+                }
+                if self.interval.is_intraday() {
+                    match self.session_summary().await {
+                        Ok(summary) => {
+                            let sessions = format!("{}{}{}",
+                                                   DataTable::new(summary, TableType::SummaryStats).to_html()?,
+                                                   self.time_of_day_chart(None, None).await?
+                                                       .to_html().replace("plotly-html-element", "time_of_day_chart"),
+                                                   self.session_gaps_chart(None, None).await?
+                                                       .to_html().replace("plotly-html-element", "session_gaps_chart"));
+                            tabs.push(("Session Stats".to_string(), sessions));
+                        }
+                        Err(e) => eprintln!("Unable to compute the session stats of {}: {}", self.ticker, e),
+                    }
+                }
                 let report = TabbedHtml::new(report_type, tabs);
                 report
             }
             ReportType::Financials => {
+                // Financial statements only exist for companies, other asset classes get their own sections
+                let quote_type = self.get_quote_type().await.unwrap_or(QuoteType::Equity);
+                match quote_type {
+                    QuoteType::Equity => {}
+                    QuoteType::Etf | QuoteType::MutualFund => {
+                        let tabs: Vec<(String, String)> = vec![
+                            ("Fund Profile".to_string(), self.asset_profile_table().await?.to_html()?),
+                            ("Top Holdings".to_string(), self.fund_holdings_table().await?.to_html()?),
+                            ("Sector Weightings".to_string(), self.fund_sectors_chart(None, None).await?
+                                .to_html().replace("plotly-html-element", "fund_sectors_chart")),
+                        ];
+                        return Ok(TabbedHtml::new(report_type, tabs));
+                    }
+                    QuoteType::Cryptocurrency => {
+                        let mut tabs: Vec<(String, String)> = vec![
+                            ("Supply Metrics".to_string(), self.asset_profile_table().await?.to_html()?),
+                        ];
+                        match self.crypto_supply_chart(CryptoDataSource::Yahoo, None, None).await {
+                            Ok(chart) => tabs.push(("Supply Chart".to_string(),
+                                chart.to_html().replace("plotly-html-element", "crypto_supply_chart"))),
+                            Err(e) => eprintln!("Unable to generate the supply chart for {}: {}", self.ticker, e),
+                        }
+                        match self.crypto_dominance_chart(None, CryptoDataSource::Yahoo, None, None).await {
+                            Ok(chart) => tabs.push(("Market Dominance".to_string(),
+                                chart.to_html().replace("plotly-html-element", "crypto_dominance_chart"))),
+                            Err(e) => eprintln!("Unable to generate the market dominance chart for {}: {}", self.ticker, e),
+                        }
+                        return Ok(TabbedHtml::new(report_type, tabs));
+                    }
+                    QuoteType::Future => {
+                        let tabs: Vec<(String, String)> = vec![
+                            ("Contract Specs".to_string(), self.asset_profile_table().await?.to_html()?),
+                        ];
+                        return Ok(TabbedHtml::new(report_type, tabs));
+                    }
+                    _ => {
+                        let tabs: Vec<(String, String)> = vec![
+                            ("Profile".to_string(), self.asset_profile_table().await?.to_html()?),
+                        ];
+                        return Ok(TabbedHtml::new(report_type, tabs));
+                    }
+                }
                 let annual_financials = self.financials_tables(StatementFrequency::Annual).await?;
                 let quarterly_financials = self.financials_tables(StatementFrequency::Quarterly).await?;
                 let tabs: Vec<(String, String)> = vec![
@@ -67,7 +171,54 @@ impl Report for Ticker {
                     ("Annual Cash Flow Statement".to_string(), annual_financials.cashflow_statement.to_html()?),
                     ("Quarterly Financial Ratios".to_string(), quarterly_financials.financial_ratios.to_html()?),
                     ("Annual Financial Ratios".to_string(), annual_financials.financial_ratios.to_html()?),
+                    ("Income Waterfall Chart".to_string(), self.income_waterfall_chart(StatementFrequency::Annual, None, None).await?
+                        .to_html().replace("plotly-html-element", "income_waterfall_chart")),
+                    ("Income Trend Chart".to_string(), self.income_trend_chart(StatementFrequency::Quarterly, None, None).await?
+                        .to_html().replace("plotly-html-element", "income_trend_chart")),
                 ];
+                let mut tabs = tabs;
+                match self.per_share_fundamentals(StatementFrequency::Annual).await {
+                    Ok(per_share) => {
+                        tabs.push(("Per Share Fundamentals".to_string(), DataTable::new(per_share, TableType::AnnualFinancialRatios).to_html()?));
+                        tabs.push(("Per Share Chart".to_string(), self.per_share_chart(StatementFrequency::Annual, None, None).await?
+                            .to_html().replace("plotly-html-element", "per_share_chart")));
+                    }
+                    Err(e) => eprintln!("Unable to compute the per share fundamentals of {}: {}", self.ticker, e),
+                }
+                for (title, statement_type, view) in [
+                    ("Common Size Income Statement", StatementType::IncomeStatement, StatementView::CommonSize),
+                    ("Common Size Balance Sheet", StatementType::BalanceSheet, StatementView::CommonSize),
+                    ("Income Statement YoY", StatementType::IncomeStatement, StatementView::YearOverYear),
+                    ("Balance Sheet YoY", StatementType::BalanceSheet, StatementView::YearOverYear),
+                ] {
+                    match self.statement_view(statement_type, StatementFrequency::Annual, view).await {
+                        Ok(statement) => tabs.push((title.to_string(), DataTable::new(statement, TableType::AnnualFinancialRatios).to_html()?)),
+                        Err(e) => eprintln!("Unable to compute the {} of {}: {}", title.to_lowercase(), self.ticker, e),
+                    }
+                }
+                for segment_type in [SegmentType::Business, SegmentType::Geographic] {
+                    let title = match segment_type {
+                        SegmentType::Business => "Business Segments",
+                        SegmentType::Geographic => "Geographic Segments",
+                    };
+                    match self.revenue_segments(segment_type).await {
+                        Ok(segments) => {
+                            tabs.push((title.to_string(), DataTable::new(segments, TableType::AnnualIncomeStatement).to_html()?));
+                            tabs.push((format!("{} Chart", title), self.revenue_segments_chart(segment_type, None, None).await?
+                                .to_html().replace("plotly-html-element", &format!("{}_segments_chart", segment_type.to_string()))));
+                        }
+                        Err(e) => eprintln!("Unable to fetch the {} segments of {}: {}", segment_type.to_string(), self.ticker, e),
+                    }
+                }
+                match (self.get_earnings_trend().await, self.estimate_revisions().await) {
+                    (Ok(trend), Ok(revisions)) => {
+                        tabs.push(("Estimates".to_string(), DataTable::new(trend, TableType::Estimates).to_html()?));
+                        tabs.push(("Estimate Revisions".to_string(), DataTable::new(revisions, TableType::Estimates).to_html()?));
+                        tabs.push(("Estimate Trend Chart".to_string(), self.estimate_trend_chart(None, None).await?
+                            .to_html().replace("plotly-html-element", "estimate_trend_chart")));
+                    }
+                    (Err(e), _) | (_, Err(e)) => eprintln!("Unable to fetch the analyst estimates of {}: {}", self.ticker, e),
+                }
                 let report = TabbedHtml::new(report_type, tabs);
                 report
             }
@@ -81,28 +232,94 @@ impl Report for Ticker {
                     ("Volatility Term Structure".to_string(), options_charts.volatility_term_structure.to_html().replace("plotly-html-element", "volatility_term_structure")),
                     ("Volatility Surface Chart".to_string(), options_charts.volatility_surface.to_html().replace("plotly-html-element", "volatility_surface")),
                 ];
+                let mut tabs = tabs;
+                match self.expected_move().await {
+                    Ok(moves) => {
+                        tabs.push(("Expected Move".to_string(), DataTable::new(moves, TableType::OptionsAnalytics).to_html()?));
+                        tabs.push(("Expected Move Chart".to_string(), self.expected_move_chart(None, None).await?
+                            .to_html().replace("plotly-html-element", "expected_move_chart")));
+                    }
+                    Err(e) => eprintln!("Unable to compute the expected move for {}: {}", self.ticker, e),
+                }
+                match self.put_call_ratios().await {
+                    Ok(ratios) => {
+                        tabs.push(("Put/Call Ratios".to_string(), DataTable::new(ratios, TableType::OptionsAnalytics).to_html()?));
+                        tabs.push(("Put/Call Sentiment".to_string(), self.put_call_sentiment_chart(None, None).await?
+                            .to_html().replace("plotly-html-element", "put_call_sentiment_chart")));
+                        tabs.push(("Open Interest Chart".to_string(), self.open_interest_chart(None, None, None).await?
+                            .to_html().replace("plotly-html-element", "open_interest_chart")));
+                    }
+                    Err(e) => eprintln!("Unable to compute the put/call ratios for {}: {}", self.ticker, e),
+                }
+                match self.max_pain().await {
+                    Ok(pain) => {
+                        tabs.push(("Max Pain".to_string(), DataTable::new(pain, TableType::OptionsAnalytics).to_html()?));
+                        tabs.push(("Max Pain Chart".to_string(), self.max_pain_chart(None, None).await?
+                            .to_html().replace("plotly-html-element", "max_pain_chart")));
+                    }
+                    Err(e) => eprintln!("Unable to compute the max pain for {}: {}", self.ticker, e),
+                }
+                match self.volatility_risk_premium().await {
+                    Ok(premium) => {
+                        tabs.push(("Implied vs Realized Volatility".to_string(), DataTable::new(premium, TableType::OptionsAnalytics).to_html()?));
+                        tabs.push(("Volatility Risk Premium Chart".to_string(), self.volatility_comparison_chart(None, None).await?
+                            .to_html().replace("plotly-html-element", "volatility_comparison_chart")));
+                    }
+                    Err(e) => eprintln!("Unable to compare the implied and realized volatility of {}: {}", self.ticker, e),
+                }
                 let report = TabbedHtml::new(report_type, tabs);
                 report
             },
             ReportType::News => {
                 let mut tabs: Vec<(String, String)> = Vec::new();
                 let mut news = self.get_news().await?;
+                let keywords = news_keywords(&news, &[&self.ticker.to_lowercase()], DEFAULT_TOP_KEYWORDS)
+                    .map_err(|e| eprintln!("Error extracting news topics: {}", e)).ok();
                 let _ = news.drop_in_place("Title")?;
+                let _ = news.drop_in_place("URL")?;
                 news.rename("Link", "Title")?;
                 let news_table = DataTable::new(news.into(), TableType::NewsSentiment).to_html()?;
                 tabs.push(("News Sentiment Data".to_string(), news_table));
                 let news_chart = self.news_sentiment_chart(None, None).await?
                     .to_html().replace("plotly-html-element", "news_chart");
                 tabs.push(("News Sentiment Chart".to_string(), news_chart));
+                if let Some(keywords) = keywords {
+                    let topics_table = DataTable::new(keywords, TableType::NewsTopics).to_html()?;
+                    tabs.push(("News Topics".to_string(), topics_table));
+                }
+                match self.news_keywords_chart(None, None, None).await {
+                    Ok(chart) => tabs.push(("Keyword Frequency Chart".to_string(),
+                                            chart.to_html().replace("plotly-html-element", "news_keywords_chart"))),
+                    Err(e) => eprintln!("Error generating keyword frequency chart: {}", e),
+                }
                 let report = TabbedHtml::new(report_type, tabs);
                 report
             }
+            ReportType::Forecast => {
+                let mut tabs: Vec<(String, String)> = Vec::new();
+                for (name, model) in [("ARIMA", ForecastModel::AutoArima), ("ETS", ForecastModel::Ets)] {
+                    match self.forecast(model, None).await {
+                        Ok(forecast) => {
+                            let chart = self.forecast_chart(model, None, None, None).await?
+                                .to_html().replace("plotly-html-element", &format!("{}_forecast_chart", model.to_string()));
+                            tabs.push((format!("{} Forecast Chart", name), chart));
+                            let table = DataTable::new(forecast.forecast, TableType::Forecast).to_html()?;
+                            tabs.push((format!("{} Forecast Data", name), table));
+                        }
+                        Err(e) => eprintln!("Error generating {} forecast: {}", name, e),
+                    }
+                }
+                TabbedHtml::new(report_type, tabs)
+            }
+            ReportType::Execution => return Err("Execution Report requires a trade list, use ExecutionAnalytics::execution_report".into()),
+            ReportType::Strategy => return Err("Strategy Report requires strategy variants, use StrategyCharts::strategy_report".into()),
         };
         Ok(report)
     }
 }
 
 impl Report for Portfolio {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err, fields(report_type = ?report_type)))]
     async fn report(&self, report_type: Option<ReportType>) -> Result<TabbedHtml, Box<dyn Error>> {
         let report_type = report_type.unwrap_or(ReportType::Performance);
         let report = match report_type {
@@ -114,8 +331,35 @@ impl Report for Portfolio {
                 let performance_chart = self.performance_chart(None, None)?
                     .to_html().replace("plotly-html-element", "performance_chart");
                 tabs.push(("Performance Chart".to_string(), performance_chart));
+                let decomposition = format!("{}{}",
+                                            self.performance_decomposition_chart(None, None)?
+                                                .to_html().replace("plotly-html-element", "performance_decomposition_chart"),
+                                            self.performance_decomposition_table()?.to_html()?);
+                tabs.push(("Performance Decomposition".to_string(), decomposition));
                 let performance_stats = self.performance_stats_table().await?.to_html()?;
                 tabs.push(("Performance Stats".to_string(), performance_stats));
+                tabs.push(("Statistical Significance".to_string(), self.significance_table()?.to_html()?));
+                let optimization_details = self.optimization_details_table()?.to_html()?;
+                tabs.push(("Optimization Details".to_string(), optimization_details));
+                let naive_benchmarks = self.naive_benchmarks().await?;
+                let naive_benchmarks = format!("{}{}", naive_benchmarks.performance_stats_table()?.to_html()?,
+                                               naive_benchmarks.equity_curves_chart(None, None)?
+                                                   .to_html().replace("plotly-html-element", "naive_benchmarks_chart"));
+                tabs.push(("Naive Benchmarks".to_string(), naive_benchmarks));
+                if let Some(target) = &self.performance_stats.volatility_target {
+                    let comparison = self.performance_stats.volatility_targeting(target)?;
+                    let volatility_targeting = format!("{}{}", comparison.performance_stats_table()?.to_html()?,
+                                                       comparison.equity_curves_chart(None, None)?
+                                                           .to_html().replace("plotly-html-element", "volatility_targeting_chart"));
+                    tabs.push(("Volatility Targeting".to_string(), volatility_targeting));
+                }
+                if let Some(filter) = &self.performance_stats.trend_filter {
+                    let comparison = self.performance_stats.trend_filtering(filter)?;
+                    let trend_filter = format!("{}{}", comparison.performance_stats_table()?.to_html()?,
+                                               comparison.equity_curves_chart(None, None)?
+                                                   .to_html().replace("plotly-html-element", "trend_filter_chart"));
+                    tabs.push(("Trend Filter".to_string(), trend_filter));
+                }
                 let returns_table = self.returns_table()?.to_html()?;
                 tabs.push(("Returns Data".to_string(), returns_table));
                 let returns_chart = self.returns_chart(None, None)?
@@ -134,6 +378,7 @@ impl Report for Portfolio {
 }
 
 impl Report for Tickers {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err, fields(symbols = self.tickers.len(), report_type = ?report_type)))]
     async fn report(&self, report_type: Option<ReportType>) -> Result<TabbedHtml, Box<dyn Error>> {
         let report_type = report_type.unwrap_or(ReportType::Performance);
         let report = match report_type {
@@ -1,9 +1,71 @@
 use std::error::Error;
 use std::fs;
 use chrono::DateTime;
+use rust_xlsxwriter::Workbook;
 use webbrowser;
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use crate::reports::i18n::ReportTranslations;
+
+/// Number and date formatting of a DataTable
+///
+/// Values are formatted in the browser with `Intl.NumberFormat` and `Intl.DateTimeFormat`, so any BCP 47 locale
+/// is supported (e.g. "de-DE" renders 1.234,56 and "fr-FR" renders 1 234,56).
+///
+/// * `locale` - BCP 47 locale of the numbers and dates (default "en-US")
+/// * `currency` - ISO 4217 code of the currency symbol of monetary tables (default "USD")
+/// * `decimals` - Number of decimal places (default 2)
+/// * `localize_dates` - Whether dates are rendered in the locale instead of as YYYY-MM-DD HH:MM:SS (default false)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataTableFormat {
+    pub locale: String,
+    pub currency: String,
+    pub decimals: usize,
+    pub localize_dates: bool,
+}
+
+impl Default for DataTableFormat {
+    fn default() -> Self {
+        DataTableFormat {
+            locale: "en-US".to_string(),
+            currency: "USD".to_string(),
+            decimals: 2,
+            localize_dates: false,
+        }
+    }
+}
+
+impl DataTableFormat {
+    pub fn new() -> DataTableFormat {
+        DataTableFormat::default()
+    }
+
+    pub fn locale(mut self, locale: &str) -> DataTableFormat {
+        self.locale = locale.to_string();
+        self
+    }
+
+    pub fn currency(mut self, currency: &str) -> DataTableFormat {
+        self.currency = currency.to_string();
+        self
+    }
+
+    pub fn decimals(mut self, decimals: usize) -> DataTableFormat {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn localize_dates(mut self, localize_dates: bool) -> DataTableFormat {
+        self.localize_dates = localize_dates;
+        self
+    }
+
+    /// Serializes the format to the JavaScript object read by the table renderers
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
 
 pub enum TableType {
     OHLCV,
@@ -20,7 +82,14 @@ pub enum TableType {
     AnnualFinancialRatios,
     QuarterlyFinancialRatios,
     PerformanceStats,
-    SummaryStats
+    SummaryStats,
+    ExecutionQuality,
+    FundHoldings,
+    NewsTopics,
+    Forecast,
+    StrategyValidation,
+    OptionsAnalytics,
+    Estimates
 }
 
 impl TableType {
@@ -40,7 +109,14 @@ impl TableType {
             TableType::AnnualFinancialRatios => "annualFinancialRatios",
             TableType::QuarterlyFinancialRatios => "quarterlyFinancialRatios",
             TableType::PerformanceStats => "performanceStats",
-            TableType::SummaryStats => "summaryStats"
+            TableType::SummaryStats => "summaryStats",
+            TableType::ExecutionQuality => "executionQuality",
+            TableType::FundHoldings => "fundHoldings",
+            TableType::NewsTopics => "newsTopics",
+            TableType::Forecast => "forecastTable",
+            TableType::StrategyValidation => "strategyValidation",
+            TableType::OptionsAnalytics => "optionsAnalytics",
+            TableType::Estimates => "estimatesTable",
         }
     }
 
@@ -61,6 +137,13 @@ impl TableType {
             TableType::QuarterlyFinancialRatios => false,
             TableType::PerformanceStats => false,
             TableType::SummaryStats => false,
+            TableType::ExecutionQuality => true,
+            TableType::FundHoldings => true,
+            TableType::NewsTopics => true,
+            TableType::Forecast => false,
+            TableType::StrategyValidation => true,
+            TableType::OptionsAnalytics => true,
+            TableType::Estimates => false,
         }
     }
 
@@ -81,6 +164,13 @@ impl TableType {
             TableType::QuarterlyFinancialRatios => NUMBER_FMT.to_string(),
             TableType::PerformanceStats => NO_FMT.to_string(),
             TableType::SummaryStats => NO_FMT.to_string(),
+            TableType::ExecutionQuality => NUMBER_FMT.to_string(),
+            TableType::FundHoldings => NUMBER_FMT.to_string(),
+            TableType::NewsTopics => NUMBER_FMT.to_string(),
+            TableType::Forecast => NUMBER_FMT.to_string(),
+            TableType::StrategyValidation => NUMBER_FMT.to_string(),
+            TableType::OptionsAnalytics => NUMBER_FMT.to_string(),
+            TableType::Estimates => NUMBER_FMT.to_string(),
         }
     }
 }
@@ -88,11 +178,77 @@ impl TableType {
 pub struct DataTable {
     data: DataFrame,
     table_type: TableType,
+    format: DataTableFormat,
+    translations: ReportTranslations,
 }
 
 impl DataTable {
     pub fn new(data: DataFrame, table_type: TableType) -> Self {
-        DataTable { data, table_type }
+        DataTable { data, table_type, format: DataTableFormat::default(), translations: ReportTranslations::default() }
+    }
+
+    /// Sets the number and date formatting of the table
+    ///
+    /// A format set on the report the table is embedded in (see `TabbedHtml::table_format`) takes precedence.
+    pub fn format(mut self, format: DataTableFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the translations of the column titles and text cells (e.g. statistic labels) of the table
+    ///
+    /// Translations set on the report the table is embedded in (see `TabbedHtml::translations`) take precedence.
+    pub fn translations(mut self, translations: ReportTranslations) -> Self {
+        self.translations = translations;
+        self
+    }
+
+    /// Underlying data of the table
+    pub fn data(&self) -> &DataFrame {
+        &self.data
+    }
+
+    /// Writes the underlying data of the table to CSV
+    ///
+    /// # Returns
+    ///
+    /// * `String` - CSV with a header row
+    pub fn to_csv(&self) -> Result<String, Box<dyn Error>> {
+        let mut df = self.data.clone();
+        let mut buffer = Vec::new();
+        CsvWriter::new(&mut buffer).include_header(true).finish(&mut df)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    /// Writes the underlying data of the table to an Excel workbook
+    ///
+    /// Numeric columns are written as numbers and all other columns as text.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - XLSX file with a header row, in a worksheet named after the table
+    pub fn to_xlsx(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(self.table_type.id())?;
+        for (col_idx, series) in self.data.get_columns().iter().enumerate() {
+            let col = col_idx as u16;
+            worksheet.write_string(0, col, series.name())?;
+            if series.dtype().is_numeric() {
+                for (row_idx, value) in series.cast(&DataType::Float64)?.f64()?.into_iter().enumerate() {
+                    if let Some(value) = value.filter(|x| x.is_finite()) {
+                        worksheet.write_number(row_idx as u32 + 1, col, value)?;
+                    }
+                }
+            } else {
+                for (row_idx, value) in series.cast(&DataType::String)?.str()?.into_iter().enumerate() {
+                    if let Some(value) = value {
+                        worksheet.write_string(row_idx as u32 + 1, col, value)?;
+                    }
+                }
+            }
+        }
+        Ok(workbook.save_to_buffer()?)
     }
 
     pub fn to_html(&self) -> Result<String, Box<dyn Error>> {
@@ -109,6 +265,17 @@ impl DataTable {
 
         let column_names = df.get_column_names();
 
+        // Datetime columns are rendered by the date formatter ahead of the table type's column definitions
+        let datetime_targets = columns.iter().enumerate()
+            .filter(|(_, col)| col.get("datatype").and_then(|dt| dt.get("Datetime")).is_some())
+            .map(|(idx, _)| idx.to_string())
+            .collect::<Vec<String>>();
+        let column_defs = format!(
+            r#"[{{ "targets": [{targets}], "render": function(data) {{ return fmtDate(data); }} }}].concat({defs})"#,
+            targets = datetime_targets.join(", "),
+            defs = self.table_type.column_defs()
+        );
+
         let values: Vec<Vec<Value>> = columns
             .iter()
             .filter_map(|col| col.get("values"))
@@ -163,7 +330,7 @@ impl DataTable {
 
         let columns: Vec<String> = column_names
             .iter()
-            .map(|name| format!(r#"{{ title: "{}" }}"#, name))
+            .map(|name| format!(r#"{{ title: translate("{}") }}"#, name))
             .collect();
 
 
@@ -189,9 +356,15 @@ impl DataTable {
 <body>
     <table id="dataTable" class="{id} display nowrap cell-border" style="width:100%"></table>
     <script>
+        // Reports can hold several tables of the same type, so each script initializes the table right before it
+        (function(tableElement) {{
         $(document).ready(function() {{
-            $('table.{id}').DataTable({{
-                data: {ordered_json_data},
+            const tableFormat = Object.assign({table_format}, window.finalyticsTableFormat || {{}});
+            const translations = Object.assign({translations}, window.finalyticsTranslations || {{}});
+            const translate = function(label) {{ return translations[label] || label; }};
+            {formatters}
+            $(tableElement).DataTable({{
+                data: {ordered_json_data}.map(row => row.map(translate)),
                 columns: [{columns}],
                 columnDefs: {column_defs},
                 scrollX: "100%",
@@ -208,6 +381,7 @@ impl DataTable {
                 ]
             }});
         }});
+        }})(document.currentScript ? document.currentScript.previousElementSibling : 'table.{id}');
     </script>
 </body>
 </html>
@@ -215,7 +389,10 @@ impl DataTable {
             ordered_json_data = ordered_json_data,
             ordering = self.table_type.ordering(),
             columns = columns.join(", "),
-            column_defs = self.table_type.column_defs()
+            column_defs = column_defs,
+            table_format = self.format.to_json()?,
+            translations = self.translations.to_json()?,
+            formatters = FORMATTERS
         );
 
         Ok(html)
@@ -231,6 +408,24 @@ impl DataTable {
     }
 }
 
+/// Formatters of the column definitions, reading the `tableFormat` object of the table
+static FORMATTERS: &str = r#"
+            const fmtValue = function(data, options) {
+                const value = Number(data);
+                return isNaN(value) ? data : new Intl.NumberFormat(tableFormat.locale, Object.assign({
+                    minimumFractionDigits: tableFormat.decimals,
+                    maximumFractionDigits: tableFormat.decimals
+                }, options)).format(value);
+            };
+            const fmtNumber = function(data) { return fmtValue(data, {}); };
+            const fmtCurrency = function(data) { return fmtValue(data, { style: 'currency', currency: tableFormat.currency }); };
+            const fmtDate = function(data) {
+                if (!tableFormat.localize_dates || !data) { return data; }
+                const date = new Date(String(data).replace(' ', 'T'));
+                return isNaN(date) ? data : date.toLocaleString(tableFormat.locale);
+            };
+"#;
+
 static NO_FMT: &str = r#"
 [
     {
@@ -248,7 +443,7 @@ static OPTIONS_FMT: &str = r#"
     },
     {
         "targets": "_all",
-        "render": function(data) { return data != null ? $.fn.dataTable.render.number(',', '.', 2).display(data) : ''; },
+        "render": function(data) { return data != null ? fmtNumber(data) : ''; },
     }
 ]
 "#;
@@ -261,7 +456,7 @@ static NEWS_FMT: &str = r#"
     },
     {
         "targets": 3,
-        "render": function(data) { return data != null ? $.fn.dataTable.render.number(',', '.', 2).display(data) : ''; },
+        "render": function(data) { return data != null ? fmtNumber(data) : ''; },
     }
 ]
 "#;
@@ -275,7 +470,7 @@ static NUMBER_FMT: &str = r#"
     },
     {
         "targets": "_all",
-        "render": function(data) { return data != null ? $.fn.dataTable.render.number(',', '.', 2).display(data) : ''; },
+        "render": function(data) { return data != null ? fmtNumber(data) : ''; },
     }
 ]
 "#;
@@ -288,7 +483,7 @@ static CURRENCY_FMT: &str = r#"
     },
     {
         "targets": "_all",
-        "render": function(data) { return data != null ? '$' + $.fn.dataTable.render.number(',', '.', 2).display(data) : ''; },
+        "render": function(data) { return data != null ? fmtCurrency(data) : ''; },
     },
 ]
 "#;
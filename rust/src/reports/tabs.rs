@@ -2,16 +2,34 @@ use std::fs::File;
 use std::io::Write;
 use std::error::Error;
 use crate::prelude::ReportType;
+use crate::reports::table::DataTableFormat;
+use crate::reports::i18n::ReportTranslations;
 
 pub struct TabbedHtml {
     report_type: ReportType,
     tabs: Vec<(String, String)>,
+    table_format: Option<DataTableFormat>,
+    translations: Option<ReportTranslations>,
 }
 
 impl TabbedHtml {
     /// Creates a new TabbedHtml instance.
     pub fn new(report_type: ReportType, tabs: Vec<(String, String)>) -> Self {
-        Self { report_type, tabs }
+        Self { report_type, tabs, table_format: None, translations: None }
+    }
+
+    /// Sets the number and date formatting of every table in the report, e.g. a decimal comma and euro symbol
+    /// with `DataTableFormat::new().locale("de-DE").currency("EUR")`
+    pub fn table_format(mut self, format: DataTableFormat) -> Self {
+        self.table_format = Some(format);
+        self
+    }
+
+    /// Translates the section titles of the report and the column titles and statistic labels of its tables,
+    /// e.g. `ReportTranslations::from_locale("fr")`
+    pub fn translations(mut self, translations: ReportTranslations) -> Self {
+        self.translations = Some(translations);
+        self
     }
 
     /// Generates the HTML as a string.
@@ -26,7 +44,10 @@ impl TabbedHtml {
             tabs.push_str(&format!(
                 r#"<button class="tab-button" onclick="openTab(event, '{id}')">{name}</button>"#,
                 id = tab_id,
-                name = name
+                name = match &self.translations {
+                    Some(translations) => translations.translate(name),
+                    None => name.clone(),
+                }
             ));
 
             // Content
@@ -37,9 +58,21 @@ impl TabbedHtml {
             ));
         }
 
+        let table_format = match &self.table_format {
+            Some(format) => format!("<script>window.finalyticsTableFormat = {};</script>",
+                                    format.to_json().unwrap_or_else(|_| "{}".to_string())),
+            None => String::new(),
+        };
+
+        let (lang, translations) = match &self.translations {
+            Some(translations) => (translations.locale.clone(), format!("<script>window.finalyticsTranslations = {};</script>",
+                                    translations.to_json().unwrap_or_else(|_| "{}".to_string()))),
+            None => ("en".to_string(), String::new()),
+        };
+
         format!(
             r#"<!DOCTYPE html>
-<html lang="en">
+<html lang="{lang}">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
@@ -81,6 +114,8 @@ impl TabbedHtml {
             display: block;
         }}
     </style>
+    {table_format}
+    {translations}
 </head>
 <body>
     <div class="tab-container">
@@ -111,7 +146,10 @@ impl TabbedHtml {
 </body>
 </html>"#,
             tabs = tabs,
-            contents = contents
+            contents = contents,
+            table_format = table_format,
+            translations = translations,
+            lang = lang
         )
     }
 
@@ -1,5 +1,8 @@
+use std::path::{Path, PathBuf};
 use plotly::{Plot, ImageFormat};
 
+use crate::charts::ticker::OptionsCharts;
+
 pub trait PlotImage {
     fn to_png(&self, filename: &str, width: usize, height: usize, scale: f64);
     fn to_svg(&self, filename: &str, width: usize, height: usize, scale: f64);
@@ -11,27 +14,88 @@ pub trait PlotImage {
 
 impl PlotImage for Plot {
     fn to_png(&self, filename: &str, width: usize, height: usize, scale: f64) {
-        self.write_image(filename, ImageFormat::PNG, width, height, scale);
+        write_plot_image(self, filename, ImageFormat::PNG, width, height, scale);
     }
 
     fn to_svg(&self, filename: &str, width: usize, height: usize, scale: f64) {
-        self.write_image(filename, ImageFormat::SVG, width, height, scale);
+        write_plot_image(self, filename, ImageFormat::SVG, width, height, scale);
     }
 
     fn to_jpeg(&self, filename: &str, width: usize, height: usize, scale: f64) {
-        self.write_image(filename, ImageFormat::JPEG, width, height, scale);
+        write_plot_image(self, filename, ImageFormat::JPEG, width, height, scale);
     }
 
     fn to_pdf(&self, filename: &str, width: usize, height: usize, scale: f64) {
-        self.write_image(filename, ImageFormat::PDF, width, height, scale);
+        write_plot_image(self, filename, ImageFormat::PDF, width, height, scale);
     }
 
     fn to_webp(&self, filename: &str, width: usize, height: usize, scale: f64) {
-        self.write_image(filename, ImageFormat::WEBP, width, height, scale);
+        write_plot_image(self, filename, ImageFormat::WEBP, width, height, scale);
     }
 
     fn to_eps(&self, filename: &str, width: usize, height: usize, scale: f64) {
-        self.write_image(filename, ImageFormat::EPS, width, height, scale);
+        write_plot_image(self, filename, ImageFormat::EPS, width, height, scale);
     }
 }
 
+/// Saves each options chart to its own file, suffixing the filename with the chart name
+/// (e.g. "options" -> "options_surface", "options_smile" and "options_term_structure")
+impl PlotImage for OptionsCharts {
+    fn to_png(&self, filename: &str, width: usize, height: usize, scale: f64) {
+        self.charts(filename).iter().for_each(|(f, p)| p.to_png(f, width, height, scale));
+    }
+
+    fn to_svg(&self, filename: &str, width: usize, height: usize, scale: f64) {
+        // Plot has an inherent to_svg returning the SVG string, which takes precedence over the trait method
+        self.charts(filename).iter().for_each(|(f, p)| PlotImage::to_svg(*p, f, width, height, scale));
+    }
+
+    fn to_jpeg(&self, filename: &str, width: usize, height: usize, scale: f64) {
+        self.charts(filename).iter().for_each(|(f, p)| p.to_jpeg(f, width, height, scale));
+    }
+
+    fn to_pdf(&self, filename: &str, width: usize, height: usize, scale: f64) {
+        self.charts(filename).iter().for_each(|(f, p)| p.to_pdf(f, width, height, scale));
+    }
+
+    fn to_webp(&self, filename: &str, width: usize, height: usize, scale: f64) {
+        self.charts(filename).iter().for_each(|(f, p)| p.to_webp(f, width, height, scale));
+    }
+
+    fn to_eps(&self, filename: &str, width: usize, height: usize, scale: f64) {
+        self.charts(filename).iter().for_each(|(f, p)| p.to_eps(f, width, height, scale));
+    }
+}
+
+impl OptionsCharts {
+    fn charts(&self, filename: &str) -> Vec<(String, &Plot)> {
+        vec![
+            (suffixed_filename(filename, "surface"), &self.volatility_surface),
+            (suffixed_filename(filename, "smile"), &self.volatility_smile),
+            (suffixed_filename(filename, "term_structure"), &self.volatility_term_structure),
+        ]
+    }
+}
+
+/// Writes a chart image, creating the parent directory of the file if it does not exist
+fn write_plot_image(plot: &Plot, filename: &str, format: ImageFormat, width: usize, height: usize, scale: f64) {
+    if let Some(parent) = Path::new(filename).parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+    }
+    plot.write_image(filename, format, width, height, scale);
+}
+
+fn suffixed_filename(filename: &str, suffix: &str) -> String {
+    let path = Path::new(filename);
+    let stem = path.file_stem().map(|x| x.to_string_lossy().to_string()).unwrap_or_default();
+    let mut name = PathBuf::from(format!("{}_{}", stem, suffix));
+    if let Some(ext) = path.extension() {
+        name.set_extension(ext);
+    }
+    match path.parent() {
+        Some(parent) => parent.join(name).to_string_lossy().to_string(),
+        None => name.to_string_lossy().to_string(),
+    }
+}
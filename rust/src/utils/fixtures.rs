@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use anyhow::{Result, Context};
+
+
+/// Source of the data returned by the web requests
+///
+/// Live: Fetch data from the web (default)
+/// Record: Fetch data from the web and save each response as a JSON fixture in the directory
+/// Replay: Serve every request from the JSON fixtures in the directory, without network access
+///
+/// The mode can also be set with the `FINALYTICS_DATA_MODE` (live, record or replay)
+/// and `FINALYTICS_FIXTURES_DIR` environment variables.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataMode {
+    Live,
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+impl DataMode {
+    pub fn from_str(mode: &str, dir: &str) -> DataMode {
+        match mode {
+            "record" => DataMode::Record(PathBuf::from(dir)),
+            "replay" => DataMode::Replay(PathBuf::from(dir)),
+            _ => DataMode::Live,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            DataMode::Live => "live".to_string(),
+            DataMode::Record(_) => "record".to_string(),
+            DataMode::Replay(_) => "replay".to_string(),
+        }
+    }
+}
+
+static DATA_MODE: Lazy<RwLock<DataMode>> = Lazy::new(|| {
+    let mode = std::env::var("FINALYTICS_DATA_MODE").unwrap_or_default();
+    let dir = std::env::var("FINALYTICS_FIXTURES_DIR").unwrap_or("fixtures".to_string());
+    RwLock::new(DataMode::from_str(&mode, &dir))
+});
+
+/// Sets the data mode for all subsequent web requests
+///
+/// Responses are only cached in memory in the live mode, so every request of the record mode reaches the
+/// network and every request of the replay mode is served from its fixture, even for urls already fetched
+/// in the same process.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use finalytics::prelude::*;
+/// use finalytics::utils::fixtures::set_data_mode;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Record the responses once ...
+///     set_data_mode(DataMode::Record("fixtures".into()));
+///     let ticker = TickerBuilder::new().ticker("AAPL").start_date("2023-01-01").end_date("2023-12-31").build();
+///     ticker.get_chart().await.unwrap();
+///
+///     // ... and serve them from the fixtures afterwards (e.g. in tests), without network access
+///     set_data_mode(DataMode::Replay("fixtures".into()));
+///     let replayed = ticker.get_chart().await.unwrap();
+///     assert!(replayed.height() > 0);
+/// }
+/// ```
+pub fn set_data_mode(mode: DataMode) {
+    if let Ok(mut guard) = DATA_MODE.write() {
+        *guard = mode;
+    }
+}
+
+/// Returns the current data mode
+pub fn data_mode() -> DataMode {
+    DATA_MODE.read().map(|x| x.clone()).unwrap_or(DataMode::Live)
+}
+
+/// Path of the fixture file for a url, named with a stable (FNV-1a) hash of the url
+pub fn fixture_path(dir: &Path, url: &str) -> PathBuf {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in url.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    dir.join(format!("{:016x}.json", hash))
+}
+
+/// Saves a response as a fixture
+pub(crate) fn save_fixture(dir: &Path, url: &str, response: Value) -> Result<()> {
+    fs::create_dir_all(dir).context("Failed to create fixtures directory")?;
+    let fixture = json!({"url": url, "response": response});
+    fs::write(fixture_path(dir, url), serde_json::to_string_pretty(&fixture)?)
+        .context("Failed to write fixture")?;
+    Ok(())
+}
+
+/// Loads the response of a url from its fixture
+pub(crate) fn load_fixture(dir: &Path, url: &str) -> Result<Value> {
+    let path = fixture_path(dir, url);
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("No recorded fixture for {} ({})", url, path.display()))?;
+    let mut fixture: Value = serde_json::from_str(&content).context("Failed to parse fixture")?;
+    Ok(fixture["response"].take())
+}
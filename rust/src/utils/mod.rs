@@ -1,4 +1,6 @@
 pub mod date_utils;
 #[cfg(feature = "kaleido")]
 pub mod chart_utils;
-pub mod web_utils;
\ No newline at end of file
+pub mod web_utils;
+pub mod synthetic;
+pub mod fixtures;
@@ -0,0 +1,448 @@
+use std::error::Error;
+use chrono::{Datelike, Duration, NaiveDateTime, Weekday};
+use polars::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use ndarray::Array2;
+use rand_distr::{Distribution, LogNormal, Normal, Poisson};
+use crate::data::config::Interval;
+use crate::utils::date_utils::to_datetime;
+
+/// Trading days per year used to scale annualized drift and volatility
+const TRADING_DAYS: f64 = 252.0;
+
+
+/// Creates a random number generator, seeded when a seed is given
+pub(crate) fn rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Length of an interval in years, used as the simulation time step
+pub fn interval_years(interval: Interval) -> f64 {
+    let days = match interval {
+        Interval::TwoMinutes => 2.0 / 390.0,
+        Interval::FiveMinutes => 5.0 / 390.0,
+        Interval::FifteenMinutes => 15.0 / 390.0,
+        Interval::ThirtyMinutes => 30.0 / 390.0,
+        Interval::SixtyMinutes | Interval::OneHour => 60.0 / 390.0,
+        Interval::NinetyMinutes => 90.0 / 390.0,
+        _ => interval.to_days(),
+    };
+    days / TRADING_DAYS
+}
+
+/// Generates weekday timestamps spaced by the interval, starting at the given date
+///
+/// # Arguments
+///
+/// * `start_date` - Start date in the format YYYY-MM-DD
+/// * `periods` - Number of timestamps
+/// * `interval` - Spacing of the timestamps
+///
+/// # Returns
+///
+/// * `Vec<NaiveDateTime>` - Timestamps
+pub fn synthetic_timestamps(start_date: &str, periods: usize, interval: Interval) -> Result<Vec<NaiveDateTime>, Box<dyn Error>> {
+    let step = match interval {
+        Interval::TwoMinutes => Duration::minutes(2),
+        Interval::FiveMinutes => Duration::minutes(5),
+        Interval::FifteenMinutes => Duration::minutes(15),
+        Interval::ThirtyMinutes => Duration::minutes(30),
+        Interval::SixtyMinutes | Interval::OneHour => Duration::hours(1),
+        Interval::NinetyMinutes => Duration::minutes(90),
+        Interval::OneDay => Duration::days(1),
+        Interval::FiveDays | Interval::OneWeek => Duration::weeks(1),
+        Interval::OneMonth => Duration::days(30),
+        Interval::ThreeMonths => Duration::days(91),
+    };
+    let mut timestamps = Vec::with_capacity(periods);
+    let mut current = to_datetime(start_date)?;
+    while timestamps.len() < periods {
+        if !matches!(current.weekday(), Weekday::Sat | Weekday::Sun) {
+            timestamps.push(current);
+        }
+        current += step;
+    }
+    Ok(timestamps)
+}
+
+/// Simulates a Geometric Brownian Motion price path
+///
+/// # Arguments
+///
+/// * `s0` - Initial price
+/// * `mu` - Annualized drift in decimal (e.g. 0.08 for 8%)
+/// * `sigma` - Annualized volatility in decimal (e.g. 0.2 for 20%)
+/// * `periods` - Number of prices to generate (including the initial price)
+/// * `interval` - Interval between prices
+/// * `seed` - Optional random seed for reproducible paths
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Simulated prices
+pub fn gbm_prices(s0: f64, mu: f64, sigma: f64, periods: usize, interval: Interval, seed: Option<u64>) -> Vec<f64> {
+    let dt = interval_years(interval);
+    let mut rng = rng(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut prices = Vec::with_capacity(periods);
+    let mut price = s0;
+    for i in 0..periods {
+        if i > 0 {
+            let z: f64 = normal.sample(&mut rng);
+            price *= ((mu - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * z).exp();
+        }
+        prices.push(price);
+    }
+    prices
+}
+
+/// Builds OHLCV bars around a simulated close price path
+///
+/// Each bar opens at the previous close, the high and low extend beyond the open and close by a random
+/// fraction of the bar volatility and the volume is drawn from a log-normal distribution.
+///
+/// # Arguments
+///
+/// * `start_date` - Start date in the format YYYY-MM-DD
+/// * `closes` - Simulated close prices
+/// * `sigma` - Annualized volatility in decimal, used to size the intrabar range
+/// * `interval` - Interval of the bars
+/// * `seed` - Optional random seed for reproducible bars
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns timestamp, open, high, low, close, volume and adjclose
+pub fn ohlcv_from_closes(start_date: &str, closes: &[f64], sigma: f64, interval: Interval, seed: Option<u64>) -> Result<DataFrame, Box<dyn Error>> {
+    let timestamps = synthetic_timestamps(start_date, closes.len(), interval)?;
+    let bar_volatility = sigma * interval_years(interval).sqrt();
+    let mut rng = rng(seed.map(|x| x.wrapping_add(1)));
+    let volume_dist = LogNormal::<f64>::new(13.0, 0.5)?;
+    let (mut open, mut high, mut low, mut volume) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for i in 0..closes.len() {
+        let o = if i == 0 { closes[0] } else { closes[i - 1] };
+        let c = closes[i];
+        open.push(o);
+        high.push(o.max(c) * (1.0 + rng.gen_range(0.0..1.0) * bar_volatility * 0.5));
+        low.push(o.min(c) * (1.0 - rng.gen_range(0.0..1.0) * bar_volatility * 0.5));
+        volume.push(volume_dist.sample(&mut rng).round());
+    }
+    let df = df!(
+        "timestamp" => &timestamps,
+        "open" => &open,
+        "high" => &high,
+        "low" => &low,
+        "close" => closes,
+        "volume" => &volume,
+        "adjclose" => closes
+    )?;
+    Ok(df)
+}
+
+/// Generates a synthetic OHLCV DataFrame following a Geometric Brownian Motion
+///
+/// # Arguments
+///
+/// * `start_date` - Start date in the format YYYY-MM-DD
+/// * `periods` - Number of bars
+/// * `interval` - Interval of the bars
+/// * `s0` - Initial price
+/// * `mu` - Annualized drift in decimal (e.g. 0.08 for 8%)
+/// * `sigma` - Annualized volatility in decimal (e.g. 0.2 for 20%)
+/// * `seed` - Optional random seed for reproducible data
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns timestamp, open, high, low, close, volume and adjclose
+///
+/// # Example
+///
+/// ```rust
+/// use finalytics::prelude::*;
+/// use finalytics::utils::synthetic::gbm_ohlcv;
+///
+/// let df = gbm_ohlcv("2023-01-01", 252, Interval::OneDay, 100.0, 0.08, 0.2, Some(42)).unwrap();
+/// let weekly = df.resample(Interval::OneWeek).unwrap();
+/// println!("{:?}", weekly);
+/// ```
+pub fn gbm_ohlcv(
+    start_date: &str,
+    periods: usize,
+    interval: Interval,
+    s0: f64,
+    mu: f64,
+    sigma: f64,
+    seed: Option<u64>,
+) -> Result<DataFrame, Box<dyn Error>> {
+    let closes = gbm_prices(s0, mu, sigma, periods, interval, seed);
+    ohlcv_from_closes(start_date, &closes, sigma, interval, seed)
+}
+
+
+/// Parameters of the Heston stochastic volatility model
+///
+/// * `v0` - Initial variance (e.g. 0.04 for 20% volatility)
+/// * `kappa` - Speed of mean reversion of the variance
+/// * `theta` - Long run variance
+/// * `xi` - Volatility of the variance
+/// * `rho` - Correlation between the price and variance shocks
+#[derive(Debug, Clone, Copy)]
+pub struct HestonParams {
+    pub v0: f64,
+    pub kappa: f64,
+    pub theta: f64,
+    pub xi: f64,
+    pub rho: f64,
+}
+
+/// Parameters of the Merton jump-diffusion model
+///
+/// * `intensity` - Expected number of jumps per year
+/// * `mean` - Mean of the log jump size (e.g. -0.05 for 5% average down jumps)
+/// * `std_dev` - Standard deviation of the log jump size
+#[derive(Debug, Clone, Copy)]
+pub struct JumpParams {
+    pub intensity: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+/// Simulates a Heston stochastic volatility price path (full truncation Euler scheme)
+///
+/// # Arguments
+///
+/// * `s0` - Initial price
+/// * `mu` - Annualized drift in decimal
+/// * `params` - Heston model parameters
+/// * `periods` - Number of prices to generate (including the initial price)
+/// * `interval` - Interval between prices
+/// * `seed` - Optional random seed for reproducible paths
+///
+/// # Returns
+///
+/// * `(Vec<f64>, Vec<f64>)` - Simulated prices and annualized volatilities
+pub fn heston_prices(s0: f64, mu: f64, params: HestonParams, periods: usize, interval: Interval, seed: Option<u64>) -> (Vec<f64>, Vec<f64>) {
+    let dt = interval_years(interval);
+    let mut rng = rng(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut prices = Vec::with_capacity(periods);
+    let mut volatilities = Vec::with_capacity(periods);
+    let (mut price, mut variance) = (s0, params.v0);
+    for i in 0..periods {
+        if i > 0 {
+            let z1: f64 = normal.sample(&mut rng);
+            let z2: f64 = params.rho * z1 + (1.0 - params.rho * params.rho).sqrt() * normal.sample(&mut rng);
+            let v = variance.max(0.0);
+            price *= ((mu - 0.5 * v) * dt + (v * dt).sqrt() * z1).exp();
+            variance += params.kappa * (params.theta - v) * dt + params.xi * (v * dt).sqrt() * z2;
+        }
+        prices.push(price);
+        volatilities.push(variance.max(0.0).sqrt());
+    }
+    (prices, volatilities)
+}
+
+/// Simulates a Merton jump-diffusion price path
+///
+/// # Arguments
+///
+/// * `s0` - Initial price
+/// * `mu` - Annualized drift in decimal
+/// * `sigma` - Annualized diffusion volatility in decimal
+/// * `jumps` - Jump parameters
+/// * `periods` - Number of prices to generate (including the initial price)
+/// * `interval` - Interval between prices
+/// * `seed` - Optional random seed for reproducible paths
+///
+/// # Returns
+///
+/// * `Vec<f64>` - Simulated prices
+pub fn jump_diffusion_prices(s0: f64, mu: f64, sigma: f64, jumps: JumpParams, periods: usize, interval: Interval, seed: Option<u64>) -> Vec<f64> {
+    let dt = interval_years(interval);
+    let mut rng = rng(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let jump_size = Normal::new(jumps.mean, jumps.std_dev.max(0.0)).unwrap();
+    let jump_count = Poisson::new((jumps.intensity * dt).max(f64::MIN_POSITIVE)).unwrap();
+    // Compensate the drift so the expected return is unaffected by the jumps
+    let compensator = jumps.intensity * ((jumps.mean + 0.5 * jumps.std_dev * jumps.std_dev).exp() - 1.0);
+    let mut prices = Vec::with_capacity(periods);
+    let mut price = s0;
+    for i in 0..periods {
+        if i > 0 {
+            let z: f64 = normal.sample(&mut rng);
+            let n: f64 = jump_count.sample(&mut rng);
+            let jump = (0..n as usize).map(|_| jump_size.sample(&mut rng)).sum::<f64>();
+            price *= ((mu - compensator - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * z + jump).exp();
+        }
+        prices.push(price);
+    }
+    prices
+}
+
+/// Simulates correlated Geometric Brownian Motion price paths for multiple assets
+///
+/// # Arguments
+///
+/// * `s0` - Initial price of each asset
+/// * `mu` - Annualized drift of each asset in decimal
+/// * `sigma` - Annualized volatility of each asset in decimal
+/// * `correlation` - Correlation matrix of the asset returns (must be positive definite)
+/// * `periods` - Number of prices to generate (including the initial price)
+/// * `interval` - Interval between prices
+/// * `seed` - Optional random seed for reproducible paths
+///
+/// # Returns
+///
+/// * `Vec<Vec<f64>>` - Simulated prices of each asset
+pub fn correlated_gbm_prices(
+    s0: &[f64],
+    mu: &[f64],
+    sigma: &[f64],
+    correlation: &Array2<f64>,
+    periods: usize,
+    interval: Interval,
+    seed: Option<u64>,
+) -> Result<Vec<Vec<f64>>, Box<dyn Error>> {
+    let n = s0.len();
+    if mu.len() != n || sigma.len() != n || correlation.shape() != [n, n] {
+        return Err("s0, mu, sigma and correlation dimensions must match the number of assets".into());
+    }
+    let chol = cholesky(correlation)?;
+    let dt = interval_years(interval);
+    let mut rng = rng(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut prices = s0.iter().map(|x| vec![*x]).collect::<Vec<Vec<f64>>>();
+    for _ in 1..periods {
+        let z = (0..n).map(|_| normal.sample(&mut rng)).collect::<Vec<f64>>();
+        for i in 0..n {
+            let shock = (0..=i).map(|j| chol[[i, j]] * z[j]).sum::<f64>();
+            let last = *prices[i].last().unwrap();
+            prices[i].push(last * ((mu[i] - 0.5 * sigma[i] * sigma[i]) * dt + sigma[i] * dt.sqrt() * shock).exp());
+        }
+    }
+    Ok(prices)
+}
+
+/// Cholesky decomposition of a symmetric positive definite matrix
+fn cholesky(matrix: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+    let n = matrix.nrows();
+    let mut lower = Array2::<f64>::zeros((n, n));
+    for i in 0..n {
+        for j in 0..=i {
+            let sum = (0..j).map(|k| lower[[i, k]] * lower[[j, k]]).sum::<f64>();
+            if i == j {
+                let value = matrix[[i, i]] - sum;
+                if value <= 0.0 {
+                    return Err("correlation matrix is not positive definite".into());
+                }
+                lower[[i, j]] = value.sqrt();
+            } else {
+                lower[[i, j]] = (matrix[[i, j]] - sum) / lower[[j, j]];
+            }
+        }
+    }
+    Ok(lower)
+}
+
+/// Generates a synthetic OHLCV DataFrame following the Heston stochastic volatility model
+///
+/// # Arguments
+///
+/// * `start_date` - Start date in the format YYYY-MM-DD
+/// * `periods` - Number of bars
+/// * `interval` - Interval of the bars
+/// * `s0` - Initial price
+/// * `mu` - Annualized drift in decimal
+/// * `params` - Heston model parameters
+/// * `seed` - Optional random seed for reproducible data
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns timestamp, open, high, low, close, volume and adjclose
+pub fn heston_ohlcv(
+    start_date: &str,
+    periods: usize,
+    interval: Interval,
+    s0: f64,
+    mu: f64,
+    params: HestonParams,
+    seed: Option<u64>,
+) -> Result<DataFrame, Box<dyn Error>> {
+    let (closes, volatilities) = heston_prices(s0, mu, params, periods, interval, seed);
+    let mean_volatility = volatilities.iter().sum::<f64>() / volatilities.len().max(1) as f64;
+    ohlcv_from_closes(start_date, &closes, mean_volatility, interval, seed)
+}
+
+/// Generates a synthetic OHLCV DataFrame following the Merton jump-diffusion model
+///
+/// # Arguments
+///
+/// * `start_date` - Start date in the format YYYY-MM-DD
+/// * `periods` - Number of bars
+/// * `interval` - Interval of the bars
+/// * `s0` - Initial price
+/// * `mu` - Annualized drift in decimal
+/// * `sigma` - Annualized diffusion volatility in decimal
+/// * `jumps` - Jump parameters
+/// * `seed` - Optional random seed for reproducible data
+///
+/// # Returns
+///
+/// * `DataFrame` - with columns timestamp, open, high, low, close, volume and adjclose
+#[allow(clippy::too_many_arguments)]
+pub fn jump_diffusion_ohlcv(
+    start_date: &str,
+    periods: usize,
+    interval: Interval,
+    s0: f64,
+    mu: f64,
+    sigma: f64,
+    jumps: JumpParams,
+    seed: Option<u64>,
+) -> Result<DataFrame, Box<dyn Error>> {
+    let closes = jump_diffusion_prices(s0, mu, sigma, jumps, periods, interval, seed);
+    ohlcv_from_closes(start_date, &closes, sigma, interval, seed)
+}
+
+/// Generates synthetic OHLCV DataFrames for multiple assets with correlated returns
+///
+/// # Arguments
+///
+/// * `symbols` - Symbols of the assets
+/// * `start_date` - Start date in the format YYYY-MM-DD
+/// * `periods` - Number of bars
+/// * `interval` - Interval of the bars
+/// * `s0` - Initial price of each asset
+/// * `mu` - Annualized drift of each asset in decimal
+/// * `sigma` - Annualized volatility of each asset in decimal
+/// * `correlation` - Correlation matrix of the asset returns
+/// * `seed` - Optional random seed for reproducible data
+///
+/// # Returns
+///
+/// * `Vec<(String, DataFrame)>` - OHLCV DataFrame of each symbol
+#[allow(clippy::too_many_arguments)]
+pub fn correlated_ohlcv(
+    symbols: &[&str],
+    start_date: &str,
+    periods: usize,
+    interval: Interval,
+    s0: &[f64],
+    mu: &[f64],
+    sigma: &[f64],
+    correlation: &Array2<f64>,
+    seed: Option<u64>,
+) -> Result<Vec<(String, DataFrame)>, Box<dyn Error>> {
+    if symbols.len() != s0.len() {
+        return Err("number of symbols must match the number of assets".into());
+    }
+    let paths = correlated_gbm_prices(s0, mu, sigma, correlation, periods, interval, seed)?;
+    let mut result = Vec::with_capacity(symbols.len());
+    for (i, closes) in paths.iter().enumerate() {
+        let df = ohlcv_from_closes(start_date, closes, sigma[i], interval, seed.map(|x| x.wrapping_add(i as u64 + 1)))?;
+        result.push((symbols[i].to_string(), df));
+    }
+    Ok(result)
+}
@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::time::{Duration, Instant};
 use chrono::{NaiveDate, NaiveDateTime};
 use once_cell::sync::Lazy;
 use polars::prelude::*;
@@ -6,11 +7,14 @@ use reqwest::{Client, StatusCode};
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
 use select::document::Document;
 use select::predicate::Name;
+use tokio::sync::RwLock;
 use tokio::task::spawn_blocking;
+use cached::Return;
 use cached::proc_macro::cached;
 use serde_json::Value;
 use anyhow::{Result, Context};
 use vader_sentiment::SentimentIntensityAnalyzer;
+use crate::utils::fixtures::{data_mode, load_fixture, save_fixture, DataMode};
 
 
 pub static REQUEST_CLIENT: Lazy<Client> = Lazy::new(|| {
@@ -20,23 +24,213 @@ pub static REQUEST_CLIENT: Lazy<Client> = Lazy::new(|| {
 
     Client::builder()
         .default_headers(headers)
+        .cookie_store(true)
         .build()
         .unwrap()
 });
 
+/// Page that sets the Yahoo Finance session cookie
+const YAHOO_COOKIE_URL: &str = "https://fc.yahoo.com";
+
+/// Endpoint returning the crumb that authenticates requests made with the session cookie
+const YAHOO_CRUMB_URL: &str = "https://query1.finance.yahoo.com/v1/test/getcrumb";
+
+/// Delay before retrying a failed crumb acquisition, doubled after each consecutive failure
+const CRUMB_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Maximum delay before retrying a failed crumb acquisition
+const CRUMB_MAX_RETRY_DELAY: Duration = Duration::from_secs(15 * 60);
+
+/// Crumb of the current Yahoo Finance session, or the last failure to acquire one
+#[derive(Default)]
+struct CrumbState {
+    crumb: Option<String>,
+    failures: u32,
+    retry_at: Option<Instant>,
+    error: String,
+}
+
+/// Crumb state shared by all the requests
+static YAHOO_CRUMB: Lazy<RwLock<CrumbState>> = Lazy::new(|| RwLock::new(CrumbState::default()));
+
+/// Returns the Yahoo Finance crumb, acquiring the session cookie and a new crumb when none is cached
+///
+/// A failed acquisition is cached and not retried before a backoff delay (30 seconds, doubling up to 15 minutes),
+/// so that requests made while Yahoo Finance refuses crumbs do not each pay for two extra round trips.
+///
+/// # Arguments
+///
+/// * `refresh` - Discard the cached crumb and acquire a new one (e.g. after the session expired)
+///
+/// # Returns
+///
+/// * `String` - Crumb to pass as the crumb query parameter
+#[cfg_attr(feature = "tracing", tracing::instrument(err))]
+pub async fn yahoo_crumb(refresh: bool) -> Result<String> {
+    if !refresh {
+        if let Some(crumb) = YAHOO_CRUMB.read().await.crumb.as_ref() {
+            return Ok(crumb.clone());
+        }
+    }
+    let mut state = YAHOO_CRUMB.write().await;
+    if let (false, Some(crumb)) = (refresh, state.crumb.as_ref()) {
+        return Ok(crumb.clone());
+    }
+    if let Some(retry_at) = state.retry_at.filter(|x| *x > Instant::now()) {
+        return Err(anyhow::anyhow!("{} (retrying in {}s)", state.error, retry_at.duration_since(Instant::now()).as_secs()));
+    }
+    match acquire_crumb().await {
+        Ok(crumb) => {
+            *state = CrumbState { crumb: Some(crumb.clone()), ..CrumbState::default() };
+            Ok(crumb)
+        }
+        Err(e) => {
+            let delay = CRUMB_RETRY_DELAY.saturating_mul(2u32.saturating_pow(state.failures)).min(CRUMB_MAX_RETRY_DELAY);
+            state.crumb = None;
+            state.failures += 1;
+            state.retry_at = Some(Instant::now() + delay);
+            state.error = format!("{:#}", e);
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %state.error, failures = state.failures, retry_secs = delay.as_secs(),
+                           "unable to acquire a Yahoo Finance crumb");
+            Err(e)
+        }
+    }
+}
+
+/// Acquires the session cookie and a new crumb
+async fn acquire_crumb() -> Result<String> {
+    // The cookie page responds with an error status but still sets the session cookie
+    let _ = REQUEST_CLIENT.get(YAHOO_COOKIE_URL).send().await.context("Failed to reach the Yahoo Finance cookie page")?;
+    let response = REQUEST_CLIENT.get(YAHOO_CRUMB_URL).send().await.context("Failed to request a Yahoo Finance crumb")?;
+    if response.status() != StatusCode::OK {
+        return Err(anyhow::anyhow!("Yahoo Finance crumb request failed with status: {}", response.status()));
+    }
+    let crumb = response.text().await.context("Failed to read the Yahoo Finance crumb")?.trim().to_string();
+    if crumb.is_empty() || crumb.contains('<') || crumb.contains(' ') {
+        return Err(anyhow::anyhow!("Yahoo Finance returned an invalid crumb: {}", crumb));
+    }
+    Ok(crumb)
+}
+
+/// Adds the crumb query parameter to a Yahoo Finance url
+fn with_crumb(url: &str, crumb: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}crumb={}", url, separator, crumb)
+}
+
+/// Sends a request, authenticating Yahoo Finance requests with the session cookie and crumb
+///
+/// A request rejected as unauthorized is retried once with a refreshed crumb. Other hosts are requested as is.
+async fn send_request(url: &str) -> Result<reqwest::Response> {
+    if !url.contains("finance.yahoo.com") {
+        return REQUEST_CLIENT.get(url).send().await.context("Failed to send request");
+    }
+    // Most endpoints still answer without a crumb, so try the request anyway
+    let crumb = yahoo_crumb(false).await;
+    #[cfg(feature = "tracing")]
+    if let Err(e) = &crumb {
+        tracing::debug!(error = %format!("{:#}", e), "sending the request without a crumb");
+    }
+    let crumb = crumb.ok();
+    let request_url = crumb.as_ref().map(|c| with_crumb(url, c)).unwrap_or(url.to_string());
+    let response = REQUEST_CLIENT.get(&request_url).send().await.context("Failed to send request")?;
+    if response.status() == StatusCode::UNAUTHORIZED || response.status() == StatusCode::FORBIDDEN {
+        #[cfg(feature = "tracing")]
+        {
+            tracing::warn!(status = %response.status(), "request rejected, refreshing the crumb");
+            tracing::Span::current().record("retries", 1);
+        }
+        let crumb = yahoo_crumb(true).await
+            .with_context(|| format!("Request was rejected with status {} and the crumb could not be refreshed", response.status()))?;
+        return REQUEST_CLIENT.get(with_crumb(url, &crumb)).send().await.context("Failed to send request");
+    }
+    Ok(response)
+}
+
+/// Result of a Yahoo Finance connectivity check
+///
+/// * `cookie` - Whether the session cookie page was reachable
+/// * `crumb` - Crumb acquired for the session, if any
+/// * `quote` - Whether an authenticated quote request succeeded
+/// * `latency_ms` - Duration of the quote request in milliseconds
+/// * `error` - First error encountered, if any
+#[derive(Debug, Clone)]
+pub struct ConnectivityReport {
+    pub cookie: bool,
+    pub crumb: Option<String>,
+    pub quote: bool,
+    pub latency_ms: u128,
+    pub error: Option<String>,
+}
+
+/// Verifies that Yahoo Finance is reachable and accepts authenticated requests
+///
+/// A fresh session cookie and crumb are acquired, ignoring the backoff of earlier failures, then a quote is
+/// requested for a liquid symbol.
+///
+/// # Returns
+///
+/// * `ConnectivityReport` struct
+pub async fn check_connectivity() -> ConnectivityReport {
+    let mut report = ConnectivityReport { cookie: false, crumb: None, quote: false, latency_ms: 0, error: None };
+    match REQUEST_CLIENT.get(YAHOO_COOKIE_URL).send().await {
+        Ok(_) => report.cookie = true,
+        Err(e) => report.error = Some(format!("Cookie page unreachable: {}", e)),
+    }
+    YAHOO_CRUMB.write().await.retry_at = None;
+    match yahoo_crumb(true).await {
+        Ok(crumb) => report.crumb = Some(crumb),
+        Err(e) => { report.error.get_or_insert(format!("{:#}", e)); }
+    }
+    let start = Instant::now();
+    match send_request("https://query1.finance.yahoo.com/v7/finance/quote?symbols=SPY").await {
+        Ok(response) if response.status() == StatusCode::OK => report.quote = true,
+        Ok(response) => { report.error.get_or_insert(format!("Quote request failed with status: {}", response.status())); }
+        Err(e) => { report.error.get_or_insert(format!("{:#}", e)); }
+    }
+    report.latency_ms = start.elapsed().as_millis();
+    report
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err, fields(url = %url, cache_hit = tracing::field::Empty,
+                                                                          retries = tracing::field::Empty)))]
+pub async fn get_json_response(url: String) -> Result<Value> {
+    // The in-memory cache only serves live requests, so that recording and replaying always hit the network and fixtures
+    match data_mode() {
+        DataMode::Live => {
+            let response = cached_json_response(url).await?;
+            #[cfg(feature = "tracing")]
+            tracing::Span::current().record("cache_hit", response.was_cached);
+            Ok(response.value)
+        }
+        DataMode::Record(dir) => {
+            let result = fetch_json_response(&url).await?;
+            save_fixture(&dir, &url, result.clone())?;
+            Ok(result)
+        }
+        DataMode::Replay(dir) => load_fixture(&dir, &url),
+    }
+}
+
 #[cached(
     result = true,
+    with_cached_flag = true,
     time = 900 // Yahoo Finance API has a 15-minute Delay for Real-Time Data
 )]
-pub async fn get_json_response(url: String) -> Result<Value> {
-    let response = REQUEST_CLIENT.get(&url).send().await.context("Failed to send request")?;
+async fn cached_json_response(url: String) -> Result<Return<Value>> {
+    Ok(Return::new(fetch_json_response(&url).await?))
+}
+
+async fn fetch_json_response(url: &str) -> Result<Value> {
+    let response = send_request(url).await?;
     if response.status() != StatusCode::OK {
         return Err(anyhow::anyhow!("Request failed with status: {}", response.status()));
     }
-    let result = response.json::<Value>().await.context("Failed to parse JSON response")?;
-    Ok(result)
+    response.json::<Value>().await.context("Failed to parse JSON response")
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip(start_date, end_date), err))]
 pub async fn fetch_news(token: &str, start_date: NaiveDate, end_date: NaiveDate, compute_sentiment: bool) -> Result<DataFrame, Box<dyn Error + Send + Sync>> {
     let url = format!(
         "https://news.google.com/rss/search?q=allintext:{}+after:{}+before:{}",
@@ -49,11 +243,31 @@ pub async fn fetch_news(token: &str, start_date: NaiveDate, end_date: NaiveDate,
     Ok(df)
 }
 
+pub(crate) async fn fetch_html(url: String) -> Result<String, Box<dyn Error + Send + Sync>> {
+    // As for the JSON responses, only live requests are cached
+    match data_mode() {
+        DataMode::Live => cached_html(url).await,
+        DataMode::Record(dir) => {
+            let body = fetch_html_body(&url).await?;
+            save_fixture(&dir, &url, Value::String(body.clone())).map_err(|e| e.to_string())?;
+            Ok(body)
+        }
+        DataMode::Replay(dir) => {
+            let fixture = load_fixture(&dir, &url).map_err(|e| e.to_string())?;
+            Ok(fixture.as_str().unwrap_or_default().to_string())
+        }
+    }
+}
+
 #[cached(
     result = true,
     time = 3600 // Cache Google News Results for 1 Hour
 )]
-async fn fetch_html(url: String) -> Result<String, Box<dyn Error + Send + Sync>> {
+async fn cached_html(url: String) -> Result<String, Box<dyn Error + Send + Sync>> {
+    fetch_html_body(&url).await
+}
+
+async fn fetch_html_body(url: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
     let response = REQUEST_CLIENT.get(url).send().await?;
 
    if response.status() != StatusCode::OK {
@@ -73,6 +287,7 @@ fn extract_news_details(body: String, compute_sentiment: bool) -> DataFrame {
     let mut titles = Vec::new();
     let mut sources = Vec::new();
     let mut links = Vec::new();
+    let mut urls = Vec::new();
     let mut pub_dates = Vec::new();
     let mut sentiment_scores = Vec::new();
 
@@ -87,6 +302,7 @@ fn extract_news_details(body: String, compute_sentiment: bool) -> DataFrame {
         let pub_date = NaiveDateTime::parse_from_str(&pub_date, "%a, %d %b %Y %H:%M:%S GMT").unwrap();
         titles.push(title.clone());
         links.push(format!(r#"<a href="{}">{}</a>"#, link, title.replace(format!("- {}", source).as_str(), "")));
+        urls.push(link);
         sources.push(source);
         pub_dates.push(pub_date);
         if compute_sentiment {
@@ -101,6 +317,7 @@ fn extract_news_details(body: String, compute_sentiment: bool) -> DataFrame {
         Series::new("Source", sources),
         Series::new("Title", titles),
         Series::new("Link", links),
+        Series::new("URL", urls),
     ]).unwrap();
 
     if compute_sentiment {
@@ -32,11 +32,26 @@ class NavbarComponent extends HTMLElement {
                                 <i class="bi bi-graph-up me-2"></i>Ticker
                             </a>
                         </li>
+                        <li class="nav-item">
+                            <a class="nav-link d-flex align-items-center" style="color: blue; font-weight: bold; text-decoration: none;" href="/options" target="_blank">
+                                <i class="bi bi-bar-chart-steps me-2"></i>Options
+                            </a>
+                        </li>
                         <li class="nav-item">
                             <a class="nav-link d-flex align-items-center" style="color: blue; font-weight: bold; text-decoration: none;" href="/portfolio" target="_blank">
                                 <i class="bi bi-pie-chart me-2"></i>Portfolio
                             </a>
                         </li>
+                        <li class="nav-item">
+                            <a class="nav-link d-flex align-items-center" style="color: blue; font-weight: bold; text-decoration: none;" href="/portfolio_builder" target="_blank">
+                                <i class="bi bi-sliders me-2"></i>Portfolio Builder
+                            </a>
+                        </li>
+                        <li class="nav-item">
+                            <a class="nav-link d-flex align-items-center" style="color: blue; font-weight: bold; text-decoration: none;" href="/library" target="_blank">
+                                <i class="bi bi-bookmark me-2"></i>Library
+                            </a>
+                        </li>
                     </ul>
                 </div>
             </div>
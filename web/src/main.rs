@@ -1,10 +1,17 @@
-use actix_web::{App, HttpServer};
+use actix_web::{guard, web, App, HttpServer};
 use env_logger::Env;
 use crate::router::index::index_html;
 use crate::router::portfolio::{portfolio, portfolio_report};
+use crate::router::portfolio_builder::portfolio_builder;
 use crate::router::symbols::get_all_symbols;
 use crate::router::ticker::{ticker, ticker_report};
+use crate::router::options::{options, options_report};
 use crate::router::code::{get_code_examples};
+use crate::router::api::{portfolio_stats, ticker_stats};
+use crate::router::export::{export_portfolio_table, export_ticker_table};
+use crate::router::session::{delete_report, delete_screener_preset, delete_watchlist, get_session, library, list_reports,
+                             list_screener_presets, list_watchlists, save_report, save_screener_preset, save_watchlist,
+                             view_report, UserStore, MAX_REPORT_REQUEST_BYTES};
 
 mod router;
 
@@ -13,17 +20,42 @@ async fn main() -> std::io::Result<()> {
 
     env_logger::init_from_env(Env::default().default_filter_or("info"));
 
-    HttpServer::new( || {
+    let user_store = web::Data::new(UserStore::from_env());
+
+    HttpServer::new(move || {
         App::new()
+            .app_data(user_store.clone())
             .service(actix_files::Files::new("/components", "src/components").show_files_listing())
             .service(actix_files::Files::new("/images", "src/images").show_files_listing())
             .service(index_html)
             .service(ticker)
             .service(ticker_report)
+            .service(options)
+            .service(options_report)
             .service(portfolio)
             .service(portfolio_report)
+            .service(portfolio_builder)
             .service(get_all_symbols)
             .service(get_code_examples)
+            .service(ticker_stats)
+            .service(portfolio_stats)
+            .service(export_ticker_table)
+            .service(export_portfolio_table)
+            .service(get_session)
+            .service(list_watchlists)
+            .service(save_watchlist)
+            .service(delete_watchlist)
+            .service(list_screener_presets)
+            .service(save_screener_preset)
+            .service(delete_screener_preset)
+            .service(list_reports)
+            .service(web::resource("/api/reports")
+                .guard(guard::Post())
+                .app_data(web::JsonConfig::default().limit(MAX_REPORT_REQUEST_BYTES))
+                .route(web::post().to(save_report)))
+            .service(view_report)
+            .service(delete_report)
+            .service(library)
     })
         .bind("0.0.0.0:8080")?
         .run()
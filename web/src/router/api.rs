@@ -0,0 +1,91 @@
+use actix_web::{get, HttpResponse, Responder};
+use actix_web::web::Query;
+use serde::Deserialize;
+use finalytics::prelude::*;
+
+#[derive(Debug, Deserialize)]
+pub struct TickerStatsQuery {
+    symbol: String,
+    start_date: String,
+    end_date: String,
+    #[serde(default = "default_interval")]
+    interval: String,
+    #[serde(default = "default_benchmark")]
+    benchmark_symbol: String,
+    #[serde(default = "default_confidence_level")]
+    confidence_level: f64,
+    #[serde(default = "default_risk_free_rate")]
+    risk_free_rate: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PortfolioStatsQuery {
+    symbols: String,
+    start_date: String,
+    end_date: String,
+    #[serde(default = "default_interval")]
+    interval: String,
+    #[serde(default = "default_benchmark")]
+    benchmark_symbol: String,
+    #[serde(default = "default_confidence_level")]
+    confidence_level: f64,
+    #[serde(default = "default_risk_free_rate")]
+    risk_free_rate: f64,
+    #[serde(default = "default_objective_function")]
+    objective_function: String,
+}
+
+fn default_interval() -> String { "1d".to_string() }
+fn default_benchmark() -> String { "^GSPC".to_string() }
+fn default_confidence_level() -> f64 { 0.95 }
+fn default_risk_free_rate() -> f64 { 0.02 }
+fn default_objective_function() -> String { "max_sharpe".to_string() }
+
+impl TickerStatsQuery {
+    pub(crate) fn ticker(&self) -> Ticker {
+        TickerBuilder::new()
+            .ticker(&self.symbol)
+            .start_date(&self.start_date)
+            .end_date(&self.end_date)
+            .interval(Interval::from_str(&self.interval))
+            .benchmark_symbol(&self.benchmark_symbol)
+            .confidence_level(self.confidence_level)
+            .risk_free_rate(self.risk_free_rate)
+            .build()
+    }
+}
+
+impl PortfolioStatsQuery {
+    pub(crate) async fn portfolio(&self) -> Result<Portfolio, Box<dyn std::error::Error>> {
+        PortfolioBuilder::new()
+            .ticker_symbols(self.symbols.split(",").map(|x| x.trim()).collect())
+            .benchmark_symbol(&self.benchmark_symbol)
+            .start_date(&self.start_date)
+            .end_date(&self.end_date)
+            .interval(Interval::from_str(&self.interval))
+            .confidence_level(self.confidence_level)
+            .risk_free_rate(self.risk_free_rate)
+            .objective_function(ObjectiveFunction::from_str(&self.objective_function))
+            .build()
+            .await
+    }
+}
+
+
+/// Performance statistics of a ticker as JSON
+#[get("/api/ticker_stats")]
+pub async fn ticker_stats(query: Query<TickerStatsQuery>) -> impl Responder {
+    match query.ticker().performance_stats().await {
+        Ok(stats) => HttpResponse::Ok().json(stats.summary()),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
+    }
+}
+
+/// Optimal weights and performance statistics of a portfolio as JSON
+#[get("/api/portfolio_stats")]
+pub async fn portfolio_stats(query: Query<PortfolioStatsQuery>) -> impl Responder {
+    match query.portfolio().await {
+        Ok(pf) => HttpResponse::Ok().json(pf.performance_stats.summary()),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
+    }
+}
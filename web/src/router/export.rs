@@ -0,0 +1,86 @@
+use std::error::Error;
+use actix_web::{get, HttpResponse, Responder};
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::web::{Path, Query};
+use serde::Deserialize;
+use finalytics::prelude::*;
+use crate::router::api::{PortfolioStatsQuery, TickerStatsQuery};
+
+/// Export options, read from the same query string as the ticker or portfolio parameters
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default = "default_format")]
+    format: String,
+    #[serde(default = "default_frequency")]
+    frequency: String,
+}
+
+fn default_format() -> String { "csv".to_string() }
+fn default_frequency() -> String { "annual".to_string() }
+
+
+/// Downloads a ticker table as CSV or XLSX
+///
+/// Tables: ohlcv, summary_stats, performance_stats, income_statement, balance_sheet, cashflow_statement,
+/// financial_ratios, options_chain, volatility_surface, asset_profile and fund_holdings
+#[get("/export/ticker/{table}")]
+pub async fn export_ticker_table(table: Path<String>, query: Query<TickerStatsQuery>, export: Query<ExportQuery>) -> impl Responder {
+    let ticker = query.ticker();
+    let frequency = StatementFrequency::from_str(&export.frequency);
+    let result: Result<DataTable, Box<dyn Error>> = match table.as_str() {
+        "ohlcv" => ticker.ohlcv_table().await,
+        "summary_stats" => ticker.summary_stats_table().await,
+        "performance_stats" => ticker.performance_stats_table().await,
+        "income_statement" => ticker.financials_tables(frequency).await.map(|x| x.income_statement),
+        "balance_sheet" => ticker.financials_tables(frequency).await.map(|x| x.balance_sheet),
+        "cashflow_statement" => ticker.financials_tables(frequency).await.map(|x| x.cashflow_statement),
+        "financial_ratios" => ticker.financials_tables(frequency).await.map(|x| x.financial_ratios),
+        "options_chain" => ticker.options_tables().await.map(|x| x.options_chain),
+        "volatility_surface" => ticker.options_tables().await.map(|x| x.volatility_surface),
+        "asset_profile" => ticker.asset_profile_table().await,
+        "fund_holdings" => ticker.fund_holdings_table().await,
+        _ => Err(format!("Unknown ticker table: {}", table).into()),
+    };
+    table_response(result, &format!("{}_{}", ticker.ticker, table), &export.format)
+}
+
+/// Downloads a portfolio table as CSV or XLSX
+///
+/// Tables: performance_stats, optimization_details and returns
+#[get("/export/portfolio/{table}")]
+pub async fn export_portfolio_table(table: Path<String>, query: Query<PortfolioStatsQuery>, export: Query<ExportQuery>) -> impl Responder {
+    let portfolio = match query.portfolio().await {
+        Ok(portfolio) => portfolio,
+        Err(e) => return HttpResponse::BadRequest().body(format!("Error building the portfolio: {}", e)),
+    };
+    let result: Result<DataTable, Box<dyn Error>> = match table.as_str() {
+        "performance_stats" => portfolio.performance_stats_table().await,
+        "optimization_details" => portfolio.optimization_details_table(),
+        "returns" => portfolio.returns_table(),
+        _ => Err(format!("Unknown portfolio table: {}", table).into()),
+    };
+    table_response(result, &format!("portfolio_{}", table), &export.format)
+}
+
+/// Writes the underlying DataFrame of a table as a file attachment
+fn table_response(table: Result<DataTable, Box<dyn Error>>, name: &str, format: &str) -> HttpResponse {
+    let table = match table {
+        Ok(table) => table,
+        Err(e) => return HttpResponse::BadRequest().body(format!("Error generating the table: {}", e)),
+    };
+    let (body, content_type, extension) = match format {
+        "csv" => (table.to_csv().map(|x| x.into_bytes()), "text/csv", "csv"),
+        "xlsx" => (table.to_xlsx(), "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "xlsx"),
+        _ => return HttpResponse::BadRequest().body(format!("Unsupported export format: {}", format)),
+    };
+    match body {
+        Ok(body) => HttpResponse::Ok()
+            .content_type(content_type)
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(format!("{}.{}", name, extension))],
+            })
+            .body(body),
+        Err(e) => HttpResponse::InternalServerError().body(format!("Error exporting the table: {}", e)),
+    }
+}
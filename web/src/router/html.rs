@@ -0,0 +1,4 @@
+/// Escapes a message echoing user input before it is embedded in a page
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -1,5 +1,11 @@
 pub mod ticker;
 pub mod portfolio;
+pub mod portfolio_builder;
 pub mod symbols;
 pub mod index;
-pub mod code;
\ No newline at end of file
+pub mod code;
+pub mod api;
+pub mod export;
+pub mod session;
+pub mod options;
+pub mod html;
\ No newline at end of file
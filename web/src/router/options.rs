@@ -0,0 +1,122 @@
+use actix_web::{get, HttpResponse, post, Responder};
+use actix_web::web::Bytes;
+use chrono::{Duration, Local};
+use serde::{Deserialize, Serialize};
+use finalytics::prelude::*;
+use finalytics::reports::tabs::TabbedHtml;
+use std::error::Error;
+use tera::{Context, Tera};
+use crate::router::html::escape_html;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OptionsFormData {
+    symbol: String,
+    expiration: String,
+    risk_free_rate: f64,
+}
+
+
+#[get("/options")]
+pub async fn options(form_data: Bytes) -> impl Responder {
+    let rendered_html = options_html(form_data).await;
+    HttpResponse::Ok().body(rendered_html)
+}
+
+#[post("/options_report")]
+pub async fn options_report(form_data: Bytes) -> impl Responder {
+    let rendered_html = options_html(form_data).await;
+    HttpResponse::Ok().body(rendered_html)
+}
+
+async fn options_html(form_data: Bytes) -> String {
+    let form_data_str = String::from_utf8(form_data.to_vec())
+        .map_err(|e| {
+            HttpResponse::BadRequest().body(format!("Invalid form data: {}", e))
+        }).unwrap();
+
+    let mut data: OptionsFormData = serde_urlencoded::from_str(&form_data_str)
+        .map_err(|e| {
+            HttpResponse::BadRequest().body(format!("Invalid form data: {}", e))
+        }).unwrap_or(OptionsFormData {
+        symbol: "AAPL".to_string(),
+        expiration: String::new(),
+        risk_free_rate: 0.02,
+    });
+
+    // One year of price history for the expected move cone
+    let end_date = Local::now().date_naive();
+    let start_date = end_date - Duration::days(365);
+    let tc = TickerBuilder::new()
+        .ticker(&data.symbol)
+        .start_date(&start_date.format("%Y-%m-%d").to_string())
+        .end_date(&end_date.format("%Y-%m-%d").to_string())
+        .interval(Interval::OneDay)
+        .risk_free_rate(data.risk_free_rate)
+        .build();
+
+    let (expirations, dashboard_html) = match options_dashboard(&tc, &data.expiration).await {
+        Ok((expirations, expiration, html)) => {
+            data.expiration = expiration;
+            (expirations, html)
+        }
+        Err(e) => (Vec::new(), format!("<div class=\"alert alert-danger\">Unable to load the options of {}: {}</div>",
+                                          escape_html(&data.symbol), escape_html(&e.to_string()))),
+    };
+
+    // Create a Tera instance and load your HTML template
+    let tera = Tera::new("src/templates/*").expect("Failed to initialize Tera");
+
+    // Create a context to pass data to the template
+    let mut context = Context::new();
+    context.insert("chart_content", &dashboard_html);
+    context.insert("expirations", &expirations);
+    context.insert("form_data", &data);
+
+    // Render the HTML template with the data
+    let rendered_html = tera
+        .render("options.html", &context)
+        .expect("Failed to render HTML");
+
+    rendered_html
+}
+
+/// Builds the dashboard tabs of the selected expiration, defaulting to the nearest expiration
+async fn options_dashboard(tc: &Ticker, expiration: &str) -> Result<(Vec<String>, String, String), Box<dyn Error>> {
+    let expirations = tc.get_options().await?.expiration_dates;
+    let expiration = match expirations.iter().find(|x| *x == expiration) {
+        Some(e) => e.clone(),
+        None => expirations.first().cloned().ok_or("No expirations found")?,
+    };
+
+    let mut tabs: Vec<(String, String)> = Vec::new();
+    let greeks = tc.chain_greeks(Some(&expiration)).await?;
+    tabs.push(("Chain & Greeks".to_string(), DataTable::new(greeks, TableType::OptionsAnalytics).to_html()?));
+    tabs.push(("Volatility Smile".to_string(), tc.expiration_smile_chart(Some(&expiration), None, None).await?
+        .to_html().replace("plotly-html-element", "expiration_smile_chart")));
+    match tc.options_charts(None, None).await {
+        Ok(charts) => tabs.push(("Volatility Surface".to_string(), charts.volatility_surface
+            .to_html().replace("plotly-html-element", "volatility_surface"))),
+        Err(e) => eprintln!("Unable to compute the volatility surface of {}: {}", tc.ticker, e),
+    }
+    tabs.push(("Open Interest".to_string(), tc.open_interest_chart(Some(&expiration), None, None).await?
+        .to_html().replace("plotly-html-element", "open_interest_chart")));
+    match tc.expected_move().await {
+        Ok(moves) => {
+            tabs.push(("Expected Move".to_string(), format!("{}{}",
+                tc.expected_move_chart(None, None).await?.to_html().replace("plotly-html-element", "expected_move_chart"),
+                DataTable::new(moves, TableType::OptionsAnalytics).to_html()?)));
+        }
+        Err(e) => eprintln!("Unable to compute the expected move of {}: {}", tc.ticker, e),
+    }
+    match tc.max_pain().await {
+        Ok(pain) => {
+            tabs.push(("Max Pain".to_string(), format!("{}{}",
+                tc.max_pain_chart(None, None).await?.to_html().replace("plotly-html-element", "max_pain_chart"),
+                DataTable::new(pain, TableType::OptionsAnalytics).to_html()?)));
+        }
+        Err(e) => eprintln!("Unable to compute the max pain of {}: {}", tc.ticker, e),
+    }
+
+    let html = TabbedHtml::new(ReportType::Options, tabs).to_html();
+    Ok((expirations, expiration, html))
+}
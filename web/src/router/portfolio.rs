@@ -1,6 +1,6 @@
 use actix_web::{get, HttpResponse, post, Responder};
 use actix_web::web::Bytes;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use tera::{Context, Tera};
 use finalytics::prelude::*;
 
@@ -13,7 +13,25 @@ struct PortfolioFormData {
     interval: String,
     confidence_level: f64,
     risk_free_rate: f64,
-    objective_function: String
+    objective_function: String,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    initial_value: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    monthly_contribution: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    goal_years: Option<usize>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    goal_value: Option<f64>,
+}
+
+/// Parses an optional form field, treating empty inputs as missing
+fn empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: std::str::FromStr,
+{
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value.and_then(|x| x.trim().parse::<T>().ok()))
 }
 
 #[get("/portfolio")]
@@ -44,7 +62,11 @@ async fn portfolio_html(form_data: Bytes) -> String {
         interval: "1d".to_string(),
         confidence_level: 0.95,
         risk_free_rate: 0.02,
-        objective_function: "max_sharpe".to_string()
+        objective_function: "max_sharpe".to_string(),
+        initial_value: None,
+        monthly_contribution: None,
+        goal_years: None,
+        goal_value: None,
     });
 
     let pf = PortfolioBuilder::new()
@@ -59,7 +81,17 @@ async fn portfolio_html(form_data: Bytes) -> String {
         .build()
         .await.unwrap();
 
-    let report_html = pf.report(Some(ReportType::Performance)).await.unwrap().to_html();
+    let mut report_html = pf.report(Some(ReportType::Performance)).await.unwrap().to_html();
+
+    // Goal projection fan chart, shown when a target value is given
+    if let Some(goal_value) = data.goal_value {
+        let plan = GoalPlan::new(data.initial_value.unwrap_or(10000.0), data.monthly_contribution.unwrap_or(0.0),
+                                 data.goal_years.unwrap_or(10), goal_value);
+        match pf.goal_projection_chart(&plan, None, None) {
+            Ok(chart) => report_html.push_str(&chart.to_inline_html(Some("goal-projection-chart"))),
+            Err(e) => eprintln!("Error generating goal projection: {}", e),
+        }
+    }
 
     // Create a Tera instance and load your HTML template
     let tera = Tera::new("src/templates/*").expect("Failed to initialize Tera");
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::error::Error;
+use actix_web::{get, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use tera::{Context, Tera};
+use finalytics::prelude::*;
+use crate::router::html::escape_html;
+
+/// Portfolio builder configuration, submitted with GET so the full configuration is kept in the URL for sharing
+///
+/// The per-asset fields are comma separated lists in the order of `symbols`, empty entries mean no value:
+/// `weights` are the manual weights of an analysis, `lower_bounds`/`upper_bounds` the weight bounds of an
+/// optimization and `categories` the category of each asset. `category_limits` are `category:min:max` entries
+/// separated by commas.
+#[derive(Debug, Deserialize, Serialize)]
+struct PortfolioBuilderFormData {
+    symbols: String,
+    #[serde(default)]
+    weights: String,
+    #[serde(default)]
+    lower_bounds: String,
+    #[serde(default)]
+    upper_bounds: String,
+    #[serde(default)]
+    categories: String,
+    #[serde(default)]
+    category_limits: String,
+    mode: String,
+    benchmark_symbol: String,
+    start_date: String,
+    end_date: String,
+    interval: String,
+    confidence_level: f64,
+    risk_free_rate: f64,
+    objective_function: String,
+}
+
+impl Default for PortfolioBuilderFormData {
+    fn default() -> Self {
+        PortfolioBuilderFormData {
+            symbols: "AAPL,MSFT,NVDA,BTC-USD".to_string(),
+            weights: "0.3,0.3,0.3,0.1".to_string(),
+            lower_bounds: String::new(),
+            upper_bounds: String::new(),
+            categories: "Tech,Tech,Tech,Crypto".to_string(),
+            category_limits: String::new(),
+            mode: "analysis".to_string(),
+            benchmark_symbol: "^GSPC".to_string(),
+            start_date: "2023-01-01".to_string(),
+            end_date: "2024-12-31".to_string(),
+            interval: "1d".to_string(),
+            confidence_level: 0.95,
+            risk_free_rate: 0.02,
+            objective_function: "max_sharpe".to_string(),
+        }
+    }
+}
+
+/// Asset row of the builder table
+#[derive(Debug, Serialize)]
+struct AssetRow {
+    symbol: String,
+    weight: String,
+    lower_bound: String,
+    upper_bound: String,
+    category: String,
+}
+
+impl PortfolioBuilderFormData {
+    fn symbols(&self) -> Vec<String> {
+        split_list(&self.symbols).into_iter().filter(|x| !x.is_empty()).collect()
+    }
+
+    fn rows(&self) -> Vec<AssetRow> {
+        let column = |list: &str, i: usize| split_list(list).get(i).cloned().unwrap_or_default();
+        self.symbols().into_iter().enumerate()
+            .map(|(i, symbol)| AssetRow {
+                symbol,
+                weight: column(&self.weights, i),
+                lower_bound: column(&self.lower_bounds, i),
+                upper_bound: column(&self.upper_bounds, i),
+                category: column(&self.categories, i),
+            })
+            .collect()
+    }
+
+    /// Builds the portfolio, pinning every asset to its manual weight in analysis mode
+    async fn portfolio(&self) -> Result<Portfolio, Box<dyn Error>> {
+        let symbols = self.symbols();
+        let rows = self.rows();
+        let mut builder = PortfolioBuilder::new();
+        builder.ticker_symbols(symbols.iter().map(|x| x.as_str()).collect())
+            .benchmark_symbol(&self.benchmark_symbol)
+            .start_date(&self.start_date)
+            .end_date(&self.end_date)
+            .interval(Interval::from_str(&self.interval))
+            .confidence_level(self.confidence_level)
+            .risk_free_rate(self.risk_free_rate)
+            .objective_function(ObjectiveFunction::from_str(&self.objective_function));
+
+        if self.mode == "analysis" {
+            let weights = rows.iter()
+                .map(|x| x.weight.parse::<f64>().map_err(|_| format!("Invalid weight for {}: '{}'", x.symbol, x.weight)))
+                .collect::<Result<Vec<f64>, String>>()?;
+            let total: f64 = weights.iter().sum();
+            if (total - 1.0).abs() > 1e-6 {
+                return Err(format!("Manual weights must sum to 1, got {:.4}", total).into());
+            }
+            for (row, weight) in rows.iter().zip(weights) {
+                builder.pin_weight(&row.symbol, weight);
+            }
+        } else {
+            let bound = |value: &str, default: f64| value.parse::<f64>().unwrap_or(default);
+            builder.constraints(Some(rows.iter()
+                .map(|x| (bound(&x.lower_bound, 0.0), bound(&x.upper_bound, 1.0)))
+                .collect()));
+
+            let mapping = rows.iter()
+                .filter(|x| !x.category.is_empty())
+                .map(|x| (x.symbol.clone(), x.category.clone()))
+                .collect::<HashMap<String, String>>();
+            let category_constraints = split_list(&self.category_limits).iter()
+                .filter(|x| !x.is_empty())
+                .map(|limit| {
+                    let parts = limit.split(':').map(|x| x.trim()).collect::<Vec<&str>>();
+                    match parts.as_slice() {
+                        [category, min, max] => Ok(CategoryConstraint::new(
+                            CategorySource::Mapping(mapping.clone()), category,
+                            min.parse::<f64>().map_err(|_| format!("Invalid category limit: '{}'", limit))?,
+                            max.parse::<f64>().map_err(|_| format!("Invalid category limit: '{}'", limit))?)),
+                        _ => Err(format!("Category limits must be category:min:max, got '{}'", limit)),
+                    }
+                })
+                .collect::<Result<Vec<CategoryConstraint>, String>>()?;
+            if !category_constraints.is_empty() {
+                builder.category_constraints(category_constraints);
+            }
+        }
+
+        builder.build().await
+    }
+}
+
+/// Splits a comma separated form list, keeping empty entries so the lists stay aligned with the symbols
+fn split_list(list: &str) -> Vec<String> {
+    if list.trim().is_empty() {
+        return Vec::new();
+    }
+    list.split(',').map(|x| x.trim().to_string()).collect()
+}
+
+#[get("/portfolio_builder")]
+pub async fn portfolio_builder(req: HttpRequest) -> impl Responder {
+    let data: PortfolioBuilderFormData = if req.query_string().is_empty() {
+        PortfolioBuilderFormData::default()
+    } else {
+        match serde_urlencoded::from_str(req.query_string()) {
+            Ok(data) => data,
+            Err(e) => return HttpResponse::BadRequest().body(format!("Invalid form data: {}", e)),
+        }
+    };
+
+    let report_html = match data.portfolio().await {
+        Ok(pf) => match pf.report(Some(ReportType::Performance)).await {
+            Ok(report) => report.to_html(),
+            Err(e) => format!(r#"<div class="alert alert-danger">Error generating the report: {}</div>"#, escape_html(&e.to_string())),
+        },
+        Err(e) => format!(r#"<div class="alert alert-danger">Error building the portfolio: {}</div>"#, escape_html(&e.to_string())),
+    };
+
+    // Create a Tera instance and load your HTML template
+    let tera = Tera::new("src/templates/*").expect("Failed to initialize Tera");
+
+    // Create a context to pass data to the template
+    let mut context = Context::new();
+    context.insert("chart_content", &report_html);
+    context.insert("form_data", &data);
+    context.insert("assets", &data.rows());
+
+    // Render the HTML template with the data
+    let rendered_html = tera
+        .render("portfolio_builder.html", &context)
+        .expect("Failed to render HTML");
+
+    HttpResponse::Ok().body(rendered_html)
+}
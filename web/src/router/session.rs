@@ -0,0 +1,508 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path as FsPath, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse, HttpResponseBuilder};
+use actix_web::cookie::{Cookie, CookieJar, Key, SameSite};
+use actix_web::cookie::time::Duration;
+use actix_web::error::ErrorTooManyRequests;
+use actix_web::web::{Data, Json, Path};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use finalytics::prelude::*;
+
+/// Name of the signed cookie holding the user id
+const SESSION_COOKIE: &str = "finalytics_user";
+
+/// Maximum size of a save report request, saved reports embed their charts so they exceed the default JSON limit
+pub const MAX_REPORT_REQUEST_BYTES: usize = 20 * 1024 * 1024;
+
+/// Maximum number of saved reports of a user
+const MAX_REPORTS: usize = 50;
+
+/// Maximum total size of the saved report HTML of a user
+const MAX_REPORT_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Maximum number of watchlists of a user
+const MAX_WATCHLISTS: usize = 100;
+
+/// Maximum number of screener presets of a user
+const MAX_SCREENER_PRESETS: usize = 100;
+
+/// Maximum number of users in the store
+const MAX_USERS: usize = 10_000;
+
+/// Maximum total size of the files in the store
+const MAX_STORE_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Maximum number of new user ids handed to a client address per minute
+const MAX_NEW_USERS_PER_MINUTE: u32 = 30;
+
+/// Sandboxes saved reports in an opaque origin, so their scripts cannot act on the session of the viewer
+const REPORT_CSP: &str = "sandbox allow-scripts allow-popups";
+
+/// Per-user persistence of watchlists, screener presets and saved reports
+///
+/// Users are anonymous: each browser gets a random id in a signed cookie, so ids cannot be forged, and its data
+/// is kept in a directory named after the id, created on its first successful write:
+///
+/// * `watchlists.json` - Watchlists, in the format of `WatchlistStore`
+/// * `screener_presets.json` - Screener filters by preset name
+/// * `reports/index.json` - Saved report metadata, with the report HTML in `reports/<id>.html`
+///
+/// Besides the per-user limits, the store caps its number of users and total size, and the number of new ids
+/// handed to a client address, so that cookieless requests cannot fill the disk.
+pub struct UserStore {
+    data_dir: PathBuf,
+    key: Key,
+    lock: Mutex<()>,
+    users: AtomicUsize,
+    bytes: AtomicU64,
+    new_users: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+/// Error of a write rejected by a user or store limit
+#[derive(Debug)]
+struct QuotaExceeded(String);
+
+impl fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for QuotaExceeded {}
+
+/// Metadata of a saved report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedReport {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    pub url: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WatchlistRequest {
+    name: String,
+    symbols: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScreenerPresetRequest {
+    name: String,
+    filters: Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveReportRequest {
+    name: String,
+    kind: String,
+    #[serde(default)]
+    url: String,
+    html: String,
+}
+
+impl UserStore {
+    /// Opens the store in `FINALYTICS_DATA_DIR` (default "data/users")
+    ///
+    /// Cookies are signed with a key derived from `FINALYTICS_SECRET_KEY` (at least 32 bytes), sessions do not
+    /// survive a restart when it is not set.
+    pub fn from_env() -> UserStore {
+        let data_dir = std::env::var("FINALYTICS_DATA_DIR").unwrap_or("data/users".to_string());
+        let key = match std::env::var("FINALYTICS_SECRET_KEY") {
+            Ok(secret) if secret.len() >= 32 => Key::derive_from(secret.as_bytes()),
+            _ => {
+                log::warn!("FINALYTICS_SECRET_KEY is not set or shorter than 32 bytes, using a random session key");
+                Key::generate()
+            }
+        };
+        let data_dir = PathBuf::from(data_dir);
+        let (users, bytes) = store_usage(&data_dir);
+        UserStore {
+            data_dir,
+            key,
+            lock: Mutex::new(()),
+            users: AtomicUsize::new(users),
+            bytes: AtomicU64::new(bytes),
+            new_users: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reads the user id from the session cookie, creating a new user when the cookie is missing or invalid
+    ///
+    /// # Returns
+    ///
+    /// * `(String, Option<Cookie>)` - User id and the cookie to set when the user is new, or a 429 error when
+    ///   the client address has been handed too many new ids
+    fn session(&self, req: &HttpRequest) -> actix_web::Result<(String, Option<Cookie<'static>>)> {
+        let mut jar = CookieJar::new();
+        if let Some(cookie) = req.cookie(SESSION_COOKIE) {
+            jar.add_original(cookie);
+        }
+        if let Some(cookie) = jar.signed(&self.key).get(SESSION_COOKIE) {
+            if is_valid_id(cookie.value()) {
+                return Ok((cookie.value().to_string(), None));
+            }
+        }
+        if !self.allow_new_user(req) {
+            return Err(ErrorTooManyRequests("Too many new sessions, try again later"));
+        }
+        let user_id = random_id();
+        jar.signed_mut(&self.key).add(Cookie::build(SESSION_COOKIE, user_id.clone())
+            .path("/")
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .max_age(Duration::days(365))
+            .finish());
+        Ok((user_id, jar.get(SESSION_COOKIE).cloned()))
+    }
+
+    /// Counts a new user id against the limit of the client address, in fixed one minute windows
+    fn allow_new_user(&self, req: &HttpRequest) -> bool {
+        let Some(address) = req.peer_addr().map(|x| x.ip()) else {
+            return true;
+        };
+        let now = Instant::now();
+        let mut new_users = self.new_users.lock().unwrap_or_else(|e| e.into_inner());
+        new_users.retain(|_, (start, _)| now.duration_since(*start).as_secs() < 60);
+        let (_, count) = new_users.entry(address).or_insert((now, 0));
+        if *count >= MAX_NEW_USERS_PER_MINUTE {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    fn user_dir(&self, user_id: &str) -> PathBuf {
+        self.data_dir.join(user_id)
+    }
+
+    fn watchlists(&self, user_id: &str) -> Result<WatchlistStore, Box<dyn Error>> {
+        WatchlistStore::open(self.user_dir(user_id).join("watchlists.json"))
+    }
+
+    /// Writes the watchlists in the format of `WatchlistStore::save`, through the store quota
+    fn save_watchlists(&self, user_id: &str, watchlists: &WatchlistStore) -> Result<(), Box<dyn Error>> {
+        self.write_json(user_id, &watchlists.path, &watchlists.watchlists.values().collect::<Vec<&Watchlist>>())
+    }
+
+    fn read_json<T: for<'de> Deserialize<'de> + Default>(&self, path: &PathBuf) -> Result<T, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(T::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn write_json<T: Serialize>(&self, user_id: &str, path: &FsPath, value: &T) -> Result<(), Box<dyn Error>> {
+        self.write_file(user_id, path, serde_json::to_string_pretty(value)?.as_bytes())
+    }
+
+    /// Writes a file of a user within the store limits, creating the user directory on the first successful write
+    fn write_file(&self, user_id: &str, path: &FsPath, contents: &[u8]) -> Result<(), Box<dyn Error>> {
+        let user_dir = self.user_dir(user_id);
+        let new_user = !user_dir.exists();
+        if new_user && self.users.load(Ordering::Relaxed) >= MAX_USERS {
+            return Err(QuotaExceeded(format!("User limit of the store reached ({} users)", MAX_USERS)).into());
+        }
+        let size = contents.len() as u64;
+        let previous = fs::metadata(path).map(|x| x.len()).unwrap_or(0);
+        if size > previous && self.bytes.load(Ordering::Relaxed) + (size - previous) > MAX_STORE_BYTES {
+            return Err(QuotaExceeded(format!("Storage limit of the store reached ({} GB)", MAX_STORE_BYTES / (1024 * 1024 * 1024))).into());
+        }
+        let written = path.parent().map_or(Ok(()), fs::create_dir_all).and_then(|_| fs::write(path, contents));
+        if let Err(e) = written {
+            if new_user {
+                let _ = fs::remove_dir_all(&user_dir);
+            }
+            return Err(e.into());
+        }
+        if new_user {
+            self.users.fetch_add(1, Ordering::Relaxed);
+        }
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+        self.bytes.fetch_sub(previous, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &FsPath) {
+        if let Ok(metadata) = fs::metadata(path) {
+            if fs::remove_file(path).is_ok() {
+                self.bytes.fetch_sub(metadata.len(), Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn presets_path(&self, user_id: &str) -> PathBuf {
+        self.user_dir(user_id).join("screener_presets.json")
+    }
+
+    fn reports_path(&self, user_id: &str) -> PathBuf {
+        self.user_dir(user_id).join("reports").join("index.json")
+    }
+
+    fn report_html_path(&self, user_id: &str, report_id: &str) -> PathBuf {
+        self.user_dir(user_id).join("reports").join(format!("{}.html", report_id))
+    }
+
+    /// Checks that a new report of `size` bytes fits in the report quota of the user
+    ///
+    /// # Returns
+    ///
+    /// * `Option<QuotaExceeded>` - Reason the report is rejected, None when it fits
+    fn report_quota_error(&self, user_id: &str, reports: &[SavedReport], size: u64) -> Option<QuotaExceeded> {
+        if reports.len() >= MAX_REPORTS {
+            return Some(QuotaExceeded(format!("Report limit reached ({} reports), delete a saved report first", MAX_REPORTS)));
+        }
+        let used = reports.iter()
+            .filter_map(|x| fs::metadata(self.report_html_path(user_id, &x.id)).ok())
+            .map(|x| x.len())
+            .sum::<u64>();
+        if used + size > MAX_REPORT_BYTES {
+            return Some(QuotaExceeded(format!("Report storage limit reached ({} MB), delete a saved report first", MAX_REPORT_BYTES / (1024 * 1024))));
+        }
+        None
+    }
+}
+
+/// Runs a store operation on the blocking thread pool, holding the store lock so that concurrent
+/// requests of a user do not overwrite each other
+async fn blocking<T, F>(store: &Data<UserStore>, operation: F) -> Result<T, Box<dyn Error>>
+where
+    T: Send + 'static,
+    F: FnOnce(&UserStore) -> Result<T, Box<dyn Error>> + Send + 'static,
+{
+    let store = store.clone();
+    let result = web::block(move || {
+        let _guard = store.lock.lock().unwrap_or_else(|e| e.into_inner());
+        // Quota errors are kept as such so that they are answered with 413
+        operation(&store).map_err(|e| match e.downcast::<QuotaExceeded>() {
+            Ok(e) => e as Box<dyn Error + Send + Sync>,
+            Err(e) => e.to_string().into(),
+        })
+    }).await.map_err(|e| e.to_string())?;
+    result.map_err(|e| e as Box<dyn Error>)
+}
+
+/// Number of users and total size of the files of the store
+fn store_usage(data_dir: &FsPath) -> (usize, u64) {
+    let Ok(entries) = fs::read_dir(data_dir) else {
+        return (0, 0);
+    };
+    let users = entries.flatten().filter(|x| x.path().is_dir()).count();
+    (users, dir_size(data_dir))
+}
+
+fn dir_size(path: &FsPath) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries.flatten()
+        .map(|x| match x.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&x.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Random 128 bit hex id from the operating system random number generator
+fn random_id() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|x| format!("{:02x}", x)).collect()
+}
+
+/// Ids are hex strings, checked before they are used in file paths
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 64 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Builds the response of a request, setting the session cookie of new users
+fn respond(mut builder: HttpResponseBuilder, cookie: Option<Cookie<'static>>) -> HttpResponseBuilder {
+    if let Some(cookie) = cookie {
+        builder.cookie(cookie);
+    }
+    builder
+}
+
+/// Returns the JSON result of a store operation, or its error, with 413 for writes over a user or store limit
+fn json_result<T: Serialize>(result: Result<T, Box<dyn Error>>, cookie: Option<Cookie<'static>>) -> HttpResponse {
+    match result {
+        Ok(value) => respond(HttpResponse::Ok(), cookie).json(value),
+        Err(e) if e.is::<QuotaExceeded>() => respond(HttpResponse::PayloadTooLarge(), cookie).json(serde_json::json!({ "error": e.to_string() })),
+        Err(e) => respond(HttpResponse::BadRequest(), cookie).json(serde_json::json!({ "error": e.to_string() })),
+    }
+}
+
+
+#[get("/api/session")]
+pub async fn get_session(req: HttpRequest, store: Data<UserStore>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    Ok(respond(HttpResponse::Ok(), cookie).json(serde_json::json!({ "user_id": user_id })))
+}
+
+#[get("/api/watchlists")]
+pub async fn list_watchlists(req: HttpRequest, store: Data<UserStore>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let result = blocking(&store, move |store| {
+        store.watchlists(&user_id).map(|x| x.watchlists.into_values().collect::<Vec<Watchlist>>())
+    }).await;
+    Ok(json_result(result, cookie))
+}
+
+/// Creates a watchlist, or replaces the symbols of an existing one
+#[post("/api/watchlists")]
+pub async fn save_watchlist(req: HttpRequest, store: Data<UserStore>, body: Json<WatchlistRequest>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let body = body.into_inner();
+    let result = blocking(&store, move |store| {
+        let mut watchlists = store.watchlists(&user_id)?;
+        if !watchlists.watchlists.contains_key(&body.name) && watchlists.watchlists.len() >= MAX_WATCHLISTS {
+            return Err(QuotaExceeded(format!("Watchlist limit reached ({} watchlists), delete a watchlist first", MAX_WATCHLISTS)).into());
+        }
+        let symbols = body.symbols.iter().map(|x| x.as_str()).collect::<Vec<&str>>();
+        watchlists.watchlists.insert(body.name.clone(), Watchlist::new(&body.name, symbols));
+        store.save_watchlists(&user_id, &watchlists)?;
+        watchlists.get(&body.name).cloned()
+    }).await;
+    Ok(json_result(result, cookie))
+}
+
+#[delete("/api/watchlists/{name}")]
+pub async fn delete_watchlist(req: HttpRequest, store: Data<UserStore>, name: Path<String>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let name = name.into_inner();
+    let result = blocking(&store, move |store| {
+        let mut watchlists = store.watchlists(&user_id)?;
+        watchlists.watchlists.remove(&name).ok_or(format!("Watchlist {} not found", name))?;
+        store.save_watchlists(&user_id, &watchlists)
+    }).await;
+    Ok(json_result(result, cookie))
+}
+
+#[get("/api/screener_presets")]
+pub async fn list_screener_presets(req: HttpRequest, store: Data<UserStore>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let result = blocking(&store, move |store| {
+        store.read_json::<BTreeMap<String, Value>>(&store.presets_path(&user_id))
+    }).await;
+    Ok(json_result(result, cookie))
+}
+
+/// Saves the filters of a screener preset, replacing any preset with the same name
+#[post("/api/screener_presets")]
+pub async fn save_screener_preset(req: HttpRequest, store: Data<UserStore>, body: Json<ScreenerPresetRequest>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let body = body.into_inner();
+    let result = blocking(&store, move |store| {
+        let path = store.presets_path(&user_id);
+        let mut presets = store.read_json::<BTreeMap<String, Value>>(&path)?;
+        if !presets.contains_key(&body.name) && presets.len() >= MAX_SCREENER_PRESETS {
+            return Err(QuotaExceeded(format!("Screener preset limit reached ({} presets), delete a preset first", MAX_SCREENER_PRESETS)).into());
+        }
+        presets.insert(body.name, body.filters);
+        store.write_json(&user_id, &path, &presets)
+    }).await;
+    Ok(json_result(result, cookie))
+}
+
+#[delete("/api/screener_presets/{name}")]
+pub async fn delete_screener_preset(req: HttpRequest, store: Data<UserStore>, name: Path<String>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let name = name.into_inner();
+    let result = blocking(&store, move |store| {
+        let path = store.presets_path(&user_id);
+        let mut presets = store.read_json::<BTreeMap<String, Value>>(&path)?;
+        presets.remove(name.as_str()).ok_or(format!("Screener preset {} not found", name))?;
+        store.write_json(&user_id, &path, &presets)
+    }).await;
+    Ok(json_result(result, cookie))
+}
+
+#[get("/api/reports")]
+pub async fn list_reports(req: HttpRequest, store: Data<UserStore>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let result = blocking(&store, move |store| store.read_json::<Vec<SavedReport>>(&store.reports_path(&user_id))).await;
+    Ok(json_result(result, cookie))
+}
+
+/// Saves a generated report, returning its metadata with the id used to view it at `/saved_reports/{id}`
+///
+/// Registered as a resource in `main` to raise its JSON payload limit to `MAX_REPORT_REQUEST_BYTES`, reports over
+/// the report quota of the user or the store limits are rejected with 413 Payload Too Large.
+pub async fn save_report(req: HttpRequest, store: Data<UserStore>, body: Json<SaveReportRequest>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let body = body.into_inner();
+    let result = blocking(&store, move |store| {
+        let path = store.reports_path(&user_id);
+        let mut reports = store.read_json::<Vec<SavedReport>>(&path)?;
+        if let Some(error) = store.report_quota_error(&user_id, &reports, body.html.len() as u64) {
+            return Err(error.into());
+        }
+        let report = SavedReport {
+            id: random_id(),
+            name: body.name.clone(),
+            kind: body.kind.clone(),
+            url: body.url.clone(),
+            created_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        };
+        store.write_file(&user_id, &store.report_html_path(&user_id, &report.id), body.html.as_bytes())?;
+        reports.push(report.clone());
+        store.write_json(&user_id, &path, &reports)?;
+        Ok(report)
+    }).await;
+    Ok(json_result(result, cookie))
+}
+
+#[get("/saved_reports/{id}")]
+pub async fn view_report(req: HttpRequest, store: Data<UserStore>, id: Path<String>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    if !is_valid_id(&id) {
+        return Ok(respond(HttpResponse::BadRequest(), cookie).body("Invalid report id"));
+    }
+    let path = store.report_html_path(&user_id, &id);
+    let response = match web::block(move || fs::read_to_string(path)).await {
+        Ok(Ok(html)) => respond(HttpResponse::Ok(), cookie)
+            .content_type("text/html")
+            .insert_header(("Content-Security-Policy", REPORT_CSP))
+            .insert_header(("X-Content-Type-Options", "nosniff"))
+            .body(html),
+        _ => respond(HttpResponse::NotFound(), cookie).body("Report not found"),
+    };
+    Ok(response)
+}
+
+#[delete("/api/reports/{id}")]
+pub async fn delete_report(req: HttpRequest, store: Data<UserStore>, id: Path<String>) -> actix_web::Result<HttpResponse> {
+    let (user_id, cookie) = store.session(&req)?;
+    let id = id.into_inner();
+    let result = blocking(&store, move |store| {
+        let path = store.reports_path(&user_id);
+        let mut reports = store.read_json::<Vec<SavedReport>>(&path)?;
+        let len = reports.len();
+        reports.retain(|x| x.id != id);
+        if reports.len() == len {
+            return Err(format!("Report {} not found", id).into());
+        }
+        store.remove_file(&store.report_html_path(&user_id, &id));
+        store.write_json(&user_id, &path, &reports)
+    }).await;
+    Ok(json_result(result, cookie))
+}
+
+/// Saved watchlists, screener presets and reports of the current user
+#[get("/library")]
+pub async fn library(req: HttpRequest, store: Data<UserStore>) -> actix_web::Result<HttpResponse> {
+    let (_, cookie) = store.session(&req)?;
+    let html = fs::read_to_string("src/templates/library.html").expect("Failed to read library html file");
+    Ok(respond(HttpResponse::Ok(), cookie).content_type("text/html").body(html))
+}
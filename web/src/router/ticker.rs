@@ -3,6 +3,7 @@ use actix_web::web::Bytes;
 use serde::{Deserialize, Serialize};
 use finalytics::prelude::*;
 use tera::{Context, Tera};
+use crate::router::html::escape_html;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TickerFormData {
@@ -59,7 +60,10 @@ async fn ticker_html(form_data: Bytes) -> String {
         .risk_free_rate(data.risk_free_rate)
         .build();
 
-    let report_html = tc.report(Some(ReportType::from_str(&data.report_type))).await.unwrap().to_html();
+    let report_html = match tc.report(Some(ReportType::from_str(&data.report_type))).await {
+        Ok(report) => report.to_html(),
+        Err(e) => format!(r#"<div class="alert alert-danger">Error generating the report: {}</div>"#, escape_html(&e.to_string())),
+    };
 
     // Create a Tera instance and load your HTML template
     let tera = Tera::new("src/templates/*").expect("Failed to initialize Tera");